@@ -5,12 +5,13 @@
 
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::inline::Inline;
 
 /// Block-level content element
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum Block {
     /// A paragraph of text
     Paragraph(Paragraph),
@@ -36,21 +37,24 @@ pub enum Block {
     Quote(QuoteBlock),
     /// A thematic break / horizontal rule (`---`)
     ThematicBreak,
+    /// An author comment (`//` lines or a `////` block), not rendered
+    Comment(CommentBlock),
 }
 
 /// A paragraph block
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Paragraph {
     /// Inline content within the paragraph
     pub inlines: Vec<Inline>,
     /// Style ID from source document (e.g., OOXML style reference)
     pub style_id: Option<String>,
     /// Additional attributes (AsciiDoc roles, etc.)
+    #[serde(default, serialize_with = "crate::json::sorted")]
     pub attributes: HashMap<String, String>,
 }
 
 /// A section heading
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Heading {
     /// Heading level (1-6, where 1 is the highest)
     pub level: u8,
@@ -60,10 +64,13 @@ pub struct Heading {
     pub style_id: Option<String>,
     /// Anchor/ID for cross-references
     pub anchor: Option<String>,
+    /// Roles of the heading (`[.draft]`), space separated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
 }
 
 /// A list (ordered or unordered)
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct List {
     /// Type of list
     pub list_type: ListType,
@@ -71,10 +78,16 @@ pub struct List {
     pub items: Vec<ListItem>,
     /// Style ID from source document
     pub style_id: Option<String>,
+    /// Explicit numbering style of an ordered list (`[loweralpha]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numbering: Option<NumberingStyle>,
+    /// Number of the first item of an ordered list, when not 1 (`[start=3]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<u32>,
 }
 
 /// List type variants
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum ListType {
     /// Unordered/bullet list
     Unordered,
@@ -82,10 +95,101 @@ pub enum ListType {
     Ordered,
     /// Description/definition list
     Description,
+    /// Callout list explaining the callouts of the preceding literal block
+    Callout,
+}
+
+/// Numbering style of an ordered list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum NumberingStyle {
+    /// 1, 2, 3
+    Arabic,
+    /// a, b, c
+    LowerAlpha,
+    /// A, B, C
+    UpperAlpha,
+    /// i, ii, iii
+    LowerRoman,
+    /// I, II, III
+    UpperRoman,
+}
+
+impl NumberingStyle {
+    /// Parse an AsciiDoc list style name (e.g. `loweralpha`)
+    pub fn from_style(style: &str) -> Option<Self> {
+        match style {
+            "arabic" => Some(Self::Arabic),
+            "loweralpha" => Some(Self::LowerAlpha),
+            "upperalpha" => Some(Self::UpperAlpha),
+            "lowerroman" => Some(Self::LowerRoman),
+            "upperroman" => Some(Self::UpperRoman),
+            _ => None,
+        }
+    }
+
+    /// AsciiDoc style name
+    pub fn as_style(&self) -> &'static str {
+        match self {
+            Self::Arabic => "arabic",
+            Self::LowerAlpha => "loweralpha",
+            Self::UpperAlpha => "upperalpha",
+            Self::LowerRoman => "lowerroman",
+            Self::UpperRoman => "upperroman",
+        }
+    }
+
+    /// Format a 1-based item number in this style
+    pub fn format(&self, number: usize) -> String {
+        match self {
+            Self::Arabic => number.to_string(),
+            Self::LowerAlpha => alpha(number),
+            Self::UpperAlpha => alpha(number).to_uppercase(),
+            Self::LowerRoman => roman(number),
+            Self::UpperRoman => roman(number).to_uppercase(),
+        }
+    }
+}
+
+/// Bijective base-26 letters: a..z, aa, ab, ...
+fn alpha(mut number: usize) -> String {
+    let mut letters = Vec::new();
+    while number > 0 {
+        number -= 1;
+        letters.push((b'a' + (number % 26) as u8) as char);
+        number /= 26;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Lowercase roman numerals
+fn roman(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut result = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            result.push_str(numeral);
+            number -= value;
+        }
+    }
+    result
 }
 
 /// A single list item
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ListItem {
     /// Item content (can contain nested blocks)
     pub content: Vec<Block>,
@@ -93,10 +197,13 @@ pub struct ListItem {
     pub level: u8,
     /// For description lists: the term being defined
     pub term: Option<Vec<Inline>>,
+    /// For checklists: whether the item is checked (`None` for plain items)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked: Option<bool>,
 }
 
 /// A table
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Table {
     /// Table rows
     pub rows: Vec<TableRow>,
@@ -109,16 +216,19 @@ pub struct Table {
 }
 
 /// A table row
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TableRow {
     /// Cells in this row
     pub cells: Vec<TableCell>,
     /// Whether this is a header row
     pub is_header: bool,
+    /// Whether this is a footer row
+    #[serde(default)]
+    pub is_footer: bool,
 }
 
 /// A table cell
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TableCell {
     /// Cell content (blocks)
     pub content: Vec<Block>,
@@ -128,19 +238,73 @@ pub struct TableCell {
     pub rowspan: u32,
     /// Horizontal alignment
     pub align: Option<Alignment>,
+    /// Cell style (`a|`, `h|`, ...); `None` for the default style
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<CellStyle>,
 }
 
 /// Column specification
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ColumnSpec {
     /// Relative width (e.g., 1, 2, 3 for proportional sizing)
     pub width: Option<u32>,
     /// Default alignment for this column
     pub align: Option<Alignment>,
+    /// Default cell style for this column
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<CellStyle>,
+}
+
+/// Table cell style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum CellStyle {
+    /// `a`: content is parsed as AsciiDoc blocks
+    AsciiDoc,
+    /// `d`: regular paragraphs
+    Default,
+    /// `e`: emphasized text
+    Emphasis,
+    /// `h`: header cell
+    Header,
+    /// `l`: literal text
+    Literal,
+    /// `m`: monospaced text
+    Monospace,
+    /// `s`: strong text
+    Strong,
+}
+
+impl CellStyle {
+    /// Parse a cell style operator (e.g. `a`)
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'a' => Some(Self::AsciiDoc),
+            'd' => Some(Self::Default),
+            'e' => Some(Self::Emphasis),
+            'h' => Some(Self::Header),
+            'l' => Some(Self::Literal),
+            'm' => Some(Self::Monospace),
+            's' => Some(Self::Strong),
+            _ => None,
+        }
+    }
+
+    /// Cell style operator
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::AsciiDoc => 'a',
+            Self::Default => 'd',
+            Self::Emphasis => 'e',
+            Self::Header => 'h',
+            Self::Literal => 'l',
+            Self::Monospace => 'm',
+            Self::Strong => 's',
+        }
+    }
 }
 
 /// Text alignment
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum Alignment {
     Left,
     Center,
@@ -148,7 +312,7 @@ pub enum Alignment {
 }
 
 /// An admonition block (note, warning, tip, etc.)
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Admonition {
     /// Type of admonition
     pub admonition_type: AdmonitionType,
@@ -159,7 +323,7 @@ pub struct Admonition {
 }
 
 /// Admonition type variants
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum AdmonitionType {
     Note,
     Tip,
@@ -169,7 +333,7 @@ pub enum AdmonitionType {
 }
 
 /// A literal/code block
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct LiteralBlock {
     /// The literal content
     pub content: String,
@@ -179,10 +343,28 @@ pub struct LiteralBlock {
     pub title: Option<String>,
     /// Style ID from source document
     pub style_id: Option<String>,
+    /// Callout markers (`<1>`) stripped from the ends of source lines
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub callouts: Vec<Callout>,
+    /// Highlighted lines (1-based), from `highlight=3..5`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlight_lines: Vec<u32>,
+}
+
+/// A callout marker in a literal block
+///
+/// Callout `number` N is explained by item N of the callout list
+/// (`ListType::Callout`) that follows the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Callout {
+    /// Callout number, starting at 1
+    pub number: u32,
+    /// Line of the block content carrying the marker (1-based)
+    pub line: u32,
 }
 
 /// Break type variants
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum BreakType {
     /// Page break
     Page,
@@ -192,7 +374,7 @@ pub enum BreakType {
 
 /// An open block - a generic container with a role/style
 /// Delimited with `--` or `====` in AsciiDoc
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct OpenBlock {
     /// The role/style of the block (e.g., "slides", "example", "abstract")
     pub role: Option<String>,
@@ -201,6 +383,7 @@ pub struct OpenBlock {
     /// Nested blocks within the open block
     pub blocks: Vec<Block>,
     /// Additional attributes
+    #[serde(default, serialize_with = "crate::json::sorted")]
     pub attributes: HashMap<String, String>,
 }
 
@@ -221,7 +404,7 @@ impl OpenBlock {
 
 /// A sidebar block - typically used for speaker notes in presentations
 /// Delimited with `****` in AsciiDoc
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Sidebar {
     /// Optional title (often ".Notes" for speaker notes)
     pub title: Option<String>,
@@ -256,7 +439,7 @@ impl Sidebar {
 }
 
 /// A quote/blockquote block
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct QuoteBlock {
     /// The quoted content
     pub blocks: Vec<Block>,
@@ -264,6 +447,25 @@ pub struct QuoteBlock {
     pub attribution: Option<String>,
     /// Citation source
     pub cite: Option<String>,
+    /// Optional title for the block
+    pub title: Option<String>,
+    /// Whether the quote is a verse (`[verse]`), whose lines end in
+    /// [`Inline::Break`]
+    #[serde(default)]
+    pub verse: bool,
+}
+
+/// An author comment
+///
+/// Writers skip comments unless they have somewhere to keep them, such as
+/// Word comments in DOCX output.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CommentBlock {
+    /// Comment text, without the `//` markers (lines joined with `\n`)
+    pub text: String,
+    /// Whether the comment is a delimited `////` block
+    #[serde(default)]
+    pub delimited: bool,
 }
 
 impl Default for Heading {
@@ -273,6 +475,7 @@ impl Default for Heading {
             text: Vec::new(),
             style_id: None,
             anchor: None,
+            role: None,
         }
     }
 }
@@ -295,6 +498,7 @@ mod tests {
             text: vec![Inline::Text("Title".to_string())],
             style_id: None,
             anchor: Some("title".to_string()),
+            role: None,
         };
         assert_eq!(h1.level, 1);
         assert_eq!(h1.anchor, Some("title".to_string()));
@@ -306,10 +510,26 @@ mod tests {
             list_type: ListType::Ordered,
             items: vec![],
             style_id: None,
+            numbering: None,
+            start: None,
         };
         assert_eq!(list.list_type, ListType::Ordered);
     }
 
+    #[test]
+    fn test_numbering_style_format() {
+        assert_eq!(
+            NumberingStyle::from_style("loweralpha"),
+            Some(NumberingStyle::LowerAlpha)
+        );
+        assert_eq!(NumberingStyle::LowerAlpha.format(1), "a");
+        assert_eq!(NumberingStyle::LowerAlpha.format(27), "aa");
+        assert_eq!(NumberingStyle::UpperAlpha.format(3), "C");
+        assert_eq!(NumberingStyle::LowerRoman.format(4), "iv");
+        assert_eq!(NumberingStyle::UpperRoman.format(1994), "MCMXCIV");
+        assert_eq!(NumberingStyle::Arabic.format(12), "12");
+    }
+
     #[test]
     fn test_table_structure() {
         let table = Table {
@@ -319,8 +539,10 @@ mod tests {
                     colspan: 1,
                     rowspan: 1,
                     align: None,
+                    style: None,
                 }],
                 is_header: true,
+                is_footer: false,
             }],
             style_id: None,
             caption: None,
@@ -5,13 +5,15 @@
 
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::block::Block;
 use crate::intent::DocumentIntent;
+use crate::source::SourceMap;
 
 /// A complete document
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Document {
     /// Document metadata (title, authors, attributes)
     pub metadata: DocumentMeta,
@@ -19,10 +21,13 @@ pub struct Document {
     pub blocks: Vec<Block>,
     /// Document compilation intent
     pub intent: Option<DocumentIntent>,
+    /// Source provenance of blocks (populated by the parser)
+    #[serde(default, skip_serializing_if = "SourceMap::is_empty")]
+    pub source_map: SourceMap,
 }
 
 /// Document metadata
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct DocumentMeta {
     /// Document title
     pub title: Option<String>,
@@ -31,6 +36,7 @@ pub struct DocumentMeta {
     /// Revision/version string
     pub revision: Option<String>,
     /// Additional attributes (AsciiDoc attributes, OOXML properties)
+    #[serde(default, serialize_with = "crate::json::sorted")]
     pub attributes: HashMap<String, String>,
 }
 
@@ -41,6 +47,7 @@ impl Document {
             metadata: DocumentMeta::default(),
             blocks: Vec::new(),
             intent: None,
+            source_map: Default::default(),
         }
     }
 
@@ -53,6 +60,7 @@ impl Document {
             },
             blocks: Vec::new(),
             intent: None,
+            source_map: Default::default(),
         }
     }
 
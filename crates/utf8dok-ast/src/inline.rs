@@ -1,12 +1,13 @@
 //! Inline elements for document content
 //!
 //! This module defines inline-level elements that appear within blocks,
-//! such as text, formatting, links, and images.
+//! such as text, formatting, links, images, and footnotes.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Inline-level content element
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum Inline {
     /// Plain text content
     Text(String),
@@ -22,10 +23,12 @@ pub enum Inline {
     Break,
     /// An anchor/bookmark (for internal cross-references)
     Anchor(String),
+    /// A footnote (`footnote:[text]` or `footnote:id[text]`)
+    Footnote(Footnote),
 }
 
 /// Text formatting types
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum FormatType {
     /// Bold text
     Bold,
@@ -42,7 +45,7 @@ pub enum FormatType {
 }
 
 /// A hyperlink element
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Link {
     /// The URL target
     pub url: String,
@@ -51,7 +54,7 @@ pub struct Link {
 }
 
 /// An image element
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Image {
     /// Image source path or URL
     pub src: String,
@@ -59,6 +62,25 @@ pub struct Image {
     pub alt: Option<String>,
 }
 
+/// A footnote element
+///
+/// A footnote with an `id` can be referenced again later with an empty
+/// `content` (`footnote:id[]`), reusing the same note.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Footnote {
+    /// Identifier for reuse (`footnote:id[]`)
+    pub id: Option<String>,
+    /// Footnote text (empty for a reference to an earlier footnote)
+    pub content: Vec<Inline>,
+}
+
+impl Footnote {
+    /// Check if this footnote refers back to an earlier one by id
+    pub fn is_reference(&self) -> bool {
+        self.id.is_some() && self.content.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,4 +112,18 @@ mod tests {
         };
         assert_eq!(link.url, "https://example.com");
     }
+
+    #[test]
+    fn test_footnote_reference() {
+        let first = Footnote {
+            id: Some("disclaimer".to_string()),
+            content: vec![Inline::Text("Not legal advice.".to_string())],
+        };
+        let again = Footnote {
+            id: Some("disclaimer".to_string()),
+            content: vec![],
+        };
+        assert!(!first.is_reference());
+        assert!(again.is_reference());
+    }
 }
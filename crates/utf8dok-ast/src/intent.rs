@@ -4,6 +4,7 @@
 //! intent and validation invariants. These are used by the compiler to
 //! understand what guarantees the document should maintain.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Document compilation intent
@@ -23,7 +24,7 @@ use serde::{Deserialize, Serialize};
 ///     .with_invariant(Invariant::new("heading_hierarchy", "Headings must be properly nested"))
 ///     .with_preserve_source(true);
 /// ```
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Default)]
 pub struct DocumentIntent {
     /// Target output format (e.g., "docx", "pdf", "html")
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -42,7 +43,11 @@ pub struct DocumentIntent {
     pub preserve_source: bool,
 
     /// Custom options for specific renderers
-    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "std::collections::HashMap::is_empty",
+        serialize_with = "crate::json::sorted"
+    )]
     pub options: std::collections::HashMap<String, String>,
 }
 
@@ -51,7 +56,7 @@ pub struct DocumentIntent {
 /// Invariants represent rules or constraints that the document must
 /// satisfy. The compiler uses these to validate the document structure
 /// and content.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Invariant {
     /// Unique identifier for this invariant type
     pub id: String,
@@ -96,7 +101,7 @@ impl Invariant {
 ///
 /// Controls how strictly the compiler validates document structure
 /// and content against invariants.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ValidationLevel {
     /// No validation - allow any document structure
@@ -0,0 +1,193 @@
+//! Versioned JSON form of documents
+//!
+//! External tools exchange documents as JSON: the serde representation of
+//! the AST types, wrapped in an envelope naming the schema version.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "document": {
+//!     "metadata": { "title": "Guide", "authors": [], "revision": null, "attributes": {} },
+//!     "blocks": [
+//!       { "Paragraph": { "inlines": [{ "Text": "Hello" }], "style_id": null, "attributes": {} } }
+//!     ],
+//!     "intent": null
+//!   }
+//! }
+//! ```
+//!
+//! Enums are externally tagged: a variant with data is an object with one
+//! key, the variant name (`{"Text": "Hello"}`), and a variant without data
+//! is its name as a string (`"ThematicBreak"`). The JSON Schema describing
+//! this form is generated from the types by [`schema`] and checked in as
+//! `schema/document.schema.json`.
+//!
+//! [`SCHEMA_VERSION`] changes when a change to the types would make
+//! existing JSON read differently or fail to read. Adding optional fields
+//! keeps the version.
+
+use std::collections::{BTreeMap, HashMap};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::document::Document;
+
+/// Version of the JSON form written by [`to_json`]
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Errors reading the JSON form of a document
+#[derive(Debug, thiserror::Error)]
+pub enum JsonError {
+    /// The input is not valid JSON, or does not match the schema
+    #[error("Invalid document JSON: {0}")]
+    Invalid(#[from] serde_json::Error),
+
+    /// The input has no `version` field
+    #[error("Document JSON has no \"version\" field")]
+    MissingVersion,
+
+    /// The input was written for a newer schema
+    #[error("Unsupported document JSON version {found} (supported: {SCHEMA_VERSION})")]
+    UnsupportedVersion {
+        /// Version found in the input
+        found: u64,
+    },
+}
+
+/// A document with the version of its JSON form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(title = "utf8dok document")]
+pub struct VersionedDocument {
+    /// Version of the JSON form (see `SCHEMA_VERSION`)
+    pub version: u32,
+    /// The document
+    pub document: Document,
+}
+
+/// Write a document as versioned, pretty-printed JSON
+///
+/// Maps are written with sorted keys, so the same document always gives
+/// the same JSON.
+pub fn to_json(doc: &Document) -> String {
+    let versioned = VersionedDocument {
+        version: SCHEMA_VERSION,
+        document: doc.clone(),
+    };
+    // The AST has no types serde_json cannot write (no non-string map keys)
+    serde_json::to_string_pretty(&versioned).expect("document serializes to JSON")
+}
+
+/// Read a document from its versioned JSON form
+///
+/// The version is checked before the document, so JSON from a newer
+/// release fails with [`JsonError::UnsupportedVersion`] rather than a
+/// confusing field error.
+pub fn from_json(json: &str) -> Result<Document, JsonError> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let version = value
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or(JsonError::MissingVersion)?;
+    if version == 0 || version > u64::from(SCHEMA_VERSION) {
+        return Err(JsonError::UnsupportedVersion { found: version });
+    }
+    let versioned: VersionedDocument = serde_json::from_value(value)?;
+    Ok(versioned.document)
+}
+
+/// The JSON Schema of the versioned JSON form, pretty-printed
+pub fn schema() -> String {
+    let schema = schemars::schema_for!(VersionedDocument);
+    let mut json = serde_json::to_string_pretty(&schema).expect("schema serializes to JSON");
+    json.push('\n');
+    json
+}
+
+/// Serialize a map with sorted keys
+pub(crate) fn sorted<S: Serializer>(
+    map: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{Block, Paragraph};
+    use crate::inline::{FormatType, Inline};
+    use crate::intent::DocumentIntent;
+
+    fn sample() -> Document {
+        let mut doc = Document::with_title("Guide");
+        doc.metadata.set_attribute("lang", "en");
+        doc.metadata.set_attribute("author", "Jo");
+        doc.push(Block::Paragraph(Paragraph {
+            inlines: vec![
+                Inline::Text("Hello ".to_string()),
+                Inline::Format(
+                    FormatType::Bold,
+                    Box::new(Inline::Text("world".to_string())),
+                ),
+            ],
+            style_id: None,
+            attributes: HashMap::new(),
+        }));
+        doc.push(Block::ThematicBreak);
+        doc.with_intent(DocumentIntent::new().with_target_format("docx"))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let doc = sample();
+        let json = to_json(&doc);
+        assert!(json.starts_with("{\n  \"version\": 1,\n  \"document\": {"));
+        assert!(json.contains("\"ThematicBreak\""));
+        assert!(json.contains("\"author\": \"Jo\",\n        \"lang\": \"en\""));
+        assert_eq!(from_json(&json).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_version_is_checked() {
+        assert!(matches!(
+            from_json(r#"{"document": {}}"#),
+            Err(JsonError::MissingVersion)
+        ));
+        assert!(matches!(
+            from_json(r#"{"version": 2, "document": {}}"#),
+            Err(JsonError::UnsupportedVersion { found: 2 })
+        ));
+        assert!(matches!(from_json("not json"), Err(JsonError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_optional_maps_may_be_omitted() {
+        let json = r#"{"version": 1, "document": {
+            "metadata": {"title": null, "authors": [], "revision": null},
+            "blocks": [{"Paragraph": {"inlines": [{"Text": "Hi"}], "style_id": null}}],
+            "intent": null
+        }}"#;
+        let doc = from_json(json).unwrap();
+        assert_eq!(doc.blocks.len(), 1);
+    }
+
+    /// The checked-in schema matches the types
+    ///
+    /// Run with `UTF8DOK_UPDATE_SCHEMA=1` to regenerate it after changing
+    /// the AST.
+    #[test]
+    fn test_schema_file_is_current() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/document.schema.json");
+        let generated = schema();
+        if std::env::var_os("UTF8DOK_UPDATE_SCHEMA").is_some() {
+            std::fs::write(path, &generated).unwrap();
+        }
+        let checked_in = std::fs::read_to_string(path).unwrap_or_default();
+        assert!(
+            checked_in == generated,
+            "schema/document.schema.json is out of date; \
+             run `UTF8DOK_UPDATE_SCHEMA=1 cargo test -p utf8dok-ast` to regenerate it"
+        );
+    }
+}
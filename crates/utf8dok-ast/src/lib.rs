@@ -9,6 +9,10 @@
 //! - [`document`] - Document root and metadata
 //! - [`block`] - Block-level elements (paragraphs, headings, lists, tables)
 //! - [`inline`] - Inline elements (text, formatting, links, images)
+//! - [`json`] - Versioned JSON form for external tools, with its JSON Schema
+//! - [`node`] - Uniform traversal of blocks, list items, cells and inlines
+//! - [`source`] - Source provenance (which file a block came from, and the
+//!   source span of every parsed node)
 //!
 //! # Example
 //!
@@ -28,15 +32,22 @@ pub mod block;
 pub mod document;
 pub mod inline;
 pub mod intent;
+pub mod json;
+pub mod node;
+pub mod source;
 
 // Re-export key types for convenience
 pub use block::{
-    Admonition, AdmonitionType, Alignment, Block, BreakType, ColumnSpec, Heading, List, ListItem,
-    ListType, LiteralBlock, OpenBlock, Paragraph, QuoteBlock, Sidebar, Table, TableCell, TableRow,
+    Admonition, AdmonitionType, Alignment, Block, BreakType, Callout, CellStyle, ColumnSpec,
+    CommentBlock, Heading, List, ListItem, ListType, LiteralBlock, NumberingStyle, OpenBlock,
+    Paragraph, QuoteBlock, Sidebar, Table, TableCell, TableRow,
 };
 pub use document::{Document, DocumentMeta};
-pub use inline::{FormatType, Image, Inline, Link};
+pub use inline::{Footnote, FormatType, Image, Inline, Link};
 pub use intent::{DocumentIntent, Invariant, ValidationLevel};
+pub use json::{from_json, to_json, JsonError, VersionedDocument, SCHEMA_VERSION};
+pub use node::Node;
+pub use source::{NodePath, SourceLocation, SourceMap, SourceSpan};
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
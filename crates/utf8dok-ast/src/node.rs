@@ -0,0 +1,142 @@
+//! Uniform access to the nodes of a document tree
+//!
+//! [`Node`] borrows any block, list item, table row, table cell or inline,
+//! and lists its children in document order. The order defines the steps of
+//! a [`NodePath`], which is how the parser keys source spans.
+
+use crate::block::{Block, ListItem, TableCell, TableRow};
+use crate::document::Document;
+use crate::inline::Inline;
+use crate::source::NodePath;
+
+/// A borrowed node of the document tree
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Node<'a> {
+    /// A block
+    Block(&'a Block),
+    /// An item of a list
+    ListItem(&'a ListItem),
+    /// A row of a table
+    TableRow(&'a TableRow),
+    /// A cell of a table row
+    TableCell(&'a TableCell),
+    /// An inline element
+    Inline(&'a Inline),
+}
+
+impl<'a> Node<'a> {
+    /// Children of this node, in document order
+    ///
+    /// Titles and captions are attributes of their block and are not
+    /// listed. A description list item lists its term inlines before its
+    /// content blocks.
+    pub fn children(&self) -> Vec<Node<'a>> {
+        match *self {
+            Node::Block(block) => match block {
+                Block::Paragraph(p) => inlines(&p.inlines),
+                Block::Heading(h) => inlines(&h.text),
+                Block::List(list) => list.items.iter().map(Node::ListItem).collect(),
+                Block::Table(table) => table.rows.iter().map(Node::TableRow).collect(),
+                Block::Admonition(a) => blocks(&a.content),
+                Block::Open(open) => blocks(&open.blocks),
+                Block::Sidebar(sidebar) => blocks(&sidebar.blocks),
+                Block::Quote(quote) => blocks(&quote.blocks),
+                Block::Literal(_) | Block::Break(_) | Block::ThematicBreak | Block::Comment(_) => {
+                    Vec::new()
+                }
+            },
+            Node::ListItem(item) => {
+                let mut children = item.term.as_deref().map(inlines).unwrap_or_default();
+                children.extend(blocks(&item.content));
+                children
+            }
+            Node::TableRow(row) => row.cells.iter().map(Node::TableCell).collect(),
+            Node::TableCell(cell) => blocks(&cell.content),
+            Node::Inline(inline) => match inline {
+                Inline::Format(_, inner) => vec![Node::Inline(inner)],
+                Inline::Span(children) => inlines(children),
+                Inline::Link(link) => inlines(&link.text),
+                Inline::Footnote(footnote) => inlines(&footnote.content),
+                Inline::Text(_) | Inline::Image(_) | Inline::Break | Inline::Anchor(_) => {
+                    Vec::new()
+                }
+            },
+        }
+    }
+
+    /// The child at `index`
+    pub fn child(&self, index: usize) -> Option<Node<'a>> {
+        self.children().into_iter().nth(index)
+    }
+}
+
+impl Document {
+    /// Look up the node at `path`
+    ///
+    /// Returns `None` for the empty path and for paths that leave the tree.
+    pub fn node(&self, path: &NodePath) -> Option<Node<'_>> {
+        let (first, rest) = path.steps().split_first()?;
+        let mut node = Node::Block(self.blocks.get(*first)?);
+        for &index in rest {
+            node = node.child(index)?;
+        }
+        Some(node)
+    }
+}
+
+fn blocks(blocks: &[Block]) -> Vec<Node<'_>> {
+    blocks.iter().map(Node::Block).collect()
+}
+
+fn inlines(inlines: &[Inline]) -> Vec<Node<'_>> {
+    inlines.iter().map(Node::Inline).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{List, ListType, Paragraph};
+    use crate::inline::FormatType;
+
+    fn paragraph(inlines: Vec<Inline>) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_node_lookup() {
+        let mut doc = Document::new();
+        doc.push(paragraph(vec![
+            Inline::Text("a ".to_string()),
+            Inline::Format(FormatType::Bold, Box::new(Inline::Text("b".to_string()))),
+        ]));
+        doc.push(Block::List(List {
+            list_type: ListType::Description,
+            items: vec![ListItem {
+                content: vec![paragraph(vec![Inline::Text("def".to_string())])],
+                level: 0,
+                term: Some(vec![Inline::Text("term".to_string())]),
+                checked: None,
+            }],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+
+        let bold = doc.node(&NodePath::from(vec![0, 1, 0]));
+        assert_eq!(bold, Some(Node::Inline(&Inline::Text("b".to_string()))));
+
+        // Term inlines come before content blocks
+        let term = doc.node(&NodePath::from(vec![1, 0, 0]));
+        assert_eq!(term, Some(Node::Inline(&Inline::Text("term".to_string()))));
+        assert!(matches!(
+            doc.node(&NodePath::from(vec![1, 0, 1])),
+            Some(Node::Block(Block::Paragraph(_)))
+        ));
+
+        assert!(doc.node(&NodePath::default()).is_none());
+        assert!(doc.node(&NodePath::from(vec![0, 5])).is_none());
+    }
+}
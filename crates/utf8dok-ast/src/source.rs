@@ -0,0 +1,321 @@
+//! Source provenance for document nodes
+//!
+//! This module records where blocks came from, so that diagnostics raised
+//! on the AST can point at the right file and line after includes have
+//! been inlined, and the exact source range of every parsed node.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A location in a source file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SourceLocation {
+    /// File path as written in the include directive, relative to the
+    /// including document (`None` for the root document)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Line number within that file (1-indexed)
+    pub line: usize,
+}
+
+/// A byte range in a source file, with the line and column where it starts
+///
+/// Offsets are relative to the file the node came from: the root document,
+/// or the included file recorded for its top-level block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SourceSpan {
+    /// Start offset (inclusive)
+    pub start: usize,
+    /// End offset (exclusive)
+    pub end: usize,
+    /// Line of the start offset (1-indexed)
+    pub line: usize,
+    /// Byte column of the start offset (1-indexed)
+    pub column: usize,
+}
+
+/// Position of a node in the document tree
+///
+/// Each step indexes the children of the previous node, as listed by
+/// [`Node::children`](crate::Node::children), starting from the top-level
+/// blocks. `[2, 0, 1]` is the second inline of the first child of the third
+/// block. Serialized as a dotted string (`"2.0.1"`).
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(into = "String", try_from = "String")]
+pub struct NodePath(pub Vec<usize>);
+
+/// Source provenance for the nodes of a document
+///
+/// Blocks without a location come from the root document; nodes without a
+/// span were not parsed from source (e.g. tables loaded from data files).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SourceMap {
+    /// Locations of top-level blocks, keyed by block index
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub blocks: BTreeMap<usize, SourceLocation>,
+    /// Source spans of blocks and inlines, keyed by node path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub spans: BTreeMap<NodePath, SourceSpan>,
+}
+
+impl SourceLocation {
+    /// Create a location in the root document
+    pub fn new(line: usize) -> Self {
+        Self { file: None, line }
+    }
+
+    /// Create a location in an included file
+    pub fn in_file(file: impl Into<String>, line: usize) -> Self {
+        Self {
+            file: Some(file.into()),
+            line,
+        }
+    }
+}
+
+impl SourceSpan {
+    /// Create a span starting at `line` and `column`
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+}
+
+impl NodePath {
+    /// The path of a top-level block
+    pub fn block(index: usize) -> Self {
+        Self(vec![index])
+    }
+
+    /// The path of this node's child at `index`
+    pub fn child(&self, index: usize) -> Self {
+        let mut steps = self.0.clone();
+        steps.push(index);
+        Self(steps)
+    }
+
+    /// The path of this node's parent (`None` at the root)
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.0.split_last()?;
+        Some(Self(parent.to_vec()))
+    }
+
+    /// Steps from the root
+    pub fn steps(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl From<Vec<usize>> for NodePath {
+    fn from(steps: Vec<usize>) -> Self {
+        Self(steps)
+    }
+}
+
+impl fmt::Display for NodePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps: Vec<String> = self.0.iter().map(usize::to_string).collect();
+        write!(f, "{}", steps.join("."))
+    }
+}
+
+impl From<NodePath> for String {
+    fn from(path: NodePath) -> Self {
+        path.to_string()
+    }
+}
+
+impl TryFrom<String> for NodePath {
+    type Error = std::num::ParseIntError;
+
+    fn try_from(path: String) -> Result<Self, Self::Error> {
+        if path.is_empty() {
+            return Ok(Self::default());
+        }
+        path.split('.')
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl SourceMap {
+    /// Create an empty source map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the location of a top-level block
+    pub fn insert_block(&mut self, index: usize, location: SourceLocation) {
+        self.blocks.insert(index, location);
+    }
+
+    /// Get the location of a top-level block
+    pub fn block(&self, index: usize) -> Option<&SourceLocation> {
+        self.blocks.get(&index)
+    }
+
+    /// Get the file a top-level block came from (`None` for the root document)
+    pub fn block_file(&self, index: usize) -> Option<&str> {
+        self.block(index).and_then(|loc| loc.file.as_deref())
+    }
+
+    /// Record the source span of a node
+    pub fn insert_span(&mut self, path: NodePath, span: SourceSpan) {
+        self.spans.insert(path, span);
+    }
+
+    /// Get the source span of a node
+    pub fn span(&self, path: &NodePath) -> Option<&SourceSpan> {
+        self.spans.get(path)
+    }
+
+    /// Get the source span of a top-level block
+    pub fn block_span(&self, index: usize) -> Option<&SourceSpan> {
+        self.span(&NodePath::block(index))
+    }
+
+    /// Check if no provenance has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty() && self.spans.is_empty()
+    }
+
+    /// Forget the node at `path` and the nodes inside it, after it was
+    /// removed from the tree
+    ///
+    /// Its later siblings move back by one.
+    pub fn remove_node(&mut self, path: &NodePath) {
+        self.renumber(path, true);
+    }
+
+    /// Make room for a node inserted into the tree at `path`
+    ///
+    /// The node that was at `path` and its later siblings move forward by
+    /// one.
+    pub fn insert_node(&mut self, path: &NodePath) {
+        self.renumber(path, false);
+    }
+
+    /// Renumber the siblings at and after `path`, dropping the node at
+    /// `path` when it was removed
+    fn renumber(&mut self, path: &NodePath, removed: bool) {
+        let Some((&index, parent)) = path.steps().split_last() else {
+            return;
+        };
+        let depth = parent.len();
+        let renumbered = |steps: &[usize]| -> Option<Vec<usize>> {
+            let mut steps = steps.to_vec();
+            if steps.len() <= depth || steps[..depth] != *parent {
+                return Some(steps);
+            }
+            match steps[depth] {
+                step if removed && step == index => return None,
+                step if removed && step > index => steps[depth] -= 1,
+                step if !removed && step >= index => steps[depth] += 1,
+                _ => {}
+            }
+            Some(steps)
+        };
+
+        self.spans = std::mem::take(&mut self.spans)
+            .into_iter()
+            .filter_map(|(path, span)| Some((NodePath(renumbered(path.steps())?), span)))
+            .collect();
+        if depth == 0 {
+            self.blocks = std::mem::take(&mut self.blocks)
+                .into_iter()
+                .filter_map(|(block, location)| Some((renumbered(&[block])?[0], location)))
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_map_lookup() {
+        let mut map = SourceMap::new();
+        assert!(map.is_empty());
+
+        map.insert_block(2, SourceLocation::in_file("chapter.adoc", 7));
+        assert_eq!(map.block_file(2), Some("chapter.adoc"));
+        assert_eq!(map.block(2).map(|loc| loc.line), Some(7));
+        assert_eq!(map.block_file(0), None);
+    }
+
+    #[test]
+    fn test_source_map_serde_roundtrip() {
+        let mut map = SourceMap::new();
+        map.insert_block(0, SourceLocation::in_file("a.adoc", 1));
+        map.insert_block(3, SourceLocation::new(12));
+
+        map.insert_span(
+            NodePath::from(vec![3, 0, 1]),
+            SourceSpan::new(40, 52, 12, 5),
+        );
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert!(json.contains("\"3.0.1\""));
+        let restored: SourceMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, map);
+    }
+
+    #[test]
+    fn test_remove_and_insert_nodes() {
+        let span = |start| SourceSpan::new(start, start + 1, 1, start + 1);
+        let mut map = SourceMap::new();
+        for (steps, start) in [
+            (vec![0], 0),
+            (vec![1], 10),
+            (vec![1, 0], 11),
+            (vec![1, 1], 12),
+            (vec![1, 2], 13),
+            (vec![2], 20),
+        ] {
+            map.insert_span(NodePath::from(steps), span(start));
+        }
+        map.insert_block(1, SourceLocation::in_file("a.adoc", 1));
+        map.insert_block(2, SourceLocation::in_file("b.adoc", 1));
+
+        // Removing 1.1 renumbers its later sibling only
+        map.remove_node(&NodePath::from(vec![1, 1]));
+        assert_eq!(map.span(&NodePath::from(vec![1, 1])), Some(&span(13)));
+        assert_eq!(map.span(&NodePath::from(vec![1, 2])), None);
+        assert_eq!(map.block_span(2), Some(&span(20)));
+
+        // Removing block 1 drops its children and moves block 2 back
+        map.remove_node(&NodePath::block(1));
+        assert_eq!(map.block_span(1), Some(&span(20)));
+        assert_eq!(map.block_file(1), Some("b.adoc"));
+        assert_eq!(map.spans.len(), 2);
+
+        // Inserting before block 1 moves it forward again
+        map.insert_node(&NodePath::block(1));
+        assert_eq!(map.block_span(1), None);
+        assert_eq!(map.block_span(2), Some(&span(20)));
+        assert_eq!(map.block_file(2), Some("b.adoc"));
+        assert_eq!(map.block_span(0), Some(&span(0)));
+    }
+
+    #[test]
+    fn test_node_path() {
+        let path = NodePath::block(2).child(0).child(1);
+        assert_eq!(path.steps(), &[2, 0, 1]);
+        assert_eq!(path.to_string(), "2.0.1");
+        assert_eq!(path.parent(), Some(NodePath::from(vec![2, 0])));
+        assert_eq!(NodePath::try_from("2.0.1".to_string()), Ok(path));
+        assert_eq!(NodePath::try_from(String::new()), Ok(NodePath::default()));
+        assert!(NodePath::default().parent().is_none());
+    }
+}
@@ -14,17 +14,24 @@ use utf8dok_core::dual_nature::{
     parse_dual_nature, transform_for_format, validate_dual_nature, ContentSelector,
     OutputFormat as DualNatureFormat,
 };
-use utf8dok_core::{parse, parse_with_config, IncludeDirective, ParserConfig};
+use utf8dok_core::{
+    generate_markdown_with_config, generate_with_config, parse_markdown, parse_with_config,
+    parse_with_diagnostics, GeneratorConfig, IncludeDirective, ParserConfig, Pipeline,
+    TransformError, TransformSpec,
+};
+use utf8dok_html::HtmlWriter;
 use utf8dok_lsp::compliance::dashboard::ComplianceDashboard;
 use utf8dok_lsp::compliance::ComplianceEngine;
 use utf8dok_lsp::config::Settings;
 use utf8dok_lsp::workspace::graph::WorkspaceGraph;
+use utf8dok_odf::{OdfPackage, OdtExtractor, OdtTemplate, OdtWriter};
 use utf8dok_ooxml::{
-    AsciiDocExtractor, DocxWriter, OoxmlArchive, SourceOrigin, StyleSheet, Template,
+    AsciiDocExtractor, DocxWriter, OoxmlArchive, SourceOrigin, StyleContract, StyleSheet, Template,
 };
-use utf8dok_plugins::PluginEngine;
+use utf8dok_plugins::{PluginEngine, ScriptTransform};
 use utf8dok_pptx::{PotxTemplate, PptxWriter, SlideExtractor};
 use utf8dok_validate::ValidationEngine;
+use utf8dok_xml::{DocBookWriter, JatsWriter};
 
 /// Output format for diagnostics
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -70,6 +77,14 @@ pub enum RenderFormat {
     Pptx,
     /// Portable Document Format (.pdf) via Typst
     Pdf,
+    /// Standalone HTML5 page (.html)
+    Html,
+    /// DocBook 5 article (.xml)
+    Docbook,
+    /// JATS article (.xml)
+    Jats,
+    /// OpenDocument Text (.odt)
+    Odt,
 }
 
 #[derive(Parser)]
@@ -102,34 +117,38 @@ enum Commands {
         template: InitTemplate,
     },
 
-    /// Extract AsciiDoc and template from a DOCX file
+    /// Extract AsciiDoc and template from a DOCX or ODT file
     Extract {
-        /// Input DOCX file
+        /// Input DOCX or ODT file
         input: PathBuf,
 
         /// Output directory
         #[arg(short, long, default_value = "output")]
         output: PathBuf,
 
-        /// Force parsing document.xml even if embedded source exists
+        /// Force parsing the document body even if embedded source exists
         #[arg(long)]
         force_parse: bool,
+
+        /// Also write the document as Markdown (document.md)
+        #[arg(long)]
+        markdown: bool,
     },
 
-    /// Render AsciiDoc to DOCX or PPTX
+    /// Render AsciiDoc or Markdown to DOCX, ODT, PPTX, PDF, HTML, DocBook or JATS
     Render {
-        /// Input AsciiDoc file
+        /// Input AsciiDoc or Markdown (.md) file
         input: PathBuf,
 
-        /// Output file (default: input with .docx or .pptx extension)
+        /// Output file (default: input with the extension of the format)
         #[arg(short, long)]
         output: Option<PathBuf>,
 
-        /// Output format: docx or pptx
+        /// Output format: docx, odt, pptx, pdf, html, docbook or jats
         #[arg(short, long, value_enum, default_value = "docx")]
         format: RenderFormat,
 
-        /// Template file (DOTX for DOCX, POTX for PPTX)
+        /// Template file (DOTX for DOCX, OTT for ODT, POTX for PPTX, style contract TOML for HTML and XML)
         #[arg(short, long)]
         template: Option<PathBuf>,
 
@@ -140,6 +159,32 @@ enum Commands {
         /// Enable data includes (Excel/CSV/TSV) with base directory for resolving paths
         #[arg(long)]
         data_dir: Option<PathBuf>,
+
+        /// Write one HTML page per top-level section into the output directory (HTML only)
+        #[arg(long)]
+        chunked: bool,
+
+        /// Read the input as AST JSON (from `utf8dok ast dump`)
+        #[arg(long)]
+        from_json: bool,
+
+        /// Turn `//` comments into Word comments on the following paragraph (DOCX only)
+        #[arg(long)]
+        word_comments: bool,
+
+        /// Earlier version of the input to show the changes from as tracked changes (DOCX only)
+        #[arg(long, value_name = "ORIGINAL")]
+        redline_against: Option<PathBuf>,
+
+        /// Style contract TOML for the template: cover, headers and footers (DOCX only)
+        #[arg(long, value_name = "TOML")]
+        style_contract: Option<PathBuf>,
+    },
+
+    /// Work with the document AST as JSON
+    Ast {
+        #[command(subcommand)]
+        command: AstCommand,
     },
 
     /// Check an AsciiDoc file for issues (validation)
@@ -156,6 +201,21 @@ enum Commands {
         plugin: Vec<PathBuf>,
     },
 
+    /// Format AsciiDoc files into the canonical layout
+    Fmt {
+        /// AsciiDoc files, or directories to format every AsciiDoc file in
+        #[arg(default_value = ".")]
+        inputs: Vec<PathBuf>,
+
+        /// Only check: list files that need formatting and fail if any do (CI/CD)
+        #[arg(long)]
+        check: bool,
+
+        /// Configuration file path
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+
     /// Audit a documentation workspace for compliance (CI/CD)
     Audit {
         /// Input directory containing AsciiDoc files
@@ -223,6 +283,34 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum AstCommand {
+    /// Write the AST of an AsciiDoc or Markdown file as versioned JSON
+    Dump {
+        /// Input AsciiDoc or Markdown (.md) file
+        input: PathBuf,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Backend to set for conditionals (e.g. docx), as when rendering
+        #[arg(short, long)]
+        backend: Option<String>,
+
+        /// Enable data includes (Excel/CSV/TSV) with base directory for resolving paths
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+    },
+
+    /// Write the JSON Schema of the AST JSON
+    Schema {
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
 /// Run the CLI application
 ///
 /// This is the main entry point for the command-line interface.
@@ -238,8 +326,9 @@ pub fn run_cli() -> Result<()> {
             input,
             output,
             force_parse,
+            markdown,
         } => {
-            extract_command(&input, &output, force_parse)?;
+            extract_command(&input, &output, force_parse, markdown)?;
         }
         Commands::Render {
             input,
@@ -248,6 +337,11 @@ pub fn run_cli() -> Result<()> {
             template,
             cover,
             data_dir,
+            chunked,
+            from_json,
+            word_comments,
+            redline_against,
+            style_contract,
         } => {
             render_command(
                 &input,
@@ -256,8 +350,31 @@ pub fn run_cli() -> Result<()> {
                 template.as_deref(),
                 cover.as_deref(),
                 data_dir.as_deref(),
+                chunked,
+                from_json,
+                word_comments,
+                redline_against.as_deref(),
+                style_contract.as_deref(),
             )?;
         }
+        Commands::Ast { command } => match command {
+            AstCommand::Dump {
+                input,
+                output,
+                backend,
+                data_dir,
+            } => {
+                ast_dump_command(
+                    &input,
+                    output.as_deref(),
+                    backend.as_deref(),
+                    data_dir.as_deref(),
+                )?;
+            }
+            AstCommand::Schema { output } => {
+                ast_schema_command(output.as_deref())?;
+            }
+        },
         Commands::Check {
             input,
             format,
@@ -265,6 +382,13 @@ pub fn run_cli() -> Result<()> {
         } => {
             check_command(&input, format, &plugin)?;
         }
+        Commands::Fmt {
+            inputs,
+            check,
+            config,
+        } => {
+            fmt_command(&inputs, check, config.as_deref())?;
+        }
         Commands::Audit {
             input,
             format,
@@ -565,7 +689,12 @@ fn is_leap_year(year: u64) -> bool {
 }
 
 /// Execute the extract command
-pub fn extract_command(input: &PathBuf, output_dir: &PathBuf, force_parse: bool) -> Result<()> {
+pub fn extract_command(
+    input: &PathBuf,
+    output_dir: &PathBuf,
+    force_parse: bool,
+    markdown: bool,
+) -> Result<()> {
     println!("utf8dok v{}", utf8dok_core::VERSION);
     println!("Extracting: {}", input.display());
 
@@ -574,6 +703,10 @@ pub fn extract_command(input: &PathBuf, output_dir: &PathBuf, force_parse: bool)
         anyhow::bail!("Input file not found: {}", input.display());
     }
 
+    if is_opendocument(input) {
+        return extract_odt(input, output_dir, force_parse, markdown);
+    }
+
     // Open the DOCX archive
     let archive = OoxmlArchive::open(input)
         .with_context(|| format!("Failed to open DOCX file: {}", input.display()))?;
@@ -602,19 +735,16 @@ pub fn extract_command(input: &PathBuf, output_dir: &PathBuf, force_parse: bool)
         .context("Failed to read styles.xml from archive")?;
     let styles = StyleSheet::parse(styles_xml).context("Failed to parse styles")?;
 
-    // Create output directory
-    fs::create_dir_all(output_dir).with_context(|| {
-        format!(
-            "Failed to create output directory: {}",
-            output_dir.display()
-        )
-    })?;
+    write_extracted_source(output_dir, asciidoc, markdown)?;
 
-    // Write AsciiDoc file
-    let adoc_path = output_dir.join("document.adoc");
-    fs::write(&adoc_path, asciidoc)
-        .with_context(|| format!("Failed to write AsciiDoc file: {}", adoc_path.display()))?;
-    println!("  Created: {}", adoc_path.display());
+    // Tracked changes are accepted in the AsciiDoc and reported separately
+    if let Some(report) = extracted.change_report() {
+        let changes_path = output_dir.join("changes.adoc");
+        fs::write(&changes_path, report).with_context(|| {
+            format!("Failed to write change report: {}", changes_path.display())
+        })?;
+        println!("  Created: {}", changes_path.display());
+    }
 
     // Copy input as template (simple copy for now)
     let template_path = output_dir.join("template.dotx");
@@ -670,6 +800,142 @@ pub fn extract_command(input: &PathBuf, output_dir: &PathBuf, force_parse: bool)
     Ok(())
 }
 
+/// Whether `input` is an OpenDocument text file (`.odt` or `.ott`)
+fn is_opendocument(input: &std::path::Path) -> bool {
+    input
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("odt") || ext.eq_ignore_ascii_case("ott"))
+}
+
+/// Create the output directory and write document.adoc, and document.md
+/// when `markdown` is set
+fn write_extracted_source(output_dir: &PathBuf, asciidoc: &str, markdown: bool) -> Result<()> {
+    // Create output directory
+    fs::create_dir_all(output_dir).with_context(|| {
+        format!(
+            "Failed to create output directory: {}",
+            output_dir.display()
+        )
+    })?;
+
+    // Write AsciiDoc file
+    let adoc_path = output_dir.join("document.adoc");
+    fs::write(&adoc_path, asciidoc)
+        .with_context(|| format!("Failed to write AsciiDoc file: {}", adoc_path.display()))?;
+    println!("  Created: {}", adoc_path.display());
+
+    // Write Markdown file, generated from the parsed AsciiDoc
+    if markdown {
+        let doc = utf8dok_core::parse(asciidoc).context("Failed to parse extracted AsciiDoc")?;
+        let config = GeneratorConfig {
+            include_header: true,
+            generate_anchors: true,
+        };
+        let md_path = output_dir.join("document.md");
+        fs::write(&md_path, generate_markdown_with_config(&doc, config))
+            .with_context(|| format!("Failed to write Markdown file: {}", md_path.display()))?;
+        println!("  Created: {}", md_path.display());
+    }
+
+    Ok(())
+}
+
+/// Extract AsciiDoc, template and pictures from an ODT file
+fn extract_odt(
+    input: &PathBuf,
+    output_dir: &PathBuf,
+    force_parse: bool,
+    markdown: bool,
+) -> Result<()> {
+    // Open the ODT package
+    let package = OdfPackage::open(input)
+        .with_context(|| format!("Failed to open ODT file: {}", input.display()))?;
+
+    // Use extractor with embedded source priority
+    let extractor = OdtExtractor::new().with_force_parse(force_parse);
+    let extracted = extractor
+        .extract_package(&package)
+        .with_context(|| format!("Failed to extract document: {}", input.display()))?;
+
+    // Report source origin
+    match extracted.source_origin {
+        SourceOrigin::Embedded => {
+            println!("  Source: embedded utf8dok/source.adoc (round-trip document)");
+        }
+        SourceOrigin::Parsed => {
+            println!("  Source: parsed from content.xml");
+        }
+    }
+
+    let asciidoc = &extracted.asciidoc;
+    write_extracted_source(output_dir, asciidoc, markdown)?;
+
+    // Copy input as template
+    let template_path = output_dir.join("template.ott");
+    fs::copy(input, &template_path)
+        .with_context(|| format!("Failed to copy template: {}", template_path.display()))?;
+    println!("  Created: {}", template_path.display());
+
+    // Extract pictures to media/, where the extracted image paths point
+    let pictures: Vec<String> = package
+        .file_list()
+        .filter(|f| f.starts_with("Pictures/"))
+        .map(|s| s.to_string())
+        .collect();
+    if !pictures.is_empty() {
+        let media_dir = output_dir.join("media");
+        fs::create_dir_all(&media_dir).with_context(|| {
+            format!("Failed to create media directory: {}", media_dir.display())
+        })?;
+
+        let mut copied = 0;
+        for picture in &pictures {
+            if let Some(data) = package.get(picture) {
+                let filename = picture.strip_prefix("Pictures/").unwrap_or(picture);
+                if fs::write(media_dir.join(filename), data).is_ok() {
+                    copied += 1;
+                }
+            }
+        }
+        if copied > 0 {
+            println!(
+                "  Copied: {} media files to {}",
+                copied,
+                media_dir.display()
+            );
+        }
+    }
+
+    // Generate style mappings TOML
+    let mut template = OdtTemplate::load(input)
+        .with_context(|| format!("Failed to load template: {}", input.display()))?;
+    let mut toml_content = String::new();
+    toml_content.push_str("# utf8dok configuration\n");
+    toml_content.push_str(&format!("# Generated from: {}\n\n", input.display()));
+    toml_content.push_str("[template]\npath = \"template.ott\"\n\n[styles]\n");
+    let headings = template
+        .heading_style_names()
+        .context("Failed to parse styles")?;
+    for (index, name) in headings.iter().enumerate() {
+        toml_content.push_str(&format!("heading{} = \"{}\"\n", index + 1, name));
+    }
+    if template.has_style("Text_20_body").unwrap_or(false) {
+        toml_content.push_str("paragraph = \"Text_20_body\"\n");
+    }
+    let toml_path = output_dir.join("utf8dok.toml");
+    fs::write(&toml_path, toml_content)
+        .with_context(|| format!("Failed to write config file: {}", toml_path.display()))?;
+    println!("  Created: {}", toml_path.display());
+
+    println!();
+    println!("Extraction complete!");
+    let line_count = asciidoc.lines().filter(|l| !l.trim().is_empty()).count();
+    println!("  {} content lines", line_count);
+
+    Ok(())
+}
+
 /// Generate configuration TOML from styles
 fn generate_config_toml(styles: &StyleSheet, input: &std::path::Path) -> String {
     let mut output = String::new();
@@ -706,6 +972,7 @@ fn generate_config_toml(styles: &StyleSheet, input: &std::path::Path) -> String
 }
 
 /// Execute the render command
+#[allow(clippy::too_many_arguments)]
 pub fn render_command(
     input: &std::path::Path,
     output: Option<&std::path::Path>,
@@ -713,6 +980,11 @@ pub fn render_command(
     template: Option<&std::path::Path>,
     cover: Option<&std::path::Path>,
     data_dir: Option<&std::path::Path>,
+    chunked: bool,
+    from_json: bool,
+    word_comments: bool,
+    redline_against: Option<&std::path::Path>,
+    style_contract: Option<&std::path::Path>,
 ) -> Result<()> {
     println!("utf8dok v{}", utf8dok_core::VERSION);
     println!("Rendering: {}", input.display());
@@ -722,20 +994,224 @@ pub fn render_command(
         anyhow::bail!("Input file not found: {}", input.display());
     }
 
+    let kind = SourceKind::of(input, from_json);
+
     match format {
-        RenderFormat::Docx => render_docx(input, output, template, cover, data_dir),
-        RenderFormat::Pptx => render_pptx(input, output, template, data_dir),
-        RenderFormat::Pdf => render_pdf(input, output, template, data_dir),
+        RenderFormat::Docx => render_docx(
+            input,
+            kind,
+            output,
+            template,
+            cover,
+            data_dir,
+            word_comments,
+            redline_against,
+            style_contract,
+        ),
+        RenderFormat::Odt => render_odt(input, kind, output, template, data_dir),
+        RenderFormat::Pptx => render_pptx(input, kind, output, template, data_dir),
+        RenderFormat::Pdf => render_pdf(input, kind, output, template, data_dir),
+        RenderFormat::Html => render_html(input, kind, output, template, data_dir, chunked),
+        RenderFormat::Docbook | RenderFormat::Jats => {
+            render_xml(input, kind, output, template, data_dir, format)
+        }
     }
 }
 
+/// Execute the ast dump command
+///
+/// Writes the parsed document as versioned JSON, which `render --from-json`
+/// reads back. Progress goes to stderr so stdout stays valid JSON.
+pub fn ast_dump_command(
+    input: &std::path::Path,
+    output: Option<&std::path::Path>,
+    backend: Option<&str>,
+    data_dir: Option<&std::path::Path>,
+) -> Result<()> {
+    let source = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read input file: {}", input.display()))?;
+    let kind = SourceKind::of(input, false);
+    let doc = parse_source(input, &source, kind, data_dir, backend)?;
+    write_or_print(output, &utf8dok_ast::to_json(&doc))
+}
+
+/// Execute the ast schema command
+pub fn ast_schema_command(output: Option<&std::path::Path>) -> Result<()> {
+    write_or_print(output, &utf8dok_ast::json::schema())
+}
+
+/// Write `content` to `output`, or to stdout when there is none
+fn write_or_print(output: Option<&std::path::Path>, content: &str) -> Result<()> {
+    match output {
+        Some(path) => {
+            fs::write(path, content)
+                .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+            eprintln!("Written: {}", path.display());
+        }
+        None => println!("{}", content.trim_end()),
+    }
+    Ok(())
+}
+
+/// Directory text includes in `input` are resolved against
+fn include_dir(input: &std::path::Path) -> String {
+    input
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Whether `input` is a Markdown file (`.md` or `.markdown`)
+fn is_markdown(input: &std::path::Path) -> bool {
+    input
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+}
+
+/// Language of a render or dump input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceKind {
+    AsciiDoc,
+    Markdown,
+    /// AST JSON written by `utf8dok ast dump`
+    Json,
+}
+
+impl SourceKind {
+    /// Kind of `input`: JSON when asked for, otherwise by extension
+    fn of(input: &std::path::Path, from_json: bool) -> Self {
+        if from_json {
+            SourceKind::Json
+        } else if is_markdown(input) {
+            SourceKind::Markdown
+        } else {
+            SourceKind::AsciiDoc
+        }
+    }
+
+    /// Name of the language, for progress output
+    fn name(self) -> &'static str {
+        match self {
+            SourceKind::AsciiDoc => "AsciiDoc",
+            SourceKind::Markdown => "Markdown",
+            SourceKind::Json => "AST JSON",
+        }
+    }
+}
+
+/// Parse source for rendering to a backend
+///
+/// Markdown files go through the Markdown frontend and AST JSON is read as
+/// it is. For AsciiDoc, sets the backend attributes (`backend-docx`, ...)
+/// used by conditionals, resolves text includes relative to the input file
+/// and enables data includes when a data directory is given.
+fn parse_for_render(
+    input: &std::path::Path,
+    source: &str,
+    kind: SourceKind,
+    data_dir: Option<&std::path::Path>,
+    backend: &str,
+) -> Result<utf8dok_ast::Document> {
+    if let (SourceKind::AsciiDoc, Some(base_path)) = (kind, data_dir) {
+        println!("    Data includes enabled: {}", base_path.display());
+    }
+    let mut doc = parse_source(input, source, kind, data_dir, Some(backend))?;
+
+    let pipeline = load_pipeline(input)?;
+    if !pipeline.is_empty() {
+        println!("  Applying transforms: {}", pipeline.names().join(", "));
+        pipeline.run(&mut doc)?;
+    }
+    Ok(doc)
+}
+
+/// Build the transform pipeline from the `[[transform]]` tables of the
+/// utf8dok.toml next to `input`
+///
+/// Script paths are relative to the config file.
+fn load_pipeline(input: &std::path::Path) -> Result<Pipeline> {
+    let config_dir = std::path::PathBuf::from(include_dir(input));
+    let config_path = config_dir.join("utf8dok.toml");
+    if !config_path.exists() {
+        return Ok(Pipeline::new());
+    }
+    let content = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config: {}", config_path.display()))?;
+    let config: toml::Table = content
+        .parse()
+        .with_context(|| format!("Failed to parse config: {}", config_path.display()))?;
+    let specs: Vec<TransformSpec> = match config.get("transform") {
+        Some(transforms) => transforms
+            .clone()
+            .try_into()
+            .with_context(|| format!("Invalid [[transform]] in {}", config_path.display()))?,
+        None => return Ok(Pipeline::new()),
+    };
+
+    let pipeline = Pipeline::from_specs(&specs, |path| {
+        let transform =
+            ScriptTransform::load(&config_dir.join(path)).map_err(|e| TransformError::Config {
+                transform: path.to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(Box::new(transform))
+    })?;
+    Ok(pipeline)
+}
+
+/// Parse source, setting the backend attributes when a backend is given
+fn parse_source(
+    input: &std::path::Path,
+    source: &str,
+    kind: SourceKind,
+    data_dir: Option<&std::path::Path>,
+    backend: Option<&str>,
+) -> Result<utf8dok_ast::Document> {
+    match kind {
+        SourceKind::AsciiDoc => {}
+        SourceKind::Markdown => {
+            if data_dir.is_some() {
+                eprintln!(
+                    "  Warning: Data includes are not supported in Markdown; ignoring --data-dir"
+                );
+            }
+            return Ok(parse_markdown(source));
+        }
+        SourceKind::Json => {
+            if data_dir.is_some() {
+                eprintln!("  Warning: AST JSON is already resolved; ignoring --data-dir");
+            }
+            return utf8dok_ast::from_json(source).context("Failed to read AST JSON");
+        }
+    }
+
+    let config = match data_dir {
+        Some(base_path) => ParserConfig::with_data_includes(base_path.to_string_lossy()),
+        None => ParserConfig::default(),
+    };
+    let config = config.with_text_includes(include_dir(input));
+    let config = match backend {
+        Some(backend) => config.with_backend(backend),
+        None => config,
+    };
+    parse_with_config(source, config).context("Failed to parse AsciiDoc content")
+}
+
 /// Render AsciiDoc to DOCX
+#[allow(clippy::too_many_arguments)]
 fn render_docx(
     input: &std::path::Path,
+    kind: SourceKind,
     output: Option<&std::path::Path>,
     template: Option<&std::path::Path>,
     cover: Option<&std::path::Path>,
     data_dir: Option<&std::path::Path>,
+    word_comments: bool,
+    redline_against: Option<&std::path::Path>,
+    style_contract: Option<&std::path::Path>,
 ) -> Result<()> {
     println!("  Format: DOCX");
 
@@ -770,14 +1246,8 @@ fn render_docx(
         .with_context(|| format!("Failed to read input file: {}", input.display()))?;
 
     // Step 2: Parse AsciiDoc to AST (with optional data includes)
-    println!("  Parsing AsciiDoc...");
-    let ast = if let Some(base_path) = data_dir {
-        println!("    Data includes enabled: {}", base_path.display());
-        let config = ParserConfig::with_data_includes(base_path.to_string_lossy());
-        parse_with_config(&source_content, config).context("Failed to parse AsciiDoc content")?
-    } else {
-        parse(&source_content).context("Failed to parse AsciiDoc content")?
-    };
+    println!("  Parsing {}...", kind.name());
+    let ast = parse_for_render(input, &source_content, kind, data_dir, "docx")?;
     println!("    {} blocks parsed", ast.blocks.len());
 
     // Step 3: Load template using Template API
@@ -786,61 +1256,186 @@ fn render_docx(
         .with_context(|| format!("Failed to load template: {}", template_path.display()))?;
 
     // Step 4: Load or generate config
+    let config_content = load_render_config(input, &template_path)?;
+
+    // Step 5: Create writer with embedded content for self-contained DOCX
+    println!("  Generating self-contained DOCX...");
+    let mut writer = DocxWriter::new();
+    // Markdown and JSON sources are embedded as AsciiDoc, so extraction round-trips
+    if kind != SourceKind::AsciiDoc {
+        let config = GeneratorConfig {
+            include_header: true,
+            generate_anchors: true,
+        };
+        writer.set_source(generate_with_config(&ast, config));
+    } else {
+        writer.set_source(&source_content);
+    }
+    writer.set_config(&config_content);
+    writer.set_word_comments(word_comments);
+    if let Some(contract) = load_style_contract(style_contract)? {
+        writer.set_style_contract(contract);
+    }
+
+    // Step 5a: Show the changes from an earlier version as tracked changes
+    if let Some(original_path) = redline_against {
+        println!("  Redlining against: {}", original_path.display());
+        let original_source = fs::read_to_string(original_path).with_context(|| {
+            format!("Failed to read original file: {}", original_path.display())
+        })?;
+        let original_kind = SourceKind::of(original_path, kind == SourceKind::Json);
+        let original = parse_for_render(
+            original_path,
+            &original_source,
+            original_kind,
+            data_dir,
+            "docx",
+        )?;
+        writer.set_redline(original);
+    }
+
+    // Step 5b: Add cover image if specified
+    if let Some(cover_path) = cover {
+        if cover_path.exists() {
+            println!("  Adding cover image: {}", cover_path.display());
+            let cover_bytes = fs::read(cover_path)
+                .with_context(|| format!("Failed to read cover image: {}", cover_path.display()))?;
+            let cover_filename = cover_path
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "cover.png".to_string());
+            writer.set_cover_image(cover_filename, cover_bytes);
+        } else {
+            eprintln!("  Warning: Cover image not found: {}", cover_path.display());
+        }
+    }
+
+    // Step 6: Generate DOCX
+    let docx_bytes = writer
+        .generate_with_template(&ast, template_obj)
+        .context("Failed to generate DOCX from AST")?;
+
+    // Step 7: Write output
+    println!("  Writing: {}", output_path.display());
+    fs::write(&output_path, &docx_bytes)
+        .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
+
+    println!();
+    println!("Render complete!");
+    println!("  Output: {}", output_path.display());
+    println!("  Size: {} bytes", docx_bytes.len());
+    println!("  Self-contained: yes (source + config embedded)");
+
+    Ok(())
+}
+
+/// Read utf8dok.toml next to `input`, or generate a minimal config
+/// naming the template, for embedding in the output
+fn load_render_config(input: &std::path::Path, template_path: &std::path::Path) -> Result<String> {
     let config_path = input
         .parent()
         .unwrap_or(std::path::Path::new("."))
         .join("utf8dok.toml");
-    let config_content = if config_path.exists() {
+    if config_path.exists() {
         println!("  Loading config: {}", config_path.display());
         fs::read_to_string(&config_path)
-            .with_context(|| format!("Failed to read config: {}", config_path.display()))?
+            .with_context(|| format!("Failed to read config: {}", config_path.display()))
     } else {
         // Generate minimal config
-        format!(
+        Ok(format!(
             "# utf8dok configuration\n\
              # Auto-generated during render\n\n\
              [template]\n\
              path = \"{}\"\n",
             template_path.display()
-        )
+        ))
+    }
+}
+
+/// Render AsciiDoc to ODT
+fn render_odt(
+    input: &std::path::Path,
+    kind: SourceKind,
+    output: Option<&std::path::Path>,
+    template: Option<&std::path::Path>,
+    data_dir: Option<&std::path::Path>,
+) -> Result<()> {
+    println!("  Format: ODT");
+
+    // Determine output path (default: input with .odt extension)
+    let output_path = match output {
+        Some(p) => p.to_path_buf(),
+        None => input.with_extension("odt"),
     };
 
-    // Step 5: Create writer with embedded content for self-contained DOCX
-    println!("  Generating self-contained DOCX...");
-    let mut writer = DocxWriter::new();
-    writer.set_source(&source_content);
-    writer.set_config(&config_content);
+    // Determine template path (default: template.ott in current directory)
+    let template_path = match template {
+        Some(p) => p.to_path_buf(),
+        None => PathBuf::from("template.ott"),
+    };
+
+    // Check template exists
+    if !template_path.exists() {
+        anyhow::bail!(
+            "Template file not found: {}\n\
+             \n\
+             To create a template, you can:\n\
+             1. Use 'utf8dok extract' on an existing ODT to generate a template\n\
+             2. Create a new Writer document and save it as .ott\n\
+             3. Specify a different template with --template <path>",
+            template_path.display()
+        );
+    }
+
+    // Step 1: Read input AsciiDoc file
+    println!("  Reading: {}", input.display());
+    let source_content = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read input file: {}", input.display()))?;
+
+    // Step 2: Parse AsciiDoc to AST (with optional data includes)
+    println!("  Parsing {}...", kind.name());
+    let ast = parse_for_render(input, &source_content, kind, data_dir, "odt")?;
+    println!("    {} blocks parsed", ast.blocks.len());
+
+    // Step 3: Load template
+    println!("  Loading template: {}", template_path.display());
+    let template_obj = OdtTemplate::load(&template_path)
+        .with_context(|| format!("Failed to load template: {}", template_path.display()))?;
 
-    // Step 5b: Add cover image if specified
-    if let Some(cover_path) = cover {
-        if cover_path.exists() {
-            println!("  Adding cover image: {}", cover_path.display());
-            let cover_bytes = fs::read(cover_path)
-                .with_context(|| format!("Failed to read cover image: {}", cover_path.display()))?;
-            let cover_filename = cover_path
-                .file_name()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_else(|| "cover.png".to_string());
-            writer.set_cover_image(cover_filename, cover_bytes);
-        } else {
-            eprintln!("  Warning: Cover image not found: {}", cover_path.display());
-        }
+    // Step 4: Load or generate config
+    let config_content = load_render_config(input, &template_path)?;
+
+    // Step 5: Create writer with embedded content for self-contained ODT
+    println!("  Generating self-contained ODT...");
+    let mut writer = OdtWriter::new();
+    // Markdown and JSON sources are embedded as AsciiDoc, so extraction round-trips
+    if kind != SourceKind::AsciiDoc {
+        let config = GeneratorConfig {
+            include_header: true,
+            generate_anchors: true,
+        };
+        writer.set_source(generate_with_config(&ast, config));
+    } else {
+        writer.set_source(&source_content);
     }
+    writer.set_config(&config_content);
+    // Images next to the input are embedded
+    writer.set_image_dir(include_dir(input));
 
-    // Step 6: Generate DOCX
-    let docx_bytes = writer
+    // Step 6: Generate ODT
+    let odt_bytes = writer
         .generate_with_template(&ast, template_obj)
-        .context("Failed to generate DOCX from AST")?;
+        .context("Failed to generate ODT from AST")?;
 
     // Step 7: Write output
     println!("  Writing: {}", output_path.display());
-    fs::write(&output_path, &docx_bytes)
+    fs::write(&output_path, &odt_bytes)
         .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
 
     println!();
     println!("Render complete!");
     println!("  Output: {}", output_path.display());
-    println!("  Size: {} bytes", docx_bytes.len());
+    println!("  Size: {} bytes", odt_bytes.len());
     println!("  Self-contained: yes (source + config embedded)");
 
     Ok(())
@@ -849,6 +1444,7 @@ fn render_docx(
 /// Render AsciiDoc to PPTX
 fn render_pptx(
     input: &std::path::Path,
+    kind: SourceKind,
     output: Option<&std::path::Path>,
     template: Option<&std::path::Path>,
     data_dir: Option<&std::path::Path>,
@@ -867,14 +1463,8 @@ fn render_pptx(
         .with_context(|| format!("Failed to read input file: {}", input.display()))?;
 
     // Step 2: Parse AsciiDoc to AST (with optional data includes)
-    println!("  Parsing AsciiDoc...");
-    let ast = if let Some(base_path) = data_dir {
-        println!("    Data includes enabled: {}", base_path.display());
-        let config = ParserConfig::with_data_includes(base_path.to_string_lossy());
-        parse_with_config(&source_content, config).context("Failed to parse AsciiDoc content")?
-    } else {
-        parse(&source_content).context("Failed to parse AsciiDoc content")?
-    };
+    println!("  Parsing {}...", kind.name());
+    let ast = parse_for_render(input, &source_content, kind, data_dir, "pptx")?;
     println!("    {} blocks parsed", ast.blocks.len());
 
     // Step 3: Extract slides from AST using SlideExtractor
@@ -929,6 +1519,7 @@ fn render_pptx(
 /// Render AsciiDoc to PDF via Typst
 fn render_pdf(
     input: &std::path::Path,
+    kind: SourceKind,
     output: Option<&std::path::Path>,
     template: Option<&std::path::Path>,
     data_dir: Option<&std::path::Path>,
@@ -947,14 +1538,8 @@ fn render_pdf(
         .with_context(|| format!("Failed to read input file: {}", input.display()))?;
 
     // Step 2: Parse AsciiDoc to AST (with optional data includes)
-    println!("  Parsing AsciiDoc...");
-    let ast = if let Some(base_path) = data_dir {
-        println!("    Data includes enabled: {}", base_path.display());
-        let config = ParserConfig::with_data_includes(base_path.to_string_lossy());
-        parse_with_config(&source_content, config).context("Failed to parse AsciiDoc content")?
-    } else {
-        parse(&source_content).context("Failed to parse AsciiDoc content")?
-    };
+    println!("  Parsing {}...", kind.name());
+    let ast = parse_for_render(input, &source_content, kind, data_dir, "pdf")?;
     println!("    {} blocks parsed", ast.blocks.len());
 
     // Step 3: Transpile AST to Typst markup
@@ -992,6 +1577,172 @@ fn render_pdf(
     Ok(())
 }
 
+/// Render AsciiDoc to HTML5
+///
+/// The template, if given, is a style contract (TOML) providing the theme
+/// and the original bookmark names of anchors.
+fn render_html(
+    input: &std::path::Path,
+    kind: SourceKind,
+    output: Option<&std::path::Path>,
+    template: Option<&std::path::Path>,
+    data_dir: Option<&std::path::Path>,
+    chunked: bool,
+) -> Result<()> {
+    println!("  Format: HTML");
+
+    // Determine output path (default: input with .html extension, or a
+    // directory named after the input for chunked output)
+    let output_path = match output {
+        Some(p) => p.to_path_buf(),
+        None if chunked => input.with_extension(""),
+        None => input.with_extension("html"),
+    };
+
+    // Step 1: Read input AsciiDoc file
+    println!("  Reading: {}", input.display());
+    let source_content = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read input file: {}", input.display()))?;
+
+    // Step 2: Parse AsciiDoc to AST (with optional data includes)
+    println!("  Parsing {}...", kind.name());
+    let ast = parse_for_render(input, &source_content, kind, data_dir, "html")?;
+    println!("    {} blocks parsed", ast.blocks.len());
+
+    // Step 3: Load style contract if specified
+    let mut writer = HtmlWriter::new();
+    writer.set_render_diagrams(true);
+    if let Some(contract) = load_style_contract(template)? {
+        writer.set_style_contract(contract);
+    }
+
+    // Step 4: Generate and write HTML
+    println!("  Generating HTML...");
+    if chunked {
+        let pages = writer.generate_chunked(&ast);
+        fs::create_dir_all(&output_path).with_context(|| {
+            format!(
+                "Failed to create output directory: {}",
+                output_path.display()
+            )
+        })?;
+        for page in &pages {
+            let page_path = output_path.join(&page.file_name);
+            fs::write(&page_path, &page.html)
+                .with_context(|| format!("Failed to write page: {}", page_path.display()))?;
+        }
+
+        println!();
+        println!("Render complete!");
+        println!("  Output: {}", output_path.display());
+        println!("  Pages: {}", pages.len());
+    } else {
+        let html = writer.generate(&ast);
+        println!("  Writing: {}", output_path.display());
+        fs::write(&output_path, &html)
+            .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
+
+        println!();
+        println!("Render complete!");
+        println!("  Output: {}", output_path.display());
+        println!("  Size: {} bytes", html.len());
+    }
+
+    Ok(())
+}
+
+/// Render AsciiDoc to DocBook 5 or JATS XML
+///
+/// The template, if given, is a style contract (TOML) providing the original
+/// bookmark names of anchors.
+fn render_xml(
+    input: &std::path::Path,
+    kind: SourceKind,
+    output: Option<&std::path::Path>,
+    template: Option<&std::path::Path>,
+    data_dir: Option<&std::path::Path>,
+    format: RenderFormat,
+) -> Result<()> {
+    let (name, backend) = match format {
+        RenderFormat::Jats => ("JATS", "jats"),
+        _ => ("DocBook", "docbook5"),
+    };
+    println!("  Format: {}", name);
+
+    // Determine output path (default: input with .xml extension)
+    let output_path = output
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| input.with_extension("xml"));
+
+    // Step 1: Read input AsciiDoc file
+    println!("  Reading: {}", input.display());
+    let source_content = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read input file: {}", input.display()))?;
+
+    // Step 2: Parse AsciiDoc to AST (with optional data includes)
+    println!("  Parsing {}...", kind.name());
+    let ast = parse_for_render(input, &source_content, kind, data_dir, backend)?;
+    println!("    {} blocks parsed", ast.blocks.len());
+
+    // Step 3: Load style contract if specified
+    let contract = load_style_contract(template)?;
+
+    // Step 4: Generate and write XML
+    println!("  Generating {}...", name);
+    let xml = match format {
+        RenderFormat::Jats => {
+            let mut writer = JatsWriter::new();
+            if let Some(contract) = contract {
+                writer.set_style_contract(contract);
+            }
+            writer.generate(&ast)
+        }
+        _ => {
+            let mut writer = DocBookWriter::new();
+            if let Some(contract) = contract {
+                writer.set_style_contract(contract);
+            }
+            writer.generate(&ast)
+        }
+    };
+    println!("  Writing: {}", output_path.display());
+    fs::write(&output_path, &xml)
+        .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
+
+    println!();
+    println!("Render complete!");
+    println!("  Output: {}", output_path.display());
+    println!("  Size: {} bytes", xml.len());
+
+    Ok(())
+}
+
+/// Load the style contract (TOML) given as the template of the HTML and
+/// XML backends or with `--style-contract` for DOCX, warning if it does not
+/// exist
+fn load_style_contract(template: Option<&std::path::Path>) -> Result<Option<StyleContract>> {
+    let Some(template_path) = template else {
+        return Ok(None);
+    };
+    if !template_path.exists() {
+        eprintln!(
+            "  Warning: Style contract not found: {}",
+            template_path.display()
+        );
+        return Ok(None);
+    }
+    println!("  Loading style contract: {}", template_path.display());
+    let content = fs::read_to_string(template_path)
+        .with_context(|| format!("Failed to read style contract: {}", template_path.display()))?;
+    let contract = StyleContract::from_toml(&content).with_context(|| {
+        format!(
+            "Failed to parse style contract: {}",
+            template_path.display()
+        )
+    })?;
+    Ok(Some(contract))
+}
+
 /// Execute the check command
 pub fn check_command(
     input: &std::path::Path,
@@ -1007,15 +1758,24 @@ pub fn check_command(
     let content = fs::read_to_string(input)
         .with_context(|| format!("Failed to read input file: {}", input.display()))?;
 
-    // Step 2: Parse AsciiDoc to AST
-    let ast = parse(&content).context("Failed to parse AsciiDoc content")?;
+    // Step 2: Parse AsciiDoc to AST, keeping the problems the parser recovered from
+    let config = ParserConfig::default().with_text_includes(include_dir(input));
+    let (ast, parse_diagnostics) = parse_with_diagnostics(&content, config);
 
     // Step 3: Run built-in validation engine
+    // (diagnostics on included content name their file relative to the input)
     let engine = ValidationEngine::with_defaults();
-    let mut diagnostics: Vec<Diagnostic> = engine
-        .validate(&ast)
+    let docdir = PathBuf::from(include_dir(input));
+    let mut diagnostics: Vec<Diagnostic> = parse_diagnostics
         .into_iter()
-        .map(|d| d.with_file(input.display().to_string()))
+        .chain(engine.validate(&ast))
+        .map(|d| {
+            let file = match &d.file {
+                Some(included) => docdir.join(included),
+                None => input.to_path_buf(),
+            };
+            d.with_file(file.display().to_string())
+        })
         .collect();
 
     // Step 4: Run plugin scripts
@@ -1152,6 +1912,71 @@ pub fn audit_command(
     Ok(())
 }
 
+/// Execute the fmt command (canonical source layout)
+pub fn fmt_command(
+    inputs: &[PathBuf],
+    check: bool,
+    config_path: Option<&std::path::Path>,
+) -> Result<()> {
+    let config = load_settings(config_path)?.format;
+
+    let mut files = Vec::new();
+    for input in inputs {
+        if input.is_dir() {
+            for extension in ["adoc", "asciidoc"] {
+                let pattern = input
+                    .join(format!("**/*.{}", extension))
+                    .display()
+                    .to_string();
+                for entry in
+                    glob(&pattern).with_context(|| format!("Invalid glob pattern: {}", pattern))?
+                {
+                    files.push(entry?);
+                }
+            }
+        } else if input.exists() {
+            files.push(input.clone());
+        } else {
+            anyhow::bail!("Input file not found: {}", input.display());
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let mut changed = 0;
+    for file in &files {
+        let content = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read input file: {}", file.display()))?;
+        let formatted = utf8dok_core::format_with_config(&content, &config);
+        if formatted == content {
+            continue;
+        }
+        changed += 1;
+        if check {
+            println!("  Needs formatting: {}", file.display());
+        } else {
+            fs::write(file, formatted)
+                .with_context(|| format!("Failed to write file: {}", file.display()))?;
+            println!("  Formatted: {}", file.display());
+        }
+    }
+
+    if check && changed > 0 {
+        anyhow::bail!(
+            "{} of {} file(s) need formatting (run `utf8dok fmt`)",
+            changed,
+            files.len()
+        );
+    }
+    println!(
+        "✓ {} file(s) checked, {} {}",
+        files.len(),
+        changed,
+        if check { "to format" } else { "formatted" }
+    );
+    Ok(())
+}
+
 /// Execute the dashboard command (HTML report generation)
 pub fn dashboard_command(
     input: &std::path::Path,
@@ -1706,10 +2531,12 @@ mod tests {
                 input,
                 output,
                 force_parse,
+                markdown,
             } => {
                 assert_eq!(input, PathBuf::from("test.docx"));
                 assert_eq!(output, PathBuf::from("result"));
                 assert!(!force_parse);
+                assert!(!markdown);
             }
             _ => panic!("Expected Extract command"),
         }
@@ -1725,10 +2552,12 @@ mod tests {
                 input,
                 output,
                 force_parse,
+                markdown,
             } => {
                 assert_eq!(input, PathBuf::from("test.docx"));
                 assert_eq!(output, PathBuf::from("output"));
                 assert!(!force_parse);
+                assert!(!markdown);
             }
             _ => panic!("Expected Extract command"),
         }
@@ -1744,15 +2573,50 @@ mod tests {
                 input,
                 output,
                 force_parse,
+                markdown,
             } => {
                 assert_eq!(input, PathBuf::from("test.docx"));
                 assert_eq!(output, PathBuf::from("output"));
                 assert!(force_parse);
+                assert!(!markdown);
             }
             _ => panic!("Expected Extract command"),
         }
     }
 
+    #[test]
+    fn test_cli_parse_extract_markdown() {
+        let args = vec!["utf8dok", "extract", "test.docx", "--markdown"];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command {
+            Commands::Extract { markdown, .. } => assert!(markdown),
+            _ => panic!("Expected Extract command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_for_render_markdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("notes.md");
+        let kind = SourceKind::of(&input, false);
+        assert_eq!(kind, SourceKind::Markdown);
+        let doc = parse_for_render(
+            &input,
+            "# Notes\n\n## Intro\n\n- [x] done",
+            kind,
+            None,
+            "docx",
+        )
+        .unwrap();
+        assert_eq!(doc.metadata.title.as_deref(), Some("Notes"));
+        assert_eq!(doc.blocks.len(), 2);
+
+        // Other extensions are AsciiDoc
+        assert!(!is_markdown(&dir.path().join("notes.adoc")));
+        assert!(is_markdown(std::path::Path::new("README.MD")));
+    }
+
     #[test]
     fn test_cli_parse_render() {
         let args = vec![
@@ -1774,6 +2638,11 @@ mod tests {
                 template,
                 cover,
                 data_dir,
+                chunked,
+                from_json,
+                word_comments,
+                redline_against,
+                style_contract,
             } => {
                 assert_eq!(input, PathBuf::from("doc.adoc"));
                 assert_eq!(output, Some(PathBuf::from("out.docx")));
@@ -1781,6 +2650,11 @@ mod tests {
                 assert_eq!(template, Some(PathBuf::from("tmpl.dotx")));
                 assert_eq!(cover, None);
                 assert_eq!(data_dir, None);
+                assert!(!chunked);
+                assert!(!from_json);
+                assert!(!word_comments);
+                assert_eq!(redline_against, None);
+                assert_eq!(style_contract, None);
             }
             _ => panic!("Expected Render command"),
         }
@@ -1807,6 +2681,11 @@ mod tests {
                 template,
                 cover: _,
                 data_dir,
+                chunked: _,
+                from_json: _,
+                word_comments: _,
+                redline_against: _,
+                style_contract: _,
             } => {
                 assert_eq!(input, PathBuf::from("slides.adoc"));
                 assert_eq!(output, Some(PathBuf::from("slides.pptx")));
@@ -1839,6 +2718,11 @@ mod tests {
                 template,
                 cover: _,
                 data_dir,
+                chunked: _,
+                from_json: _,
+                word_comments: _,
+                redline_against: _,
+                style_contract: _,
             } => {
                 assert_eq!(input, PathBuf::from("slides.adoc"));
                 assert_eq!(output, None);
@@ -1863,6 +2747,11 @@ mod tests {
                 template,
                 cover: _,
                 data_dir,
+                chunked: _,
+                from_json: _,
+                word_comments: _,
+                redline_against: _,
+                style_contract: _,
             } => {
                 assert_eq!(input, PathBuf::from("report.adoc"));
                 assert_eq!(output, None);
@@ -1874,6 +2763,463 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_render_html_chunked() {
+        let args = vec!["utf8dok", "render", "guide.adoc", "-f", "html", "--chunked"];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command {
+            Commands::Render {
+                format, chunked, ..
+            } => {
+                assert!(matches!(format, RenderFormat::Html));
+                assert!(chunked);
+            }
+            _ => panic!("Expected Render command"),
+        }
+    }
+
+    #[test]
+    fn test_render_html() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("guide.adoc");
+        fs::write(
+            &input,
+            "= Guide\n\nIntro.\n\n== Setup\n\nSee <<usage>>.\n\n[[usage]]\n== Usage\n\nRun it.\n",
+        )
+        .unwrap();
+        let contract = dir.path().join("style-contract.toml");
+        fs::write(&contract, "[theme]\naccent_color = \"C00000\"\n").unwrap();
+
+        render_command(
+            &input,
+            None,
+            RenderFormat::Html,
+            Some(&contract),
+            None,
+            None,
+            false,
+            false,
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let html = fs::read_to_string(dir.path().join("guide.html")).unwrap();
+        assert!(html.contains("<title>Guide</title>"));
+        assert!(html.contains("<a href=\"#usage\">usage</a>"));
+        assert!(html.contains("--accent-color: #c00000;"));
+
+        render_command(
+            &input,
+            None,
+            RenderFormat::Html,
+            None,
+            None,
+            None,
+            true,
+            false,
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let pages = dir.path().join("guide");
+        assert!(pages.join("index.html").exists());
+        assert!(pages.join("setup.html").exists());
+        let setup = fs::read_to_string(pages.join("setup.html")).unwrap();
+        assert!(setup.contains("<a href=\"usage.html#usage\">usage</a>"));
+    }
+
+    #[test]
+    fn test_render_xml() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("paper.adoc");
+        fs::write(
+            &input,
+            "= Paper\n\n== Method\n\nSee <<method>>.footnote:[Details.]\n",
+        )
+        .unwrap();
+
+        render_command(
+            &input,
+            None,
+            RenderFormat::Docbook,
+            None,
+            None,
+            None,
+            false,
+            false,
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let docbook = fs::read_to_string(dir.path().join("paper.xml")).unwrap();
+        assert!(docbook.contains("<section xml:id=\"_method\">"));
+
+        let output = dir.path().join("paper.jats.xml");
+        render_command(
+            &input,
+            Some(&output),
+            RenderFormat::Jats,
+            None,
+            None,
+            None,
+            false,
+            false,
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let jats = fs::read_to_string(&output).unwrap();
+        assert!(jats.contains("<sec id=\"_method\">"));
+        assert!(jats.contains("<fn id=\"fn1\">"));
+    }
+
+    #[test]
+    fn test_cli_parse_ast() {
+        let cli =
+            Cli::try_parse_from(["utf8dok", "ast", "dump", "doc.adoc", "-b", "docx"]).unwrap();
+        match cli.command {
+            Commands::Ast {
+                command:
+                    AstCommand::Dump {
+                        input,
+                        output,
+                        backend,
+                        ..
+                    },
+            } => {
+                assert_eq!(input, PathBuf::from("doc.adoc"));
+                assert_eq!(output, None);
+                assert_eq!(backend.as_deref(), Some("docx"));
+            }
+            _ => panic!("Expected ast dump command"),
+        }
+
+        let cli = Cli::try_parse_from(["utf8dok", "render", "doc.json", "--from-json"]).unwrap();
+        match cli.command {
+            Commands::Render { from_json, .. } => assert!(from_json),
+            _ => panic!("Expected Render command"),
+        }
+    }
+
+    #[test]
+    fn test_ast_dump_and_render_from_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("guide.adoc");
+        fs::write(
+            &input,
+            "= Guide\n\nifdef::backend-html[]\nWeb only.\nendif::[]\n\n== Setup\n\nRun *it*.\n",
+        )
+        .unwrap();
+
+        let json = dir.path().join("guide.json");
+        ast_dump_command(&input, Some(&json), Some("html"), None).unwrap();
+        let dumped = fs::read_to_string(&json).unwrap();
+        assert!(dumped.starts_with("{\n  \"version\": 1,"));
+        assert!(dumped.contains("Web only."));
+
+        // The dump reads back as the same document
+        let doc = utf8dok_ast::from_json(&dumped).unwrap();
+        assert_eq!(doc.metadata.title.as_deref(), Some("Guide"));
+
+        let output = dir.path().join("guide.html");
+        render_command(
+            &json,
+            Some(&output),
+            RenderFormat::Html,
+            None,
+            None,
+            None,
+            false,
+            true,
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let html = fs::read_to_string(&output).unwrap();
+        assert!(html.contains("<title>Guide</title>"));
+        assert!(html.contains("Web only."));
+        assert!(html.contains("<strong>it</strong>"));
+
+        // JSON from a newer release is rejected
+        fs::write(&json, "{\"version\": 9, \"document\": {}}").unwrap();
+        let err = render_command(
+            &json,
+            Some(&output),
+            RenderFormat::Html,
+            None,
+            None,
+            None,
+            false,
+            true,
+            false,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("Unsupported document JSON version 9"));
+    }
+
+    #[test]
+    fn test_render_applies_transforms() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("guide.adoc");
+        fs::write(
+            &input,
+            "= Guide\n\n== Setup\n\nSee <<usage>>.\n\n[.draft]\n--\nTo do.\n--\n\n\
+             [[usage]]\n== Usage\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("utf8dok.toml"),
+            "[[transform]]\nname = \"strip-draft\"\n\n\
+             [[transform]]\nname = \"number-sections\"\n\n\
+             [[transform]]\nname = \"script\"\npath = \"retitle.rhai\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("retitle.rhai"),
+            "doc.metadata.title = \"Handbook\";\n",
+        )
+        .unwrap();
+
+        render_command(
+            &input,
+            None,
+            RenderFormat::Html,
+            None,
+            None,
+            None,
+            false,
+            false,
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let html = fs::read_to_string(dir.path().join("guide.html")).unwrap();
+        assert!(html.contains("<title>Handbook</title>"));
+        assert!(html.contains("1. Setup"));
+        assert!(html.contains("2. Usage"));
+        assert!(!html.contains("To do."));
+
+        // Configuration errors name the transform
+        fs::write(
+            dir.path().join("utf8dok.toml"),
+            "[[transform]]\nname = \"rewrite-links\"\npattern = \"(\"\nreplacement = \"\"\n",
+        )
+        .unwrap();
+        let err = render_command(
+            &input,
+            None,
+            RenderFormat::Html,
+            None,
+            None,
+            None,
+            false,
+            false,
+            false,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("transform 'rewrite-links'"));
+    }
+
+    #[test]
+    fn test_render_docx_with_style_contract() {
+        use utf8dok_ooxml::test_utils::{create_template_with_styles, extract_file};
+
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("design.adoc");
+        fs::write(
+            &input,
+            "= Design Notes\n:classification: Internal\n\nBody.\n",
+        )
+        .unwrap();
+        let template = dir.path().join("corporate.dotx");
+        fs::write(&template, create_template_with_styles()).unwrap();
+        let contract = dir.path().join("style-contract.toml");
+        fs::write(
+            &contract,
+            "[header]\ncontent = \"{title}\\t\\t{classification}\"\n\n\
+             [footer]\ncontent = \"Page {page} of {pages}\"\n",
+        )
+        .unwrap();
+
+        render_command(
+            &input,
+            None,
+            RenderFormat::Docx,
+            Some(&template),
+            None,
+            None,
+            false,
+            false,
+            false,
+            None,
+            Some(&contract),
+        )
+        .unwrap();
+        let docx = fs::read(dir.path().join("design.docx")).unwrap();
+        let header = extract_file(&docx, "word/header1.xml").unwrap();
+        assert!(header.contains(">Design Notes</w:t>"));
+        assert!(header.contains(">Internal</w:t>"));
+        let footer = extract_file(&docx, "word/footer1.xml").unwrap();
+        assert!(footer.contains(" PAGE "));
+        assert!(footer.contains(" NUMPAGES "));
+    }
+
+    #[test]
+    fn test_redline_and_extract_change_report() {
+        use utf8dok_ooxml::test_utils::create_template_with_styles;
+
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("notes-v1.adoc");
+        fs::write(&original, "= Notes\n\nThe service runs slowly.\n").unwrap();
+        let input = dir.path().join("notes.adoc");
+        let source = "= Notes\nJane Doe\n\nThe service runs fast.\n";
+        fs::write(&input, source).unwrap();
+        let template = dir.path().join("corporate.dotx");
+        fs::write(&template, create_template_with_styles()).unwrap();
+
+        render_command(
+            &input,
+            None,
+            RenderFormat::Docx,
+            Some(&template),
+            None,
+            None,
+            false,
+            false,
+            false,
+            Some(&original),
+            None,
+        )
+        .unwrap();
+        let output = dir.path().join("extracted");
+        extract_command(&dir.path().join("notes.docx"), &output, false, false).unwrap();
+
+        // The revised source comes back, with the changes reported beside it
+        assert_eq!(
+            fs::read_to_string(output.join("document.adoc")).unwrap(),
+            source
+        );
+        let report = fs::read_to_string(output.join("changes.adoc")).unwrap();
+        assert!(report.contains("|Deleted |slowly. |Jane Doe |"));
+        assert!(report.contains("|Inserted |fast. |Jane Doe |"));
+
+        // Parsing the body accepts the changes
+        extract_command(&dir.path().join("notes.docx"), &output, true, false).unwrap();
+        let adoc = fs::read_to_string(output.join("document.adoc")).unwrap();
+        assert!(adoc.contains("The service runs fast."));
+        assert!(!adoc.contains("slowly"));
+    }
+
+    #[test]
+    fn test_render_and_extract_odt() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("guide.adoc");
+        let source = "= Guide\n\n== Setup\n\nimage::logo.png[Logo]\n";
+        fs::write(&input, source).unwrap();
+        fs::write(dir.path().join("logo.png"), b"\x89PNG").unwrap();
+        let template = dir.path().join("corporate.ott");
+        fs::write(
+            &template,
+            utf8dok_odf::test_utils::create_minimal_template(),
+        )
+        .unwrap();
+
+        render_command(
+            &input,
+            None,
+            RenderFormat::Odt,
+            Some(&template),
+            None,
+            None,
+            false,
+            false,
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let odt = dir.path().join("guide.odt");
+        assert!(odt.exists());
+
+        let output = dir.path().join("extracted");
+        extract_command(&odt, &output, false, false).unwrap();
+        assert_eq!(
+            fs::read_to_string(output.join("document.adoc")).unwrap(),
+            source
+        );
+        assert!(output.join("template.ott").exists());
+        let config = fs::read_to_string(output.join("utf8dok.toml")).unwrap();
+        assert!(config.contains("path = \"template.ott\""));
+        assert!(config.contains("heading1 = \"Heading_20_1\""));
+
+        // The image is embedded, and extracted next to the converted body
+        extract_command(&odt, &output, true, false).unwrap();
+        let adoc = fs::read_to_string(output.join("document.adoc")).unwrap();
+        assert!(adoc.contains("== Setup"));
+        assert!(adoc.contains("image::media/logo.png[Logo]"));
+        assert!(output.join("media/logo.png").exists());
+    }
+
+    #[test]
+    fn test_cli_parse_fmt() {
+        let args = vec!["utf8dok", "fmt", "docs", "intro.adoc", "--check"];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command {
+            Commands::Fmt {
+                inputs,
+                check,
+                config,
+            } => {
+                assert_eq!(inputs, [PathBuf::from("docs"), PathBuf::from("intro.adoc")]);
+                assert!(check);
+                assert!(config.is_none());
+            }
+            _ => panic!("Expected Fmt command"),
+        }
+    }
+
+    #[test]
+    fn test_fmt_check_and_write() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("guide")).unwrap();
+        let messy = dir.path().join("guide/setup.adoc");
+        let tidy = dir.path().join("index.adoc");
+        fs::write(&messy, "= Setup\n\nInstall it. Run it.\n\n*   step\n").unwrap();
+        fs::write(&tidy, "= Index\n\nOne sentence.\n").unwrap();
+        let inputs = [dir.path().to_path_buf()];
+
+        let err = fmt_command(&inputs, true, None).unwrap_err();
+        assert!(err.to_string().contains("1 of 2 file(s) need formatting"));
+        assert!(fs::read_to_string(&messy)
+            .unwrap()
+            .contains("Install it. Run it."));
+
+        fmt_command(&inputs, false, None).unwrap();
+        assert_eq!(
+            fs::read_to_string(&messy).unwrap(),
+            "= Setup\n\nInstall it.\nRun it.\n\n* step\n"
+        );
+        fmt_command(&inputs, true, None).unwrap();
+
+        // Rules can be turned off in the configuration
+        let config = dir.path().join("utf8dok.toml");
+        fs::write(&config, "[format]\nsentence_per_line = false\n").unwrap();
+        fs::write(&messy, "Install it. Run it.\n").unwrap();
+        fmt_command(std::slice::from_ref(&messy), true, Some(&config)).unwrap();
+    }
+
     #[test]
     fn test_cli_parse_check() {
         let args = vec!["utf8dok", "check", "doc.adoc"];
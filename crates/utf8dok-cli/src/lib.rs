@@ -1,8 +1,8 @@
 //! utf8dok CLI - Command-line interface library
 //!
 //! This library provides the CLI functionality for utf8dok, including:
-//! - Extract: Convert DOCX to AsciiDoc
-//! - Render: Convert AsciiDoc to DOCX
+//! - Extract: Convert DOCX to AsciiDoc (and Markdown)
+//! - Render: Convert AsciiDoc or Markdown to DOCX
 //! - Check: Validate AsciiDoc files
 //!
 //! # Library Usage
@@ -14,7 +14,7 @@
 //! run_cli();
 //!
 //! // Or use individual commands programmatically
-//! extract_command(&input, &output, false)?;
+//! extract_command(&input, &output, false, false)?;
 //! check_command(&input, OutputFormat::Json, &plugins)?;
 //! ```
 //!
@@ -27,6 +27,15 @@
 //! # Render AsciiDoc to DOCX
 //! utf8dok render document.adoc --output final.docx
 //!
+//! # Render Markdown to DOCX
+//! utf8dok render README.md --output readme.docx
+//!
+//! # Render AsciiDoc to HTML, one page per section
+//! utf8dok render document.adoc --format html --chunked
+//!
+//! # Render AsciiDoc to DocBook 5 or JATS XML
+//! utf8dok render document.adoc --format docbook
+//!
 //! # Check AsciiDoc for issues
 //! utf8dok check document.adoc --format json
 //! ```
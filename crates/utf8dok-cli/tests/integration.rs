@@ -445,3 +445,62 @@ fn test_extract_no_embedded_source() {
         "Should contain parsed content"
     );
 }
+
+/// The same admonition, parsed once, reaches every backend
+#[test]
+fn test_admonition_across_backends() {
+    use utf8dok_ast::{AdmonitionType, Block};
+    use utf8dok_pptx::{SlideContent, SlideExtractor};
+
+    let source = r#"= Deck
+
+== Safety
+
+.Hot surface
+[WARNING]
+====
+Do not touch the heater.
+====
+"#;
+
+    let doc = parse(source).unwrap();
+    let Block::Admonition(admonition) = &doc.blocks[1] else {
+        panic!("Expected admonition, got {:?}", doc.blocks[1]);
+    };
+    assert_eq!(admonition.admonition_type, AdmonitionType::Warning);
+
+    // DOCX: title paragraph and styled content
+    let docx = DocxWriter::generate(&doc, &create_test_template()).unwrap();
+    let archive = OoxmlArchive::from_reader(Cursor::new(docx)).unwrap();
+    let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
+    assert!(doc_xml.contains("<w:pStyle w:val=\"WarningTitle\"/>"));
+    assert!(doc_xml.contains("<w:pStyle w:val=\"Warning\"/>"));
+    assert!(doc_xml.contains("Do not touch the heater."));
+
+    // PPTX: admonition content on the slide
+    let deck = SlideExtractor::extract(&doc);
+    let slide = deck
+        .slides
+        .iter()
+        .find(|slide| slide.title.as_deref() == Some("Safety"))
+        .expect("Safety slide");
+    let content = slide
+        .content
+        .iter()
+        .find_map(|content| match content {
+            SlideContent::Admonition(admonition) => Some(admonition),
+            _ => None,
+        })
+        .expect("Admonition on slide");
+    assert_eq!(
+        content.admonition_type,
+        utf8dok_pptx::slide::AdmonitionType::Warning
+    );
+    assert_eq!(content.title.as_deref(), Some("Hot surface"));
+    assert_eq!(content.content.runs[0].text, "Do not touch the heater.");
+
+    // Typst: labelled block with the title
+    let typst = utf8dok_pdf::Transpiler::transpile(&doc);
+    assert!(typst.contains("*Warning:* *Hot surface*"));
+    assert!(typst.contains("Do not touch the heater."));
+}
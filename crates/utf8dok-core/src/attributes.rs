@@ -0,0 +1,771 @@
+//! Attribute reference substitution
+//!
+//! This module replaces `{name}` references in text with attribute values,
+//! as done by AsciiDoc's attributes substitution.
+//!
+//! # Supported References
+//!
+//! - `{name}`: Document attribute (`:name: value`) or render-time attribute
+//! - Built-in attributes: `{doctitle}`, `{author}`, `{email}`, `{revnumber}`
+//!   and the others set from the document header (see
+//!   [`author_line_attributes`] and [`revision_line_attributes`]),
+//!   `{localdate}`, `{localtime}`, `{localyear}` and the character
+//!   replacement attributes (`{nbsp}`, `{startsb}`, ...)
+//! - `{counter:name}` / `{counter:name:start}`: Increment and insert a counter
+//! - `{counter2:name}`: Increment a counter without inserting it
+//! - `{set:name:value}` / `{set:name!}`: Define or unset an attribute inline
+//! - `\{name}`: Escaped reference, kept literally without the backslash
+//!
+//! # Missing References
+//!
+//! Undefined references are handled according to the `attribute-missing`
+//! attribute (`skip`, `drop`, `drop-line` or `warn`), falling back to the
+//! configured [`AttributeMissing`] mode.
+//!
+//! # Diagnostic Codes
+//!
+//! - `ATTR101`: Reference to an undefined attribute
+//!
+//! # Example
+//!
+//! ```
+//! use utf8dok_core::attributes::AttributeSubstitutor;
+//!
+//! let mut attributes = AttributeSubstitutor::new();
+//! attributes.set("product-name", "utf8dok");
+//!
+//! let text = attributes.substitute("Welcome to {product-name}!");
+//! assert_eq!(text.as_deref(), Some("Welcome to utf8dok!"));
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use chrono::{DateTime, Datelike, FixedOffset, Local};
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Span};
+
+/// Character replacement attributes defined by AsciiDoc
+const CHARACTER_ATTRIBUTES: &[(&str, &str)] = &[
+    ("empty", ""),
+    ("blank", ""),
+    ("sp", " "),
+    ("nbsp", "\u{00a0}"),
+    ("zwsp", "\u{200b}"),
+    ("wj", "\u{2060}"),
+    ("apos", "'"),
+    ("quot", "\""),
+    ("lsquo", "\u{2018}"),
+    ("rsquo", "\u{2019}"),
+    ("ldquo", "\u{201c}"),
+    ("rdquo", "\u{201d}"),
+    ("deg", "\u{00b0}"),
+    ("plus", "+"),
+    ("brvbar", "\u{00a6}"),
+    ("vbar", "|"),
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("startsb", "["),
+    ("endsb", "]"),
+    ("caret", "^"),
+    ("asterisk", "*"),
+    ("tilde", "~"),
+    ("backslash", "\\"),
+    ("backtick", "`"),
+    ("two-colons", "::"),
+    ("two-semicolons", ";;"),
+    ("cpp", "C++"),
+    ("pp", "++"),
+];
+
+/// How to handle references to undefined attributes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttributeMissing {
+    /// Leave the reference as written
+    Skip,
+    /// Remove the reference
+    Drop,
+    /// Remove the whole line containing the reference
+    DropLine,
+    /// Leave the reference as written and report a warning
+    #[default]
+    Warn,
+}
+
+impl AttributeMissing {
+    /// Parse an `attribute-missing` value (`skip`, `drop`, `drop-line`, `warn`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "skip" => Some(Self::Skip),
+            "drop" => Some(Self::Drop),
+            "drop-line" => Some(Self::DropLine),
+            "warn" => Some(Self::Warn),
+            _ => None,
+        }
+    }
+}
+
+/// An attribute entry line (`:name: value`, `:name!:` or `:!name:`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeEntry {
+    /// Attribute name
+    pub name: String,
+    /// Attribute value (`None` when the entry unsets the attribute)
+    pub value: Option<String>,
+}
+
+impl AttributeEntry {
+    /// Parse an attribute entry line
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use utf8dok_core::attributes::AttributeEntry;
+    ///
+    /// let entry = AttributeEntry::parse(":version: 1.2").unwrap();
+    /// assert_eq!(entry.name, "version");
+    /// assert_eq!(entry.value.as_deref(), Some("1.2"));
+    ///
+    /// assert_eq!(AttributeEntry::parse(":draft!:").unwrap().value, None);
+    /// ```
+    pub fn parse(line: &str) -> Option<Self> {
+        let rest = line.strip_prefix(':')?;
+        let colon = rest.find(':')?;
+        let name = &rest[..colon];
+        let value = &rest[colon + 1..];
+        if !value.is_empty() && !value.starts_with(char::is_whitespace) {
+            return None;
+        }
+
+        let (name, unset) = match name.strip_suffix('!').or_else(|| name.strip_prefix('!')) {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        if !is_attribute_name(name) {
+            return None;
+        }
+
+        Some(Self {
+            name: name.to_string(),
+            value: (!unset).then(|| value.trim().to_string()),
+        })
+    }
+}
+
+/// Attributes derived from the author line under the document title
+///
+/// Authors are separated by `;` and written as `First [Middle] Last
+/// [<email>]`, with `_` joining words of a single name. The first author
+/// sets `author`, `firstname`, `middlename`, `lastname`, `authorinitials`
+/// and `email`; the others set the same names suffixed with `_2`, `_3`,
+/// ... and `authors` lists every name.
+///
+/// # Example
+///
+/// ```
+/// use utf8dok_core::attributes::author_line_attributes;
+///
+/// let attributes = author_line_attributes("Jane Q. Doe <jane@example.com>; John Smith");
+/// assert!(attributes.contains(&("lastname".to_string(), "Doe".to_string())));
+/// assert!(attributes.contains(&("email".to_string(), "jane@example.com".to_string())));
+/// assert!(attributes.contains(&("author_2".to_string(), "John Smith".to_string())));
+/// assert!(attributes.contains(&("authors".to_string(), "Jane Q. Doe, John Smith".to_string())));
+/// ```
+pub fn author_line_attributes(line: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut names = Vec::new();
+    let authors = line.split(';').map(str::trim).filter(|a| !a.is_empty());
+    for (index, author) in authors.enumerate() {
+        let (name, email) = match author.strip_suffix('>').and_then(|a| a.rsplit_once('<')) {
+            Some((name, email)) => (name.trim(), Some(email.trim())),
+            None => (author, None),
+        };
+        let suffix = match index {
+            0 => String::new(),
+            _ => format!("_{}", index + 1),
+        };
+        for (key, value) in author_attributes(name, email) {
+            if key == "author" {
+                names.push(value.clone());
+            }
+            attributes.push((format!("{}{}", key, suffix), value));
+        }
+    }
+    if !names.is_empty() {
+        attributes.push(("authors".to_string(), names.join(", ")));
+    }
+    attributes
+}
+
+/// Attributes derived from a single author's name and email
+///
+/// Names of more than three words are kept whole as the first name.
+pub fn author_attributes(name: &str, email: Option<&str>) -> Vec<(&'static str, String)> {
+    let words = name
+        .split_whitespace()
+        .map(|word| word.replace('_', " "))
+        .collect::<Vec<_>>();
+    let (first, middle, last) = match words.as_slice() {
+        [] => return Vec::new(),
+        [first] => (first.clone(), None, None),
+        [first, last] => (first.clone(), None, Some(last.clone())),
+        [first, middle, last] => (first.clone(), Some(middle.clone()), Some(last.clone())),
+        _ => (words.join(" "), None, None),
+    };
+
+    let parts = [Some(&first), middle.as_ref(), last.as_ref()];
+    let author = parts
+        .iter()
+        .flatten()
+        .map(|p| p.as_str())
+        .collect::<Vec<_>>();
+    let initials = parts
+        .iter()
+        .flatten()
+        .filter_map(|part| part.chars().next())
+        .collect::<String>();
+
+    let mut attributes = vec![
+        ("author", author.join(" ")),
+        ("firstname", first),
+        ("authorinitials", initials),
+    ];
+    attributes.extend(middle.map(|middle| ("middlename", middle)));
+    attributes.extend(last.map(|last| ("lastname", last)));
+    attributes.extend(
+        email
+            .filter(|email| !email.is_empty())
+            .map(|email| ("email", email.to_string())),
+    );
+    attributes
+}
+
+/// Attributes derived from the revision line under the author line
+///
+/// `v1.2, 2024-05-01: Draft for review` sets `revnumber`, `revdate` and
+/// `revremark`; each part is optional. A line with neither a version nor a
+/// comma is taken as the date.
+///
+/// # Example
+///
+/// ```
+/// use utf8dok_core::attributes::revision_line_attributes;
+///
+/// assert_eq!(
+///     revision_line_attributes("v1.2, 2024-05-01: Draft"),
+///     vec![
+///         ("revnumber", "1.2".to_string()),
+///         ("revdate", "2024-05-01".to_string()),
+///         ("revremark", "Draft".to_string()),
+///     ]
+/// );
+/// ```
+pub fn revision_line_attributes(line: &str) -> Vec<(&'static str, String)> {
+    let (rest, remark) = match line.split_once(':') {
+        Some((rest, remark)) => (rest, Some(remark.trim())),
+        None => (line, None),
+    };
+    let (number, date) = match rest.split_once(',') {
+        Some((number, date)) => (Some(number), date.trim()),
+        None if is_version(rest.trim()) => (Some(rest), ""),
+        None => (None, rest.trim()),
+    };
+    let number = number
+        .map(|number| {
+            number
+                .trim()
+                .trim_start_matches(|c: char| !c.is_ascii_digit())
+        })
+        .filter(|number| !number.is_empty());
+
+    [
+        ("revnumber", number),
+        ("revdate", Some(date).filter(|date| !date.is_empty())),
+        ("revremark", remark.filter(|remark| !remark.is_empty())),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some((name, value?.to_string())))
+    .collect()
+}
+
+/// Check if `text` is a version number (`v1.2`)
+fn is_version(text: &str) -> bool {
+    let mut chars = text.chars();
+    matches!(chars.next(), Some('v' | 'V')) && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+/// A substituted reference: its range in the input and the range of its
+/// value in the output
+pub(crate) type Replacement = (Range<usize>, Range<usize>);
+
+/// Attribute table and reference substitution
+///
+/// Render-time attributes given to [`AttributeSubstitutor::with_attributes`]
+/// cannot be redefined by the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeSubstitutor {
+    /// Render-time attributes (cannot be overridden by the document)
+    locked: HashMap<String, String>,
+    /// Attributes defined so far (built-ins and document entries)
+    values: HashMap<String, String>,
+    /// Fallback when `attribute-missing` is not set
+    missing: AttributeMissing,
+    /// Warnings for missing references
+    diagnostics: Diagnostics,
+}
+
+impl Default for AttributeSubstitutor {
+    fn default() -> Self {
+        Self::with_attributes(HashMap::new())
+    }
+}
+
+impl AttributeSubstitutor {
+    /// Create a substitutor with only the built-in attributes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a substitutor seeded with render-time attributes
+    pub fn with_attributes(attributes: HashMap<String, String>) -> Self {
+        let (date, time, year) = local_date_time();
+        let values = HashMap::from([
+            ("localdate".to_string(), date.clone()),
+            ("localtime".to_string(), time.clone()),
+            ("localdatetime".to_string(), format!("{} {}", date, time)),
+            ("localyear".to_string(), year),
+            ("docdate".to_string(), date),
+        ]);
+
+        Self {
+            locked: attributes
+                .into_iter()
+                .map(|(name, value)| (name.to_lowercase(), value))
+                .collect(),
+            values,
+            missing: AttributeMissing::default(),
+            diagnostics: Diagnostics::new(),
+        }
+    }
+
+    /// Set the handling of missing references when `attribute-missing` is unset
+    pub fn with_missing(mut self, missing: AttributeMissing) -> Self {
+        self.missing = missing;
+        self
+    }
+
+    /// Define an attribute (ignored for render-time attributes)
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        let name = name.to_lowercase();
+        if !self.locked.contains_key(&name) {
+            self.values.insert(name, value.into());
+        }
+    }
+
+    /// Unset an attribute (ignored for render-time attributes)
+    pub fn unset(&mut self, name: &str) {
+        self.values.remove(&name.to_lowercase());
+    }
+
+    /// Apply an attribute entry
+    pub fn apply(&mut self, entry: &AttributeEntry) {
+        match &entry.value {
+            Some(value) => self.set(&entry.name, value.clone()),
+            None => self.unset(&entry.name),
+        }
+    }
+
+    /// Look up an attribute value
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        self.locked
+            .get(&name)
+            .or_else(|| self.values.get(&name))
+            .map(String::as_str)
+            .or_else(|| {
+                CHARACTER_ATTRIBUTES
+                    .iter()
+                    .find(|(builtin, _)| *builtin == name)
+                    .map(|(_, value)| *value)
+            })
+    }
+
+    /// Effective handling of missing references
+    pub fn missing(&self) -> AttributeMissing {
+        self.get("attribute-missing")
+            .and_then(AttributeMissing::parse)
+            .unwrap_or(self.missing)
+    }
+
+    /// Diagnostics reported so far
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Take the diagnostics reported so far
+    pub fn take_diagnostics(&mut self) -> Diagnostics {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Substitute attribute references in a line of text
+    ///
+    /// Returns `None` when the line must be dropped (`attribute-missing`
+    /// set to `drop-line`). Diagnostic spans are relative to `text`.
+    pub fn substitute(&mut self, text: &str) -> Option<String> {
+        self.substitute_mapped(text).map(|(output, _)| output)
+    }
+
+    /// Substitute attribute references in a line of text, with the
+    /// replacements made
+    ///
+    /// Each replacement pairs the range of a reference in `text` with the
+    /// range of its value in the output, in order.
+    pub(crate) fn substitute_mapped(&mut self, text: &str) -> Option<(String, Vec<Replacement>)> {
+        let mut replacements = Vec::new();
+        if !text.contains('{') {
+            return Some((text.to_string(), replacements));
+        }
+
+        let mut output = String::with_capacity(text.len());
+        let mut consumed = 0;
+        let mut search = 0;
+
+        while let Some(open) = text[search..].find('{').map(|i| search + i) {
+            let Some(close) = text[open..].find('}').map(|i| open + i) else {
+                break;
+            };
+            let reference = &text[open + 1..close];
+            if !is_reference(reference) {
+                search = open + 1;
+                continue;
+            }
+
+            // Escaped reference: drop the backslash, keep the braces
+            if text[..open].ends_with('\\') {
+                output.push_str(&text[consumed..open - 1]);
+                let start = output.len();
+                output.push_str(&text[open..=close]);
+                replacements.push((open - 1..close + 1, start..output.len()));
+                consumed = close + 1;
+                search = consumed;
+                continue;
+            }
+
+            output.push_str(&text[consumed..open]);
+            let start = output.len();
+            match self.resolve(reference) {
+                Some(value) => output.push_str(&value),
+                None => match self.missing() {
+                    AttributeMissing::Skip => output.push_str(&text[open..=close]),
+                    AttributeMissing::Drop => {}
+                    AttributeMissing::DropLine => return None,
+                    AttributeMissing::Warn => {
+                        output.push_str(&text[open..=close]);
+                        self.diagnostics.push(
+                            Diagnostic::warning(format!(
+                                "Missing attribute reference: {{{}}}",
+                                reference
+                            ))
+                            .with_code("ATTR101")
+                            .with_span(Span::new(open, close + 1))
+                            .with_help(format!(
+                                "Define it with `:{}: value`, or escape it as `\\{{{}}}`",
+                                reference, reference
+                            )),
+                        );
+                    }
+                },
+            }
+            replacements.push((open..close + 1, start..output.len()));
+            consumed = close + 1;
+            search = consumed;
+        }
+
+        output.push_str(&text[consumed..]);
+        Some((output, replacements))
+    }
+
+    /// Resolve a reference (without braces) to its replacement text
+    fn resolve(&mut self, reference: &str) -> Option<String> {
+        if let Some(rest) = reference.strip_prefix("counter:") {
+            return Some(self.increment(rest));
+        }
+        if let Some(rest) = reference.strip_prefix("counter2:") {
+            self.increment(rest);
+            return Some(String::new());
+        }
+        if let Some(rest) = reference.strip_prefix("set:") {
+            match rest.split_once(':') {
+                Some((name, value)) => self.set(name, value),
+                None => match rest.strip_suffix('!') {
+                    Some(name) => self.unset(name),
+                    None => self.set(rest, ""),
+                },
+            }
+            return Some(String::new());
+        }
+        self.get(reference).map(str::to_string)
+    }
+
+    /// Increment a counter (`name` or `name:start`) and return its new value
+    fn increment(&mut self, counter: &str) -> String {
+        let (name, start) = match counter.split_once(':') {
+            Some((name, start)) => (name, start),
+            None => (counter, "1"),
+        };
+
+        let next = match self.get(name) {
+            Some(current) => match current.parse::<i64>() {
+                Ok(number) => (number + 1).to_string(),
+                Err(_) => match current.chars().collect::<Vec<_>>().as_slice() {
+                    [c] if c.is_ascii_alphabetic() => char::from(*c as u8 + 1).to_string(),
+                    _ => start.to_string(),
+                },
+            },
+            None => start.to_string(),
+        };
+        self.set(name, next.clone());
+        next
+    }
+}
+
+/// Check if `name` is a valid attribute name
+fn is_attribute_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Check if the text between braces is an attribute reference
+fn is_reference(reference: &str) -> bool {
+    if let Some(rest) = reference
+        .strip_prefix("counter:")
+        .or_else(|| reference.strip_prefix("counter2:"))
+    {
+        let name = rest.split_once(':').map_or(rest, |(name, _)| name);
+        return is_attribute_name(name);
+    }
+    if let Some(rest) = reference.strip_prefix("set:") {
+        let name = rest.split_once(':').map_or(rest, |(name, _)| name);
+        return is_attribute_name(name.strip_suffix('!').unwrap_or(name));
+    }
+    is_attribute_name(reference)
+}
+
+/// Current date, time and year for the `local*` attributes
+///
+/// Honours `SOURCE_DATE_EPOCH` for reproducible output, in UTC. Otherwise
+/// the local time zone is used.
+fn local_date_time() -> (String, String, String) {
+    let epoch = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0));
+    match epoch {
+        Some(time) => format_date_time(time.fixed_offset()),
+        None => format_date_time(Local::now().fixed_offset()),
+    }
+}
+
+/// Date, time and year of `time`
+///
+/// The time names its zone as `UTC` or a `+hhmm` offset.
+fn format_date_time(time: DateTime<FixedOffset>) -> (String, String, String) {
+    let zone = match time.offset().local_minus_utc() {
+        0 => "UTC".to_string(),
+        _ => time.format("%z").to_string(),
+    };
+    (
+        time.format("%Y-%m-%d").to_string(),
+        format!("{} {}", time.format("%H:%M:%S"), zone),
+        time.year().to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute_defined_attribute() {
+        let mut attributes = AttributeSubstitutor::new();
+        attributes.set("product-name", "utf8dok");
+        assert_eq!(
+            attributes.substitute("Use {product-name} and {Product-Name}."),
+            Some("Use utf8dok and utf8dok.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_render_time_attributes_take_precedence() {
+        let locked = HashMap::from([("edition".to_string(), "pro".to_string())]);
+        let mut attributes = AttributeSubstitutor::with_attributes(locked);
+        attributes.set("edition", "community");
+        assert_eq!(attributes.get("edition"), Some("pro"));
+    }
+
+    #[test]
+    fn test_escaped_reference() {
+        let mut attributes = AttributeSubstitutor::new();
+        attributes.set("name", "value");
+        assert_eq!(
+            attributes.substitute(r"Write \{name} to get {name}"),
+            Some("Write {name} to get value".to_string())
+        );
+    }
+
+    #[test]
+    fn test_non_reference_braces_untouched() {
+        let mut attributes = AttributeSubstitutor::new();
+        let text = "fn main() { println!(\"{} {x y}\"); }";
+        assert_eq!(attributes.substitute(text), Some(text.to_string()));
+        assert!(attributes.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_character_attributes() {
+        let mut attributes = AttributeSubstitutor::new();
+        assert_eq!(
+            attributes.substitute("{startsb}x{endsb}{sp}{cpp}"),
+            Some("[x] C++".to_string())
+        );
+    }
+
+    #[test]
+    fn test_counters() {
+        let mut attributes = AttributeSubstitutor::new();
+        assert_eq!(
+            attributes.substitute("{counter:step} {counter:step} {counter2:step}{step}"),
+            Some("1 2 3".to_string())
+        );
+        assert_eq!(
+            attributes.substitute("{counter:appendix:A} {counter:appendix:A}"),
+            Some("A B".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_directive() {
+        let mut attributes = AttributeSubstitutor::new();
+        assert_eq!(
+            attributes.substitute("{set:mode:fast}{mode}{set:mode!}"),
+            Some("fast".to_string())
+        );
+        assert_eq!(attributes.get("mode"), None);
+    }
+
+    #[test]
+    fn test_missing_reference_warns_by_default() {
+        let mut attributes = AttributeSubstitutor::new();
+        assert_eq!(
+            attributes.substitute("Hello {nobody}"),
+            Some("Hello {nobody}".to_string())
+        );
+
+        let diagnostic = attributes.diagnostics().iter().next().unwrap();
+        assert_eq!(diagnostic.code.as_deref(), Some("ATTR101"));
+        assert_eq!(diagnostic.span, Some(Span::new(6, 14)));
+    }
+
+    #[test]
+    fn test_missing_reference_modes() {
+        let mut attributes = AttributeSubstitutor::new().with_missing(AttributeMissing::Skip);
+        assert_eq!(
+            attributes.substitute("a {x} b"),
+            Some("a {x} b".to_string())
+        );
+
+        attributes.set("attribute-missing", "drop");
+        assert_eq!(attributes.substitute("a {x} b"), Some("a  b".to_string()));
+
+        attributes.set("attribute-missing", "drop-line");
+        assert_eq!(attributes.substitute("a {x} b"), None);
+
+        assert!(attributes.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_parse_attribute_entry() {
+        assert_eq!(
+            AttributeEntry::parse(":toc:"),
+            Some(AttributeEntry {
+                name: "toc".to_string(),
+                value: Some(String::new()),
+            })
+        );
+        assert_eq!(AttributeEntry::parse(":!toc:").unwrap().value, None);
+        assert_eq!(AttributeEntry::parse(":not an entry: x"), None);
+        assert_eq!(AttributeEntry::parse("::x"), None);
+        assert_eq!(AttributeEntry::parse(":url:https://example.com"), None);
+    }
+
+    #[test]
+    fn test_author_line() {
+        let attributes = author_line_attributes("Jane Q. Doe <jane@example.com>; Mary_Ann Smith");
+        let get = |name: &str| {
+            attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(get("author"), Some("Jane Q. Doe"));
+        assert_eq!(get("firstname"), Some("Jane"));
+        assert_eq!(get("middlename"), Some("Q."));
+        assert_eq!(get("lastname"), Some("Doe"));
+        assert_eq!(get("authorinitials"), Some("JQD"));
+        assert_eq!(get("email"), Some("jane@example.com"));
+        assert_eq!(get("author_2"), Some("Mary Ann Smith"));
+        assert_eq!(get("firstname_2"), Some("Mary Ann"));
+        assert_eq!(get("email_2"), None);
+        assert_eq!(get("authors"), Some("Jane Q. Doe, Mary Ann Smith"));
+    }
+
+    #[test]
+    fn test_revision_line() {
+        assert_eq!(
+            revision_line_attributes("v2.0"),
+            vec![("revnumber", "2.0".to_string())]
+        );
+        assert_eq!(
+            revision_line_attributes("Version 3, May 2024"),
+            vec![
+                ("revnumber", "3".to_string()),
+                ("revdate", "May 2024".to_string())
+            ]
+        );
+        assert_eq!(
+            revision_line_attributes("2024-05-01"),
+            vec![("revdate", "2024-05-01".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_format_date_time() {
+        let time = |offset: i32| {
+            let zone = FixedOffset::east_opt(offset).unwrap();
+            DateTime::parse_from_rfc3339("2024-01-01T23:30:00Z")
+                .unwrap()
+                .with_timezone(&zone)
+        };
+        assert_eq!(
+            format_date_time(time(0)),
+            (
+                "2024-01-01".to_string(),
+                "23:30:00 UTC".to_string(),
+                "2024".to_string()
+            )
+        );
+        assert_eq!(
+            format_date_time(time(2 * 3600)),
+            (
+                "2024-01-02".to_string(),
+                "01:30:00 +0200".to_string(),
+                "2024".to_string()
+            )
+        );
+        assert_eq!(format_date_time(time(-5 * 3600 - 1800)).1, "18:00:00 -0530");
+    }
+}
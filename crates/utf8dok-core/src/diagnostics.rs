@@ -3,6 +3,8 @@
 //! This module provides structures for reporting errors, warnings, and
 //! informational messages during document compilation.
 
+use std::ops::Range;
+
 use serde::{Deserialize, Serialize};
 
 /// A diagnostic message from the compiler
@@ -234,6 +236,12 @@ impl Span {
     }
 }
 
+impl From<utf8dok_ast::SourceSpan> for Span {
+    fn from(span: utf8dok_ast::SourceSpan) -> Self {
+        Span::new(span.start, span.end).with_position(span.line, span.column)
+    }
+}
+
 impl std::fmt::Display for Severity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -280,7 +288,7 @@ impl std::fmt::Display for Diagnostic {
 }
 
 /// A collection of diagnostics
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Diagnostics {
     /// List of diagnostics
     diagnostics: Vec<Diagnostic>,
@@ -336,6 +344,16 @@ impl Diagnostics {
     pub fn len(&self) -> usize {
         self.diagnostics.len()
     }
+
+    /// Replace the diagnostics in `range` with `diagnostics`
+    pub(crate) fn splice(&mut self, range: Range<usize>, diagnostics: Diagnostics) {
+        self.diagnostics.splice(range, diagnostics.diagnostics);
+    }
+
+    /// The diagnostics from `start` on, for adjusting in place
+    pub(crate) fn tail_mut(&mut self, start: usize) -> &mut [Diagnostic] {
+        &mut self.diagnostics[start..]
+    }
 }
 
 impl IntoIterator for Diagnostics {
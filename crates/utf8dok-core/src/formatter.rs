@@ -0,0 +1,1025 @@
+//! AsciiDoc Source Formatter
+//!
+//! Rewrites AsciiDoc source into a canonical layout without going through
+//! the AST, so everything the parser does not model (comments,
+//! conditionals, unknown macros) is kept exactly as written. Only layout
+//! changes, never what the parser reads from the source:
+//!
+//! - one sentence per line in paragraphs, list items and admonitions
+//! - one space between list markers and the item text, and `*` for `-` bullets
+//! - columns of simple tables aligned
+//! - delimiters four characters long, one more per enclosing block of the
+//!   same kind
+//! - attribute entries of the document header sorted by name
+//! - trailing whitespace removed and runs of blank lines collapsed
+//!
+//! Verbatim content (listing, literal, passthrough, comment and verse blocks,
+//! literal paragraphs, styled paragraphs such as `[source]` and
+//! `%hardbreaks`) is copied unchanged, as are indented lines. Formatting is
+//! idempotent, so `format(format(s)) == format(s)`.
+//!
+//! # Example
+//!
+//! ```
+//! use utf8dok_core::formatter::format;
+//!
+//! let source = "= Doc\n:toc:\n:author: Jane\n\nFirst sentence. Second one.\n\n*   item\n";
+//! assert_eq!(
+//!     format(source),
+//!     "= Doc\n:author: Jane\n:toc:\n\nFirst sentence.\nSecond one.\n\n* item\n"
+//! );
+//! ```
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::parser::{
+    callout_item, description_item, is_comment_delimiter, is_literal_delimiter, numbered_item,
+    ordered_item, unordered_item, DIAGRAM_TYPES,
+};
+
+/// Formatter configuration
+///
+/// Read from the `[format]` table of `utf8dok.toml`; every rule is on by
+/// default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatConfig {
+    /// Put each sentence of a paragraph on its own line
+    pub sentence_per_line: bool,
+    /// Write list markers followed by a single space, with `*` for `-`
+    pub normalize_lists: bool,
+    /// Pad the cells of simple tables so columns line up
+    pub align_tables: bool,
+    /// Write block delimiters with their canonical length
+    pub normalize_delimiters: bool,
+    /// Sort the attribute entries of the document header by name
+    pub sort_attributes: bool,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            sentence_per_line: true,
+            normalize_lists: true,
+            align_tables: true,
+            normalize_delimiters: true,
+            sort_attributes: true,
+        }
+    }
+}
+
+/// Format AsciiDoc source with the default configuration
+pub fn format(source: &str) -> String {
+    format_with_config(source, &FormatConfig::default())
+}
+
+/// Format AsciiDoc source with a custom configuration
+pub fn format_with_config(source: &str, config: &FormatConfig) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut formatter = Formatter::new(config);
+    let body = formatter.header(&lines);
+    formatter.body(&lines[body..]);
+    formatter.finish()
+}
+
+/// Styles whose paragraphs and blocks are copied verbatim
+const VERBATIM_STYLES: [&str; 8] = [
+    "source",
+    "listing",
+    "literal",
+    "pass",
+    "stem",
+    "latexmath",
+    "asciimath",
+    "verse",
+];
+
+/// A delimited block open at the current line
+struct Frame {
+    /// Delimiter as written, which closes the block
+    delimiter: String,
+    /// Delimiter as the formatter writes it
+    canonical: String,
+    /// Whether the content is copied unchanged
+    verbatim: bool,
+    /// Whether a verbatim style copies the text of the block unchanged; the
+    /// parser still reads the blocks nested in it
+    styled: bool,
+}
+
+impl Frame {
+    /// Whether `line` closes the block
+    fn closed_by(&self, line: &str) -> bool {
+        // The parser ends a listing block at any run of dashes
+        line == self.delimiter
+            || (self.delimiter != "--"
+                && self.delimiter.starts_with('-')
+                && is_literal_delimiter(line))
+    }
+}
+
+/// Lines of a paragraph collected before they are reflowed
+struct Paragraph {
+    /// List marker or admonition label written before the first line
+    prefix: String,
+    lines: Vec<String>,
+}
+
+struct Formatter<'a> {
+    config: &'a FormatConfig,
+    out: Vec<String>,
+    frames: Vec<Frame>,
+    paragraph: Option<Paragraph>,
+    /// Whether the next block was styled to be verbatim
+    verbatim_style: bool,
+    /// Whether a literal or styled paragraph is being copied
+    verbatim_paragraph: bool,
+    /// Whether the header sets `:hardbreaks-option:`
+    hardbreaks: bool,
+    /// Whether the document ends in a table or comment block without a
+    /// closing delimiter, whose blank lines are content
+    unclosed: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(config: &'a FormatConfig) -> Self {
+        Self {
+            config,
+            out: Vec::new(),
+            frames: Vec::new(),
+            paragraph: None,
+            verbatim_style: false,
+            verbatim_paragraph: false,
+            hardbreaks: false,
+            unclosed: false,
+        }
+    }
+
+    /// Format the document header, returning the index of the first body line
+    fn header(&mut self, lines: &[&str]) -> usize {
+        let mut index = comment_lines(lines);
+        self.out.extend(
+            lines[..index]
+                .iter()
+                .map(|line| line.trim_end().to_string()),
+        );
+        // Only an unclosed comment block takes in a trailing blank line
+        self.unclosed = self.out.last().is_some_and(|line| line.is_empty());
+        // Attribute entries above a title stay in place
+        loop {
+            let mut title = index;
+            while let Some(line) = lines.get(title) {
+                match comment_lines(&lines[title..]) {
+                    0 if attribute_entry_name(line).is_some() => title += 1,
+                    0 => break,
+                    comments => title += comments,
+                }
+            }
+            if !lines.get(title).is_some_and(|line| line.starts_with("= ")) {
+                break;
+            }
+            self.out.extend(
+                lines[index..title]
+                    .iter()
+                    .map(|line| line.trim_end().to_string()),
+            );
+            index = title;
+
+            // Author and revision lines, up to two after each title line
+            let mut after_title = 0;
+            loop {
+                // Comments do not take the place of a header line
+                let next = index + comment_lines(&lines[index..]);
+                let Some(line) = lines.get(next) else {
+                    break;
+                };
+                let title = line.starts_with("= ");
+                let header_line = after_title < 2
+                    && !line.trim().is_empty()
+                    && !line.starts_with(':')
+                    && !line.starts_with("//");
+                if !title && !header_line {
+                    break;
+                }
+                after_title = if title { 0 } else { after_title + 1 };
+                self.out.extend(
+                    lines[index..=next]
+                        .iter()
+                        .map(|line| line.trim_end().to_string()),
+                );
+                index = next + 1;
+            }
+        }
+
+        // Attribute entries, each with the comments above it
+        let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+        let mut comments = Vec::new();
+        while let Some(line) = lines.get(index) {
+            let line = line.trim_end();
+            if is_line_comment(line) {
+                comments.push(line.to_string());
+            } else if let Some(name) = attribute_entry_name(line) {
+                comments.push(line.to_string());
+                let mut continued = line.ends_with(" \\");
+                while continued {
+                    let Some(next) = lines.get(index + 1) else {
+                        break;
+                    };
+                    comments.push(next.trim_end().to_string());
+                    continued = next.trim_end().ends_with(" \\");
+                    index += 1;
+                }
+                entries.push((name, std::mem::take(&mut comments)));
+            } else {
+                break;
+            }
+            index += 1;
+        }
+
+        self.hardbreaks = entries
+            .iter()
+            .any(|(name, _)| name == "hardbreaks-option" || name == "hardbreaks");
+        if self.config.sort_attributes && !references_entries(&entries) {
+            entries.sort_by_cached_key(|(name, _)| name.to_lowercase());
+        }
+        self.out
+            .extend(entries.into_iter().flat_map(|(_, lines)| lines));
+        self.out.extend(comments);
+        index
+    }
+
+    fn body(&mut self, lines: &[&str]) {
+        let mut index = 0;
+        while index < lines.len() {
+            let line = lines[index];
+            index += 1;
+            let trimmed = line.trim_end();
+
+            if let Some(frame) = self.frames.last() {
+                if frame.verbatim {
+                    if frame.closed_by(trimmed) {
+                        self.close_frame();
+                    } else {
+                        self.out.push(line.to_string());
+                    }
+                    continue;
+                }
+                if frame.styled
+                    && !is_block_delimiter(trimmed)
+                    && table_delimiter(trimmed).is_none()
+                {
+                    self.out.push(line.to_string());
+                    continue;
+                }
+            }
+            // The parser ends even literal paragraphs at delimiters
+            if self.verbatim_paragraph {
+                if !trimmed.is_empty()
+                    && !is_block_delimiter(trimmed)
+                    && table_delimiter(trimmed).is_none()
+                {
+                    self.out.push(line.to_string());
+                    continue;
+                }
+                self.verbatim_paragraph = false;
+            }
+
+            // A blank first line ends the header before it starts
+            if trimmed.is_empty() {
+                self.flush();
+                if self.out.last().is_none_or(|last| !last.is_empty()) {
+                    self.out.push(String::new());
+                }
+                continue;
+            }
+            if is_line_comment(trimmed) {
+                match self.paragraph.as_mut() {
+                    Some(paragraph) => paragraph.lines.push(trimmed.to_string()),
+                    None => self.out.push(trimmed.to_string()),
+                }
+                continue;
+            }
+            if let Some(delimiter) = table_delimiter(trimmed) {
+                let end = lines[index..]
+                    .iter()
+                    .position(|line| line.trim_end() == trimmed)
+                    .map(|offset| index + offset);
+                let rows = &lines[index..end.unwrap_or(lines.len())];
+                self.table(trimmed, delimiter, rows, end.is_some());
+                index = end.map_or(lines.len(), |end| end + 1);
+                continue;
+            }
+            if is_block_delimiter(trimmed) {
+                self.delimiter(trimmed);
+                continue;
+            }
+            self.line(line, trimmed);
+        }
+        self.flush();
+    }
+
+    /// Handle a line that is neither blank, a comment nor a delimiter
+    fn line(&mut self, line: &str, trimmed: &str) {
+        if trimmed == "+" {
+            self.flush();
+            self.out.push(trimmed.to_string());
+            return;
+        }
+        if let Some(paragraph) = self.paragraph.as_mut() {
+            // List items start a paragraph of their own, as they end the
+            // parser's; anything else continues this one
+            if !starts_item(trimmed) {
+                paragraph.lines.push(trimmed.to_string());
+                return;
+            }
+            self.flush();
+        }
+
+        if is_preprocessor_directive(trimmed) || is_block_title(trimmed) {
+            self.out.push(trimmed.to_string());
+            return;
+        }
+        if is_block_attribute_line(trimmed) {
+            self.verbatim_style |= is_verbatim_style(trimmed);
+            self.out.push(trimmed.to_string());
+            return;
+        }
+        if is_block_line(trimmed) {
+            self.begin_block();
+            self.out.push(trimmed.to_string());
+            return;
+        }
+        if let Some((marker, text)) = list_item(trimmed) {
+            let prefix = match self.config.normalize_lists {
+                true => format!("{} ", marker),
+                false => trimmed[..trimmed.len() - text.len()].to_string(),
+            };
+            self.start_paragraph(prefix, text);
+            return;
+        }
+        if let Some(text) = callout_item(trimmed) {
+            self.start_paragraph(item_prefix(trimmed, text), text);
+            return;
+        }
+        if let Some((_, _, text)) = description_item(trimmed) {
+            self.begin_block();
+            // A term without a definition keeps its line; the definition
+            // lines below it are read with it
+            match text.is_empty() {
+                true => self.start_paragraph(String::new(), trimmed),
+                false => self.start_paragraph(item_prefix(trimmed, text), text),
+            }
+            return;
+        }
+        if let Some((label, text)) = admonition(trimmed) {
+            self.begin_block();
+            self.start_paragraph(format!("{}: ", label), text);
+            return;
+        }
+
+        let verbatim = self.verbatim_style || line.starts_with(char::is_whitespace);
+        self.begin_block();
+        if verbatim {
+            self.verbatim_paragraph = true;
+            self.out.push(line.to_string());
+        } else {
+            self.start_paragraph(String::new(), trimmed);
+        }
+    }
+
+    /// Start a block, which takes the style of the attribute lines above it
+    fn begin_block(&mut self) {
+        self.verbatim_style = false;
+    }
+
+    fn start_paragraph(&mut self, prefix: String, text: &str) {
+        self.verbatim_style = false;
+        self.paragraph = Some(Paragraph {
+            prefix,
+            lines: vec![text.to_string()],
+        });
+    }
+
+    /// Write out the open paragraph, one sentence per line
+    ///
+    /// The parser reads list item text a line at a time, so the sentences
+    /// are only reflowed when the parser reads them as it reads the lines
+    /// as written.
+    fn flush(&mut self) {
+        let Some(paragraph) = self.paragraph.take() else {
+            return;
+        };
+        let mut lines = paragraph.lines.clone();
+        lines[0].insert_str(0, &paragraph.prefix);
+        if self.config.sentence_per_line && !self.hardbreaks {
+            let reflowed = reflow(&paragraph);
+            if reflowed != lines && parses_alike(&lines, &reflowed) {
+                lines = reflowed;
+            }
+        }
+        self.out.extend(lines);
+    }
+
+    fn delimiter(&mut self, delimiter: &str) {
+        self.flush();
+        if self
+            .frames
+            .last()
+            .is_some_and(|frame| frame.delimiter == delimiter)
+        {
+            self.close_frame();
+            return;
+        }
+
+        let verbatim = matches!(&delimiter[..1], "-" | "." | "+" | "/") && delimiter != "--";
+        let styled = !verbatim
+            && (self.verbatim_style || self.frames.last().is_some_and(|frame| frame.styled));
+        // Verbatim content may hold a line that would close the block under
+        // a shorter delimiter, except listing content, which cannot hold
+        // a run of dashes
+        let keep = verbatim && !is_literal_delimiter(delimiter);
+        let canonical = if delimiter == "--" || keep || !self.config.normalize_delimiters {
+            delimiter.to_string()
+        } else {
+            let c = &delimiter[..1];
+            let depth = self
+                .frames
+                .iter()
+                .filter(|frame| frame.delimiter != "--" && frame.delimiter.starts_with(c))
+                .count();
+            c.repeat(4 + depth)
+        };
+        self.begin_block();
+        self.out.push(canonical.clone());
+        self.frames.push(Frame {
+            delimiter: delimiter.to_string(),
+            canonical,
+            verbatim,
+            styled,
+        });
+    }
+
+    fn close_frame(&mut self) {
+        let frame = self.frames.pop().expect("frame is open");
+        self.out.push(frame.canonical);
+        self.verbatim_style = false;
+    }
+
+    /// Write a table, aligning its columns when every cell is simple
+    fn table(&mut self, delimiter: &str, separator: char, rows: &[&str], closed: bool) {
+        self.flush();
+        self.begin_block();
+        let canonical = if self.config.normalize_delimiters {
+            format!("{}===", separator)
+        } else {
+            delimiter.to_string()
+        };
+        self.out.push(canonical.clone());
+
+        let aligned = if self.config.align_tables && closed {
+            align_table(rows, separator)
+        } else {
+            None
+        };
+        match aligned {
+            Some(rows) => self.out.extend(rows),
+            None => self
+                .out
+                .extend(rows.iter().map(|row| row.trim_end().to_string())),
+        }
+        if closed {
+            self.out.push(canonical);
+        }
+        self.unclosed = !closed;
+    }
+
+    fn finish(mut self) -> String {
+        // Blank lines at the end of an unclosed verbatim block, comment block
+        // or table are content
+        while self.out.last().is_some_and(|line| line.is_empty())
+            && !self.unclosed
+            && !self.frames.last().is_some_and(|frame| frame.verbatim)
+        {
+            self.out.pop();
+        }
+        if self.out.is_empty() {
+            return String::new();
+        }
+        let mut text = self.out.join("\n");
+        text.push('\n');
+        text
+    }
+}
+
+/// The lines of a paragraph with one sentence per line
+///
+/// Lines that would read as markup at the start of a line (comments, list
+/// items, directives) and indented lines keep a line of their own.
+fn reflow(paragraph: &Paragraph) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut text = String::new();
+    for line in &paragraph.lines {
+        if looks_like_markup(line) || line.starts_with(char::is_whitespace) {
+            push_sentences(&mut lines, &text);
+            lines.push(line.clone());
+            text.clear();
+            continue;
+        }
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(line);
+        // Hard line breaks (` +`) end a line whatever follows them
+        if line.ends_with(" +") {
+            push_sentences(&mut lines, &text);
+            text.clear();
+        }
+    }
+    push_sentences(&mut lines, &text);
+    if let Some(first) = lines.first_mut() {
+        first.insert_str(0, &paragraph.prefix);
+    }
+    lines
+}
+
+/// Whether the parser reads two layouts of a paragraph alike: on its own,
+/// continuing a list item and attached to one, after which the parser goes
+/// back to reading the item a line at a time
+fn parses_alike(lines: &[String], other: &[String]) -> bool {
+    let blocks = |before: &str, lines: &[String]| {
+        crate::parse(&format!("{}{}", before, lines.join("\n")))
+            .ok()
+            .map(|doc| doc.blocks)
+    };
+    ["", "* item\n", "* item\n+\n"]
+        .iter()
+        .all(|before| blocks(before, lines) == blocks(before, other))
+}
+
+fn push_sentences(lines: &mut Vec<String>, text: &str) {
+    if !text.is_empty() {
+        lines.extend(split_sentences(text).into_iter().map(str::to_string));
+    }
+}
+
+/// Pad the cells of a table so its columns line up
+///
+/// Only tables written one row per line, with every cell separated by
+/// `| ` and no cell specifiers, are aligned; anything else could change
+/// meaning when padded.
+fn align_table(rows: &[&str], separator: char) -> Option<Vec<String>> {
+    let mut cells: Vec<Option<Vec<&str>>> = Vec::new();
+    for row in rows {
+        let row = row.trim_end();
+        if row.is_empty() {
+            cells.push(None);
+            continue;
+        }
+        let mut parts = row.split(separator);
+        if !parts.next()?.is_empty() {
+            return None;
+        }
+        let parts: Vec<&str> = parts.collect();
+        // A cell not followed by a space may end in a cell specifier
+        // (`2+|`, `a|`) or an escaped separator
+        if parts[..parts.len() - 1]
+            .iter()
+            .any(|cell| !cell.ends_with(' '))
+        {
+            return None;
+        }
+        cells.push(Some(parts.iter().map(|cell| cell.trim()).collect()));
+    }
+
+    let columns = cells.iter().flatten().next()?.len();
+    if cells.iter().flatten().any(|row| row.len() != columns) {
+        return None;
+    }
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            cells
+                .iter()
+                .flatten()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    Some(
+        cells
+            .into_iter()
+            .map(|row| {
+                let Some(row) = row else {
+                    return String::new();
+                };
+                let mut line = String::new();
+                for (cell, width) in row.iter().zip(&widths) {
+                    line.push(separator);
+                    line.push(' ');
+                    line.push_str(cell);
+                    line.extend(std::iter::repeat_n(' ', width - cell.chars().count() + 1));
+                }
+                line.trim_end().to_string()
+            })
+            .collect(),
+    )
+}
+
+/// Split paragraph text into sentences
+///
+/// A sentence ends at `.`, `?` or `!` followed by a single space and a
+/// capital letter, outside of brackets, cross references and code. Abbreviations
+/// and initials do not end sentences, and text that would read as markup
+/// at the start of a line stays with the sentence before it.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut brackets = 0usize;
+    let mut xrefs = 0usize;
+    let mut code = false;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '[' => brackets += 1,
+            ']' => brackets = brackets.saturating_sub(1),
+            '<' if text[index..].starts_with("<<") => xrefs += 1,
+            '>' if text[index..].starts_with(">>") => xrefs = xrefs.saturating_sub(1),
+            '`' => code = !code,
+            '.' | '?' | '!' if brackets == 0 && xrefs == 0 && !code => {
+                let mut end = index + 1;
+                while let Some(&(next, closing)) = chars.peek() {
+                    if !matches!(closing, ')' | '"' | '\'' | '*' | '_' | '”' | '’') {
+                        break;
+                    }
+                    end = next + closing.len_utf8();
+                    chars.next();
+                }
+                let rest = &text[end..];
+                let next = rest.trim_start();
+                if rest.len() - next.len() != 1
+                    || !rest.starts_with(' ')
+                    || !starts_sentence(next)
+                    || (c == '.' && ends_abbreviation(&text[start..index]))
+                    || looks_like_markup(next)
+                {
+                    continue;
+                }
+                sentences.push(&text[start..end]);
+                start = text.len() - next.len();
+            }
+            _ => {}
+        }
+    }
+    if start < text.len() || sentences.is_empty() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
+/// Whether text starts with a capital letter, possibly after quotes or
+/// formatting marks
+fn starts_sentence(text: &str) -> bool {
+    text.trim_start_matches(['*', '_', '`', '"', '\'', '(', '“', '‘'])
+        .chars()
+        .next()
+        .is_some_and(char::is_uppercase)
+}
+
+/// Whether text before a period ends with an abbreviation or an initial
+fn ends_abbreviation(text: &str) -> bool {
+    const ABBREVIATIONS: [&str; 16] = [
+        "e.g", "i.e", "etc", "vs", "mr", "mrs", "ms", "dr", "prof", "st", "no", "fig", "cf",
+        "approx", "ca", "al",
+    ];
+    if text.ends_with('.') {
+        // Ellipsis
+        return true;
+    }
+    let word = text
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches(['(', '"', '\'', '*', '_']);
+    let mut letters = word.chars();
+    match (letters.next(), letters.next()) {
+        (Some(letter), None) if letter.is_alphabetic() => true,
+        _ => ABBREVIATIONS.contains(&word.to_lowercase().as_str()),
+    }
+}
+
+/// Whether a line starting with this text would be read as markup
+fn looks_like_markup(text: &str) -> bool {
+    const PREFIXES: [&str; 10] = ["=", "#", "[", ".", "//", ":", "|", "!", "+", "<"];
+    PREFIXES.iter().any(|prefix| text.starts_with(prefix))
+        || list_item(text).is_some()
+        || admonition(text).is_some()
+        || is_block_line(text)
+        || is_block_delimiter(text)
+        || description_item(text).is_some()
+}
+
+fn is_line_comment(line: &str) -> bool {
+    line.starts_with("//") && !line.trim_end().chars().all(|c| c == '/')
+}
+
+/// Number of lines at the start of `lines` taken by comment lines and
+/// comment blocks
+fn comment_lines(lines: &[&str]) -> usize {
+    let mut count = 0;
+    while let Some(line) = lines.get(count) {
+        let line = line.trim_end();
+        if is_line_comment(line) {
+            count += 1;
+        } else if is_comment_delimiter(line) {
+            let Some(end) = lines[count + 1..]
+                .iter()
+                .position(|next| next.trim_end() == line)
+            else {
+                return lines.len();
+            };
+            count += end + 2;
+        } else {
+            break;
+        }
+    }
+    count
+}
+
+/// Name of the attribute an entry sets or unsets (`:name:`, `:!name:`)
+fn attribute_entry_name(line: &str) -> Option<String> {
+    static ENTRY: OnceLock<Regex> = OnceLock::new();
+    let entry = ENTRY.get_or_init(|| {
+        Regex::new(r"^:!?([A-Za-z0-9_][A-Za-z0-9_-]*)!?:(\s|$)").expect("valid regex")
+    });
+    entry.captures(line).map(|captures| captures[1].to_string())
+}
+
+/// Whether an entry value refers to another header attribute, which pins
+/// the order of the entries
+fn references_entries(entries: &[(String, Vec<String>)]) -> bool {
+    entries.iter().any(|(_, lines)| {
+        entries.iter().any(|(name, _)| {
+            let reference = format!("{{{}}}", name);
+            lines
+                .iter()
+                .any(|line| !is_line_comment(line) && line.contains(&reference))
+        })
+    })
+}
+
+/// Delimiters the parser reads
+fn is_block_delimiter(line: &str) -> bool {
+    line == "--"
+        || (line.len() >= 4
+            && ['-', '.', '=', '*', '_', '+', '/']
+                .iter()
+                .any(|&c| line.chars().all(|l| l == c)))
+}
+
+/// Separator of a table opened by this line (`|===`, `!===`, `,===`, `:===`)
+fn table_delimiter(line: &str) -> Option<char> {
+    let separator = line.chars().next()?;
+    let rest = &line[separator.len_utf8()..];
+    (matches!(separator, '|' | '!' | ',' | ':')
+        && rest.len() >= 3
+        && rest.chars().all(|c| c == '='))
+    .then_some(separator)
+}
+
+fn is_preprocessor_directive(line: &str) -> bool {
+    ["ifdef::", "ifndef::", "ifeval::", "endif::", "include::"]
+        .iter()
+        .any(|directive| line.starts_with(directive))
+}
+
+/// Block attribute lists and anchors (`[source,rust]`, `[[id]]`, `[#id]`)
+fn is_block_attribute_line(line: &str) -> bool {
+    line.starts_with('[') && line.ends_with(']')
+}
+
+fn is_block_title(line: &str) -> bool {
+    let mut chars = line.chars();
+    chars.next() == Some('.') && chars.next().is_some_and(|c| !c.is_whitespace() && c != '.')
+}
+
+/// Lines that are blocks on their own: headings, attribute entries,
+/// breaks and block macros
+fn is_block_line(line: &str) -> bool {
+    static MACRO: OnceLock<Regex> = OnceLock::new();
+    let block_macro =
+        MACRO.get_or_init(|| Regex::new(r"^[A-Za-z][\w-]*::\S*\[.*\]$").expect("valid regex"));
+    let heading = line.trim_start_matches(['=', '#']);
+    (heading.len() < line.len() && line.len() - heading.len() <= 6 && heading.starts_with(' '))
+        || attribute_entry_name(line).is_some()
+        || matches!(line, "'''" | "---" | "***" | "<<<" | "- - -" | "* * *")
+        || block_macro.is_match(line)
+}
+
+/// Split a list item line into its normalized marker and text, as the
+/// parser reads them: `*` and `.` repeated by nesting depth, `-` as `*`,
+/// and explicit numbers (`1.`, `a.`, `i)`) as written
+fn list_item(line: &str) -> Option<(&str, &str)> {
+    if let Some((_, _, text)) = numbered_item(line) {
+        let (marker, _) = line.split_once(' ')?;
+        return Some((marker, text));
+    }
+    let (level, text) = unordered_item(line).or_else(|| ordered_item(line))?;
+    match line.starts_with('-') {
+        true => Some(("*", text)),
+        false => Some((&line[..=level], text)),
+    }
+}
+
+/// Whether a line starts a list item, which the parser reads a line at a
+/// time until the next item
+fn starts_item(line: &str) -> bool {
+    list_item(line).is_some() || callout_item(line).is_some() || description_item(line).is_some()
+}
+
+/// The marker of a callout or description list item line ending in `text`,
+/// with a single space after it
+fn item_prefix(line: &str, text: &str) -> String {
+    format!("{} ", line[..line.len() - text.len()].trim_end())
+}
+
+/// Split an admonition paragraph into its label and text
+fn admonition(line: &str) -> Option<(&str, &str)> {
+    let (label, text) = line.split_once(": ")?;
+    matches!(label, "NOTE" | "TIP" | "IMPORTANT" | "WARNING" | "CAUTION")
+        .then(|| (label, text.trim()))
+}
+
+/// Whether a block attribute line styles the next block to be verbatim
+fn is_verbatim_style(line: &str) -> bool {
+    let attributes = line.trim_start_matches('[').trim_end_matches(']');
+    if line.starts_with("[[") {
+        return false;
+    }
+    let first = attributes.split(',').next().unwrap_or_default().trim();
+    let style = first
+        .split(['#', '.', '%'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    VERBATIM_STYLES.contains(&style.as_str())
+        || DIAGRAM_TYPES.contains(&style.as_str())
+        || first.contains("%hardbreaks")
+        || attributes.contains("options=hardbreaks")
+        || attributes.contains("opts=hardbreaks")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentence_per_line() {
+        let source = "Tools like cURL, e.g. the CLI, work. Dr. Smith agrees!\n\
+                      See [the guide. It helps] and <<intro,Sec. One>>. Then `a. B` ends.\n";
+        assert_eq!(
+            format(source),
+            "Tools like cURL, e.g. the CLI, work.\nDr. Smith agrees!\n\
+             See [the guide. It helps] and <<intro,Sec. One>>.\nThen `a. B` ends.\n"
+        );
+    }
+
+    #[test]
+    fn test_split_keeps_markup_off_line_starts() {
+        assert_eq!(
+            split_sentences(
+                "Use the dot. .NET ships. NOTE: not a label. Term:: here. Then A. B. Smith left."
+            ),
+            [
+                "Use the dot. .NET ships. NOTE: not a label. Term:: here.",
+                "Then A. B. Smith left."
+            ]
+        );
+        assert_eq!(split_sentences("Hard break. +"), ["Hard break. +"]);
+    }
+
+    #[test]
+    fn test_hard_breaks_are_kept() {
+        assert_eq!(
+            format("Roses are red. +\nViolets are blue.\n"),
+            "Roses are red. +\nViolets are blue.\n"
+        );
+        let source = "= Doc\n:hardbreaks-option:\n\nOne. Two.\nThree\n";
+        assert_eq!(format(source), source);
+    }
+
+    #[test]
+    fn test_list_markers() {
+        let source = "*   one. Two\n**  nested\n.   first\n..  sub\n\n\
+                      1. first\n2.  second\n\na. alpha\ni) roman\n\n- dash\n\n\
+                      * x\n- y\n\n  ** indented\n";
+        assert_eq!(
+            format(source),
+            "* one.\nTwo\n** nested\n. first\n.. sub\n\n\
+             1. first\n2. second\n\na. alpha\ni) roman\n\n* dash\n\n\
+             * x\n* y\n\n  ** indented\n"
+        );
+    }
+
+    #[test]
+    fn test_list_ends_at_unattached_block() {
+        let source = "* a\n+\n--\n* inside\n--\n* b\n\nText.\n\n* c\n";
+        assert_eq!(format(source), source);
+    }
+
+    #[test]
+    fn test_delimiters() {
+        let source = "======\nOuter.\n\n=======\nInner.\n=======\n======\n\n\
+                      ______\nQuote. Two.\n______\n";
+        assert_eq!(
+            format(source),
+            "====\nOuter.\n\n=====\nInner.\n=====\n====\n\n____\nQuote.\nTwo.\n____\n"
+        );
+    }
+
+    #[test]
+    fn test_verbatim_content_is_untouched() {
+        let source = "------\nfn main() {   \n\n\n}\n------\n\n\
+                      [verse]\n____\nOne. Two.\n____\n\n\
+                      [source,rust]\nlet a = 1. B;\n\n  literal. Paragraph.\n\n\
+                      ////\nComment. Block.\n////\n\n\
+                      ....\nLiteral.  Block.\n  kept\n....\n";
+        let formatted = format(source);
+        assert!(formatted.contains("----\nfn main() {   \n\n\n}\n----\n"));
+        assert!(formatted.contains("____\nOne. Two.\n____\n"));
+        assert!(formatted.contains("[source,rust]\nlet a = 1. B;\n"));
+        assert!(formatted.contains("\n  literal. Paragraph.\n"));
+        assert!(formatted.contains("////\nComment. Block.\n////\n"));
+        assert!(formatted.contains("....\nLiteral.  Block.\n  kept\n....\n"));
+    }
+
+    #[test]
+    fn test_table_alignment() {
+        let source = "|===\n|Name |Value\n\n| a | long value\n|===\n";
+        assert_eq!(
+            format(source),
+            "|===\n| Name | Value\n\n| a    | long value\n|===\n"
+        );
+
+        // Cell specifiers keep the table as written
+        let source = "|===\n2+| span\n| a | b\n|===\n";
+        assert_eq!(format(source), source);
+    }
+
+    #[test]
+    fn test_header_attributes_sorted() {
+        let source =
+            "// top\n= Doc\nJane Doe\n:toc: left\n// icons\n:icons: font\n:!sectids:\n\nBody.\n";
+        assert_eq!(
+            format(source),
+            "// top\n= Doc\nJane Doe\n// icons\n:icons: font\n:!sectids:\n:toc: left\n\nBody.\n"
+        );
+
+        // References pin the order
+        let source = "= Doc\n:product: utf8dok\n:docname: {product} guide\n";
+        assert_eq!(format(source), source);
+    }
+
+    #[test]
+    fn test_markup_lines_in_paragraphs_stay_put() {
+        let source = "* item. Next\n// note\nmore. Text\n** sub\n\nPara\n* not ours\nend.\n";
+        assert_eq!(
+            format(source),
+            "* item.\nNext\n// note\nmore.\nText\n** sub\n\nPara\n* not ours end.\n"
+        );
+    }
+
+    #[test]
+    fn test_whitespace_and_comments() {
+        let source = "CLI:: Command line. Runs tools.\n\nPara one.   \n// keep me  \ncontinued.\n\n\n\nPara two.\n\n\n";
+        assert_eq!(
+            format(source),
+            "CLI:: Command line.\nRuns tools.\n\nPara one.\n// keep me\ncontinued.\n\nPara two.\n"
+        );
+    }
+
+    #[test]
+    fn test_disabled_rules() {
+        let config = FormatConfig {
+            sentence_per_line: false,
+            normalize_lists: false,
+            align_tables: false,
+            normalize_delimiters: false,
+            sort_attributes: false,
+        };
+        let source = "= Doc\n:b: 1\n:a: 2\n\nOne. Two.\n\n- item\n\n======\nX\n======\n";
+        assert_eq!(format_with_config(source, &config), source);
+    }
+
+    #[test]
+    fn test_idempotent() {
+        let source = "= Doc\n:z: 1\n:a: 2\n\n== Section\n\nOne. Two. Three.\n\
+                      - a\n  - b\n\n|===\n|x |yy\n|===\n\nNOTE: Note one. Note two.\n";
+        let once = format(source);
+        assert_eq!(format(&once), once);
+    }
+}
@@ -14,6 +14,7 @@
 //!     text: vec![Inline::Text("My Title".to_string())],
 //!     style_id: None,
 //!     anchor: None,
+//!     role: None,
 //! }));
 //!
 //! let asciidoc = generate(&doc);
@@ -23,10 +24,12 @@
 use std::fmt::Write;
 
 use utf8dok_ast::{
-    Admonition, AdmonitionType, Block, BreakType, Document, FormatType, Heading, Inline, List,
-    ListItem, ListType, LiteralBlock, Paragraph, Table,
+    Admonition, AdmonitionType, Block, BreakType, CellStyle, ColumnSpec, Document, FormatType,
+    Heading, Inline, List, ListItem, ListType, LiteralBlock, Paragraph, Table, TableCell,
 };
 
+use crate::table::CellSpec;
+
 /// AsciiDoc generator configuration
 #[derive(Debug, Clone, Default)]
 pub struct GeneratorConfig {
@@ -40,6 +43,12 @@ pub struct GeneratorConfig {
 pub struct AsciiDocGenerator {
     config: GeneratorConfig,
     output: String,
+    /// Delimiters of the compound blocks being generated, innermost last
+    open_delimiters: Vec<String>,
+    /// Number of tables being generated (nested tables use `!`)
+    table_depth: usize,
+    /// Generating a verse, whose line breaks need no ` +` marker
+    in_verse: bool,
 }
 
 impl AsciiDocGenerator {
@@ -48,6 +57,9 @@ impl AsciiDocGenerator {
         Self {
             config: GeneratorConfig::default(),
             output: String::new(),
+            open_delimiters: Vec::new(),
+            table_depth: 0,
+            in_verse: false,
         }
     }
 
@@ -56,6 +68,9 @@ impl AsciiDocGenerator {
         Self {
             config,
             output: String::new(),
+            open_delimiters: Vec::new(),
+            table_depth: 0,
+            in_verse: false,
         }
     }
 
@@ -73,14 +88,23 @@ impl AsciiDocGenerator {
                     writeln!(self.output, "{}", doc.metadata.authors.join("; ")).unwrap();
                 }
 
-                // Add revision
+                // Add revision (the revision line needs an author line
+                // before it)
                 if let Some(ref rev) = doc.metadata.revision {
-                    writeln!(self.output, "v{}", rev).unwrap();
+                    if !doc.metadata.authors.is_empty() {
+                        writeln!(self.output, "v{}", rev).unwrap();
+                    } else if !doc.metadata.attributes.contains_key("revnumber") {
+                        writeln!(self.output, ":revnumber: {}", rev).unwrap();
+                    }
                 }
 
                 // Add attributes
                 for (key, value) in &doc.metadata.attributes {
-                    writeln!(self.output, ":{}: {}", key, value).unwrap();
+                    if value.is_empty() {
+                        writeln!(self.output, ":{}:", key).unwrap();
+                    } else {
+                        writeln!(self.output, ":{}: {}", key, value).unwrap();
+                    }
                 }
 
                 writeln!(self.output).unwrap();
@@ -99,6 +123,35 @@ impl AsciiDocGenerator {
         self.output.trim_end().to_string()
     }
 
+    /// Generate the blocks of a compound block, separated by blank lines
+    fn generate_blocks(&mut self, blocks: &[Block]) {
+        for (i, block) in blocks.iter().enumerate() {
+            if i > 0 {
+                writeln!(self.output).unwrap();
+            }
+            self.generate_block(block);
+        }
+    }
+
+    /// Generate a compound block's content between delimiter lines
+    ///
+    /// Blocks nested in a block of the same kind get a longer delimiter.
+    fn generate_delimited(&mut self, delimiter: &str, blocks: &[Block]) {
+        let mut delimiter = delimiter.to_string();
+        if delimiter.len() >= 4 {
+            let marker = delimiter.chars().next().unwrap_or('=');
+            while self.open_delimiters.contains(&delimiter) {
+                delimiter.push(marker);
+            }
+        }
+
+        writeln!(self.output, "{}", delimiter).unwrap();
+        self.open_delimiters.push(delimiter.clone());
+        self.generate_blocks(blocks);
+        self.open_delimiters.pop();
+        writeln!(self.output, "{}", delimiter).unwrap();
+    }
+
     /// Generate a single block
     fn generate_block(&mut self, block: &Block) {
         match block {
@@ -113,6 +166,7 @@ impl AsciiDocGenerator {
             Block::Sidebar(sidebar) => self.generate_sidebar(sidebar),
             Block::Quote(quote) => self.generate_quote(quote),
             Block::ThematicBreak => self.generate_thematic_break(),
+            Block::Comment(comment) => self.generate_comment(comment),
         }
     }
 
@@ -124,6 +178,9 @@ impl AsciiDocGenerator {
                 writeln!(self.output, "[[{}]]", anchor).unwrap();
             }
         }
+        if let Some(role) = &heading.role {
+            self.generate_roles(role);
+        }
 
         // Heading prefix: == for level 1, === for level 2, etc.
         // AsciiDoc uses = for doc title (level 0), == for section level 1, etc.
@@ -140,12 +197,25 @@ impl AsciiDocGenerator {
 
     /// Generate a paragraph
     fn generate_paragraph(&mut self, para: &Paragraph) {
+        // Optional title (e.g., a figure caption)
+        if let Some(title) = para.attributes.get("title") {
+            writeln!(self.output, ".{}", title).unwrap();
+        }
+        if let Some(role) = para.attributes.get("role") {
+            self.generate_roles(role);
+        }
         for inline in &para.inlines {
             self.generate_inline(inline);
         }
         writeln!(self.output).unwrap();
     }
 
+    /// Generate the block attribute line of space-separated roles
+    fn generate_roles(&mut self, roles: &str) {
+        let roles: Vec<&str> = roles.split_whitespace().collect();
+        writeln!(self.output, "[.{}]", roles.join(".")).unwrap();
+    }
+
     /// Generate inline content
     fn generate_inline(&mut self, inline: &Inline) {
         match inline {
@@ -197,6 +267,7 @@ impl AsciiDocGenerator {
                 }
                 write!(self.output, "]").unwrap();
             }
+            Inline::Break if self.in_verse => writeln!(self.output).unwrap(),
             Inline::Break => {
                 writeln!(self.output, " +").unwrap();
             }
@@ -204,56 +275,97 @@ impl AsciiDocGenerator {
                 // Generate inline anchor: [[name]]
                 write!(self.output, "[[{}]]", name).unwrap();
             }
+            Inline::Footnote(footnote) => {
+                // Generate footnote macro: footnote:id[text]
+                write!(self.output, "footnote:").unwrap();
+                if let Some(ref id) = footnote.id {
+                    write!(self.output, "{}", id).unwrap();
+                }
+                // Closing brackets inside the text must be escaped
+                let start = self.output.len();
+                for inner in &footnote.content {
+                    self.generate_inline(inner);
+                }
+                let text = self.output.split_off(start);
+                write!(self.output, "[{}]", text.replace(']', "\\]")).unwrap();
+            }
         }
     }
 
     /// Generate a list
     fn generate_list(&mut self, list: &List) {
-        for item in &list.items {
-            self.generate_list_item(item, &list.list_type);
+        // Explicit numbering style and start: [loweralpha,start=3]
+        let mut attributes: Vec<String> = list
+            .numbering
+            .map(|numbering| numbering.as_style().to_string())
+            .into_iter()
+            .collect();
+        if let Some(start) = list.start {
+            attributes.push(format!("start={}", start));
+        }
+        if !attributes.is_empty() {
+            writeln!(self.output, "[{}]", attributes.join(",")).unwrap();
+        }
+        for (index, item) in list.items.iter().enumerate() {
+            self.generate_list_item(item, &list.list_type, index);
         }
     }
 
-    /// Generate a list item
-    fn generate_list_item(&mut self, item: &ListItem, list_type: &ListType) {
+    /// Generate a list item (`index` numbers callout list items)
+    fn generate_list_item(&mut self, item: &ListItem, list_type: &ListType, index: usize) {
         // Generate marker based on type and level
-        let marker = match list_type {
-            ListType::Unordered => "*".repeat(item.level as usize + 1),
-            ListType::Ordered => ".".repeat(item.level as usize + 1),
+        match list_type {
+            ListType::Unordered => {
+                write!(self.output, "{}", "*".repeat(item.level as usize + 1)).unwrap()
+            }
+            ListType::Ordered => {
+                write!(self.output, "{}", ".".repeat(item.level as usize + 1)).unwrap()
+            }
             ListType::Description => {
-                // For description lists, handle term separately
+                // For description lists, the term precedes the marker
                 if let Some(ref term) = item.term {
                     for inline in term {
                         self.generate_inline(inline);
                     }
-                    write!(self.output, ":: ").unwrap();
                 }
-                String::new()
+                let marker = match item.level {
+                    0 => "::",
+                    1 => ":::",
+                    2 => "::::",
+                    _ => ";;",
+                };
+                write!(self.output, "{}", marker).unwrap();
             }
-        };
+            ListType::Callout => write!(self.output, "<{}>", index + 1).unwrap(),
+        }
 
-        if !marker.is_empty() {
-            write!(self.output, "{} ", marker).unwrap();
+        // Checklist state
+        match item.checked {
+            Some(true) => write!(self.output, " [x]").unwrap(),
+            Some(false) => write!(self.output, " [ ]").unwrap(),
+            None => {}
         }
 
-        // Generate item content
+        // Generate item content; only a leading paragraph shares the marker line
         for (i, block) in item.content.iter().enumerate() {
-            if i > 0 {
-                // Continuation for multi-block list items
-                writeln!(self.output, "+").unwrap();
-            }
             match block {
-                Block::Paragraph(p) => {
-                    for inline in &p.inlines {
-                        self.generate_inline(inline);
-                    }
-                    writeln!(self.output).unwrap();
+                Block::Paragraph(p) if i == 0 => {
+                    write!(self.output, " ").unwrap();
+                    self.generate_paragraph(p);
                 }
                 _ => {
+                    if i == 0 {
+                        writeln!(self.output).unwrap();
+                    }
+                    // Continuation for multi-block list items
+                    writeln!(self.output, "+").unwrap();
                     self.generate_block(block);
                 }
             }
         }
+        if item.content.is_empty() {
+            writeln!(self.output).unwrap();
+        }
     }
 
     /// Generate a table
@@ -273,30 +385,102 @@ impl AsciiDocGenerator {
             writeln!(self.output).unwrap();
         }
 
-        // Check if first row is header
-        let has_header = table.rows.first().map(|r| r.is_header).unwrap_or(false);
-        if has_header {
-            writeln!(self.output, "[%header]").unwrap();
+        // Tables of plain cells keep one cell per line and rows separated by
+        // blank lines; others need a column spec to delimit rows
+        let simple = table.columns.is_empty()
+            && table
+                .rows
+                .iter()
+                .all(|row| !row.is_footer && row.cells.iter().all(is_plain_cell));
+
+        let first_row = &table.rows[0];
+        let mut options = String::new();
+        if first_row.is_header {
+            options.push_str("%header");
+        } else if !simple && first_row.cells.len() == 1 {
+            // A lone first cell followed by a blank line is an implicit header
+            options.push_str("%noheader");
+        }
+        if table.rows.last().is_some_and(|row| row.is_footer) {
+            options.push_str("%footer");
+        }
+        let mut attributes: Vec<String> = Vec::new();
+        if !options.is_empty() {
+            attributes.push(options);
+        }
+        if !simple {
+            let cols = if table.columns.is_empty() {
+                let width: u32 = first_row.cells.iter().map(|cell| cell.colspan).sum();
+                vec!["1"; width as usize].join(",")
+            } else {
+                table
+                    .columns
+                    .iter()
+                    .map(column_spec)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            };
+            attributes.push(format!("cols=\"{}\"", cols));
+        }
+        if !attributes.is_empty() {
+            writeln!(self.output, "[{}]", attributes.join(",")).unwrap();
         }
 
-        writeln!(self.output, "|===").unwrap();
+        // Tables nested in AsciiDoc cells use `!` as separator
+        let separator = if self.table_depth > 0 { '!' } else { '|' };
+        writeln!(self.output, "{}===", separator).unwrap();
+        self.table_depth += 1;
 
         for row in &table.rows {
             for cell in &row.cells {
-                write!(self.output, "| ").unwrap();
+                let spec = if simple {
+                    String::new()
+                } else {
+                    CellSpec::to_spec_string(cell.colspan, cell.rowspan, cell.align, cell.style)
+                };
+                let content = self.generate_cell_content(cell);
+                let content = content.replace(separator, &format!("\\{}", separator));
+                match cell.style {
+                    Some(CellStyle::AsciiDoc | CellStyle::Literal) => {
+                        writeln!(self.output, "{}{}\n{}", spec, separator, content).unwrap()
+                    }
+                    _ => writeln!(self.output, "{}{} {}", spec, separator, content).unwrap(),
+                }
+            }
+            writeln!(self.output).unwrap();
+        }
+
+        self.table_depth -= 1;
+        writeln!(self.output, "{}===", separator).unwrap();
+    }
+
+    /// Generate the text of a table cell
+    fn generate_cell_content(&mut self, cell: &TableCell) -> String {
+        let outer = std::mem::take(&mut self.output);
+        match cell.style {
+            Some(CellStyle::AsciiDoc) => self.generate_blocks(&cell.content),
+            Some(CellStyle::Literal) => {
                 for block in &cell.content {
+                    if let Block::Literal(literal) = block {
+                        writeln!(self.output, "{}", literal.content).unwrap();
+                    }
+                }
+            }
+            _ => {
+                for (i, block) in cell.content.iter().enumerate() {
                     if let Block::Paragraph(p) = block {
+                        if i > 0 {
+                            write!(self.output, "\n\n").unwrap();
+                        }
                         for inline in &p.inlines {
                             self.generate_inline(inline);
                         }
                     }
                 }
-                writeln!(self.output).unwrap();
             }
-            writeln!(self.output).unwrap();
         }
-
-        writeln!(self.output, "|===").unwrap();
+        let content = std::mem::replace(&mut self.output, outer);
+        content.trim_end().to_string()
     }
 
     /// Generate an admonition
@@ -319,13 +503,7 @@ impl AsciiDocGenerator {
         }
 
         writeln!(self.output, "[{}]", label).unwrap();
-        writeln!(self.output, "====").unwrap();
-
-        for block in &admonition.content {
-            self.generate_block(block);
-        }
-
-        writeln!(self.output, "====").unwrap();
+        self.generate_delimited("====", &admonition.content);
     }
 
     /// Generate a literal/code block
@@ -335,19 +513,58 @@ impl AsciiDocGenerator {
             writeln!(self.output, ".{}", title).unwrap();
         }
 
+        // Passthrough and literal content is written back between ++++
+        // and .... delimiters
+        let delimiter = match literal.style_id.as_deref() {
+            Some("pass") => Some("++++"),
+            Some("literal") if literal.language.is_none() => Some("...."),
+            _ => None,
+        };
+        if let Some(delimiter) = delimiter {
+            writeln!(self.output, "{}", delimiter).unwrap();
+            writeln!(self.output, "{}", literal.content).unwrap();
+            writeln!(self.output, "{}", delimiter).unwrap();
+            return;
+        }
+
         // Block attribute: [style_id] or [source,language]
         if let Some(ref style) = literal.style_id {
             // Diagram or custom style: [mermaid], [plantuml], etc.
             writeln!(self.output, "[{}]", style).unwrap();
-        } else if let Some(ref lang) = literal.language {
-            // Source code block: [source,rust]
-            writeln!(self.output, "[source,{}]", lang).unwrap();
+        } else if literal.language.is_some() || !literal.highlight_lines.is_empty() {
+            // Source code block: [source,rust] or [source,rust,highlight=2..4]
+            write!(self.output, "[source").unwrap();
+            if let Some(ref lang) = literal.language {
+                write!(self.output, ",{}", lang).unwrap();
+            }
+            if !literal.highlight_lines.is_empty() {
+                write!(
+                    self.output,
+                    ",highlight=\"{}\"",
+                    format_line_ranges(&literal.highlight_lines)
+                )
+                .unwrap();
+            }
+            writeln!(self.output, "]").unwrap();
         }
 
         writeln!(self.output, "----").unwrap();
-        write!(self.output, "{}", literal.content).unwrap();
-        if !literal.content.ends_with('\n') {
-            writeln!(self.output).unwrap();
+        if literal.callouts.is_empty() {
+            write!(self.output, "{}", literal.content).unwrap();
+            if !literal.content.ends_with('\n') {
+                writeln!(self.output).unwrap();
+            }
+        } else {
+            // Callout markers go back to the end of their lines
+            for (index, line) in literal.content.split('\n').enumerate() {
+                write!(self.output, "{}", line).unwrap();
+                for callout in &literal.callouts {
+                    if callout.line as usize == index + 1 {
+                        write!(self.output, " <{}>", callout.number).unwrap();
+                    }
+                }
+                writeln!(self.output).unwrap();
+            }
         }
         writeln!(self.output, "----").unwrap();
     }
@@ -362,21 +579,23 @@ impl AsciiDocGenerator {
 
     /// Generate an open block
     fn generate_open_block(&mut self, open: &utf8dok_ast::OpenBlock) {
-        // Role attribute
-        if let Some(ref role) = open.role {
-            writeln!(self.output, "[{}]", role).unwrap();
-        }
-
         // Optional title
         if let Some(ref title) = open.title {
             writeln!(self.output, ".{}", title).unwrap();
         }
 
-        writeln!(self.output, "--").unwrap();
-        for block in &open.blocks {
-            self.generate_block(block);
+        // Example blocks have their own delimiter
+        if open.role.as_deref() == Some("example") {
+            self.generate_delimited("====", &open.blocks);
+            return;
+        }
+
+        // Role attribute
+        if let Some(ref role) = open.role {
+            writeln!(self.output, "[{}]", role).unwrap();
         }
-        writeln!(self.output, "--").unwrap();
+
+        self.generate_delimited("--", &open.blocks);
     }
 
     /// Generate a sidebar block
@@ -386,35 +605,61 @@ impl AsciiDocGenerator {
             writeln!(self.output, ".{}", title).unwrap();
         }
 
-        writeln!(self.output, "****").unwrap();
-        for block in &sidebar.blocks {
-            self.generate_block(block);
-        }
-        writeln!(self.output, "****").unwrap();
+        self.generate_delimited("****", &sidebar.blocks);
     }
 
     /// Generate a quote block
     fn generate_quote(&mut self, quote: &utf8dok_ast::QuoteBlock) {
-        write!(self.output, "[quote").unwrap();
-        if let Some(ref attribution) = quote.attribution {
-            write!(self.output, ", {}", attribution).unwrap();
+        // Optional title
+        if let Some(ref title) = quote.title {
+            writeln!(self.output, ".{}", title).unwrap();
         }
-        if let Some(ref cite) = quote.cite {
-            write!(self.output, ", {}", cite).unwrap();
+
+        // [quote, attribution, cite] or [verse, ...] (values with commas
+        // are quoted)
+        let quoted = |value: &Option<String>| match value.as_deref() {
+            Some(v) if v.contains(',') => format!("\"{}\"", v),
+            Some(v) => v.to_string(),
+            None => String::new(),
+        };
+        let style = if quote.verse { "verse" } else { "quote" };
+        write!(self.output, "[{}", style).unwrap();
+        if quote.attribution.is_some() || quote.cite.is_some() {
+            write!(self.output, ", {}", quoted(&quote.attribution)).unwrap();
+        }
+        if quote.cite.is_some() {
+            write!(self.output, ", {}", quoted(&quote.cite)).unwrap();
         }
         writeln!(self.output, "]").unwrap();
 
-        writeln!(self.output, "____").unwrap();
-        for block in &quote.blocks {
-            self.generate_block(block);
-        }
-        writeln!(self.output, "____").unwrap();
+        let in_verse = std::mem::replace(&mut self.in_verse, quote.verse);
+        self.generate_delimited("____", &quote.blocks);
+        self.in_verse = in_verse;
     }
 
     /// Generate a thematic break
     fn generate_thematic_break(&mut self) {
         writeln!(self.output, "'''").unwrap();
     }
+
+    /// Generate a comment as `//` lines or a `////` block
+    fn generate_comment(&mut self, comment: &utf8dok_ast::CommentBlock) {
+        if comment.delimited {
+            writeln!(self.output, "////").unwrap();
+            for line in comment.text.lines() {
+                writeln!(self.output, "{}", line).unwrap();
+            }
+            writeln!(self.output, "////").unwrap();
+            return;
+        }
+        for line in comment.text.split('\n') {
+            if line.is_empty() {
+                writeln!(self.output, "//").unwrap();
+            } else {
+                writeln!(self.output, "// {}", line).unwrap();
+            }
+        }
+    }
 }
 
 impl Default for AsciiDocGenerator {
@@ -435,6 +680,48 @@ pub fn generate_with_config(doc: &Document, config: GeneratorConfig) -> String {
     generator.generate(doc)
 }
 
+/// Format sorted line numbers as ranges (`1,3..5`)
+fn format_line_ranges(lines: &[u32]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let start = lines[i];
+        while i + 1 < lines.len() && lines[i + 1] == lines[i] + 1 {
+            i += 1;
+        }
+        if lines[i] == start {
+            ranges.push(start.to_string());
+        } else {
+            ranges.push(format!("{}..{}", start, lines[i]));
+        }
+        i += 1;
+    }
+    ranges.join(",")
+}
+
+/// Whether a table cell can be written without a cell spec
+fn is_plain_cell(cell: &TableCell) -> bool {
+    cell.colspan <= 1
+        && cell.rowspan <= 1
+        && cell.align.is_none()
+        && cell.style.is_none()
+        && cell.content.len() <= 1
+        && cell
+            .content
+            .iter()
+            .all(|block| matches!(block, Block::Paragraph(_)))
+}
+
+/// Write a column spec for the `cols` attribute: `^2a`
+fn column_spec(column: &ColumnSpec) -> String {
+    let mut spec = CellSpec::to_spec_string(1, 1, column.align, None);
+    spec.push_str(&column.width.unwrap_or(1).to_string());
+    if let Some(style) = column.style {
+        spec.push(style.as_char());
+    }
+    spec
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,6 +736,7 @@ mod tests {
             text: vec![Inline::Text("My Title".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
 
         let output = generate(&doc);
@@ -464,18 +752,21 @@ mod tests {
             text: vec![Inline::Text("Level 1".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
         doc.push(Block::Heading(Heading {
             level: 2,
             text: vec![Inline::Text("Level 2".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
         doc.push(Block::Heading(Heading {
             level: 3,
             text: vec![Inline::Text("Level 3".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
 
         let output = generate(&doc);
@@ -580,6 +871,7 @@ mod tests {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
                 ListItem {
                     content: vec![Block::Paragraph(Paragraph {
@@ -589,9 +881,12 @@ mod tests {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
             ],
             style_id: None,
+            numbering: None,
+            start: None,
         }));
 
         let output = generate(&doc);
@@ -613,6 +908,7 @@ mod tests {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
                 ListItem {
                     content: vec![Block::Paragraph(Paragraph {
@@ -622,9 +918,12 @@ mod tests {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
             ],
             style_id: None,
+            numbering: None,
+            start: None,
         }));
 
         let output = generate(&doc);
@@ -646,6 +945,7 @@ mod tests {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
                 ListItem {
                     content: vec![Block::Paragraph(Paragraph {
@@ -655,9 +955,12 @@ mod tests {
                     })],
                     level: 1,
                     term: None,
+                    checked: None,
                 },
             ],
             style_id: None,
+            numbering: None,
+            start: None,
         }));
 
         let output = generate(&doc);
@@ -673,6 +976,8 @@ mod tests {
             language: Some("rust".to_string()),
             title: None,
             style_id: None,
+            callouts: Vec::new(),
+            highlight_lines: Vec::new(),
         }));
 
         let output = generate(&doc);
@@ -713,6 +1018,22 @@ mod tests {
         assert_eq!(output, "image::logo.png[Company Logo]");
     }
 
+    #[test]
+    fn test_image_with_title() {
+        let mut doc = Document::new();
+        doc.push(Block::Paragraph(Paragraph {
+            inlines: vec![Inline::Image(utf8dok_ast::Image {
+                src: "arch.png".to_string(),
+                alt: None,
+            })],
+            style_id: None,
+            attributes: HashMap::from([("title".to_string(), "Architecture".to_string())]),
+        }));
+
+        let output = generate(&doc);
+        assert_eq!(output, ".Architecture\nimage::arch.png[]");
+    }
+
     #[test]
     fn test_page_break() {
         let mut doc = Document::new();
@@ -761,6 +1082,7 @@ mod tests {
             text: vec![Inline::Text("Getting Started".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
 
         // Paragraph with bold text
@@ -789,6 +1111,7 @@ mod tests {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
                 ListItem {
                     content: vec![Block::Paragraph(Paragraph {
@@ -798,9 +1121,12 @@ mod tests {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
             ],
             style_id: None,
+            numbering: None,
+            start: None,
         }));
 
         let output = generate(&doc);
@@ -836,6 +1162,7 @@ This is *important* information.
                 attributes: HashMap::new(),
             })],
             intent: None,
+            ..Default::default()
         };
 
         let config = GeneratorConfig {
@@ -866,6 +1193,7 @@ This is *important* information.
                             colspan: 1,
                             rowspan: 1,
                             align: None,
+                            style: None,
                         },
                         TableCell {
                             content: vec![Block::Paragraph(Paragraph {
@@ -876,9 +1204,11 @@ This is *important* information.
                             colspan: 1,
                             rowspan: 1,
                             align: None,
+                            style: None,
                         },
                     ],
                     is_header: true,
+                    is_footer: false,
                 },
                 TableRow {
                     cells: vec![
@@ -891,6 +1221,7 @@ This is *important* information.
                             colspan: 1,
                             rowspan: 1,
                             align: None,
+                            style: None,
                         },
                         TableCell {
                             content: vec![Block::Paragraph(Paragraph {
@@ -901,9 +1232,11 @@ This is *important* information.
                             colspan: 1,
                             rowspan: 1,
                             align: None,
+                            style: None,
                         },
                     ],
                     is_header: false,
+                    is_footer: false,
                 },
             ],
             style_id: None,
@@ -0,0 +1,197 @@
+//! Section ids shared by the output formats
+//!
+//! Every writer names a heading the same way, so a cross reference to an
+//! implicit section id (`<<_setup>>`) resolves in the HTML, DOCX, DocBook
+//! and JATS output alike. A heading takes its own anchor, or the `[[id]]`
+//! anchor on the line above it, and otherwise an Asciidoctor-style id
+//! generated from its text that no other id of the document uses.
+//!
+//! Anchors are returned as written; writers that rename them (through a
+//! style contract, or to a valid XML id) do so themselves.
+
+use std::collections::HashSet;
+
+use utf8dok_ast::{Block, Document, Inline, Node, NodePath};
+
+use crate::markdown::plain_text;
+
+/// Where the id of a heading comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdSource {
+    /// The heading's own anchor
+    Anchor,
+    /// An anchor-only paragraph right above the heading
+    AnchorAbove,
+    /// Generated from the heading text
+    Generated,
+}
+
+/// The id of a heading
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionId {
+    /// Path of the heading
+    pub path: NodePath,
+    /// The id
+    pub id: String,
+    /// Where the id comes from
+    pub source: IdSource,
+}
+
+impl SectionId {
+    /// Index of the heading in `Document::blocks`, if it is a top-level block
+    pub fn block(&self) -> Option<usize> {
+        match self.path.steps() {
+            [index] => Some(*index),
+            _ => None,
+        }
+    }
+}
+
+/// The id of every heading of `doc`, in document order
+pub fn section_ids(doc: &Document) -> Vec<SectionId> {
+    // Anchors are reserved so generated ids never collide with them
+    let mut used = HashSet::new();
+    for block in &doc.blocks {
+        collect_anchors(Node::Block(block), &mut used);
+    }
+    let mut ids = Vec::new();
+    let blocks = doc.blocks.iter().map(Node::Block).collect();
+    visit(blocks, &NodePath::default(), &mut used, &mut ids);
+    ids
+}
+
+/// Assign ids to the headings among `nodes` and their descendants
+fn visit(
+    nodes: Vec<Node<'_>>,
+    parent: &NodePath,
+    used: &mut HashSet<String>,
+    ids: &mut Vec<SectionId>,
+) {
+    let mut above: Option<&str> = None;
+    for (index, node) in nodes.into_iter().enumerate() {
+        let path = parent.child(index);
+        if let Node::Block(Block::Heading(heading)) = node {
+            let (id, source) = match (&heading.anchor, above) {
+                (Some(anchor), _) => (anchor.clone(), IdSource::Anchor),
+                (None, Some(anchor)) => (anchor.to_string(), IdSource::AnchorAbove),
+                (None, None) => (
+                    unique_id(&heading_slug(&heading.text), used),
+                    IdSource::Generated,
+                ),
+            };
+            ids.push(SectionId {
+                path: path.clone(),
+                id,
+                source,
+            });
+        }
+        above = match node {
+            Node::Block(block) => anchor_only(block),
+            _ => None,
+        };
+        visit(node.children(), &path, used, ids);
+    }
+}
+
+/// Add the name of every anchor in `node` and its descendants to `used`
+fn collect_anchors(node: Node<'_>, used: &mut HashSet<String>) {
+    match node {
+        Node::Inline(Inline::Anchor(name)) => {
+            used.insert(name.clone());
+        }
+        Node::Block(Block::Heading(heading)) => {
+            if let Some(anchor) = &heading.anchor {
+                used.insert(anchor.clone());
+            }
+        }
+        _ => {}
+    }
+    for child in node.children() {
+        collect_anchors(child, used);
+    }
+}
+
+/// The anchor of a paragraph consisting of a single anchor
+pub fn anchor_only(block: &Block) -> Option<&str> {
+    match block {
+        Block::Paragraph(p) => match p.inlines.as_slice() {
+            [Inline::Anchor(name)] => Some(name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// An Asciidoctor-style id for heading text: `_` followed by the lowercase
+/// words joined with `_`
+pub fn heading_slug(text: &[Inline]) -> String {
+    let mut slug = String::from("_");
+    for c in plain_text(text).chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    let trimmed = slug.trim_end_matches('_');
+    if trimmed.is_empty() {
+        "_section".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// `base`, or `base_2`, `base_3`... if already used; records the result
+pub fn unique_id(base: &str, used: &mut HashSet<String>) -> String {
+    let mut id = base.to_string();
+    let mut n = 2;
+    while used.contains(&id) {
+        id = format!("{}_{}", base, n);
+        n += 1;
+    }
+    used.insert(id.clone());
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn ids(source: &str) -> Vec<(Vec<usize>, String, IdSource)> {
+        section_ids(&parse(source).unwrap())
+            .into_iter()
+            .map(|section| (section.path.0, section.id, section.source))
+            .collect()
+    }
+
+    #[test]
+    fn test_section_ids() {
+        let source = "= Doc\n\n== Getting Started!\n\n[[custom]]\n== Custom\n\n\
+                      == Getting started\n\n--\n== Inner\n--\n\n== _section\n\n== ...\n";
+        assert_eq!(
+            ids(source),
+            [
+                (vec![0], "_getting_started".to_string(), IdSource::Generated),
+                (vec![2], "custom".to_string(), IdSource::AnchorAbove),
+                (
+                    vec![3],
+                    "_getting_started_2".to_string(),
+                    IdSource::Generated
+                ),
+                (vec![4, 0], "_inner".to_string(), IdSource::Generated),
+                (vec![5], "_section".to_string(), IdSource::Generated),
+                (vec![6], "_section_2".to_string(), IdSource::Generated),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_anchors_are_reserved() {
+        let source = "= Doc\n\nSee [[_setup]]here.\n\n== Setup\n";
+        assert_eq!(
+            ids(source),
+            [(vec![1], "_setup_2".to_string(), IdSource::Generated)]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 //! Include directive parsing and resolution.
 //!
-//! This module handles `include::` directives for data files (Excel, CSV, TSV).
+//! This module handles `include::` directives for data files (Excel, CSV, TSV)
+//! and for text files (`.adoc` chapters, source files). Data files resolve to
+//! tables; text files are inlined by the [`crate::preprocessor`].
 //!
 //! # Syntax
 //!
@@ -8,6 +10,8 @@
 //! include::path/to/file.xlsx[sheet=Sheet1,range=A1:C10,header]
 //! include::data.csv[range=1:100,header]
 //! include::data.tsv[header]
+//! include::chapter.adoc[leveloffset=+1,tags=summary]
+//! include::example.rs[lines=5..20;30..-1]
 //! ```
 //!
 //! # Attributes
@@ -16,6 +20,9 @@
 //! - `range=A1:C10` - Cell/row range (defaults to all data)
 //! - `header` - Treat first row as header
 //! - `delimiter=;` - Field delimiter (CSV only)
+//! - `leveloffset=+1` - Shift section levels of an included text file
+//! - `lines=5..20` - Line ranges of a text file (`;`-separated, `-1` = last line)
+//! - `tag=NAME` / `tags=a;b` - Regions between `tag::NAME[]` and `end::NAME[]`
 //!
 //! # Example
 //!
@@ -44,10 +51,89 @@ pub struct IncludeDirective {
     pub header: bool,
     /// Field delimiter (for CSV files)
     pub delimiter: Option<char>,
+    /// Section level offset (for text files)
+    pub leveloffset: Option<LevelOffset>,
+    /// Line ranges to include (for text files)
+    pub lines: Vec<LineRange>,
+    /// Tagged regions to include (for text files)
+    pub tags: Vec<String>,
     /// Additional attributes
     pub attributes: HashMap<String, String>,
 }
 
+/// Section level offset of a text include
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelOffset {
+    /// Relative to the including document (`leveloffset=+1`, `leveloffset=-1`)
+    Relative(i32),
+    /// Absolute offset (`leveloffset=2`)
+    Absolute(i32),
+}
+
+/// An inclusive range of 1-indexed lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineRange {
+    /// First line to include
+    pub start: usize,
+    /// Last line to include (`None` = end of file)
+    pub end: Option<usize>,
+}
+
+impl LevelOffset {
+    /// Parse a `leveloffset` value
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(n) = value.strip_prefix('+') {
+            n.parse().ok().map(LevelOffset::Relative)
+        } else if value.starts_with('-') {
+            value.parse().ok().map(LevelOffset::Relative)
+        } else {
+            value.parse().ok().map(LevelOffset::Absolute)
+        }
+    }
+
+    /// Apply this offset on top of the including document's offset
+    pub fn apply(self, current: i32) -> i32 {
+        match self {
+            LevelOffset::Relative(n) => current + n,
+            LevelOffset::Absolute(n) => n,
+        }
+    }
+}
+
+impl LineRange {
+    /// Parse a `lines` value such as `5..20;30..-1;42`
+    pub fn parse_list(value: &str) -> Option<Vec<Self>> {
+        value
+            .split(';')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(|part| match part.split_once("..") {
+                Some((start, end)) => {
+                    let start = start.trim().parse().ok()?;
+                    let end = match end.trim() {
+                        "" | "-1" => None,
+                        n => Some(n.parse().ok()?),
+                    };
+                    Some(LineRange { start, end })
+                }
+                None => {
+                    let line = part.parse().ok()?;
+                    Some(LineRange {
+                        start: line,
+                        end: Some(line),
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Check if a 1-indexed line number falls within this range
+    pub fn contains(&self, line: usize) -> bool {
+        line >= self.start && self.end.is_none_or(|end| line <= end)
+    }
+}
+
 impl IncludeDirective {
     /// Parse an include directive from a line
     ///
@@ -78,6 +164,9 @@ impl IncludeDirective {
         let mut range = None;
         let mut header = false;
         let mut delimiter = None;
+        let mut leveloffset = None;
+        let mut lines = Vec::new();
+        let mut tags = Vec::new();
         let mut attributes = HashMap::new();
 
         for attr in attrs_str.split(',') {
@@ -100,6 +189,18 @@ impl IncludeDirective {
                     "sheet" => sheet = Some(value),
                     "range" => range = Some(value),
                     "delimiter" => delimiter = value.chars().next(),
+                    "leveloffset" => leveloffset = LevelOffset::parse(&value),
+                    "lines" => {
+                        lines = LineRange::parse_list(value.trim_matches('"')).unwrap_or_default()
+                    }
+                    "tag" | "tags" => tags.extend(
+                        value
+                            .trim_matches('"')
+                            .split(';')
+                            .map(str::trim)
+                            .filter(|t| !t.is_empty())
+                            .map(String::from),
+                    ),
                     _ => {
                         attributes.insert(key, value);
                     }
@@ -113,6 +214,9 @@ impl IncludeDirective {
             range,
             header,
             delimiter,
+            leveloffset,
+            lines,
+            tags,
             attributes,
         })
     }
@@ -130,6 +234,74 @@ impl IncludeDirective {
     pub fn extension(&self) -> Option<&str> {
         Path::new(&self.path).extension()?.to_str()
     }
+
+    /// Select the lines of a text include according to `lines=` and `tags=`
+    ///
+    /// Returns the selected lines paired with their 1-indexed line numbers.
+    /// When both are given, `lines=` wins (as in Asciidoctor). Tag marker
+    /// lines themselves are never included.
+    pub fn select_lines<'a>(&self, content: &'a str) -> Result<Vec<(usize, &'a str)>, String> {
+        let numbered = content.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        if !self.lines.is_empty() {
+            return Ok(numbered
+                .filter(|(n, _)| self.lines.iter().any(|range| range.contains(*n)))
+                .collect());
+        }
+
+        if self.tags.is_empty() {
+            return Ok(numbered.collect());
+        }
+
+        let mut selected = Vec::new();
+        let mut open: Vec<&str> = Vec::new();
+        let mut found: Vec<&str> = Vec::new();
+        for (n, line) in numbered {
+            if let Some(tag) = tag_marker(line, "tag::") {
+                if self.tags.iter().any(|t| t == tag) {
+                    found.push(tag);
+                }
+                open.push(tag);
+                continue;
+            }
+            if let Some(tag) = tag_marker(line, "end::") {
+                if let Some(pos) = open.iter().rposition(|t| *t == tag) {
+                    open.remove(pos);
+                }
+                continue;
+            }
+            if open
+                .iter()
+                .any(|t| self.tags.iter().any(|wanted| wanted == t))
+            {
+                selected.push((n, line));
+            }
+        }
+
+        let missing: Vec<&str> = self
+            .tags
+            .iter()
+            .map(String::as_str)
+            .filter(|t| !found.contains(t))
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("Tag(s) not found: {}", missing.join(", ")));
+        }
+
+        Ok(selected)
+    }
+}
+
+/// Extract the tag name from a `tag::NAME[]` or `end::NAME[]` marker line
+fn tag_marker<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    let start = line.find(prefix)? + prefix.len();
+    let rest = &line[start..];
+    let end = rest.find("[]")?;
+    let name = &rest[..end];
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(name)
 }
 
 /// Resolve a data include directive to a Table
@@ -325,4 +497,52 @@ mod tests {
         let directive = IncludeDirective::parse("include::no_extension[]").unwrap();
         assert_eq!(directive.extension(), None);
     }
+
+    #[test]
+    fn test_parse_include_text_attributes() {
+        let directive = IncludeDirective::parse(
+            "include::chapter.adoc[leveloffset=+1,tags=summary;intro,lines=5..20]",
+        )
+        .unwrap();
+
+        assert_eq!(directive.leveloffset, Some(LevelOffset::Relative(1)));
+        assert_eq!(directive.tags, vec!["summary", "intro"]);
+        assert_eq!(
+            directive.lines,
+            vec![LineRange {
+                start: 5,
+                end: Some(20)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_level_offset_parse() {
+        assert_eq!(LevelOffset::parse("+2"), Some(LevelOffset::Relative(2)));
+        assert_eq!(LevelOffset::parse("-1"), Some(LevelOffset::Relative(-1)));
+        assert_eq!(LevelOffset::parse("3"), Some(LevelOffset::Absolute(3)));
+        assert_eq!(LevelOffset::parse("x"), None);
+        assert_eq!(LevelOffset::Relative(1).apply(1), 2);
+        assert_eq!(LevelOffset::Absolute(0).apply(2), 0);
+    }
+
+    #[test]
+    fn test_select_lines_by_range() {
+        let directive = IncludeDirective::parse("include::f.rs[lines=2..3;5..-1]").unwrap();
+        let selected = directive.select_lines("a\nb\nc\nd\ne\nf").unwrap();
+        assert_eq!(selected, vec![(2, "b"), (3, "c"), (5, "e"), (6, "f")]);
+    }
+
+    #[test]
+    fn test_select_lines_by_tag() {
+        let content = "before\n// tag::summary[]\nsummary text\n// end::summary[]\nafter";
+        let directive = IncludeDirective::parse("include::f.adoc[tag=summary]").unwrap();
+        assert_eq!(
+            directive.select_lines(content).unwrap(),
+            vec![(3, "summary text")]
+        );
+
+        let missing = IncludeDirective::parse("include::f.adoc[tag=other]").unwrap();
+        assert!(missing.select_lines(content).is_err());
+    }
 }
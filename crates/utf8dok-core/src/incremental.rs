@@ -0,0 +1,405 @@
+//! Incremental Reparsing
+//!
+//! [`IncrementalParse`] keeps a parsed document together with checkpoints
+//! of the parser state between its top-level blocks. A [`TextEdit`] is
+//! applied by resuming the parser from the last checkpoint before the edit
+//! and parsing until it reaches a checkpoint after the edit where the
+//! parser is in the same state as in the previous parse. The blocks before
+//! and after that window are reused, with the spans of the later ones
+//! shifted by the edit.
+//!
+//! The result is always the document a full parse of the edited text would
+//! produce. Documents with conditional or include directives are parsed in
+//! full, since which lines they contain depends on directives outside the
+//! edited region.
+//!
+//! # Example
+//!
+//! ```
+//! use utf8dok_core::{IncrementalParse, ParserConfig, TextEdit};
+//!
+//! let mut parse = IncrementalParse::new("= Doc\n\nFirst.\n\nSecond.\n", ParserConfig::default());
+//! let reparse = parse.apply(&TextEdit::new(7..12, "Changed"));
+//! assert_eq!(reparse.parsed, 1);
+//! assert_eq!(parse.text(), "= Doc\n\nChanged.\n\nSecond.\n");
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use utf8dok_ast::{Document, NodePath, SourceSpan};
+
+use crate::diagnostics::{Diagnostic, Diagnostics};
+use crate::parser::{parse_from, parse_with_checkpoints, Checkpoint, ParserConfig, Resumed};
+use crate::preprocessor::has_directives;
+
+/// Replacement of a byte range of a text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte range replaced, in the text before the edit
+    pub range: Range<usize>,
+    /// Text inserted in place of the range
+    pub text: String,
+}
+
+impl TextEdit {
+    /// Create an edit replacing `range` with `text`
+    pub fn new(range: Range<usize>, text: impl Into<String>) -> Self {
+        Self {
+            range,
+            text: text.into(),
+        }
+    }
+
+    /// Change in text length made by the edit
+    fn delta(&self) -> isize {
+        self.text.len() as isize - self.range.len() as isize
+    }
+}
+
+/// How much of a document an edit caused to be parsed again
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reparse {
+    /// Top-level blocks taken over from the previous parse
+    pub reused: usize,
+    /// Top-level blocks parsed again
+    pub parsed: usize,
+}
+
+/// A parsed document that can be updated by text edits
+#[derive(Debug, Clone)]
+pub struct IncrementalParse {
+    /// Current text
+    text: String,
+    /// Parser configuration, reused for every reparse
+    config: ParserConfig,
+    /// Document parsed from the current text
+    document: Document,
+    /// Diagnostics of the current parse
+    diagnostics: Diagnostics,
+    /// Checkpoints between top-level blocks, in text order
+    checkpoints: Vec<Checkpoint>,
+    /// Whether the text has conditional or include directives
+    directives: bool,
+}
+
+impl IncrementalParse {
+    /// Parse `text` in full
+    pub fn new(text: impl Into<String>, config: ParserConfig) -> Self {
+        let text = text.into();
+        let (document, diagnostics, checkpoints) = parse_with_checkpoints(&text, config.clone());
+        let directives = has_directives(&text);
+        Self {
+            text,
+            config,
+            document,
+            diagnostics,
+            checkpoints,
+            directives,
+        }
+    }
+
+    /// The current text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The document parsed from the current text
+    pub fn document(&self) -> &Document {
+        &self.document
+    }
+
+    /// Diagnostics of the current parse
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Take the document and its diagnostics
+    pub fn into_parts(self) -> (Document, Diagnostics) {
+        (self.document, self.diagnostics)
+    }
+
+    /// Apply `edit` to the text and update the document
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range of the edit is out of bounds or does not lie on
+    /// `char` boundaries, like [`String::replace_range`].
+    pub fn apply(&mut self, edit: &TextEdit) -> Reparse {
+        let line_delta =
+            newlines(&edit.text) as isize - newlines(&self.text[edit.range.clone()]) as isize;
+        self.text.replace_range(edit.range.clone(), &edit.text);
+
+        // Only the edited lines can have brought in a directive
+        let edited = self.text[..edit.range.start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let edit_end = edit.range.start + edit.text.len();
+        let edited_end = self.text[edit_end..]
+            .find('\n')
+            .map_or(self.text.len(), |index| edit_end + index);
+        if self.directives || has_directives(&self.text[edited..edited_end]) {
+            return self.reparse_all();
+        }
+
+        // Resume before the edit; the text up to the checkpoint is unchanged
+        let restart = self
+            .checkpoints
+            .partition_point(|checkpoint| checkpoint.offset <= edit.range.start)
+            .checked_sub(1);
+        let start = restart.map(|index| (&self.checkpoints[index], self.document.metadata.clone()));
+
+        // Stop at an old checkpoint past the edit, if reached in the same state
+        let old = &self.checkpoints;
+        let resynced = |checkpoint: &Checkpoint| -> Option<usize> {
+            if checkpoint.offset < edit_end {
+                return None;
+            }
+            let offset = checkpoint.offset.checked_add_signed(-edit.delta())?;
+            let index = old.binary_search_by_key(&offset, |c| c.offset).ok()?;
+            (offset >= edit.range.end && old[index].same_state(checkpoint)).then_some(index)
+        };
+        let resumed = parse_from(&self.text, self.config.clone(), start, |checkpoint| {
+            resynced(checkpoint).is_some()
+        });
+        let resync = resumed.stopped_at.as_ref().and_then(resynced);
+
+        // Splice the reparsed blocks between the unchanged ones; only what
+        // follows them is shifted, up to the end of the document
+        let (kept_blocks, kept_diagnostics) = restart.map_or((0, 0), |index| {
+            let checkpoint = &self.checkpoints[index];
+            (checkpoint.blocks, checkpoint.diagnostics)
+        });
+        let (tail_blocks, tail_diagnostics) = match resync {
+            Some(index) => {
+                let checkpoint = &self.checkpoints[index];
+                (checkpoint.blocks, checkpoint.diagnostics)
+            }
+            None => (self.document.blocks.len(), self.diagnostics.len()),
+        };
+        let Resumed {
+            document,
+            diagnostics,
+            checkpoints,
+            ..
+        } = resumed;
+        let parsed = document.blocks.len();
+        let shift = Shift {
+            blocks: (kept_blocks + parsed) as isize - tail_blocks as isize,
+            diagnostics: (kept_diagnostics + diagnostics.len()) as isize
+                - tail_diagnostics as isize,
+            offset: edit.delta(),
+            line: line_delta,
+        };
+
+        // Blocks and their source map
+        self.document.metadata = document.metadata;
+        self.document
+            .blocks
+            .splice(kept_blocks..tail_blocks, document.blocks);
+        let source_map = &mut self.document.source_map;
+        splice_map(
+            &mut source_map.blocks,
+            document.source_map.blocks,
+            kept_blocks..tail_blocks,
+            shift.blocks,
+            |_| {},
+        );
+        splice_map(
+            &mut source_map.spans,
+            document.source_map.spans,
+            kept_blocks..tail_blocks,
+            shift.blocks,
+            |span| shift.span(span),
+        );
+
+        // Diagnostics
+        for diagnostic in self.diagnostics.tail_mut(tail_diagnostics) {
+            shift.diagnostic(diagnostic);
+        }
+        self.diagnostics
+            .splice(kept_diagnostics..tail_diagnostics, diagnostics);
+
+        // Checkpoints
+        let tail_checkpoints = resync.unwrap_or(self.checkpoints.len());
+        for checkpoint in &mut self.checkpoints[tail_checkpoints..] {
+            shift.checkpoint(checkpoint);
+        }
+        self.checkpoints.splice(
+            restart.unwrap_or(0)..tail_checkpoints,
+            checkpoints.into_iter().map(|mut checkpoint| {
+                checkpoint.blocks += kept_blocks;
+                checkpoint.diagnostics += kept_diagnostics;
+                checkpoint
+            }),
+        );
+
+        Reparse {
+            reused: self.document.blocks.len() - parsed,
+            parsed,
+        }
+    }
+
+    /// Parse the current text in full
+    fn reparse_all(&mut self) -> Reparse {
+        let (document, diagnostics, checkpoints) =
+            parse_with_checkpoints(&self.text, self.config.clone());
+        self.document = document;
+        self.diagnostics = diagnostics;
+        self.checkpoints = checkpoints;
+        self.directives = has_directives(&self.text);
+        Reparse {
+            reused: 0,
+            parsed: self.document.blocks.len(),
+        }
+    }
+}
+
+/// Adjustment of the reused part of a document after an edit
+#[derive(Debug, Clone, Copy, Default)]
+struct Shift {
+    /// Change in the index of top-level blocks
+    blocks: isize,
+    /// Change in the index of diagnostics
+    diagnostics: isize,
+    /// Change in byte offsets
+    offset: isize,
+    /// Change in line numbers
+    line: isize,
+}
+
+impl Shift {
+    fn span(&self, span: &mut SourceSpan) {
+        span.start = span.start.saturating_add_signed(self.offset);
+        span.end = span.end.saturating_add_signed(self.offset);
+        span.line = span.line.saturating_add_signed(self.line);
+    }
+
+    fn diagnostic(&self, diagnostic: &mut Diagnostic) {
+        if let Some(span) = diagnostic.span.as_mut() {
+            span.start = span.start.saturating_add_signed(self.offset);
+            span.end = span.end.saturating_add_signed(self.offset);
+            span.line = span.line.map(|line| line.saturating_add_signed(self.line));
+        }
+    }
+
+    fn checkpoint(&self, checkpoint: &mut Checkpoint) {
+        checkpoint.offset = checkpoint.offset.saturating_add_signed(self.offset);
+        checkpoint.line = checkpoint.line.saturating_add_signed(self.line);
+        checkpoint.blocks = checkpoint.blocks.saturating_add_signed(self.blocks);
+        checkpoint.diagnostics = checkpoint
+            .diagnostics
+            .saturating_add_signed(self.diagnostics);
+    }
+}
+
+/// Key of a source map entry, ordered by top-level block first
+trait BlockKey: Ord + Sized {
+    /// The smallest key of the block at `index`
+    fn first(index: usize) -> Self;
+
+    /// The key moved by `by` top-level blocks
+    fn renumber(self, by: isize) -> Self;
+}
+
+impl BlockKey for usize {
+    fn first(index: usize) -> Self {
+        index
+    }
+
+    fn renumber(self, by: isize) -> Self {
+        self.saturating_add_signed(by)
+    }
+}
+
+impl BlockKey for NodePath {
+    fn first(index: usize) -> Self {
+        NodePath::block(index)
+    }
+
+    fn renumber(mut self, by: isize) -> Self {
+        if let Some(first) = self.0.first_mut() {
+            *first = first.saturating_add_signed(by);
+        }
+        self
+    }
+}
+
+/// Replace the entries of the blocks in `replaced` with `new`, whose blocks
+/// are numbered from zero, and move the entries after them by `blocks`
+///
+/// The entries before `replaced` are left in place.
+fn splice_map<K: BlockKey + Clone, V>(
+    map: &mut BTreeMap<K, V>,
+    new: BTreeMap<K, V>,
+    replaced: Range<usize>,
+    blocks: isize,
+    shift: impl Fn(&mut V),
+) {
+    let tail = K::first(replaced.end);
+    if blocks == 0 {
+        // The entries after the replaced blocks keep their keys
+        let stale: Vec<K> = map
+            .range(K::first(replaced.start)..tail.clone())
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            map.remove(&key);
+        }
+        map.range_mut(tail..).for_each(|(_, value)| shift(value));
+    } else {
+        let mut moved = map.split_off(&K::first(replaced.start));
+        for (key, mut value) in moved.split_off(&tail) {
+            shift(&mut value);
+            map.insert(key.renumber(blocks), value);
+        }
+    }
+    for (key, value) in new {
+        map.insert(key.renumber(replaced.start as isize), value);
+    }
+}
+
+/// Number of line breaks in `text`
+fn newlines(text: &str) -> usize {
+    text.bytes().filter(|&b| b == b'\n').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_reparses_only_the_changed_block() {
+        let text = "= Doc\n\nFirst.\n\nSecond.\n\nThird.\n";
+        let mut parse = IncrementalParse::new(text, ParserConfig::default());
+        let reparse = parse.apply(&TextEdit::new(15..21, "Other"));
+
+        assert_eq!(parse.text(), "= Doc\n\nFirst.\n\nOther.\n\nThird.\n");
+        assert_eq!(
+            reparse,
+            Reparse {
+                reused: 2,
+                parsed: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_directives_force_a_full_parse() {
+        let text = "ifdef::x[]\nHidden.\nendif::[]\n\nFirst.\n\nSecond.\n";
+        let mut parse = IncrementalParse::new(text, ParserConfig::default());
+        let reparse = parse.apply(&TextEdit::new(text.len() - 3..text.len() - 2, ""));
+        assert_eq!(reparse.reused, 0);
+    }
+
+    #[test]
+    fn test_typed_directive_forces_a_full_parse() {
+        let text = "First.\n\nifdef::x[]\n\nHidden.\n\nendif::[]\n";
+        let mut parse =
+            IncrementalParse::new(text.replace("ifdef", "ifdex"), ParserConfig::default());
+        let reparse = parse.apply(&TextEdit::new(12..13, "f"));
+
+        assert_eq!(parse.text(), text);
+        assert_eq!(reparse.reused, 0);
+        assert_eq!(parse.document(), &crate::parse(text).unwrap());
+    }
+}
@@ -1,11 +1,18 @@
 //! utf8dok-core - Plain text, powerful docs
 //!
-//! Core library for utf8dok, providing AsciiDoc parsing and generation.
+//! Core library for utf8dok, providing AsciiDoc and Markdown parsing and
+//! generation.
 //!
 //! # Modules
 //!
 //! - [`parser`] - Parse AsciiDoc text into AST
 //! - [`generator`] - Generate AsciiDoc text from AST
+//! - [`formatter`] - Rewrite AsciiDoc source into a canonical layout
+//! - [`markdown`] - Parse and generate CommonMark/GFM with the same AST
+//! - [`preprocessor`] - Evaluate `ifdef`/`ifndef`/`ifeval` conditionals before parsing
+//! - [`attributes`] - Substitute `{name}` attribute references in text
+//! - [`incremental`] - Reparse only the blocks touched by a text edit
+//! - [`transform`] - Rewrite parsed documents before rendering
 //!
 //! # Example
 //!
@@ -20,6 +27,7 @@
 //!     text: vec![Inline::Text("Hello".to_string())],
 //!     style_id: None,
 //!     anchor: None,
+//!     role: None,
 //! }));
 //! doc.push(Block::Paragraph(Paragraph {
 //!     inlines: vec![Inline::Text("World".to_string())],
@@ -33,21 +41,39 @@
 //! assert!(asciidoc.contains("World"));
 //! ```
 
+pub mod attributes;
 pub mod diagnostics;
 pub mod dual_nature;
+pub mod formatter;
 pub mod generator;
+pub mod ids;
 pub mod include;
+pub mod incremental;
+pub mod markdown;
 pub mod parser;
+pub mod preprocessor;
+mod spans;
+mod table;
+pub mod transform;
 
 // Re-export main types and functions
+pub use attributes::{AttributeMissing, AttributeSubstitutor};
 pub use diagnostics::{Diagnostic, Diagnostics, Severity, Span};
 pub use dual_nature::{
     parse_dual_nature, transform_for_format, validate_dual_nature, ContentSelector,
     DualNatureBlock, DualNatureDocument, OutputFormat,
 };
+pub use formatter::{format, format_with_config, FormatConfig};
 pub use generator::{generate, generate_with_config, AsciiDocGenerator, GeneratorConfig};
+pub use ids::{section_ids, IdSource, SectionId};
 pub use include::{resolve_data_include, IncludeDirective};
-pub use parser::{parse, parse_with_config, ParserConfig};
+pub use incremental::{IncrementalParse, Reparse, TextEdit};
+pub use markdown::{
+    generate_markdown, generate_markdown_with_config, parse_markdown, MarkdownGenerator,
+};
+pub use parser::{parse, parse_with_config, parse_with_diagnostics, ParserConfig};
+pub use preprocessor::{PreprocessResult, Preprocessor};
+pub use transform::{Pipeline, Transform, TransformError, TransformSpec};
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -56,7 +82,7 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 mod tests {
     use super::*;
     use std::collections::HashMap;
-    use utf8dok_ast::{Block, Document, Heading, Inline, Link, Paragraph};
+    use utf8dok_ast::{Block, Document, Footnote, Heading, Inline, Link, Paragraph};
 
     #[test]
     fn test_version() {
@@ -74,6 +100,7 @@ mod tests {
             text: vec![Inline::Text("Section One".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
 
         // Generate AsciiDoc
@@ -140,6 +167,35 @@ mod tests {
         }
     }
 
+    /// Test round-trip fidelity for footnotes
+    #[test]
+    fn test_roundtrip_footnotes() {
+        let mut doc = Document::new();
+        doc.push(Block::Paragraph(Paragraph {
+            inlines: vec![
+                Inline::Text("Claim.".to_string()),
+                Inline::Footnote(Footnote {
+                    id: Some("src".to_string()),
+                    content: vec![Inline::Text("See [1] and [2].".to_string())],
+                }),
+                Inline::Text(" Again.".to_string()),
+                Inline::Footnote(Footnote {
+                    id: Some("src".to_string()),
+                    content: vec![],
+                }),
+            ],
+            style_id: None,
+            attributes: HashMap::new(),
+        }));
+
+        let asciidoc = generate(&doc);
+        assert!(asciidoc.contains("footnote:src[See [1\\] and [2\\].]"));
+        assert!(asciidoc.contains("footnote:src[]"));
+
+        let parsed = parse(&asciidoc).unwrap();
+        assert_eq!(parsed.blocks, doc.blocks);
+    }
+
     /// Test complete round-trip with multiple elements
     #[test]
     fn test_roundtrip_complete() {
@@ -151,6 +207,7 @@ mod tests {
             text: vec![Inline::Text("Introduction".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
 
         // Paragraph with link
@@ -173,6 +230,7 @@ mod tests {
             text: vec![Inline::Text("Details".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
 
         // Generate and parse
@@ -0,0 +1,706 @@
+//! Markdown Generator
+//!
+//! Converts a `utf8dok_ast::Document` into GitHub Flavored Markdown, in the
+//! syntax the Markdown parser reads back. Features Markdown lacks are
+//! written as inline HTML where GitHub renders it (`<mark>`, `<sup>`,
+//! `<br>`, anchors, page breaks), or in the nearest Markdown form: block titles
+//! become bold paragraphs and spanned table cells are padded with empty
+//! ones.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use utf8dok_ast::{
+    Admonition, AdmonitionType, Alignment, Block, BreakType, CommentBlock, Document, FormatType,
+    Heading, Inline, List, ListType, LiteralBlock, Table, TableCell,
+};
+
+use super::plain_text;
+use crate::generator::GeneratorConfig;
+use crate::parser::DIAGRAM_TYPES;
+
+/// HTML block written for page breaks, read back as a page break
+pub(super) const PAGE_BREAK: &str = "<div style=\"page-break-after: always\"></div>";
+
+/// Markdown generator
+pub struct MarkdownGenerator {
+    config: GeneratorConfig,
+    output: String,
+    /// Footnote definitions (label and text), written after the blocks
+    footnotes: Vec<(String, String)>,
+    /// Footnote labels in use
+    labels: HashSet<String>,
+    /// Whether inlines are being written into a table cell
+    in_table_cell: bool,
+}
+
+impl MarkdownGenerator {
+    /// Create a new generator with default configuration
+    pub fn new() -> Self {
+        Self::with_config(GeneratorConfig::default())
+    }
+
+    /// Create a generator with custom configuration
+    ///
+    /// `include_header` writes the document title as a `#` heading;
+    /// `generate_anchors` writes heading anchors as `{#id}` attributes.
+    pub fn with_config(config: GeneratorConfig) -> Self {
+        Self {
+            config,
+            output: String::new(),
+            footnotes: Vec::new(),
+            labels: HashSet::new(),
+            in_table_cell: false,
+        }
+    }
+
+    /// Generate Markdown from a document
+    pub fn generate(&mut self, doc: &Document) -> String {
+        self.output.clear();
+        self.footnotes.clear();
+        self.labels.clear();
+
+        if self.config.include_header {
+            if let Some(ref title) = doc.metadata.title {
+                writeln!(self.output, "# {}\n", escape_text(title)).unwrap();
+            }
+        }
+
+        self.generate_blocks(&doc.blocks);
+
+        // Footnote definitions follow the text
+        for (label, text) in std::mem::take(&mut self.footnotes) {
+            writeln!(self.output, "\n[^{}]: {}", label, text).unwrap();
+        }
+
+        self.output.trim_end().to_string()
+    }
+
+    /// Generate blocks separated by blank lines
+    fn generate_blocks(&mut self, blocks: &[Block]) {
+        let mut previous: Option<(&Block, bool)> = None;
+        for block in blocks {
+            // Markdown joins adjacent lists of the same kind unless their
+            // markers differ
+            let alternate = match (previous, block) {
+                (Some((Block::List(a), alternate)), Block::List(b)) => {
+                    a.list_type == b.list_type && !alternate
+                }
+                _ => false,
+            };
+            let text = self.render(|generator| generator.generate_block(block, alternate));
+            if text.trim().is_empty() {
+                continue;
+            }
+            if !self.output.is_empty() {
+                self.output.push('\n');
+            }
+            self.output.push_str(&text);
+            previous = Some((block, alternate));
+        }
+    }
+
+    /// Generate into a separate buffer and return its text
+    fn render(&mut self, generate: impl FnOnce(&mut Self)) -> String {
+        let outer = std::mem::take(&mut self.output);
+        generate(self);
+        let mut text = std::mem::replace(&mut self.output, outer);
+        if !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Generate blocks into a separate buffer, without the final line break
+    fn render_blocks(&mut self, blocks: &[Block]) -> String {
+        let outer = std::mem::take(&mut self.output);
+        self.generate_blocks(blocks);
+        let text = std::mem::replace(&mut self.output, outer);
+        text.trim_end().to_string()
+    }
+
+    /// Generate a single block (`alternate` picks the other list markers)
+    fn generate_block(&mut self, block: &Block, alternate: bool) {
+        match block {
+            Block::Heading(h) => self.generate_heading(h),
+            Block::Paragraph(p) => {
+                self.generate_title(p.attributes.get("title").map(String::as_str));
+                self.generate_paragraph(&p.inlines)
+            }
+            Block::List(l) => self.generate_list(l, alternate),
+            Block::Table(t) => self.generate_table(t),
+            Block::Admonition(a) => self.generate_admonition(a),
+            Block::Literal(l) => self.generate_literal(l),
+            Block::Break(BreakType::Page) => writeln!(self.output, "{}", PAGE_BREAK).unwrap(),
+            Block::Break(BreakType::Section) | Block::ThematicBreak => {
+                writeln!(self.output, "---").unwrap()
+            }
+            Block::Open(open) => {
+                self.generate_title(open.title.as_deref());
+                self.generate_blocks(&open.blocks);
+            }
+            Block::Sidebar(sidebar) => {
+                self.generate_title(sidebar.title.as_deref());
+                self.generate_blocks(&sidebar.blocks);
+            }
+            Block::Quote(quote) => self.generate_quote(quote),
+            Block::Comment(comment) => self.generate_comment(comment),
+        }
+    }
+
+    /// Generate a comment as an HTML comment (on its own lines if delimited)
+    fn generate_comment(&mut self, comment: &CommentBlock) {
+        if comment.delimited {
+            writeln!(self.output, "<!--\n{}\n-->", comment.text).unwrap();
+        } else {
+            writeln!(self.output, "<!-- {} -->", comment.text).unwrap();
+        }
+    }
+
+    /// Generate a block title as a bold paragraph
+    fn generate_title(&mut self, title: Option<&str>) {
+        if let Some(title) = title {
+            writeln!(self.output, "**{}**\n", escape_text(title)).unwrap();
+        }
+    }
+
+    /// Generate a heading: `##` for level 1, as `#` is the document title
+    fn generate_heading(&mut self, heading: &Heading) {
+        let prefix = "#".repeat(heading.level as usize + 1);
+        let text = self.render_inlines(&heading.text);
+        write!(self.output, "{} {}", prefix, text.replace('\n', " ")).unwrap();
+        if self.config.generate_anchors {
+            if let Some(ref anchor) = heading.anchor {
+                write!(self.output, " {{#{}}}", anchor).unwrap();
+            }
+        }
+        writeln!(self.output).unwrap();
+    }
+
+    /// Generate a paragraph, escaping line starts that read as block syntax
+    fn generate_paragraph(&mut self, inlines: &[Inline]) {
+        let mut text = self.render_inlines(inlines);
+        // A break ending the paragraph needs the HTML form
+        if text.ends_with("\\\n") {
+            text.truncate(text.len() - 2);
+            text.push_str("<br>");
+        }
+        for line in text.split('\n') {
+            writeln!(self.output, "{}", escape_line_start(line.trim_start())).unwrap();
+        }
+    }
+
+    /// Render inlines into a string
+    fn render_inlines(&mut self, inlines: &[Inline]) -> String {
+        let outer = std::mem::take(&mut self.output);
+        for inline in inlines {
+            self.generate_inline(inline);
+        }
+        std::mem::replace(&mut self.output, outer)
+    }
+
+    /// Generate inline content
+    fn generate_inline(&mut self, inline: &Inline) {
+        match inline {
+            Inline::Text(text) => self.output.push_str(&escape_text(text)),
+            Inline::Format(FormatType::Monospace, inner) => {
+                let code = plain_text(std::slice::from_ref(inner.as_ref()));
+                self.output.push_str(&code_span(&code));
+            }
+            Inline::Format(format_type, inner) => {
+                let (open, close) = match format_type {
+                    FormatType::Bold => ("**", "**"),
+                    // `_` cannot emphasize inside words, `*` is ambiguous
+                    // next to bold
+                    FormatType::Italic
+                        if self.output.ends_with('*')
+                            || matches!(inner.as_ref(), Inline::Format(FormatType::Bold, _)) =>
+                    {
+                        ("_", "_")
+                    }
+                    FormatType::Italic => ("*", "*"),
+                    FormatType::Highlight => ("<mark>", "</mark>"),
+                    FormatType::Superscript => ("<sup>", "</sup>"),
+                    FormatType::Subscript => ("<sub>", "</sub>"),
+                    FormatType::Monospace => unreachable!("handled above"),
+                };
+                self.output.push_str(open);
+                self.generate_inline(inner);
+                self.output.push_str(close);
+            }
+            Inline::Span(inlines) => {
+                for inner in inlines {
+                    self.generate_inline(inner);
+                }
+            }
+            Inline::Link(link) => {
+                if link.text.is_empty() && link.url.contains("://") {
+                    write!(self.output, "<{}>", link.url).unwrap();
+                } else {
+                    let text = match link.text.is_empty() {
+                        true => escape_text(&link.url),
+                        false => self.render_inlines(&link.text),
+                    };
+                    write!(self.output, "[{}]({})", text, destination(&link.url)).unwrap();
+                }
+            }
+            Inline::Image(image) => {
+                let alt = escape_text(image.alt.as_deref().unwrap_or_default());
+                write!(self.output, "![{}]({})", alt, destination(&image.src)).unwrap();
+            }
+            Inline::Break if self.in_table_cell => self.output.push_str("<br>"),
+            Inline::Break => self.output.push_str("\\\n"),
+            Inline::Anchor(name) => write!(self.output, "<a id=\"{}\"></a>", name).unwrap(),
+            Inline::Footnote(footnote) => {
+                let label = match footnote.id {
+                    Some(ref id) => id.clone(),
+                    None => (1..)
+                        .map(|n: usize| n.to_string())
+                        .find(|label| !self.labels.contains(label))
+                        .unwrap_or_default(),
+                };
+                write!(self.output, "[^{}]", label).unwrap();
+                if self.labels.insert(label.clone()) {
+                    let text = self.render_inlines(&footnote.content);
+                    self.footnotes.push((label, text.replace('\n', "\n    ")));
+                }
+            }
+        }
+    }
+
+    /// Generate a list; items of deeper levels are indented under the
+    /// content of their parent
+    fn generate_list(&mut self, list: &List, alternate: bool) {
+        if list.list_type == ListType::Description {
+            self.generate_description_list(list);
+            return;
+        }
+
+        let (bullet, delimiter) = if alternate { ('*', ')') } else { ('-', '.') };
+        // Content column of the last item at each level, and item numbers
+        let mut columns: Vec<usize> = Vec::new();
+        let mut numbers: Vec<usize> = Vec::new();
+        let mut tight = true;
+
+        for item in &list.items {
+            let level = item.level as usize;
+            let indent = match level {
+                0 => 0,
+                _ => columns
+                    .get(level - 1)
+                    .or(columns.last())
+                    .copied()
+                    .unwrap_or(0),
+            };
+            numbers.resize(level + 1, 0);
+            if level == 0 && numbers[0] == 0 {
+                // Top-level numbering begins at the list's start
+                numbers[0] = list.start.unwrap_or(1).saturating_sub(1) as usize;
+            }
+            numbers[level] += 1;
+
+            let mut marker = match list.list_type {
+                ListType::Unordered => bullet.to_string(),
+                _ => format!("{}{}", numbers[level], delimiter),
+            };
+            marker.push(' ');
+            columns.truncate(level);
+            columns.resize(level, indent);
+            columns.push(indent + marker.len());
+
+            let checkbox = match item.checked {
+                Some(true) => "[x] ",
+                Some(false) => "[ ] ",
+                None => "",
+            };
+            let body = self.render_blocks(&item.content);
+            if !tight {
+                writeln!(self.output).unwrap();
+            }
+            tight = item.content.len() <= 1;
+
+            let line = format!("{}{}{}", " ".repeat(indent), marker, checkbox);
+            let mut lines = body.split('\n');
+            let first = lines.next().unwrap_or_default();
+            writeln!(self.output, "{}", format!("{}{}", line, first).trim_end()).unwrap();
+            for line in lines {
+                writeln!(self.output, "{}", indent_line(line, indent + marker.len())).unwrap();
+            }
+        }
+    }
+
+    /// Generate a definition list: the term, then `: ` and its definition
+    fn generate_description_list(&mut self, list: &List) {
+        for (index, item) in list.items.iter().enumerate() {
+            if index > 0 {
+                writeln!(self.output).unwrap();
+            }
+            if let Some(ref term) = item.term {
+                let term = self.render_inlines(term);
+                writeln!(
+                    self.output,
+                    "{}",
+                    escape_line_start(&term.replace('\n', " "))
+                )
+                .unwrap();
+            }
+            let body = self.render_blocks(&item.content);
+            let mut lines = body.split('\n');
+            writeln!(self.output, ": {}", lines.next().unwrap_or_default()).unwrap();
+            for line in lines {
+                writeln!(self.output, "{}", indent_line(line, 2)).unwrap();
+            }
+        }
+    }
+
+    /// Generate a pipe table
+    ///
+    /// Markdown tables always have a header row, so tables without one get
+    /// an empty header. Spanned cells are followed by empty cells.
+    fn generate_table(&mut self, table: &Table) {
+        let width = table
+            .rows
+            .iter()
+            .map(|row| {
+                row.cells
+                    .iter()
+                    .map(|cell| cell.colspan.max(1))
+                    .sum::<u32>()
+            })
+            .max()
+            .unwrap_or(0) as usize;
+        if width == 0 {
+            return;
+        }
+
+        if let Some(ref caption) = table.caption {
+            let caption = self.render_inlines(caption);
+            writeln!(self.output, "**{}**\n", caption.replace('\n', " ")).unwrap();
+        }
+
+        let (header, body) = match table.rows.split_first() {
+            Some((first, rest)) if first.is_header => (self.render_row(&first.cells), rest),
+            _ => (Vec::new(), table.rows.as_slice()),
+        };
+        self.write_row(&header, width);
+
+        let separators: Vec<&str> = (0..width)
+            .map(
+                |column| match table.columns.get(column).and_then(|c| c.align) {
+                    Some(Alignment::Left) => ":---",
+                    Some(Alignment::Center) => ":---:",
+                    Some(Alignment::Right) => "---:",
+                    None => "---",
+                },
+            )
+            .collect();
+        writeln!(self.output, "| {} |", separators.join(" | ")).unwrap();
+
+        for row in body {
+            let cells = self.render_row(&row.cells);
+            self.write_row(&cells, width);
+        }
+    }
+
+    /// Render the cells of a row, padding spanned cells with empty ones
+    fn render_row(&mut self, cells: &[TableCell]) -> Vec<String> {
+        let mut texts = Vec::new();
+        for cell in cells {
+            texts.push(self.render_cell(cell));
+            for _ in 1..cell.colspan {
+                texts.push(String::new());
+            }
+        }
+        texts
+    }
+
+    /// Write a table row of `width` cells
+    fn write_row(&mut self, cells: &[String], width: usize) {
+        let mut row = String::from("|");
+        for column in 0..width {
+            let text = cells.get(column).map(String::as_str).unwrap_or_default();
+            match text.is_empty() {
+                true => row.push_str("  |"),
+                false => write!(row, " {} |", text).unwrap(),
+            }
+        }
+        writeln!(self.output, "{}", row).unwrap();
+    }
+
+    /// Render the paragraphs of a table cell on one line
+    fn render_cell(&mut self, cell: &TableCell) -> String {
+        self.in_table_cell = true;
+        let mut texts = Vec::new();
+        for block in &cell.content {
+            match block {
+                Block::Paragraph(p) => texts.push(self.render_inlines(&p.inlines)),
+                Block::Literal(l) => texts.push(code_span(&l.content.replace('\n', " "))),
+                _ => {}
+            }
+        }
+        self.in_table_cell = false;
+        texts.join(" ").replace('\n', " ").replace('|', "\\|")
+    }
+
+    /// Generate an admonition as a GitHub alert
+    fn generate_admonition(&mut self, admonition: &Admonition) {
+        let label = match admonition.admonition_type {
+            AdmonitionType::Note => "NOTE",
+            AdmonitionType::Tip => "TIP",
+            AdmonitionType::Important => "IMPORTANT",
+            AdmonitionType::Warning => "WARNING",
+            AdmonitionType::Caution => "CAUTION",
+        };
+        writeln!(self.output, "> [!{}]", label).unwrap();
+
+        let mut body = String::new();
+        if let Some(ref title) = admonition.title {
+            let title = self.render_inlines(title);
+            writeln!(body, "**{}**\n", title.replace('\n', " ")).unwrap();
+        }
+        body.push_str(&self.render_blocks(&admonition.content));
+        self.write_quoted(&body);
+    }
+
+    /// Generate a quote block; the attribution ends the quote
+    fn generate_quote(&mut self, quote: &utf8dok_ast::QuoteBlock) {
+        self.generate_title(quote.title.as_deref());
+        let mut body = self.render_blocks(&quote.blocks);
+        let source: Vec<&str> = [quote.attribution.as_deref(), quote.cite.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        if !source.is_empty() {
+            write!(body, "\n\n— {}", escape_text(&source.join(", "))).unwrap();
+        }
+        self.write_quoted(&body);
+    }
+
+    /// Write text with every line prefixed by `>`
+    fn write_quoted(&mut self, text: &str) {
+        for line in text.trim().lines() {
+            match line.is_empty() {
+                true => writeln!(self.output, ">").unwrap(),
+                false => writeln!(self.output, "> {}", line).unwrap(),
+            }
+        }
+    }
+
+    /// Generate a literal/code block as a fenced code block
+    fn generate_literal(&mut self, literal: &LiteralBlock) {
+        self.generate_title(literal.title.as_deref());
+
+        // Passthrough content is written as an HTML block
+        if literal.style_id.as_deref() == Some("pass") {
+            writeln!(self.output, "{}", literal.content).unwrap();
+            return;
+        }
+
+        // The info string names the language or the diagram type
+        let diagram = literal
+            .style_id
+            .as_deref()
+            .filter(|style| DIAGRAM_TYPES.contains(&style.to_lowercase().as_str()));
+        let info = diagram.or(literal.language.as_deref()).unwrap_or_default();
+
+        let longest = longest_run(&literal.content, '`');
+        let fence = "`".repeat(longest.max(2) + 1);
+        writeln!(self.output, "{}{}", fence, info).unwrap();
+        if literal.callouts.is_empty() {
+            writeln!(self.output, "{}", literal.content).unwrap();
+        } else {
+            // Callout markers go back to the end of their lines
+            for (index, line) in literal.content.split('\n').enumerate() {
+                write!(self.output, "{}", line).unwrap();
+                for callout in &literal.callouts {
+                    if callout.line as usize == index + 1 {
+                        write!(self.output, " <{}>", callout.number).unwrap();
+                    }
+                }
+                writeln!(self.output).unwrap();
+            }
+        }
+        writeln!(self.output, "{}", fence).unwrap();
+    }
+}
+
+impl Default for MarkdownGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function to generate Markdown from a document
+pub fn generate_markdown(doc: &Document) -> String {
+    let mut generator = MarkdownGenerator::new();
+    generator.generate(doc)
+}
+
+/// Generate Markdown with custom configuration
+pub fn generate_markdown_with_config(doc: &Document, config: GeneratorConfig) -> String {
+    let mut generator = MarkdownGenerator::with_config(config);
+    generator.generate(doc)
+}
+
+/// Escape the characters of `text` that Markdown would read as markup
+fn escape_text(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut escaped = String::with_capacity(text.len());
+    for (index, &c) in chars.iter().enumerate() {
+        let word = |i: Option<usize>| {
+            i.and_then(|i| chars.get(i))
+                .is_some_and(|c| c.is_alphanumeric())
+        };
+        let escape = match c {
+            '\\' | '*' | '`' | '[' | ']' | '<' | '~' | '^' => true,
+            // Underscores inside words are not emphasis
+            '_' => !(word(index.checked_sub(1)) && word(Some(index + 1))),
+            // Only entity references need escaping
+            '&' => entity_at(&chars[index + 1..]),
+            _ => false,
+        };
+        if escape {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Whether `rest` (the text after an `&`) reads as an entity reference
+fn entity_at(rest: &[char]) -> bool {
+    let name: String = rest.iter().take(32).collect();
+    static ENTITY: OnceLock<Regex> = OnceLock::new();
+    ENTITY
+        .get_or_init(|| {
+            Regex::new(r"^(?:#[0-9]+|#[xX][0-9a-fA-F]+|[A-Za-z][A-Za-z0-9]*);").unwrap()
+        })
+        .is_match(&name)
+}
+
+/// Escape the start of a paragraph line that would begin a heading, quote,
+/// list or setext underline
+fn escape_line_start(line: &str) -> String {
+    static ORDERED: OnceLock<Regex> = OnceLock::new();
+    let ordered = ORDERED.get_or_init(|| Regex::new(r"^([0-9]{1,9})([.)])").unwrap());
+
+    match line.chars().next() {
+        Some('#' | '>' | '-' | '+' | '=' | '|') => format!("\\{}", line),
+        Some('0'..='9') => ordered.replace(line, "$1\\$2").into_owned(),
+        _ => line.to_string(),
+    }
+}
+
+/// Indent a line of nested block content (blank lines stay empty)
+fn indent_line(line: &str, indent: usize) -> String {
+    match line.is_empty() {
+        true => String::new(),
+        false => format!("{}{}", " ".repeat(indent), line),
+    }
+}
+
+/// An inline code span delimited by more backticks than it contains
+fn code_span(code: &str) -> String {
+    let fence = "`".repeat(longest_run(code, '`') + 1);
+    let padding = if code.starts_with('`') || code.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{fence}{padding}{code}{padding}{fence}")
+}
+
+/// Link destination, in angle brackets when it has spaces or parentheses
+fn destination(url: &str) -> String {
+    match url.contains([' ', '(', ')']) {
+        true => format!("<{}>", url),
+        false => url.to_string(),
+    }
+}
+
+/// Length of the longest run of `c` in `text`
+fn longest_run(text: &str, c: char) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+    for ch in text.chars() {
+        run = if ch == c { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use utf8dok_ast::{ListItem, Paragraph};
+
+    fn paragraph(text: &str) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines: vec![Inline::Text(text.to_string())],
+            style_id: None,
+            attributes: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a*b* [x] <y>"), "a\\*b\\* \\[x\\] \\<y>");
+        assert_eq!(escape_text("snake_case _word_"), "snake_case \\_word\\_");
+        assert_eq!(escape_text("R&D &amp;"), "R&D \\&amp;");
+    }
+
+    #[test]
+    fn test_escape_line_start() {
+        assert_eq!(escape_line_start("# not a heading"), "\\# not a heading");
+        assert_eq!(escape_line_start("2024. A year"), "2024\\. A year");
+        assert_eq!(escape_line_start("Plain"), "Plain");
+    }
+
+    #[test]
+    fn test_code_span() {
+        assert_eq!(code_span("a`b"), "``a`b``");
+        assert_eq!(code_span("`x`"), "`` `x` ``");
+    }
+
+    #[test]
+    fn test_nested_list_indentation() {
+        let item = |text: &str, level| ListItem {
+            content: vec![paragraph(text)],
+            level,
+            term: None,
+            checked: None,
+        };
+        let mut doc = Document::new();
+        doc.push(Block::List(List {
+            list_type: ListType::Ordered,
+            items: vec![item("One", 0), item("Nested", 1), item("Two", 0)],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+        assert_eq!(generate_markdown(&doc), "1. One\n   1. Nested\n2. Two");
+    }
+
+    #[test]
+    fn test_adjacent_lists_alternate_markers() {
+        let list = Block::List(List {
+            list_type: ListType::Unordered,
+            items: vec![ListItem {
+                content: vec![paragraph("x")],
+                level: 0,
+                term: None,
+                checked: None,
+            }],
+            style_id: None,
+            numbering: None,
+            start: None,
+        });
+        let mut doc = Document::new();
+        doc.push(list.clone());
+        doc.push(list);
+        assert_eq!(generate_markdown(&doc), "- x\n\n* x");
+    }
+}
@@ -0,0 +1,64 @@
+//! Markdown Frontend and Generator
+//!
+//! Reads CommonMark with the GitHub extensions (tables, task lists,
+//! strikethrough, footnotes, alerts) into the same `utf8dok_ast::Document`
+//! the AsciiDoc parser produces, and writes documents back out as Markdown.
+//! Anything parsed from Markdown can be rendered by every backend.
+//!
+//! # Mapping
+//!
+//! | Markdown | AST |
+//! |----------|-----|
+//! | A lone leading `# Title` | `metadata.title`, with `##` as level 1 |
+//! | `- [x] item` | `ListItem::checked` |
+//! | `` ```rust `` | `LiteralBlock::language` |
+//! | `` ```mermaid `` (any diagram type) | `LiteralBlock::style_id` |
+//! | `> [!WARNING]` | `Block::Admonition` |
+//! | `[^note]` and its definition | `Inline::Footnote` |
+//! | `term` / `: definition` | `ListType::Description` |
+//! | HTML blocks | passthrough literal (`style_id` `pass`) |
+//!
+//! Markdown has no counterpart for block titles, callouts, cell spans or
+//! page breaks; the generator writes the closest readable equivalent.
+//!
+//! # Example
+//!
+//! ```
+//! use utf8dok_core::{generate_markdown, parse_markdown};
+//!
+//! let doc = parse_markdown("# Guide\n\n## Setup\n\n- [x] Install\n- [ ] Configure\n");
+//! assert_eq!(doc.metadata.title.as_deref(), Some("Guide"));
+//!
+//! let markdown = generate_markdown(&doc);
+//! assert!(markdown.contains("## Setup"));
+//! assert!(markdown.contains("- [x] Install"));
+//! ```
+
+mod generator;
+mod parser;
+
+pub use generator::{generate_markdown, generate_markdown_with_config, MarkdownGenerator};
+pub use parser::parse_markdown;
+
+use utf8dok_ast::Inline;
+
+/// Text of inlines with all formatting dropped
+pub(crate) fn plain_text(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    for inline in inlines {
+        push_plain_text(inline, &mut text);
+    }
+    text
+}
+
+fn push_plain_text(inline: &Inline, text: &mut String) {
+    match inline {
+        Inline::Text(t) => text.push_str(t),
+        Inline::Format(_, inner) => push_plain_text(inner, text),
+        Inline::Span(inlines) => inlines.iter().for_each(|i| push_plain_text(i, text)),
+        Inline::Link(link) => link.text.iter().for_each(|i| push_plain_text(i, text)),
+        Inline::Image(image) => text.push_str(image.alt.as_deref().unwrap_or_default()),
+        Inline::Break => text.push(' '),
+        Inline::Anchor(_) | Inline::Footnote(_) => {}
+    }
+}
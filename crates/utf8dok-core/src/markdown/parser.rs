@@ -0,0 +1,743 @@
+//! Markdown Parser
+//!
+//! Builds AST blocks from the pulldown-cmark event stream. Footnote
+//! definitions are set aside first so that each footnote's text can be
+//! placed at its first reference, as AsciiDoc's `footnote:[]` does.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use pulldown_cmark::{
+    Alignment as CmarkAlignment, BlockQuoteKind, CodeBlockKind, Event, HeadingLevel, LinkType,
+    Options, Parser as CmarkParser, Tag, TagEnd,
+};
+use regex::Regex;
+use utf8dok_ast::{
+    Admonition, AdmonitionType, Alignment, Block, BreakType, ColumnSpec, CommentBlock, Document,
+    Footnote, FormatType, Heading, Image, Inline, Link, List, ListItem, ListType, LiteralBlock,
+    NodePath, Paragraph, QuoteBlock, SourceSpan, Table, TableCell, TableRow,
+};
+
+use super::generator::PAGE_BREAK;
+use super::plain_text;
+use crate::parser::DIAGRAM_TYPES;
+
+/// An event with the source range it was parsed from
+type Spanned<'a> = (Event<'a>, Range<usize>);
+
+/// Parse Markdown text into a document
+///
+/// Every text is valid Markdown, so parsing cannot fail: syntax that is not
+/// recognised is kept as text. Top-level blocks get source spans.
+pub fn parse_markdown(text: &str) -> Document {
+    MarkdownParser::new(text).parse()
+}
+
+/// CommonMark extensions enabled for parsing: GitHub Flavored Markdown plus
+/// the extensions that map onto AsciiDoc features
+fn options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_GFM
+        | Options::ENABLE_HEADING_ATTRIBUTES
+        | Options::ENABLE_DEFINITION_LIST
+        | Options::ENABLE_SUPERSCRIPT
+        | Options::ENABLE_SUBSCRIPT
+}
+
+struct MarkdownParser<'a> {
+    text: &'a str,
+    /// Events of the document body
+    events: Vec<Spanned<'a>>,
+    /// Index of the next event
+    position: usize,
+    /// Events of the footnote definitions not referenced yet, by label
+    definitions: HashMap<String, Vec<Spanned<'a>>>,
+    /// Number of references to each footnote label
+    references: HashMap<String, usize>,
+    /// Levels removed from heading levels: 1 when `#` is the document title
+    heading_shift: u8,
+}
+
+impl<'a> MarkdownParser<'a> {
+    fn new(text: &'a str) -> Self {
+        let mut events = Vec::new();
+        let mut definitions = HashMap::new();
+        let mut references: HashMap<String, usize> = HashMap::new();
+        let mut definition: Option<(String, Vec<Spanned<'a>>)> = None;
+        let mut level_one_headings = 0;
+
+        for (event, range) in CmarkParser::new_ext(text, options()).into_offset_iter() {
+            match &event {
+                Event::FootnoteReference(label) => {
+                    *references.entry(label.to_string()).or_default() += 1;
+                }
+                Event::Start(Tag::Heading {
+                    level: HeadingLevel::H1,
+                    ..
+                }) if definition.is_none() => level_one_headings += 1,
+                _ => {}
+            }
+            match event {
+                Event::Start(Tag::FootnoteDefinition(label)) => {
+                    definition = Some((label.to_string(), Vec::new()));
+                }
+                Event::End(TagEnd::FootnoteDefinition) => {
+                    if let Some((label, mut body)) = definition.take() {
+                        body.push((Event::End(TagEnd::FootnoteDefinition), range));
+                        definitions.entry(label).or_insert(body);
+                    }
+                }
+                event => match definition.as_mut() {
+                    Some((_, body)) => body.push((event, range)),
+                    None => events.push((event, range)),
+                },
+            }
+        }
+
+        Self {
+            text,
+            events,
+            position: 0,
+            definitions,
+            references,
+            // Like `=` in AsciiDoc, a single `#` heading is the document
+            // title; documents using several are sectioned from `#` down
+            heading_shift: if level_one_headings > 1 { 0 } else { 1 },
+        }
+    }
+
+    fn parse(mut self) -> Document {
+        let mut doc = Document::new();
+
+        let leading_title = matches!(
+            self.events.first(),
+            Some((
+                Event::Start(Tag::Heading {
+                    level: HeadingLevel::H1,
+                    ..
+                }),
+                _
+            ))
+        );
+        if leading_title && self.heading_shift == 1 {
+            self.next();
+            doc.metadata.title = Some(plain_text(&self.inlines()));
+        }
+
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(self.text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        while self.position < self.events.len() {
+            for (block, range) in self.blocks_with_ranges() {
+                let end = range.start + self.text[range.clone()].trim_end().len();
+                let line = line_starts.partition_point(|&start| start <= range.start);
+                let column = range.start - line_starts[line - 1] + 1;
+                doc.source_map.insert_span(
+                    NodePath::block(doc.blocks.len()),
+                    SourceSpan::new(range.start, end, line, column),
+                );
+                doc.push(block);
+            }
+        }
+
+        doc
+    }
+
+    fn next(&mut self) -> Option<Spanned<'a>> {
+        let event = self.events.get(self.position).cloned();
+        self.position += 1;
+        event
+    }
+
+    fn peek(&self) -> Option<&Event<'a>> {
+        self.events.get(self.position).map(|(event, _)| event)
+    }
+
+    /// Blocks up to the end of the enclosing element
+    fn blocks(&mut self) -> Vec<Block> {
+        self.blocks_with_ranges()
+            .into_iter()
+            .map(|(block, _)| block)
+            .collect()
+    }
+
+    /// Blocks up to the end of the enclosing element, with their source
+    /// ranges
+    ///
+    /// Text directly inside a tight list item becomes a paragraph.
+    fn blocks_with_ranges(&mut self) -> Vec<(Block, Range<usize>)> {
+        let mut blocks = Vec::new();
+        let mut loose: Option<(Vec<Inline>, Range<usize>)> = None;
+
+        while let Some((event, range)) = self.next() {
+            let block = match event {
+                Event::End(_) => break,
+                Event::Start(tag) if is_block_tag(&tag) => self.block(tag),
+                Event::Rule => Some(Block::ThematicBreak),
+                Event::TaskListMarker(_) => None,
+                event => {
+                    let (inlines, loose_range) =
+                        loose.get_or_insert_with(|| (Vec::new(), range.clone()));
+                    loose_range.end = range.end;
+                    self.push_inline(event, inlines);
+                    continue;
+                }
+            };
+            if let Some((inlines, loose_range)) = loose.take() {
+                blocks.push((paragraph(inlines), loose_range));
+            }
+            if let Some(block) = block {
+                blocks.push((block, range));
+            }
+        }
+
+        if let Some((inlines, loose_range)) = loose {
+            blocks.push((paragraph(inlines), loose_range));
+        }
+        blocks
+    }
+
+    /// The block started by `tag`, consuming its events
+    fn block(&mut self, tag: Tag<'a>) -> Option<Block> {
+        let block = match tag {
+            Tag::Paragraph => paragraph(self.inlines()),
+            Tag::Heading { level, id, .. } => Block::Heading(Heading {
+                level: (level as u8).saturating_sub(self.heading_shift).max(1),
+                text: self.inlines(),
+                style_id: None,
+                anchor: id.map(|id| id.to_string()),
+                role: None,
+            }),
+            Tag::BlockQuote(kind) => {
+                let blocks = self.blocks();
+                match kind {
+                    Some(kind) => Block::Admonition(Admonition {
+                        admonition_type: admonition_type(kind),
+                        content: blocks,
+                        title: None,
+                    }),
+                    None => Block::Quote(QuoteBlock {
+                        blocks,
+                        attribution: None,
+                        cite: None,
+                        title: None,
+                        verse: false,
+                    }),
+                }
+            }
+            Tag::CodeBlock(kind) => {
+                let info = match kind {
+                    CodeBlockKind::Fenced(info) => info.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let content = self.raw_text();
+                // Diagram languages select a diagram block, as `[mermaid]` does
+                let language = info.split_whitespace().next().map(str::to_string);
+                let (language, style_id) = match language {
+                    Some(lang) if DIAGRAM_TYPES.contains(&lang.to_lowercase().as_str()) => {
+                        (None, Some(lang))
+                    }
+                    lang => (lang, None),
+                };
+                Block::Literal(LiteralBlock {
+                    content,
+                    language,
+                    title: None,
+                    style_id,
+                    callouts: Vec::new(),
+                    highlight_lines: Vec::new(),
+                })
+            }
+            Tag::HtmlBlock => {
+                let content = self.raw_text();
+                if content.trim() == PAGE_BREAK {
+                    return Some(Block::Break(BreakType::Page));
+                }
+                if let Some(comment) = html_comment(&content) {
+                    return Some(Block::Comment(comment));
+                }
+                Block::Literal(LiteralBlock {
+                    content,
+                    language: None,
+                    title: None,
+                    style_id: Some("pass".to_string()),
+                    callouts: Vec::new(),
+                    highlight_lines: Vec::new(),
+                })
+            }
+            Tag::List(start) => {
+                let list_type = match start {
+                    Some(_) => ListType::Ordered,
+                    None => ListType::Unordered,
+                };
+                Block::List(List {
+                    items: self.list_items(&list_type),
+                    list_type,
+                    style_id: None,
+                    numbering: None,
+                    start: start.filter(|&start| start != 1).map(|start| start as u32),
+                })
+            }
+            Tag::DefinitionList => Block::List(List {
+                list_type: ListType::Description,
+                items: self.definition_items(),
+                style_id: None,
+                numbering: None,
+                start: None,
+            }),
+            Tag::Table(alignments) => Block::Table(self.table(&alignments)),
+            _ => {
+                // Metadata and stray definitions carry no content
+                self.blocks();
+                return None;
+            }
+        };
+        Some(block)
+    }
+
+    /// Text of a code or HTML block, without its final line break
+    fn raw_text(&mut self) -> String {
+        let mut content = String::new();
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::Text(text) | Event::Html(text) => content.push_str(&text),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        if content.ends_with('\n') {
+            content.pop();
+        }
+        content
+    }
+
+    /// Items of a list, with the items of nested lists of the same kind
+    /// following their parent one level down
+    fn list_items(&mut self, list_type: &ListType) -> Vec<ListItem> {
+        let mut items = Vec::new();
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::Start(Tag::Item) => {
+                    let checked = self.task_marker();
+                    let mut content = self.blocks();
+                    let mut nested = Vec::new();
+                    while let Some(Block::List(list)) = content.last() {
+                        if list.list_type != *list_type {
+                            break;
+                        }
+                        let Some(Block::List(list)) = content.pop() else {
+                            break;
+                        };
+                        nested.splice(0..0, list.items);
+                    }
+                    items.push(ListItem {
+                        content,
+                        level: 0,
+                        term: None,
+                        checked,
+                    });
+                    items.extend(nested.into_iter().map(|mut item| {
+                        item.level += 1;
+                        item
+                    }));
+                }
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        items
+    }
+
+    /// State of the task list marker starting a list item, if any
+    fn task_marker(&mut self) -> Option<bool> {
+        // Items of loose lists have the marker inside their first paragraph
+        let index = match self.peek() {
+            Some(Event::Start(Tag::Paragraph)) => self.position + 1,
+            _ => self.position,
+        };
+        match self.events.get(index) {
+            Some((Event::TaskListMarker(checked), _)) => {
+                let checked = *checked;
+                self.events.remove(index);
+                Some(checked)
+            }
+            _ => None,
+        }
+    }
+
+    /// Items of a definition list: one per term, holding its definitions
+    fn definition_items(&mut self) -> Vec<ListItem> {
+        let mut items: Vec<ListItem> = Vec::new();
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::Start(Tag::DefinitionListTitle) => items.push(ListItem {
+                    content: Vec::new(),
+                    level: 0,
+                    term: Some(self.inlines()),
+                    checked: None,
+                }),
+                Event::Start(Tag::DefinitionListDefinition) => {
+                    let blocks = self.blocks();
+                    match items.last_mut() {
+                        Some(item) => item.content.extend(blocks),
+                        None => items.push(ListItem {
+                            content: blocks,
+                            level: 0,
+                            term: None,
+                            checked: None,
+                        }),
+                    }
+                }
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        items
+    }
+
+    /// A table; a header row of empty cells means the table has no header
+    fn table(&mut self, alignments: &[CmarkAlignment]) -> Table {
+        let aligns: Vec<Option<Alignment>> = alignments.iter().map(|a| alignment(*a)).collect();
+        let mut rows = Vec::new();
+        while let Some((event, _)) = self.next() {
+            let is_header = match event {
+                Event::Start(Tag::TableHead) => true,
+                Event::Start(Tag::TableRow) => false,
+                Event::End(_) => break,
+                _ => continue,
+            };
+            let cells = self.table_cells(&aligns);
+            let empty = cells.iter().all(|cell| match cell.content.as_slice() {
+                [Block::Paragraph(p)] => p.inlines.is_empty(),
+                _ => false,
+            });
+            if !(is_header && empty) {
+                rows.push(TableRow {
+                    cells,
+                    is_header,
+                    is_footer: false,
+                });
+            }
+        }
+
+        let columns = match aligns.iter().any(Option::is_some) {
+            true => aligns
+                .iter()
+                .map(|align| ColumnSpec {
+                    width: None,
+                    align: *align,
+                    style: None,
+                })
+                .collect(),
+            false => Vec::new(),
+        };
+        Table {
+            rows,
+            style_id: None,
+            caption: None,
+            columns,
+        }
+    }
+
+    /// Cells of a table row, aligned like their column
+    fn table_cells(&mut self, aligns: &[Option<Alignment>]) -> Vec<TableCell> {
+        let mut cells = Vec::new();
+        while let Some((event, _)) = self.next() {
+            match event {
+                Event::Start(Tag::TableCell) => cells.push(TableCell {
+                    content: vec![paragraph(self.inlines())],
+                    colspan: 1,
+                    rowspan: 1,
+                    align: aligns.get(cells.len()).copied().flatten(),
+                    style: None,
+                }),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        cells
+    }
+
+    /// Inlines up to the end of the enclosing element
+    fn inlines(&mut self) -> Vec<Inline> {
+        let mut inlines = Vec::new();
+        while let Some((event, _)) = self.next() {
+            if let Event::End(_) = event {
+                break;
+            }
+            self.push_inline(event, &mut inlines);
+        }
+        inlines
+    }
+
+    /// Inlines up to the inline HTML tag `close`, or the end of the
+    /// enclosing element
+    fn inlines_until_html(&mut self, close: &str) -> Vec<Inline> {
+        let mut inlines = Vec::new();
+        while let Some(event) = self.peek() {
+            match event {
+                Event::End(_) => break,
+                Event::InlineHtml(html) if html.eq_ignore_ascii_case(close) => {
+                    self.next();
+                    break;
+                }
+                _ => {
+                    let (event, _) = self.next().expect("peeked event");
+                    self.push_inline(event, &mut inlines);
+                }
+            }
+        }
+        inlines
+    }
+
+    /// Add the inline for `event` to `inlines`, consuming the events of
+    /// elements it starts
+    fn push_inline(&mut self, event: Event<'a>, inlines: &mut Vec<Inline>) {
+        let inline = match event {
+            Event::Text(text) => Inline::Text(text.to_string()),
+            Event::Code(code) => Inline::Format(
+                FormatType::Monospace,
+                Box::new(Inline::Text(code.to_string())),
+            ),
+            Event::InlineMath(math) | Event::DisplayMath(math) => Inline::Text(math.to_string()),
+            // Lines are joined with a space, except after a hard break
+            Event::SoftBreak if matches!(inlines.last(), Some(Inline::Break)) => return,
+            Event::SoftBreak => Inline::Text(" ".to_string()),
+            Event::HardBreak => Inline::Break,
+            Event::Html(html) | Event::InlineHtml(html) => self.html_inline(&html),
+            Event::FootnoteReference(label) => Inline::Footnote(self.footnote(&label)),
+            Event::Start(Tag::Emphasis) => format(FormatType::Italic, self.inlines()),
+            Event::Start(Tag::Strong) => format(FormatType::Bold, self.inlines()),
+            Event::Start(Tag::Superscript) => format(FormatType::Superscript, self.inlines()),
+            Event::Start(Tag::Subscript) => format(FormatType::Subscript, self.inlines()),
+            Event::Start(Tag::Strikethrough) => {
+                // The AST has no strikethrough; keep the text
+                for inline in self.inlines() {
+                    push_merged(inlines, inline);
+                }
+                return;
+            }
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) => {
+                let url = match link_type {
+                    LinkType::Email => format!("mailto:{}", dest_url),
+                    _ => dest_url.to_string(),
+                };
+                Inline::Link(Link {
+                    url,
+                    text: self.inlines(),
+                })
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                let alt = plain_text(&self.inlines());
+                Inline::Image(Image {
+                    src: dest_url.to_string(),
+                    alt: (!alt.is_empty()).then_some(alt),
+                })
+            }
+            Event::Start(_) => {
+                self.inlines();
+                return;
+            }
+            Event::End(_) | Event::Rule | Event::TaskListMarker(_) => return,
+        };
+        push_merged(inlines, inline);
+    }
+
+    /// Inline HTML the Markdown generator writes for AST features Markdown
+    /// lacks (`<mark>`, `<sup>`, `<sub>`, `<br>`, `<a id>` anchors); other
+    /// HTML stays text
+    fn html_inline(&mut self, html: &str) -> Inline {
+        static ANCHOR: OnceLock<Regex> = OnceLock::new();
+        let anchor =
+            ANCHOR.get_or_init(|| Regex::new(r#"^<a\s+(?:id|name)="([^"]+)"\s*>$"#).unwrap());
+
+        let tag = html.trim().to_ascii_lowercase();
+        match tag.as_str() {
+            "<br>" | "<br/>" | "<br />" => Inline::Break,
+            "<mark>" => format(FormatType::Highlight, self.inlines_until_html("</mark>")),
+            "<sup>" => format(FormatType::Superscript, self.inlines_until_html("</sup>")),
+            "<sub>" => format(FormatType::Subscript, self.inlines_until_html("</sub>")),
+            _ => match anchor.captures(html.trim()) {
+                Some(captures) => {
+                    if matches!(self.peek(), Some(Event::InlineHtml(close)) if close.eq_ignore_ascii_case("</a>"))
+                    {
+                        self.next();
+                    }
+                    Inline::Anchor(captures[1].to_string())
+                }
+                None => Inline::Text(html.to_string()),
+            },
+        }
+    }
+
+    /// The footnote for a reference to `label`
+    ///
+    /// The first reference carries the text; later ones refer back to it by
+    /// id. Numbered footnotes referenced once need no id.
+    fn footnote(&mut self, label: &str) -> Footnote {
+        let content = match self.definitions.remove(label) {
+            Some(events) => {
+                let body = std::mem::replace(&mut self.events, events);
+                let position = std::mem::replace(&mut self.position, 0);
+                let blocks = self.blocks();
+                self.events = body;
+                self.position = position;
+
+                let mut content = Vec::new();
+                for block in blocks {
+                    if let Block::Paragraph(p) = block {
+                        if !content.is_empty() {
+                            push_merged(&mut content, Inline::Text(" ".to_string()));
+                        }
+                        p.inlines
+                            .into_iter()
+                            .for_each(|inline| push_merged(&mut content, inline));
+                    }
+                }
+                content
+            }
+            None => Vec::new(),
+        };
+
+        let numbered = label.chars().all(|c| c.is_ascii_digit());
+        let single = self.references.get(label).copied().unwrap_or_default() <= 1;
+        Footnote {
+            id: (!(numbered && single)).then(|| label.to_string()),
+            content,
+        }
+    }
+}
+
+/// Whether `tag` starts a block rather than an inline
+fn is_block_tag(tag: &Tag<'_>) -> bool {
+    !matches!(
+        tag,
+        Tag::Emphasis
+            | Tag::Strong
+            | Tag::Strikethrough
+            | Tag::Superscript
+            | Tag::Subscript
+            | Tag::Link { .. }
+            | Tag::Image { .. }
+    )
+}
+
+fn paragraph(inlines: Vec<Inline>) -> Block {
+    Block::Paragraph(Paragraph {
+        inlines,
+        style_id: None,
+        attributes: HashMap::new(),
+    })
+}
+
+/// Formatted inlines, grouped in a span when there are several
+fn format(format_type: FormatType, mut inlines: Vec<Inline>) -> Inline {
+    let inner = match inlines.len() {
+        1 => inlines.remove(0),
+        0 => Inline::Text(String::new()),
+        _ => Inline::Span(inlines),
+    };
+    Inline::Format(format_type, Box::new(inner))
+}
+
+/// Push `inline`, joining it to a preceding text
+fn push_merged(inlines: &mut Vec<Inline>, inline: Inline) {
+    if let (Some(Inline::Text(last)), Inline::Text(text)) = (inlines.last_mut(), &inline) {
+        last.push_str(text);
+        return;
+    }
+    inlines.push(inline);
+}
+
+/// A comment from an HTML block that is a single `<!-- -->` comment
+fn html_comment(content: &str) -> Option<CommentBlock> {
+    let inner = content.trim().strip_prefix("<!--")?.strip_suffix("-->")?;
+    if inner.contains("-->") {
+        return None;
+    }
+    let delimited = inner.starts_with('\n');
+    Some(CommentBlock {
+        text: inner.trim_matches(|c| c == '\n' || c == ' ').to_string(),
+        delimited,
+    })
+}
+
+fn admonition_type(kind: BlockQuoteKind) -> AdmonitionType {
+    match kind {
+        BlockQuoteKind::Note => AdmonitionType::Note,
+        BlockQuoteKind::Tip => AdmonitionType::Tip,
+        BlockQuoteKind::Important => AdmonitionType::Important,
+        BlockQuoteKind::Warning => AdmonitionType::Warning,
+        BlockQuoteKind::Caution => AdmonitionType::Caution,
+    }
+}
+
+fn alignment(alignment: CmarkAlignment) -> Option<Alignment> {
+    match alignment {
+        CmarkAlignment::None => None,
+        CmarkAlignment::Left => Some(Alignment::Left),
+        CmarkAlignment::Center => Some(Alignment::Center),
+        CmarkAlignment::Right => Some(Alignment::Right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_levels_follow_the_title() {
+        let doc = parse_markdown("# Title\n\n## Section\n\n### Subsection");
+        assert_eq!(doc.metadata.title.as_deref(), Some("Title"));
+        let levels: Vec<u8> = doc
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Heading(h) => Some(h.level),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(levels, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_several_top_level_headings_are_sections() {
+        let doc = parse_markdown("# One\n\ntext\n\n# Two\n\n## Inner");
+        assert_eq!(doc.metadata.title, None);
+        assert!(matches!(&doc.blocks[0], Block::Heading(h) if h.level == 1));
+        assert!(matches!(&doc.blocks[3], Block::Heading(h) if h.level == 2));
+    }
+
+    #[test]
+    fn test_soft_breaks_join_lines() {
+        let doc = parse_markdown("one\ntwo  \nthree");
+        let Block::Paragraph(p) = &doc.blocks[0] else {
+            panic!("Expected paragraph");
+        };
+        assert_eq!(
+            p.inlines,
+            vec![
+                Inline::Text("one two".to_string()),
+                Inline::Break,
+                Inline::Text("three".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_block_spans() {
+        let input = "# Title\n\nFirst.\n\n- item\n- item\n";
+        let doc = parse_markdown(input);
+        let span = doc.source_map.block_span(1).unwrap();
+        assert_eq!(&input[span.start..span.end], "- item\n- item");
+        assert_eq!((span.line, span.column), (5, 1));
+    }
+}
@@ -11,7 +11,44 @@
 //! - Headings: `== Level 1`, `=== Level 2`, etc.
 //! - Paragraphs: Text separated by blank lines
 //! - Formatting: `*bold*`, `_italic_`, `` `mono` ``
-//! - Lists: `* unordered`, `. ordered`
+//! - Footnotes: `footnote:[text]`, `footnote:id[text]`, `footnote:id[]`
+//! - Attribute references: `{name}`, `{counter:name}` (see [`crate::attributes`])
+//! - Lists: `* unordered`, `- unordered`, `. ordered`, `1. numbered`
+//!   (`a.`, `i)`), `term:: definition`, checklists
+//!   (`* [x] done`), numbering styles (`[loweralpha]`), start numbers
+//!   (`[start=3]`) and `+` continuation lines that attach a block to the
+//!   previous item
+//! - Delimited blocks: `----` listing, `....` literal, `++++` passthrough,
+//!   and the compound `====` example, `****` sidebar, `____` quote (or
+//!   `[verse]`, keeping its line breaks) and `--` open blocks (nestable by
+//!   varying the delimiter length)
+//! - Source blocks: `[source,rust,highlight=2..4]` with callouts (`<1>`)
+//!   explained by a following callout list (`<1> text`)
+//! - Block titles: `.Title` before a block
+//! - Comments: `// text` lines and `////` blocks, kept as
+//!   [`Block::Comment`] (line comments inside a paragraph, list or table
+//!   are dropped)
+//! - Admonitions: `NOTE: text`, `[TIP]` paragraphs and `[WARNING]` on a
+//!   `====` block (also `IMPORTANT` and `CAUTION`)
+//! - Conditionals: `ifdef::`, `ifndef::`, `ifeval::`, `endif::` (see [`crate::preprocessor`])
+//! - Text includes: `include::chapter.adoc[leveloffset=+1]` (when a document
+//!   directory is configured; see [`ParserConfig::with_text_includes`])
+//!
+//! Every parsed block and inline gets a source span in
+//! `Document::source_map`, keyed by its [`NodePath`].
+//!
+//! # Diagnostics
+//!
+//! The parser never fails: [`parse_with_diagnostics`] returns a best-effort
+//! document together with the problems it recovered from. Besides the
+//! preprocessor (`PRE1xx`, `INC101`-`INC104`) and attribute (`ATTR101`)
+//! codes, it reports:
+//!
+//! - `PARSE101`: Delimited block not closed before end of document
+//! - `PARSE102`: Malformed block attribute list (unterminated quote)
+//! - `PARSE103`: Unknown block style
+//! - `INC105`: Data include could not be resolved
+//! - `INC106`: Include directive left unresolved by the configuration
 //!
 //! # Example
 //!
@@ -31,13 +68,25 @@
 
 use anyhow::Result;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::sync::OnceLock;
 use utf8dok_ast::{
-    Block, Document, DocumentMeta, FormatType, Heading, Image, Inline, Link, List, ListItem,
-    ListType, LiteralBlock, Paragraph, Table, TableCell, TableRow,
+    Admonition, AdmonitionType, Block, Callout, CellStyle, ColumnSpec, CommentBlock, Document,
+    DocumentMeta, Footnote, FormatType, Heading, Image, Inline, Link, List, ListItem, ListType,
+    LiteralBlock, NodePath, NumberingStyle, OpenBlock, Paragraph, QuoteBlock, Sidebar,
+    SourceLocation, SourceMap, SourceSpan, Table, TableCell, TableRow,
 };
 
+use crate::attributes::{
+    author_attributes, author_line_attributes, revision_line_attributes, AttributeEntry,
+    AttributeMissing, AttributeSubstitutor, Replacement,
+};
+use crate::diagnostics::{Diagnostic, Diagnostics, Span};
 use crate::include::{resolve_data_include, IncludeDirective};
+use crate::preprocessor::{plain_lines, PreprocessedLine, Preprocessor};
+use crate::spans::{nest, subslice_range, Spans, TextMap};
+use crate::table::{self, CellSpec, RawCell, TableFormat, TableOptions};
 
 /// Configuration for the parser
 #[derive(Debug, Clone, Default)]
@@ -48,6 +97,16 @@ pub struct ParserConfig {
     pub resolve_data_includes: bool,
     /// Whether to emit warnings for unresolved includes
     pub warn_unresolved: bool,
+    /// Render-time attributes visible to conditionals (e.g. `backend-docx`)
+    pub attributes: HashMap<String, String>,
+    /// Directory of the root document, used to resolve text includes
+    /// (text includes are left unresolved when unset)
+    pub docdir: Option<String>,
+    /// Maximum nesting depth for text includes (defaults to 64)
+    pub max_include_depth: Option<usize>,
+    /// Handling of references to undefined attributes, unless the document
+    /// sets `attribute-missing`
+    pub attribute_missing: AttributeMissing,
 }
 
 impl ParserConfig {
@@ -57,8 +116,32 @@ impl ParserConfig {
             base_path: Some(base_path.into()),
             resolve_data_includes: true,
             warn_unresolved: true,
+            ..Default::default()
         }
     }
+
+    /// Inline text includes, resolving paths relative to `docdir`
+    pub fn with_text_includes(mut self, docdir: impl Into<String>) -> Self {
+        self.docdir = Some(docdir.into());
+        self
+    }
+
+    /// Set a render-time attribute
+    pub fn with_attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set the target backend (e.g. "docx", "pptx", "pdf")
+    ///
+    /// Defines `backend`, `backend-<name>`, `filetype` and `filetype-<name>`
+    /// so sources can use `ifdef::backend-docx[]`.
+    pub fn with_backend(self, backend: &str) -> Self {
+        self.with_attribute("backend", backend)
+            .with_attribute(format!("backend-{}", backend), "")
+            .with_attribute("filetype", backend)
+            .with_attribute(format!("filetype-{}", backend), "")
+    }
 }
 
 /// Parser state for tracking what kind of block we're currently building
@@ -66,19 +149,157 @@ impl ParserConfig {
 enum ParserState {
     /// At the root level, not in any block
     Root,
-    /// Building a paragraph with accumulated lines
-    Paragraph(Vec<String>),
+    /// Building a paragraph with accumulated lines (joined with spaces)
+    Paragraph(Vec<String>, TextMap),
     /// Building a list with accumulated items
-    List(ListType, Vec<ListItem>),
-    /// Building a table with rows and current row being built
-    /// (completed_rows, current_row_cells, expected_column_count)
+    List {
+        list_type: ListType,
+        items: Vec<ListItem>,
+        numbering: Option<NumberingStyle>,
+        /// First item number (`[start=3]`)
+        start: Option<u32>,
+        /// Spans of the items, relative to the list
+        spans: Spans,
+    },
+    /// Building a table; its content is laid out when it closes
     Table {
-        rows: Vec<Vec<TableCell>>,
-        current_row: Vec<TableCell>,
-        col_count: Option<usize>,
+        delimiter: String,
+        lines: Vec<String>,
+        /// Map of the lines joined with newlines
+        map: TextMap,
     },
-    /// Building a literal block (delimited by ----)
-    Literal(Vec<String>),
+    /// Building a listing (`----`) or literal (`....`) block
+    Literal {
+        delimiter: String,
+        lines: Vec<String>,
+    },
+    /// Building a passthrough block (delimited by ++++)
+    Passthrough {
+        delimiter: String,
+        lines: Vec<String>,
+    },
+    /// Building a comment from `//` lines, or a `////` block (captured
+    /// verbatim until its `delimiter`)
+    Comment {
+        delimiter: Option<String>,
+        lines: Vec<String>,
+    },
+}
+
+/// Kind of a compound delimited block
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompoundKind {
+    /// `====`
+    Example,
+    /// `****`
+    Sidebar,
+    /// `____`
+    Quote,
+    /// `--`
+    Open,
+    /// `====` or `--` with an admonition style (`[NOTE]`)
+    Admonition,
+}
+
+/// Implicit header line expected after the document title
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HeaderLine {
+    /// `Jane Doe <jane@example.com>; John Smith`
+    Author,
+    /// `v1.2, 2024-05-01: Draft`
+    Revision,
+}
+
+/// A list suspended by a `+` continuation line
+///
+/// The next block at the same container depth is attached to the last
+/// item, and the list is resumed.
+#[derive(Debug)]
+struct ContinuedList {
+    /// Number of open containers when the continuation was seen
+    depth: usize,
+    /// The suspended `ParserState::List`
+    state: ParserState,
+    /// Location where the list started
+    origin: Option<SourceLocation>,
+    /// Span of the line the list started on
+    start: Option<SourceSpan>,
+}
+
+/// A block anchor line (`[[id]]`) waiting for the block it identifies
+#[derive(Debug)]
+struct BlockAnchor {
+    id: String,
+    /// The anchor line, which starts a following paragraph
+    line: String,
+    /// Location and span of the anchor line
+    origin: SourceLocation,
+    span: SourceSpan,
+    /// Map for the anchor line text
+    map: TextMap,
+}
+
+/// A compound delimited block whose content is still being parsed
+#[derive(Debug)]
+struct Container {
+    /// Delimiter line that closes the block
+    delimiter: String,
+    kind: CompoundKind,
+    /// Block style from the attribute line (`[quote, ...]`, `[abstract]`)
+    style: Option<String>,
+    /// Positional attributes after the style
+    positional: Vec<String>,
+    /// Named attributes (`key=value`)
+    named: HashMap<String, String>,
+    title: Option<String>,
+    /// Blocks parsed so far
+    blocks: Vec<Block>,
+    /// Spans of the blocks parsed so far, relative to the container
+    spans: Spans,
+    /// Location of the opening delimiter
+    origin: SourceLocation,
+    /// Span of the opening delimiter line
+    start: SourceSpan,
+}
+
+/// Parser state at the start of a line where no block is open
+///
+/// Parsing the rest of a document from a checkpoint yields the same blocks
+/// as the parse that recorded it (see [`crate::incremental`]).
+#[derive(Debug, Clone)]
+pub(crate) struct Checkpoint {
+    /// Byte offset of the line
+    pub offset: usize,
+    /// Line number (1-indexed)
+    pub line: usize,
+    /// Number of top-level blocks before the line
+    pub blocks: usize,
+    /// Number of diagnostics reported before the line
+    pub diagnostics: usize,
+    /// Attributes defined before the line
+    attributes: AttributeSubstitutor,
+    /// Whether a blank line followed the last list item
+    list_gap: bool,
+}
+
+impl Checkpoint {
+    /// Whether parsing continues the same way from both checkpoints
+    pub fn same_state(&self, other: &Checkpoint) -> bool {
+        self.list_gap == other.list_gap && self.attributes == other.attributes
+    }
+}
+
+/// Blocks parsed from a checkpoint, up to the end of the document or the
+/// first checkpoint accepted by the caller
+pub(crate) struct Resumed {
+    /// The blocks parsed, numbered from zero
+    pub document: Document,
+    /// Diagnostics for the blocks parsed
+    pub diagnostics: Diagnostics,
+    /// Checkpoints passed, with counts relative to the resumed parse
+    pub checkpoints: Vec<Checkpoint>,
+    /// The checkpoint parsing stopped at, if any
+    pub stopped_at: Option<Checkpoint>,
 }
 
 /// AsciiDoc parser using a state machine approach
@@ -91,12 +312,45 @@ struct Parser {
     state: ParserState,
     /// Whether we've parsed the document header (title + attributes)
     header_done: bool,
+    /// Author or revision line expected next in the header
+    header_line: Option<HeaderLine>,
     /// Pending block attributes (e.g., [source,rust], [mermaid])
     pending_attributes: Vec<String>,
+    /// Pending block title (`.Title`)
+    pending_title: Option<String>,
+    /// Pending block anchor (`[[id]]`)
+    pending_anchor: Option<BlockAnchor>,
+    /// Open compound blocks, innermost last
+    containers: Vec<Container>,
+    /// List waiting for the block attached by a `+` continuation
+    continued_list: Option<ContinuedList>,
+    /// Whether a blank line followed the last list item
+    list_gap: bool,
     /// Parser configuration
     config: ParserConfig,
-    /// Warnings accumulated during parsing
-    warnings: Vec<String>,
+    /// Problems recovered from during parsing
+    diagnostics: Diagnostics,
+    /// Attribute values for reference substitution
+    attributes: AttributeSubstitutor,
+    /// Location of the line being processed
+    current: SourceLocation,
+    /// Byte offset of the line being processed
+    current_offset: usize,
+    /// Length of the line being processed, before substitutions
+    current_len: usize,
+    /// Attribute references replaced in the line being processed
+    current_replacements: Vec<Replacement>,
+    /// Span of the line processed before the current one
+    previous_span: SourceSpan,
+    /// Location where the block under construction started
+    block_origin: Option<SourceLocation>,
+    /// Span of the line the block under construction started on
+    block_start: Option<SourceSpan>,
+    /// End offset of the last line that belongs to the block under
+    /// construction
+    block_end: usize,
+    /// Provenance of blocks that came from included files
+    source_map: SourceMap,
 }
 
 impl Parser {
@@ -105,51 +359,707 @@ impl Parser {
     }
 
     fn with_config(config: ParserConfig) -> Self {
+        let attributes = AttributeSubstitutor::with_attributes(config.attributes.clone())
+            .with_missing(config.attribute_missing);
         Self {
             metadata: DocumentMeta::default(),
             blocks: Vec::new(),
             state: ParserState::Root,
             header_done: false,
+            header_line: None,
             pending_attributes: Vec::new(),
+            pending_title: None,
+            pending_anchor: None,
+            containers: Vec::new(),
+            continued_list: None,
+            list_gap: false,
             config,
-            warnings: Vec::new(),
+            diagnostics: Diagnostics::new(),
+            attributes,
+            current: SourceLocation::new(0),
+            current_offset: 0,
+            current_len: 0,
+            current_replacements: Vec::new(),
+            previous_span: SourceSpan::new(0, 0, 1, 1),
+            block_origin: None,
+            block_start: None,
+            block_end: 0,
+            source_map: SourceMap::new(),
         }
     }
 
     /// Parse the entire document
-    fn parse(mut self, text: &str) -> Result<Document> {
-        // Normalize line endings
-        let text = text.replace("\r\n", "\n");
+    fn parse(mut self, text: &str) -> (Document, Diagnostics) {
+        for line in self.preprocess(text) {
+            self.process_source_line(line);
+        }
+        self.finish()
+    }
+
+    /// Evaluate conditionals and inline text includes
+    /// (also normalizes line endings)
+    fn preprocess(&mut self, text: &str) -> Vec<PreprocessedLine> {
+        let mut preprocessor = Preprocessor::with_attributes(self.config.attributes.clone());
+        if let Some(docdir) = &self.config.docdir {
+            preprocessor = preprocessor.with_include_dir(docdir);
+        }
+        if let Some(depth) = self.config.max_include_depth {
+            preprocessor = preprocessor.with_max_include_depth(depth);
+        }
+        let preprocessed = preprocessor.process(text);
+        for diagnostic in preprocessed.diagnostics {
+            self.diagnostics.push(diagnostic);
+        }
+        preprocessed.lines
+    }
+
+    /// Process one preprocessed line, tracking where blocks start and end
+    fn process_source_line(&mut self, line: PreprocessedLine) {
+        self.previous_span = self.line_span();
+        self.current = SourceLocation {
+            file: line.file,
+            line: line.line,
+        };
+        self.current_offset = line.offset;
+        self.current_len = line.text.len();
+        self.current_replacements.clear();
+        if self.state == ParserState::Root {
+            self.block_origin = Some(self.current.clone());
+            self.block_start = Some(self.line_span());
+        }
+
+        if line.level_offset != 0 && !self.in_verbatim_block() {
+            self.process_line(&offset_heading(&line.text, line.level_offset));
+        } else {
+            self.process_line(&line.text);
+        }
 
-        for line in text.lines() {
-            self.process_line(line);
+        // A block started by this line (after finishing the previous one)
+        if self.state != ParserState::Root {
+            if self.block_start.is_none() {
+                self.block_origin = Some(self.current.clone());
+                self.block_start = Some(self.line_span());
+            }
+            if !line.text.trim().is_empty() {
+                self.consume_line();
+            }
         }
+    }
 
-        // Flush any remaining state
+    /// Finish the document at its end
+    fn finish(mut self) -> (Document, Diagnostics) {
+        // Flush any remaining state, closing unterminated blocks
+        self.report_unclosed_block();
         self.flush_state();
+        self.resume_continued_list();
+        self.flush_state();
+        self.flush_pending_anchor();
+        while let Some(container) = self.containers.last() {
+            let (delimiter, start, origin) = (
+                container.delimiter.clone(),
+                container.start,
+                container.origin.clone(),
+            );
+            self.report_unclosed(&delimiter, start, origin);
+            self.close_container();
+        }
 
-        Ok(Document {
+        let document = Document {
             metadata: self.metadata,
             blocks: self.blocks,
             intent: None,
+            source_map: self.source_map,
+        };
+        (document, self.diagnostics)
+    }
+
+    /// Checkpoint before `line`, when no block or block attribute is
+    /// pending and the header is done
+    fn checkpoint(&self, line: &PreprocessedLine) -> Option<Checkpoint> {
+        let idle = self.header_done
+            && self.state == ParserState::Root
+            && self.containers.is_empty()
+            && self.continued_list.is_none()
+            && self.pending_attributes.is_empty()
+            && self.pending_title.is_none()
+            && self.pending_anchor.is_none()
+            && line.file.is_none();
+        idle.then(|| Checkpoint {
+            offset: line.offset,
+            line: line.line,
+            blocks: self.blocks.len(),
+            diagnostics: self.diagnostics.len(),
+            attributes: self.attributes.clone(),
+            list_gap: self.list_gap,
         })
     }
 
+    /// Restore the state saved in `checkpoint`
+    fn restore(&mut self, checkpoint: &Checkpoint, metadata: DocumentMeta) {
+        self.metadata = metadata;
+        self.header_done = true;
+        self.attributes = checkpoint.attributes.clone();
+        self.list_gap = checkpoint.list_gap;
+    }
+
+    /// Report a literal, passthrough or table block left open at the end
+    /// of the document
+    fn report_unclosed_block(&mut self) {
+        let delimiter = match &self.state {
+            ParserState::Literal { delimiter, .. }
+            | ParserState::Passthrough { delimiter, .. }
+            | ParserState::Table { delimiter, .. }
+            | ParserState::Comment {
+                delimiter: Some(delimiter),
+                ..
+            } => delimiter.clone(),
+            _ => return,
+        };
+        let start = self.block_start.unwrap_or_else(|| self.line_span());
+        let origin = self
+            .block_origin
+            .clone()
+            .unwrap_or_else(|| self.current.clone());
+        self.report_unclosed(&delimiter, start, origin);
+    }
+
+    /// Report an unclosed delimited block opened by `delimiter` at `start`
+    fn report_unclosed(&mut self, delimiter: &str, start: SourceSpan, origin: SourceLocation) {
+        let diagnostic = Diagnostic::error(format!(
+            "Delimited block opened with '{}' is never closed",
+            delimiter
+        ))
+        .with_code("PARSE101")
+        .with_span(start.into())
+        .with_help(format!(
+            "Add a closing '{}' line; the block runs to the end of the document",
+            delimiter
+        ));
+        let diagnostic = match origin.file {
+            Some(file) => diagnostic.with_file(file),
+            None => diagnostic,
+        };
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Append a block, recording its provenance if it came from an include
+    ///
+    /// Blocks inside a compound block are added to that block instead.
+    /// `spans` are the spans of the block's children, relative to the
+    /// block; the block itself spans from the line it started on to the
+    /// last line consumed.
+    fn push_block(&mut self, block: Block, mut spans: Spans) {
+        // A block that did not take the pending anchor keeps it in front
+        self.flush_pending_anchor();
+
+        // A block title only applies to the block that follows it
+        self.pending_title = None;
+        let start = self.block_start.take().unwrap_or_else(|| self.line_span());
+        let end = self.block_end.max(start.start);
+        spans.insert(NodePath::default(), SourceSpan { end, ..start });
+
+        if self
+            .continued_list
+            .as_ref()
+            .is_some_and(|continued| continued.depth == self.containers.len())
+        {
+            self.attach_to_list_item(block, spans);
+            return;
+        }
+        if let Some(container) = self.containers.last_mut() {
+            nest(
+                &mut container.spans,
+                &NodePath::block(container.blocks.len()),
+                spans,
+            );
+            container.blocks.push(block);
+            return;
+        }
+        let origin = self
+            .block_origin
+            .take()
+            .unwrap_or_else(|| self.current.clone());
+        if origin.file.is_some() {
+            self.source_map.insert_block(self.blocks.len(), origin);
+        }
+        nest(
+            &mut self.source_map.spans,
+            &NodePath::block(self.blocks.len()),
+            spans,
+        );
+        self.blocks.push(block);
+    }
+
+    /// Attach a block to the last item of the continued list and resume it
+    fn attach_to_list_item(&mut self, block: Block, block_spans: Spans) {
+        self.resume_continued_list();
+        if let ParserState::List { items, spans, .. } = &mut self.state {
+            let index = items.len().saturating_sub(1);
+            if let Some(item) = items.last_mut() {
+                let item_path = NodePath::block(index);
+                let end = block_spans.get(&NodePath::default()).map(|span| span.end);
+                nest(spans, &item_path.child(item_child_count(item)), block_spans);
+                extend_span(spans, &item_path, end);
+                item.content.push(block);
+            }
+        }
+    }
+
+    /// Span of the line being processed
+    fn line_span(&self) -> SourceSpan {
+        SourceSpan::new(
+            self.current_offset,
+            self.current_offset + self.current_len,
+            self.current.line,
+            1,
+        )
+    }
+
+    /// Map for the `len` bytes of the line being processed, after
+    /// attribute substitution
+    fn line_text_map(&self, len: usize) -> TextMap {
+        TextMap::substituted(len, self.line_span(), &self.current_replacements)
+    }
+
+    /// Map for `part`, a slice of the line being processed
+    fn line_map(&self, line: &str, part: &str) -> TextMap {
+        self.line_text_map(line.len())
+            .slice(subslice_range(line, part))
+    }
+
+    /// Mark the line being processed as the last line of the current block
+    fn consume_line(&mut self) {
+        self.block_end = self.current_offset + self.current_len;
+    }
+
+    /// Resume a list suspended by a `+` continuation at the current depth
+    fn resume_continued_list(&mut self) {
+        if self
+            .continued_list
+            .as_ref()
+            .is_some_and(|continued| continued.depth == self.containers.len())
+        {
+            if let Some(continued) = self.continued_list.take() {
+                self.state = continued.state;
+                self.block_origin = continued.origin;
+                self.block_start = continued.start;
+                self.list_gap = false;
+            }
+        }
+    }
+
+    /// Suspend the current list so the next block attaches to its last item
+    fn continue_list(&mut self) {
+        // A continuation after an attached paragraph resumes the list first
+        if !matches!(self.state, ParserState::List { .. }) {
+            self.flush_state();
+        }
+        if matches!(self.state, ParserState::List { .. }) {
+            let state = std::mem::replace(&mut self.state, ParserState::Root);
+            self.continued_list = Some(ContinuedList {
+                depth: self.containers.len(),
+                state,
+                origin: self.block_origin.take(),
+                start: self.block_start.take(),
+            });
+        }
+    }
+
+    /// Substitute attribute references in `text`, found at byte `column`
+    /// of the current line
+    ///
+    /// Returns `None` when the line must be dropped (`attribute-missing`
+    /// set to `drop-line`).
+    fn substitute_attributes(&mut self, text: &str, column: usize) -> Option<String> {
+        self.substitute_mapped(text, column)
+            .map(|(substituted, _)| substituted)
+    }
+
+    /// Substitute attribute references in `text` like
+    /// [`substitute_attributes`](Self::substitute_attributes), with the
+    /// range of each reference in `text` and of its value in the result
+    fn substitute_mapped(
+        &mut self,
+        text: &str,
+        column: usize,
+    ) -> Option<(String, Vec<Replacement>)> {
+        let substituted = self.attributes.substitute_mapped(text);
+        for diagnostic in self.attributes.take_diagnostics() {
+            let diagnostic = match diagnostic.span {
+                Some(span) => diagnostic.with_span(
+                    Span::new(
+                        self.current_offset + column + span.start,
+                        self.current_offset + column + span.end,
+                    )
+                    .with_position(self.current.line, column + span.start + 1),
+                ),
+                None => diagnostic,
+            };
+            self.warn(diagnostic);
+        }
+        substituted
+    }
+
+    /// Record a diagnostic, naming the included file it came from
+    fn warn(&mut self, diagnostic: Diagnostic) {
+        let diagnostic = match &self.current.file {
+            Some(file) => diagnostic.with_file(file.clone()),
+            None => diagnostic,
+        };
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Check a block attribute line (`[...]`) before it is applied
+    fn check_block_attributes(&mut self, line: &str, attrs: &str) {
+        let span = Span::from(self.line_span());
+        if attrs.chars().filter(|&c| c == '"').count() % 2 == 1 {
+            self.warn(
+                Diagnostic::error(format!("Malformed attribute list: [{}]", attrs))
+                    .with_code("PARSE102")
+                    .with_span(span)
+                    .with_help("Close the quoted value with '\"'"),
+            );
+            return;
+        }
+
+        let Some(style) = split_attribute_list(attrs).into_iter().next() else {
+            return;
+        };
+        if style.contains('=') || style.is_empty() {
+            return;
+        }
+        // Shorthand ids, roles and options follow the style: [quote#id.role%opt]
+        let name = style
+            .split(['#', '.', '%'])
+            .next()
+            .unwrap_or_default()
+            .trim();
+        if name.is_empty() || is_known_style(name) {
+            return;
+        }
+        let column = line.find(name).unwrap_or(1);
+        let start = self.current_offset + column;
+        self.warn(
+            Diagnostic::warning(format!("Unknown block style '{}'", name))
+                .with_code("PARSE103")
+                .with_span(
+                    Span::new(start, start + name.len())
+                        .with_position(self.current.line, column + 1),
+                )
+                .with_help("The style is kept as a custom paragraph style"),
+        );
+    }
+
+    /// Apply an attribute entry (`:name: value`, `:name!:`)
+    ///
+    /// Header entries are also recorded in the document metadata.
+    fn define_attribute(&mut self, line: &str, entry: AttributeEntry) {
+        let value = match &entry.value {
+            Some(value) => {
+                let column = line.trim_end().len() - value.len();
+                // A dropped line leaves the attribute empty
+                Some(
+                    self.substitute_attributes(value, column)
+                        .unwrap_or_default(),
+                )
+            }
+            None => None,
+        };
+
+        if !self.header_done {
+            // Attributes set without a value (`:toc:`) are recorded as empty
+            if let Some(value) = value.as_ref() {
+                match entry.name.as_str() {
+                    _ if value.is_empty() => {}
+                    "author" => {
+                        self.metadata.authors = vec![value.clone()];
+                        // The name parts can still be set by their own entries
+                        for (name, part) in author_attributes(value, None) {
+                            if name != "author" && self.attributes.get(name).is_none() {
+                                self.attributes.set(name, part);
+                            }
+                        }
+                    }
+                    "revnumber" => self.metadata.revision = Some(value.clone()),
+                    _ => {}
+                }
+                self.metadata
+                    .attributes
+                    .insert(entry.name.clone(), value.clone());
+            }
+        }
+
+        self.attributes.apply(&AttributeEntry { value, ..entry });
+    }
+
+    /// Apply the author line under the document title
+    fn define_authors(&mut self, line: &str) {
+        let mut authors = Vec::new();
+        for (name, value) in author_line_attributes(line) {
+            if name == "author" || name.starts_with("author_") {
+                authors.push(value.clone());
+            } else if name == "email" || name.starts_with("email_") {
+                self.metadata.attributes.insert(name.clone(), value.clone());
+            }
+            self.attributes.set(&name, value);
+        }
+        self.metadata.authors = authors;
+    }
+
+    /// Apply the revision line under the author line
+    fn define_revision(&mut self, line: &str) {
+        for (name, value) in revision_line_attributes(line) {
+            match name {
+                "revnumber" => self.metadata.revision = Some(value.clone()),
+                _ => {
+                    self.metadata
+                        .attributes
+                        .insert(name.to_string(), value.clone());
+                }
+            }
+            self.attributes.set(name, value);
+        }
+    }
+
+    /// Whether the current block's content is taken verbatim
+    fn in_verbatim_block(&self) -> bool {
+        matches!(
+            self.state,
+            ParserState::Literal { .. }
+                | ParserState::Passthrough { .. }
+                | ParserState::Comment {
+                    delimiter: Some(_),
+                    ..
+                }
+        )
+    }
+
+    /// Whether paragraphs go in a verse block (`[verse]` over `____`)
+    fn in_verse(&self) -> bool {
+        self.containers.last().is_some_and(|container| {
+            container.kind == CompoundKind::Quote && container.style.as_deref() == Some("verse")
+        })
+    }
+
+    /// Append the pending block anchor as an anchor paragraph
+    fn flush_pending_anchor(&mut self) {
+        let Some(anchor) = self.pending_anchor.take() else {
+            return;
+        };
+        let title = self.pending_title.take();
+        let (origin, start) = (self.block_origin.take(), self.block_start.take());
+        self.block_origin = Some(anchor.origin);
+        self.block_start = Some(anchor.span);
+        let paragraph = Paragraph {
+            inlines: vec![Inline::Anchor(anchor.id)],
+            style_id: None,
+            attributes: HashMap::new(),
+        };
+        self.push_block(Block::Paragraph(paragraph), Spans::new());
+        (self.block_origin, self.block_start) = (origin, start);
+        self.pending_title = title;
+    }
+
+    /// Start a compound delimited block, consuming the pending title and
+    /// block attributes
+    fn open_container(&mut self, kind: CompoundKind, delimiter: &str) {
+        self.flush_state();
+        self.flush_pending_anchor();
+
+        let mut attributes = std::mem::take(&mut self.pending_attributes)
+            .iter()
+            .flat_map(|attrs| split_attribute_list(attrs))
+            .collect::<Vec<_>>()
+            .into_iter();
+        let style = attributes.next().filter(|s| !s.contains('='));
+        let mut positional = Vec::new();
+        let mut named = HashMap::new();
+        for attr in attributes {
+            match attr.split_once('=') {
+                Some((key, value)) => {
+                    named.insert(key.trim().to_string(), value.trim().to_string());
+                }
+                None => positional.push(attr),
+            }
+        }
+
+        // An attribute style can turn an open block into another compound block
+        let kind = match (kind, style.as_deref()) {
+            (CompoundKind::Open, Some("example")) => CompoundKind::Example,
+            (CompoundKind::Open, Some("sidebar")) => CompoundKind::Sidebar,
+            (CompoundKind::Open, Some("quote" | "verse")) => CompoundKind::Quote,
+            (CompoundKind::Example | CompoundKind::Open, Some(style))
+                if admonition_type(style).is_some() =>
+            {
+                CompoundKind::Admonition
+            }
+            (kind, _) => kind,
+        };
+
+        self.containers.push(Container {
+            delimiter: delimiter.to_string(),
+            kind,
+            style,
+            positional,
+            named,
+            title: self.pending_title.take(),
+            blocks: Vec::new(),
+            spans: Spans::new(),
+            origin: self.current.clone(),
+            start: self.line_span(),
+        });
+    }
+
+    /// Finish the innermost compound block and append it to its parent
+    fn close_container(&mut self) {
+        self.flush_state();
+        self.resume_continued_list();
+        self.flush_state();
+        let Some(container) = self.containers.pop() else {
+            return;
+        };
+
+        let Container {
+            kind,
+            style,
+            positional,
+            named,
+            title,
+            blocks,
+            spans,
+            origin,
+            start,
+            ..
+        } = container;
+        let mut positional = positional
+            .into_iter()
+            .map(|value| (!value.is_empty()).then_some(value));
+        let block = match kind {
+            CompoundKind::Sidebar => Block::Sidebar(Sidebar { title, blocks }),
+            CompoundKind::Quote => Block::Quote(QuoteBlock {
+                attribution: positional.next().flatten(),
+                cite: positional.next().flatten(),
+                title,
+                blocks,
+                verse: style.as_deref() == Some("verse"),
+            }),
+            CompoundKind::Example => Block::Open(OpenBlock {
+                role: Some("example".to_string()),
+                title,
+                blocks,
+                attributes: named,
+            }),
+            CompoundKind::Open => Block::Open(OpenBlock {
+                role: style,
+                title,
+                blocks,
+                attributes: named,
+            }),
+            CompoundKind::Admonition => Block::Admonition(Admonition {
+                admonition_type: style
+                    .as_deref()
+                    .and_then(admonition_type)
+                    .unwrap_or(AdmonitionType::Note),
+                content: blocks,
+                title: title.map(|title| parse_inlines(&title)),
+            }),
+        };
+
+        self.block_origin = Some(origin);
+        self.block_start = Some(start);
+        self.consume_line();
+        self.push_block(block, spans);
+    }
+
     /// Process a single line
     fn process_line(&mut self, line: &str) {
+        // Literal, passthrough and comment block content is captured verbatim
+        let closes = match &self.state {
+            // Any run of dashes ends a listing block
+            ParserState::Literal { delimiter, .. } if delimiter.starts_with('-') => {
+                Some(is_literal_delimiter(line))
+            }
+            ParserState::Literal { delimiter, .. } => Some(line == delimiter),
+            ParserState::Passthrough { delimiter, .. }
+            | ParserState::Comment {
+                delimiter: Some(delimiter),
+                ..
+            } => Some(line == delimiter),
+            _ => None,
+        };
+        if let Some(closes) = closes {
+            match &mut self.state {
+                _ if closes => {
+                    self.consume_line();
+                    self.flush_state();
+                }
+                ParserState::Literal { lines, .. }
+                | ParserState::Passthrough { lines, .. }
+                | ParserState::Comment { lines, .. } => lines.push(line.to_string()),
+                _ => {}
+            }
+            return;
+        }
+
+        // Comments are taken before attribute substitution, and don't end
+        // the header
+        if self.handle_comment(line) {
+            return;
+        }
+
+        // Attribute entries and substitution
+        if let Some(entry) = AttributeEntry::parse(line) {
+            self.header_line = None;
+            self.define_attribute(line, entry);
+            return;
+        }
+        let Some((substituted, replacements)) = self.substitute_mapped(line, 0) else {
+            return;
+        };
+        self.current_replacements = replacements;
+        let line = substituted.as_str();
+
+        // Table content is buffered until the closing delimiter
+        let line_map = self.line_text_map(line.len());
+        if let ParserState::Table {
+            delimiter,
+            lines,
+            map,
+        } = &mut self.state
+        {
+            if line == delimiter {
+                self.consume_line();
+                self.flush_state();
+            } else {
+                lines.push(line.to_string());
+                map.push(&line_map);
+            }
+            return;
+        }
+
         // Check for document title (level 0 heading)
         if !self.header_done && line.starts_with("= ") && !line.starts_with("== ") {
             self.flush_state();
             let title = line[2..].trim().to_string();
+            self.attributes.set("doctitle", title.clone());
             self.metadata.title = Some(title);
+            self.header_line = Some(HeaderLine::Author);
             return;
         }
 
-        // Check for document attributes (only in header)
-        if !self.header_done && line.starts_with(':') && line.contains(": ") {
-            if let Some((key, value)) = self.parse_attribute(line) {
-                self.metadata.attributes.insert(key, value);
-                return;
+        // The author and revision lines directly follow the title
+        if !self.header_done && !line.trim().is_empty() {
+            match self.header_line.take() {
+                Some(HeaderLine::Author) => {
+                    self.define_authors(line);
+                    self.header_line = Some(HeaderLine::Revision);
+                    return;
+                }
+                Some(HeaderLine::Revision) => {
+                    self.define_revision(line);
+                    return;
+                }
+                None => {}
             }
         }
 
@@ -171,20 +1081,16 @@ impl Parser {
 
         // Empty line handling
         if line.trim().is_empty() {
-            // If we're inside a table, empty lines are row separators
-            if let ParserState::Table {
-                rows,
-                current_row,
-                col_count: _,
-            } = &mut self.state
-            {
-                if !current_row.is_empty() {
-                    // Push current row to rows and start a new row
-                    rows.push(std::mem::take(current_row));
-                }
+            // Blank lines between list items don't end the list
+            if matches!(self.state, ParserState::List { .. }) {
+                self.list_gap = true;
                 return;
             }
             self.flush_state();
+            // An attached paragraph ends here and its list resumes
+            if matches!(self.state, ParserState::List { .. }) {
+                self.list_gap = true;
+            }
             self.header_done = true;
             return;
         }
@@ -192,153 +1098,175 @@ impl Parser {
         // Once we see a non-header element, header is done
         self.header_done = true;
 
-        // Check for table delimiter |===
-        if line.trim() == "|===" {
-            match &self.state {
-                ParserState::Table { .. } => {
-                    // End of table - flush it
-                    self.flush_state();
-                }
-                _ => {
-                    // Start of table - flush any previous state and start table
-                    self.flush_state();
-                    self.state = ParserState::Table {
-                        rows: Vec::new(),
-                        current_row: Vec::new(),
-                        col_count: None,
-                    };
-                }
-            }
+        // Check for table delimiter (|===, ,===, :===, !===)
+        if is_table_delimiter(line) {
+            self.flush_state();
+            self.state = ParserState::Table {
+                delimiter: line.to_string(),
+                lines: Vec::new(),
+                map: TextMap::default(),
+            };
             return;
         }
 
-        // If we're in a table, handle table cell lines
-        if let ParserState::Table {
-            rows,
-            current_row,
-            col_count,
-        } = &mut self.state
-        {
-            if let Some(cell_content) = line.strip_prefix('|') {
-                // Split by | to handle multiple cells on one line: | A | B | C
-                let cell_parts: Vec<&str> = cell_content.split('|').collect();
-
-                // Collect cells from this line
-                let mut line_cells = Vec::new();
-                let is_multicell_line = cell_parts.len() > 1;
-
-                if cell_parts.len() == 1 {
-                    // Single cell - content may be empty (for empty cells like "| ")
-                    let content = cell_content.trim();
-                    let inlines = parse_inlines(content);
-                    line_cells.push(TableCell {
-                        content: vec![Block::Paragraph(Paragraph {
-                            inlines,
-                            style_id: None,
-                            attributes: HashMap::new(),
-                        })],
-                        colspan: 1,
-                        rowspan: 1,
-                        align: None,
-                    });
-                } else {
-                    // Multiple cells on this line: | A | B | C
-                    for cell_text in cell_parts {
-                        let trimmed = cell_text.trim();
-                        let inlines = parse_inlines(trimmed);
-                        line_cells.push(TableCell {
-                            content: vec![Block::Paragraph(Paragraph {
-                                inlines,
-                                style_id: None,
-                                attributes: HashMap::new(),
-                            })],
-                            colspan: 1,
-                            rowspan: 1,
-                            align: None,
-                        });
-                    }
-                }
-
-                // Set column count only if this is a multi-cell line
-                // (cells on separate lines use blank-line row separators)
-                if col_count.is_none() && is_multicell_line {
-                    *col_count = Some(line_cells.len());
-                }
+        // List continuation attaches the next block to the current item
+        if line == "+" && !self.list_gap && self.in_list_item() {
+            self.continue_list();
+            return;
+        }
 
-                // Add cells to current row
-                current_row.extend(line_cells);
+        // Check for listing and literal block delimiters (---- or ....)
+        if is_literal_delimiter(line) || is_dotted_delimiter(line) {
+            // Start of literal block (the end is handled with its content)
+            self.flush_state();
+            self.state = ParserState::Literal {
+                delimiter: line.to_string(),
+                lines: Vec::new(),
+            };
+            return;
+        }
 
-                // If we have multi-cell rows and filled a row, push it and start new
-                if let Some(cols) = *col_count {
-                    if current_row.len() >= cols {
-                        rows.push(std::mem::take(current_row));
-                    }
-                }
-            }
+        // Check for passthrough block delimiter (++++ or more)
+        if line.len() >= 4 && line.chars().all(|c| c == '+') {
+            self.flush_state();
+            self.state = ParserState::Passthrough {
+                delimiter: line.to_string(),
+                lines: Vec::new(),
+            };
             return;
         }
 
-        // Check for literal block delimiter (---- or more dashes)
-        if line.starts_with("----") && line.chars().all(|c| c == '-') {
-            match &self.state {
-                ParserState::Literal(_) => {
-                    // End of literal block - flush it
-                    self.flush_state();
-                }
-                _ => {
-                    // Start of literal block
-                    self.flush_state();
-                    self.state = ParserState::Literal(Vec::new());
-                }
-            }
+        // Check for the end of the innermost compound block
+        if self
+            .containers
+            .last()
+            .is_some_and(|container| container.delimiter == line)
+        {
+            self.close_container();
             return;
         }
 
-        // If we're in a literal block, capture lines verbatim
-        if let ParserState::Literal(lines) = &mut self.state {
-            lines.push(line.to_string());
+        // Check for the start of a compound block
+        if let Some(kind) = compound_delimiter(line) {
+            self.open_container(kind, line);
             return;
         }
 
+        // Check for block anchor ([[id]] or [[id, reftext]])
+        if let Some(id) = block_anchor(line) {
+            if !matches!(self.state, ParserState::Paragraph(..)) {
+                self.flush_state();
+                self.flush_pending_anchor();
+                self.pending_anchor = Some(BlockAnchor {
+                    id: id.to_string(),
+                    line: line.to_string(),
+                    origin: self.current.clone(),
+                    span: self.line_span(),
+                    map: self.line_text_map(line.len()),
+                });
+                return;
+            }
+        }
+
+        // Check for block title (.Title)
+        if let Some(title) = block_title(line) {
+            if !matches!(self.state, ParserState::Paragraph(..)) {
+                self.flush_state();
+                self.pending_title = Some(title.to_string());
+                return;
+            }
+        }
+
         // Check for block attributes [...]
         if line.starts_with('[') && line.ends_with(']') && !line.contains("[[") {
             // Don't flush state - attributes accumulate
             let attr_content = &line[1..line.len() - 1];
+            self.check_block_attributes(line, attr_content);
             self.pending_attributes.push(attr_content.to_string());
             return;
         }
 
         // Check for headings (== Level 1, === Level 2, etc.)
-        if let Some(heading) = self.try_parse_heading(line) {
+        if let Some(mut heading) = self.try_parse_heading(line) {
             self.flush_state();
-            self.pending_attributes.clear(); // Headings don't use block attributes in MVP
-            self.blocks.push(Block::Heading(heading));
+            // Headings only keep the roles of their block attributes
+            heading.role = block_roles(&self.pending_attributes);
+            self.pending_attributes.clear();
+            let text = line.trim_start_matches('=').trim();
+            let spans = self
+                .line_map(line, text)
+                .span(0..text.len())
+                .map(|span| (NodePath::block(0), span))
+                .into_iter()
+                .collect();
+            self.consume_line();
+            self.push_block(Block::Heading(heading), spans);
             return;
         }
 
         // Check for unordered list item (* item or ** item)
-        if let Some((level, content)) = self.try_parse_unordered_item(line) {
-            self.handle_list_item(ListType::Unordered, level, content);
+        if let Some((level, content)) = unordered_item(line) {
+            let map = self.line_map(line, content);
+            self.handle_list_item(ListType::Unordered, level, content, map, None);
             return;
         }
 
         // Check for ordered list item (. item or .. item)
-        if let Some((level, content)) = self.try_parse_ordered_item(line) {
-            self.handle_list_item(ListType::Ordered, level, content);
+        if let Some((level, content)) = ordered_item(line) {
+            let map = self.line_map(line, content);
+            self.handle_list_item(ListType::Ordered, level, content, map, None);
+            return;
+        }
+
+        // Check for explicitly numbered list item (1. item, a. item, i) item)
+        if let Some((numbering, number, content)) = numbered_item(line) {
+            let map = self.line_map(line, content);
+            let marker = Some((numbering, number));
+            self.handle_list_item(ListType::Ordered, 0, content, map, marker);
+            return;
+        }
+
+        // Check for callout list item (<1> explanation)
+        if let Some(content) = callout_item(line) {
+            let (paragraph, paragraph_spans) =
+                mapped_paragraph(content, &self.line_map(line, content));
+            let item = ListItem {
+                content: vec![paragraph],
+                level: 0,
+                term: None,
+                checked: None,
+            };
+            let mut spans = Spans::new();
+            nest(&mut spans, &NodePath::block(0), paragraph_spans);
+            self.push_list_item(ListType::Callout, item, spans, None);
             return;
         }
 
         // Check for image macro (image::path[alt, attrs])
-        if let Some(para) = self.try_parse_image(line) {
+        if let Some(mut para) = self.try_parse_image(line) {
             self.flush_state();
-            self.blocks.push(Block::Paragraph(para));
+            if let Some(title) = self.pending_title.take() {
+                para.attributes.insert("title".to_string(), title);
+            }
+            self.consume_line();
+            let spans = Spans::from([(NodePath::block(0), self.line_span())]);
+            self.push_block(Block::Paragraph(para), spans);
             return;
         }
 
         // Check for include directive (include::path[attrs])
         if let Some(block) = self.try_parse_include(line) {
             self.flush_state();
-            self.blocks.push(block);
+            self.consume_line();
+            self.push_block(block, Spans::new());
+            return;
+        }
+
+        // Check for description list item (term:: definition)
+        if let Some((level, term, definition)) = description_item(line) {
+            let term_map = self.line_map(line, term);
+            let definition_map = self.line_map(line, definition);
+            self.handle_description_item(level, term, term_map, definition, definition_map);
             return;
         }
 
@@ -346,6 +1274,63 @@ impl Parser {
         self.handle_paragraph_line(line);
     }
 
+    /// Handle a `//` comment line or a `////` comment block delimiter
+    ///
+    /// Returns whether the line was a comment line. Comment lines inside a
+    /// paragraph, list or table are dropped so they don't break it up.
+    fn handle_comment(&mut self, line: &str) -> bool {
+        if is_comment_delimiter(line) {
+            if !matches!(self.state, ParserState::Table { .. }) {
+                self.flush_state();
+                self.state = ParserState::Comment {
+                    delimiter: Some(line.to_string()),
+                    lines: Vec::new(),
+                };
+            }
+            return true;
+        }
+        let Some(text) = line_comment(line) else {
+            // A non-comment line ends a run of line comments
+            if matches!(self.state, ParserState::Comment { .. }) {
+                self.flush_state();
+            }
+            return false;
+        };
+        match &mut self.state {
+            ParserState::Root => {
+                self.state = ParserState::Comment {
+                    delimiter: None,
+                    lines: vec![text.to_string()],
+                }
+            }
+            ParserState::Comment { lines, .. } => lines.push(text.to_string()),
+            // A comment after a blank line ends the list, so the next list
+            // starts over
+            ParserState::List { .. } if self.list_gap => {
+                self.flush_state();
+                self.state = ParserState::Comment {
+                    delimiter: None,
+                    lines: vec![text.to_string()],
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Whether the current line can continue a list item
+    fn in_list_item(&self) -> bool {
+        match self.state {
+            ParserState::List { .. } => true,
+            // A paragraph attached by an earlier continuation
+            ParserState::Paragraph(..) => self
+                .continued_list
+                .as_ref()
+                .is_some_and(|continued| continued.depth == self.containers.len()),
+            _ => false,
+        }
+    }
+
     /// Check if an attribute key is a block-level attribute that should not be rendered
     fn is_block_attribute(key: &str) -> bool {
         // Dual-nature attributes
@@ -413,54 +1398,13 @@ impl Parser {
                 text: vec![Inline::Text(text)],
                 style_id: None,
                 anchor: None,
+                role: None,
             });
         }
 
         None
     }
 
-    /// Try to parse an unordered list item
-    fn try_parse_unordered_item(&self, line: &str) -> Option<(usize, String)> {
-        // Count leading '*' characters
-        let mut level = 0;
-        for ch in line.chars() {
-            if ch == '*' {
-                level += 1;
-            } else {
-                break;
-            }
-        }
-
-        // Must have at least one '*' followed by a space
-        if level >= 1 && line.len() > level && line.chars().nth(level) == Some(' ') {
-            let content = line[level + 1..].trim().to_string();
-            return Some((level - 1, content)); // level 0 = *, level 1 = **, etc.
-        }
-
-        None
-    }
-
-    /// Try to parse an ordered list item
-    fn try_parse_ordered_item(&self, line: &str) -> Option<(usize, String)> {
-        // Count leading '.' characters
-        let mut level = 0;
-        for ch in line.chars() {
-            if ch == '.' {
-                level += 1;
-            } else {
-                break;
-            }
-        }
-
-        // Must have at least one '.' followed by a space
-        if level >= 1 && line.len() > level && line.chars().nth(level) == Some(' ') {
-            let content = line[level + 1..].trim().to_string();
-            return Some((level - 1, content)); // level 0 = ., level 1 = .., etc.
-        }
-
-        None
-    }
-
     /// Try to parse an image macro line: image::path[alt, attrs]
     fn try_parse_image(&self, line: &str) -> Option<Paragraph> {
         // Match image::path[attributes] pattern
@@ -512,8 +1456,14 @@ impl Parser {
             // For non-data includes, we could expand this later
             // For now, emit a warning and skip
             if self.config.warn_unresolved {
-                self.warnings
-                    .push(format!("Non-data include not resolved: {}", directive.path));
+                self.warn(
+                    Diagnostic::warning(format!("Include not resolved: {}", directive.path))
+                        .with_code("INC106")
+                        .with_span(self.line_span().into())
+                        .with_help(
+                            "Text includes are resolved when a document directory is configured",
+                        ),
+                );
             }
             return None;
         }
@@ -521,10 +1471,14 @@ impl Parser {
         // Check if we should resolve includes
         if !self.config.resolve_data_includes {
             if self.config.warn_unresolved {
-                self.warnings.push(format!(
-                    "Data include not resolved (disabled): {}",
-                    directive.path
-                ));
+                self.warn(
+                    Diagnostic::warning(format!(
+                        "Data include not resolved (disabled): {}",
+                        directive.path
+                    ))
+                    .with_code("INC106")
+                    .with_span(self.line_span().into()),
+                );
             }
             // Return a placeholder paragraph
             return Some(Block::Paragraph(Paragraph {
@@ -541,10 +1495,15 @@ impl Parser {
         match resolve_data_include(&directive, base_path) {
             Ok(table) => Some(Block::Table(table)),
             Err(err) => {
-                self.warnings.push(format!(
-                    "Failed to resolve include '{}': {}",
-                    directive.path, err
-                ));
+                self.warn(
+                    Diagnostic::error(format!(
+                        "Failed to resolve include '{}': {}",
+                        directive.path, err
+                    ))
+                    .with_code("INC105")
+                    .with_span(self.line_span().into())
+                    .with_help("Data files are resolved relative to the configured base path"),
+                );
                 // Return error placeholder
                 Some(Block::Paragraph(Paragraph {
                     inlines: vec![Inline::Text(format!(
@@ -558,117 +1517,541 @@ impl Parser {
         }
     }
 
-    /// Handle a list item
-    fn handle_list_item(&mut self, list_type: ListType, level: usize, content: String) {
-        let inlines = parse_inlines(&content);
+    /// Handle a list item whose text `content` is mapped by `map`
+    ///
+    /// `marker` is the numbering style and number of an explicitly numbered
+    /// item, which apply when the item starts a list.
+    fn handle_list_item(
+        &mut self,
+        list_type: ListType,
+        level: usize,
+        content: &str,
+        map: TextMap,
+        marker: Option<(NumberingStyle, u32)>,
+    ) {
+        // Checklist markers only apply to unordered items
+        let (checked, text) = match list_type {
+            ListType::Unordered => checklist_marker(content),
+            _ => (None, content),
+        };
+        let (paragraph, paragraph_spans) =
+            mapped_paragraph(text, &map.slice(subslice_range(content, text)));
         let item = ListItem {
-            content: vec![Block::Paragraph(Paragraph {
-                inlines,
-                style_id: None,
-                attributes: HashMap::new(),
-            })],
+            content: vec![paragraph],
             level: level as u8,
             term: None,
+            checked,
         };
+        let mut spans = Spans::new();
+        nest(&mut spans, &NodePath::block(0), paragraph_spans);
+        self.push_list_item(list_type, item, spans, marker);
+    }
 
+    /// Handle a description list item
+    fn handle_description_item(
+        &mut self,
+        level: usize,
+        term: &str,
+        term_map: TextMap,
+        definition: &str,
+        definition_map: TextMap,
+    ) {
+        let (term, mut spans) = parse_mapped_inlines(term, &term_map);
+        // The definition may also start on the following line
+        let content = if definition.is_empty() {
+            Vec::new()
+        } else {
+            let (paragraph, paragraph_spans) = mapped_paragraph(definition, &definition_map);
+            nest(&mut spans, &NodePath::block(term.len()), paragraph_spans);
+            vec![paragraph]
+        };
+        let item = ListItem {
+            content,
+            level: level as u8,
+            term: Some(term),
+            checked: None,
+        };
+        self.push_list_item(ListType::Description, item, spans, None);
+    }
+
+    /// Add an item to the current list, or start a new list
+    ///
+    /// `item_spans` are the spans of the item's children; the item spans
+    /// the line being processed. Block attributes take precedence over the
+    /// numbering of an explicit `marker`.
+    fn push_list_item(
+        &mut self,
+        list_type: ListType,
+        item: ListItem,
+        mut item_spans: Spans,
+        marker: Option<(NumberingStyle, u32)>,
+    ) {
+        item_spans.insert(NodePath::default(), self.line_span());
+
+        // Finishing an attached paragraph may resume the list
+        if !matches!(self.state, ParserState::List { .. }) {
+            self.flush_state();
+        }
+
+        // Block attributes after a blank line start a new list
+        let restarts = self.list_gap && !self.pending_attributes.is_empty();
+        self.list_gap = false;
         match &mut self.state {
-            ParserState::List(current_type, items) if *current_type == list_type => {
+            ParserState::List {
+                list_type: current_type,
+                items,
+                spans,
+                ..
+            } if *current_type == list_type && !restarts => {
                 // Continue the current list
+                nest(spans, &NodePath::block(items.len()), item_spans);
                 items.push(item);
             }
-            _ => {
-                // Start a new list (flush any previous state)
-                self.flush_state();
-                self.state = ParserState::List(list_type, vec![item]);
+            _ => {
+                // Start a new list (flush any previous state); lists
+                // consume their block attributes
+                self.flush_state();
+                let numbering = self
+                    .pending_attributes
+                    .first()
+                    .and_then(|attrs| split_attribute_list(attrs).into_iter().next())
+                    .and_then(|style| NumberingStyle::from_style(&style))
+                    .or(marker
+                        .map(|(numbering, _)| numbering)
+                        .filter(|&numbering| numbering != NumberingStyle::Arabic));
+                let start = self
+                    .pending_attributes
+                    .iter()
+                    .flat_map(|attrs| split_attribute_list(attrs))
+                    .find_map(|attr| attr.strip_prefix("start=")?.trim().parse().ok())
+                    .or(marker.map(|(_, number)| number))
+                    .filter(|&start| start != 1 && list_type == ListType::Ordered);
+                self.pending_attributes.clear();
+                let mut spans = Spans::new();
+                nest(&mut spans, &NodePath::block(0), item_spans);
+                self.state = ParserState::List {
+                    list_type,
+                    items: vec![item],
+                    numbering,
+                    start,
+                    spans,
+                };
+            }
+        }
+    }
+
+    /// Handle a paragraph line
+    fn handle_paragraph_line(&mut self, line: &str) {
+        let source = self.line_span();
+        let line_map = self.line_text_map(line.len());
+        let previous = self.previous_span;
+        match &mut self.state {
+            // A line directly below a list item continues its text
+            ParserState::List { items, spans, .. } if !self.list_gap => {
+                let index = items.len().saturating_sub(1);
+                if let Some(item) = items.last_mut() {
+                    let item_path = NodePath::block(index);
+                    let map = line_map;
+                    let last_child = item_path.child(item_child_count(item).saturating_sub(1));
+                    match item.content.last_mut() {
+                        Some(Block::Paragraph(para)) => {
+                            let path = last_child;
+                            let (continuation, line_spans) = parse_mapped_inlines(line, &map);
+                            let merged = para.inlines.len();
+                            let first = append_line(&mut para.inlines, continuation);
+                            if first > merged {
+                                // The space joining the lines: the line break
+                                let space = SourceSpan {
+                                    start: previous.end,
+                                    end: source.start,
+                                    line: previous.line,
+                                    column: previous.column + previous.end - previous.start,
+                                };
+                                spans.insert(path.child(first - 1), space);
+                            }
+                            for (inline, span) in line_spans {
+                                let (index, rest) = inline.steps().split_first().unwrap();
+                                let inline = path.child(first + index);
+                                if first < merged && *index == 0 {
+                                    // Merged into the last text of the paragraph
+                                    extend_span(spans, &inline, Some(span.end));
+                                } else {
+                                    spans.insert(NodePath([inline.steps(), rest].concat()), span);
+                                }
+                            }
+                            extend_span(spans, &path, Some(source.end));
+                        }
+                        _ => {
+                            let (paragraph, paragraph_spans) = mapped_paragraph(line, &map);
+                            nest(
+                                spans,
+                                &item_path.child(item_child_count(item)),
+                                paragraph_spans,
+                            );
+                            item.content.push(paragraph);
+                        }
+                    }
+                    extend_span(spans, &item_path, Some(source.end));
+                }
+            }
+            ParserState::Paragraph(lines, map) => {
+                // Continue the current paragraph
+                lines.push(line.to_string());
+                map.push(&line_map);
+            }
+            _ => {
+                // Start a new paragraph, with the inline anchor of a pending
+                // block anchor line
+                self.flush_state();
+                self.state = match self.pending_anchor.take() {
+                    Some(anchor) => {
+                        self.block_origin = Some(anchor.origin);
+                        self.block_start = Some(anchor.span);
+                        let mut map = anchor.map;
+                        map.push(&line_map);
+                        ParserState::Paragraph(vec![anchor.line, line.to_string()], map)
+                    }
+                    None => ParserState::Paragraph(vec![line.to_string()], line_map),
+                };
+            }
+        }
+    }
+
+    /// Flush the current state to blocks
+    fn flush_state(&mut self) {
+        let state = std::mem::replace(&mut self.state, ParserState::Root);
+        self.list_gap = false;
+
+        match state {
+            ParserState::Root => {}
+            ParserState::Paragraph(lines, map) => {
+                if !lines.is_empty() {
+                    // Verse lines keep their breaks (a newline is read as
+                    // a hard break)
+                    let text = match self.in_verse() {
+                        true => lines.join("\n"),
+                        false => lines.join(" "),
+                    };
+
+                    // Paragraphs consume their block attributes; an
+                    // admonition style or label turns them into admonitions
+                    let style = self
+                        .pending_attributes
+                        .first()
+                        .and_then(|attrs| split_attribute_list(attrs).into_iter().next());
+                    let role = block_roles(&self.pending_attributes);
+                    self.pending_attributes.clear();
+                    let admonition = match style.as_deref().and_then(admonition_type) {
+                        Some(kind) => Some((kind, text.as_str())),
+                        None => admonition_paragraph(&text),
+                    };
+
+                    match admonition {
+                        Some((admonition_type, content)) => {
+                            let title = self.pending_title.take();
+                            let content_map = map.slice(subslice_range(&text, content));
+                            let (paragraph, paragraph_spans) =
+                                mapped_paragraph(content, &content_map);
+                            let mut spans = Spans::new();
+                            nest(&mut spans, &NodePath::block(0), paragraph_spans);
+                            self.push_block(
+                                Block::Admonition(Admonition {
+                                    admonition_type,
+                                    content: vec![paragraph],
+                                    title: title.map(|title| parse_inlines(&title)),
+                                }),
+                                spans,
+                            )
+                        }
+                        None => {
+                            let (mut paragraph, spans) = mapped_paragraph(&text, &map);
+                            if let (Block::Paragraph(paragraph), Some(role)) =
+                                (&mut paragraph, role)
+                            {
+                                paragraph.attributes.insert("role".to_string(), role);
+                            }
+                            self.push_block(paragraph, spans)
+                        }
+                    }
+                }
+            }
+            ParserState::List {
+                list_type,
+                items,
+                numbering,
+                start,
+                spans,
+            } => {
+                if !items.is_empty() {
+                    self.push_block(
+                        Block::List(List {
+                            list_type,
+                            items,
+                            style_id: None,
+                            numbering,
+                            start,
+                        }),
+                        spans,
+                    );
+                }
+            }
+            ParserState::Table {
+                delimiter,
+                lines,
+                map,
+            } => {
+                let (table, spans) = self.build_table(&delimiter, &lines, &map);
+                self.push_block(Block::Table(table), spans);
+            }
+            ParserState::Literal { delimiter, lines } => {
+                // Parse pending attributes to extract language and style
+                let (language, mut style_id) = self.parse_block_attributes();
+                // A `....` block is a literal block unless styled otherwise
+                if is_dotted_delimiter(&delimiter) && language.is_none() {
+                    style_id.get_or_insert_with(|| "literal".to_string());
+                }
+                let highlight_lines = self
+                    .pending_attributes
+                    .iter()
+                    .flat_map(|attrs| split_attribute_list(attrs))
+                    .find_map(|attr| attr.strip_prefix("highlight=").map(line_ranges))
+                    .unwrap_or_default();
+
+                // Callouts only apply to source and listing blocks, not diagrams
+                let (content, callouts) = if style_id.is_none() {
+                    extract_callouts(&lines)
+                } else {
+                    (lines.join("\n"), Vec::new())
+                };
+
+                let title = self.pending_title.take();
+                self.push_block(
+                    Block::Literal(LiteralBlock {
+                        content,
+                        language,
+                        title,
+                        style_id,
+                        callouts,
+                        highlight_lines,
+                    }),
+                    Spans::new(),
+                );
+
+                // Clear pending attributes after use
+                self.pending_attributes.clear();
+            }
+            ParserState::Passthrough { lines, .. } => {
+                // Passthrough content is kept raw for the backend
+                let title = self.pending_title.take();
+                self.push_block(
+                    Block::Literal(LiteralBlock {
+                        content: lines.join("\n"),
+                        language: None,
+                        title,
+                        style_id: Some("pass".to_string()),
+                        callouts: Vec::new(),
+                        highlight_lines: Vec::new(),
+                    }),
+                    Spans::new(),
+                );
+                self.pending_attributes.clear();
+            }
+            ParserState::Comment { delimiter, lines } => {
+                // Comments leave the title and attributes to the next block
+                let title = self.pending_title.take();
+                self.push_block(
+                    Block::Comment(CommentBlock {
+                        text: lines.join("\n"),
+                        delimited: delimiter.is_some(),
+                    }),
+                    Spans::new(),
+                );
+                self.pending_title = title;
             }
         }
     }
 
-    /// Handle a paragraph line
-    fn handle_paragraph_line(&mut self, line: &str) {
-        match &mut self.state {
-            ParserState::Paragraph(lines) => {
-                // Continue the current paragraph
-                lines.push(line.to_string());
+    /// Lay out buffered table content, consuming the pending title and
+    /// block attributes
+    ///
+    /// Returns the spans of the rows and cells of prefix-separated tables
+    /// (`map` maps the lines joined with newlines).
+    fn build_table(&mut self, delimiter: &str, lines: &[String], map: &TextMap) -> (Table, Spans) {
+        let attributes: Vec<String> = std::mem::take(&mut self.pending_attributes)
+            .iter()
+            .flat_map(|attrs| split_attribute_list(attrs))
+            .collect();
+        let options = TableOptions::new(delimiter, &attributes);
+        let text = lines.join("\n");
+
+        let mut header = options.header;
+        let rows = match options.format {
+            TableFormat::Psv => {
+                let cells = table::split_cells(&text, options.separator);
+                let columns = match options.columns.len() {
+                    0 => table::first_line_columns(&cells),
+                    n => n,
+                };
+                if columns > 1 || !options.columns.is_empty() {
+                    if header.is_none() && table::has_implicit_header(lines) {
+                        header = Some(true);
+                    }
+                    table::arrange_rows(cells, columns)
+                } else {
+                    // Cells on separate lines, rows separated by blank lines
+                    table::arrange_rows_by_blank_lines(cells)
+                }
             }
-            _ => {
-                // Start a new paragraph
-                self.flush_state();
-                self.state = ParserState::Paragraph(vec![line.to_string()]);
+            TableFormat::Csv | TableFormat::Dsv => {
+                table::split_records(&text, options.format, options.separator)
+                    .into_iter()
+                    .map(|record| {
+                        record
+                            .into_iter()
+                            .enumerate()
+                            .map(|(column, text)| {
+                                let cell = RawCell {
+                                    spec: CellSpec::default(),
+                                    text,
+                                    line: 0,
+                                    blank_line_after: false,
+                                    range: 0..0,
+                                    text_start: 0,
+                                };
+                                (column, cell)
+                            })
+                            .collect()
+                    })
+                    .collect()
             }
-        }
-    }
+        };
 
-    /// Flush the current state to blocks
-    fn flush_state(&mut self) {
-        let state = std::mem::replace(&mut self.state, ParserState::Root);
+        // Only prefix-separated cells know where they are in the source
+        let mapped = options.format == TableFormat::Psv;
+        let mut spans = Spans::new();
+        let row_count = rows.len();
+        let rows = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, cells)| {
+                let is_header = index == 0 && header == Some(true);
+                let row_path = NodePath::block(index);
+                if let (true, Some((_, first)), Some((_, last))) =
+                    (mapped, cells.first(), cells.last())
+                {
+                    if let Some(span) = map.span(first.range.start..last.range.end) {
+                        spans.insert(row_path.clone(), span);
+                    }
+                }
+                let cells = cells
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, (column, raw))| {
+                        // Column styles don't apply to the header row
+                        let column = options.columns.get(column).filter(|_| !is_header);
+                        let cell_map = map.slice(raw.text_start..raw.range.end);
+                        let cell_span = map.span(raw.range.clone());
+                        let (cell, cell_spans) = self.table_cell(raw, column, &cell_map);
+                        if let (true, Some(span)) = (mapped, cell_span) {
+                            let path = row_path.child(position);
+                            spans.insert(path.clone(), span);
+                            nest(&mut spans, &path, cell_spans);
+                        }
+                        cell
+                    })
+                    .collect();
+                TableRow {
+                    cells,
+                    is_header,
+                    is_footer: options.footer && index + 1 == row_count && !is_header,
+                }
+            })
+            .collect();
 
-        match state {
-            ParserState::Root => {}
-            ParserState::Paragraph(lines) => {
-                if !lines.is_empty() {
-                    let text = lines.join(" ");
-                    let inlines = parse_inlines(&text);
-                    self.blocks.push(Block::Paragraph(Paragraph {
-                        inlines,
-                        style_id: None,
-                        attributes: HashMap::new(),
-                    }));
+        let table = Table {
+            rows,
+            style_id: None,
+            caption: self.pending_title.take().map(|title| parse_inlines(&title)),
+            columns: options.columns,
+        };
+        (table, spans)
+    }
+
+    /// Turn a cell's text into blocks according to its style
+    ///
+    /// Returns the spans of the cell content, with `map` mapping the cell
+    /// text.
+    fn table_cell(
+        &mut self,
+        raw: RawCell,
+        column: Option<&ColumnSpec>,
+        map: &TextMap,
+    ) -> (TableCell, Spans) {
+        let style = raw.spec.style.or(column.and_then(|c| c.style));
+        let mut spans = Spans::new();
+        let content = match style {
+            Some(CellStyle::AsciiDoc) => {
+                // AsciiDoc cells are parsed as a nested document
+                let (doc, diagnostics) = Parser::with_config(self.config.clone()).parse(&raw.text);
+                for diagnostic in diagnostics {
+                    self.warn(map_diagnostic(diagnostic, map));
                 }
+                spans = map.map_spans(doc.source_map.spans);
+                doc.blocks
             }
-            ParserState::List(list_type, items) => {
-                if !items.is_empty() {
-                    self.blocks.push(Block::List(List {
-                        list_type,
-                        items,
-                        style_id: None,
-                    }));
+            Some(CellStyle::Literal) => {
+                if let Some(span) = map.span(0..map.text_len()) {
+                    spans.insert(NodePath::block(0), span);
                 }
+                vec![Block::Literal(LiteralBlock {
+                    content: raw.text,
+                    language: None,
+                    title: None,
+                    style_id: None,
+                    callouts: Vec::new(),
+                    highlight_lines: Vec::new(),
+                })]
             }
-            ParserState::Table {
-                mut rows,
-                current_row,
-                col_count: _,
-            } => {
-                // Push any remaining current_row to rows
-                if !current_row.is_empty() {
-                    rows.push(current_row);
-                }
-                if !rows.is_empty() {
-                    // Convert Vec<Vec<TableCell>> to Vec<TableRow>
-                    let table_rows: Vec<TableRow> = rows
-                        .into_iter()
-                        .map(|cells| TableRow {
-                            cells,
-                            is_header: false,
-                        })
+            _ => {
+                let mut paragraphs = Vec::new();
+                for part in raw.text.split("\n\n").map(str::trim) {
+                    if part.is_empty() {
+                        continue;
+                    }
+                    let lines: Vec<&str> = part.lines().map(str::trim).collect();
+                    let ranges: Vec<Range<usize>> = lines
+                        .iter()
+                        .map(|line| subslice_range(&raw.text, line))
                         .collect();
-                    self.blocks.push(Block::Table(Table {
-                        rows: table_rows,
+                    let (paragraph, paragraph_spans) =
+                        mapped_paragraph(&lines.join(" "), &map.join(&ranges));
+                    nest(
+                        &mut spans,
+                        &NodePath::block(paragraphs.len()),
+                        paragraph_spans,
+                    );
+                    paragraphs.push(paragraph);
+                }
+                if paragraphs.is_empty() {
+                    vec![Block::Paragraph(Paragraph {
+                        inlines: parse_inlines(""),
                         style_id: None,
-                        caption: None,
-                        columns: vec![],
-                    }));
+                        attributes: HashMap::new(),
+                    })]
+                } else {
+                    paragraphs
                 }
             }
-            ParserState::Literal(lines) => {
-                // Create literal block with content and pending attributes
-                let content = lines.join("\n");
-
-                // Parse pending attributes to extract language and style
-                let (language, style_id) = self.parse_block_attributes();
-
-                self.blocks.push(Block::Literal(LiteralBlock {
-                    content,
-                    language,
-                    title: None,
-                    style_id,
-                }));
+        };
 
-                // Clear pending attributes after use
-                self.pending_attributes.clear();
-            }
-        }
+        let cell = TableCell {
+            content,
+            colspan: raw.spec.colspan,
+            rowspan: raw.spec.rowspan,
+            align: raw.spec.align.or(column.and_then(|c| c.align)),
+            style,
+        };
+        (cell, spans)
     }
 
     /// Parse pending block attributes to extract language and style_id.
@@ -683,37 +2066,16 @@ impl Parser {
 
         // Check for source block: [source,lang] or [source]
         if attr.starts_with("source") {
-            if let Some(comma_pos) = attr.find(',') {
-                let lang = attr[comma_pos + 1..].trim().to_string();
-                return (Some(lang), None);
-            }
-            return (None, None);
+            let language = split_attribute_list(attr)
+                .into_iter()
+                .nth(1)
+                .filter(|lang| !lang.is_empty() && !lang.contains('='));
+            return (language, None);
         }
 
         // Check for known diagram types
-        let known_diagram_types = [
-            "mermaid",
-            "plantuml",
-            "graphviz",
-            "ditaa",
-            "d2",
-            "blockdiag",
-            "seqdiag",
-            "actdiag",
-            "nwdiag",
-            "c4plantuml",
-            "svgbob",
-            "vega",
-            "vegalite",
-            "wavedrom",
-            "bytefield",
-            "erd",
-            "nomnoml",
-            "pikchr",
-        ];
-
         let attr_lower = attr.to_lowercase();
-        for diagram_type in known_diagram_types {
+        for diagram_type in DIAGRAM_TYPES {
             if attr_lower == diagram_type {
                 return (None, Some(attr.to_string()));
             }
@@ -724,20 +2086,154 @@ impl Parser {
     }
 }
 
+/// Diagram block styles, rendered by `utf8dok-diagrams`
+pub(crate) const DIAGRAM_TYPES: [&str; 18] = [
+    "mermaid",
+    "plantuml",
+    "graphviz",
+    "ditaa",
+    "d2",
+    "blockdiag",
+    "seqdiag",
+    "actdiag",
+    "nwdiag",
+    "c4plantuml",
+    "svgbob",
+    "vega",
+    "vegalite",
+    "wavedrom",
+    "bytefield",
+    "erd",
+    "nomnoml",
+    "pikchr",
+];
+
+/// Block styles and section styles the parser or a backend understands
+const BLOCK_STYLES: [&str; 41] = [
+    "source",
+    "listing",
+    "literal",
+    "pass",
+    "normal",
+    "stem",
+    "latexmath",
+    "asciimath",
+    "verse",
+    "quote",
+    "example",
+    "sidebar",
+    "open",
+    "abstract",
+    "partintro",
+    "comment",
+    "NOTE",
+    "TIP",
+    "IMPORTANT",
+    "WARNING",
+    "CAUTION",
+    "discrete",
+    "float",
+    "appendix",
+    "glossary",
+    "bibliography",
+    "preface",
+    "colophon",
+    "dedication",
+    "index",
+    "arabic",
+    "decimal",
+    "loweralpha",
+    "upperalpha",
+    "lowerroman",
+    "upperroman",
+    "lowergreek",
+    "horizontal",
+    "qanda",
+    "slides",
+    "notes",
+];
+
+/// Whether `style` is a known block style (diagram types in any case)
+fn is_known_style(style: &str) -> bool {
+    BLOCK_STYLES.contains(&style) || DIAGRAM_TYPES.contains(&style.to_lowercase().as_str())
+}
+
 /// Parse inline formatting in text
 fn parse_inlines(text: &str) -> Vec<Inline> {
-    // Regex patterns for inline formatting
-    // Order matters: we process left-to-right
-    let bold_re = Regex::new(r"\*([^*]+)\*").unwrap();
-    let italic_re = Regex::new(r"_([^_]+)_").unwrap();
-    let mono_re = Regex::new(r"`([^`]+)`").unwrap();
-    // Cross-reference: <<anchor,text>> or <<anchor>>
-    let xref_re = Regex::new(r"<<([^,>]+),([^>]+)>>|<<([^>]+)>>").unwrap();
-    // Inline anchor: [[name]]
-    let anchor_re = Regex::new(r"\[\[([^\]]+)\]\]").unwrap();
+    parse_inline_ranges(text).0
+}
+
+/// Parse inline formatting in text, with the spans of the inlines mapped
+/// through `map`
+fn parse_mapped_inlines(text: &str, map: &TextMap) -> (Vec<Inline>, Spans) {
+    let (inlines, ranges) = parse_inline_ranges(text);
+    let spans = ranges
+        .into_iter()
+        .filter_map(|(path, range)| Some((path, map.span(range)?)))
+        .collect();
+    (inlines, spans)
+}
+
+/// Parse a paragraph, with its spans (and its own, at the empty path)
+/// mapped through `map`
+fn mapped_paragraph(text: &str, map: &TextMap) -> (Block, Spans) {
+    let (inlines, mut spans) = parse_mapped_inlines(text, map);
+    if let Some(span) = map.span(0..text.len()) {
+        spans.insert(NodePath::default(), span);
+    }
+    let paragraph = Block::Paragraph(Paragraph {
+        inlines,
+        style_id: None,
+        attributes: HashMap::new(),
+    });
+    (paragraph, spans)
+}
+
+/// Regex patterns for inline formatting, compiled once
+struct InlinePatterns {
+    bold: Regex,
+    italic: Regex,
+    mono: Regex,
+    /// Cross-reference: `<<anchor,text>>` or `<<anchor>>`
+    xref: Regex,
+    /// Inline anchor: `[[name]]`
+    anchor: Regex,
+    /// Footnote: `footnote:[text]` or `footnote:id[text]` (`\]` escapes a
+    /// bracket)
+    footnote: Regex,
+}
+
+impl InlinePatterns {
+    fn get() -> &'static Self {
+        static PATTERNS: OnceLock<InlinePatterns> = OnceLock::new();
+        PATTERNS.get_or_init(|| Self {
+            bold: Regex::new(r"\*([^*]+)\*").unwrap(),
+            italic: Regex::new(r"_([^_]+)_").unwrap(),
+            mono: Regex::new(r"`([^`]+)`").unwrap(),
+            xref: Regex::new(r"<<([^,>]+),([^>]+)>>|<<([^>]+)>>").unwrap(),
+            anchor: Regex::new(r"\[\[([^\]]+)\]\]").unwrap(),
+            footnote: Regex::new(r"footnote:([\w-]*)\[((?:\\\]|[^\]])*)\]").unwrap(),
+        })
+    }
+}
+
+/// Parse inline formatting in text, with the byte range of each inline
+/// (keyed by its path below the text)
+fn parse_inline_ranges(text: &str) -> (Vec<Inline>, BTreeMap<NodePath, Range<usize>>) {
+    let InlinePatterns {
+        bold: bold_re,
+        italic: italic_re,
+        mono: mono_re,
+        xref: xref_re,
+        anchor: anchor_re,
+        footnote: footnote_re,
+    } = InlinePatterns::get();
 
     let mut result = Vec::new();
+    let mut ranges = BTreeMap::new();
     let mut remaining = text;
+    // Offset of `remaining` in `text`
+    let mut base = 0;
 
     while !remaining.is_empty() {
         // Find the earliest match of any formatting
@@ -746,6 +2242,9 @@ fn parse_inlines(text: &str) -> Vec<Inline> {
         let mono_match = mono_re.find(remaining);
         let xref_match = xref_re.find(remaining);
         let anchor_match = anchor_re.find(remaining);
+        let footnote_match = footnote_re.find(remaining);
+        // Only verse lines are joined with newlines
+        let break_match = remaining.find('\n');
 
         // Determine which match comes first
         let earliest = [
@@ -754,6 +2253,8 @@ fn parse_inlines(text: &str) -> Vec<Inline> {
             mono_match.map(|m| (m.start(), m.end(), "mono")),
             xref_match.map(|m| (m.start(), m.end(), "xref")),
             anchor_match.map(|m| (m.start(), m.end(), "anchor")),
+            footnote_match.map(|m| (m.start(), m.end(), "footnote")),
+            break_match.map(|at| (at, at + 1, "break")),
         ]
         .into_iter()
         .flatten()
@@ -763,11 +2264,18 @@ fn parse_inlines(text: &str) -> Vec<Inline> {
             Some((start, end, format_type)) => {
                 // Add any text before the match
                 if start > 0 {
+                    ranges.insert(NodePath::block(result.len()), base..base + start);
                     result.push(Inline::Text(remaining[..start].to_string()));
                 }
 
                 // Extract the content inside the markers
                 let matched = &remaining[start..end];
+                let path = NodePath::block(result.len());
+                let at = base + start;
+                ranges.insert(path.clone(), at..base + end);
+                if matches!(format_type, "bold" | "italic" | "mono") {
+                    ranges.insert(path.child(0), at + 1..base + end - 1);
+                }
 
                 // Create the appropriate inline element
                 let inline = match format_type {
@@ -795,6 +2303,9 @@ fn parse_inlines(text: &str) -> Vec<Inline> {
                     "xref" => {
                         // Parse cross-reference: <<anchor,text>> or <<anchor>>
                         if let Some(caps) = xref_re.captures(matched) {
+                            if let Some(text) = caps.get(2).or_else(|| caps.get(3)) {
+                                ranges.insert(path.child(0), at + text.start()..at + text.end());
+                            }
                             if let (Some(anchor), Some(text_match)) = (caps.get(1), caps.get(2)) {
                                 // <<anchor,text>> format
                                 Inline::Link(Link {
@@ -828,16 +2339,60 @@ fn parse_inlines(text: &str) -> Vec<Inline> {
                             Inline::Text(matched.to_string())
                         }
                     }
+                    "footnote" => {
+                        // Parse footnote: footnote:id[text]
+                        match footnote_re.captures(matched) {
+                            Some(caps) => {
+                                let id = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+                                let raw = caps.get(2).unwrap();
+                                let content = raw.as_str().replace("\\]", "]");
+                                let trimmed = content.trim();
+                                let (inlines, inner) = if trimmed.is_empty() {
+                                    Default::default()
+                                } else {
+                                    parse_inline_ranges(trimmed)
+                                };
+                                // Map positions in the unescaped, trimmed
+                                // text back to the raw content
+                                let lead = content.len() - content.trim_start().len();
+                                let escapes: Vec<usize> = raw
+                                    .as_str()
+                                    .match_indices("\\]")
+                                    .enumerate()
+                                    .map(|(k, (position, _))| position - k)
+                                    .collect();
+                                let to_raw = |p: usize| {
+                                    let p = p + lead;
+                                    at + raw.start()
+                                        + p
+                                        + escapes.iter().filter(|&&e| e < p).count()
+                                };
+                                for (inner_path, range) in inner {
+                                    let nested =
+                                        NodePath([path.steps(), inner_path.steps()].concat());
+                                    ranges.insert(nested, to_raw(range.start)..to_raw(range.end));
+                                }
+                                Inline::Footnote(Footnote {
+                                    id: (!id.is_empty()).then(|| id.to_string()),
+                                    content: inlines,
+                                })
+                            }
+                            None => Inline::Text(matched.to_string()),
+                        }
+                    }
+                    "break" => Inline::Break,
                     _ => unreachable!(),
                 };
                 result.push(inline);
 
                 // Continue with the rest
                 remaining = &remaining[end..];
+                base += end;
             }
             None => {
                 // No more formatting, add remaining text
                 if !remaining.is_empty() {
+                    ranges.insert(NodePath::block(result.len()), base..text.len());
                     result.push(Inline::Text(remaining.to_string()));
                 }
                 break;
@@ -847,10 +2402,402 @@ fn parse_inlines(text: &str) -> Vec<Inline> {
 
     // Handle empty input
     if result.is_empty() && text.is_empty() {
+        ranges.insert(NodePath::block(0), 0..0);
         result.push(Inline::Text(String::new()));
     }
 
-    result
+    (result, ranges)
+}
+
+/// Check for a table delimiter: `|===`, `,===` (CSV), `:===` (DSV), or
+/// `!===` (nested in an AsciiDoc cell)
+fn is_table_delimiter(line: &str) -> bool {
+    let mut chars = line.chars();
+    matches!(chars.next(), Some('|' | ',' | ':' | '!'))
+        && line.len() >= 4
+        && chars.all(|c| c == '=')
+}
+
+/// Check for a literal block delimiter (`----` or more dashes)
+pub(crate) fn is_literal_delimiter(line: &str) -> bool {
+    line.len() >= 4 && line.chars().all(|c| c == '-')
+}
+
+/// Check for a literal block delimiter (`....` or more dots)
+pub(crate) fn is_dotted_delimiter(line: &str) -> bool {
+    line.len() >= 4 && line.chars().all(|c| c == '.')
+}
+
+/// Check if a line delimits a comment block (`////` or more slashes)
+pub(crate) fn is_comment_delimiter(line: &str) -> bool {
+    line.len() >= 4 && line.chars().all(|c| c == '/')
+}
+
+/// Text of a `//` comment line, without the marker and one following space
+fn line_comment(line: &str) -> Option<&str> {
+    let text = line.strip_prefix("//")?;
+    if text.starts_with('/') {
+        return None;
+    }
+    Some(text.strip_prefix(' ').unwrap_or(text))
+}
+
+/// Recognize the delimiter line of a compound block
+///
+/// `--` has a fixed length; the others are four or more repeated
+/// characters, so the same kind of block can be nested with a longer
+/// delimiter.
+fn compound_delimiter(line: &str) -> Option<CompoundKind> {
+    if line == "--" {
+        return Some(CompoundKind::Open);
+    }
+    let first = line.chars().next()?;
+    if line.len() < 4 || !line.chars().all(|c| c == first) {
+        return None;
+    }
+    match first {
+        '=' => Some(CompoundKind::Example),
+        '*' => Some(CompoundKind::Sidebar),
+        '_' => Some(CompoundKind::Quote),
+        _ => None,
+    }
+}
+
+/// Map an admonition label (`NOTE`, `TIP`, ...) to its type
+fn admonition_type(label: &str) -> Option<AdmonitionType> {
+    match label {
+        "NOTE" => Some(AdmonitionType::Note),
+        "TIP" => Some(AdmonitionType::Tip),
+        "IMPORTANT" => Some(AdmonitionType::Important),
+        "WARNING" => Some(AdmonitionType::Warning),
+        "CAUTION" => Some(AdmonitionType::Caution),
+        _ => None,
+    }
+}
+
+/// Recognize an admonition paragraph (`NOTE: text`), returning its type
+/// and the text after the label
+fn admonition_paragraph(text: &str) -> Option<(AdmonitionType, &str)> {
+    let (label, rest) = text.split_once(": ")?;
+    Some((admonition_type(label)?, rest.trim_start()))
+}
+
+/// Extract the ID of a block anchor line (`[[id]]` or `[[id, reftext]]`)
+fn block_anchor(line: &str) -> Option<&str> {
+    let inner = line.trim_end().strip_prefix("[[")?.strip_suffix("]]")?;
+    let id = inner.split(',').next()?.trim();
+    let valid = !id.is_empty()
+        && !id
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '[' | ']' | ','));
+    valid.then_some(id)
+}
+
+/// Extract the text of a block title line (`.Title`)
+fn block_title(line: &str) -> Option<&str> {
+    let title = line.strip_prefix('.')?;
+    match title.chars().next() {
+        Some(c) if !c.is_whitespace() && c != '.' => Some(title.trim_end()),
+        _ => None,
+    }
+}
+
+/// Parse a description list item: `term:: definition`
+///
+/// Returns the nesting level (`::`, `:::`, `::::`, `;;`), the term and the
+/// definition, which is empty when it starts on the next line.
+pub(crate) fn description_item(line: &str) -> Option<(usize, &str, &str)> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        let marker = bytes[i];
+        if (marker == b':' || marker == b';') && bytes[i + 1] == marker {
+            let run = bytes[i..].iter().take_while(|&&b| b == marker).count();
+            let level = match (marker, run) {
+                (b':', 2..=4) => Some(run - 2),
+                (b';', 2) => Some(3),
+                _ => None,
+            };
+            let rest = &line[i + run..];
+            let term = line[..i].trim();
+            if let Some(level) = level {
+                if !term.is_empty() && (rest.is_empty() || rest.starts_with([' ', '\t'])) {
+                    return Some((level, term, rest.trim()));
+                }
+            }
+            i += run;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Parse an unordered list item (`* item`, `** item`, `- item`),
+/// returning its nesting level (0 for `*` and `-`) and text
+pub(crate) fn unordered_item(line: &str) -> Option<(usize, &str)> {
+    if let Some(text) = line.strip_prefix("- ") {
+        return Some((0, text.trim()));
+    }
+    marked_item(line, '*')
+}
+
+/// Parse an ordered list item (`. item`, `.. item`), returning its nesting
+/// level (0 for `.`) and text
+pub(crate) fn ordered_item(line: &str) -> Option<(usize, &str)> {
+    marked_item(line, '.')
+}
+
+/// Parse an explicitly numbered list item (`1. item`, `a. item`,
+/// `A. item`, `i) item`, `I) item`), returning the numbering style its
+/// marker implies, its number and its text
+pub(crate) fn numbered_item(line: &str) -> Option<(NumberingStyle, u32, &str)> {
+    let (marker, text) = line.split_once(' ')?;
+    let (numbering, number) = if let Some(number) = marker.strip_suffix('.') {
+        let mut letters = number.chars();
+        match (letters.next(), letters.next()) {
+            _ if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) => {
+                (NumberingStyle::Arabic, number.parse().ok()?)
+            }
+            (Some(letter @ 'a'..='z'), None) => {
+                (NumberingStyle::LowerAlpha, letter as u32 - 'a' as u32 + 1)
+            }
+            (Some(letter @ 'A'..='Z'), None) => {
+                (NumberingStyle::UpperAlpha, letter as u32 - 'A' as u32 + 1)
+            }
+            _ => return None,
+        }
+    } else {
+        let numeral = marker.strip_suffix(')')?;
+        let numbering = if numeral.bytes().all(|b| b"ivxlcdm".contains(&b)) {
+            NumberingStyle::LowerRoman
+        } else if numeral.bytes().all(|b| b"IVXLCDM".contains(&b)) {
+            NumberingStyle::UpperRoman
+        } else {
+            return None;
+        };
+        (numbering, roman_value(numeral)?)
+    };
+    Some((numbering, number, text.trim()))
+}
+
+/// The value of a roman numeral, in either case
+fn roman_value(numeral: &str) -> Option<u32> {
+    let digits: Vec<u32> = numeral
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            'i' => 1,
+            'v' => 5,
+            'x' => 10,
+            'l' => 50,
+            'c' => 100,
+            'd' => 500,
+            _ => 1000,
+        })
+        .collect();
+    let value = digits.iter().enumerate().fold(0, |value, (i, &digit)| {
+        match digits.get(i + 1).is_some_and(|&next| next > digit) {
+            true => value - digit as i64,
+            false => value + digit as i64,
+        }
+    });
+    u32::try_from(value).ok().filter(|&value| value > 0)
+}
+
+/// Parse a list item whose marker repeats `marker` once per nesting level
+/// and is followed by a space
+fn marked_item(line: &str, marker: char) -> Option<(usize, &str)> {
+    let count = line.chars().take_while(|&c| c == marker).count();
+    if count == 0 || !line[count..].starts_with(' ') {
+        return None;
+    }
+    Some((count - 1, line[count + 1..].trim()))
+}
+
+/// Parse a callout list item (`<1> text`, `<.> text`), returning its text
+pub(crate) fn callout_item(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix('<')?;
+    let end = rest.find('>')?;
+    let marker = &rest[..end];
+    let text = &rest[end + 1..];
+    let numbered =
+        marker == "." || (!marker.is_empty() && marker.bytes().all(|b| b.is_ascii_digit()));
+    (numbered && text.starts_with([' ', '\t'])).then(|| text.trim())
+}
+
+/// Strip callout markers (`<1>`, `<.>`, `// <1>`, `<!--1-->`) from the
+/// ends of source lines
+///
+/// `<.>` markers are numbered after the previous callout.
+fn extract_callouts(lines: &[String]) -> (String, Vec<Callout>) {
+    static TRAILING: OnceLock<Regex> = OnceLock::new();
+    static MARKER: OnceLock<Regex> = OnceLock::new();
+    let trailing_re = TRAILING.get_or_init(|| {
+        Regex::new(r"(?:\s*(?://|#|--|;;)?\s*(?:<(?:\d+|\.)>|<!--(?:\d+|\.)-->))+\s*$").unwrap()
+    });
+    let marker_re = MARKER.get_or_init(|| Regex::new(r"<(\d+|\.)>|<!--(\d+|\.)-->").unwrap());
+
+    let mut callouts: Vec<Callout> = Vec::new();
+    let mut content = Vec::with_capacity(lines.len());
+    for (index, line) in lines.iter().enumerate() {
+        let Some(found) = trailing_re.find(line) else {
+            content.push(line.as_str());
+            continue;
+        };
+        for caps in marker_re.captures_iter(found.as_str()) {
+            let marker = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+            let number = match marker.parse() {
+                Ok(number) => number,
+                Err(_) => callouts.last().map_or(1, |callout| callout.number + 1),
+            };
+            callouts.push(Callout {
+                number,
+                line: index as u32 + 1,
+            });
+        }
+        content.push(&line[..found.start()]);
+    }
+    (content.join("\n"), callouts)
+}
+
+/// Parse line ranges such as `1,3..5` or `2-4;7` into sorted line numbers
+fn line_ranges(spec: &str) -> Vec<u32> {
+    let mut lines: Vec<u32> = spec
+        .split([',', ';', ' '])
+        .filter_map(|part| {
+            let part = part.trim();
+            let (start, end) = part
+                .split_once("..")
+                .or_else(|| part.split_once('-'))
+                .unwrap_or((part, part));
+            Some(start.trim().parse().ok()?..=end.trim().parse().ok()?)
+        })
+        .flatten()
+        .collect();
+    lines.sort_unstable();
+    lines.dedup();
+    lines
+}
+
+/// Split a checklist marker (`[x]`, `[*]`, `[ ]`) off an unordered item
+fn checklist_marker(content: &str) -> (Option<bool>, &str) {
+    for (marker, checked) in [
+        ("[x] ", true),
+        ("[X] ", true),
+        ("[*] ", true),
+        ("[ ] ", false),
+    ] {
+        if let Some(rest) = content.strip_prefix(marker) {
+            return (Some(checked), rest.trim_start());
+        }
+    }
+    (None, content)
+}
+
+/// Append the inlines of a continuation line to already parsed paragraph
+/// inlines
+///
+/// Returns the index the line's first inline ended up at; it is merged into
+/// the last inline when both are text.
+fn append_line(inlines: &mut Vec<Inline>, continuation: Vec<Inline>) -> usize {
+    let mut continuation = continuation.into_iter();
+    let first = match (inlines.last_mut(), continuation.next()) {
+        (Some(Inline::Text(last)), Some(Inline::Text(first))) => {
+            last.push(' ');
+            last.push_str(&first);
+            inlines.len() - 1
+        }
+        (_, Some(first)) => {
+            inlines.push(Inline::Text(" ".to_string()));
+            inlines.push(first);
+            inlines.len() - 1
+        }
+        (_, None) => inlines.len(),
+    };
+    inlines.extend(continuation);
+    first
+}
+
+/// Number of children of a list item (term inlines, then content blocks)
+fn item_child_count(item: &ListItem) -> usize {
+    item.term.as_ref().map_or(0, Vec::len) + item.content.len()
+}
+
+/// Extend the span at `path` to `end`
+fn extend_span(spans: &mut Spans, path: &NodePath, end: Option<usize>) {
+    if let (Some(span), Some(end)) = (spans.get_mut(path), end) {
+        span.end = span.end.max(end);
+    }
+}
+
+/// Map the span of a diagnostic found in assembled text (e.g. by a nested
+/// parser) to the source
+fn map_diagnostic(mut diagnostic: Diagnostic, map: &TextMap) -> Diagnostic {
+    if let Some(span) = diagnostic
+        .span
+        .as_ref()
+        .and_then(|span| map.span(span.start..span.end))
+    {
+        diagnostic.span = Some(span.into());
+    }
+    diagnostic
+}
+
+/// The roles given by block attribute lists, space separated: the `.role`
+/// shorthands after the style (`[.draft]`, `[quote.lead.draft]`) and the
+/// `role` attribute
+fn block_roles(attribute_lists: &[String]) -> Option<String> {
+    let mut roles = Vec::new();
+    for (position, attrs) in attribute_lists.iter().enumerate() {
+        for (index, attr) in split_attribute_list(attrs).into_iter().enumerate() {
+            if let Some(value) = attr.strip_prefix("role=") {
+                roles.extend(value.split_whitespace().map(str::to_string));
+            } else if position == 0 && index == 0 && !attr.contains('=') {
+                let mut rest = attr.as_str();
+                while let Some(start) = rest.find(['#', '.', '%']) {
+                    let end = rest[start + 1..]
+                        .find(['#', '.', '%'])
+                        .map_or(rest.len(), |end| start + 1 + end);
+                    if rest[start..].starts_with('.') && end > start + 1 {
+                        roles.push(rest[start + 1..end].to_string());
+                    }
+                    rest = &rest[end..];
+                }
+            }
+        }
+    }
+    (!roles.is_empty()).then(|| roles.join(" "))
+}
+
+/// Split a block attribute list on commas, honouring double-quoted values
+///
+/// `quote, "Doe, Jane", Memoirs` yields `quote`, `Doe, Jane` and `Memoirs`.
+fn split_attribute_list(attrs: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in attrs.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => values.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    values.push(current.trim().to_string());
+    values
+}
+
+/// Shift the level of a heading line by `offset` (`leveloffset=`)
+///
+/// Levels are clamped to the document title level; other lines are
+/// returned unchanged.
+fn offset_heading(line: &str, offset: i32) -> String {
+    let marker = line.chars().take_while(|&c| c == '=').count();
+    if marker == 0 || !line[marker..].starts_with(' ') {
+        return line.to_string();
+    }
+    let level = (marker as i32 + offset).max(1) as usize;
+    format!("{}{}", "=".repeat(level), &line[marker..])
 }
 
 /// Parse AsciiDoc text into an AST Document.
@@ -869,8 +2816,8 @@ fn parse_inlines(text: &str) -> Vec<Inline> {
 /// Currently, the parser is lenient and will not fail on unknown syntax.
 /// Unknown constructs are treated as plain paragraph text.
 pub fn parse(text: &str) -> Result<Document> {
-    let parser = Parser::new();
-    parser.parse(text)
+    let (document, _) = Parser::new().parse(text);
+    Ok(document)
 }
 
 /// Parse AsciiDoc text with configuration options
@@ -894,8 +2841,97 @@ pub fn parse(text: &str) -> Result<Document> {
 /// let doc = parse_with_config(input, config)?;
 /// ```
 pub fn parse_with_config(text: &str, config: ParserConfig) -> Result<Document> {
-    let parser = Parser::with_config(config);
-    parser.parse(text)
+    let (document, _) = Parser::with_config(config).parse(text);
+    Ok(document)
+}
+
+/// Parse AsciiDoc text, returning the problems recovered from
+///
+/// Parsing never fails: unclosed blocks run to the end of the document,
+/// malformed attributes and unresolved includes are skipped or replaced
+/// by a placeholder, and each is reported as a [`Diagnostic`] with a code
+/// and a source span (see the [module documentation](self#diagnostics)).
+///
+/// # Example
+///
+/// ```ignore
+/// use utf8dok_core::{parse_with_diagnostics, ParserConfig};
+///
+/// let (doc, diagnostics) = parse_with_diagnostics("----\nopen", ParserConfig::default());
+/// assert_eq!(doc.blocks.len(), 1);
+/// assert!(diagnostics.has_errors());
+/// ```
+pub fn parse_with_diagnostics(text: &str, config: ParserConfig) -> (Document, Diagnostics) {
+    Parser::with_config(config).parse(text)
+}
+
+/// Parse AsciiDoc text, recording a [`Checkpoint`] wherever no block is
+/// open
+pub(crate) fn parse_with_checkpoints(
+    text: &str,
+    config: ParserConfig,
+) -> (Document, Diagnostics, Vec<Checkpoint>) {
+    let mut parser = Parser::with_config(config);
+    let mut checkpoints = Vec::new();
+    for line in parser.preprocess(text) {
+        checkpoints.extend(parser.checkpoint(&line));
+        parser.process_source_line(line);
+    }
+    let (document, diagnostics) = parser.finish();
+    (document, diagnostics, checkpoints)
+}
+
+/// Parse `text`, which has no preprocessor directives, from `start` (the
+/// beginning of the document when `None`)
+///
+/// `start` comes with the document metadata of the parse that recorded it.
+/// Parsing stops before the first line whose checkpoint `stop` accepts;
+/// otherwise it runs to the end of the document. Lines are read as they
+/// are parsed, so the text after the stop is never looked at.
+pub(crate) fn parse_from(
+    text: &str,
+    config: ParserConfig,
+    start: Option<(&Checkpoint, DocumentMeta)>,
+    mut stop: impl FnMut(&Checkpoint) -> bool,
+) -> Resumed {
+    let mut parser = Parser::with_config(config);
+    let (offset, line) = match start {
+        Some((checkpoint, metadata)) => {
+            parser.restore(checkpoint, metadata);
+            (checkpoint.offset, checkpoint.line)
+        }
+        None => (0, 1),
+    };
+
+    let mut checkpoints = Vec::new();
+    for mut source in plain_lines(&text[offset..]) {
+        source.offset += offset;
+        source.line += line - 1;
+        if let Some(checkpoint) = parser.checkpoint(&source) {
+            if stop(&checkpoint) {
+                return Resumed {
+                    document: Document {
+                        metadata: parser.metadata,
+                        blocks: parser.blocks,
+                        intent: None,
+                        source_map: parser.source_map,
+                    },
+                    diagnostics: parser.diagnostics,
+                    checkpoints,
+                    stopped_at: Some(checkpoint),
+                };
+            }
+            checkpoints.push(checkpoint);
+        }
+        parser.process_source_line(source);
+    }
+    let (document, diagnostics) = parser.finish();
+    Resumed {
+        document,
+        diagnostics,
+        checkpoints,
+        stopped_at: None,
+    }
 }
 
 #[cfg(test)]
@@ -988,4 +3024,220 @@ mod tests {
             panic!("Expected Heading block");
         }
     }
+
+    #[test]
+    fn test_conditionals_select_backend_content() {
+        let input = "ifdef::backend-docx[]\nWord text\nendif::[]\n\nifdef::backend-pptx[]\nSlide text\nendif::[]";
+
+        let doc = parse_with_config(input, ParserConfig::default().with_backend("docx")).unwrap();
+        assert_eq!(doc.blocks.len(), 1);
+        if let Block::Paragraph(p) = &doc.blocks[0] {
+            assert_eq!(p.inlines, vec![Inline::Text("Word text".to_string())]);
+        } else {
+            panic!("Expected Paragraph block");
+        }
+
+        // Directives never leak into the output as literal paragraphs
+        let doc = parse(input).unwrap();
+        assert!(doc.blocks.is_empty());
+    }
+
+    #[test]
+    fn test_attribute_references_substituted() {
+        let input = "= Guide\n:author: Jane Doe\n:revnumber: 2.1\n:product: utf8dok\n\n\
+                     == About {product}\n\n\
+                     {doctitle} by {author}, v{revnumber}.\n\n\
+                     :product: utf8dok Pro\n\n\
+                     Now {product}.\n\n\
+                     ----\n{product}\n----";
+        let doc = parse(input).unwrap();
+
+        assert_eq!(doc.metadata.authors, vec!["Jane Doe".to_string()]);
+        assert_eq!(doc.metadata.revision, Some("2.1".to_string()));
+
+        let texts: Vec<String> = doc
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Heading(h) => format!("{:?}", h.text),
+                Block::Paragraph(p) => format!("{:?}", p.inlines),
+                Block::Literal(l) => l.content.clone(),
+                _ => String::new(),
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                format!("{:?}", vec![Inline::Text("About utf8dok".to_string())]),
+                format!(
+                    "{:?}",
+                    vec![Inline::Text("Guide by Jane Doe, v2.1.".to_string())]
+                ),
+                format!("{:?}", vec![Inline::Text("Now utf8dok Pro.".to_string())]),
+                // Literal blocks are verbatim
+                "{product}".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_author_and_revision_lines() {
+        let input =
+            "= Guide\nJane Q. Doe <jane@example.com>; John Smith\nv2.1, 2024-05-01: Draft\n\
+                     :toc:\n\n\
+                     {author} ({authorinitials}, {email}) and {author_2}.\n\n\
+                     {lastname}, {firstname}: v{revnumber} of {revdate}, {revremark}.";
+        let doc = parse(input).unwrap();
+
+        assert_eq!(
+            doc.metadata.authors,
+            vec!["Jane Q. Doe".to_string(), "John Smith".to_string()]
+        );
+        assert_eq!(doc.metadata.revision, Some("2.1".to_string()));
+        assert_eq!(
+            doc.metadata.attributes.get("email").map(String::as_str),
+            Some("jane@example.com")
+        );
+        assert_eq!(
+            doc.metadata.attributes.get("revdate").map(String::as_str),
+            Some("2024-05-01")
+        );
+        assert!(doc.metadata.attributes.contains_key("toc"));
+
+        let texts: Vec<_> = doc
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Paragraph(p) => p.inlines.clone(),
+                _ => Vec::new(),
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                vec![Inline::Text(
+                    "Jane Q. Doe (JQD, jane@example.com) and John Smith.".to_string()
+                )],
+                vec![Inline::Text(
+                    "Doe, Jane: v2.1 of 2024-05-01, Draft.".to_string()
+                )],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_author_entry_sets_name_parts() {
+        let input = "= Guide\n:author: Jane Doe\n\n{firstname} {lastname} ({authorinitials})";
+        let doc = parse(input).unwrap();
+
+        let Block::Paragraph(p) = &doc.blocks[0] else {
+            panic!("Expected paragraph");
+        };
+        assert_eq!(p.inlines, vec![Inline::Text("Jane Doe (JD)".to_string())]);
+    }
+
+    #[test]
+    fn test_attribute_missing_drop_line() {
+        let input = ":attribute-missing: drop-line\n\nKept\nDropped {nobody}\nAlso kept";
+        let doc = parse(input).unwrap();
+
+        assert_eq!(doc.blocks.len(), 1);
+        if let Block::Paragraph(p) = &doc.blocks[0] {
+            assert_eq!(p.inlines, vec![Inline::Text("Kept Also kept".to_string())]);
+        } else {
+            panic!("Expected Paragraph block");
+        }
+    }
+
+    #[test]
+    fn test_attribute_missing_warning() {
+        let mut parser = Parser::with_config(ParserConfig::default());
+        parser.current.line = 3;
+        assert_eq!(
+            parser.substitute_attributes("See {nobody}", 0).as_deref(),
+            Some("See {nobody}")
+        );
+        assert_eq!(parser.diagnostics.len(), 1);
+        let diagnostic = parser.diagnostics.iter().next().unwrap();
+        assert_eq!(diagnostic.code.as_deref(), Some("ATTR101"));
+
+        let config = ParserConfig {
+            attribute_missing: AttributeMissing::Skip,
+            ..Default::default()
+        };
+        let mut parser = Parser::with_config(config);
+        parser.substitute_attributes("See {nobody}", 0);
+        assert!(parser.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_parse_footnotes() {
+        let inlines = parse_inlines(
+            "Claim.footnote:[See *RFC 2119*.] Again.footnote:rfc[Key words \\] apply.] Reused.footnote:rfc[]",
+        );
+        assert_eq!(
+            inlines,
+            vec![
+                Inline::Text("Claim.".to_string()),
+                Inline::Footnote(Footnote {
+                    id: None,
+                    content: vec![
+                        Inline::Text("See ".to_string()),
+                        Inline::Format(
+                            FormatType::Bold,
+                            Box::new(Inline::Text("RFC 2119".to_string()))
+                        ),
+                        Inline::Text(".".to_string()),
+                    ],
+                }),
+                Inline::Text(" Again.".to_string()),
+                Inline::Footnote(Footnote {
+                    id: Some("rfc".to_string()),
+                    content: vec![Inline::Text("Key words ] apply.".to_string())],
+                }),
+                Inline::Text(" Reused.".to_string()),
+                Inline::Footnote(Footnote {
+                    id: Some("rfc".to_string()),
+                    content: vec![],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_callouts() {
+        let lines: Vec<String> = [
+            "use std::io; // <1>",
+            "let x = 1; <2> <3>",
+            "print(x)  # <.>",
+            "<item/> <!--5-->",
+            "let v: Vec<T> = vec![];",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+
+        let (content, callouts) = extract_callouts(&lines);
+        assert_eq!(
+            content,
+            "use std::io;\nlet x = 1;\nprint(x)\n<item/>\nlet v: Vec<T> = vec![];"
+        );
+        let pairs: Vec<(u32, u32)> = callouts.iter().map(|c| (c.number, c.line)).collect();
+        assert_eq!(pairs, vec![(1, 1), (2, 2), (3, 2), (4, 3), (5, 4)]);
+    }
+
+    #[test]
+    fn test_line_ranges() {
+        assert_eq!(line_ranges("3..5"), vec![3, 4, 5]);
+        assert_eq!(line_ranges("1,4-5;2"), vec![1, 2, 4, 5]);
+        assert!(line_ranges("x").is_empty());
+    }
+
+    #[test]
+    fn test_callout_item() {
+        assert_eq!(callout_item("<1> Imports"), Some("Imports"));
+        assert_eq!(callout_item("<.> Auto"), Some("Auto"));
+        assert_eq!(callout_item("<<xref>> text"), None);
+        assert_eq!(callout_item("<b> bold"), None);
+    }
 }
@@ -0,0 +1,934 @@
+//! Conditional preprocessing
+//!
+//! This module evaluates AsciiDoc preprocessor conditionals before block
+//! parsing, so that only the lines selected for the current render reach
+//! the parser.
+//!
+//! # Syntax
+//!
+//! ```text
+//! ifdef::backend-docx[]
+//! Only in Word output.
+//! endif::backend-docx[]
+//!
+//! ifndef::draft[]
+//! Hidden while :draft: is set.
+//! endif::[]
+//!
+//! ifdef::backend-pptx[Single-line form, only in slides.]
+//!
+//! ifdef::audience-exec,audience-board[]   (any attribute set)
+//! ifdef::approved+signed[]                 (all attributes set)
+//!
+//! ifeval::[{revnumber} >= 2]
+//! ifeval::["{classification}" == "internal"]
+//! ```
+//!
+//! Conditionals nest. Attributes come from the render-time attributes in
+//! [`ParserConfig::attributes`](crate::ParserConfig) (e.g. `backend-docx`)
+//! and from `:name: value` / `:name!:` entries seen earlier in the document.
+//! Render-time attributes take precedence over document entries.
+//!
+//! # Text Includes
+//!
+//! When an include directory is configured, `include::` directives for
+//! non-data files are inlined recursively, honouring `leveloffset=`,
+//! `lines=` and `tags=` (see [`crate::include`]). Every emitted line keeps
+//! the file and line number it came from. Data includes (xlsx, csv, tsv)
+//! are passed through to the parser, which turns them into tables.
+//!
+//! # Diagnostic Codes
+//!
+//! - `PRE101`: `endif` without a matching `ifdef`/`ifndef`/`ifeval`
+//! - `PRE102`: Conditional not closed before end of document
+//! - `PRE103`: `endif` names a different attribute than the open conditional
+//! - `PRE104`: `ifeval` expression could not be evaluated
+//! - `INC101`: Include file not found or unreadable
+//! - `INC102`: Include cycle detected
+//! - `INC103`: Maximum include depth exceeded
+//! - `INC104`: Requested tag not found in included file
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::attributes::AttributeEntry;
+use crate::diagnostics::{Diagnostic, Diagnostics, Span};
+use crate::include::IncludeDirective;
+
+/// Default maximum nesting depth for text includes (as in Asciidoctor)
+pub const DEFAULT_MAX_INCLUDE_DEPTH: usize = 64;
+
+/// A source line that survived preprocessing
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreprocessedLine {
+    /// Line text (without the trailing newline)
+    pub text: String,
+    /// Line number in the file the line came from (1-indexed)
+    pub line: usize,
+    /// Byte offset of the line start in that file
+    pub offset: usize,
+    /// Included file the line came from (`None` for the root document)
+    pub file: Option<String>,
+    /// Section level offset from `leveloffset=` on enclosing includes
+    pub level_offset: i32,
+}
+
+/// Result of running the preprocessor over a document
+#[derive(Debug, Clone, Default)]
+pub struct PreprocessResult {
+    /// Lines selected for block parsing
+    pub lines: Vec<PreprocessedLine>,
+    /// Diagnostics raised while evaluating conditionals and includes
+    pub diagnostics: Diagnostics,
+}
+
+/// The kind of an open conditional
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ConditionalKind {
+    Ifdef,
+    Ifndef,
+    Ifeval,
+}
+
+/// An open conditional on the preprocessor stack
+#[derive(Debug, Clone)]
+struct Conditional {
+    /// Directive that opened the conditional
+    kind: ConditionalKind,
+    /// Attribute target as written (empty for `ifeval`)
+    target: String,
+    /// Whether lines inside this conditional are kept
+    active: bool,
+    /// Line where the conditional was opened (1-indexed)
+    line: usize,
+}
+
+/// A parsed preprocessor directive line
+#[derive(Debug, Clone, PartialEq)]
+enum Directive<'a> {
+    /// `ifdef::target[content]` / `ifndef::target[content]`
+    Ifdef {
+        negated: bool,
+        target: &'a str,
+        content: &'a str,
+    },
+    /// `ifeval::[expression]`
+    Ifeval { expression: &'a str },
+    /// `endif::target[]`
+    Endif { target: &'a str },
+}
+
+/// A raw line of a source file
+#[derive(Debug, Clone, Copy)]
+struct SourceLine<'a> {
+    /// Line number (1-indexed)
+    number: usize,
+    /// Byte offset of the line start
+    offset: usize,
+    /// Line text without line terminator
+    text: &'a str,
+}
+
+/// The file currently being preprocessed
+#[derive(Debug, Clone)]
+struct IncludeFrame {
+    /// Display path of the file (`None` for the root document)
+    file: Option<String>,
+    /// Directory used to resolve includes in this file
+    dir: Option<PathBuf>,
+    /// Canonical paths of the included files leading to this one
+    chain: Vec<PathBuf>,
+    /// Accumulated section level offset
+    level_offset: i32,
+}
+
+/// Conditional and include preprocessor
+///
+/// # Example
+///
+/// ```
+/// use utf8dok_core::preprocessor::Preprocessor;
+///
+/// let source = "ifdef::backend-docx[]\nWord only\nendif::[]\nAlways";
+/// let result = Preprocessor::new().with_attribute("backend-docx", "").process(source);
+///
+/// let lines: Vec<&str> = result.lines.iter().map(|l| l.text.as_str()).collect();
+/// assert_eq!(lines, vec!["Word only", "Always"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Preprocessor {
+    /// Render-time attributes (cannot be overridden by the document)
+    locked: HashMap<String, String>,
+    /// Attributes defined by the document so far
+    attributes: HashMap<String, String>,
+    /// Directory of the root document; text includes are resolved when set
+    include_dir: Option<PathBuf>,
+    /// Maximum nesting depth for text includes
+    max_include_depth: usize,
+}
+
+impl Default for Preprocessor {
+    fn default() -> Self {
+        Self {
+            locked: HashMap::new(),
+            attributes: HashMap::new(),
+            include_dir: None,
+            max_include_depth: DEFAULT_MAX_INCLUDE_DEPTH,
+        }
+    }
+}
+
+impl Preprocessor {
+    /// Create a preprocessor with no render-time attributes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a preprocessor seeded with render-time attributes
+    pub fn with_attributes(attributes: HashMap<String, String>) -> Self {
+        Self {
+            locked: attributes,
+            ..Self::default()
+        }
+    }
+
+    /// Add a render-time attribute
+    pub fn with_attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.locked.insert(name.into(), value.into());
+        self
+    }
+
+    /// Resolve text includes relative to this directory
+    pub fn with_include_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.include_dir = Some(dir.into());
+        self
+    }
+
+    /// Set the maximum nesting depth for text includes
+    pub fn with_max_include_depth(mut self, depth: usize) -> Self {
+        self.max_include_depth = depth;
+        self
+    }
+
+    /// Run the preprocessor over a complete document
+    pub fn process(mut self, text: &str) -> PreprocessResult {
+        let mut result = PreprocessResult::default();
+        let root = IncludeFrame {
+            file: None,
+            dir: self.include_dir.clone(),
+            chain: Vec::new(),
+            level_offset: 0,
+        };
+        let lines = split_lines(text);
+        self.process_lines(&lines, text.len(), &root, &mut result);
+        result
+    }
+
+    /// Preprocess the lines of one file
+    fn process_lines(
+        &mut self,
+        lines: &[SourceLine<'_>],
+        end_offset: usize,
+        frame: &IncludeFrame,
+        result: &mut PreprocessResult,
+    ) {
+        let mut stack: Vec<Conditional> = Vec::new();
+
+        for source in lines {
+            let (line, line_no) = (source.text, source.number);
+            let span =
+                Span::new(source.offset, source.offset + line.len()).with_position(line_no, 1);
+            let active = stack.iter().all(|c| c.active);
+
+            // Escaped directive: emit verbatim without the backslash
+            if let Some(rest) = line.strip_prefix('\\') {
+                if parse_directive(rest).is_some() || rest.starts_with("include::") {
+                    if active {
+                        emit(result, frame, rest, span);
+                    }
+                    continue;
+                }
+            }
+
+            match parse_directive(line) {
+                Some(Directive::Ifdef {
+                    negated,
+                    target,
+                    content,
+                }) => {
+                    let matched = self.evaluate_ifdef(target) != negated;
+                    if !content.is_empty() {
+                        // Single-line form: no endif required
+                        if active && matched {
+                            emit(result, frame, content, span);
+                        }
+                        continue;
+                    }
+                    stack.push(Conditional {
+                        kind: if negated {
+                            ConditionalKind::Ifndef
+                        } else {
+                            ConditionalKind::Ifdef
+                        },
+                        target: target.to_string(),
+                        active: matched,
+                        line: line_no,
+                    });
+                }
+                Some(Directive::Ifeval { expression }) => {
+                    // Skipped regions are not evaluated, but still nest
+                    let matched = if active {
+                        match self.evaluate_ifeval(expression) {
+                            Ok(value) => value,
+                            Err(message) => {
+                                report(
+                                    result,
+                                    frame,
+                                    Diagnostic::warning(format!(
+                                        "Invalid ifeval expression '{}': {}",
+                                        expression, message
+                                    ))
+                                    .with_code("PRE104")
+                                    .with_span(span)
+                                    .with_help(
+                                        "Use `ifeval::[<value> <op> <value>]` with one of \
+                                         ==, !=, <, <=, >, >=",
+                                    ),
+                                );
+                                false
+                            }
+                        }
+                    } else {
+                        false
+                    };
+                    stack.push(Conditional {
+                        kind: ConditionalKind::Ifeval,
+                        target: String::new(),
+                        active: matched,
+                        line: line_no,
+                    });
+                }
+                Some(Directive::Endif { target }) => match stack.pop() {
+                    Some(open) => {
+                        if !target.is_empty() && target != open.target {
+                            let opened = match open.kind {
+                                ConditionalKind::Ifdef => "ifdef",
+                                ConditionalKind::Ifndef => "ifndef",
+                                ConditionalKind::Ifeval => "ifeval",
+                            };
+                            report(
+                                result,
+                                frame,
+                                Diagnostic::warning(format!(
+                                    "Mismatched endif: expected endif::{}[] to close {}::{}[]",
+                                    open.target, opened, open.target
+                                ))
+                                .with_code("PRE103")
+                                .with_span(span)
+                                .with_note(format!("Conditional opened on line {}", open.line)),
+                            );
+                        }
+                    }
+                    None => {
+                        report(
+                            result,
+                            frame,
+                            Diagnostic::error("Unbalanced endif: no open conditional")
+                                .with_code("PRE101")
+                                .with_span(span)
+                                .with_help("Remove this endif or add the missing ifdef/ifndef"),
+                        );
+                    }
+                },
+                None if !active => {}
+                None => match self.text_include(line, frame) {
+                    Some(directive) => self.include(&directive, span, frame, result),
+                    None => {
+                        self.track_attribute_entry(line);
+                        emit(result, frame, line, span);
+                    }
+                },
+            }
+        }
+
+        for open in stack.into_iter().rev() {
+            report(
+                result,
+                frame,
+                Diagnostic::error(format!(
+                    "Conditional opened on line {} is never closed",
+                    open.line
+                ))
+                .with_code("PRE102")
+                .with_span(Span::point(end_offset).with_line(open.line))
+                .with_help("Add a matching endif::[]"),
+            );
+        }
+    }
+
+    /// Parse a line as a text include to inline, if includes are enabled
+    ///
+    /// Data includes are left to the parser, which resolves them to tables.
+    fn text_include(&self, line: &str, frame: &IncludeFrame) -> Option<IncludeDirective> {
+        if frame.dir.is_none() || !line.starts_with("include::") {
+            return None;
+        }
+        let directive = IncludeDirective::parse(&self.substitute_attributes(line))?;
+        (!directive.is_data_file()).then_some(directive)
+    }
+
+    /// Inline a text include into the result
+    fn include(
+        &mut self,
+        directive: &IncludeDirective,
+        span: Span,
+        frame: &IncludeFrame,
+        result: &mut PreprocessResult,
+    ) {
+        if frame.chain.len() >= self.max_include_depth {
+            report(
+                result,
+                frame,
+                Diagnostic::error(format!(
+                    "Maximum include depth ({}) exceeded: {}",
+                    self.max_include_depth, directive.path
+                ))
+                .with_code("INC103")
+                .with_span(span),
+            );
+            return;
+        }
+
+        let dir = frame.dir.as_deref().unwrap_or(Path::new("."));
+        let content = dir
+            .join(&directive.path)
+            .canonicalize()
+            .and_then(|path| std::fs::read_to_string(&path).map(|content| (path, content)));
+        let (canonical, content) = match content {
+            Ok(found) => found,
+            Err(err) => {
+                report(
+                    result,
+                    frame,
+                    Diagnostic::error(format!(
+                        "Include file not found: {} ({})",
+                        directive.path, err
+                    ))
+                    .with_code("INC101")
+                    .with_span(span)
+                    .with_help("Paths are resolved relative to the including file"),
+                );
+                // Leave a visible marker in the output, as Asciidoctor does
+                emit(
+                    result,
+                    frame,
+                    &format!("Unresolved directive - include::{}[]", directive.path),
+                    span,
+                );
+                return;
+            }
+        };
+
+        if frame.chain.contains(&canonical) {
+            let cycle: Vec<String> = frame
+                .chain
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|p| p.display().to_string())
+                .collect();
+            report(
+                result,
+                frame,
+                Diagnostic::error(format!("Include cycle detected: {}", directive.path))
+                    .with_code("INC102")
+                    .with_span(span)
+                    .with_note(cycle.join(" -> ")),
+            );
+            return;
+        }
+
+        let selected = directive.select_lines(&content).unwrap_or_else(|message| {
+            report(
+                result,
+                frame,
+                Diagnostic::warning(format!("{} in {}", message, directive.path))
+                    .with_code("INC104")
+                    .with_span(span),
+            );
+            Vec::new()
+        });
+
+        let child = IncludeFrame {
+            file: Some(match &frame.file {
+                Some(parent) => Path::new(parent)
+                    .parent()
+                    .unwrap_or(Path::new(""))
+                    .join(&directive.path)
+                    .to_string_lossy()
+                    .to_string(),
+                None => directive.path.clone(),
+            }),
+            dir: canonical.parent().map(Path::to_path_buf),
+            chain: frame
+                .chain
+                .iter()
+                .cloned()
+                .chain(std::iter::once(canonical.clone()))
+                .collect(),
+            level_offset: directive.leveloffset.map_or(frame.level_offset, |offset| {
+                offset.apply(frame.level_offset)
+            }),
+        };
+
+        let starts: Vec<usize> = split_lines(&content).iter().map(|l| l.offset).collect();
+        let lines: Vec<SourceLine<'_>> = selected
+            .into_iter()
+            .map(|(number, text)| SourceLine {
+                number,
+                offset: starts.get(number - 1).copied().unwrap_or(0),
+                text,
+            })
+            .collect();
+        self.process_lines(&lines, content.len(), &child, result);
+    }
+
+    /// Look up an attribute value, render-time attributes first
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.locked
+            .get(name)
+            .or_else(|| self.attributes.get(name))
+            .map(|s| s.as_str())
+    }
+
+    /// Record `:name: value` and `:name!:` entries so later conditionals see them
+    fn track_attribute_entry(&mut self, line: &str) {
+        let Some(entry) = AttributeEntry::parse(line) else {
+            return;
+        };
+        if self.locked.contains_key(&entry.name) {
+            return;
+        }
+        match entry.value {
+            Some(value) => {
+                self.attributes.insert(entry.name, value);
+            }
+            None => {
+                self.attributes.remove(&entry.name);
+            }
+        }
+    }
+
+    /// Evaluate an `ifdef` target: `a`, `a,b` (any) or `a+b` (all)
+    fn evaluate_ifdef(&self, target: &str) -> bool {
+        if target.contains(',') {
+            target
+                .split(',')
+                .any(|name| self.attribute(name.trim()).is_some())
+        } else {
+            target
+                .split('+')
+                .all(|name| self.attribute(name.trim()).is_some())
+        }
+    }
+
+    /// Evaluate an `ifeval` expression of the form `lhs op rhs`
+    fn evaluate_ifeval(&self, expression: &str) -> Result<bool, String> {
+        let expression = self.substitute_attributes(expression);
+
+        const OPERATORS: [&str; 6] = ["==", "!=", "<=", ">=", "<", ">"];
+        let (pos, op) = OPERATORS
+            .iter()
+            .filter_map(|op| expression.find(op).map(|pos| (pos, *op)))
+            .min_by_key(|(pos, op)| (*pos, std::cmp::Reverse(op.len())))
+            .ok_or_else(|| "missing comparison operator".to_string())?;
+
+        let lhs = Operand::parse(&expression[..pos])?;
+        let rhs = Operand::parse(&expression[pos + op.len()..])?;
+        let ordering = lhs.compare(&rhs);
+
+        Ok(match op {
+            "==" => ordering == Some(std::cmp::Ordering::Equal),
+            "!=" => ordering != Some(std::cmp::Ordering::Equal),
+            "<" => ordering == Some(std::cmp::Ordering::Less),
+            "<=" => matches!(
+                ordering,
+                Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
+            ),
+            ">" => ordering == Some(std::cmp::Ordering::Greater),
+            ">=" => matches!(
+                ordering,
+                Some(std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)
+            ),
+            _ => unreachable!(),
+        })
+    }
+
+    /// Replace `{name}` references with attribute values (missing ones become empty)
+    fn substitute_attributes(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(open) = rest.find('{') {
+            output.push_str(&rest[..open]);
+            match rest[open..].find('}') {
+                Some(close) => {
+                    let name = &rest[open + 1..open + close];
+                    output.push_str(self.attribute(name).unwrap_or(""));
+                    rest = &rest[open + close + 1..];
+                }
+                None => {
+                    output.push_str(&rest[open..]);
+                    rest = "";
+                }
+            }
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+/// An operand in an `ifeval` expression
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl Operand {
+    fn parse(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return Err("missing operand".to_string());
+        }
+        for quote in ['"', '\''] {
+            if raw.len() >= 2 && raw.starts_with(quote) && raw.ends_with(quote) {
+                return Ok(Operand::Text(raw[1..raw.len() - 1].to_string()));
+            }
+        }
+        if let Ok(number) = raw.parse::<f64>() {
+            return Ok(Operand::Number(number));
+        }
+        match raw {
+            "true" => Ok(Operand::Bool(true)),
+            "false" => Ok(Operand::Bool(false)),
+            _ => Ok(Operand::Text(raw.to_string())),
+        }
+    }
+
+    fn compare(&self, other: &Operand) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Operand::Number(a), Operand::Number(b)) => a.partial_cmp(b),
+            (Operand::Bool(a), Operand::Bool(b)) => Some(a.cmp(b)),
+            (Operand::Text(a), Operand::Text(b)) => Some(a.cmp(b)),
+            // Mixed types compare by their textual form
+            (a, b) => Some(a.as_text().cmp(&b.as_text())),
+        }
+    }
+
+    fn as_text(&self) -> String {
+        match self {
+            Operand::Number(n) => n.to_string(),
+            Operand::Bool(b) => b.to_string(),
+            Operand::Text(t) => t.clone(),
+        }
+    }
+}
+
+/// Split source text into lines, accepting both `\n` and `\r\n`
+fn split_lines(text: &str) -> Vec<SourceLine<'_>> {
+    source_lines(text).collect()
+}
+
+/// The lines of `text`, split as they are needed
+fn source_lines(text: &str) -> impl Iterator<Item = SourceLine<'_>> {
+    let mut offset = 0;
+    text.split_inclusive('\n')
+        .enumerate()
+        .map(move |(index, raw)| {
+            let line = raw.strip_suffix('\n').unwrap_or(raw);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let source = SourceLine {
+                number: index + 1,
+                offset,
+                text: line,
+            };
+            offset += raw.len();
+            source
+        })
+}
+
+/// Whether `text` has conditional or include directives, whose effect
+/// depends on the lines around them
+pub(crate) fn has_directives(text: &str) -> bool {
+    source_lines(text)
+        .any(|line| parse_directive(line.text).is_some() || line.text.starts_with("include::"))
+}
+
+/// The lines the preprocessor emits for `text`, which has no directives
+/// (see [`has_directives`]), produced one at a time
+///
+/// Unlike [`Preprocessor::process`], lines past the point where the caller
+/// stops reading are never split or copied.
+pub(crate) fn plain_lines(text: &str) -> impl Iterator<Item = PreprocessedLine> + '_ {
+    source_lines(text).map(|source| {
+        // Escaped directives lose their backslash
+        let text = source
+            .text
+            .strip_prefix('\\')
+            .filter(|rest| parse_directive(rest).is_some() || rest.starts_with("include::"))
+            .unwrap_or(source.text);
+        PreprocessedLine {
+            text: text.to_string(),
+            line: source.number,
+            offset: source.offset,
+            file: None,
+            level_offset: 0,
+        }
+    })
+}
+
+/// Emit a kept line, tagged with the file it came from
+fn emit(result: &mut PreprocessResult, frame: &IncludeFrame, text: &str, span: Span) {
+    result.lines.push(PreprocessedLine {
+        text: text.to_string(),
+        line: span.line.unwrap_or(0),
+        offset: span.start,
+        file: frame.file.clone(),
+        level_offset: frame.level_offset,
+    });
+}
+
+/// Record a diagnostic, pointing at the included file when not the root
+fn report(result: &mut PreprocessResult, frame: &IncludeFrame, diagnostic: Diagnostic) {
+    result.diagnostics.push(match &frame.file {
+        Some(file) => diagnostic.with_file(file.clone()),
+        None => diagnostic,
+    });
+}
+
+/// Parse a preprocessor directive line, if it is one
+fn parse_directive(line: &str) -> Option<Directive<'_>> {
+    let (name, rest) = line.split_once("::")?;
+    if !matches!(name, "ifdef" | "ifndef" | "ifeval" | "endif") {
+        return None;
+    }
+    let bracket = rest.find('[')?;
+    if !rest.ends_with(']') {
+        return None;
+    }
+    let target = &rest[..bracket];
+    let content = &rest[bracket + 1..rest.len() - 1];
+
+    match name {
+        "ifdef" | "ifndef" if !target.is_empty() => Some(Directive::Ifdef {
+            negated: name == "ifndef",
+            target,
+            content,
+        }),
+        "ifeval" if target.is_empty() => Some(Directive::Ifeval {
+            expression: content,
+        }),
+        "endif" if content.is_empty() => Some(Directive::Endif { target }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kept(result: &PreprocessResult) -> Vec<&str> {
+        result.lines.iter().map(|l| l.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_ifdef_block() {
+        let source = "before\nifdef::backend-docx[]\ndocx only\nendif::backend-docx[]\nafter";
+
+        let docx = Preprocessor::new()
+            .with_attribute("backend-docx", "")
+            .process(source);
+        assert_eq!(kept(&docx), vec!["before", "docx only", "after"]);
+        assert!(docx.diagnostics.is_empty());
+
+        let pptx = Preprocessor::new()
+            .with_attribute("backend-pptx", "")
+            .process(source);
+        assert_eq!(kept(&pptx), vec!["before", "after"]);
+    }
+
+    #[test]
+    fn test_ifndef_block() {
+        let source = "ifndef::draft[]\nfinal text\nendif::[]";
+        assert_eq!(
+            kept(&Preprocessor::new().process(source)),
+            vec!["final text"]
+        );
+        assert!(kept(
+            &Preprocessor::new()
+                .with_attribute("draft", "")
+                .process(source)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_single_line_form() {
+        let source = "ifdef::backend-pptx[Slides only]\nifndef::backend-pptx[Docs only]";
+        let result = Preprocessor::new()
+            .with_attribute("backend-pptx", "")
+            .process(source);
+        assert_eq!(kept(&result), vec!["Slides only"]);
+    }
+
+    #[test]
+    fn test_any_and_all_targets() {
+        let pre = || Preprocessor::new().with_attribute("a", "");
+        assert_eq!(kept(&pre().process("ifdef::a,b[yes]")), vec!["yes"]);
+        assert!(kept(&pre().process("ifdef::a+b[yes]")).is_empty());
+        assert_eq!(
+            kept(&pre().with_attribute("b", "").process("ifdef::a+b[yes]")),
+            vec!["yes"]
+        );
+    }
+
+    #[test]
+    fn test_nested_conditionals() {
+        let source = "\
+ifdef::outer[]
+outer
+ifdef::inner[]
+both
+endif::inner[]
+ifndef::inner[]
+outer only
+endif::inner[]
+endif::outer[]
+ifdef::inner[]
+inner without outer
+endif::[]";
+
+        let result = Preprocessor::new()
+            .with_attribute("outer", "")
+            .process(source);
+        assert_eq!(kept(&result), vec!["outer", "outer only"]);
+        assert!(result.diagnostics.is_empty());
+
+        // Nested ifdef inside a skipped region must not close the outer one
+        let result = Preprocessor::new()
+            .with_attribute("inner", "")
+            .process(source);
+        assert_eq!(kept(&result), vec!["inner without outer"]);
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_document_attributes_are_tracked() {
+        let source = ":edition: pro\nifdef::edition[]\nPro content\nendif::[]\n:edition!:\nifdef::edition[still set]";
+        let result = Preprocessor::new().process(source);
+        assert_eq!(
+            kept(&result),
+            vec![":edition: pro", "Pro content", ":edition!:"]
+        );
+    }
+
+    #[test]
+    fn test_render_attributes_take_precedence() {
+        let source = ":backend: html\nifeval::[\"{backend}\" == \"docx\"]\nword\nendif::[]";
+        let result = Preprocessor::new()
+            .with_attribute("backend", "docx")
+            .process(source);
+        assert_eq!(kept(&result), vec![":backend: html", "word"]);
+    }
+
+    #[test]
+    fn test_ifeval_comparisons() {
+        let pre = || Preprocessor::new().with_attribute("revnumber", "3");
+        let eval = |expr: &str| {
+            let source = format!("ifeval::[{}]\nyes\nendif::[]", expr);
+            !kept(&pre().process(&source)).is_empty()
+        };
+
+        assert!(eval("{revnumber} >= 2"));
+        assert!(eval("{revnumber} > 2.5"));
+        assert!(!eval("{revnumber} < 3"));
+        assert!(eval("{revnumber} <= 3"));
+        assert!(eval("{revnumber} == 3"));
+        assert!(eval("{revnumber} != 4"));
+        assert!(eval("\"{revnumber}\" == \"3\""));
+        assert!(eval("'{missing}' == ''"));
+        assert!(!eval("\"a\" > \"b\""));
+    }
+
+    #[test]
+    fn test_ifeval_invalid_expression() {
+        let result = Preprocessor::new().process("ifeval::[{x}]\nhidden\nendif::[]");
+        assert!(kept(&result).is_empty());
+        assert_eq!(result.diagnostics.len(), 1);
+        let diag = result.diagnostics.iter().next().unwrap();
+        assert_eq!(diag.code.as_deref(), Some("PRE104"));
+    }
+
+    #[test]
+    fn test_unbalanced_endif() {
+        let result = Preprocessor::new().process("text\nendif::[]\nmore");
+        assert_eq!(kept(&result), vec!["text", "more"]);
+        assert!(result.diagnostics.has_errors());
+
+        let diag = result.diagnostics.iter().next().unwrap();
+        assert_eq!(diag.code.as_deref(), Some("PRE101"));
+        let span = diag.span.unwrap();
+        assert_eq!(span.line, Some(2));
+        assert_eq!((span.start, span.end), (5, 14));
+    }
+
+    #[test]
+    fn test_unclosed_conditional() {
+        let result = Preprocessor::new().process("ifdef::a[]\nhidden");
+        assert!(kept(&result).is_empty());
+
+        let diag = result.diagnostics.iter().next().unwrap();
+        assert_eq!(diag.code.as_deref(), Some("PRE102"));
+        assert_eq!(diag.span.unwrap().line, Some(1));
+    }
+
+    #[test]
+    fn test_mismatched_endif_target() {
+        let result = Preprocessor::new()
+            .with_attribute("a", "")
+            .process("ifdef::a[]\nshown\nendif::b[]");
+        assert_eq!(kept(&result), vec!["shown"]);
+
+        let diag = result.diagnostics.iter().next().unwrap();
+        assert_eq!(diag.code.as_deref(), Some("PRE103"));
+    }
+
+    #[test]
+    fn test_escaped_directive() {
+        let result = Preprocessor::new().process("\\ifdef::a[]\n\\endif::[]");
+        assert_eq!(kept(&result), vec!["ifdef::a[]", "endif::[]"]);
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_line_numbers_preserved() {
+        let result = Preprocessor::new().process("one\nifdef::x[]\ntwo\nendif::[]\nthree\n");
+        let numbers: Vec<usize> = result.lines.iter().map(|l| l.line).collect();
+        assert_eq!(numbers, vec![1, 5]);
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        let result = Preprocessor::new()
+            .with_attribute("a", "")
+            .process("ifdef::a[]\r\nkept\r\nendif::[]\r\n");
+        assert_eq!(kept(&result), vec!["kept"]);
+    }
+
+    #[test]
+    fn test_non_directives_pass_through() {
+        let source = "include::chapter.adoc[]\nifdef text without brackets\nendif:: nope";
+        let result = Preprocessor::new().process(source);
+        assert_eq!(kept(&result).len(), 3);
+    }
+}
@@ -0,0 +1,272 @@
+//! Source Span Bookkeeping
+//!
+//! The parser assembles block text from several source lines (joining
+//! paragraph lines, trimming list markers, splitting table cells) before
+//! parsing inlines. [`TextMap`] remembers where each piece of that text came
+//! from, so byte ranges found in the assembled text can be turned back into
+//! [`SourceSpan`]s.
+//!
+//! Spans of a node under construction are kept relative to it (the node
+//! itself at the empty path) and [`nest`]ed into their parent as it grows.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use utf8dok_ast::{NodePath, SourceSpan};
+
+use crate::attributes::Replacement;
+
+/// Spans keyed by node path, relative to the node they belong to
+pub(crate) type Spans = BTreeMap<NodePath, SourceSpan>;
+
+/// Add `spans` to `into`, below the node at `prefix`
+pub(crate) fn nest(into: &mut Spans, prefix: &NodePath, spans: Spans) {
+    for (path, span) in spans {
+        into.insert(NodePath([prefix.steps(), path.steps()].concat()), span);
+    }
+}
+
+/// A run of assembled text taken from one source line
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Segment {
+    /// Start of the run in the assembled text
+    text_start: usize,
+    /// Length of the run in the assembled text
+    len: usize,
+    /// Source range the run came from
+    source: SourceSpan,
+}
+
+/// Maps byte positions of assembled text back to the source
+///
+/// Attribute substitution can change the length of a line: the value of a
+/// reference maps to the reference, positions past its end clamped to it.
+/// The separator joining two lines maps to the line break between them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TextMap {
+    segments: Vec<Segment>,
+}
+
+impl TextMap {
+    /// A map for a line of text taken from `source` after attribute
+    /// substitution, given the range of each reference in the source line
+    /// and of its value in the text
+    pub fn substituted(len: usize, source: SourceSpan, replacements: &[Replacement]) -> Self {
+        let mut map = Self::default();
+        let mut push = |text: Range<usize>, from: Range<usize>| {
+            map.segments.push(Segment {
+                text_start: text.start,
+                len: text.len(),
+                source: SourceSpan {
+                    start: source.start + from.start,
+                    end: source.start + from.end,
+                    line: source.line,
+                    column: source.column + from.start,
+                },
+            });
+        };
+        let (mut text, mut from) = (0, 0);
+        for (reference, value) in replacements {
+            if value.start > text {
+                push(text..value.start, from..reference.start);
+            }
+            push(value.clone(), reference.clone());
+            (text, from) = (value.end, reference.end);
+        }
+        let source_len = source.end - source.start;
+        push(text..len.max(text), from.min(source_len)..source_len);
+        map
+    }
+
+    /// Append the text of a line map, after the one-byte separator that
+    /// joins it to the previous text
+    pub fn push(&mut self, line: &TextMap) {
+        let offset = match self.segments.is_empty() {
+            true => 0,
+            false => self.text_len() + 1,
+        };
+        self.segments
+            .extend(line.segments.iter().map(|segment| Segment {
+                text_start: offset + segment.text_start,
+                ..*segment
+            }));
+    }
+
+    /// Length of the assembled text
+    pub fn text_len(&self) -> usize {
+        self.segments.last().map_or(0, |s| s.text_start + s.len)
+    }
+
+    /// Source span of a byte range of the assembled text
+    pub fn span(&self, range: Range<usize>) -> Option<SourceSpan> {
+        let first = self.segment_at(range.start)?;
+        let last = match range.end > range.start {
+            true => self.segment_at(range.end - 1)?,
+            false => first,
+        };
+        let start_delta = first.delta(range.start);
+        let end = match self.segment_after(last) {
+            // Ending with the separator after a line: the line break
+            Some(next) if range.end > last.text_start + last.len => next.source.start,
+            _ => last.source.start + last.delta(range.end),
+        };
+        Some(SourceSpan {
+            start: first.source.start + start_delta,
+            end: end.max(first.source.start + start_delta),
+            line: first.source.line,
+            column: first.source.column + start_delta,
+        })
+    }
+
+    /// Map for a byte range of this text
+    pub fn slice(&self, range: Range<usize>) -> TextMap {
+        self.join(std::slice::from_ref(&range))
+    }
+
+    /// Map for the text made by joining `ranges` of this text with one-byte
+    /// separators
+    pub fn join(&self, ranges: &[Range<usize>]) -> TextMap {
+        let mut map = TextMap::default();
+        let mut cursor = 0;
+        for range in ranges {
+            for segment in &self.segments {
+                let start = range.start.max(segment.text_start);
+                let end = range.end.min(segment.text_start + segment.len);
+                if start > end || (start == end && !range.is_empty()) {
+                    continue;
+                }
+                let from = segment.delta(start);
+                map.segments.push(Segment {
+                    text_start: cursor + start - range.start,
+                    len: end - start,
+                    source: SourceSpan {
+                        start: segment.source.start + from,
+                        end: segment.source.start + segment.delta(end),
+                        line: segment.source.line,
+                        column: segment.source.column + from,
+                    },
+                });
+            }
+            cursor += range.len() + 1;
+        }
+        map
+    }
+
+    /// Map spans found in this text (e.g. by a nested parser) to the source
+    pub fn map_spans(&self, spans: Spans) -> Spans {
+        spans
+            .into_iter()
+            .filter_map(|(path, span)| Some((path, self.span(span.start..span.end)?)))
+            .collect()
+    }
+
+    fn segment_at(&self, position: usize) -> Option<&Segment> {
+        self.segments
+            .iter()
+            .rev()
+            .find(|segment| segment.text_start <= position)
+            .or(self.segments.first())
+    }
+
+    /// The segment of the line after the one `segment` ends
+    fn segment_after(&self, segment: &Segment) -> Option<&Segment> {
+        self.segments
+            .iter()
+            .find(|next| next.text_start > segment.text_start + segment.len)
+    }
+}
+
+impl Segment {
+    /// Source offset of a text position, relative to the segment start
+    ///
+    /// The end of the text maps to the end of the source, whether
+    /// substitution made it longer or shorter.
+    fn delta(&self, position: usize) -> usize {
+        let source_len = self.source.end - self.source.start;
+        match position.saturating_sub(self.text_start).min(self.len) {
+            offset if offset == self.len => source_len,
+            offset => offset.min(source_len),
+        }
+    }
+}
+
+/// Byte range of `part` within `text`, when `part` is a slice of it
+pub(crate) fn subslice_range(text: &str, part: &str) -> Range<usize> {
+    let start = (part.as_ptr() as usize)
+        .checked_sub(text.as_ptr() as usize)
+        .filter(|start| start + part.len() <= text.len())
+        .unwrap_or(0);
+    start..start + part.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> TextMap {
+        // "alpha beta" from two lines, the second indented by two bytes
+        let mut map = TextMap::default();
+        map.push(&TextMap::substituted(5, SourceSpan::new(10, 15, 2, 1), &[]));
+        map.push(&TextMap::substituted(4, SourceSpan::new(18, 22, 3, 3), &[]));
+        map
+    }
+
+    #[test]
+    fn test_span_across_lines() {
+        let map = map();
+        assert_eq!(map.text_len(), 10);
+        assert_eq!(map.span(0..5), Some(SourceSpan::new(10, 15, 2, 1)));
+        assert_eq!(map.span(6..10), Some(SourceSpan::new(18, 22, 3, 3)));
+        assert_eq!(map.span(2..8), Some(SourceSpan::new(12, 20, 2, 3)));
+        assert_eq!(map.span(7..7), Some(SourceSpan::new(19, 19, 3, 4)));
+    }
+
+    #[test]
+    fn test_substituted_text_is_clamped() {
+        // 12 bytes of text from a 5-byte source line
+        let map = TextMap::substituted(12, SourceSpan::new(0, 5, 1, 1), &[]);
+        assert_eq!(map.span(3..12), Some(SourceSpan::new(3, 5, 1, 4)));
+        assert_eq!(map.span(8..10), Some(SourceSpan::new(5, 5, 1, 6)));
+    }
+
+    #[test]
+    fn test_substituted_references() {
+        // "{x} *b*" with {x} expanded to 7 bytes: "xxxxxxx *b*"
+        let map = TextMap::substituted(11, SourceSpan::new(20, 27, 4, 1), &[(0..3, 0..7)]);
+        assert_eq!(map.text_len(), 11);
+        assert_eq!(map.span(0..7), Some(SourceSpan::new(20, 23, 4, 1)));
+        assert_eq!(map.span(8..11), Some(SourceSpan::new(24, 27, 4, 5)));
+        assert_eq!(map.span(9..10), Some(SourceSpan::new(25, 26, 4, 6)));
+        assert_eq!(map.span(2..11), Some(SourceSpan::new(22, 27, 4, 3)));
+    }
+
+    #[test]
+    fn test_separator_maps_to_line_break() {
+        assert_eq!(map().span(5..6), Some(SourceSpan::new(15, 18, 2, 6)));
+    }
+
+    #[test]
+    fn test_join() {
+        let joined = map().join(&[1..3, 7..9]);
+        // "lp et"
+        assert_eq!(joined.text_len(), 5);
+        assert_eq!(joined.span(3..5), Some(SourceSpan::new(19, 21, 3, 4)));
+    }
+
+    #[test]
+    fn test_nest() {
+        let mut spans = Spans::new();
+        let mut child = Spans::new();
+        child.insert(NodePath::default(), SourceSpan::new(0, 1, 1, 1));
+        child.insert(NodePath::block(0), SourceSpan::new(0, 1, 1, 1));
+        nest(&mut spans, &NodePath::from(vec![2, 1]), child);
+        let paths: Vec<String> = spans.keys().map(|p| p.to_string()).collect();
+        assert_eq!(paths, vec!["2.1", "2.1.0"]);
+    }
+
+    #[test]
+    fn test_subslice_range() {
+        let text = "* item";
+        assert_eq!(subslice_range(text, &text[2..]), 2..6);
+        assert_eq!(subslice_range(text, "other"), 0..5);
+    }
+}
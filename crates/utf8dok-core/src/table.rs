@@ -0,0 +1,591 @@
+//! AsciiDoc Table Layout
+//!
+//! The parser buffers the lines between table delimiters (`|===`, `,===`,
+//! `:===`, or `!===` for tables nested in AsciiDoc cells) and hands them to
+//! this module, which works out the table options, splits the content into
+//! cells and arranges the cells into rows.
+//!
+//! # Supported Syntax
+//!
+//! - Column specs: `[cols="1,2a,^3"]`, `[cols="3*"]`
+//!   (`[multiplier*][align][width][style]`)
+//! - Cell specs: `2+|` (colspan), `.3+|` (rowspan), `2.3+|`, `3*|`
+//!   (duplicate), `^|` (align), `a|` (style)
+//! - Options: `%header`, `%footer`, `%noheader`, `options="header,footer"`,
+//!   and the implicit header row (a single first line followed by a blank line)
+//! - Formats: `format=psv` (default), `format=csv`, `format=dsv`, `separator=;`
+//!
+//! Turning cell text into blocks is left to the parser.
+
+use regex::Regex;
+use std::ops::Range;
+use std::sync::OnceLock;
+use utf8dok_ast::{Alignment, CellStyle, ColumnSpec};
+
+/// Data format of the table content
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TableFormat {
+    /// Prefix-separated values: `| cell | cell`
+    Psv,
+    /// Comma-separated values, with double-quoted fields
+    Csv,
+    /// Delimiter-separated values: `cell:cell`
+    Dsv,
+}
+
+/// Options of a table, from its block attributes and delimiter
+#[derive(Debug, Clone)]
+pub(crate) struct TableOptions {
+    /// `Some(true)` for `%header`, `Some(false)` for `%noheader`
+    pub header: Option<bool>,
+    /// `%footer`
+    pub footer: bool,
+    pub format: TableFormat,
+    /// Cell separator
+    pub separator: char,
+    /// Column specs from `cols`
+    pub columns: Vec<ColumnSpec>,
+}
+
+impl TableOptions {
+    /// Read the options from the table delimiter and its block attributes
+    /// (already split into individual values)
+    pub fn new(delimiter: &str, attributes: &[String]) -> Self {
+        let prefix = delimiter.chars().next().unwrap_or('|');
+        let mut options = Self {
+            header: None,
+            footer: false,
+            format: match prefix {
+                ',' => TableFormat::Csv,
+                ':' => TableFormat::Dsv,
+                _ => TableFormat::Psv,
+            },
+            separator: prefix,
+            columns: Vec::new(),
+        };
+
+        let mut separator = None;
+        for attr in attributes {
+            match attr.split_once('=') {
+                Some((key, value)) => {
+                    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+                    match key.trim() {
+                        "cols" => options.columns = parse_cols(value),
+                        "options" | "opts" => {
+                            for option in value.split(',') {
+                                options.set_option(option.trim());
+                            }
+                        }
+                        "format" => match value {
+                            "csv" => options.format = TableFormat::Csv,
+                            "dsv" => options.format = TableFormat::Dsv,
+                            "psv" => options.format = TableFormat::Psv,
+                            _ => {}
+                        },
+                        "separator" => separator = value.chars().next(),
+                        _ => {}
+                    }
+                }
+                // Shorthand options: %header%footer
+                None => {
+                    for option in attr.split('%').skip(1) {
+                        options.set_option(option.trim());
+                    }
+                }
+            }
+        }
+
+        options.separator = separator.unwrap_or(match (options.format, prefix) {
+            (TableFormat::Csv, _) => ',',
+            (TableFormat::Dsv, _) => ':',
+            (TableFormat::Psv, '!') => '!',
+            (TableFormat::Psv, _) => '|',
+        });
+        options
+    }
+
+    fn set_option(&mut self, option: &str) {
+        match option {
+            "header" => self.header = Some(true),
+            "noheader" => self.header = Some(false),
+            "footer" => self.footer = true,
+            _ => {}
+        }
+    }
+}
+
+/// Parse a `cols` attribute value: `1,2a,^3` or `3*`
+pub(crate) fn parse_cols(cols: &str) -> Vec<ColumnSpec> {
+    static COL: OnceLock<Regex> = OnceLock::new();
+    let re = COL.get_or_init(|| {
+        Regex::new(r"^(?:(\d+)\*)?([<^>])?(?:\.[<^>])?(\d+%?|~)?([adehlmsv])?$").unwrap()
+    });
+
+    // A bare number is a column count
+    if let Ok(count) = cols.trim().parse::<usize>() {
+        return (0..count).map(|_| column(None, None, None)).collect();
+    }
+
+    let separator = if cols.contains(';') { ';' } else { ',' };
+    let mut columns = Vec::new();
+    for spec in cols.split(separator) {
+        let Some(caps) = re.captures(spec.trim()) else {
+            columns.push(column(None, None, None));
+            continue;
+        };
+        let repeat = caps.get(1).map_or(1, |m| m.as_str().parse().unwrap_or(1));
+        let align = caps.get(2).and_then(|m| alignment(m.as_str()));
+        let width = caps
+            .get(3)
+            .and_then(|m| m.as_str().trim_end_matches('%').parse().ok());
+        let style = caps
+            .get(4)
+            .and_then(|m| m.as_str().chars().next())
+            .and_then(CellStyle::from_char);
+        for _ in 0..repeat {
+            columns.push(column(width, align, style));
+        }
+    }
+    columns
+}
+
+fn column(width: Option<u32>, align: Option<Alignment>, style: Option<CellStyle>) -> ColumnSpec {
+    ColumnSpec {
+        width,
+        align,
+        style,
+    }
+}
+
+fn alignment(op: &str) -> Option<Alignment> {
+    match op {
+        "<" => Some(Alignment::Left),
+        "^" => Some(Alignment::Center),
+        ">" => Some(Alignment::Right),
+        _ => None,
+    }
+}
+
+/// Spec written in front of a cell separator: `2.3+^a|`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CellSpec {
+    pub colspan: u32,
+    pub rowspan: u32,
+    /// Duplication factor (`3*|`)
+    pub repeat: usize,
+    pub align: Option<Alignment>,
+    pub style: Option<CellStyle>,
+}
+
+impl Default for CellSpec {
+    fn default() -> Self {
+        Self {
+            colspan: 1,
+            rowspan: 1,
+            repeat: 1,
+            align: None,
+            style: None,
+        }
+    }
+}
+
+impl CellSpec {
+    /// Parse a cell spec; the empty spec is the default
+    pub fn parse(spec: &str) -> Option<Self> {
+        static SPEC: OnceLock<Regex> = OnceLock::new();
+        let re = SPEC.get_or_init(|| {
+            Regex::new(r"^(?:(\d+)\*)?(?:(\d+)?(?:\.(\d+))?\+)?([<^>])?(?:\.[<^>])?([adehlmsv])?$")
+                .unwrap()
+        });
+        let caps = re.captures(spec)?;
+        let number = |i: usize| caps.get(i).and_then(|m| m.as_str().parse().ok());
+        Some(Self {
+            colspan: number(2).unwrap_or(1).max(1),
+            rowspan: number(3).unwrap_or(1).max(1),
+            repeat: number(1).map_or(1, |n: u32| n.max(1) as usize),
+            align: caps.get(4).and_then(|m| alignment(m.as_str())),
+            style: caps
+                .get(5)
+                .and_then(|m| m.as_str().chars().next())
+                .and_then(CellStyle::from_char),
+        })
+    }
+
+    /// Write the spec back in front of a separator
+    pub fn to_spec_string(
+        colspan: u32,
+        rowspan: u32,
+        align: Option<Alignment>,
+        style: Option<CellStyle>,
+    ) -> String {
+        let mut spec = String::new();
+        if colspan > 1 {
+            spec.push_str(&colspan.to_string());
+        }
+        if rowspan > 1 {
+            spec.push_str(&format!(".{}", rowspan));
+        }
+        if colspan > 1 || rowspan > 1 {
+            spec.push('+');
+        }
+        match align {
+            Some(Alignment::Left) => spec.push('<'),
+            Some(Alignment::Center) => spec.push('^'),
+            Some(Alignment::Right) => spec.push('>'),
+            None => {}
+        }
+        if let Some(style) = style {
+            spec.push(style.as_char());
+        }
+        spec
+    }
+}
+
+/// A cell split out of the table content, before layout
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RawCell {
+    pub spec: CellSpec,
+    /// Cell text, trimmed, with escaped separators restored
+    pub text: String,
+    /// Index of the content line holding the cell's separator
+    pub line: usize,
+    /// Whether a blank line follows the cell
+    pub blank_line_after: bool,
+    /// Byte range of the cell in the table content, from its spec to the
+    /// end of its text
+    pub range: Range<usize>,
+    /// Byte offset of the cell text in the table content
+    pub text_start: usize,
+}
+
+/// Split prefix-separated table content into cells
+///
+/// A cell starts at each separator; the spec in front of the separator
+/// belongs to the new cell. Text before the first separator is ignored.
+pub(crate) fn split_cells(text: &str, separator: char) -> Vec<RawCell> {
+    let mut cells = Vec::new();
+    // Spec, line and start of the cell being collected
+    let mut current: Option<(CellSpec, usize, usize)> = None;
+    let mut buffer = String::new();
+    // Content offset of the buffer and escapes dropped from it
+    let mut buffer_start = 0;
+    let mut escapes = 0;
+    let mut line = 0;
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        if c == '\\' && chars.peek().map(|&(_, next)| next) == Some(separator) {
+            buffer.push(separator);
+            escapes += 1;
+            chars.next();
+            continue;
+        }
+        if c == '\n' {
+            line += 1;
+        }
+        if c != separator {
+            buffer.push(c);
+            continue;
+        }
+
+        // The last word of the buffer may be this cell's spec
+        let word_start = buffer
+            .rfind(|c: char| c.is_whitespace())
+            .map_or(0, |i| i + 1);
+        let (spec, spec_len) = match CellSpec::parse(&buffer[word_start..]) {
+            Some(spec) => (spec, buffer.len() - word_start),
+            None => (CellSpec::default(), 0),
+        };
+        buffer.truncate(buffer.len() - spec_len);
+
+        if let Some((spec, line, start)) = current.take() {
+            cells.push(finish_cell(
+                spec,
+                line,
+                &buffer,
+                start,
+                buffer_start,
+                escapes,
+            ));
+        }
+        buffer.clear();
+        buffer_start = position + c.len_utf8();
+        escapes = 0;
+        current = Some((spec, line, position - spec_len));
+    }
+    if let Some((spec, line, start)) = current {
+        cells.push(finish_cell(
+            spec,
+            line,
+            &buffer,
+            start,
+            buffer_start,
+            escapes,
+        ));
+    }
+    cells
+}
+
+/// Finish a cell whose text `raw` started at content offset `raw_start`,
+/// with `escapes` escaped separators restored
+fn finish_cell(
+    spec: CellSpec,
+    line: usize,
+    raw: &str,
+    start: usize,
+    raw_start: usize,
+    escapes: usize,
+) -> RawCell {
+    let text = raw.trim();
+    let trailing = &raw[raw.trim_end().len()..];
+    let text_start = raw_start + (raw.len() - raw.trim_start().len());
+    RawCell {
+        spec,
+        // Keep the indentation of literal cells
+        text: match spec.style {
+            Some(CellStyle::Literal) => raw
+                .strip_prefix(' ')
+                .unwrap_or(raw)
+                .trim_matches('\n')
+                .trim_end()
+                .to_string(),
+            _ => text.to_string(),
+        },
+        line,
+        blank_line_after: trailing.matches('\n').count() >= 2,
+        range: start..(text_start + text.len() + escapes).max(raw_start),
+        text_start,
+    }
+}
+
+/// Split CSV or DSV table content into records of fields
+pub(crate) fn split_records(text: &str, format: TableFormat, separator: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let quotes = format == TableFormat::Csv;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quotes && quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quotes && (quoted || field.trim().is_empty()) => {
+                if !quoted {
+                    field.clear();
+                }
+                quoted = !quoted;
+            }
+            '\\' if !quotes && chars.peek() == Some(&separator) => {
+                field.push(separator);
+                chars.next();
+            }
+            c if c == separator && !quoted => {
+                record.push(std::mem::take(&mut field).trim().to_string());
+            }
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field).trim().to_string());
+                if record.iter().any(|f| !f.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            c => field.push(c),
+        }
+    }
+    record.push(field.trim().to_string());
+    if record.iter().any(|f| !f.is_empty()) {
+        records.push(record);
+    }
+    records
+}
+
+/// Whether the content starts with a single line followed by a blank line
+/// (the implicit header row)
+pub(crate) fn has_implicit_header(lines: &[String]) -> bool {
+    matches!(lines, [first, second, ..] if !first.trim().is_empty() && second.trim().is_empty())
+}
+
+/// Arrange cells into rows of `columns` grid columns
+///
+/// Each cell is paired with the grid column it starts in. Spanned cells
+/// take up their slots, including slots in the rows below a rowspan.
+pub(crate) fn arrange_rows(cells: Vec<RawCell>, columns: usize) -> Vec<Vec<(usize, RawCell)>> {
+    let columns = columns.max(1);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    // Remaining rows each grid column is covered by a rowspan from above
+    let mut covered = vec![0u32; columns];
+    let mut position = 0;
+
+    let skip_covered = |position: &mut usize, covered: &[u32]| {
+        while *position < columns && covered[*position] > 0 {
+            *position += 1;
+        }
+    };
+
+    for cell in cells.into_iter().flat_map(|cell| {
+        let repeat = cell.spec.repeat;
+        std::iter::repeat_n(cell, repeat)
+    }) {
+        skip_covered(&mut position, &covered);
+        if position >= columns {
+            // Row is full: move on, releasing one row of each rowspan
+            rows.push(std::mem::take(&mut row));
+            for count in covered.iter_mut() {
+                *count = count.saturating_sub(1);
+            }
+            position = 0;
+            skip_covered(&mut position, &covered);
+        }
+
+        let start = position;
+        let end = (start + cell.spec.colspan as usize).min(columns);
+        if cell.spec.rowspan > 1 {
+            for count in &mut covered[start..end] {
+                // The current row is released when it completes
+                *count = cell.spec.rowspan;
+            }
+        }
+        position = end;
+        row.push((start, cell));
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
+
+/// Arrange cells into rows separated by blank lines
+///
+/// Used when the column count is unknown and the first row has its cells
+/// on separate lines.
+pub(crate) fn arrange_rows_by_blank_lines(cells: Vec<RawCell>) -> Vec<Vec<(usize, RawCell)>> {
+    let mut rows = Vec::new();
+    let mut row: Vec<(usize, RawCell)> = Vec::new();
+    let mut position = 0;
+    for cell in cells {
+        let ends_row = cell.blank_line_after;
+        let colspan = cell.spec.colspan as usize;
+        row.push((position, cell));
+        position += colspan;
+        if ends_row {
+            rows.push(std::mem::take(&mut row));
+            position = 0;
+        }
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
+
+/// Number of grid columns implied by the cells on the first content line
+pub(crate) fn first_line_columns(cells: &[RawCell]) -> usize {
+    let Some(first) = cells.first() else {
+        return 0;
+    };
+    cells
+        .iter()
+        .take_while(|cell| cell.line == first.line)
+        .map(|cell| cell.spec.colspan as usize * cell.spec.repeat)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cols() {
+        let columns = parse_cols("1,2a,^3");
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[0].width, Some(1));
+        assert_eq!(columns[1].style, Some(CellStyle::AsciiDoc));
+        assert_eq!(columns[2].align, Some(Alignment::Center));
+        assert_eq!(columns[2].width, Some(3));
+
+        assert_eq!(parse_cols("3*").len(), 3);
+        assert_eq!(parse_cols("2*>m,1").len(), 3);
+        assert_eq!(parse_cols("4").len(), 4);
+    }
+
+    #[test]
+    fn test_cell_spec() {
+        let spec = CellSpec::parse("2.3+^a").unwrap();
+        assert_eq!((spec.colspan, spec.rowspan), (2, 3));
+        assert_eq!(spec.align, Some(Alignment::Center));
+        assert_eq!(spec.style, Some(CellStyle::AsciiDoc));
+        assert_eq!(CellSpec::parse("3*").unwrap().repeat, 3);
+        assert_eq!(CellSpec::parse(""), Some(CellSpec::default()));
+        assert!(CellSpec::parse("word").is_none());
+
+        assert_eq!(
+            CellSpec::to_spec_string(2, 3, Some(Alignment::Center), Some(CellStyle::AsciiDoc)),
+            "2.3+^a"
+        );
+    }
+
+    #[test]
+    fn test_split_cells() {
+        let cells = split_cells("| A | B \\| C\n2+| wide\n\nh| D", '|');
+        let texts: Vec<&str> = cells.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["A", "B | C", "wide", "D"]);
+        assert_eq!(cells[2].spec.colspan, 2);
+        assert!(cells[2].blank_line_after);
+        assert_eq!(cells[3].spec.style, Some(CellStyle::Header));
+        assert_eq!(first_line_columns(&cells), 2);
+    }
+
+    #[test]
+    fn test_split_cells_ranges() {
+        let text = "| A | B \\| C\n2+| wide";
+        let cells = split_cells(text, '|');
+        let ranges: Vec<&str> = cells.iter().map(|c| &text[c.range.clone()]).collect();
+        assert_eq!(ranges, vec!["| A", "| B \\| C", "2+| wide"]);
+        assert_eq!(&text[cells[2].text_start..], "wide");
+    }
+
+    #[test]
+    fn test_arrange_rows_with_spans() {
+        // | A .2+| B | C
+        // | D | E
+        let cells = split_cells("| A .2+| B | C\n| D | E", '|');
+        let rows = arrange_rows(cells, 3);
+        assert_eq!(rows.len(), 2);
+        let columns: Vec<usize> = rows[1].iter().map(|(column, _)| *column).collect();
+        assert_eq!(columns, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_split_records() {
+        let records = split_records(
+            "Name,Note\n\"Doe, Jane\",\"Said \"\"hi\"\"\"\n",
+            TableFormat::Csv,
+            ',',
+        );
+        assert_eq!(records[1], vec!["Doe, Jane", "Said \"hi\""]);
+
+        let records = split_records("a:b\nc\\:d:e", TableFormat::Dsv, ':');
+        assert_eq!(records[1], vec!["c:d", "e"]);
+    }
+
+    #[test]
+    fn test_table_options() {
+        let attrs = vec!["%header%footer".to_string(), "cols=2*".to_string()];
+        let options = TableOptions::new("|===", &attrs);
+        assert_eq!(options.header, Some(true));
+        assert!(options.footer);
+        assert_eq!(options.columns.len(), 2);
+
+        let options = TableOptions::new(",===", &[]);
+        assert_eq!(options.format, TableFormat::Csv);
+        let options = TableOptions::new("|===", &["format=dsv".into(), "separator=;".into()]);
+        assert_eq!((options.format, options.separator), (TableFormat::Dsv, ';'));
+        assert_eq!(TableOptions::new("!===", &[]).separator, '!');
+    }
+}
@@ -0,0 +1,146 @@
+//! Removal of draft-only content
+
+use utf8dok_ast::{Block, Document, NodePath};
+
+use super::{has_role, visit_block_lists_at, Transform, TransformError};
+
+/// Removes blocks marked as draft-only
+///
+/// A block is draft-only when its role includes the draft role, as in
+/// `[.draft]` or `[role=draft]`. A draft heading takes its whole section
+/// with it, up to the next heading of the same or a higher level. Text
+/// guarded by `ifdef::draft[]` is handled by the preprocessor before
+/// parsing instead.
+#[derive(Debug, Clone)]
+pub struct StripDraft {
+    role: String,
+}
+
+impl Default for StripDraft {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_ROLE)
+    }
+}
+
+impl StripDraft {
+    /// Role marking draft-only blocks by default
+    pub const DEFAULT_ROLE: &'static str = "draft";
+
+    /// Remove blocks with the role `role`
+    pub fn new(role: impl Into<String>) -> Self {
+        Self { role: role.into() }
+    }
+
+    fn is_draft(&self, block: &Block) -> bool {
+        let roles = match block {
+            Block::Open(open) => open
+                .role
+                .iter()
+                .chain(open.attributes.get("role"))
+                .collect(),
+            Block::Paragraph(paragraph) => paragraph.attributes.get("role").into_iter().collect(),
+            Block::Heading(heading) => heading.role.iter().collect(),
+            _ => Vec::new(),
+        };
+        roles.into_iter().any(|roles| has_role(roles, &self.role))
+    }
+}
+
+impl Transform for StripDraft {
+    fn name(&self) -> &str {
+        "strip-draft"
+    }
+
+    fn apply(&self, doc: &mut Document) -> Result<(), TransformError> {
+        let source_map = &mut doc.source_map;
+        visit_block_lists_at(
+            &mut doc.blocks,
+            &NodePath::default(),
+            0,
+            &mut |blocks, path| {
+                // Level of the draft section being removed
+                let mut section = None;
+                let drafts: Vec<bool> = blocks
+                    .iter()
+                    .map(|block| match block {
+                        Block::Heading(heading)
+                            if section.is_some_and(|level| heading.level > level) =>
+                        {
+                            true
+                        }
+                        Block::Heading(heading) => {
+                            section = self.is_draft(block).then_some(heading.level);
+                            section.is_some()
+                        }
+                        _ => section.is_some() || self.is_draft(block),
+                    })
+                    .collect();
+                // Last first, so the paths of the blocks before stay put
+                for index in (0..blocks.len()).rev() {
+                    if drafts[index] {
+                        blocks.remove(index);
+                        source_map.remove_node(&path(index));
+                    }
+                }
+            },
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_strips_draft_blocks() {
+        let mut doc = parse(
+            "= Doc\n\nKept.\n\n[.draft]\n--\nTo do.\n--\n\n\
+             ****\nSide.\n\n[.draft]\n--\nNested draft.\n--\n****\n\n\
+             [abstract]\n--\nSummary.\n--\n",
+        )
+        .unwrap();
+        let before = doc.blocks.len();
+        StripDraft::default().apply(&mut doc).unwrap();
+        assert_eq!(doc.blocks.len(), before - 1);
+
+        let asciidoc = crate::generate(&doc);
+        assert!(asciidoc.contains("Kept."));
+        assert!(asciidoc.contains("Side."));
+        assert!(asciidoc.contains("Summary."));
+        assert!(!asciidoc.contains("To do."));
+        assert!(!asciidoc.contains("Nested draft."));
+    }
+
+    #[test]
+    fn test_strips_draft_paragraphs_and_sections() {
+        let mut doc = parse(
+            "= Doc\n\nKept.\n\n[.draft]\nDraft paragraph.\n\n\
+             [role=\"lead draft\"]\nDraft lead.\n\n\
+             [.draft]\n== Draft section\n\nDraft body.\n\n=== Draft subsection\n\n\
+             More draft.\n\n== Published\n\nPublished body.\n",
+        )
+        .unwrap();
+        StripDraft::default().apply(&mut doc).unwrap();
+
+        let asciidoc = crate::generate(&doc);
+        assert!(asciidoc.contains("Kept."));
+        assert!(asciidoc.contains("== Published"));
+        assert!(asciidoc.contains("Published body."));
+        assert!(!asciidoc.contains("raft"), "{}", asciidoc);
+        assert_eq!(doc.blocks.len(), 3);
+    }
+
+    #[test]
+    fn test_custom_role_and_paragraph_attribute() {
+        let mut doc = crate::parse_markdown("Kept.\n\nInternal.\n");
+        if let Block::Paragraph(paragraph) = &mut doc.blocks[1] {
+            paragraph
+                .attributes
+                .insert("role".to_string(), "internal".to_string());
+        }
+        StripDraft::new("internal").apply(&mut doc).unwrap();
+        assert_eq!(doc.blocks.len(), 1);
+    }
+}
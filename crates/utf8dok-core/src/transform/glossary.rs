@@ -0,0 +1,253 @@
+//! Generated glossary
+
+use std::collections::BTreeMap;
+
+use utf8dok_ast::{
+    Block, Document, Heading, Inline, List, ListItem, ListType, NodePath, Paragraph,
+};
+
+use super::{has_role, visit_block_lists_at, visit_inlines, Transform, TransformError};
+use crate::markdown::plain_text;
+
+/// Collects the terms a document uses into a glossary section
+///
+/// Definitions come from the configured terms and from description lists
+/// in `[glossary]` open blocks anywhere in the document, which are moved
+/// into the glossary; a definition in the document wins over a configured
+/// one. Only terms the document text uses, as whole words, are listed,
+/// sorted alphabetically.
+///
+/// The list goes under an existing heading with the glossary title, or
+/// under a new heading at the end of the document.
+#[derive(Debug, Clone)]
+pub struct Glossary {
+    title: String,
+    level: u8,
+    terms: BTreeMap<String, String>,
+}
+
+impl Default for Glossary {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TITLE)
+    }
+}
+
+impl Glossary {
+    /// Title of the glossary section by default
+    pub const DEFAULT_TITLE: &'static str = "Glossary";
+
+    /// Create a glossary titled `title`
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            level: 1,
+            terms: BTreeMap::new(),
+        }
+    }
+
+    /// Set the level of the heading added for the glossary
+    pub fn with_level(mut self, level: u8) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Add configured terms and their definitions
+    pub fn with_terms(mut self, terms: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.terms.extend(terms);
+        self
+    }
+
+    /// Add a configured term and its definition
+    pub fn with_term(mut self, term: impl Into<String>, definition: impl Into<String>) -> Self {
+        self.terms.insert(term.into(), definition.into());
+        self
+    }
+}
+
+impl Transform for Glossary {
+    fn name(&self) -> &str {
+        "glossary"
+    }
+
+    fn apply(&self, doc: &mut Document) -> Result<(), TransformError> {
+        let mut definitions: BTreeMap<String, Vec<Block>> = self
+            .terms
+            .iter()
+            .map(|(term, definition)| {
+                let paragraph = Paragraph {
+                    inlines: vec![Inline::Text(definition.clone())],
+                    ..Default::default()
+                };
+                (term.clone(), vec![Block::Paragraph(paragraph)])
+            })
+            .collect();
+        definitions.extend(take_glossary_blocks(doc));
+
+        let mut text = String::new();
+        visit_inlines(&mut doc.blocks, &mut |inline| {
+            if let Inline::Text(t) = inline {
+                text.push_str(t);
+                text.push('\n');
+            }
+        });
+
+        let mut used: Vec<_> = definitions
+            .into_iter()
+            .filter(|(term, _)| uses_term(&text, term))
+            .collect();
+        if used.is_empty() {
+            return Ok(());
+        }
+        used.sort_by_cached_key(|(term, _)| term.to_lowercase());
+
+        let list = Block::List(List {
+            list_type: ListType::Description,
+            items: used
+                .into_iter()
+                .map(|(term, content)| ListItem {
+                    content,
+                    level: 0,
+                    term: Some(vec![Inline::Text(term)]),
+                    checked: None,
+                })
+                .collect(),
+            style_id: None,
+            numbering: None,
+            start: None,
+        });
+
+        let existing = doc.blocks.iter().position(|block| {
+            matches!(block, Block::Heading(heading)
+                if plain_text(&heading.text).eq_ignore_ascii_case(&self.title))
+        });
+        match existing {
+            Some(index) => {
+                doc.blocks.insert(index + 1, list);
+                doc.source_map.insert_node(&NodePath::block(index + 1));
+            }
+            None => {
+                doc.push(Block::Heading(Heading {
+                    level: self.level,
+                    text: vec![Inline::Text(self.title.clone())],
+                    style_id: None,
+                    anchor: None,
+                    role: None,
+                }));
+                doc.push(list);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Remove `[glossary]` open blocks, returning the terms they define
+fn take_glossary_blocks(doc: &mut Document) -> Vec<(String, Vec<Block>)> {
+    let mut definitions = Vec::new();
+    let source_map = &mut doc.source_map;
+    let root = NodePath::default();
+    visit_block_lists_at(&mut doc.blocks, &root, 0, &mut |blocks, path| {
+        // Last first, so the paths of the blocks before stay put
+        let mut taken = Vec::new();
+        for index in (0..blocks.len()).rev() {
+            if let Block::Open(open) = &blocks[index] {
+                if open
+                    .role
+                    .as_deref()
+                    .is_some_and(|role| has_role(role, "glossary"))
+                {
+                    taken.push(blocks.remove(index));
+                    source_map.remove_node(&path(index));
+                }
+            }
+        }
+        for block in taken.into_iter().rev() {
+            let Block::Open(open) = block else {
+                continue;
+            };
+            for block in open.blocks {
+                if let Block::List(list) = block {
+                    for item in list.items {
+                        if let Some(term) = item.term {
+                            definitions.push((plain_text(&term), item.content));
+                        }
+                    }
+                }
+            }
+        }
+    });
+    definitions
+}
+
+/// Whether `text` contains `term` as a whole word
+fn uses_term(text: &str, term: &str) -> bool {
+    !term.is_empty()
+        && text.match_indices(term).any(|(start, _)| {
+            let before = text[..start].chars().next_back();
+            let after = text[start + term.len()..].chars().next();
+            !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn glossary_terms(doc: &Document) -> Vec<String> {
+        match doc.blocks.last() {
+            Some(Block::List(list)) => list
+                .items
+                .iter()
+                .map(|item| plain_text(item.term.as_deref().unwrap_or_default()))
+                .collect(),
+            other => panic!("Expected glossary list, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_collects_used_terms() {
+        let mut doc = parse(
+            "= Doc\n\n== Intro\n\nThe API ships with an sdk and C++ bindings.\n\n\
+             [glossary]\n--\nSDK:: Software development kit\nCLI:: Command line\n--\n",
+        )
+        .unwrap();
+        Glossary::default()
+            .with_term("API", "Application programming interface")
+            .with_term("C++", "A language")
+            .with_term("AP", "Unused, only part of a word")
+            .apply(&mut doc)
+            .unwrap();
+
+        // SDK is defined in the document but only used in lower case
+        assert_eq!(glossary_terms(&doc), ["API", "C++"]);
+        let asciidoc = crate::generate(&doc);
+        assert!(asciidoc.contains("== Glossary"));
+        assert!(!asciidoc.contains("Command line"));
+    }
+
+    #[test]
+    fn test_fills_existing_section() {
+        let mut doc = parse(
+            "= Doc\n\nUse the CLI.\n\n== Glossary\n\n== Index\n\n\
+             [glossary]\n--\nCLI:: Command line interface\n--\n",
+        )
+        .unwrap();
+        Glossary::default().apply(&mut doc).unwrap();
+
+        let asciidoc = crate::generate(&doc);
+        let glossary = asciidoc.find("== Glossary").unwrap();
+        let entry = asciidoc.find("CLI:: Command line interface").unwrap();
+        let index = asciidoc.find("== Index").unwrap();
+        assert!(glossary < entry && entry < index);
+    }
+
+    #[test]
+    fn test_no_terms_no_section() {
+        let mut doc = parse("= Doc\n\nNothing to define.\n").unwrap();
+        Glossary::default()
+            .with_term("API", "Application programming interface")
+            .apply(&mut doc)
+            .unwrap();
+        assert_eq!(doc.blocks.len(), 1);
+    }
+}
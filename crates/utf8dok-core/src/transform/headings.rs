@@ -0,0 +1,196 @@
+//! Heading filters: level shifting and section numbering
+
+use std::collections::HashMap;
+
+use utf8dok_ast::{Block, Document, Inline, NodePath};
+
+use super::{visit_block_lists, visit_block_lists_at, Transform, TransformError};
+use crate::ids::{section_ids, IdSource};
+
+/// Deepest heading level
+const MAX_LEVEL: u8 = 6;
+
+/// Moves every heading down (or up, for a negative shift) by some levels
+///
+/// Useful when a document is included under a heading of another one.
+/// Levels stay between 1 and 6.
+#[derive(Debug, Clone)]
+pub struct ShiftHeadings {
+    by: i8,
+}
+
+impl ShiftHeadings {
+    /// Shift headings by `by` levels
+    pub fn new(by: i8) -> Self {
+        Self { by }
+    }
+}
+
+impl Transform for ShiftHeadings {
+    fn name(&self) -> &str {
+        "shift-headings"
+    }
+
+    fn apply(&self, doc: &mut Document) -> Result<(), TransformError> {
+        visit_block_lists(&mut doc.blocks, &mut |blocks| {
+            for block in blocks.iter_mut() {
+                if let Block::Heading(heading) = block {
+                    let level = i16::from(heading.level) + i16::from(self.by);
+                    heading.level = level.clamp(1, i16::from(MAX_LEVEL)) as u8;
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Prefixes headings with their section number (`1.`, `1.2.`)
+///
+/// Numbers follow the headings in document order, as `:sectnums:` does.
+/// Levels count from the top heading level of the document, so a shifted
+/// document still starts at `1.`; headings deeper than the configured
+/// level keep their text. Numbered headings keep the id generated from
+/// their text as an explicit anchor, so links to them still resolve.
+#[derive(Debug, Clone)]
+pub struct NumberSections {
+    levels: u8,
+}
+
+impl Default for NumberSections {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_LEVELS)
+    }
+}
+
+impl NumberSections {
+    /// Levels numbered by default, as for `:sectnumlevels:`
+    pub const DEFAULT_LEVELS: u8 = 3;
+
+    /// Number headings of the top `levels` levels
+    pub fn new(levels: u8) -> Self {
+        Self { levels }
+    }
+}
+
+impl Transform for NumberSections {
+    fn name(&self) -> &str {
+        "number-sections"
+    }
+
+    fn apply(&self, doc: &mut Document) -> Result<(), TransformError> {
+        let mut top = MAX_LEVEL;
+        visit_block_lists(&mut doc.blocks, &mut |blocks| {
+            for block in blocks.iter() {
+                if let Block::Heading(heading) = block {
+                    top = top.min(heading.level.max(1));
+                }
+            }
+        });
+
+        // Numbers must not change the ids generated from the heading text,
+        // which cross references point at
+        let mut generated: HashMap<NodePath, String> = section_ids(doc)
+            .into_iter()
+            .filter(|section| section.source == IdSource::Generated)
+            .map(|section| (section.path, section.id))
+            .collect();
+
+        let mut counters = [0u32; MAX_LEVEL as usize];
+        let source_map = &mut doc.source_map;
+        let root = NodePath::default();
+        visit_block_lists_at(&mut doc.blocks, &root, 0, &mut |blocks, path| {
+            for (index, block) in blocks.iter_mut().enumerate() {
+                let Block::Heading(heading) = block else {
+                    continue;
+                };
+                let depth = usize::from(heading.level.clamp(top, MAX_LEVEL) - top + 1);
+                counters[depth - 1] += 1;
+                counters[depth..].fill(0);
+                if depth > usize::from(self.levels) {
+                    continue;
+                }
+
+                if let Some(id) = generated.remove(&path(index)) {
+                    heading.anchor = Some(id);
+                }
+                let mut number: String = counters[..depth]
+                    .iter()
+                    .map(|counter| format!("{}.", counter))
+                    .collect();
+                number.push(' ');
+                match heading.text.first_mut() {
+                    Some(Inline::Text(text)) => text.insert_str(0, &number),
+                    _ => {
+                        heading.text.insert(0, Inline::Text(number));
+                        source_map.insert_node(&path(index).child(0));
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn headings(doc: &Document) -> Vec<(u8, String)> {
+        doc.blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Heading(heading) => {
+                    Some((heading.level, crate::markdown::plain_text(&heading.text)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shift_headings_clamps() {
+        let mut doc = parse("= Doc\n\n== One\n\n===== Deep\n").unwrap();
+        ShiftHeadings::new(2).apply(&mut doc).unwrap();
+        assert_eq!(
+            headings(&doc),
+            [(3, "One".to_string()), (6, "Deep".to_string())]
+        );
+
+        ShiftHeadings::new(-4).apply(&mut doc).unwrap();
+        assert_eq!(
+            headings(&doc),
+            [(1, "One".to_string()), (2, "Deep".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_number_sections() {
+        let mut doc =
+            parse("= Doc\n\n== Intro\n\n=== Scope\n\n==== Detail\n\n=== Terms\n\n== Usage\n")
+                .unwrap();
+        NumberSections::default().apply(&mut doc).unwrap();
+        assert_eq!(
+            headings(&doc),
+            [
+                (1, "1. Intro".to_string()),
+                (2, "1.1. Scope".to_string()),
+                (3, "1.1.1. Detail".to_string()),
+                (2, "1.2. Terms".to_string()),
+                (1, "2. Usage".to_string()),
+            ]
+        );
+
+        let Block::Heading(intro) = &doc.blocks[0] else {
+            panic!("Expected heading");
+        };
+        assert_eq!(intro.anchor.as_deref(), Some("_intro"));
+
+        let mut doc = parse("= Doc\n\n== Intro\n\n=== Scope\n").unwrap();
+        NumberSections::new(1).apply(&mut doc).unwrap();
+        assert_eq!(
+            headings(&doc),
+            [(1, "1. Intro".to_string()), (2, "Scope".to_string())]
+        );
+    }
+}
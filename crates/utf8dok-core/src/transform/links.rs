@@ -0,0 +1,133 @@
+//! Link rewriting
+
+use regex::Regex;
+use utf8dok_ast::{Document, Inline};
+
+use super::{visit_inlines, Transform, TransformError};
+
+/// Rewrites link targets matching a regular expression
+///
+/// The first match in each target is replaced, and `$1`, `${name}` in the
+/// replacement refer to capture groups. Cross references are links to
+/// `#id`, so they are rewritten too when the pattern matches them.
+///
+/// ```
+/// use utf8dok_core::transform::RewriteLinks;
+///
+/// let rewrite = RewriteLinks::new(r"\.adoc(#|$)", ".html$1").unwrap();
+/// assert_eq!(rewrite.rewrite("setup.adoc#linux"), "setup.html#linux");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RewriteLinks {
+    pattern: Regex,
+    replacement: String,
+}
+
+impl RewriteLinks {
+    /// Rewrite targets matching `pattern` with `replacement`
+    pub fn new(pattern: &str, replacement: &str) -> Result<Self, TransformError> {
+        let pattern = Regex::new(pattern).map_err(|e| TransformError::Config {
+            transform: "rewrite-links".to_string(),
+            message: e.to_string(),
+        })?;
+        Ok(Self {
+            pattern,
+            replacement: replacement.to_string(),
+        })
+    }
+
+    /// The rewritten form of a link target
+    pub fn rewrite(&self, url: &str) -> String {
+        self.pattern
+            .replace(url, self.replacement.as_str())
+            .into_owned()
+    }
+}
+
+impl Transform for RewriteLinks {
+    fn name(&self) -> &str {
+        "rewrite-links"
+    }
+
+    fn apply(&self, doc: &mut Document) -> Result<(), TransformError> {
+        visit_inlines(&mut doc.blocks, &mut |inline| {
+            if let Inline::Link(link) = inline {
+                link.url = self.rewrite(&link.url);
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utf8dok_ast::{Block, Link, List, ListItem, ListType, Paragraph};
+
+    fn link(url: &str) -> Inline {
+        Inline::Link(Link {
+            url: url.to_string(),
+            text: vec![Inline::Text("here".to_string())],
+        })
+    }
+
+    fn urls(doc: &mut Document) -> Vec<String> {
+        let mut urls = Vec::new();
+        visit_inlines(&mut doc.blocks, &mut |inline| {
+            if let Inline::Link(link) = inline {
+                urls.push(link.url.clone());
+            }
+        });
+        urls
+    }
+
+    #[test]
+    fn test_rewrites_nested_links() {
+        let mut doc = Document::new();
+        doc.push(Block::Paragraph(Paragraph {
+            inlines: vec![
+                link("http://old.example.com/a"),
+                Inline::Span(vec![link("https://other.example.com/")]),
+            ],
+            ..Default::default()
+        }));
+        doc.push(Block::List(List {
+            list_type: ListType::Unordered,
+            items: vec![ListItem {
+                content: vec![Block::Paragraph(Paragraph {
+                    inlines: vec![link("http://old.example.com/b")],
+                    ..Default::default()
+                })],
+                level: 0,
+                term: None,
+                checked: None,
+            }],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+
+        RewriteLinks::new("^http://old\\.example\\.com/", "https://docs.example.com/")
+            .unwrap()
+            .apply(&mut doc)
+            .unwrap();
+        assert_eq!(
+            urls(&mut doc),
+            [
+                "https://docs.example.com/a",
+                "https://other.example.com/",
+                "https://docs.example.com/b"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cross_references_are_links() {
+        let mut doc = crate::parse("See <<setup>>.\n").unwrap();
+        RewriteLinks::new("^#", "guide.html#")
+            .unwrap()
+            .apply(&mut doc)
+            .unwrap();
+        assert_eq!(urls(&mut doc), ["guide.html#setup"]);
+    }
+}
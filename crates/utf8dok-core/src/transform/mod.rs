@@ -0,0 +1,580 @@
+//! AST Transformation Pipeline
+//!
+//! Transforms rewrite a parsed document before it is rendered, so documents
+//! can be adapted without forking a backend. A [`Pipeline`] runs them in
+//! order on the same `Document`, whichever frontend produced it.
+//!
+//! # Built-in Filters
+//!
+//! | Name | Filter | Effect |
+//! |------|--------|--------|
+//! | `shift-headings` | [`ShiftHeadings`] | Moves every heading up or down by some levels |
+//! | `number-sections` | [`NumberSections`] | Prefixes headings with `1.2.` section numbers |
+//! | `rewrite-links` | [`RewriteLinks`] | Rewrites link targets with a regular expression |
+//! | `strip-draft` | [`StripDraft`] | Removes blocks with the `draft` role |
+//! | `glossary` | [`Glossary`] | Collects the terms the document uses into a glossary |
+//!
+//! # Configuration
+//!
+//! Pipelines are described by [`TransformSpec`] entries, one per filter,
+//! which `utf8dok.toml` lists as `[[transform]]` tables:
+//!
+//! ```toml
+//! [[transform]]
+//! name = "shift-headings"
+//! by = 1
+//!
+//! [[transform]]
+//! name = "rewrite-links"
+//! pattern = "\\.adoc(#|$)"
+//! replacement = ".html$1"
+//!
+//! [[transform]]
+//! name = "script"
+//! path = "transforms/redact.rhai"
+//! ```
+//!
+//! Script transforms are provided by `utf8dok-plugins`; the loader passed
+//! to [`Pipeline::from_specs`] builds them.
+//!
+//! # Example
+//!
+//! ```
+//! use utf8dok_core::parse;
+//! use utf8dok_core::transform::{NumberSections, Pipeline, ShiftHeadings};
+//!
+//! let mut doc = parse("= Guide\n\n== Setup\n\n=== Linux\n").unwrap();
+//! let pipeline = Pipeline::new()
+//!     .with(ShiftHeadings::new(1))
+//!     .with(NumberSections::default());
+//! pipeline.run(&mut doc).unwrap();
+//!
+//! let asciidoc = utf8dok_core::generate(&doc);
+//! assert!(asciidoc.contains("=== 1. Setup"));
+//! assert!(asciidoc.contains("==== 1.1. Linux"));
+//! ```
+
+mod draft;
+mod glossary;
+mod headings;
+mod links;
+
+pub use draft::StripDraft;
+pub use glossary::Glossary;
+pub use headings::{NumberSections, ShiftHeadings};
+pub use links::RewriteLinks;
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use utf8dok_ast::{Block, Document, Inline, Node, NodePath, SourceMap};
+
+/// A rewrite of a parsed document
+pub trait Transform {
+    /// Name of the transform, for progress output and errors
+    fn name(&self) -> &str;
+
+    /// Rewrite the document in place
+    ///
+    /// A transform that adds, removes or moves nodes keeps
+    /// `doc.source_map` pointing at the same nodes (see
+    /// [`SourceMap::remove_node`] and [`SourceMap::insert_node`]). When
+    /// the structure changes and the map is left as it was, the pipeline
+    /// clears it.
+    fn apply(&self, doc: &mut Document) -> Result<(), TransformError>;
+}
+
+/// Errors building or running transforms
+#[derive(Debug, thiserror::Error)]
+pub enum TransformError {
+    /// The configuration of a transform is invalid
+    #[error("Invalid configuration for transform '{transform}': {message}")]
+    Config {
+        /// Name of the transform
+        transform: String,
+        /// What is wrong
+        message: String,
+    },
+
+    /// A transform failed while rewriting the document
+    #[error("Transform '{transform}' failed: {message}")]
+    Failed {
+        /// Name of the transform
+        transform: String,
+        /// What went wrong
+        message: String,
+    },
+}
+
+/// Transforms applied in order
+#[derive(Default)]
+pub struct Pipeline {
+    transforms: Vec<Box<dyn Transform>>,
+}
+
+impl Pipeline {
+    /// Create an empty pipeline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a transform to the end of the pipeline
+    pub fn with(mut self, transform: impl Transform + 'static) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Add a boxed transform to the end of the pipeline
+    pub fn push(&mut self, transform: Box<dyn Transform>) {
+        self.transforms.push(transform);
+    }
+
+    /// Build a pipeline from configuration
+    ///
+    /// `load_script` builds the transform for each `script` entry, given
+    /// its path as configured.
+    pub fn from_specs(
+        specs: &[TransformSpec],
+        mut load_script: impl FnMut(&str) -> Result<Box<dyn Transform>, TransformError>,
+    ) -> Result<Self, TransformError> {
+        let mut pipeline = Self::new();
+        for spec in specs {
+            let transform: Box<dyn Transform> = match spec {
+                TransformSpec::ShiftHeadings { by } => Box::new(ShiftHeadings::new(*by)),
+                TransformSpec::NumberSections { levels } => Box::new(NumberSections::new(*levels)),
+                TransformSpec::RewriteLinks {
+                    pattern,
+                    replacement,
+                } => Box::new(RewriteLinks::new(pattern, replacement)?),
+                TransformSpec::StripDraft { role } => Box::new(StripDraft::new(role)),
+                TransformSpec::Glossary {
+                    title,
+                    level,
+                    terms,
+                } => Box::new(
+                    Glossary::new(title)
+                        .with_level(*level)
+                        .with_terms(terms.clone()),
+                ),
+                TransformSpec::Script { path } => load_script(path)?,
+            };
+            pipeline.push(transform);
+        }
+        Ok(pipeline)
+    }
+
+    /// Whether the pipeline has no transforms
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+
+    /// Number of transforms in the pipeline
+    pub fn len(&self) -> usize {
+        self.transforms.len()
+    }
+
+    /// Names of the transforms, in order
+    pub fn names(&self) -> Vec<&str> {
+        self.transforms.iter().map(|t| t.name()).collect()
+    }
+
+    /// Apply every transform to the document, stopping at the first error
+    ///
+    /// The source map is cleared after a transform that changes the
+    /// structure of the tree without updating it.
+    pub fn run(&self, doc: &mut Document) -> Result<(), TransformError> {
+        for transform in &self.transforms {
+            if doc.source_map.is_empty() {
+                transform.apply(doc)?;
+                continue;
+            }
+            let (shape, source_map) = (tree_shape(doc), doc.source_map.clone());
+            transform.apply(doc)?;
+            if doc.source_map == source_map && tree_shape(doc) != shape {
+                doc.source_map = SourceMap::default();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Configuration of one transform
+///
+/// The `name` field selects the filter; the other fields are its options.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "name", rename_all = "kebab-case")]
+pub enum TransformSpec {
+    /// [`ShiftHeadings`]
+    ShiftHeadings {
+        /// Levels to move headings down by (negative moves them up)
+        by: i8,
+    },
+    /// [`NumberSections`]
+    NumberSections {
+        /// Deepest heading level to number
+        #[serde(default = "default_section_levels")]
+        levels: u8,
+    },
+    /// [`RewriteLinks`]
+    RewriteLinks {
+        /// Regular expression matched against link targets
+        pattern: String,
+        /// Replacement for the first match (`$1` refers to groups)
+        replacement: String,
+    },
+    /// [`StripDraft`]
+    StripDraft {
+        /// Role marking draft blocks
+        #[serde(default = "default_draft_role")]
+        role: String,
+    },
+    /// [`Glossary`]
+    Glossary {
+        /// Title of the glossary section
+        #[serde(default = "default_glossary_title")]
+        title: String,
+        /// Level of the glossary heading
+        #[serde(default = "default_glossary_level")]
+        level: u8,
+        /// Terms and their definitions
+        #[serde(default)]
+        terms: BTreeMap<String, String>,
+    },
+    /// A Rhai script (see `utf8dok-plugins`)
+    Script {
+        /// Path of the script
+        path: String,
+    },
+}
+
+fn default_section_levels() -> u8 {
+    NumberSections::DEFAULT_LEVELS
+}
+
+fn default_draft_role() -> String {
+    StripDraft::DEFAULT_ROLE.to_string()
+}
+
+fn default_glossary_title() -> String {
+    Glossary::DEFAULT_TITLE.to_string()
+}
+
+fn default_glossary_level() -> u8 {
+    1
+}
+
+/// Call `f` on `blocks` and on every block list nested in them, outermost
+/// first
+///
+/// Blocks `f` removes are not visited.
+pub(crate) fn visit_block_lists(blocks: &mut Vec<Block>, f: &mut impl FnMut(&mut Vec<Block>)) {
+    visit_block_lists_at(blocks, &NodePath::default(), 0, &mut |blocks, _| f(blocks));
+}
+
+/// Call `f` on `blocks` and on every block list nested in them, outermost
+/// first, with a function giving the [`NodePath`] of a block by its index
+/// in the list
+///
+/// `parent` is the path of the node holding `blocks`, and `first` the
+/// index of the first block among its children. Paths are those of the
+/// tree as `f` leaves it.
+pub(crate) fn visit_block_lists_at(
+    blocks: &mut Vec<Block>,
+    parent: &NodePath,
+    first: usize,
+    f: &mut impl FnMut(&mut Vec<Block>, &dyn Fn(usize) -> NodePath),
+) {
+    f(blocks, &|index| parent.child(first + index));
+    for (index, block) in blocks.iter_mut().enumerate() {
+        let path = parent.child(first + index);
+        match block {
+            Block::List(list) => {
+                for (index, item) in list.items.iter_mut().enumerate() {
+                    let first = item.term.as_ref().map_or(0, Vec::len);
+                    visit_block_lists_at(&mut item.content, &path.child(index), first, f);
+                }
+            }
+            Block::Table(table) => {
+                for (row_index, row) in table.rows.iter_mut().enumerate() {
+                    for (index, cell) in row.cells.iter_mut().enumerate() {
+                        let path = path.child(row_index).child(index);
+                        visit_block_lists_at(&mut cell.content, &path, 0, f);
+                    }
+                }
+            }
+            Block::Admonition(admonition) => {
+                visit_block_lists_at(&mut admonition.content, &path, 0, f)
+            }
+            Block::Open(open) => visit_block_lists_at(&mut open.blocks, &path, 0, f),
+            Block::Sidebar(sidebar) => visit_block_lists_at(&mut sidebar.blocks, &path, 0, f),
+            Block::Quote(quote) => visit_block_lists_at(&mut quote.blocks, &path, 0, f),
+            Block::Paragraph(_)
+            | Block::Heading(_)
+            | Block::Literal(_)
+            | Block::Break(_)
+            | Block::ThematicBreak
+            | Block::Comment(_) => {}
+        }
+    }
+}
+
+/// Kind of a node, as far as the structure of the tree goes
+#[derive(Debug, PartialEq)]
+enum NodeKind {
+    Block(std::mem::Discriminant<Block>),
+    ListItem,
+    TableRow,
+    TableCell,
+    Inline(std::mem::Discriminant<Inline>),
+}
+
+/// Path and kind of every node of the document, in document order
+fn tree_shape(doc: &Document) -> Vec<(NodePath, NodeKind)> {
+    fn visit(node: Node<'_>, path: NodePath, shape: &mut Vec<(NodePath, NodeKind)>) {
+        let kind = match node {
+            Node::Block(block) => NodeKind::Block(std::mem::discriminant(block)),
+            Node::ListItem(_) => NodeKind::ListItem,
+            Node::TableRow(_) => NodeKind::TableRow,
+            Node::TableCell(_) => NodeKind::TableCell,
+            Node::Inline(inline) => NodeKind::Inline(std::mem::discriminant(inline)),
+        };
+        shape.push((path.clone(), kind));
+        for (index, child) in node.children().into_iter().enumerate() {
+            visit(child, path.child(index), shape);
+        }
+    }
+
+    let mut shape = Vec::new();
+    for (index, block) in doc.blocks.iter().enumerate() {
+        visit(Node::Block(block), NodePath::block(index), &mut shape);
+    }
+    shape
+}
+
+/// Call `f` on every inline of `blocks`, including link texts, formatted
+/// text and footnotes
+pub(crate) fn visit_inlines(blocks: &mut Vec<Block>, f: &mut impl FnMut(&mut Inline)) {
+    visit_block_lists(blocks, &mut |blocks| {
+        for block in blocks.iter_mut() {
+            let inlines = match block {
+                Block::Paragraph(paragraph) => Some(&mut paragraph.inlines),
+                Block::Heading(heading) => Some(&mut heading.text),
+                Block::Table(table) => table.caption.as_mut(),
+                Block::Admonition(admonition) => admonition.title.as_mut(),
+                _ => None,
+            };
+            inlines
+                .into_iter()
+                .flatten()
+                .for_each(|inline| visit_inline(inline, f));
+            if let Block::List(list) = block {
+                list.items
+                    .iter_mut()
+                    .filter_map(|item| item.term.as_mut())
+                    .flatten()
+                    .for_each(|inline| visit_inline(inline, f));
+            }
+        }
+    });
+}
+
+fn visit_inline(inline: &mut Inline, f: &mut impl FnMut(&mut Inline)) {
+    f(inline);
+    let children: &mut [Inline] = match inline {
+        Inline::Format(_, inner) => std::slice::from_mut(inner.as_mut()),
+        Inline::Span(inlines) => inlines,
+        Inline::Link(link) => &mut link.text,
+        Inline::Footnote(footnote) => &mut footnote.content,
+        Inline::Text(_) | Inline::Image(_) | Inline::Break | Inline::Anchor(_) => &mut [],
+    };
+    for child in children {
+        visit_inline(child, f);
+    }
+}
+
+/// Whether a role attribute (`.draft`, `abstract.draft`, `draft internal`)
+/// includes `role`
+pub(crate) fn has_role(roles: &str, role: &str) -> bool {
+    roles
+        .split(['.', ' ', ','])
+        .any(|candidate| candidate == role)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    struct Retitle;
+
+    impl Transform for Retitle {
+        fn name(&self) -> &str {
+            "retitle"
+        }
+
+        fn apply(&self, doc: &mut Document) -> Result<(), TransformError> {
+            doc.metadata.title = Some("Retitled".to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_pipeline_runs_in_order() {
+        let mut doc = parse("= Guide\n\n== Setup\n").unwrap();
+        let pipeline = Pipeline::new()
+            .with(Retitle)
+            .with(ShiftHeadings::new(2))
+            .with(ShiftHeadings::new(-1));
+        assert_eq!(
+            pipeline.names(),
+            ["retitle", "shift-headings", "shift-headings"]
+        );
+
+        pipeline.run(&mut doc).unwrap();
+        assert_eq!(doc.metadata.title.as_deref(), Some("Retitled"));
+        match &doc.blocks[0] {
+            Block::Heading(heading) => assert_eq!(heading.level, 2),
+            other => panic!("Expected heading, got {:?}", other),
+        }
+    }
+
+    /// Removes the first block, leaving the source map as it is
+    struct DropFirst;
+
+    impl Transform for DropFirst {
+        fn name(&self) -> &str {
+            "drop-first"
+        }
+
+        fn apply(&self, doc: &mut Document) -> Result<(), TransformError> {
+            doc.blocks.remove(0);
+            Ok(())
+        }
+    }
+
+    fn source_text<'a>(doc: &Document, source: &'a str, steps: &[usize]) -> &'a str {
+        let span = doc
+            .source_map
+            .span(&NodePath::from(steps.to_vec()))
+            .unwrap_or_else(|| panic!("No span for {:?}", steps));
+        &source[span.start..span.end]
+    }
+
+    #[test]
+    fn test_source_map_follows_transforms() {
+        let source = "= Guide\n\nIntro.\n\n[.draft]\n--\nNot yet.\n--\n\n\
+                      [glossary]\n--\nAPI:: Interface\n--\n\n\
+                      == Setup\n\n* one\n* two\n\nUse the API.\n";
+        let mut doc = parse(source).unwrap();
+        Pipeline::new()
+            .with(StripDraft::default())
+            .with(NumberSections::default())
+            .with(Glossary::default())
+            .run(&mut doc)
+            .unwrap();
+
+        assert_eq!(source_text(&doc, source, &[0]), "Intro.");
+        assert_eq!(source_text(&doc, source, &[1]), "== Setup");
+        assert_eq!(source_text(&doc, source, &[2, 1]), "* two");
+        assert_eq!(source_text(&doc, source, &[3]), "Use the API.");
+        // The generated glossary has no source
+        assert_eq!(doc.blocks.len(), 6);
+        assert!(doc.source_map.span(&NodePath::block(4)).is_none());
+        assert!(doc.source_map.span(&NodePath::block(5)).is_none());
+    }
+
+    #[test]
+    fn test_section_number_before_formatted_title_keeps_spans() {
+        use utf8dok_ast::{FormatType, Heading, SourceSpan};
+
+        let mut doc = Document::new();
+        doc.push(Block::Heading(Heading {
+            level: 1,
+            text: vec![Inline::Format(
+                FormatType::Bold,
+                Box::new(Inline::Text("Setup".to_string())),
+            )],
+            style_id: None,
+            anchor: None,
+            role: None,
+        }));
+        let span = SourceSpan::new(3, 10, 1, 4);
+        doc.source_map.insert_span(NodePath::from(vec![0, 0]), span);
+
+        Pipeline::new()
+            .with(NumberSections::default())
+            .run(&mut doc)
+            .unwrap();
+        assert_eq!(doc.source_map.span(&NodePath::from(vec![0, 0])), None);
+        assert_eq!(
+            doc.source_map.span(&NodePath::from(vec![0, 1])),
+            Some(&span)
+        );
+    }
+
+    #[test]
+    fn test_source_map_cleared_by_unmapped_structure_change() {
+        let source = "First.\n\nSecond.\n";
+        let mut doc = parse(source).unwrap();
+
+        // Changes that keep the structure keep the map
+        Pipeline::new()
+            .with(Retitle)
+            .with(ShiftHeadings::new(1))
+            .run(&mut doc)
+            .unwrap();
+        assert_eq!(source_text(&doc, source, &[1]), "Second.");
+
+        Pipeline::new().with(DropFirst).run(&mut doc).unwrap();
+        assert!(doc.source_map.is_empty());
+    }
+
+    #[test]
+    fn test_specs_from_config() {
+        let specs: Vec<TransformSpec> = serde_json::from_str(
+            r#"[
+                {"name": "number-sections"},
+                {"name": "strip-draft", "role": "wip"},
+                {"name": "script", "path": "fix.rhai"},
+                {"name": "glossary", "terms": {"API": "Application interface"}}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(specs[0], TransformSpec::NumberSections { levels: 3 });
+
+        let mut scripts = Vec::new();
+        let pipeline = Pipeline::from_specs(&specs, |path| {
+            scripts.push(path.to_string());
+            Ok(Box::new(Retitle))
+        })
+        .unwrap();
+        assert_eq!(scripts, ["fix.rhai"]);
+        assert_eq!(
+            pipeline.names(),
+            ["number-sections", "strip-draft", "retitle", "glossary"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_spec_is_reported() {
+        let specs = [TransformSpec::RewriteLinks {
+            pattern: "(".to_string(),
+            replacement: String::new(),
+        }];
+        let error = Pipeline::from_specs(&specs, |_| unreachable!())
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            TransformError::Config { ref transform, .. } if transform == "rewrite-links"
+        ));
+    }
+
+    #[test]
+    fn test_has_role() {
+        assert!(has_role(".draft", "draft"));
+        assert!(has_role("abstract.draft", "draft"));
+        assert!(has_role("draft internal", "draft"));
+        assert!(!has_role(".drafty", "draft"));
+    }
+}
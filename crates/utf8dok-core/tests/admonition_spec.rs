@@ -0,0 +1,133 @@
+//! Admonition Specification Tests
+//!
+//! These tests verify the paragraph form (`NOTE: text`, `[TIP]` paragraphs)
+//! and the delimited form (`[WARNING]` on a `====` block) of admonitions.
+
+use utf8dok_ast::{AdmonitionType, Block, Inline};
+use utf8dok_core::{generate, parse};
+
+#[test]
+fn test_parse_admonition_paragraphs() {
+    let cases = [
+        ("NOTE", AdmonitionType::Note),
+        ("TIP", AdmonitionType::Tip),
+        ("IMPORTANT", AdmonitionType::Important),
+        ("WARNING", AdmonitionType::Warning),
+        ("CAUTION", AdmonitionType::Caution),
+    ];
+
+    for (label, expected) in cases {
+        let input = format!("{}: Mind the *gap*\nbetween lines.", label);
+        let doc = parse(&input).unwrap();
+        assert_eq!(doc.blocks.len(), 1);
+        match &doc.blocks[0] {
+            Block::Admonition(admonition) => {
+                assert_eq!(admonition.admonition_type, expected);
+                assert!(admonition.title.is_none());
+                let Block::Paragraph(para) = &admonition.content[0] else {
+                    panic!("Expected paragraph content");
+                };
+                assert_eq!(para.inlines[0], Inline::Text("Mind the ".to_string()));
+            }
+            other => panic!("Expected {} admonition, got {:?}", label, other),
+        }
+    }
+}
+
+#[test]
+fn test_label_must_start_the_paragraph() {
+    let doc = parse("Remember: NOTE: is not at the start.\n\nNote: lowercase").unwrap();
+    assert!(doc
+        .blocks
+        .iter()
+        .all(|block| matches!(block, Block::Paragraph(_))));
+}
+
+#[test]
+fn test_parse_admonition_style_on_paragraph() {
+    let input = r#".Shortcut
+[TIP]
+Press Ctrl+S to save."#;
+
+    let doc = parse(input).unwrap();
+    match &doc.blocks[0] {
+        Block::Admonition(admonition) => {
+            assert_eq!(admonition.admonition_type, AdmonitionType::Tip);
+            assert_eq!(
+                admonition.title,
+                Some(vec![Inline::Text("Shortcut".to_string())])
+            );
+        }
+        other => panic!("Expected tip, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_admonition_block_with_nested_content() {
+    let input = r#".Before you start
+[IMPORTANT]
+====
+Back up your data.
+
+* Settings
+* Documents
+
+----
+cp -r ~/data /backup
+----
+===="#;
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 1);
+    match &doc.blocks[0] {
+        Block::Admonition(admonition) => {
+            assert_eq!(admonition.admonition_type, AdmonitionType::Important);
+            assert_eq!(
+                admonition.title,
+                Some(vec![Inline::Text("Before you start".to_string())])
+            );
+            assert_eq!(admonition.content.len(), 3);
+            assert!(matches!(admonition.content[1], Block::List(_)));
+            assert!(matches!(admonition.content[2], Block::Literal(_)));
+        }
+        other => panic!("Expected important block, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_block_attributes_do_not_leak_past_paragraphs() {
+    let input = r#"[CAUTION]
+Hot surface.
+
+----
+plain code
+----"#;
+
+    let doc = parse(input).unwrap();
+    match &doc.blocks[1] {
+        Block::Literal(lit) => assert!(lit.style_id.is_none()),
+        other => panic!("Expected literal, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_roundtrip_admonitions() {
+    let input = r#"NOTE: A short note.
+
+.Careful
+[WARNING]
+====
+First.
+
+Second.
+===="#;
+
+    let doc = parse(input).unwrap();
+    let generated = generate(&doc);
+    let reparsed = parse(&generated).unwrap();
+    assert_eq!(
+        reparsed.blocks, doc.blocks,
+        "Round-trip should preserve admonitions. Generated:\n{}",
+        generated
+    );
+}
@@ -0,0 +1,112 @@
+//! Callout Specification Tests
+//!
+//! These tests verify source callouts (`<1>`, `<.>`, `// <1>`), the callout
+//! lists that explain them, highlighted lines (`highlight=2..4`), and their
+//! round-trip through the generator.
+
+use utf8dok_ast::{Block, Callout, Inline, ListType, LiteralBlock};
+use utf8dok_core::{generate, parse};
+
+/// Parse `input` and return its first block as a literal block
+fn parse_literal(input: &str) -> LiteralBlock {
+    let doc = parse(input).unwrap();
+    match &doc.blocks[0] {
+        Block::Literal(literal) => literal.clone(),
+        other => panic!("Expected literal, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_callouts_are_stripped_from_code() {
+    let literal = parse_literal(
+        r#"[source,rust]
+----
+use std::io; // <1>
+fn main() { <2> <3>
+}
+----"#,
+    );
+
+    assert_eq!(literal.language.as_deref(), Some("rust"));
+    assert_eq!(literal.content, "use std::io;\nfn main() {\n}");
+    assert_eq!(
+        literal.callouts,
+        vec![
+            Callout { number: 1, line: 1 },
+            Callout { number: 2, line: 2 },
+            Callout { number: 3, line: 2 },
+        ]
+    );
+}
+
+#[test]
+fn test_auto_numbered_callouts() {
+    let literal = parse_literal("----\nfirst <.>\nsecond <.>\n----");
+    let numbers: Vec<u32> = literal.callouts.iter().map(|c| c.number).collect();
+    assert_eq!(numbers, vec![1, 2]);
+}
+
+#[test]
+fn test_callout_list_follows_code() {
+    let input = r#"----
+docker run app <1>
+----
+<1> Starts the container
+that serves the app.
+<.> Second explanation"#;
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 2);
+    let Block::List(list) = &doc.blocks[1] else {
+        panic!("Expected callout list");
+    };
+    assert_eq!(list.list_type, ListType::Callout);
+    assert_eq!(list.items.len(), 2);
+    let Block::Paragraph(para) = &list.items[0].content[0] else {
+        panic!("Expected paragraph");
+    };
+    assert_eq!(
+        para.inlines,
+        vec![Inline::Text(
+            "Starts the container that serves the app.".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_highlighted_lines() {
+    let literal = parse_literal("[source,rust,highlight=\"1,3..4\"]\n----\na\nb\nc\nd\n----");
+    assert_eq!(literal.language.as_deref(), Some("rust"));
+    assert_eq!(literal.highlight_lines, vec![1, 3, 4]);
+}
+
+#[test]
+fn test_diagrams_keep_angle_markers() {
+    let literal = parse_literal("[plantuml]\n----\nA -> B : <1>\n----");
+    assert!(literal.callouts.is_empty());
+    assert_eq!(literal.content, "A -> B : <1>");
+}
+
+#[test]
+fn test_roundtrip_callouts() {
+    let input = r#"[source,bash,highlight="2..3"]
+----
+cd app <1>
+make
+make install <2>
+----
+<1> Enter the project.
+<2> Install it."#;
+
+    let doc = parse(input).unwrap();
+    let generated = generate(&doc);
+    let reparsed = parse(&generated).unwrap();
+    assert_eq!(
+        reparsed.blocks, doc.blocks,
+        "Round-trip should preserve callouts. Generated:\n{}",
+        generated
+    );
+    assert!(generated.contains("[source,bash,highlight=\"2..3\"]"));
+    assert!(generated.contains("make install <2>"));
+    assert!(generated.contains("<1> Enter the project."));
+}
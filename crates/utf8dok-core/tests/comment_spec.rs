@@ -0,0 +1,114 @@
+//! Comment Specification Tests
+//!
+//! These tests verify that `//` line comments and `////` comment blocks
+//! are parsed into comment blocks instead of leaking into paragraphs, and
+//! that they survive the round-trip through the AsciiDoc and Markdown
+//! generators.
+
+use utf8dok_ast::{Block, CommentBlock, Inline};
+use utf8dok_core::{generate, generate_markdown, parse, parse_markdown};
+
+fn comment(text: &str, delimited: bool) -> Block {
+    Block::Comment(CommentBlock {
+        text: text.to_string(),
+        delimited,
+    })
+}
+
+/// Collect the plain text of a paragraph block
+fn paragraph_text(block: &Block) -> String {
+    match block {
+        Block::Paragraph(p) => p
+            .inlines
+            .iter()
+            .filter_map(|inline| match inline {
+                Inline::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect(),
+        other => panic!("Expected paragraph, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_line_comments_are_one_block() {
+    let doc = parse("// First line\n//second line\n//\nPara one.").unwrap();
+    assert_eq!(doc.blocks.len(), 2);
+    assert_eq!(doc.blocks[0], comment("First line\nsecond line\n", false));
+    assert_eq!(paragraph_text(&doc.blocks[1]), "Para one.");
+}
+
+#[test]
+fn test_comment_block_is_verbatim() {
+    let input = "////\n== Not a heading\n\n* not a list {missing}\n////\n\nText.";
+    let doc = parse(input).unwrap();
+    assert_eq!(
+        doc.blocks[0],
+        comment("== Not a heading\n\n* not a list {missing}", true)
+    );
+    assert_eq!(paragraph_text(&doc.blocks[1]), "Text.");
+}
+
+#[test]
+fn test_comments_inside_paragraphs_and_lists_are_dropped() {
+    let input = "Para one\n// inside\nmore.\n\n* item\n// between\n* next";
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 2);
+    assert_eq!(paragraph_text(&doc.blocks[0]), "Para one more.");
+    match &doc.blocks[1] {
+        Block::List(list) => assert_eq!(list.items.len(), 2),
+        other => panic!("Expected list, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_comments_leave_block_attributes_and_title() {
+    let input = ".Example\n[source,rust]\n// the entry point\n----\nfn main() {}\n----";
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks[0], comment("the entry point", false));
+    match &doc.blocks[1] {
+        Block::Literal(literal) => {
+            assert_eq!(literal.language.as_deref(), Some("rust"));
+            assert_eq!(literal.title.as_deref(), Some("Example"));
+        }
+        other => panic!("Expected literal block, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_comments_in_the_header() {
+    let doc = parse("= Title\n// draft\n:author: Jane\n\nText.").unwrap();
+    assert_eq!(doc.metadata.title.as_deref(), Some("Title"));
+    assert_eq!(
+        doc.metadata.attributes.get("author").map(String::as_str),
+        Some("Jane")
+    );
+    assert_eq!(doc.blocks[0], comment("draft", false));
+}
+
+#[test]
+fn test_comments_in_compound_blocks() {
+    let doc = parse("====\n// note to self\nInside.\n====").unwrap();
+    match &doc.blocks[0] {
+        Block::Open(open) => assert_eq!(open.blocks[0], comment("note to self", false)),
+        other => panic!("Expected example block, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_asciidoc_roundtrip() {
+    let input = "// Review with legal\n\nPara.\n\n////\nA longer\nnote\n////";
+    let doc = parse(input).unwrap();
+    let generated = generate(&doc);
+    assert_eq!(generated, input);
+    assert_eq!(parse(&generated).unwrap().blocks, doc.blocks);
+}
+
+#[test]
+fn test_markdown_roundtrip() {
+    let doc = parse("// Review\n\nPara.\n\n////\nA longer\nnote\n////").unwrap();
+    let markdown = generate_markdown(&doc);
+    assert!(markdown.contains("<!-- Review -->\n"));
+    assert!(markdown.contains("<!--\nA longer\nnote\n-->"));
+    assert_eq!(parse_markdown(&markdown).blocks, doc.blocks);
+}
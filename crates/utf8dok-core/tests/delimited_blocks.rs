@@ -0,0 +1,333 @@
+//! Delimited Block Tests
+//!
+//! These tests verify parsing of the compound delimited blocks (example,
+//! sidebar, quote, verse, open) and literal and passthrough blocks,
+//! including nesting, block titles and quote attribution, and their
+//! round-trip through the generator.
+
+use utf8dok_ast::{Block, FormatType, Inline};
+use utf8dok_core::{generate, parse};
+
+/// Collect the plain text of a paragraph block
+fn paragraph_text(block: &Block) -> String {
+    match block {
+        Block::Paragraph(p) => p
+            .inlines
+            .iter()
+            .filter_map(|inline| match inline {
+                Inline::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect(),
+        other => panic!("Expected paragraph, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_example_block() {
+    let input = r#".Sample
+====
+First paragraph.
+
+Second paragraph.
+===="#;
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 1);
+    match &doc.blocks[0] {
+        Block::Open(open) => {
+            assert_eq!(open.role.as_deref(), Some("example"));
+            assert_eq!(open.title.as_deref(), Some("Sample"));
+            assert_eq!(open.blocks.len(), 2);
+            assert_eq!(paragraph_text(&open.blocks[1]), "Second paragraph.");
+        }
+        other => panic!("Expected example block, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_sidebar_block() {
+    let input = r#".Notes
+****
+Speaker notes here.
+****"#;
+
+    let doc = parse(input).unwrap();
+    match &doc.blocks[0] {
+        Block::Sidebar(sidebar) => {
+            assert!(sidebar.is_notes());
+            assert_eq!(sidebar.as_text(), "Speaker notes here.");
+        }
+        other => panic!("Expected sidebar, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_quote_block_attribution() {
+    let input = r#"[quote, "Lovelace, Ada", Notes on the Analytical Engine]
+____
+The engine weaves algebraic patterns.
+____"#;
+
+    let doc = parse(input).unwrap();
+    match &doc.blocks[0] {
+        Block::Quote(quote) => {
+            assert_eq!(quote.attribution.as_deref(), Some("Lovelace, Ada"));
+            assert_eq!(
+                quote.cite.as_deref(),
+                Some("Notes on the Analytical Engine")
+            );
+            assert_eq!(quote.blocks.len(), 1);
+        }
+        other => panic!("Expected quote, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_open_block_with_style() {
+    let input = r#"[abstract]
+--
+Summary text.
+--
+
+[quote, Anonymous]
+--
+Styled open block.
+--"#;
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 2);
+    match &doc.blocks[0] {
+        Block::Open(open) => assert_eq!(open.role.as_deref(), Some("abstract")),
+        other => panic!("Expected open block, got {:?}", other),
+    }
+    match &doc.blocks[1] {
+        Block::Quote(quote) => assert_eq!(quote.attribution.as_deref(), Some("Anonymous")),
+        other => panic!("Expected quote, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_nested_blocks() {
+    let input = r#"====
+Outer example.
+
+******
+Sidebar in an example.
+
+======
+Example in a sidebar in an example.
+
+----
+code
+----
+======
+******
+===="#;
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 1);
+    let Block::Open(outer) = &doc.blocks[0] else {
+        panic!("Expected example block");
+    };
+    assert_eq!(outer.blocks.len(), 2);
+    let Block::Sidebar(sidebar) = &outer.blocks[1] else {
+        panic!("Expected nested sidebar, got {:?}", outer.blocks[1]);
+    };
+    let Block::Open(inner) = &sidebar.blocks[1] else {
+        panic!("Expected nested example, got {:?}", sidebar.blocks[1]);
+    };
+    assert_eq!(inner.role.as_deref(), Some("example"));
+    assert!(matches!(&inner.blocks[1], Block::Literal(l) if l.content == "code"));
+}
+
+#[test]
+fn test_delimiters_inside_literal_are_content() {
+    let input = r#"====
+----
+****
+not a sidebar
+----
+===="#;
+
+    let doc = parse(input).unwrap();
+    let Block::Open(open) = &doc.blocks[0] else {
+        panic!("Expected example block");
+    };
+    assert_eq!(open.blocks.len(), 1);
+    assert!(matches!(&open.blocks[0], Block::Literal(l) if l.content == "****\nnot a sidebar"));
+}
+
+#[test]
+fn test_parse_passthrough_block() {
+    let input = r#"++++
+<div class="banner">
+
+  <p>{raw} *content*</p>
+</div>
+++++"#;
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 1);
+    match &doc.blocks[0] {
+        Block::Literal(lit) => {
+            assert_eq!(lit.style_id.as_deref(), Some("pass"));
+            assert_eq!(
+                lit.content,
+                "<div class=\"banner\">\n\n  <p>{raw} *content*</p>\n</div>"
+            );
+        }
+        other => panic!("Expected passthrough, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_literal_block() {
+    let input = ".Output\n....\nliteral {x}\n  indented\n----\n....";
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 1);
+    match &doc.blocks[0] {
+        Block::Literal(lit) => {
+            assert_eq!(lit.style_id.as_deref(), Some("literal"));
+            assert_eq!(lit.title.as_deref(), Some("Output"));
+            assert_eq!(lit.content, "literal {x}\n  indented\n----");
+        }
+        other => panic!("Expected literal, got {:?}", other),
+    }
+    assert_eq!(generate(&doc), input);
+}
+
+#[test]
+fn test_parse_verse_block() {
+    let input = "[verse, Poet, Collected Poems]\n____\nline one\nline *two*\n\nsecond stanza\n____";
+
+    let doc = parse(input).unwrap();
+    let Block::Quote(quote) = &doc.blocks[0] else {
+        panic!("Expected verse, got {:?}", doc.blocks[0]);
+    };
+    assert!(quote.verse);
+    assert_eq!(quote.attribution.as_deref(), Some("Poet"));
+    assert_eq!(quote.blocks.len(), 2);
+    let Block::Paragraph(stanza) = &quote.blocks[0] else {
+        panic!("Expected stanza, got {:?}", quote.blocks[0]);
+    };
+    assert_eq!(
+        stanza.inlines,
+        [
+            Inline::Text("line one".to_string()),
+            Inline::Break,
+            Inline::Text("line ".to_string()),
+            Inline::Format(FormatType::Bold, Box::new(Inline::Text("two".to_string()))),
+        ]
+    );
+    assert_eq!(generate(&doc), input);
+}
+
+#[test]
+fn test_block_titles_on_literal_and_table() {
+    let input = r#".Listing
+----
+ls -l
+----
+
+.Results
+|===
+| A | B
+|==="#;
+
+    let doc = parse(input).unwrap();
+    match &doc.blocks[0] {
+        Block::Literal(lit) => assert_eq!(lit.title.as_deref(), Some("Listing")),
+        other => panic!("Expected literal, got {:?}", other),
+    }
+    match &doc.blocks[1] {
+        Block::Table(table) => assert_eq!(
+            table.caption,
+            Some(vec![Inline::Text("Results".to_string())])
+        ),
+        other => panic!("Expected table, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_block_title_on_image() {
+    let input = ".Architecture\nimage::arch.png[Overview]\n\nimage::plain.png[]";
+
+    let doc = parse(input).unwrap();
+    match &doc.blocks[0] {
+        Block::Paragraph(p) => {
+            assert!(matches!(&p.inlines[..], [Inline::Image(_)]));
+            assert_eq!(
+                p.attributes.get("title").map(String::as_str),
+                Some("Architecture")
+            );
+        }
+        other => panic!("Expected image paragraph, got {:?}", other),
+    }
+    match &doc.blocks[1] {
+        Block::Paragraph(p) => assert!(p.attributes.is_empty()),
+        other => panic!("Expected image paragraph, got {:?}", other),
+    }
+    assert_eq!(generate(&doc), input);
+}
+
+#[test]
+fn test_unterminated_block_is_closed_at_end() {
+    let doc = parse("****\nDangling sidebar.").unwrap();
+    assert!(matches!(&doc.blocks[0], Block::Sidebar(s) if s.blocks.len() == 1));
+}
+
+#[test]
+fn test_roundtrip_delimited_blocks() {
+    let input = r#".Sample
+====
+Outer example.
+
+======
+Nested example.
+======
+====
+
+[quote, "Doe, Jane", Memoirs]
+____
+Quoted text.
+
+[quote, Inner]
+________
+A quote in a quote.
+________
+____
+
+.Notes
+****
+Notes.
+****
+
+[abstract]
+--
+Abstract text.
+--
+
+++++
+<b>raw</b>
+++++"#;
+
+    let doc = parse(input).unwrap();
+    let generated = generate(&doc);
+    let reparsed = parse(&generated).unwrap();
+
+    assert_eq!(
+        reparsed.blocks, doc.blocks,
+        "Round-trip should preserve blocks. Generated:\n{}",
+        generated
+    );
+    assert!(
+        generated.contains("\n=====\n"),
+        "Nested example needs a longer delimiter"
+    );
+    assert!(
+        generated.contains("\n_____\n"),
+        "Nested quote needs a longer delimiter"
+    );
+}
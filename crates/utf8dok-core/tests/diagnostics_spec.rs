@@ -0,0 +1,151 @@
+//! Parser Diagnostics Specification Tests
+//!
+//! These tests verify that `parse_with_diagnostics` always returns a
+//! best-effort document, and reports what it recovered from (unclosed
+//! delimiters, malformed attributes, unknown styles, unresolved includes)
+//! with codes and source spans.
+
+use utf8dok_ast::Block;
+use utf8dok_core::{parse_with_diagnostics, Diagnostic, Diagnostics, ParserConfig};
+
+/// Parse `input` with the default configuration
+fn parse(input: &str) -> (utf8dok_ast::Document, Diagnostics) {
+    parse_with_diagnostics(input, ParserConfig::default())
+}
+
+/// The only diagnostic with `code`
+fn single<'a>(diagnostics: &'a Diagnostics, code: &str) -> &'a Diagnostic {
+    let found: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code.as_deref() == Some(code))
+        .collect();
+    assert_eq!(found.len(), 1, "Expected one {}: {:?}", code, diagnostics);
+    found[0]
+}
+
+/// Source text covered by a diagnostic's span
+fn spanned<'a>(diagnostic: &Diagnostic, input: &'a str) -> &'a str {
+    let span = diagnostic.span.as_ref().expect("diagnostic has a span");
+    &input[span.start..span.end]
+}
+
+#[test]
+fn test_clean_document_has_no_diagnostics() {
+    let (doc, diagnostics) =
+        parse("= Title\n\n[source,rust]\n----\nfn main() {}\n----\n\n[NOTE]\nCareful.");
+    assert_eq!(doc.blocks.len(), 2);
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn test_unclosed_literal_block() {
+    let input = "Intro.\n\n----\ncode\nmore code";
+    let (doc, diagnostics) = parse(input);
+
+    // The block runs to the end of the document
+    let Block::Literal(literal) = &doc.blocks[1] else {
+        panic!("Expected literal, got {:?}", doc.blocks[1]);
+    };
+    assert_eq!(literal.content, "code\nmore code");
+
+    let diagnostic = single(&diagnostics, "PARSE101");
+    assert!(diagnostic.is_error());
+    assert_eq!(spanned(diagnostic, input), "----");
+    assert_eq!(diagnostic.span.as_ref().unwrap().line, Some(3));
+}
+
+#[test]
+fn test_unclosed_compound_blocks() {
+    let input = "====\nExample.\n\n****\nSidebar.";
+    let (doc, diagnostics) = parse(input);
+
+    assert_eq!(doc.blocks.len(), 1);
+    let unclosed: Vec<&str> = diagnostics
+        .iter()
+        .filter(|d| d.code.as_deref() == Some("PARSE101"))
+        .map(|d| spanned(d, input))
+        .collect();
+    // Innermost first, as they are closed
+    assert_eq!(unclosed, vec!["****", "===="]);
+}
+
+#[test]
+fn test_unclosed_table() {
+    let input = "|===\n| A | B";
+    let (doc, diagnostics) = parse(input);
+    assert!(matches!(doc.blocks[0], Block::Table(_)));
+    assert_eq!(spanned(single(&diagnostics, "PARSE101"), input), "|===");
+}
+
+#[test]
+fn test_malformed_attribute_list() {
+    let input = "Intro.\n\n[quote, \"Doe, Jane]\nQuoted.";
+    let (doc, diagnostics) = parse(input);
+    assert_eq!(doc.blocks.len(), 2);
+
+    let diagnostic = single(&diagnostics, "PARSE102");
+    assert_eq!(spanned(diagnostic, input), "[quote, \"Doe, Jane]");
+    assert_eq!(diagnostic.span.as_ref().unwrap().line, Some(3));
+}
+
+#[test]
+fn test_unknown_block_style() {
+    let input = "[sidebar]\nKnown.\n\n[quoet#intro.lead]\nTypo.\n\n[PLANTUML]\n----\nA -> B\n----";
+    let (_, diagnostics) = parse(input);
+
+    let diagnostic = single(&diagnostics, "PARSE103");
+    assert!(diagnostic.is_warning());
+    assert!(diagnostic.message.contains("quoet"));
+    assert_eq!(spanned(diagnostic, input), "quoet");
+    assert_eq!(diagnostic.span.as_ref().unwrap().column, Some(2));
+}
+
+#[test]
+fn test_named_attributes_are_not_styles() {
+    let (_, diagnostics) = parse("[cols=\"1,2\"]\n|===\n| a | b\n|===\n\n[#anchor.role]\nText.");
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn test_failed_data_include() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = "Before.\n\ninclude::missing.csv[]";
+    let config = ParserConfig::with_data_includes(dir.path().to_string_lossy());
+    let (doc, diagnostics) = parse_with_diagnostics(input, config);
+
+    // A placeholder paragraph keeps the document usable
+    assert_eq!(doc.blocks.len(), 2);
+    let diagnostic = single(&diagnostics, "INC105");
+    assert!(diagnostic.is_error());
+    assert_eq!(spanned(diagnostic, input), "include::missing.csv[]");
+}
+
+#[test]
+fn test_unresolved_include_is_reported() {
+    let input = "include::chapter.adoc[]";
+    let config = ParserConfig {
+        warn_unresolved: true,
+        ..Default::default()
+    };
+    let (_, diagnostics) = parse_with_diagnostics(input, config);
+    let diagnostic = single(&diagnostics, "INC106");
+    assert!(diagnostic.is_warning());
+    assert_eq!(spanned(diagnostic, input), input);
+}
+
+#[test]
+fn test_missing_text_include_keeps_preprocessor_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = "include::nowhere.adoc[]";
+    let config = ParserConfig::default().with_text_includes(dir.path().to_string_lossy());
+    let (_, diagnostics) = parse_with_diagnostics(input, config);
+    assert!(single(&diagnostics, "INC101").is_error());
+}
+
+#[test]
+fn test_diagnostics_in_asciidoc_cells_map_to_the_table() {
+    let input = "[cols=\"1a\"]\n|===\na|\n[bogus]\ncell\n|===";
+    let (_, diagnostics) = parse(input);
+    let diagnostic = single(&diagnostics, "PARSE103");
+    assert_eq!(spanned(diagnostic, input), "bogus");
+}
@@ -169,6 +169,8 @@ fn test_roundtrip_diagram() {
         language: None,
         title: None,
         style_id: Some("mermaid".to_string()),
+        callouts: Vec::new(),
+        highlight_lines: Vec::new(),
     }));
 
     // Generate AsciiDoc
@@ -217,6 +219,8 @@ fn test_roundtrip_source_block() {
         language: Some("rust".to_string()),
         title: None,
         style_id: None,
+        callouts: Vec::new(),
+        highlight_lines: Vec::new(),
     }));
 
     let asciidoc = generate(&doc);
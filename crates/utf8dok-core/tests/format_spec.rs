@@ -0,0 +1,193 @@
+//! Formatter Specification Tests
+//!
+//! These tests format a document using most block types and check that
+//! the result is stable and parses to the same document as the source.
+
+use utf8dok_ast::{Document, SourceMap};
+use utf8dok_core::{format, generate, parse};
+
+const DOCUMENT: &str = r#"= Operations Guide
+Jane Doe <jane@example.org>
+:toc: left
+:icons: font
+
+[[intro]]
+== Introduction
+
+The CLI is *fast*. It has a footnote:[A note. With sentences.] too.
+See <<intro,Section 1. Intro>> for more! Is it? Yes.
+
+.Checks
+[NOTE]
+======
+Run the checks. Then deploy.
+
+* nested list. With text.
+** deeper item
+======
+
+TIP: Short tip. Another sentence.
+
+* first item. Sentence two.
+* second
+.. sub ordered
+
+|===
+|Col A |Col B
+
+|1 |two
+|three |4
+|===
+
+[source,python]
+----
+def f():
+    return 1.   Two
+----
+
+ indented literal. Keep.
+
+CLI:: Command line interface. Runs commands.
+
+[quote, Someone]
+____
+Quoted text. Second quoted.
+____
+
+[%hardbreaks]
+Line one. A.
+Line two. B.
+"#;
+
+/// Generated AsciiDoc with whitespace collapsed, to compare documents
+fn normalized(source: &str) -> String {
+    let doc = parse(source).unwrap();
+    generate(&doc)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn test_formatting_preserves_document() {
+    let formatted = format(DOCUMENT);
+    assert_ne!(formatted, DOCUMENT);
+    assert_eq!(normalized(&formatted), normalized(DOCUMENT));
+}
+
+#[test]
+fn test_formatting_is_idempotent() {
+    let formatted = format(DOCUMENT);
+    assert_eq!(format(&formatted), formatted);
+}
+
+#[test]
+fn test_formatted_layout() {
+    let formatted = format(DOCUMENT);
+    assert!(formatted.starts_with(
+        "= Operations Guide\nJane Doe <jane@example.org>\n:icons: font\n:toc: left\n"
+    ));
+    assert!(formatted
+        .contains("The CLI is *fast*.\nIt has a footnote:[A note. With sentences.] too.\n"));
+    assert!(formatted.contains("* second\n.. sub ordered\n"));
+    assert!(formatted.contains("\n====\nRun the checks.\nThen deploy.\n"));
+    assert!(formatted.contains("| Col A | Col B\n\n| 1     | two\n| three | 4\n"));
+    assert!(formatted.contains("    return 1.   Two\n"));
+    assert!(formatted.contains("Line one. A.\nLine two. B.\n"));
+}
+
+/// The parsed document without source spans, which formatting moves
+fn parsed(source: &str) -> Document {
+    let mut doc = parse(source).unwrap();
+    doc.source_map = SourceMap::default();
+    doc
+}
+
+#[test]
+fn test_formatting_preserves_parsed_document() {
+    // A small linear congruential generator keeps the documents reproducible
+    let mut seed: u64 = 0xf0a7;
+    let mut next = |bound: usize| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) as usize) % bound.max(1)
+    };
+    let lines = [
+        "",
+        "",
+        "Text. More text.",
+        "plain words",
+        "Dr. Smith left. Then A. B. Smith came!",
+        "1. first",
+        "2. second",
+        "a. alpha",
+        "i) roman",
+        "- dash",
+        "* item. Sentence two.",
+        "** deep",
+        "*** deeper",
+        "* [x] done",
+        ". one",
+        ".. two",
+        "  indented. Line.",
+        "\tTabbed. Line.",
+        "   * indented item",
+        "+",
+        "[source,rust]",
+        "[%hardbreaks]",
+        "[[anchor]]",
+        ".Block title",
+        "----",
+        "....",
+        "====",
+        "======",
+        "____",
+        "--",
+        "|===",
+        "| a | b",
+        "|long cell |x",
+        "NOTE: Careful. Really.",
+        "Term:: Definition. More.",
+        "Term::",
+        "<1> Callout. Text.",
+        "// comment",
+        ":attr: value",
+        "== Section",
+        "Hard break. +",
+        "See `a. B` and <<id,Sec. One>>. Next.",
+        "*bold*. Next",
+        "= Title",
+        "Jane Doe",
+        ":b: {attr}",
+        "*   spaced item. Two.",
+        "  ** nested",
+        "Term;; Def. Two.",
+        "two  spaces. Here. Now",
+        "* a *b. C* d",
+        "<1>",
+        "*",
+        "[verse]",
+        "////",
+        "++++",
+        "'''",
+        "image::a.png[]",
+        "Ends with. *Bold* start",
+    ];
+
+    for case in 0..2000 {
+        let count = 1 + next(16);
+        let source: String = (0..count)
+            .map(|_| format!("{}\n", lines[next(lines.len())]))
+            .collect();
+        let formatted = format(&source);
+        assert_eq!(
+            parsed(&formatted),
+            parsed(&source),
+            "case {}: {:?} formatted as {:?}",
+            case,
+            source,
+            formatted
+        );
+    }
+}
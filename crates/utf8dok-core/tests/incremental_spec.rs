@@ -0,0 +1,273 @@
+//! Incremental Parsing Equivalence Tests
+//!
+//! These tests verify that applying an edit to an `IncrementalParse` yields
+//! exactly the document (blocks, metadata, source map) and diagnostics of a
+//! full parse of the edited text, for edits anywhere in a document using
+//! most of the supported syntax, and for long sequences of edits.
+
+use utf8dok_core::{parse_with_diagnostics, IncrementalParse, ParserConfig, TextEdit};
+
+const DOCUMENT: &str = r#"= Incremental Parsing
+Jane Doe
+:toc:
+:product: utf8dok
+
+Preamble paragraph about {product}.
+
+== Lists
+
+* First item
+with a lazy line
+* Second item
++
+----
+attached listing
+----
+** Nested item
+
+. One
+. Two
+
+[loweralpha]
+. Alpha
+
+Term:: Definition of the term.
+Other term::
+More text.
+
+* [x] Done
+* [ ] Todo
+
+== Code
+
+.Example code
+[source,rust,highlight=2]
+----
+fn main() { // <1>
+    println!("hi"); // <2>
+}
+----
+<1> Entry point
+<2> Output
+
+....
+literal
+....
+
+== Tables
+
+[cols="1,2a",options="header"]
+|===
+| Name | Description
+
+| One
+| Uses *bold* and
+a second line
+
+| Two
+a|
+* nested list
+* in a cell
+|===
+
+[format=csv]
+,===
+a,b
+c,d
+,===
+
+== Compound Blocks
+
+NOTE: An admonition paragraph.
+
+[WARNING]
+====
+Careful *here*.
+====
+
+****
+Sidebar with a footnote.footnote:[A note.]
+
+____
+Nested quote.
+____
+****
+
+[quote, Someone, Somewhere]
+____
+Quoted text.
+____
+
+:product: changed
+:count: {counter:num}
+
+Now {product} and {counter:num}.
+
+[[anchor]]
+== Links
+
+See https://example.com[example] and <<anchor>>.
+
+image::diagram.png[Diagram]
+
+'''
+
+Last paragraph.
+"#;
+
+/// Assert that `parse` matches a full parse of its text
+fn assert_equivalent(parse: &IncrementalParse, context: &str) {
+    let (document, diagnostics) = parse_with_diagnostics(parse.text(), ParserConfig::default());
+    assert_eq!(
+        parse.document(),
+        &document,
+        "Incremental parse differs after {}\nText:\n{}",
+        context,
+        parse.text()
+    );
+    assert_eq!(
+        parse.diagnostics(),
+        &diagnostics,
+        "Diagnostics differ after {}",
+        context
+    );
+}
+
+/// Byte offsets of the line starts of `text`
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .filter(|&i| i <= text.len())
+        .collect()
+}
+
+/// Apply `edit` to a fresh parse of `text` and compare with a full parse
+fn check_edit(text: &str, edit: TextEdit) {
+    let mut parse = IncrementalParse::new(text, ParserConfig::default());
+    let context = format!("{:?}", edit);
+    parse.apply(&edit);
+    assert_equivalent(&parse, &context);
+}
+
+#[test]
+fn test_initial_parse_matches_full_parse() {
+    let parse = IncrementalParse::new(DOCUMENT, ParserConfig::default());
+    assert_equivalent(&parse, "the initial parse");
+}
+
+#[test]
+fn test_character_edits_everywhere() {
+    for (position, _) in DOCUMENT.char_indices() {
+        check_edit(DOCUMENT, TextEdit::new(position..position, "x"));
+        check_edit(DOCUMENT, TextEdit::new(position..position + 1, ""));
+    }
+}
+
+#[test]
+fn test_line_breaks_everywhere() {
+    for (position, _) in DOCUMENT.char_indices() {
+        check_edit(DOCUMENT, TextEdit::new(position..position, "\n"));
+    }
+}
+
+#[test]
+fn test_structural_lines_inserted_at_every_line() {
+    let snippets = [
+        "\n",
+        "----\n",
+        "====\n",
+        "|===\n",
+        "+\n",
+        "* item\n",
+        ". step\n",
+        "term:: definition\n",
+        "<1> callout\n",
+        "[source]\n",
+        "[NOTE]\n",
+        ".Title\n",
+        ":product: edited\n",
+        ":toc!:\n",
+        "== Section\n",
+        "= Title\n",
+        "ifdef::product[]\n",
+        "include::missing.adoc[]\n",
+    ];
+    for start in line_starts(DOCUMENT) {
+        for snippet in snippets {
+            check_edit(DOCUMENT, TextEdit::new(start..start, snippet));
+        }
+    }
+}
+
+#[test]
+fn test_line_deletions() {
+    let starts = line_starts(DOCUMENT);
+    for window in 1..=3 {
+        for (index, &start) in starts.iter().enumerate() {
+            let end = starts
+                .get(index + window)
+                .copied()
+                .unwrap_or(DOCUMENT.len());
+            check_edit(DOCUMENT, TextEdit::new(start..end, ""));
+        }
+    }
+}
+
+#[test]
+fn test_edit_sequences() {
+    // A small linear congruential generator keeps the sequence reproducible
+    let mut seed: u64 = 0x5eed;
+    let mut next = |bound: usize| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) as usize) % bound.max(1)
+    };
+    let inserts = [
+        "a", " ", "\n", "\n\n", "*", "----\n", "* x\n", "|", "====\n", ":a: b\n", "+\n",
+    ];
+
+    let mut parse = IncrementalParse::new(DOCUMENT, ParserConfig::default());
+    for step in 0..600 {
+        let text = parse.text();
+        let boundaries: Vec<usize> = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([text.len()])
+            .collect();
+        let start = boundaries[next(boundaries.len())];
+        let edit = match next(3) {
+            0 => TextEdit::new(start..start, inserts[next(inserts.len())]),
+            _ => {
+                let after = boundaries.iter().filter(|&&b| b >= start);
+                let end = after.take(1 + next(12)).last().copied().unwrap_or(start);
+                let text = if next(2) == 0 {
+                    ""
+                } else {
+                    inserts[next(inserts.len())]
+                };
+                TextEdit::new(start..end, text)
+            }
+        };
+        let context = format!("step {}: {:?}", step, edit);
+        parse.apply(&edit);
+        assert_equivalent(&parse, &context);
+    }
+}
+
+#[test]
+fn test_unchanged_blocks_are_reused() {
+    let mut parse = IncrementalParse::new(DOCUMENT, ParserConfig::default());
+    let blocks = parse.document().blocks.len();
+    let offset = DOCUMENT.find("Last paragraph").unwrap();
+    let reparse = parse.apply(&TextEdit::new(offset..offset + 4, "Final"));
+
+    assert_eq!(reparse.parsed, 1);
+    assert_eq!(reparse.reused, blocks - 1);
+
+    // An edit in the middle reuses the blocks on both sides
+    let offset = DOCUMENT.find("Nested quote").unwrap();
+    let reparse = parse.apply(&TextEdit::new(offset..offset + 6, "Inner"));
+    assert_eq!(reparse.parsed, 1);
+    assert_equivalent(&parse, "two edits");
+}
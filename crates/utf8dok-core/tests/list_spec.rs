@@ -0,0 +1,266 @@
+//! List Specification Tests
+//!
+//! These tests verify description lists, checklists, list continuation
+//! (`+` and lazy lines), explicit numbering styles such as `[loweralpha]`
+//! and start numbers such as `[start=3]` or markers such as `c.`, and their
+//! round-trip through the generator.
+
+use utf8dok_ast::{Block, Inline, List, ListType, NumberingStyle};
+use utf8dok_core::{generate, parse};
+
+/// Parse `input` and return its only block as a list
+fn parse_list(input: &str) -> List {
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 1, "Expected one block: {:?}", doc.blocks);
+    match &doc.blocks[0] {
+        Block::List(list) => list.clone(),
+        other => panic!("Expected list, got {:?}", other),
+    }
+}
+
+/// Collect the plain text of a paragraph block
+fn paragraph_text(block: &Block) -> String {
+    match block {
+        Block::Paragraph(p) => p
+            .inlines
+            .iter()
+            .filter_map(|inline| match inline {
+                Inline::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect(),
+        other => panic!("Expected paragraph, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_description_list() {
+    let list = parse_list(
+        r#"CPU:: The brain of the computer.
+RAM::
+Volatile memory.
+Cache::: Nested level.
+Level 3:::: Deeper.
+Level 4;; Deepest."#,
+    );
+
+    assert_eq!(list.list_type, ListType::Description);
+    assert_eq!(list.items.len(), 5);
+    assert_eq!(
+        list.items[0].term,
+        Some(vec![Inline::Text("CPU".to_string())])
+    );
+    assert_eq!(
+        paragraph_text(&list.items[0].content[0]),
+        "The brain of the computer."
+    );
+    assert_eq!(
+        paragraph_text(&list.items[1].content[0]),
+        "Volatile memory."
+    );
+    let levels: Vec<u8> = list.items.iter().map(|item| item.level).collect();
+    assert_eq!(levels, vec![0, 0, 1, 2, 3]);
+}
+
+#[test]
+fn test_macros_are_not_description_terms() {
+    let doc = parse("image::diagram.png[Diagram]\n\nSee std::mem for details.").unwrap();
+    assert!(doc
+        .blocks
+        .iter()
+        .all(|block| matches!(block, Block::Paragraph(_))));
+}
+
+#[test]
+fn test_parse_checklist() {
+    let list = parse_list("* [x] Backup taken\n* [*] Services stopped\n* [ ] Upgrade run\n* Plain");
+
+    let checked: Vec<Option<bool>> = list.items.iter().map(|item| item.checked).collect();
+    assert_eq!(checked, vec![Some(true), Some(true), Some(false), None]);
+    assert_eq!(paragraph_text(&list.items[2].content[0]), "Upgrade run");
+}
+
+#[test]
+fn test_parse_numbering_style() {
+    let list = parse_list("[loweralpha]\n. First\n. Second");
+    assert_eq!(list.list_type, ListType::Ordered);
+    assert_eq!(list.numbering, Some(NumberingStyle::LowerAlpha));
+
+    let list = parse_list(". Plain");
+    assert_eq!(list.numbering, None);
+}
+
+#[test]
+fn test_parse_start_number() {
+    let list = parse_list("[upperroman,start=4]\n. Fourth\n. Fifth");
+    assert_eq!(list.numbering, Some(NumberingStyle::UpperRoman));
+    assert_eq!(list.start, Some(4));
+
+    // The default start and bulleted lists have no start number
+    assert_eq!(parse_list("[start=1]\n. First").start, None);
+    assert_eq!(parse_list("[start=3]\n* Bullet").start, None);
+}
+
+#[test]
+fn test_parse_explicit_markers() {
+    let list = parse_list("- One\n- Two");
+    assert_eq!(list.list_type, ListType::Unordered);
+    assert_eq!(list.items.len(), 2);
+
+    let list = parse_list("1. First\n2. Second");
+    assert_eq!(list.list_type, ListType::Ordered);
+    assert_eq!((list.numbering, list.start), (None, None));
+    assert_eq!(paragraph_text(&list.items[1].content[0]), "Second");
+
+    let list = parse_list("c. Third\nd. Fourth");
+    assert_eq!(list.numbering, Some(NumberingStyle::LowerAlpha));
+    assert_eq!(list.start, Some(3));
+
+    let list = parse_list("IV) Fourth\nV) Fifth");
+    assert_eq!(list.numbering, Some(NumberingStyle::UpperRoman));
+    assert_eq!(list.start, Some(4));
+
+    // Block attributes override the marker
+    let list = parse_list("[arabic]\na. First");
+    assert_eq!(list.numbering, Some(NumberingStyle::Arabic));
+}
+
+#[test]
+fn test_comment_after_blank_line_separates_lists() {
+    let doc = parse(". One\n. Two\n\n//\n. One again").unwrap();
+    assert_eq!(doc.blocks.len(), 3);
+    assert!(matches!(doc.blocks[1], Block::Comment(_)));
+    let Block::List(second) = &doc.blocks[2] else {
+        panic!("Expected second list");
+    };
+    assert_eq!(second.items.len(), 1);
+}
+
+#[test]
+fn test_blank_lines_between_items_keep_the_list() {
+    let list = parse_list("* One\n\n* Two\n\n\n* Three");
+    assert_eq!(list.items.len(), 3);
+}
+
+#[test]
+fn test_attributes_after_blank_line_start_a_new_list() {
+    let doc = parse(". One\n\n[upperroman]\n. Two").unwrap();
+    assert_eq!(doc.blocks.len(), 2);
+    let Block::List(second) = &doc.blocks[1] else {
+        panic!("Expected second list");
+    };
+    assert_eq!(second.numbering, Some(NumberingStyle::UpperRoman));
+}
+
+#[test]
+fn test_lazy_continuation_line() {
+    let list = parse_list("* First line\nsecond line\n* Next");
+    assert_eq!(list.items.len(), 2);
+    assert_eq!(
+        paragraph_text(&list.items[0].content[0]),
+        "First line second line"
+    );
+}
+
+#[test]
+fn test_paragraph_after_blank_line_ends_the_list() {
+    let doc = parse("* Item\n\nA paragraph.").unwrap();
+    assert_eq!(doc.blocks.len(), 2);
+    assert!(matches!(doc.blocks[1], Block::Paragraph(_)));
+}
+
+#[test]
+fn test_list_continuation_attaches_blocks() {
+    let input = r#". Stop the service
++
+[source,bash]
+----
+systemctl stop app
+----
++
+Wait for it to exit.
+. Check the ports
++
+|===
+| Port | State
+| 8080 | closed
+|===
+. Done"#;
+
+    let list = parse_list(input);
+    assert_eq!(list.items.len(), 3);
+
+    let first = &list.items[0].content;
+    assert_eq!(first.len(), 3);
+    assert!(matches!(&first[1], Block::Literal(l) if l.language.as_deref() == Some("bash")));
+    assert_eq!(paragraph_text(&first[2]), "Wait for it to exit.");
+
+    let second = &list.items[1].content;
+    assert_eq!(second.len(), 2);
+    assert!(matches!(second[1], Block::Table(_)));
+}
+
+#[test]
+fn test_list_continuation_with_compound_block() {
+    let input = r#"* Item
++
+====
+Inside the example.
+====
+* Next"#;
+
+    let list = parse_list(input);
+    assert_eq!(list.items.len(), 2);
+    assert!(matches!(&list.items[0].content[1], Block::Open(open) if open.blocks.len() == 1));
+}
+
+#[test]
+fn test_list_continuation_inside_compound_block() {
+    let input = r#"****
+* Item
++
+----
+code
+----
+****"#;
+
+    let doc = parse(input).unwrap();
+    let Block::Sidebar(sidebar) = &doc.blocks[0] else {
+        panic!("Expected sidebar");
+    };
+    let Block::List(list) = &sidebar.blocks[0] else {
+        panic!("Expected list in sidebar");
+    };
+    assert_eq!(list.items[0].content.len(), 2);
+}
+
+#[test]
+fn test_roundtrip_lists() {
+    let input = r#"[loweralpha,start=2]
+. Prepare
++
+----
+make prepare
+----
+. Run
+
+Term:: Definition.
+Other term:: Another definition.
+Nested::: Nested definition.
+
+* [x] Done
+* [ ] Pending"#;
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 3);
+    let generated = generate(&doc);
+    let reparsed = parse(&generated).unwrap();
+    assert_eq!(
+        reparsed.blocks, doc.blocks,
+        "Round-trip should preserve lists. Generated:\n{}",
+        generated
+    );
+    assert!(generated.contains("[loweralpha,start=2]"));
+    assert!(generated.contains("* [x] Done"));
+    assert!(generated.contains("Nested::: Nested definition."));
+}
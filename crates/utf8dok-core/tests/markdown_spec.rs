@@ -0,0 +1,363 @@
+//! Markdown Frontend and Generator Specification Tests
+//!
+//! These tests verify that CommonMark/GFM input produces the same AST as the
+//! equivalent AsciiDoc, and that generated Markdown parses back to the
+//! document it was generated from.
+
+use std::collections::HashMap;
+use utf8dok_ast::{
+    AdmonitionType, Block, BreakType, Document, FormatType, Inline, ListType, NodePath, Paragraph,
+};
+use utf8dok_core::{generate_markdown, generate_markdown_with_config, parse, parse_markdown};
+use utf8dok_core::{GeneratorConfig, MarkdownGenerator};
+
+const DOCUMENT: &str = r#"# Release Notes
+
+Intro with **bold**, _italic_, `code`, ^sup^ and ~sub~ text.
+See [the guide](https://example.com/guide) and [setup](#setup).
+
+## Setup {#setup}
+
+- [x] Install the tool
+- [ ] Configure it
+  - nested option
+- Plain item
+
+1. First
+2. Second
+
+   With a second paragraph.
+
+| Name | Value |
+|:-----|------:|
+| a    | 1     |
+| b    | 2     |
+
+```rust
+fn main() {}
+```
+
+```mermaid
+graph TD; A-->B
+```
+
+> [!WARNING]
+> Mind the gap.
+
+> Quoted wisdom.
+
+A claim.[^1] Another claim.[^src] Repeated.[^src]
+
+Term
+: Definition of the term.
+
+---
+
+![Diagram](images/diagram.png)
+
+[^1]: The first note.
+[^src]: A shared source.
+"#;
+
+/// Round-trip `doc` through Markdown and compare it with the original
+fn assert_round_trip(doc: &Document) {
+    let config = GeneratorConfig {
+        include_header: true,
+        generate_anchors: true,
+    };
+    let markdown = generate_markdown_with_config(doc, config);
+    let parsed = parse_markdown(&markdown);
+    assert_eq!(parsed.blocks, doc.blocks, "Markdown:\n{}", markdown);
+    assert_eq!(parsed.metadata.title, doc.metadata.title);
+}
+
+#[test]
+fn test_title_and_sections() {
+    let doc = parse_markdown(DOCUMENT);
+    assert_eq!(doc.metadata.title.as_deref(), Some("Release Notes"));
+    let Block::Heading(heading) = &doc.blocks[1] else {
+        panic!("Expected heading, got {:?}", doc.blocks[1]);
+    };
+    assert_eq!(heading.level, 1);
+    assert_eq!(heading.anchor.as_deref(), Some("setup"));
+}
+
+#[test]
+fn test_inline_formatting_matches_asciidoc() {
+    let markdown = parse_markdown("Some **bold**, _italic_ and `code` text.");
+    let asciidoc = parse("Some *bold*, _italic_ and `code` text.").unwrap();
+    assert_eq!(markdown.blocks, asciidoc.blocks);
+}
+
+#[test]
+fn test_links() {
+    let doc = parse_markdown(DOCUMENT);
+    let Block::Paragraph(p) = &doc.blocks[0] else {
+        panic!("Expected paragraph");
+    };
+    let urls: Vec<&str> = p
+        .inlines
+        .iter()
+        .filter_map(|inline| match inline {
+            Inline::Link(link) => Some(link.url.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(urls, vec!["https://example.com/guide", "#setup"]);
+}
+
+#[test]
+fn test_task_lists_and_nesting() {
+    let doc = parse_markdown(DOCUMENT);
+    let Block::List(list) = &doc.blocks[2] else {
+        panic!("Expected list, got {:?}", doc.blocks[2]);
+    };
+    assert_eq!(list.list_type, ListType::Unordered);
+    let states: Vec<(u8, Option<bool>)> = list.items.iter().map(|i| (i.level, i.checked)).collect();
+    assert_eq!(
+        states,
+        vec![(0, Some(true)), (0, Some(false)), (1, None), (0, None)]
+    );
+
+    // Same structure as the AsciiDoc checklist
+    let asciidoc =
+        parse("* [x] Install the tool\n* [ ] Configure it\n** nested option\n* Plain item")
+            .unwrap();
+    assert_eq!(doc.blocks[2], asciidoc.blocks[0]);
+}
+
+#[test]
+fn test_loose_list_items() {
+    let doc = parse_markdown(DOCUMENT);
+    let Block::List(list) = &doc.blocks[3] else {
+        panic!("Expected list, got {:?}", doc.blocks[3]);
+    };
+    assert_eq!(list.list_type, ListType::Ordered);
+    assert_eq!(list.items[1].content.len(), 2);
+}
+
+#[test]
+fn test_tables() {
+    let doc = parse_markdown(DOCUMENT);
+    let Block::Table(table) = &doc.blocks[4] else {
+        panic!("Expected table, got {:?}", doc.blocks[4]);
+    };
+    assert_eq!(table.rows.len(), 3);
+    assert!(table.rows[0].is_header);
+    assert_eq!(table.columns.len(), 2);
+    assert_eq!(
+        table.rows[1].cells[1].align,
+        Some(utf8dok_ast::Alignment::Right)
+    );
+}
+
+#[test]
+fn test_headerless_table() {
+    let doc = parse_markdown("|  |  |\n|--|--|\n| a | b |");
+    let Block::Table(table) = &doc.blocks[0] else {
+        panic!("Expected table");
+    };
+    assert_eq!(table.rows.len(), 1);
+    assert!(!table.rows[0].is_header);
+}
+
+#[test]
+fn test_code_and_diagrams() {
+    let doc = parse_markdown(DOCUMENT);
+    let (Block::Literal(code), Block::Literal(diagram)) = (&doc.blocks[5], &doc.blocks[6]) else {
+        panic!("Expected literals");
+    };
+    assert_eq!(code.language.as_deref(), Some("rust"));
+    assert_eq!(code.content, "fn main() {}");
+    assert_eq!(diagram.style_id.as_deref(), Some("mermaid"));
+    assert_eq!(diagram.language, None);
+
+    // Same as a [mermaid] block
+    let asciidoc = parse("[mermaid]\n----\ngraph TD; A-->B\n----").unwrap();
+    assert_eq!(doc.blocks[6], asciidoc.blocks[0]);
+}
+
+#[test]
+fn test_alerts_and_quotes() {
+    let doc = parse_markdown(DOCUMENT);
+    let Block::Admonition(admonition) = &doc.blocks[7] else {
+        panic!("Expected admonition, got {:?}", doc.blocks[7]);
+    };
+    assert_eq!(admonition.admonition_type, AdmonitionType::Warning);
+    assert!(matches!(doc.blocks[8], Block::Quote(_)));
+}
+
+#[test]
+fn test_footnotes() {
+    let doc = parse_markdown(DOCUMENT);
+    let Block::Paragraph(p) = &doc.blocks[9] else {
+        panic!("Expected paragraph, got {:?}", doc.blocks[9]);
+    };
+    let footnotes: Vec<_> = p
+        .inlines
+        .iter()
+        .filter_map(|inline| match inline {
+            Inline::Footnote(footnote) => Some(footnote),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(footnotes.len(), 3);
+    // A numbered footnote used once needs no id
+    assert_eq!(footnotes[0].id, None);
+    assert_eq!(
+        footnotes[0].content,
+        vec![Inline::Text("The first note.".to_string())]
+    );
+    // The text goes with the first reference
+    assert_eq!(footnotes[1].id.as_deref(), Some("src"));
+    assert!(!footnotes[1].content.is_empty());
+    assert!(footnotes[2].content.is_empty());
+
+    let asciidoc =
+        parse("A claim.footnote:[The first note.] Another claim.footnote:src[A shared source.] Repeated.footnote:src[]")
+            .unwrap();
+    assert_eq!(doc.blocks[9], asciidoc.blocks[0]);
+}
+
+#[test]
+fn test_definition_lists_breaks_and_images() {
+    let doc = parse_markdown(DOCUMENT);
+    let Block::List(list) = &doc.blocks[10] else {
+        panic!("Expected list, got {:?}", doc.blocks[10]);
+    };
+    assert_eq!(list.list_type, ListType::Description);
+    assert_eq!(
+        list.items[0].term,
+        Some(vec![Inline::Text("Term".to_string())])
+    );
+    assert_eq!(doc.blocks[11], Block::ThematicBreak);
+
+    let asciidoc = parse("image::images/diagram.png[Diagram]").unwrap();
+    assert_eq!(doc.blocks[12], asciidoc.blocks[0]);
+}
+
+#[test]
+fn test_block_spans() {
+    let doc = parse_markdown(DOCUMENT);
+    let span = doc.source_map.span(&NodePath::block(5)).unwrap();
+    assert_eq!(
+        &DOCUMENT[span.start..span.end],
+        "```rust\nfn main() {}\n```"
+    );
+}
+
+#[test]
+fn test_round_trip_of_parsed_markdown() {
+    assert_round_trip(&parse_markdown(DOCUMENT));
+}
+
+#[test]
+fn test_round_trip_of_asciidoc() {
+    let asciidoc = r#"= Design Notes
+
+== Overview
+
+Text with *bold*, _italic_, `mono`, an [[here]]anchor
+and snake_case names.
+
+* One
+** Two
+*** Three
+* Back to one
+
+. Alpha
+. Beta
+
+* Separate list
+
+[source,python]
+----
+print("```")
+----
+
+[NOTE]
+====
+Remember this.
+====
+
+|===
+| A | B
+
+| 1 | 2
+|===
+
+Literal-looking text: 1. not a list, # not a heading, *not bold*.
+"#;
+    assert_round_trip(&parse(asciidoc).unwrap());
+}
+
+#[test]
+fn test_html_fallbacks_round_trip() {
+    let text = |t: &str| Inline::Text(t.to_string());
+    let format = |format_type, t: &str| Inline::Format(format_type, Box::new(text(t)));
+    let mut doc = Document::new();
+    doc.push(Block::Paragraph(Paragraph {
+        inlines: vec![
+            Inline::Anchor("top".to_string()),
+            text("Some "),
+            format(FormatType::Highlight, "marked"),
+            text(" text, E = mc"),
+            format(FormatType::Superscript, "2"),
+            text(" and H"),
+            format(FormatType::Subscript, "2"),
+            text("O"),
+            Inline::Break,
+            text("next line"),
+        ],
+        style_id: None,
+        attributes: HashMap::new(),
+    }));
+    doc.push(Block::Break(BreakType::Page));
+
+    let markdown = generate_markdown(&doc);
+    assert!(markdown.starts_with(
+        "<a id=\"top\"></a>Some <mark>marked</mark> text, E = mc<sup>2</sup> and H<sub>2</sub>O\\\nnext line"
+    ));
+    assert_round_trip(&doc);
+}
+
+#[test]
+fn test_generator_writes_lossy_features_readably() {
+    let asciidoc = r#".Example code
+[source,rust]
+----
+let x = 1; // <1>
+----
+<1> The binding
+
+[cols="1,1"]
+|===
+2+| Spanning cell
+| a | b
+|===
+
+[quote, Ada Lovelace]
+____
+That brain of mine.
+____"#;
+    let doc = parse(asciidoc).unwrap();
+    let markdown = MarkdownGenerator::new().generate(&doc);
+    assert!(markdown.starts_with("**Example code**\n\n```rust\nlet x = 1; <1>\n```"));
+    assert!(markdown.contains("1. The binding"));
+    assert!(markdown.contains("| Spanning cell |  |"));
+    assert!(markdown.contains("> That brain of mine.\n>\n> — Ada Lovelace"));
+}
+
+#[test]
+fn test_ordered_list_start_number() {
+    let doc = parse_markdown("3. Third\n4. Fourth");
+    let Block::List(list) = &doc.blocks[0] else {
+        panic!("Expected list, got {:?}", doc.blocks[0]);
+    };
+    assert_eq!(list.start, Some(3));
+    assert_eq!(
+        doc.blocks,
+        parse("[start=3]\n. Third\n. Fourth").unwrap().blocks
+    );
+    assert!(generate_markdown(&doc).starts_with("3. Third\n4. Fourth"));
+}
@@ -6,9 +6,10 @@
 use std::collections::HashMap;
 
 use utf8dok_ast::{
-    Block, Document, DocumentMeta, FormatType, Heading, Inline, ListType, Paragraph,
+    Block, Document, DocumentMeta, FormatType, Heading, Inline, ListType, NodePath, Paragraph,
+    SourceMap, SourceSpan,
 };
-use utf8dok_core::parser;
+use utf8dok_core::{generate_with_config, parser, GeneratorConfig};
 
 /// Test basic document parsing flow
 ///
@@ -34,6 +35,22 @@ Hello *world*."#;
     let mut expected_attrs = HashMap::new();
     expected_attrs.insert("version".to_string(), "1.0".to_string());
 
+    let mut source_map = SourceMap::new();
+    for (path, start, end, line, column) in [
+        (vec![0], 31, 45, 4, 1),
+        (vec![0, 0], 34, 45, 4, 4),
+        (vec![1], 47, 61, 6, 1),
+        (vec![1, 0], 47, 53, 6, 1),
+        (vec![1, 1], 53, 60, 6, 7),
+        (vec![1, 1, 0], 54, 59, 6, 8),
+        (vec![1, 2], 60, 61, 6, 14),
+    ] {
+        source_map.insert_span(
+            NodePath::from(path),
+            SourceSpan::new(start, end, line, column),
+        );
+    }
+
     let expected = Document {
         metadata: DocumentMeta {
             title: Some("Test Document".to_string()),
@@ -47,6 +64,7 @@ Hello *world*."#;
                 text: vec![Inline::Text("Section One".to_string())],
                 style_id: None,
                 anchor: None,
+                role: None,
             }),
             Block::Paragraph(Paragraph {
                 inlines: vec![
@@ -62,6 +80,7 @@ Hello *world*."#;
             }),
         ],
         intent: None,
+        source_map,
     };
 
     let result = parser::parse(input).expect("Parser should not error");
@@ -216,6 +235,23 @@ Content here."#;
     );
 }
 
+/// Test that attributes set without a value are recorded as empty
+#[test]
+fn test_parse_attributes_without_value() {
+    let input = "= My Document\n:toc:\n:sectnums!:\n\nContent here.";
+
+    let result = parser::parse(input).expect("Parser should not error");
+
+    assert_eq!(result.metadata.attributes.get("toc"), Some(&String::new()));
+    assert_eq!(result.metadata.attributes.get("sectnums"), None);
+    assert!(result.metadata.authors.is_empty());
+    let config = GeneratorConfig {
+        include_header: true,
+        generate_anchors: false,
+    };
+    assert!(generate_with_config(&result, config).contains("\n:toc:\n"));
+}
+
 /// Test nested list items
 #[test]
 fn test_parse_nested_list() {
@@ -341,3 +377,43 @@ fn test_parse_multicell_table() {
         panic!("Expected Table block");
     }
 }
+
+/// Test a block anchor in front of a block title
+///
+/// The anchor stays an anchor-only paragraph in front of the block, and the
+/// title still applies to the block.
+#[test]
+fn test_parse_block_anchor_before_title() {
+    let input =
+        "[[fig-arch]]\n.Architecture\nimage::arch.png[]\n\n[[tab-ports]]\n.Ports\n|===\n| 80\n|===";
+
+    let result = parser::parse(input).expect("Parser should not error");
+
+    assert_eq!(result.blocks.len(), 4);
+    let anchor = |block: &Block| match block {
+        Block::Paragraph(para) => para.inlines.clone(),
+        other => panic!("Expected anchor paragraph, got {:?}", other),
+    };
+    assert_eq!(
+        anchor(&result.blocks[0]),
+        vec![Inline::Anchor("fig-arch".to_string())]
+    );
+    if let Block::Paragraph(figure) = &result.blocks[1] {
+        assert!(matches!(figure.inlines[0], Inline::Image(_)));
+        assert_eq!(
+            figure.attributes.get("title").map(String::as_str),
+            Some("Architecture")
+        );
+    } else {
+        panic!("Expected figure paragraph");
+    }
+    assert_eq!(
+        anchor(&result.blocks[2]),
+        vec![Inline::Anchor("tab-ports".to_string())]
+    );
+    if let Block::Table(table) = &result.blocks[3] {
+        assert_eq!(table.caption, Some(vec![Inline::Text("Ports".to_string())]));
+    } else {
+        panic!("Expected Table block");
+    }
+}
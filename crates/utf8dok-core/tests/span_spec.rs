@@ -0,0 +1,222 @@
+//! Source Span Specification Tests
+//!
+//! These tests verify that the parser records a byte-accurate source span
+//! for every block and inline, keyed by node path in the document's source
+//! map, including nodes nested in lists, compound blocks and table cells.
+
+use utf8dok_ast::{Document, Node, NodePath, SourceSpan};
+use utf8dok_core::{parse, parse_with_config, ParserConfig};
+
+/// Source text covered by the span at `path`
+fn spanned<'a>(doc: &Document, input: &'a str, path: &[usize]) -> &'a str {
+    let span = doc
+        .source_map
+        .span(&NodePath::from(path.to_vec()))
+        .unwrap_or_else(|| panic!("No span at {:?}", path));
+    &input[span.start..span.end]
+}
+
+/// Paths of all nodes below `node`, in document order
+fn node_paths(node: Node<'_>, path: NodePath, paths: &mut Vec<NodePath>) {
+    for (index, child) in node.children().into_iter().enumerate() {
+        let child_path = path.child(index);
+        paths.push(child_path.clone());
+        node_paths(child, child_path, paths);
+    }
+}
+
+#[test]
+fn test_every_node_has_a_span() {
+    let input = r#"= Title
+
+== Intro
+
+Some *bold* and `code`
+on two lines.footnote:[A note.]
+
+* First _item_
+lazy line
+* Second
++
+----
+attached
+----
+
+Term:: Definition.
+
+****
+Inside a sidebar.
+
+NOTE: Careful.
+****
+
+[cols="1,1a"]
+|===
+| Plain | Nested *cell*
+
+| Two
+paragraphs
+a|
+* nested list
+|==="#;
+
+    let doc = parse(input).unwrap();
+    let mut paths = Vec::new();
+    for index in 0..doc.blocks.len() {
+        let path = NodePath::block(index);
+        paths.push(path.clone());
+        node_paths(doc.node(&path).unwrap(), path, &mut paths);
+    }
+
+    for path in &paths {
+        let span = doc
+            .source_map
+            .span(path)
+            .unwrap_or_else(|| panic!("No span for node {}", path));
+        assert!(span.start <= span.end && span.end <= input.len());
+    }
+    // And every span belongs to a node
+    for path in doc.source_map.spans.keys() {
+        assert!(doc.node(path).is_some(), "Span for missing node {}", path);
+    }
+}
+
+#[test]
+fn test_block_and_inline_spans() {
+    let input = "== Intro\n\nSome *bold* and `code`\non two lines.";
+    let doc = parse(input).unwrap();
+
+    assert_eq!(spanned(&doc, input, &[0]), "== Intro");
+    assert_eq!(spanned(&doc, input, &[0, 0]), "Intro");
+    assert_eq!(
+        spanned(&doc, input, &[1]),
+        "Some *bold* and `code`\non two lines."
+    );
+    assert_eq!(spanned(&doc, input, &[1, 1]), "*bold*");
+    assert_eq!(spanned(&doc, input, &[1, 1, 0]), "bold");
+    assert_eq!(spanned(&doc, input, &[1, 3, 0]), "code");
+    // The last text runs across the line break
+    assert_eq!(spanned(&doc, input, &[1, 4]), "\non two lines.");
+
+    let code = doc.source_map.span(&NodePath::from(vec![1, 3])).unwrap();
+    assert_eq!(*code, SourceSpan::new(26, 32, 3, 17));
+}
+
+#[test]
+fn test_list_spans() {
+    let input =
+        "* First _item_\nlazy line\n* Second\n+\n----\nattached\n----\n\nTerm:: Definition.";
+    let doc = parse(input).unwrap();
+
+    assert_eq!(
+        spanned(&doc, input, &[0]),
+        "* First _item_\nlazy line\n* Second\n+\n----\nattached\n----"
+    );
+    assert_eq!(spanned(&doc, input, &[0, 0]), "* First _item_\nlazy line");
+    assert_eq!(spanned(&doc, input, &[0, 0, 0]), "First _item_\nlazy line");
+    assert_eq!(spanned(&doc, input, &[0, 0, 0, 1, 0]), "item");
+    // The joining space is the line break before the lazy line
+    assert_eq!(spanned(&doc, input, &[0, 0, 0, 2]), "\n");
+    assert_eq!(spanned(&doc, input, &[0, 0, 0, 3]), "lazy line");
+    // The attached block extends the item
+    assert_eq!(spanned(&doc, input, &[0, 1, 1]), "----\nattached\n----");
+    assert_eq!(
+        spanned(&doc, input, &[0, 1]),
+        "* Second\n+\n----\nattached\n----"
+    );
+
+    // Description terms come before the definition
+    assert_eq!(spanned(&doc, input, &[1, 0, 0]), "Term");
+    assert_eq!(spanned(&doc, input, &[1, 0, 1]), "Definition.");
+}
+
+#[test]
+fn test_compound_block_spans() {
+    let input = "****\nInside.\n\nNOTE: Careful *here*.\n****";
+    let doc = parse(input).unwrap();
+
+    assert_eq!(spanned(&doc, input, &[0]), input);
+    assert_eq!(spanned(&doc, input, &[0, 0]), "Inside.");
+    assert_eq!(spanned(&doc, input, &[0, 1]), "NOTE: Careful *here*.");
+    assert_eq!(spanned(&doc, input, &[0, 1, 0]), "Careful *here*.");
+    assert_eq!(spanned(&doc, input, &[0, 1, 0, 1, 0]), "here");
+}
+
+#[test]
+fn test_table_spans() {
+    let input = "[cols=\"1,1a\"]\n|===\n| A *b* 2+| C\n| D\nmore\na|\n* nested\n|===";
+    let doc = parse(input).unwrap();
+
+    assert_eq!(spanned(&doc, input, &[0]), &input[14..]);
+    assert_eq!(spanned(&doc, input, &[0, 0]), "| A *b* 2+| C");
+    assert_eq!(spanned(&doc, input, &[0, 0, 1]), "2+| C");
+    assert_eq!(spanned(&doc, input, &[0, 0, 0, 0, 1, 0]), "b");
+    // Cell lines are joined like paragraph lines
+    assert_eq!(spanned(&doc, input, &[0, 1, 0, 0]), "D\nmore");
+    // Blocks of AsciiDoc cells map into the table
+    assert_eq!(spanned(&doc, input, &[0, 1, 1, 0]), "* nested");
+    assert_eq!(spanned(&doc, input, &[0, 1, 1, 0, 0, 0, 0]), "nested");
+}
+
+#[test]
+fn test_footnote_spans() {
+    let input = "Text.footnote:[See *[1\\]* here]";
+    let doc = parse(input).unwrap();
+    assert_eq!(spanned(&doc, input, &[0, 1]), "footnote:[See *[1\\]* here]");
+    assert_eq!(spanned(&doc, input, &[0, 1, 1]), "*[1\\]*");
+    assert_eq!(spanned(&doc, input, &[0, 1, 2]), " here");
+}
+
+#[test]
+fn test_substituted_text_stays_on_its_line() {
+    let input = ":product: utf8dok the document compiler\n\nUse {product} *today*.";
+    let doc = parse(input).unwrap();
+    let bold = doc.source_map.span(&NodePath::from(vec![0, 1])).unwrap();
+    let paragraph = doc.source_map.block_span(0).unwrap();
+    assert_eq!(paragraph.line, 3);
+    assert!(bold.start >= paragraph.start && bold.end <= paragraph.end);
+}
+
+#[test]
+fn test_spans_map_through_expanded_references() {
+    let input =
+        ":x: a-very-long-value\n\n{x} *bold* end\n\n* {x} one\n*two* {x} more\n\n{x}\n*b* c";
+    let doc = parse(input).unwrap();
+
+    assert_eq!(spanned(&doc, input, &[0, 0]), "{x} ");
+    assert_eq!(spanned(&doc, input, &[0, 1]), "*bold*");
+    assert_eq!(spanned(&doc, input, &[0, 1, 0]), "bold");
+    assert_eq!(spanned(&doc, input, &[0, 2]), " end");
+    // List item continuation lines
+    assert_eq!(spanned(&doc, input, &[1, 0, 0, 1]), "\n");
+    assert_eq!(spanned(&doc, input, &[1, 0, 0, 2, 0]), "two");
+    assert_eq!(spanned(&doc, input, &[1, 0, 0, 3]), " {x} more");
+    // Paragraph lines
+    assert_eq!(spanned(&doc, input, &[2, 1, 0]), "b");
+
+    for (path, span) in &doc.source_map.spans {
+        assert!(span.start < span.end, "Empty span for node {}", path);
+    }
+}
+
+#[test]
+fn test_spans_of_included_blocks_are_relative_to_their_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("chapter.adoc"),
+        "\n== Chapter\n\nBody *text*.\n",
+    )
+    .unwrap();
+    let input = "Intro.\n\ninclude::chapter.adoc[]";
+
+    let config = ParserConfig::default().with_text_includes(dir.path().to_string_lossy());
+    let doc = parse_with_config(input, config).unwrap();
+
+    assert!(doc.source_map.block_file(1).is_some());
+    assert_eq!(
+        *doc.source_map.block_span(1).unwrap(),
+        SourceSpan::new(1, 11, 2, 1)
+    );
+    let bold = doc.source_map.span(&NodePath::from(vec![2, 1])).unwrap();
+    assert_eq!((bold.start, bold.end, bold.line), (18, 24, 4));
+}
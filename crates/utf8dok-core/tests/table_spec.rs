@@ -0,0 +1,256 @@
+//! Table Specification Tests
+//!
+//! These tests verify column specs, cell spans, cell styles (including
+//! AsciiDoc cells with nested blocks and tables), header and footer rows,
+//! CSV/DSV content, and the round-trip through the generator.
+
+use utf8dok_ast::{Alignment, Block, CellStyle, Inline, Table};
+use utf8dok_core::{generate, parse};
+
+/// Parse `input` and return its only block as a table
+fn parse_table(input: &str) -> Table {
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 1, "Expected one block: {:?}", doc.blocks);
+    match &doc.blocks[0] {
+        Block::Table(table) => table.clone(),
+        other => panic!("Expected table, got {:?}", other),
+    }
+}
+
+/// Plain text of a cell's first paragraph
+fn cell_text(table: &Table, row: usize, cell: usize) -> String {
+    match &table.rows[row].cells[cell].content[0] {
+        Block::Paragraph(p) => p
+            .inlines
+            .iter()
+            .filter_map(|inline| match inline {
+                Inline::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect(),
+        other => panic!("Expected paragraph, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_column_specs() {
+    let table = parse_table(
+        r#"[cols="1,2a,^3"]
+|===
+| One | Two | Three
+|==="#,
+    );
+
+    assert_eq!(table.columns.len(), 3);
+    assert_eq!(table.columns[1].width, Some(2));
+    assert_eq!(table.columns[1].style, Some(CellStyle::AsciiDoc));
+    assert_eq!(table.columns[2].align, Some(Alignment::Center));
+
+    // Column defaults apply to the cells below
+    let row = &table.rows[0];
+    assert_eq!(row.cells[1].style, Some(CellStyle::AsciiDoc));
+    assert_eq!(row.cells[2].align, Some(Alignment::Center));
+}
+
+#[test]
+fn test_cells_on_separate_lines_follow_column_count() {
+    let table = parse_table(
+        r#"[cols="2*"]
+|===
+| A
+| B
+| C
+| D
+|==="#,
+    );
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(cell_text(&table, 1, 0), "C");
+}
+
+#[test]
+fn test_colspan_and_rowspan() {
+    let table = parse_table(
+        r#"[cols="3*"]
+|===
+2+| Wide | Right
+.2+| Tall | B | C
+| D | E
+|==="#,
+    );
+
+    assert_eq!(table.rows.len(), 3);
+    assert_eq!(table.rows[0].cells.len(), 2);
+    assert_eq!(table.rows[0].cells[0].colspan, 2);
+    assert_eq!(table.rows[1].cells[0].rowspan, 2);
+    // The tall cell covers the first column of the last row
+    assert_eq!(table.rows[2].cells.len(), 2);
+    assert_eq!(cell_text(&table, 2, 0), "D");
+}
+
+#[test]
+fn test_duplicated_cells() {
+    let table = parse_table("[cols=\"3*\"]\n|===\n3*| x\n|===");
+    assert_eq!(table.rows.len(), 1);
+    assert_eq!(table.rows[0].cells.len(), 3);
+}
+
+#[test]
+fn test_asciidoc_cell_with_nested_blocks() {
+    let input = r#"[cols="1,2"]
+|===
+| Step
+a|
+Run the script:
+
+[source,bash]
+----
+./deploy.sh \| tee log
+----
+
+* checks pass
+|==="#;
+
+    let table = parse_table(input);
+    let cell = &table.rows[0].cells[1];
+    assert_eq!(cell.style, Some(CellStyle::AsciiDoc));
+    assert_eq!(cell.content.len(), 3);
+    assert!(matches!(&cell.content[1], Block::Literal(l) if l.content == "./deploy.sh | tee log"));
+    assert!(matches!(cell.content[2], Block::List(_)));
+}
+
+#[test]
+fn test_nested_table_in_asciidoc_cell() {
+    let input = r#"[cols="1,1"]
+|===
+| Outer
+a|
+!===
+! Inner A ! Inner B
+!===
+|==="#;
+
+    let table = parse_table(input);
+    match &table.rows[0].cells[1].content[0] {
+        Block::Table(inner) => {
+            assert_eq!(inner.rows.len(), 1);
+            assert_eq!(cell_text(inner, 0, 1), "Inner B");
+        }
+        other => panic!("Expected nested table, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_literal_and_styled_cells() {
+    let table = parse_table(
+        r#"[cols="1,1"]
+|===
+h| Key l|
+  indented
+    more
+|==="#,
+    );
+    let row = &table.rows[0];
+    assert_eq!(row.cells[0].style, Some(CellStyle::Header));
+    match &row.cells[1].content[0] {
+        Block::Literal(lit) => assert_eq!(lit.content, "  indented\n    more"),
+        other => panic!("Expected literal cell, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_header_and_footer_rows() {
+    let table = parse_table(
+        r#"[%header%footer,cols="2*"]
+|===
+| Item | Cost
+| Tea | 3
+| Total | 3
+|==="#,
+    );
+    assert!(table.rows[0].is_header);
+    assert!(!table.rows[1].is_header && !table.rows[1].is_footer);
+    assert!(table.rows[2].is_footer);
+}
+
+#[test]
+fn test_implicit_header_row() {
+    let table = parse_table("|===\n| Name | Value\n\n| a | 1\n|===");
+    assert!(table.rows[0].is_header);
+    assert!(!table.rows[1].is_header);
+
+    let table = parse_table("[%noheader]\n|===\n| Name | Value\n\n| a | 1\n|===");
+    assert!(!table.rows[0].is_header);
+}
+
+#[test]
+fn test_csv_table() {
+    let table = parse_table(
+        r#"[%header,format=csv]
+|===
+Name,Quote
+"Doe, Jane","Said ""hi"""
+|==="#,
+    );
+    assert_eq!(table.rows.len(), 2);
+    assert!(table.rows[0].is_header);
+    assert_eq!(cell_text(&table, 1, 0), "Doe, Jane");
+    assert_eq!(cell_text(&table, 1, 1), "Said \"hi\"");
+
+    let table = parse_table(",===\na,b\nc,d\n,===");
+    assert_eq!(table.rows.len(), 2);
+}
+
+#[test]
+fn test_dsv_table() {
+    let table = parse_table("[format=dsv]\n|===\nroot:x:0\nbin:x:1\n|===");
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(table.rows[1].cells.len(), 3);
+    assert_eq!(cell_text(&table, 1, 0), "bin");
+
+    let table = parse_table(":===\na:b\n:===");
+    assert_eq!(cell_text(&table, 0, 1), "b");
+}
+
+#[test]
+fn test_roundtrip_tables() {
+    let input = r#".Spans
+[%header%footer,cols="1,^2,1"]
+|===
+| A | B | C
+2+| Wide .2+| Tall
+| D | E
+| F | G | H
+|===
+
+[cols="1,1a"]
+|===
+| Step
+a|
+Run it.
+
+----
+make \| tee log
+----
+
+| Nested
+a|
+!===
+! x ! y
+!===
+|===
+
+|===
+| Simple
+| Table
+|==="#;
+
+    let doc = parse(input).unwrap();
+    assert_eq!(doc.blocks.len(), 3);
+    let generated = generate(&doc);
+    let reparsed = parse(&generated).unwrap();
+    assert_eq!(
+        reparsed.blocks, doc.blocks,
+        "Round-trip should preserve tables. Generated:\n{}",
+        generated
+    );
+}
@@ -0,0 +1,169 @@
+//! Integration tests for text include directives
+//!
+//! Tests recursive inlining of `.adoc` includes with level offsets,
+//! tag and line selection, cycle detection and source provenance.
+
+use std::fs;
+use std::path::Path;
+
+use tempfile::TempDir;
+use utf8dok_ast::{Block, Document, Inline};
+use utf8dok_core::{parse_with_config, ParserConfig};
+
+/// Write a set of files into a fresh temporary directory
+fn write_files(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (name, content) in files {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+/// Parse `input` with text includes resolved relative to `dir`
+fn parse_in(dir: &Path, input: &str) -> Document {
+    let config = ParserConfig::default().with_text_includes(dir.to_string_lossy());
+    parse_with_config(input, config).unwrap()
+}
+
+/// Get the plain text of a paragraph or heading block
+fn block_text(block: &Block) -> String {
+    let inlines = match block {
+        Block::Paragraph(p) => &p.inlines,
+        Block::Heading(h) => &h.text,
+        _ => return String::new(),
+    };
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => text.as_str(),
+            _ => "",
+        })
+        .collect()
+}
+
+#[test]
+fn test_text_include_inlines_chapter() {
+    let dir = write_files(&[("chapter.adoc", "== Chapter\n\nChapter body.\n")]);
+    let doc = parse_in(dir.path(), "= Book\n\ninclude::chapter.adoc[]\n\nAfter.\n");
+
+    assert_eq!(doc.blocks.len(), 3);
+    assert!(matches!(&doc.blocks[0], Block::Heading(h) if h.level == 1));
+    assert_eq!(block_text(&doc.blocks[1]), "Chapter body.");
+    assert_eq!(block_text(&doc.blocks[2]), "After.");
+}
+
+#[test]
+fn test_text_include_leveloffset() {
+    let dir = write_files(&[
+        (
+            "chapter.adoc",
+            "= Chapter\n\n== Section\n\ninclude::nested.adoc[]\n",
+        ),
+        ("nested.adoc", "== Nested\n"),
+    ]);
+    let doc = parse_in(
+        dir.path(),
+        "= Book\n\ninclude::chapter.adoc[leveloffset=+1]\n",
+    );
+
+    let levels: Vec<u8> = doc
+        .blocks
+        .iter()
+        .filter_map(|b| match b {
+            Block::Heading(h) => Some(h.level),
+            _ => None,
+        })
+        .collect();
+    // Offsets are inherited by nested includes
+    assert_eq!(levels, vec![1, 2, 2]);
+    assert_eq!(block_text(&doc.blocks[0]), "Chapter");
+}
+
+#[test]
+fn test_text_include_tags_and_lines() {
+    let dir = write_files(&[(
+        "chapter.adoc",
+        "Intro.\n\n// tag::summary[]\nSummary text.\n// end::summary[]\n\nLine seven.\n",
+    )]);
+
+    let doc = parse_in(dir.path(), "include::chapter.adoc[tags=summary]\n");
+    assert_eq!(doc.blocks.len(), 1);
+    assert_eq!(block_text(&doc.blocks[0]), "Summary text.");
+
+    let doc = parse_in(dir.path(), "include::chapter.adoc[lines=7]\n");
+    assert_eq!(doc.blocks.len(), 1);
+    assert_eq!(block_text(&doc.blocks[0]), "Line seven.");
+}
+
+#[test]
+fn test_text_include_cycle_is_broken() {
+    let dir = write_files(&[
+        ("a.adoc", "From A.\n\ninclude::b.adoc[]\n"),
+        ("b.adoc", "From B.\n\ninclude::a.adoc[]\n"),
+    ]);
+    let doc = parse_in(dir.path(), "include::a.adoc[]\n");
+
+    let texts: Vec<String> = doc.blocks.iter().map(block_text).collect();
+    assert_eq!(texts, vec!["From A.", "From B."]);
+}
+
+#[test]
+fn test_text_include_depth_limit() {
+    let dir = write_files(&[
+        ("one.adoc", "One.\n\ninclude::two.adoc[]\n"),
+        ("two.adoc", "Two.\n\ninclude::three.adoc[]\n"),
+        ("three.adoc", "Three.\n"),
+    ]);
+    let mut config = ParserConfig::default().with_text_includes(dir.path().to_string_lossy());
+    config.max_include_depth = Some(2);
+    let doc = parse_with_config("include::one.adoc[]\n", config).unwrap();
+
+    let texts: Vec<String> = doc.blocks.iter().map(block_text).collect();
+    assert_eq!(texts, vec!["One.", "Two."]);
+}
+
+#[test]
+fn test_text_include_provenance() {
+    let dir = write_files(&[
+        (
+            "chapters/intro.adoc",
+            "== Intro\n\ninclude::detail.adoc[]\n",
+        ),
+        ("chapters/detail.adoc", "\nDetail paragraph.\n"),
+    ]);
+    let doc = parse_in(
+        dir.path(),
+        "= Book\n\nPreface.\n\ninclude::chapters/intro.adoc[]\n",
+    );
+
+    assert_eq!(doc.blocks.len(), 3);
+    assert_eq!(doc.source_map.block_file(0), None);
+
+    let heading = doc.source_map.block(1).unwrap();
+    assert_eq!(heading.file.as_deref(), Some("chapters/intro.adoc"));
+    assert_eq!(heading.line, 1);
+
+    let detail = doc.source_map.block(2).unwrap();
+    assert_eq!(detail.file.as_deref(), Some("chapters/detail.adoc"));
+    assert_eq!(detail.line, 2);
+}
+
+#[test]
+fn test_text_include_missing_file() {
+    let dir = write_files(&[]);
+    let doc = parse_in(dir.path(), "include::missing.adoc[]\n");
+
+    assert_eq!(doc.blocks.len(), 1);
+    assert!(block_text(&doc.blocks[0]).contains("missing.adoc"));
+}
+
+#[test]
+fn test_text_include_disabled_by_default() {
+    let dir = write_files(&[("chapter.adoc", "Chapter body.\n")]);
+    let input = format!("include::{}/chapter.adoc[]\n", dir.path().display());
+    let doc = parse_with_config(&input, ParserConfig::default()).unwrap();
+
+    assert!(doc.blocks.iter().all(|b| block_text(b) != "Chapter body."));
+}
@@ -0,0 +1,110 @@
+//! Transform Pipeline Specification Tests
+//!
+//! These tests run pipelines configured the way `utf8dok.toml` configures
+//! them over parsed AsciiDoc and Markdown, and check that every built-in
+//! filter sees the same document whichever frontend produced it.
+
+use utf8dok_ast::{Block, Document, Inline};
+use utf8dok_core::transform::{Glossary, StripDraft};
+use utf8dok_core::{generate, parse, parse_markdown, Pipeline, TransformSpec};
+
+const DOCUMENT: &str = r#"= Operations Guide
+
+== Install
+
+Download the CLI from <<download,the portal>>.
+
+[.draft]
+--
+Add the Windows steps.
+--
+
+[[download]]
+=== Download
+
+The CLI talks to the API.
+
+[glossary]
+--
+CLI:: Command line interface
+--
+"#;
+
+fn pipeline(config: &str) -> Pipeline {
+    let specs: Vec<TransformSpec> = serde_json::from_str(config).unwrap();
+    Pipeline::from_specs(&specs, |path| panic!("unexpected script {}", path)).unwrap()
+}
+
+fn headings(doc: &Document) -> Vec<(u8, String)> {
+    doc.blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Heading(heading) => Some((
+                heading.level,
+                heading
+                    .text
+                    .iter()
+                    .map(|inline| match inline {
+                        Inline::Text(text) => text.as_str(),
+                        _ => "",
+                    })
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_configured_pipeline() {
+    let mut doc = parse(DOCUMENT).unwrap();
+    pipeline(
+        r##"[
+            {"name": "strip-draft"},
+            {"name": "shift-headings", "by": 1},
+            {"name": "number-sections", "levels": 2},
+            {"name": "rewrite-links", "pattern": "^#", "replacement": "ops.html#"},
+            {"name": "glossary", "level": 2, "terms": {"API": "Application programming interface"}}
+        ]"##,
+    )
+    .run(&mut doc)
+    .unwrap();
+
+    // Numbering starts at the shifted top level
+    assert_eq!(
+        headings(&doc),
+        [
+            (2, "1. Install".to_string()),
+            (3, "1.1. Download".to_string()),
+            (2, "Glossary".to_string()),
+        ]
+    );
+
+    let asciidoc = generate(&doc);
+    assert!(!asciidoc.contains("Windows"));
+    assert!(asciidoc.contains("ops.html#download[the portal]"));
+    assert!(asciidoc.contains("API:: Application programming interface"));
+    assert!(asciidoc.contains("CLI:: Command line interface"));
+}
+
+#[test]
+fn test_markdown_documents_are_transformed_alike() {
+    let mut doc =
+        parse_markdown("# Guide\n\nThe CLI is fast.\n\n## Usage\n\nRun it.\n\n## Glossary\n");
+    if let Some(Block::Paragraph(paragraph)) = doc.blocks.get_mut(0) {
+        paragraph
+            .attributes
+            .insert("role".to_string(), "draft".to_string());
+    }
+    Pipeline::new()
+        .with(StripDraft::default())
+        .with(Glossary::default().with_term("CLI", "Command line interface"))
+        .run(&mut doc)
+        .unwrap();
+
+    // The draft paragraph was the only use of the term
+    let markdown = utf8dok_core::generate_markdown(&doc);
+    assert!(!markdown.contains("The CLI is fast."));
+    assert!(!markdown.contains("Command line interface"));
+    assert!(markdown.contains("## Glossary"));
+}
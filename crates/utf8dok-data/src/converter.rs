@@ -56,7 +56,8 @@ impl TableConverter {
         let columns: Vec<ColumnSpec> = (0..num_cols)
             .map(|_| ColumnSpec {
                 width: None,
-                align: options.default_alignment.clone(),
+                align: options.default_alignment,
+                style: None,
             })
             .collect();
 
@@ -101,7 +102,11 @@ impl TableConverter {
             cells.push(Self::create_cell(String::new()));
         }
 
-        TableRow { cells, is_header }
+        TableRow {
+            cells,
+            is_header,
+            is_footer: false,
+        }
     }
 
     /// Create a table cell from a string value
@@ -121,6 +126,7 @@ impl TableConverter {
             colspan: 1,
             rowspan: 1,
             align: None,
+            style: None,
         }
     }
 
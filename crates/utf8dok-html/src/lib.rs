@@ -0,0 +1,64 @@
+//! utf8dok-html - HTML5 generation
+//!
+//! This crate renders utf8dok documents as standalone, accessible HTML5
+//! pages.
+//!
+//! # Features
+//!
+//! - A generated table of contents and `<section>` elements labelled by
+//!   their headings
+//! - Anchor ids matching the bookmarks of the DOCX output, including the
+//!   original Word bookmark names restored through a `StyleContract`
+//! - Diagrams rendered as inline SVG through the diagram engine, falling
+//!   back to their source
+//! - An embedded stylesheet themed from the `StyleContract` theme defaults
+//! - Optional chunked output, with one page per top-level section
+//!
+//! # Example
+//!
+//! ```
+//! use utf8dok_ast::{Block, Document, Heading, Inline};
+//! use utf8dok_html::HtmlWriter;
+//!
+//! let mut doc = Document::with_title("Guide");
+//! doc.push(Block::Heading(Heading {
+//!     level: 1,
+//!     text: vec![Inline::Text("Setup".to_string())],
+//!     style_id: None,
+//!     anchor: None,
+//!     role: None,
+//! }));
+//!
+//! let html = HtmlWriter::new().generate(&doc);
+//! assert!(html.starts_with("<!DOCTYPE html>"));
+//! assert!(html.contains("<a href=\"#_setup\">Setup</a>"));
+//! assert!(html.contains("<h2 id=\"_setup\">Setup</h2>"));
+//! ```
+
+mod outline;
+mod theme;
+mod writer;
+
+pub use theme::{stylesheet, theme_css};
+pub use writer::{HtmlConfig, HtmlPage, HtmlWriter};
+
+/// Crate version
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Convenience function to render a document as a single HTML page
+pub fn render_html(doc: &utf8dok_ast::Document) -> String {
+    HtmlWriter::new().generate(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_structure() {
+        // Verify exports are accessible
+        let _ = HtmlWriter::generate;
+        let _ = HtmlWriter::generate_chunked;
+        let _ = render_html;
+    }
+}
@@ -0,0 +1,335 @@
+//! Document outline: section ids, TOC entries and page chunks
+//!
+//! Ids are assigned once for the whole document so that the single-page
+//! and chunked outputs use the same fragment identifiers, and so that a
+//! link can be pointed at the page holding its target.
+
+use std::collections::{HashMap, HashSet};
+
+use utf8dok_ast::{Block, Document, Inline, Node};
+use utf8dok_core::ids::{section_ids, unique_id, IdSource};
+use utf8dok_ooxml::StyleContract;
+
+/// A top-level heading
+pub(crate) struct Section<'a> {
+    /// Heading level (1 for `==`)
+    pub level: u8,
+    /// Fragment identifier of the heading
+    pub id: String,
+    /// Index of the heading in `Document::blocks`
+    pub block: usize,
+    /// Heading text
+    pub text: &'a [Inline],
+}
+
+/// A page of chunked output: the blocks from `start` up to the next chunk
+pub(crate) struct Chunk {
+    /// File name of the page
+    pub file_name: String,
+    /// First block of the page
+    pub start: usize,
+    /// Index into `Outline::sections` of the section opening the page
+    pub section: Option<usize>,
+}
+
+/// Ids and structure of a document, computed before rendering
+pub(crate) struct Outline<'a> {
+    /// Top-level headings in document order
+    pub sections: Vec<Section<'a>>,
+    /// Heading id by block index
+    heading_ids: HashMap<usize, String>,
+    /// Anchor-only paragraphs whose anchor became the following heading's id
+    absorbed: HashSet<usize>,
+    /// Pages, when chunking (a single page otherwise)
+    pub chunks: Vec<Chunk>,
+    /// Page holding each id
+    targets: HashMap<String, usize>,
+}
+
+impl<'a> Outline<'a> {
+    /// Compute the outline of `doc`, splitting it into pages at top-level
+    /// sections when `chunked` is set
+    pub fn new(doc: &'a Document, contract: Option<&StyleContract>, chunked: bool) -> Self {
+        let mut sections = Vec::new();
+        let mut heading_ids = HashMap::new();
+        let mut absorbed = HashSet::new();
+        for section in section_ids(doc) {
+            let Some(index) = section.block() else {
+                continue;
+            };
+            let Block::Heading(heading) = &doc.blocks[index] else {
+                continue;
+            };
+            let id = match section.source {
+                IdSource::Generated => section.id,
+                IdSource::Anchor => anchor_id(contract, &section.id),
+                IdSource::AnchorAbove => {
+                    // `[[id]]` on the line above the heading
+                    absorbed.insert(index - 1);
+                    anchor_id(contract, &section.id)
+                }
+            };
+            heading_ids.insert(index, id.clone());
+            sections.push(Section {
+                level: heading.level,
+                id,
+                block: index,
+                text: &heading.text,
+            });
+        }
+
+        let mut outline = Self {
+            sections,
+            heading_ids,
+            absorbed,
+            chunks: vec![Chunk {
+                file_name: "index.html".to_string(),
+                start: 0,
+                section: None,
+            }],
+            targets: HashMap::new(),
+        };
+        if chunked {
+            outline.split(doc, contract);
+        }
+        outline
+    }
+
+    /// Split the document into an index page and a page per top-level section
+    fn split(&mut self, doc: &Document, contract: Option<&StyleContract>) {
+        let Some(top) = self.sections.iter().map(|s| s.level).min() else {
+            return;
+        };
+        let mut names: HashSet<String> = HashSet::from(["index".to_string()]);
+        for (position, section) in self.sections.iter().enumerate() {
+            if section.level != top {
+                continue;
+            }
+            // An anchor paragraph above the heading moves with it
+            let start = section
+                .block
+                .checked_sub(1)
+                .filter(|previous| self.absorbed.contains(previous))
+                .unwrap_or(section.block);
+            let name = unique_id(&file_stem(&section.id), &mut names);
+            self.chunks.push(Chunk {
+                file_name: format!("{}.html", name),
+                start,
+                section: Some(position),
+            });
+        }
+
+        for (page, range) in self.chunk_ranges(doc.blocks.len()).into_iter().enumerate() {
+            for index in range {
+                if let Some(id) = self.heading_ids.get(&index) {
+                    self.targets.insert(id.clone(), page);
+                }
+                let targets = &mut self.targets;
+                collect_anchors(Node::Block(&doc.blocks[index]), contract, &mut |id| {
+                    targets.entry(id).or_insert(page);
+                });
+            }
+        }
+    }
+
+    /// Block ranges of the pages, in order
+    pub fn chunk_ranges(&self, block_count: usize) -> Vec<std::ops::Range<usize>> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .map(|(page, chunk)| {
+                let end = self
+                    .chunks
+                    .get(page + 1)
+                    .map_or(block_count, |next| next.start);
+                chunk.start..end
+            })
+            .collect()
+    }
+
+    /// The id of the heading at block `index`
+    pub fn heading_id(&self, index: usize) -> Option<&str> {
+        self.heading_ids.get(&index).map(String::as_str)
+    }
+
+    /// Whether the block at `index` is an anchor paragraph merged into a heading
+    pub fn is_absorbed(&self, index: usize) -> bool {
+        self.absorbed.contains(&index)
+    }
+
+    /// The page holding `id`, if the outline is chunked and knows it
+    pub fn page_of(&self, id: &str) -> Option<usize> {
+        self.targets.get(id).copied()
+    }
+}
+
+/// The fragment identifier for an anchor: the original Word bookmark name
+/// when the style contract maps it, as the DOCX writer does
+pub(crate) fn anchor_id(contract: Option<&StyleContract>, anchor: &str) -> String {
+    contract
+        .and_then(|contract| contract.get_word_bookmark(anchor))
+        .unwrap_or(anchor)
+        .to_string()
+}
+
+/// Call `found` with the id of every anchor in `node` and its descendants
+fn collect_anchors(
+    node: Node<'_>,
+    contract: Option<&StyleContract>,
+    found: &mut dyn FnMut(String),
+) {
+    match node {
+        Node::Inline(Inline::Anchor(name)) => found(anchor_id(contract, name)),
+        Node::Block(Block::Heading(heading)) => {
+            if let Some(anchor) = &heading.anchor {
+                found(anchor_id(contract, anchor));
+            }
+        }
+        _ => {}
+    }
+    for child in node.children() {
+        collect_anchors(child, contract, found);
+    }
+}
+
+/// A file name stem for a section id, safe on every platform
+fn file_stem(id: &str) -> String {
+    let stem: String = id
+        .trim_start_matches('_')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "section".to_string()
+    } else {
+        stem
+    }
+}
+
+/// Text of inlines with all formatting dropped
+pub(crate) fn plain_text(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(t) => text.push_str(t),
+            Inline::Format(_, inner) => text.push_str(&plain_text(std::slice::from_ref(inner))),
+            Inline::Span(inlines) => text.push_str(&plain_text(inlines)),
+            Inline::Link(link) => text.push_str(&plain_text(&link.text)),
+            Inline::Image(image) => text.push_str(image.alt.as_deref().unwrap_or_default()),
+            Inline::Break => text.push(' '),
+            Inline::Anchor(_) | Inline::Footnote(_) => {}
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use utf8dok_ast::{Heading, Paragraph};
+    use utf8dok_ooxml::{AnchorMapping, AnchorType};
+
+    fn heading(level: u8, text: &str) -> Block {
+        Block::Heading(Heading {
+            level,
+            text: vec![Inline::Text(text.to_string())],
+            style_id: None,
+            anchor: None,
+            role: None,
+        })
+    }
+
+    fn paragraph(inlines: Vec<Inline>) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines,
+            style_id: None,
+            attributes: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_heading_ids() {
+        let mut doc = Document::new();
+        doc.push(heading(1, "Getting Started!"));
+        doc.push(heading(2, "Getting started"));
+        doc.push(paragraph(vec![Inline::Anchor("custom".to_string())]));
+        doc.push(heading(1, "Custom"));
+        doc.push(paragraph(vec![Inline::Anchor("_overview".to_string())]));
+        doc.push(heading(1, "Overview"));
+
+        let outline = Outline::new(&doc, None, false);
+        let ids: Vec<&str> = outline.sections.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "_getting_started",
+                "_getting_started_2",
+                "custom",
+                "_overview"
+            ]
+        );
+        assert!(outline.is_absorbed(2));
+    }
+
+    #[test]
+    fn test_generated_ids_avoid_explicit_anchors() {
+        let mut doc = Document::new();
+        doc.push(paragraph(vec![
+            Inline::Text("See ".to_string()),
+            Inline::Anchor("_intro".to_string()),
+        ]));
+        doc.push(heading(1, "Intro"));
+        let outline = Outline::new(&doc, None, false);
+        assert_eq!(outline.sections[0].id, "_intro_2");
+    }
+
+    #[test]
+    fn test_anchor_ids_use_contract_bookmarks() {
+        let mut contract = StyleContract::default();
+        contract.add_anchor(
+            "_Toc123",
+            AnchorMapping {
+                semantic_id: "intro".to_string(),
+                anchor_type: AnchorType::Heading,
+                target_heading: None,
+                original_bookmark: Some("_Toc123".to_string()),
+            },
+        );
+        assert_eq!(anchor_id(Some(&contract), "intro"), "_Toc123");
+        assert_eq!(anchor_id(Some(&contract), "other"), "other");
+        assert_eq!(anchor_id(None, "intro"), "intro");
+    }
+
+    #[test]
+    fn test_chunks() {
+        let mut doc = Document::new();
+        doc.push(paragraph(vec![Inline::Text("Preamble".to_string())]));
+        doc.push(heading(1, "One"));
+        doc.push(heading(2, "One A"));
+        doc.push(paragraph(vec![Inline::Anchor("deep".to_string())]));
+        doc.push(paragraph(vec![Inline::Anchor("two".to_string())]));
+        doc.push(heading(1, "Two"));
+
+        let outline = Outline::new(&doc, None, true);
+        let files: Vec<&str> = outline
+            .chunks
+            .iter()
+            .map(|c| c.file_name.as_str())
+            .collect();
+        assert_eq!(files, vec!["index.html", "one.html", "two.html"]);
+        assert_eq!(
+            outline.chunk_ranges(doc.blocks.len()),
+            vec![0..1, 1..4, 4..6]
+        );
+        assert_eq!(outline.page_of("_one_a"), Some(1));
+        assert_eq!(outline.page_of("deep"), Some(1));
+        assert_eq!(outline.page_of("two"), Some(2));
+    }
+}
@@ -0,0 +1,141 @@
+//! Stylesheet generation
+//!
+//! The default stylesheet is written against CSS custom properties; a
+//! `StyleContract` theme overrides them so the page picks up the fonts and
+//! accent color of the Word template it was extracted from.
+
+use utf8dok_ooxml::ThemeDefaults;
+
+/// Default stylesheet, using the custom properties set by [`theme_css`]
+const BASE_CSS: &str = r#"*, *::before, *::after { box-sizing: border-box; }
+body { margin: 0 auto; max-width: 60rem; padding: 1rem 1.5rem 3rem; font-family: var(--body-font); font-size: var(--base-font-size); line-height: 1.6; color: #1f2328; background: #fff; }
+h1, h2, h3, h4, h5, h6 { font-family: var(--heading-font); line-height: 1.25; color: var(--accent-color); }
+a { color: var(--accent-color); }
+a:focus-visible { outline: 2px solid var(--accent-color); outline-offset: 2px; }
+.skip-link { position: absolute; left: -999px; }
+.skip-link:focus { left: 1rem; top: 1rem; padding: .5rem; background: #fff; }
+.byline { color: #59636e; }
+nav.toc { border-left: 3px solid var(--accent-color); padding-left: 1rem; margin: 1.5rem 0; }
+nav.toc ol { list-style: none; padding-left: 1.25rem; }
+nav.toc > ol { padding-left: 0; }
+nav.pager { display: flex; justify-content: space-between; gap: 1rem; margin: 2rem 0; }
+pre { padding: .75rem 1rem; overflow-x: auto; background: #f6f8fa; border-radius: 4px; }
+code { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: .9em; }
+pre mark { background: #fff3a8; }
+.conum { display: inline-block; min-width: 1.4em; border-radius: 50%; background: var(--accent-color); color: #fff; font: bold .75em sans-serif; text-align: center; }
+figure { margin: 1.5rem 0; }
+figcaption, .title { font-style: italic; color: #59636e; }
+figure.diagram svg, figure.image img { max-width: 100%; height: auto; }
+table { border-collapse: collapse; margin: 1.5rem 0; }
+th, td { border: 1px solid #d1d9e0; padding: .4rem .75rem; vertical-align: top; }
+thead th { background: #f6f8fa; }
+caption { caption-side: top; text-align: left; font-style: italic; }
+.admonition { border-left: 4px solid var(--accent-color); background: #f6f8fa; padding: .5rem 1rem; margin: 1.5rem 0; }
+.admonition.warning, .admonition.caution { border-color: #cf222e; }
+.admonition .title { font-weight: bold; font-style: normal; }
+.sidebar { border: 1px solid #d1d9e0; padding: .5rem 1rem; margin: 1.5rem 0; }
+blockquote { margin: 0; padding-left: 1rem; border-left: 3px solid #d1d9e0; }
+.page-break { break-after: page; }
+.footnotes { border-top: 1px solid #d1d9e0; margin-top: 3rem; font-size: .9em; }
+@media print { nav.toc, nav.pager, .skip-link { display: none; } body { max-width: none; } }
+"#;
+
+/// The full stylesheet for `theme`
+pub fn stylesheet(theme: &ThemeDefaults) -> String {
+    format!("{}{}", theme_css(theme), BASE_CSS)
+}
+
+/// CSS custom properties for `theme`, falling back to neutral defaults
+///
+/// Values are sanitized, since a style contract is untrusted input that
+/// ends up inside a `<style>` element.
+pub fn theme_css(theme: &ThemeDefaults) -> String {
+    let body_font = theme
+        .body_font
+        .as_deref()
+        .and_then(font_family)
+        .unwrap_or_else(|| "system-ui, -apple-system, \"Segoe UI\", sans-serif".to_string());
+    let heading_font = theme
+        .heading_font
+        .as_deref()
+        .and_then(font_family)
+        .unwrap_or_else(|| "var(--body-font)".to_string());
+    let font_size = theme
+        .base_font_size
+        .filter(|size| (6..=72).contains(size))
+        .map(|size| format!("{}pt", size))
+        .unwrap_or_else(|| "1rem".to_string());
+    let accent = theme
+        .accent_color
+        .as_deref()
+        .and_then(hex_color)
+        .unwrap_or_else(|| "#1a5fb4".to_string());
+
+    format!(
+        ":root {{\n  --body-font: {};\n  --heading-font: {};\n  --base-font-size: {};\n  --accent-color: {};\n}}\n",
+        body_font, heading_font, font_size, accent
+    )
+}
+
+/// A quoted font family with a generic fallback, or None if nothing is left
+/// after removing characters that could escape the declaration
+fn font_family(name: &str) -> Option<String> {
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, '"' | '\'' | '\\' | ';' | '{' | '}' | '<' | '>'))
+        .collect();
+    let name = name.trim();
+    (!name.is_empty()).then(|| format!("\"{}\", sans-serif", name))
+}
+
+/// A `#rrggbb` or `#rgb` color from a Word-style hex value (with or without `#`)
+fn hex_color(value: &str) -> Option<String> {
+    let hex = value.trim().trim_start_matches('#');
+    let valid = matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    valid.then(|| format!("#{}", hex.to_ascii_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_theme() {
+        let css = theme_css(&ThemeDefaults::default());
+        assert!(css.contains("--accent-color: #1a5fb4;"));
+        assert!(css.contains("--base-font-size: 1rem;"));
+        assert!(css.contains("--heading-font: var(--body-font);"));
+    }
+
+    #[test]
+    fn test_contract_theme() {
+        let theme = ThemeDefaults {
+            heading_font: Some("Calibri Light".to_string()),
+            body_font: Some("Calibri".to_string()),
+            base_font_size: Some(11),
+            accent_color: Some("4472C4".to_string()),
+        };
+        let css = stylesheet(&theme);
+        assert!(css.starts_with(":root {"));
+        assert!(css.contains("--heading-font: \"Calibri Light\", sans-serif;"));
+        assert!(css.contains("--body-font: \"Calibri\", sans-serif;"));
+        assert!(css.contains("--base-font-size: 11pt;"));
+        assert!(css.contains("--accent-color: #4472c4;"));
+    }
+
+    #[test]
+    fn test_theme_values_are_sanitized() {
+        let theme = ThemeDefaults {
+            heading_font: Some("x\"; } body { display: none".to_string()),
+            body_font: Some("\"\"".to_string()),
+            base_font_size: Some(1000),
+            accent_color: Some("red; }".to_string()),
+        };
+        let css = theme_css(&theme);
+        assert!(!css.contains("display: none;"));
+        assert!(css.contains("--heading-font: \"x  body  display: none\", sans-serif;"));
+        assert!(css.contains("--body-font: system-ui"));
+        assert!(css.contains("--base-font-size: 1rem;"));
+        assert!(css.contains("--accent-color: #1a5fb4;"));
+    }
+}
@@ -0,0 +1,1113 @@
+//! HTML5 writer
+//!
+//! Renders a document as a standalone page with an embedded stylesheet, or
+//! as a set of pages with one per top-level section. Sections become
+//! `<section>` elements labelled by their heading, so the outline is
+//! available to assistive technology as well as through the TOC.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use utf8dok_ast::{
+    Admonition, AdmonitionType, Alignment, Block, BreakType, CellStyle, Document, FormatType,
+    Heading, Inline, List, ListItem, ListType, LiteralBlock, NumberingStyle, QuoteBlock, Table,
+    TableCell,
+};
+use utf8dok_diagrams::{DiagramEngine, DiagramType};
+use utf8dok_ooxml::{StyleContract, ThemeDefaults};
+
+use crate::outline::{anchor_id, plain_text, Outline};
+use crate::theme::stylesheet;
+
+/// Options for HTML generation
+#[derive(Debug, Clone)]
+pub struct HtmlConfig {
+    /// Include a table of contents
+    pub toc: bool,
+    /// Number of section levels listed in the TOC, overridden by the
+    /// `toclevels` document attribute
+    pub toc_levels: u8,
+    /// Embed the default stylesheet, themed by the style contract
+    pub stylesheet: bool,
+}
+
+impl Default for HtmlConfig {
+    fn default() -> Self {
+        Self {
+            toc: true,
+            toc_levels: 2,
+            stylesheet: true,
+        }
+    }
+}
+
+/// A page of HTML output
+#[derive(Debug, Clone, PartialEq)]
+pub struct HtmlPage {
+    /// File name of the page (`index.html` for the first page)
+    pub file_name: String,
+    /// Page title
+    pub title: String,
+    /// The complete HTML document
+    pub html: String,
+}
+
+/// HTML5 document writer
+///
+/// Anchors use the same names as the bookmarks written by the DOCX writer:
+/// with a style contract, anchors extracted from Word get their original
+/// bookmark names back, so links into either output keep working.
+pub struct HtmlWriter {
+    config: HtmlConfig,
+    style_contract: Option<StyleContract>,
+    diagram_engine: Option<DiagramEngine>,
+}
+
+impl Default for HtmlWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlWriter {
+    /// Create a writer with the default configuration
+    pub fn new() -> Self {
+        Self::with_config(HtmlConfig::default())
+    }
+
+    /// Create a writer with a custom configuration
+    pub fn with_config(config: HtmlConfig) -> Self {
+        Self {
+            config,
+            style_contract: None,
+            diagram_engine: None,
+        }
+    }
+
+    /// Set the style contract used for anchor names and the theme
+    pub fn set_style_contract(&mut self, contract: StyleContract) {
+        self.style_contract = Some(contract);
+    }
+
+    /// Render diagram blocks as inline SVG with the default diagram engine
+    ///
+    /// Diagrams that fail to render are shown as source code.
+    pub fn set_render_diagrams(&mut self, render: bool) {
+        self.diagram_engine = render.then(DiagramEngine::new);
+    }
+
+    /// Render diagram blocks as inline SVG with a custom diagram engine
+    pub fn set_diagram_engine(&mut self, engine: DiagramEngine) {
+        self.diagram_engine = Some(engine);
+    }
+
+    /// Generate a standalone HTML page for the whole document
+    pub fn generate(&self, doc: &Document) -> String {
+        let outline = Outline::new(doc, self.style_contract.as_ref(), false);
+        Renderer::new(self, doc, &outline).page(0).html
+    }
+
+    /// Generate an index page followed by one page per top-level section
+    ///
+    /// Content before the first section goes on the index page. Links to
+    /// anchors on other pages point at the page holding them.
+    pub fn generate_chunked(&self, doc: &Document) -> Vec<HtmlPage> {
+        let outline = Outline::new(doc, self.style_contract.as_ref(), true);
+        let mut renderer = Renderer::new(self, doc, &outline);
+        (0..outline.chunks.len())
+            .map(|page| renderer.page(page))
+            .collect()
+    }
+}
+
+/// Rendering state for one document
+struct Renderer<'a> {
+    writer: &'a HtmlWriter,
+    doc: &'a Document,
+    outline: &'a Outline<'a>,
+    /// Page being rendered
+    page: usize,
+    output: String,
+    /// Footnotes of the current page: number and rendered content
+    notes: Vec<(usize, String)>,
+    /// Number and page of each footnote with an id
+    note_ids: HashMap<String, (usize, usize)>,
+    /// Footnotes are numbered across pages
+    note_count: usize,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(writer: &'a HtmlWriter, doc: &'a Document, outline: &'a Outline<'a>) -> Self {
+        Self {
+            writer,
+            doc,
+            outline,
+            page: 0,
+            output: String::new(),
+            notes: Vec::new(),
+            note_ids: HashMap::new(),
+            note_count: 0,
+        }
+    }
+
+    /// Render page `page` of the outline
+    fn page(&mut self, page: usize) -> HtmlPage {
+        self.page = page;
+        let range = self.outline.chunk_ranges(self.doc.blocks.len())[page].clone();
+        let chunked = self.outline.chunks.len() > 1;
+        let title = match (self.page_title(page), &self.doc.metadata.title) {
+            (Some(section), Some(doc_title)) => format!("{} \u{2013} {}", section, doc_title),
+            (Some(section), None) => section,
+            (None, Some(doc_title)) => doc_title.clone(),
+            (None, None) => "Untitled".to_string(),
+        };
+
+        let body = self.capture(|r| {
+            r.write_body(range);
+            r.write_footnotes();
+        });
+        let attributes = &self.doc.metadata.attributes;
+        let lang = attributes.get("lang").map_or("en", String::as_str);
+
+        let out = &mut self.output;
+        out.push_str("<!DOCTYPE html>\n");
+        out.push_str(&format!("<html lang=\"{}\">\n<head>\n", escape_attr(lang)));
+        out.push_str("<meta charset=\"utf-8\">\n");
+        out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        out.push_str(&format!(
+            "<meta name=\"generator\" content=\"utf8dok {}\">\n",
+            crate::VERSION
+        ));
+        if !self.doc.metadata.authors.is_empty() {
+            out.push_str(&format!(
+                "<meta name=\"author\" content=\"{}\">\n",
+                escape_attr(&self.doc.metadata.authors.join(", "))
+            ));
+        }
+        out.push_str(&format!("<title>{}</title>\n", escape_html(&title)));
+        if self.writer.config.stylesheet {
+            let default_theme = ThemeDefaults::default();
+            let theme = self
+                .writer
+                .style_contract
+                .as_ref()
+                .map_or(&default_theme, |contract| &contract.theme);
+            out.push_str(&format!("<style>\n{}</style>\n", stylesheet(theme)));
+        }
+        out.push_str("</head>\n<body>\n");
+        out.push_str("<a class=\"skip-link\" href=\"#content\">Skip to content</a>\n");
+
+        if page == 0 {
+            self.write_header();
+        }
+        if self.writer.config.toc {
+            self.write_toc();
+        }
+        if chunked {
+            self.write_pager();
+        }
+        self.output.push_str("<main id=\"content\">\n");
+        self.output.push_str(&body);
+        self.output.push_str("</main>\n");
+        if chunked {
+            self.write_pager();
+        }
+        self.output.push_str("</body>\n</html>\n");
+
+        HtmlPage {
+            file_name: self.outline.chunks[page].file_name.clone(),
+            title,
+            html: std::mem::take(&mut self.output),
+        }
+    }
+
+    /// Title of the section opening `page`, as plain text
+    fn page_title(&self, page: usize) -> Option<String> {
+        self.outline.chunks[page]
+            .section
+            .map(|section| plain_text(self.outline.sections[section].text))
+    }
+
+    /// Run `write` and return what it wrote instead of appending it
+    fn capture(&mut self, write: impl FnOnce(&mut Self)) -> String {
+        let saved = std::mem::take(&mut self.output);
+        write(self);
+        std::mem::replace(&mut self.output, saved)
+    }
+
+    /// Write the document title, authors and revision
+    fn write_header(&mut self) {
+        let meta = &self.doc.metadata;
+        if meta.title.is_none() && meta.authors.is_empty() {
+            return;
+        }
+        self.output.push_str("<header>\n");
+        if let Some(title) = &meta.title {
+            self.output
+                .push_str(&format!("<h1>{}</h1>\n", escape_html(title)));
+        }
+        let mut details = Vec::new();
+        if !meta.authors.is_empty() {
+            details.push(format!(
+                "<span class=\"author\">{}</span>",
+                escape_html(&meta.authors.join(", "))
+            ));
+        }
+        if let Some(revision) = &meta.revision {
+            details.push(format!(
+                "<span class=\"revision\">Version {}</span>",
+                escape_html(revision)
+            ));
+        }
+        if !details.is_empty() {
+            self.output.push_str(&format!(
+                "<p class=\"byline\">{}</p>\n",
+                details.join(" \u{b7} ")
+            ));
+        }
+        self.output.push_str("</header>\n");
+    }
+
+    /// Write the table of contents
+    fn write_toc(&mut self) {
+        let outline = self.outline;
+        let Some(top) = outline.sections.iter().map(|s| s.level).min() else {
+            return;
+        };
+        let attributes = &self.doc.metadata.attributes;
+        let levels = attributes
+            .get("toclevels")
+            .and_then(|levels| levels.trim().parse::<u8>().ok())
+            .unwrap_or(self.writer.config.toc_levels)
+            .max(1);
+        let entries: Vec<_> = outline
+            .sections
+            .iter()
+            .filter(|s| s.level - top < levels)
+            .collect();
+        let toc_title = attributes
+            .get("toc-title")
+            .map_or("Table of Contents", String::as_str);
+
+        self.output
+            .push_str("<nav class=\"toc\" aria-labelledby=\"toc-title\">\n");
+        self.output.push_str(&format!(
+            "<h2 id=\"toc-title\">{}</h2>\n",
+            escape_html(toc_title)
+        ));
+        self.write_nested(
+            &entries,
+            |section| (section.level - top) as usize,
+            "<ol>",
+            "</ol>",
+            |r, section| {
+                let href = r.href_to(&section.id);
+                r.output
+                    .push_str(&format!("<a href=\"{}\">", escape_attr(&href)));
+                r.write_inlines(section.text);
+                r.output.push_str("</a>");
+            },
+        );
+        self.output.push_str("</nav>\n");
+    }
+
+    /// Write previous/next links between chunked pages
+    fn write_pager(&mut self) {
+        self.output
+            .push_str("<nav class=\"pager\" aria-label=\"Pages\">\n");
+        let chunks = &self.outline.chunks;
+        if self.page > 0 {
+            let previous = self.page - 1;
+            let label = self
+                .page_title(previous)
+                .or_else(|| self.doc.metadata.title.clone())
+                .unwrap_or_else(|| "Contents".to_string());
+            self.output.push_str(&format!(
+                "<a href=\"{}\" rel=\"prev\">\u{2190} {}</a>\n",
+                escape_attr(&chunks[previous].file_name),
+                escape_html(&label)
+            ));
+        }
+        if self.page + 1 < chunks.len() {
+            let next = self.page + 1;
+            let label = self.page_title(next).unwrap_or_default();
+            self.output.push_str(&format!(
+                "<a href=\"{}\" rel=\"next\">{} \u{2192}</a>\n",
+                escape_attr(&chunks[next].file_name),
+                escape_html(&label)
+            ));
+        }
+        self.output.push_str("</nav>\n");
+    }
+
+    /// Write the top-level blocks in `range`, wrapping sections
+    fn write_body(&mut self, range: std::ops::Range<usize>) {
+        let doc = self.doc;
+        let mut open_sections: Vec<u8> = Vec::new();
+        for index in range {
+            if self.outline.is_absorbed(index) {
+                continue;
+            }
+            let block = &doc.blocks[index];
+            let Block::Heading(heading) = block else {
+                self.write_block(block);
+                continue;
+            };
+            while open_sections
+                .last()
+                .is_some_and(|&level| level >= heading.level)
+            {
+                open_sections.pop();
+                self.output.push_str("</section>\n");
+            }
+            open_sections.push(heading.level);
+            let id = self.outline.heading_id(index).map(str::to_string);
+            if let Some(id) = &id {
+                self.output.push_str(&format!(
+                    "<section aria-labelledby=\"{}\">\n",
+                    escape_attr(id)
+                ));
+            } else {
+                self.output.push_str("<section>\n");
+            }
+            self.write_heading(heading, id.as_deref());
+        }
+        for _ in open_sections {
+            self.output.push_str("</section>\n");
+        }
+    }
+
+    fn write_blocks(&mut self, blocks: &[Block]) {
+        for block in blocks {
+            self.write_block(block);
+        }
+    }
+
+    fn write_block(&mut self, block: &Block) {
+        match block {
+            Block::Paragraph(p) => match p.inlines.as_slice() {
+                // A paragraph holding only anchors marks a position
+                inlines if inlines.iter().all(|i| matches!(i, Inline::Anchor(_))) => {
+                    self.write_inlines(inlines);
+                    self.output.push('\n');
+                }
+                [image @ Inline::Image(_)] => {
+                    self.output.push_str("<figure class=\"image\">\n");
+                    self.write_inline(image);
+                    self.output.push('\n');
+                    if let Some(title) = p.attributes.get("title") {
+                        self.output.push_str(&format!(
+                            "<figcaption>{}</figcaption>\n",
+                            escape_html(title)
+                        ));
+                    }
+                    self.output.push_str("</figure>\n");
+                }
+                inlines => {
+                    self.output.push_str("<p>");
+                    self.write_inlines(inlines);
+                    self.output.push_str("</p>\n");
+                }
+            },
+            Block::Heading(heading) => {
+                let id = heading
+                    .anchor
+                    .as_ref()
+                    .map(|anchor| anchor_id(self.writer.style_contract.as_ref(), anchor));
+                self.write_heading(heading, id.as_deref());
+            }
+            Block::List(list) => self.write_list(list),
+            Block::Table(table) => self.write_table(table),
+            Block::Admonition(admonition) => self.write_admonition(admonition),
+            Block::Literal(literal) => self.write_literal(literal),
+            Block::Break(BreakType::Page | BreakType::Section) => {
+                self.output
+                    .push_str("<div class=\"page-break\" role=\"presentation\"></div>\n");
+            }
+            Block::Open(open) => {
+                match &open.role {
+                    Some(role) => self
+                        .output
+                        .push_str(&format!("<div class=\"open {}\">\n", escape_attr(role))),
+                    None => self.output.push_str("<div class=\"open\">\n"),
+                }
+                self.write_title(open.title.as_deref());
+                self.write_blocks(&open.blocks);
+                self.output.push_str("</div>\n");
+            }
+            Block::Sidebar(sidebar) => {
+                self.output.push_str("<aside class=\"sidebar\">\n");
+                self.write_title(sidebar.title.as_deref());
+                self.write_blocks(&sidebar.blocks);
+                self.output.push_str("</aside>\n");
+            }
+            Block::Quote(quote) => self.write_quote(quote),
+            Block::ThematicBreak => self.output.push_str("<hr>\n"),
+            // Comments are not rendered
+            Block::Comment(_) => {}
+        }
+    }
+
+    /// Write a block title
+    fn write_title(&mut self, title: Option<&str>) {
+        if let Some(title) = title {
+            self.output
+                .push_str(&format!("<p class=\"title\">{}</p>\n", escape_html(title)));
+        }
+    }
+
+    fn write_heading(&mut self, heading: &Heading, id: Option<&str>) {
+        // The document title is the page's h1; sections start at h2
+        let tag = format!("h{}", (heading.level + 1).clamp(1, 6));
+        match id {
+            Some(id) => self
+                .output
+                .push_str(&format!("<{} id=\"{}\">", tag, escape_attr(id))),
+            None => self.output.push_str(&format!("<{}>", tag)),
+        }
+        self.write_inlines(&heading.text);
+        self.output.push_str(&format!("</{}>\n", tag));
+    }
+
+    fn write_list(&mut self, list: &List) {
+        let (open, close) = match (&list.list_type, list.numbering) {
+            (ListType::Description, _) => {
+                self.output.push_str("<dl>\n");
+                for item in &list.items {
+                    self.output.push_str("<dt>");
+                    if let Some(term) = &item.term {
+                        self.write_inlines(term);
+                    }
+                    self.output.push_str("</dt>\n<dd>");
+                    self.write_compact(&item.content);
+                    self.output.push_str("</dd>\n");
+                }
+                self.output.push_str("</dl>\n");
+                return;
+            }
+            (ListType::Unordered, _) if list.items.iter().any(|i| i.checked.is_some()) => {
+                ("<ul class=\"checklist\">", "</ul>")
+            }
+            (ListType::Unordered, _) => ("<ul>", "</ul>"),
+            (ListType::Callout, _) => ("<ol class=\"colist\">", "</ol>"),
+            (ListType::Ordered, Some(NumberingStyle::LowerAlpha)) => ("<ol type=\"a\">", "</ol>"),
+            (ListType::Ordered, Some(NumberingStyle::UpperAlpha)) => ("<ol type=\"A\">", "</ol>"),
+            (ListType::Ordered, Some(NumberingStyle::LowerRoman)) => ("<ol type=\"i\">", "</ol>"),
+            (ListType::Ordered, Some(NumberingStyle::UpperRoman)) => ("<ol type=\"I\">", "</ol>"),
+            (ListType::Ordered, _) => ("<ol>", "</ol>"),
+        };
+        self.write_nested(
+            &list.items,
+            |item| item.level as usize,
+            open,
+            close,
+            |r, item| r.write_list_item(item),
+        );
+    }
+
+    fn write_list_item(&mut self, item: &ListItem) {
+        match item.checked {
+            Some(true) => self
+                .output
+                .push_str("<input type=\"checkbox\" checked disabled> "),
+            Some(false) => self.output.push_str("<input type=\"checkbox\" disabled> "),
+            None => {}
+        }
+        self.write_compact(&item.content);
+    }
+
+    /// Write items carrying a nesting level as nested lists
+    ///
+    /// An item more than one level deeper than the previous one is nested
+    /// only one level, as is the first item.
+    fn write_nested<T>(
+        &mut self,
+        items: &[T],
+        level: impl Fn(&T) -> usize,
+        open: &str,
+        close: &str,
+        mut write_item: impl FnMut(&mut Self, &T),
+    ) {
+        self.output.push_str(open);
+        self.output.push('\n');
+        let mut depth = 0;
+        for (position, item) in items.iter().enumerate() {
+            let target = if position == 0 {
+                0
+            } else {
+                level(item).min(depth + 1)
+            };
+            if target > depth {
+                self.output.push('\n');
+                self.output.push_str(open);
+                self.output.push('\n');
+                depth = target;
+            } else if position > 0 {
+                self.output.push_str("</li>\n");
+                while depth > target {
+                    self.output.push_str(close);
+                    self.output.push_str("\n</li>\n");
+                    depth -= 1;
+                }
+            }
+            self.output.push_str("<li>");
+            write_item(self, item);
+        }
+        if !items.is_empty() {
+            self.output.push_str("</li>\n");
+        }
+        for _ in 0..depth {
+            self.output.push_str(close);
+            self.output.push_str("\n</li>\n");
+        }
+        self.output.push_str(close);
+        self.output.push('\n');
+    }
+
+    /// Write the content of a list item or cell: a lone paragraph is
+    /// written without its `<p>`
+    fn write_compact(&mut self, blocks: &[Block]) {
+        match blocks {
+            [Block::Paragraph(p)] if !matches!(p.inlines.as_slice(), [Inline::Image(_)]) => {
+                self.write_inlines(&p.inlines);
+            }
+            blocks => {
+                self.output.push('\n');
+                self.write_blocks(blocks);
+            }
+        }
+    }
+
+    fn write_table(&mut self, table: &Table) {
+        self.output.push_str("<table>\n");
+        if let Some(caption) = &table.caption {
+            self.output.push_str("<caption>");
+            self.write_inlines(caption);
+            self.output.push_str("</caption>\n");
+        }
+
+        let total: u32 = table.columns.iter().filter_map(|c| c.width).sum();
+        if total > 0 {
+            self.output.push_str("<colgroup>\n");
+            for column in &table.columns {
+                match column.width {
+                    Some(width) => self.output.push_str(&format!(
+                        "<col style=\"width: {:.2}%\">\n",
+                        width as f64 * 100.0 / total as f64
+                    )),
+                    None => self.output.push_str("<col>\n"),
+                }
+            }
+            self.output.push_str("</colgroup>\n");
+        }
+
+        let mut group = "";
+        for row in &table.rows {
+            let row_group = if row.is_header {
+                "thead"
+            } else if row.is_footer {
+                "tfoot"
+            } else {
+                "tbody"
+            };
+            if row_group != group {
+                if !group.is_empty() {
+                    self.output.push_str(&format!("</{}>\n", group));
+                }
+                self.output.push_str(&format!("<{}>\n", row_group));
+                group = row_group;
+            }
+            self.output.push_str("<tr>\n");
+            let mut column = 0;
+            for cell in &row.cells {
+                let align = cell
+                    .align
+                    .or_else(|| table.columns.get(column).and_then(|c| c.align));
+                self.write_cell(cell, row.is_header, align);
+                column += cell.colspan.max(1) as usize;
+            }
+            self.output.push_str("</tr>\n");
+        }
+        if !group.is_empty() {
+            self.output.push_str(&format!("</{}>\n", group));
+        }
+        self.output.push_str("</table>\n");
+    }
+
+    fn write_cell(&mut self, cell: &TableCell, header_row: bool, align: Option<Alignment>) {
+        let header = header_row || cell.style == Some(CellStyle::Header);
+        let tag = if header { "th" } else { "td" };
+        let mut attributes = String::new();
+        if header_row {
+            attributes.push_str(" scope=\"col\"");
+        } else if header {
+            attributes.push_str(" scope=\"row\"");
+        }
+        if cell.colspan > 1 {
+            attributes.push_str(&format!(" colspan=\"{}\"", cell.colspan));
+        }
+        if cell.rowspan > 1 {
+            attributes.push_str(&format!(" rowspan=\"{}\"", cell.rowspan));
+        }
+        match align {
+            Some(Alignment::Center) => attributes.push_str(" style=\"text-align: center\""),
+            Some(Alignment::Right) => attributes.push_str(" style=\"text-align: right\""),
+            _ => {}
+        }
+        self.output.push_str(&format!("<{}{}>", tag, attributes));
+        self.write_compact(&cell.content);
+        self.output.push_str(&format!("</{}>\n", tag));
+    }
+
+    fn write_admonition(&mut self, admonition: &Admonition) {
+        let (class, label) = match admonition.admonition_type {
+            AdmonitionType::Note => ("note", "Note"),
+            AdmonitionType::Tip => ("tip", "Tip"),
+            AdmonitionType::Important => ("important", "Important"),
+            AdmonitionType::Warning => ("warning", "Warning"),
+            AdmonitionType::Caution => ("caution", "Caution"),
+        };
+        self.output.push_str(&format!(
+            "<div class=\"admonition {}\" role=\"note\">\n<p class=\"title\">",
+            class
+        ));
+        match &admonition.title {
+            Some(title) => self.write_inlines(title),
+            None => self.output.push_str(label),
+        }
+        self.output.push_str("</p>\n");
+        self.write_blocks(&admonition.content);
+        self.output.push_str("</div>\n");
+    }
+
+    fn write_quote(&mut self, quote: &QuoteBlock) {
+        self.write_title(quote.title.as_deref());
+        self.output
+            .push_str("<figure class=\"quote\">\n<blockquote>\n");
+        self.write_blocks(&quote.blocks);
+        self.output.push_str("</blockquote>\n");
+        if quote.attribution.is_some() || quote.cite.is_some() {
+            self.output.push_str("<figcaption>\u{2014} ");
+            if let Some(attribution) = &quote.attribution {
+                self.output.push_str(&escape_html(attribution));
+            }
+            if let Some(cite) = &quote.cite {
+                if quote.attribution.is_some() {
+                    self.output.push_str(", ");
+                }
+                self.output
+                    .push_str(&format!("<cite>{}</cite>", escape_html(cite)));
+            }
+            self.output.push_str("</figcaption>\n");
+        }
+        self.output.push_str("</figure>\n");
+    }
+
+    fn write_literal(&mut self, literal: &LiteralBlock) {
+        let style = literal.style_id.as_deref();
+        // Passthrough content is HTML for this backend, unless it was
+        // written for Word
+        if style == Some("pass") && !literal.content.trim_start().starts_with("<w:") {
+            self.output.push_str(&literal.content);
+            self.output.push('\n');
+            return;
+        }
+
+        let diagram = style.filter(|style| DiagramType::from_str(style).is_ok());
+        if let Some(style) = diagram {
+            if self.write_diagram(literal, style) {
+                return;
+            }
+        }
+
+        if let Some(title) = &literal.title {
+            self.output.push_str(&format!(
+                "<figure class=\"listing\">\n<figcaption>{}</figcaption>\n",
+                escape_html(title)
+            ));
+        }
+        // Unrendered diagrams are shown as source in their language
+        match literal.language.as_deref().or(diagram) {
+            Some(language) => self.output.push_str(&format!(
+                "<pre><code class=\"language-{}\">",
+                escape_attr(language)
+            )),
+            None => self.output.push_str("<pre><code>"),
+        }
+        for (index, line) in literal.content.split('\n').enumerate() {
+            let number = index as u32 + 1;
+            if index > 0 {
+                self.output.push('\n');
+            }
+            let highlighted = literal.highlight_lines.contains(&number);
+            if highlighted {
+                self.output.push_str("<mark>");
+            }
+            self.output.push_str(&escape_html(line));
+            for callout in literal.callouts.iter().filter(|c| c.line == number) {
+                self.output
+                    .push_str(&format!(" <b class=\"conum\">{}</b>", callout.number));
+            }
+            if highlighted {
+                self.output.push_str("</mark>");
+            }
+        }
+        self.output.push_str("</code></pre>\n");
+        if literal.title.is_some() {
+            self.output.push_str("</figure>\n");
+        }
+    }
+
+    /// Write a diagram as inline SVG, returning false if it cannot be rendered
+    fn write_diagram(&mut self, literal: &LiteralBlock, style: &str) -> bool {
+        let Some(engine) = &self.writer.diagram_engine else {
+            return false;
+        };
+        let Ok(diagram_type) = DiagramType::from_str(style) else {
+            return false;
+        };
+        let svg = match engine.render_svg(&literal.content, diagram_type) {
+            Ok(svg) => String::from_utf8_lossy(&svg).into_owned(),
+            Err(_) => return false,
+        };
+        // Drop the XML declaration and doctype, which are invalid in HTML
+        let Some(start) = svg.find("<svg") else {
+            return false;
+        };
+
+        let label = literal
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("{} diagram", style));
+        self.output.push_str("<figure class=\"diagram\">\n");
+        self.output.push_str(&format!(
+            "<svg role=\"img\" aria-label=\"{}\"{}\n",
+            escape_attr(&label),
+            svg[start + 4..].trim_end()
+        ));
+        if let Some(title) = &literal.title {
+            self.output.push_str(&format!(
+                "<figcaption>{}</figcaption>\n",
+                escape_html(title)
+            ));
+        }
+        self.output.push_str("</figure>\n");
+        true
+    }
+
+    fn write_inlines(&mut self, inlines: &[Inline]) {
+        for inline in inlines {
+            self.write_inline(inline);
+        }
+    }
+
+    fn write_inline(&mut self, inline: &Inline) {
+        match inline {
+            Inline::Text(text) => self.output.push_str(&escape_html(text)),
+            Inline::Format(format_type, inner) => {
+                let tag = match format_type {
+                    FormatType::Bold => "strong",
+                    FormatType::Italic => "em",
+                    FormatType::Monospace => "code",
+                    FormatType::Highlight => "mark",
+                    FormatType::Superscript => "sup",
+                    FormatType::Subscript => "sub",
+                };
+                self.output.push_str(&format!("<{}>", tag));
+                self.write_inline(inner);
+                self.output.push_str(&format!("</{}>", tag));
+            }
+            Inline::Span(inlines) => self.write_inlines(inlines),
+            Inline::Link(link) => {
+                let href = match link.url.strip_prefix('#') {
+                    Some(anchor) => {
+                        self.href_to(&anchor_id(self.writer.style_contract.as_ref(), anchor))
+                    }
+                    None => link.url.clone(),
+                };
+                self.output
+                    .push_str(&format!("<a href=\"{}\">", escape_attr(&href)));
+                self.write_inlines(&link.text);
+                self.output.push_str("</a>");
+            }
+            Inline::Image(image) => {
+                self.output.push_str(&format!(
+                    "<img src=\"{}\" alt=\"{}\">",
+                    escape_attr(&image.src),
+                    escape_attr(image.alt.as_deref().unwrap_or_default())
+                ));
+            }
+            Inline::Break => self.output.push_str("<br>\n"),
+            Inline::Anchor(name) => {
+                let id = anchor_id(self.writer.style_contract.as_ref(), name);
+                self.output
+                    .push_str(&format!("<a id=\"{}\"></a>", escape_attr(&id)));
+            }
+            Inline::Footnote(footnote) => {
+                // A later use of a footnote id refers back to the same note
+                let earlier = footnote
+                    .id
+                    .as_ref()
+                    .and_then(|id| self.note_ids.get(id))
+                    .copied();
+                if let (Some((number, page)), true) = (earlier, footnote.content.is_empty()) {
+                    let href = self.href_on_page(page, &format!("_footnotedef_{}", number));
+                    self.output.push_str(&format!(
+                        "<sup class=\"footnote\">[<a href=\"{}\" role=\"doc-noteref\">{}</a>]</sup>",
+                        escape_attr(&href),
+                        number
+                    ));
+                    return;
+                }
+
+                self.note_count += 1;
+                let number = self.note_count;
+                if let Some(id) = &footnote.id {
+                    self.note_ids.insert(id.clone(), (number, self.page));
+                }
+                let content = self.capture(|r| r.write_inlines(&footnote.content));
+                self.notes.push((number, content));
+                self.output.push_str(&format!(
+                    "<sup class=\"footnote\">[<a id=\"_footnoteref_{0}\" href=\"#_footnotedef_{0}\" role=\"doc-noteref\">{0}</a>]</sup>",
+                    number
+                ));
+            }
+        }
+    }
+
+    /// Write the footnotes collected on the current page
+    fn write_footnotes(&mut self) {
+        if self.notes.is_empty() {
+            return;
+        }
+        self.output.push_str(
+            "<section class=\"footnotes\" role=\"doc-endnotes\" aria-label=\"Footnotes\">\n<ol>\n",
+        );
+        for (number, content) in std::mem::take(&mut self.notes) {
+            self.output.push_str(&format!(
+                "<li id=\"_footnotedef_{0}\" value=\"{0}\">{1} <a href=\"#_footnoteref_{0}\" role=\"doc-backlink\" aria-label=\"Back to reference {0}\">\u{21a9}</a></li>\n",
+                number, content
+            ));
+        }
+        self.output.push_str("</ol>\n</section>\n");
+    }
+
+    /// Link target for element `id`, on whichever page holds it
+    fn href_to(&self, id: &str) -> String {
+        match self.outline.page_of(id) {
+            Some(page) => self.href_on_page(page, id),
+            None => format!("#{}", id),
+        }
+    }
+
+    /// Link target for element `id` on `page`
+    fn href_on_page(&self, page: usize, id: &str) -> String {
+        if page == self.page {
+            format!("#{}", id)
+        } else {
+            format!("{}#{}", self.outline.chunks[page].file_name, id)
+        }
+    }
+}
+
+/// Escape text content
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escape an attribute value
+fn escape_attr(text: &str) -> String {
+    escape_html(text).replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use utf8dok_ast::{Footnote, Paragraph, TableRow};
+
+    fn text(t: &str) -> Inline {
+        Inline::Text(t.to_string())
+    }
+
+    fn paragraph(inlines: Vec<Inline>) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines,
+            style_id: None,
+            attributes: HashMap::new(),
+        })
+    }
+
+    fn item(level: u8, t: &str) -> ListItem {
+        ListItem {
+            content: vec![paragraph(vec![text(t)])],
+            level,
+            term: None,
+            checked: None,
+        }
+    }
+
+    fn body(doc: &Document) -> String {
+        let html = HtmlWriter::new().generate(doc);
+        let start = html.find("<main id=\"content\">\n").unwrap() + 20;
+        let end = html.find("</main>").unwrap();
+        html[start..end].to_string()
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(
+            escape_html("a < b && c > d"),
+            "a &lt; b &amp;&amp; c &gt; d"
+        );
+        assert_eq!(escape_attr("say \"hi\""), "say &quot;hi&quot;");
+    }
+
+    #[test]
+    fn test_nested_list() {
+        let mut doc = Document::new();
+        doc.push(Block::List(List {
+            list_type: ListType::Unordered,
+            items: vec![item(0, "a"), item(1, "b"), item(2, "c"), item(0, "d")],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+        assert_eq!(
+            body(&doc),
+            "<ul>\n<li>a\n<ul>\n<li>b\n<ul>\n<li>c</li>\n</ul>\n</li>\n</ul>\n</li>\n<li>d</li>\n</ul>\n"
+        );
+    }
+
+    #[test]
+    fn test_list_styles() {
+        let mut doc = Document::new();
+        doc.push(Block::List(List {
+            list_type: ListType::Ordered,
+            items: vec![item(0, "a")],
+            style_id: None,
+            numbering: Some(NumberingStyle::LowerRoman),
+            start: None,
+        }));
+        let mut checked = item(0, "done");
+        checked.checked = Some(true);
+        doc.push(Block::List(List {
+            list_type: ListType::Unordered,
+            items: vec![checked],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+        let html = body(&doc);
+        assert!(html.contains("<ol type=\"i\">\n<li>a</li>"));
+        assert!(html.contains(
+            "<ul class=\"checklist\">\n<li><input type=\"checkbox\" checked disabled> done</li>"
+        ));
+    }
+
+    #[test]
+    fn test_table() {
+        let cell = |t: &str| TableCell {
+            content: vec![paragraph(vec![text(t)])],
+            colspan: 1,
+            rowspan: 1,
+            align: None,
+            style: None,
+        };
+        let mut doc = Document::new();
+        doc.push(Block::Table(Table {
+            rows: vec![
+                TableRow {
+                    cells: vec![cell("Name"), cell("Value")],
+                    is_header: true,
+                    is_footer: false,
+                },
+                TableRow {
+                    cells: vec![
+                        cell("a"),
+                        TableCell {
+                            align: Some(Alignment::Right),
+                            ..cell("1")
+                        },
+                    ],
+                    is_header: false,
+                    is_footer: false,
+                },
+            ],
+            style_id: None,
+            caption: Some(vec![text("Values")]),
+            columns: vec![],
+        }));
+        assert_eq!(
+            body(&doc),
+            "<table>\n<caption>Values</caption>\n<thead>\n<tr>\n<th scope=\"col\">Name</th>\n<th scope=\"col\">Value</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>a</td>\n<td style=\"text-align: right\">1</td>\n</tr>\n</tbody>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn test_code_with_callouts_and_highlights() {
+        let mut doc = Document::new();
+        doc.push(Block::Literal(LiteralBlock {
+            content: "let x = 1;\nlet y = x < 2;".to_string(),
+            language: Some("rust".to_string()),
+            title: Some("Example".to_string()),
+            style_id: None,
+            callouts: vec![utf8dok_ast::Callout { number: 1, line: 2 }],
+            highlight_lines: vec![1],
+        }));
+        assert_eq!(
+            body(&doc),
+            "<figure class=\"listing\">\n<figcaption>Example</figcaption>\n<pre><code class=\"language-rust\"><mark>let x = 1;</mark>\nlet y = x &lt; 2; <b class=\"conum\">1</b></code></pre>\n</figure>\n"
+        );
+    }
+
+    #[test]
+    fn test_footnotes() {
+        let note = |id: Option<&str>, content: Vec<Inline>| {
+            Inline::Footnote(Footnote {
+                id: id.map(str::to_string),
+                content,
+            })
+        };
+        let mut doc = Document::new();
+        doc.push(paragraph(vec![
+            text("A"),
+            note(None, vec![text("First.")]),
+            text(" B"),
+            note(Some("src"), vec![text("Shared.")]),
+            text(" C"),
+            note(Some("src"), vec![]),
+        ]));
+        let html = body(&doc);
+        assert!(html.contains(
+            "<a id=\"_footnoteref_1\" href=\"#_footnotedef_1\" role=\"doc-noteref\">1</a>"
+        ));
+        assert!(html.contains("C<sup class=\"footnote\">[<a href=\"#_footnotedef_2\" role=\"doc-noteref\">2</a>]</sup>"));
+        assert!(html
+            .contains("<li id=\"_footnotedef_2\" value=\"2\">Shared. <a href=\"#_footnoteref_2\""));
+        assert!(!html.contains("_footnotedef_3"));
+    }
+
+    #[test]
+    fn test_passthrough() {
+        let literal = |content: &str| {
+            Block::Literal(LiteralBlock {
+                content: content.to_string(),
+                language: None,
+                title: None,
+                style_id: Some("pass".to_string()),
+                callouts: vec![],
+                highlight_lines: vec![],
+            })
+        };
+        let mut doc = Document::new();
+        doc.push(literal("<video src=\"demo.mp4\"></video>"));
+        doc.push(literal("<w:p/>"));
+        assert_eq!(
+            body(&doc),
+            "<video src=\"demo.mp4\"></video>\n<pre><code>&lt;w:p/&gt;</code></pre>\n"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! HTML5 Backend Specification Tests
+//!
+//! These tests render parsed AsciiDoc and check the page structure, the
+//! TOC and anchors, inline diagrams, theming and chunked output.
+
+use utf8dok_core::parse;
+use utf8dok_diagrams::{
+    DiagramEngine, DiagramRenderer, DiagramType, OutputFormat, RenderError, RenderOptions,
+    RenderResult,
+};
+use utf8dok_html::{render_html, HtmlConfig, HtmlWriter};
+use utf8dok_ooxml::{AnchorMapping, AnchorType, StyleContract};
+
+const DOCUMENT: &str = r#"= User Guide
+:toclevels: 2
+
+Welcome to the guide.footnote:[Preview release.]
+
+[[install]]
+== Installation
+
+Run the installer. See <<usage,the usage section>>.
+
+=== Requirements
+
+* Linux
+** x86_64
+* macOS
+
+==== Deep Section
+
+Not in the TOC.
+
+[mermaid]
+----
+graph TD; A-->B
+----
+
+== Usage
+
+[[usage]]Use the `run` command, as in <<install>>.footnote:[Once per machine.]
+
+NOTE: Mind the *details*.
+"#;
+
+/// Renders Mermaid diagrams as a fixed SVG document
+struct FakeRenderer;
+
+impl DiagramRenderer for FakeRenderer {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn supports(&self, diagram_type: DiagramType) -> bool {
+        diagram_type == DiagramType::Mermaid
+    }
+
+    fn render(
+        &self,
+        source: &str,
+        _diagram_type: DiagramType,
+        format: OutputFormat,
+        _options: &RenderOptions,
+    ) -> RenderResult<Vec<u8>> {
+        if format != OutputFormat::Svg || source.contains("broken") {
+            return Err(RenderError::Unavailable("not supported".to_string()));
+        }
+        Ok(b"<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\"><text>A</text></svg>\n".to_vec())
+    }
+}
+
+fn fake_engine() -> DiagramEngine {
+    let mut engine = DiagramEngine::empty();
+    engine.add_renderer(Box::new(FakeRenderer));
+    engine
+}
+
+/// The `<main>` element of a page
+fn main_content(html: &str) -> &str {
+    let start = html.find("<main").unwrap();
+    let end = html.find("</main>").unwrap();
+    &html[start..end]
+}
+
+#[test]
+fn test_standalone_page() {
+    let html = render_html(&parse(DOCUMENT).unwrap());
+    assert!(
+        html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">")
+    );
+    assert!(html.contains("<title>User Guide</title>"));
+    assert!(html.contains("<style>\n:root {"));
+    assert!(html.contains("<header>\n<h1>User Guide</h1>\n</header>"));
+    assert!(html.contains("<a class=\"skip-link\" href=\"#content\">"));
+    assert!(html.ends_with("</body>\n</html>\n"));
+}
+
+#[test]
+fn test_sections_and_headings() {
+    let html = render_html(&parse(DOCUMENT).unwrap());
+    let main = main_content(&html);
+    assert!(main
+        .contains("<section aria-labelledby=\"install\">\n<h2 id=\"install\">Installation</h2>"));
+    assert!(main.contains(
+        "<section aria-labelledby=\"_requirements\">\n<h3 id=\"_requirements\">Requirements</h3>"
+    ));
+    assert!(main.contains("<h4 id=\"_deep_section\">Deep Section</h4>"));
+    // The anchor paragraph became the heading id
+    assert!(!main.contains("<a id=\"install\"></a>"));
+    // Sections close before the next one at the same level
+    let usage = main.find("<section aria-labelledby=\"_usage\">").unwrap();
+    assert!(main[..usage].ends_with("</section>\n</section>\n</section>\n"));
+}
+
+#[test]
+fn test_toc() {
+    let html = render_html(&parse(DOCUMENT).unwrap());
+    let start = html.find("<nav class=\"toc\"").unwrap();
+    let end = start + html[start..].find("</nav>").unwrap();
+    assert_eq!(
+        &html[start..end],
+        "<nav class=\"toc\" aria-labelledby=\"toc-title\">\n\
+         <h2 id=\"toc-title\">Table of Contents</h2>\n\
+         <ol>\n\
+         <li><a href=\"#install\">Installation</a>\n\
+         <ol>\n\
+         <li><a href=\"#_requirements\">Requirements</a></li>\n\
+         </ol>\n\
+         </li>\n\
+         <li><a href=\"#_usage\">Usage</a></li>\n\
+         </ol>\n"
+    );
+
+    let writer = HtmlWriter::with_config(HtmlConfig {
+        toc: false,
+        ..HtmlConfig::default()
+    });
+    assert!(!writer
+        .generate(&parse(DOCUMENT).unwrap())
+        .contains("class=\"toc\""));
+}
+
+#[test]
+fn test_cross_references() {
+    let html = render_html(&parse(DOCUMENT).unwrap());
+    assert!(html.contains("See <a href=\"#usage\">the usage section</a>."));
+    assert!(html.contains("<a id=\"usage\"></a>Use the <code>run</code> command"));
+    assert!(html.contains("<a href=\"#install\">install</a>"));
+}
+
+#[test]
+fn test_anchors_use_contract_bookmarks() {
+    let mut contract = StyleContract::default();
+    contract.add_anchor(
+        "_Toc192197374",
+        AnchorMapping {
+            semantic_id: "install".to_string(),
+            anchor_type: AnchorType::Toc,
+            target_heading: Some("Installation".to_string()),
+            original_bookmark: Some("_Toc192197374".to_string()),
+        },
+    );
+    let mut writer = HtmlWriter::new();
+    writer.set_style_contract(contract);
+    let html = writer.generate(&parse(DOCUMENT).unwrap());
+
+    // Same names as the DOCX bookmarks and hyperlink anchors
+    assert!(html.contains("<h2 id=\"_Toc192197374\">Installation</h2>"));
+    assert!(html.contains("<a href=\"#_Toc192197374\">install</a>"));
+    assert!(html.contains("<li><a href=\"#_Toc192197374\">Installation</a>"));
+    assert!(!html.contains("\"#install\""));
+
+    // Links across chunked pages resolve the bookmark name as well
+    let pages = writer.generate_chunked(&parse(DOCUMENT).unwrap());
+    assert_eq!(pages[1].file_name, "toc192197374.html");
+    assert!(pages[2]
+        .html
+        .contains("<a href=\"toc192197374.html#_Toc192197374\">install</a>"));
+}
+
+#[test]
+fn test_theme_from_style_contract() {
+    let mut contract = StyleContract::default();
+    contract.theme.body_font = Some("Georgia".to_string());
+    contract.theme.accent_color = Some("C00000".to_string());
+    let mut writer = HtmlWriter::new();
+    writer.set_style_contract(contract);
+    let html = writer.generate(&parse(DOCUMENT).unwrap());
+    assert!(html.contains("--body-font: \"Georgia\", sans-serif;"));
+    assert!(html.contains("--accent-color: #c00000;"));
+
+    let writer = HtmlWriter::with_config(HtmlConfig {
+        stylesheet: false,
+        ..HtmlConfig::default()
+    });
+    assert!(!writer
+        .generate(&parse(DOCUMENT).unwrap())
+        .contains("<style>"));
+}
+
+#[test]
+fn test_inline_svg_diagrams() {
+    let mut writer = HtmlWriter::new();
+    writer.set_diagram_engine(fake_engine());
+    let html = writer.generate(&parse(DOCUMENT).unwrap());
+    assert!(html.contains(
+        "<figure class=\"diagram\">\n<svg role=\"img\" aria-label=\"mermaid diagram\" xmlns=\"http://www.w3.org/2000/svg\"><text>A</text></svg>\n</figure>"
+    ));
+    assert!(!html.contains("<?xml"));
+    assert!(!html.contains("graph TD"));
+}
+
+#[test]
+fn test_unrendered_diagrams_show_source() {
+    // Without an engine
+    let html = render_html(&parse(DOCUMENT).unwrap());
+    assert!(html.contains("<pre><code class=\"language-mermaid\">graph TD; A--&gt;B</code></pre>"));
+
+    // When rendering fails
+    let mut writer = HtmlWriter::new();
+    writer.set_diagram_engine(fake_engine());
+    let doc = parse(".Flow\n[mermaid]\n----\nbroken\n----").unwrap();
+    let html = writer.generate(&doc);
+    assert!(html.contains(
+        "<figcaption>Flow</figcaption>\n<pre><code class=\"language-mermaid\">broken</code></pre>"
+    ));
+}
+
+#[test]
+fn test_image_figure_caption() {
+    let doc = parse(".Architecture\nimage::arch.png[Overview]").unwrap();
+    let html = render_html(&doc);
+    assert!(html.contains(
+        "<figure class=\"image\">\n<img src=\"arch.png\" alt=\"Overview\">\n<figcaption>Architecture</figcaption>\n</figure>"
+    ));
+}
+
+#[test]
+fn test_chunked_pages() {
+    let doc = parse(DOCUMENT).unwrap();
+    let pages = HtmlWriter::new().generate_chunked(&doc);
+    let names: Vec<(&str, &str)> = pages
+        .iter()
+        .map(|p| (p.file_name.as_str(), p.title.as_str()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("index.html", "User Guide"),
+            ("install.html", "Installation \u{2013} User Guide"),
+            ("usage.html", "Usage \u{2013} User Guide"),
+        ]
+    );
+
+    // The preamble stays on the index, each section gets its own page
+    assert!(main_content(&pages[0].html).contains("Welcome to the guide."));
+    assert!(!main_content(&pages[0].html).contains("<section aria-labelledby"));
+    assert!(main_content(&pages[1].html).contains("<h3 id=\"_requirements\">"));
+    assert!(!main_content(&pages[1].html).contains("_usage"));
+
+    // Only the index has the document header; every page has the TOC and pager
+    assert!(pages[0].html.contains("<header>"));
+    assert!(!pages[1].html.contains("<header>"));
+    assert!(pages[2]
+        .html
+        .contains("<li><a href=\"install.html#install\">Installation</a>"));
+    assert!(pages[1]
+        .html
+        .contains("<a href=\"index.html\" rel=\"prev\">\u{2190} User Guide</a>"));
+    assert!(pages[1]
+        .html
+        .contains("<a href=\"usage.html\" rel=\"next\">Usage \u{2192}</a>"));
+}
+
+#[test]
+fn test_chunked_links_and_footnotes() {
+    let doc = parse(DOCUMENT).unwrap();
+    let pages = HtmlWriter::new().generate_chunked(&doc);
+
+    // Links to anchors on other pages name the page
+    assert!(pages[1]
+        .html
+        .contains("See <a href=\"usage.html#usage\">the usage section</a>."));
+    assert!(pages[2]
+        .html
+        .contains("<a href=\"install.html#install\">install</a>"));
+
+    // Footnotes are numbered across pages and listed where they are used
+    assert!(pages[0]
+        .html
+        .contains("<li id=\"_footnotedef_1\" value=\"1\">Preview release."));
+    assert!(pages[2]
+        .html
+        .contains("<li id=\"_footnotedef_2\" value=\"2\">Once per machine."));
+    assert!(!pages[1].html.contains("role=\"doc-endnotes\""));
+}
+
+#[test]
+fn test_chunked_document_without_sections() {
+    let doc = parse("Just a paragraph.").unwrap();
+    let pages = HtmlWriter::new().generate_chunked(&doc);
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].file_name, "index.html");
+    assert_eq!(pages[0].title, "Untitled");
+    assert!(!pages[0].html.contains("class=\"pager\""));
+    assert!(!pages[0].html.contains("class=\"toc\""));
+}
@@ -3,6 +3,7 @@
 //! Defines the configuration structures for enterprise customization.
 
 use serde::{Deserialize, Serialize};
+use utf8dok_core::FormatConfig;
 
 /// Rule severity levels for compliance rules
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -46,6 +47,8 @@ pub struct Settings {
     pub plugins: PluginSettings,
     /// Workspace settings
     pub workspace: WorkspaceSettings,
+    /// Source formatter settings
+    pub format: FormatConfig,
 }
 
 impl Settings {
@@ -119,6 +119,20 @@ entry_points = ["index.adoc", "README.adoc"]
         assert_eq!(settings.workspace.root, Some("docs/".to_string()));
         assert_eq!(settings.workspace.entry_points.len(), 2);
     }
+
+    /// Test 7b: Formatter configuration parsing
+    #[test]
+    fn test_format_config_parsing() {
+        let toml = r#"
+[format]
+sentence_per_line = false
+"#;
+
+        let settings: Settings = toml::from_str(toml).unwrap();
+
+        assert!(!settings.format.sentence_per_line);
+        assert!(settings.format.align_tables);
+    }
 }
 
 // ==================== LOADER TESTS ====================
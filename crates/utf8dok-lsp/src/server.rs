@@ -4,6 +4,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::compliance::ComplianceEngine;
 use crate::config::Settings;
@@ -17,29 +18,36 @@ use serde_json::Value;
 use tokio::sync::RwLock;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::Diagnostic;
+use tower_lsp::lsp_types::TextEdit as LspTextEdit;
 use tower_lsp::lsp_types::{
     CodeActionOrCommand, CodeActionParams, CodeActionProviderCapability, CompletionOptions,
     CompletionParams, CompletionResponse, DiagnosticOptions, DiagnosticRelatedInformation,
     DiagnosticServerCapabilities, DiagnosticSeverity, DidChangeConfigurationParams,
     DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, DocumentSymbolParams, DocumentSymbolResponse, FoldingRange,
-    FoldingRangeParams, FoldingRangeProviderCapability, InitializeParams, InitializeResult,
-    InitializedParams, Location, MessageType, NumberOrString, OneOf, Position,
-    PrepareRenameResponse, Range, RenameParams, SelectionRange, SelectionRangeParams,
-    SelectionRangeProviderCapability, SemanticToken, SemanticTokens, SemanticTokensFullOptions,
-    SemanticTokensLegend, SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
-    SemanticTokensServerCapabilities, ServerCapabilities, ServerInfo, SymbolInformation,
-    TextDocumentSyncCapability, TextDocumentSyncKind, Url, WorkDoneProgressOptions, WorkspaceEdit,
-    WorkspaceSymbolParams,
+    DidSaveTextDocumentParams, DocumentFormattingParams, DocumentSymbolParams,
+    DocumentSymbolResponse, FoldingRange, FoldingRangeParams, FoldingRangeProviderCapability,
+    InitializeParams, InitializeResult, InitializedParams, Location, MessageType, NumberOrString,
+    OneOf, Position, PrepareRenameResponse, Range, RenameParams, SelectionRange,
+    SelectionRangeParams, SelectionRangeProviderCapability, SemanticToken, SemanticTokens,
+    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, SemanticTokensParams,
+    SemanticTokensResult, SemanticTokensServerCapabilities, ServerCapabilities, ServerInfo,
+    SymbolInformation, TextDocumentContentChangeEvent, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url, WorkDoneProgressOptions, WorkspaceEdit, WorkspaceSymbolParams,
 };
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 use tracing::{debug, info, warn};
 
+use utf8dok_ast::Document;
 use utf8dok_core::diagnostics::{Diagnostic as Utf8dokDiagnostic, Severity};
-use utf8dok_core::parse;
+use utf8dok_core::{IncrementalParse, ParserConfig, TextEdit};
 use utf8dok_validate::ValidationEngine;
 
+/// Quiet period after a change before the document is validated, so a
+/// burst of keystrokes is validated once
+const VALIDATION_DELAY: Duration = Duration::from_millis(250);
+
 /// LSP Backend state
+#[derive(Clone)]
 pub struct Backend {
     /// LSP client for sending notifications
     client: Client,
@@ -47,6 +55,11 @@ pub struct Backend {
     validation_engine: Arc<RwLock<ValidationEngine>>,
     /// Document store for open documents
     documents: Arc<RwLock<HashMap<Url, String>>>,
+    /// Parses of open documents, updated incrementally on change
+    parses: Arc<RwLock<HashMap<Url, IncrementalParse>>>,
+    /// Number of changes to each document, so that only the validation
+    /// scheduled by the latest one runs
+    changes: Arc<RwLock<HashMap<Url, u64>>>,
     /// Workspace graph for cross-file intelligence
     workspace_graph: Arc<RwLock<WorkspaceGraph>>,
     /// Configuration settings
@@ -71,6 +84,8 @@ impl Backend {
             client,
             validation_engine: Arc::new(RwLock::new(ValidationEngine::with_defaults())),
             documents: Arc::new(RwLock::new(HashMap::new())),
+            parses: Arc::new(RwLock::new(HashMap::new())),
+            changes: Arc::new(RwLock::new(HashMap::new())),
             workspace_graph: Arc::new(RwLock::new(WorkspaceGraph::new())),
             settings: Arc::new(RwLock::new(settings)),
             compliance_engine: Arc::new(RwLock::new(compliance_engine)),
@@ -110,6 +125,37 @@ impl Backend {
     async fn remove_document(&self, uri: &Url) {
         let mut docs = self.documents.write().await;
         docs.remove(uri);
+        let mut parses = self.parses.write().await;
+        parses.remove(uri);
+        let mut changes = self.changes.write().await;
+        changes.remove(uri);
+    }
+
+    /// Apply content changes to the parse of a document, returning its new
+    /// text
+    ///
+    /// Ranged changes only reparse the blocks they touch.
+    async fn apply_changes(
+        &self,
+        uri: &Url,
+        changes: Vec<TextDocumentContentChangeEvent>,
+    ) -> String {
+        let previous = self.get_document(uri).await.unwrap_or_default();
+        let mut parses = self.parses.write().await;
+        let parse = parses
+            .entry(uri.clone())
+            .or_insert_with(|| IncrementalParse::new(previous, ParserConfig::default()));
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let start = self.position_to_offset(range.start, parse.text());
+                    let end = self.position_to_offset(range.end, parse.text());
+                    parse.apply(&TextEdit::new(start..end.max(start), change.text));
+                }
+                None => *parse = IncrementalParse::new(change.text, ParserConfig::default()),
+            }
+        }
+        parse.text().to_string()
     }
 
     /// Update the workspace graph with document content
@@ -124,43 +170,57 @@ impl Backend {
         graph.remove_document(uri.as_str());
     }
 
+    /// Validate a document once no further change has come in for
+    /// [`VALIDATION_DELAY`]
+    async fn schedule_validation(&self, uri: Url) {
+        let change = {
+            let mut changes = self.changes.write().await;
+            let count = changes.entry(uri.clone()).or_default();
+            *count += 1;
+            *count
+        };
+        let backend = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(VALIDATION_DELAY).await;
+            // A later change has scheduled its own validation
+            if backend.changes.read().await.get(&uri) != Some(&change) {
+                return;
+            }
+            if let Some(text) = backend.get_document(&uri).await {
+                backend.validate(uri, text).await;
+            }
+        });
+    }
+
     /// Validate a document and publish diagnostics
     async fn validate(&self, uri: Url, text: String) {
         debug!("Validating document: {}", uri);
 
-        // Parse the AsciiDoc content
-        let ast = match parse(&text) {
-            Ok(doc) => doc,
-            Err(e) => {
-                warn!("Failed to parse document {}: {}", uri, e);
-                // Publish a parse error diagnostic
-                let diagnostic = Diagnostic {
-                    range: Range {
-                        start: Position::new(0, 0),
-                        end: Position::new(0, 0),
-                    },
-                    severity: Some(DiagnosticSeverity::ERROR),
-                    code: Some(NumberOrString::String("PARSE001".to_string())),
-                    source: Some("utf8dok".to_string()),
-                    message: format!("Parse error: {}", e),
-                    ..Default::default()
-                };
-                self.client
-                    .publish_diagnostics(uri, vec![diagnostic], None)
-                    .await;
-                return;
-            }
-        };
+        // Validate the document of its incremental parse (always a
+        // best-effort document) in place, parsing afresh if the text was
+        // replaced
+        let mut parses = self.parses.write().await;
+        let parse = parses
+            .entry(uri.clone())
+            .or_insert_with(|| IncrementalParse::new(text.as_str(), ParserConfig::default()));
+        if parse.text() != text {
+            *parse = IncrementalParse::new(text.as_str(), ParserConfig::default());
+        }
+        let parses = parses.downgrade();
+        let parse = &parses[&uri];
 
         // Run validation
         let engine = self.validation_engine.read().await;
-        let utf8dok_diagnostics = engine.validate(&ast);
+        let utf8dok_diagnostics = engine.validate(parse.document());
 
-        // Convert to LSP diagnostics
-        let mut lsp_diagnostics: Vec<Diagnostic> = utf8dok_diagnostics
-            .into_iter()
-            .map(|d| self.convert_diagnostic(&d, &text))
+        // Convert parser and validator diagnostics to LSP diagnostics
+        let mut lsp_diagnostics: Vec<Diagnostic> = parse
+            .diagnostics()
+            .iter()
+            .chain(&utf8dok_diagnostics)
+            .map(|d| self.convert_diagnostic(d, parse.document(), &text))
             .collect();
+        drop(parses);
 
         // Add writing quality diagnostics (Phase 17)
         let quality = self.quality_plugin.read().await;
@@ -185,7 +245,12 @@ impl Backend {
     }
 
     /// Convert utf8dok diagnostic to LSP diagnostic
-    fn convert_diagnostic(&self, diag: &Utf8dokDiagnostic, source_text: &str) -> Diagnostic {
+    fn convert_diagnostic(
+        &self,
+        diag: &Utf8dokDiagnostic,
+        doc: &Document,
+        source_text: &str,
+    ) -> Diagnostic {
         // Convert severity
         let severity = match diag.severity {
             Severity::Error | Severity::Fatal => Some(DiagnosticSeverity::ERROR),
@@ -196,7 +261,10 @@ impl Backend {
 
         // Convert span to range
         let range = if let Some(span) = &diag.span {
-            if let (Some(line), Some(col)) = (span.line, span.column) {
+            if span.start < span.end && diag.file.is_none() {
+                // Byte offsets into this document cover the whole node
+                self.offset_to_range(span.start, span.end, source_text)
+            } else if let (Some(line), Some(col)) = (span.line, span.column) {
                 // Use line/col from span (1-indexed to 0-indexed)
                 Range {
                     start: Position::new(
@@ -211,7 +279,7 @@ impl Backend {
             }
         } else {
             // No span info - try to extract from notes (block index)
-            self.extract_range_from_notes(diag, source_text)
+            self.extract_range_from_notes(diag, doc, source_text)
         };
 
         // Build code
@@ -285,14 +353,38 @@ impl Backend {
         }
     }
 
+    /// Convert an LSP position (UTF-16 columns) to a byte offset
+    ///
+    /// Positions past the end of a line or of the text are clamped.
+    fn position_to_offset(&self, position: Position, text: &str) -> usize {
+        let line_start = text
+            .split_inclusive('\n')
+            .take(position.line as usize)
+            .map(str::len)
+            .sum::<usize>();
+        let line = text[line_start..].split('\n').next().unwrap_or_default();
+        let mut units = 0;
+        for (offset, ch) in line.char_indices() {
+            if units >= position.character as usize {
+                return line_start + offset;
+            }
+            units += ch.len_utf16();
+        }
+        line_start + line.len()
+    }
+
     /// Try to extract range from diagnostic notes (e.g., "Found at block index X")
-    fn extract_range_from_notes(&self, diag: &Utf8dokDiagnostic, source_text: &str) -> Range {
+    fn extract_range_from_notes(
+        &self,
+        diag: &Utf8dokDiagnostic,
+        doc: &Document,
+        source_text: &str,
+    ) -> Range {
         // Look for "block index N" pattern in notes
         for note in &diag.notes {
             if let Some(idx_str) = note.strip_prefix("Found at block index ") {
                 if let Ok(block_idx) = idx_str.trim().parse::<usize>() {
-                    // Try to find the Nth block-like element (heading markers)
-                    return self.find_block_range(block_idx, source_text);
+                    return self.find_block_range(block_idx, doc, source_text);
                 }
             }
         }
@@ -304,42 +396,18 @@ impl Backend {
         }
     }
 
-    /// Find the range of a block by index (simplified heuristic)
-    fn find_block_range(&self, block_idx: usize, text: &str) -> Range {
-        let mut current_block = 0usize;
-
-        for (line_num, line) in text.lines().enumerate() {
-            let trimmed = line.trim();
-            // Check for block markers (headings, paragraphs after blank lines)
-            let is_heading = trimmed.starts_with('=');
-            let is_block_start = is_heading || (!trimmed.is_empty() && line_num > 0);
-
-            if is_block_start && !trimmed.is_empty() {
-                if current_block == block_idx {
-                    let block_line = line_num as u32;
-                    return Range {
-                        start: Position::new(block_line, 0),
-                        end: Position::new(block_line, line.len() as u32),
-                    };
-                }
-                // Only count non-empty content as blocks
-                if is_heading
-                    || (line_num > 0
-                        && text
-                            .lines()
-                            .nth(line_num.saturating_sub(1))
-                            .map(|l| l.trim().is_empty())
-                            .unwrap_or(false))
-                {
-                    current_block += 1;
-                }
+    /// Find the range of a top-level block from its source span
+    ///
+    /// Blocks from included files have no range in this document.
+    fn find_block_range(&self, block_idx: usize, doc: &Document, text: &str) -> Range {
+        match doc.source_map.block_span(block_idx) {
+            Some(span) if doc.source_map.block_file(block_idx).is_none() => {
+                self.offset_to_range(span.start, span.end, text)
             }
-        }
-
-        // Default if block not found
-        Range {
-            start: Position::new(0, 0),
-            end: Position::new(0, 0),
+            _ => Range {
+                start: Position::new(0, 0),
+                end: Position::new(0, 0),
+            },
         }
     }
 }
@@ -352,7 +420,7 @@ impl LanguageServer for Backend {
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 // Diagnostics
                 diagnostic_provider: Some(DiagnosticServerCapabilities::Options(
@@ -404,6 +472,8 @@ impl LanguageServer for Backend {
                 ),
                 // Workspace symbols (Phase 11)
                 workspace_symbol_provider: Some(OneOf::Left(true)),
+                // Source formatting
+                document_formatting_provider: Some(OneOf::Left(true)),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
@@ -436,14 +506,12 @@ impl LanguageServer for Backend {
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         debug!("Document changed: {}", params.text_document.uri);
-        // Since we use FULL sync, the entire content is in the first change
-        if let Some(change) = params.content_changes.into_iter().next() {
-            let uri = params.text_document.uri.clone();
-            let text = change.text.clone();
-            self.store_document(uri.clone(), text.clone()).await;
-            self.update_workspace_graph(&uri, &text).await;
-            self.validate(uri, text).await;
-        }
+        // With incremental sync, each change replaces a range of the text
+        let uri = params.text_document.uri.clone();
+        let text = self.apply_changes(&uri, params.content_changes).await;
+        self.store_document(uri.clone(), text.clone()).await;
+        self.update_workspace_graph(&uri, &text).await;
+        self.schedule_validation(uri).await;
     }
 
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
@@ -522,6 +590,43 @@ impl LanguageServer for Backend {
         Ok(Some(ranges))
     }
 
+    async fn formatting(
+        &self,
+        params: DocumentFormattingParams,
+    ) -> Result<Option<Vec<LspTextEdit>>> {
+        let uri = params.text_document.uri;
+        debug!("Formatting request for: {}", uri);
+
+        // Get document from store
+        let text = match self.get_document(&uri).await {
+            Some(doc) => doc,
+            None => {
+                warn!("Document not found for formatting: {}", uri);
+                return Ok(None);
+            }
+        };
+
+        let config = self.settings.read().await.format.clone();
+        let formatted = utf8dok_core::format_with_config(&text, &config);
+        if formatted == text {
+            return Ok(Some(Vec::new()));
+        }
+
+        // Replace the whole document
+        let last_line = text.rsplit('\n').next().unwrap_or_default();
+        let end = Position::new(
+            text.matches('\n').count() as u32,
+            last_line.encode_utf16().count() as u32,
+        );
+        Ok(Some(vec![LspTextEdit {
+            range: Range {
+                start: Position::new(0, 0),
+                end,
+            },
+            new_text: formatted,
+        }]))
+    }
+
     async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
@@ -855,6 +960,7 @@ pub async fn run_server() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utf8dok_core::{parse, parse_with_diagnostics};
 
     #[test]
     fn test_severity_conversion() {
@@ -911,12 +1017,14 @@ mod tests {
             text: vec![Inline::Text("Title".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
         doc.blocks.push(Block::Heading(Heading {
             level: 4, // Skip levels 2 and 3
             text: vec![Inline::Text("Deep".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
 
         let engine = ValidationEngine::with_defaults();
@@ -929,6 +1037,106 @@ mod tests {
             .any(|d| d.code.as_deref() == Some("DOC101")));
     }
 
+    #[tokio::test]
+    async fn test_diagnostic_range_from_source_span() {
+        let (service, _socket) = LspService::new(Backend::new);
+        let backend = service.inner();
+
+        let text = "= Title\n\nIntro text\nover two lines.\n\n==== Deep";
+        let doc = parse(text).unwrap();
+        let engine = ValidationEngine::with_defaults();
+        let diag = engine
+            .validate(&doc)
+            .into_iter()
+            .find(|d| d.code.as_deref() == Some("DOC101"))
+            .unwrap();
+
+        let lsp = backend.convert_diagnostic(&diag, &doc, text);
+        assert_eq!(lsp.range.start, Position::new(5, 0));
+        assert_eq!(lsp.range.end, Position::new(5, 9));
+
+        // The paragraph spans both of its lines
+        let range = backend.find_block_range(0, &doc, text);
+        assert_eq!(range.start, Position::new(2, 0));
+        assert_eq!(range.end, Position::new(3, 15));
+    }
+
+    #[tokio::test]
+    async fn test_parser_diagnostic_range() {
+        let (service, _socket) = LspService::new(Backend::new);
+        let backend = service.inner();
+
+        let text = "Intro.\n\n----\nnever closed";
+        let (doc, diagnostics) = parse_with_diagnostics(text, ParserConfig::default());
+        let diag = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("PARSE101"))
+            .unwrap();
+
+        let lsp = backend.convert_diagnostic(diag, &doc, text);
+        assert_eq!(lsp.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(lsp.range.start, Position::new(2, 0));
+        assert_eq!(lsp.range.end, Position::new(2, 4));
+    }
+
+    #[tokio::test]
+    async fn test_ranged_changes_update_the_parse() {
+        let (service, _socket) = LspService::new(Backend::new);
+        let backend = service.inner();
+        let uri = Url::parse("file:///doc.adoc").unwrap();
+        backend
+            .store_document(uri.clone(), "= Doc\n\nCafé 😀 one.\n\nTwo.\n".to_string())
+            .await;
+
+        // "one" starts at UTF-16 column 8 (the emoji takes two units)
+        let change = |line, start, end, text: &str| TextDocumentContentChangeEvent {
+            range: Some(Range {
+                start: Position::new(line, start),
+                end: Position::new(line, end),
+            }),
+            range_length: None,
+            text: text.to_string(),
+        };
+        let text = backend
+            .apply_changes(&uri, vec![change(2, 8, 11, "1"), change(4, 0, 3, "2")])
+            .await;
+        assert_eq!(text, "= Doc\n\nCafé 😀 1.\n\n2.\n");
+
+        let parses = backend.parses.read().await;
+        let parse = &parses[&uri];
+        assert_eq!(
+            parse.document(),
+            &parse_with_diagnostics(&text, ParserConfig::default()).0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_formatting_replaces_document() {
+        let (service, _socket) = LspService::new(Backend::new);
+        let backend = service.inner();
+        let uri = Url::parse("file:///doc.adoc").unwrap();
+        let params = |uri: &Url| DocumentFormattingParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+            options: Default::default(),
+            work_done_progress_params: Default::default(),
+        };
+
+        backend
+            .store_document(uri.clone(), "= Doc\n\nOne. Two 😀.  ".to_string())
+            .await;
+        let edits = backend.formatting(params(&uri)).await.unwrap().unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].range.end, Position::new(2, 14));
+        assert_eq!(edits[0].new_text, "= Doc\n\nOne.\nTwo 😀.\n");
+
+        // Formatted documents need no edits
+        backend
+            .store_document(uri.clone(), edits[0].new_text.clone())
+            .await;
+        let edits = backend.formatting(params(&uri)).await.unwrap().unwrap();
+        assert!(edits.is_empty());
+    }
+
     #[test]
     fn test_diagnostic_to_lsp_conversion() {
         use utf8dok_core::diagnostics::Span;
@@ -0,0 +1,54 @@
+//! Error types for ODF operations
+
+use thiserror::Error;
+
+/// Errors that can occur during ODF operations
+#[derive(Error, Debug)]
+pub enum OdfError {
+    /// Error reading or writing the ZIP package
+    #[error("Package error: {0}")]
+    Package(#[from] zip::result::ZipError),
+
+    /// Error reading or writing files
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Error parsing XML content
+    #[error("XML parsing error: {0}")]
+    Xml(#[from] quick_xml::Error),
+
+    /// Required file not found in the package
+    #[error("Required file not found: {0}")]
+    MissingFile(String),
+
+    /// The package is not an OpenDocument text document or template
+    #[error("Not an OpenDocument text package: {0}")]
+    NotText(String),
+
+    /// Error reading or writing the utf8dok manifest
+    #[error("Manifest error: {0}")]
+    Manifest(#[from] utf8dok_ooxml::OoxmlError),
+}
+
+/// Result type for ODF operations
+pub type Result<T> = std::result::Result<T, OdfError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_file_error_message() {
+        let err = OdfError::MissingFile("content.xml".to_string());
+        assert_eq!(err.to_string(), "Required file not found: content.xml");
+    }
+
+    #[test]
+    fn test_not_text_error_message() {
+        let err = OdfError::NotText("application/vnd.oasis.opendocument.spreadsheet".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Not an OpenDocument text package: application/vnd.oasis.opendocument.spreadsheet"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! ODT to AsciiDoc extraction
+//!
+//! Like the DOCX extractor, this returns the AsciiDoc source embedded by a
+//! previous render when the package has one, and otherwise converts the
+//! document body.
+
+use std::path::Path;
+
+use utf8dok_ast::DocumentMeta;
+use utf8dok_core::generator::{generate_with_config, GeneratorConfig};
+use utf8dok_ooxml::SourceOrigin;
+
+use crate::error::Result;
+use crate::package::OdfPackage;
+use crate::reader::read_document;
+
+/// Result of extracting an ODT document
+#[derive(Debug)]
+pub struct ExtractedOdt {
+    /// The AsciiDoc content
+    pub asciidoc: String,
+    /// Document metadata read from the document (empty for embedded source)
+    pub metadata: DocumentMeta,
+    /// Indicates where the AsciiDoc content came from
+    pub source_origin: SourceOrigin,
+}
+
+/// Extracts ODT documents to AsciiDoc
+pub struct OdtExtractor {
+    /// Include document attributes header
+    pub include_header: bool,
+    /// Force converting content.xml even if embedded source exists
+    pub force_parse: bool,
+}
+
+impl Default for OdtExtractor {
+    fn default() -> Self {
+        Self {
+            include_header: true,
+            force_parse: false,
+        }
+    }
+}
+
+impl OdtExtractor {
+    /// Create a new extractor with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether to force converting content.xml even if embedded source exists
+    pub fn with_force_parse(mut self, force: bool) -> Self {
+        self.force_parse = force;
+        self
+    }
+
+    /// Extract a document from a file path
+    pub fn extract_file<P: AsRef<Path>>(&self, path: P) -> Result<ExtractedOdt> {
+        let package = OdfPackage::open(path)?;
+        self.extract_package(&package)
+    }
+
+    /// Extract from an already-opened package
+    ///
+    /// If the package contains embedded utf8dok source (from a previous render),
+    /// that source is returned directly unless `force_parse` is set.
+    pub fn extract_package(&self, package: &OdfPackage) -> Result<ExtractedOdt> {
+        package.ensure_text()?;
+
+        if !self.force_parse {
+            if let Some(embedded_source) = package.read_utf8dok_string("source.adoc") {
+                return Ok(ExtractedOdt {
+                    asciidoc: embedded_source,
+                    metadata: DocumentMeta::default(),
+                    source_origin: SourceOrigin::Embedded,
+                });
+            }
+        }
+
+        let document = read_document(package)?;
+        let mut asciidoc = generate_with_config(
+            &document,
+            GeneratorConfig {
+                include_header: self.include_header,
+                generate_anchors: true,
+            },
+        );
+        asciidoc.push('\n');
+
+        Ok(ExtractedOdt {
+            asciidoc,
+            metadata: document.metadata,
+            source_origin: SourceOrigin::Parsed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_minimal_template;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_embedded_source_wins_unless_forced() {
+        let mut package = OdfPackage::from_reader(Cursor::new(create_minimal_template())).unwrap();
+        package.set_string("utf8dok/source.adoc", "= Original\n");
+
+        let extracted = OdtExtractor::new().extract_package(&package).unwrap();
+        assert_eq!(extracted.source_origin, SourceOrigin::Embedded);
+        assert_eq!(extracted.asciidoc, "= Original\n");
+
+        let forced = OdtExtractor::new()
+            .with_force_parse(true)
+            .extract_package(&package)
+            .unwrap();
+        assert_eq!(forced.source_origin, SourceOrigin::Parsed);
+        assert_eq!(forced.asciidoc, "Template placeholder\n");
+    }
+}
@@ -0,0 +1,77 @@
+//! utf8dok-odf - OpenDocument Text generation and extraction
+//!
+//! This crate renders utf8dok documents as OpenDocument Text (`.odt`) for
+//! LibreOffice and other ODF suites, and extracts ODT files back to
+//! AsciiDoc.
+//!
+//! # Features
+//!
+//! - Rendering onto a Writer template (`.ott`): its page layout and common
+//!   styles are kept, and the common styles the content needs are added
+//!   when the template lacks them
+//! - Headings with outline levels and bookmarks, nested and numbered lists,
+//!   tables with spans, footnotes, images and cross references
+//! - Self-contained output: the AsciiDoc source and configuration are
+//!   embedded in a `utf8dok/` folder, as in the DOCX output
+//! - Extraction returning the embedded source, or converting the document
+//!   body when there is none
+//!
+//! # Example
+//!
+//! ```
+//! use utf8dok_ast::{Block, Document, Heading, Inline};
+//! use utf8dok_odf::{test_utils, OdfPackage, OdtExtractor, OdtWriter};
+//!
+//! let mut doc = Document::with_title("Guide");
+//! doc.push(Block::Heading(Heading {
+//!     level: 1,
+//!     text: vec![Inline::Text("Setup".to_string())],
+//!     style_id: None,
+//!     anchor: None,
+//!     role: None,
+//! }));
+//!
+//! let template = test_utils::create_minimal_template();
+//! let odt = OdtWriter::generate(&doc, &template).unwrap();
+//!
+//! let package = OdfPackage::from_reader(std::io::Cursor::new(odt)).unwrap();
+//! let extracted = OdtExtractor::new().extract_package(&package).unwrap();
+//! assert!(extracted.asciidoc.contains("== Setup"));
+//! ```
+
+pub mod error;
+pub mod extract;
+pub mod package;
+pub mod reader;
+pub mod styles;
+pub mod template;
+pub mod writer;
+mod xml;
+
+#[doc(hidden)]
+pub mod test_utils;
+
+pub use error::{OdfError, Result};
+pub use extract::{ExtractedOdt, OdtExtractor};
+pub use package::{OdfPackage, MIMETYPE_TEMPLATE, MIMETYPE_TEXT};
+pub use styles::OdtStyles;
+pub use template::OdtTemplate;
+pub use utf8dok_ooxml::SourceOrigin;
+pub use writer::OdtWriter;
+
+/// Crate version
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_structure() {
+        // Verify exports are accessible
+        let _ = OdtWriter::new;
+        let _ = OdtTemplate::load::<&str>;
+        let _ = OdtExtractor::new;
+        let _ = reader::read_document;
+    }
+}
@@ -0,0 +1,297 @@
+//! Package handling for ODT/OTT files
+//!
+//! OpenDocument files are ZIP packages. Unlike OOXML, the package has two
+//! fixed parts: an uncompressed `mimetype` file stored first, and a
+//! `META-INF/manifest.xml` listing every other file with its media type.
+//! [`OdfPackage::write_to`] produces both from the files it holds.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+use zip::read::ZipArchive;
+use zip::write::{SimpleFileOptions, ZipWriter};
+use zip::CompressionMethod;
+
+use crate::error::{OdfError, Result};
+
+/// Media type of an OpenDocument text document (.odt)
+pub const MIMETYPE_TEXT: &str = "application/vnd.oasis.opendocument.text";
+
+/// Media type of an OpenDocument text template (.ott)
+pub const MIMETYPE_TEMPLATE: &str = "application/vnd.oasis.opendocument.text-template";
+
+/// Path of the package manifest
+pub const PACKAGE_MANIFEST_PATH: &str = "META-INF/manifest.xml";
+
+/// Represents an unpacked OpenDocument package
+#[derive(Debug)]
+pub struct OdfPackage {
+    /// All files in the package, keyed by path
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl OdfPackage {
+    /// Open and unpack an ODT/OTT file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path)?;
+        Self::from_reader(file)
+    }
+
+    /// Create from any reader that implements Read + Seek
+    pub fn from_reader<R: Read + Seek>(reader: R) -> Result<Self> {
+        let mut archive = ZipArchive::new(reader)?;
+        let mut files = HashMap::new();
+
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            let name = file.name().to_string();
+
+            // Skip directories
+            if name.ends_with('/') {
+                continue;
+            }
+
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents)?;
+            files.insert(name, contents);
+        }
+
+        Ok(Self { files })
+    }
+
+    /// Get a file's contents by path
+    pub fn get(&self, path: &str) -> Option<&[u8]> {
+        self.files.get(path).map(|v| v.as_slice())
+    }
+
+    /// Get a file's contents as a string
+    pub fn get_string(&self, path: &str) -> Option<String> {
+        self.files
+            .get(path)
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    /// Get the package media type (the `mimetype` file)
+    pub fn mimetype(&self) -> Option<&str> {
+        self.get("mimetype")
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .map(str::trim)
+    }
+
+    /// Check that the package is a text document or text template
+    ///
+    /// Packages without a `mimetype` file are accepted, since the file is
+    /// only recommended by the specification.
+    pub fn ensure_text(&self) -> Result<()> {
+        match self.mimetype() {
+            None | Some(MIMETYPE_TEXT) | Some(MIMETYPE_TEMPLATE) => Ok(()),
+            Some(other) => Err(OdfError::NotText(other.to_string())),
+        }
+    }
+
+    /// Get the document content (content.xml)
+    pub fn content_xml(&self) -> Result<&[u8]> {
+        self.get("content.xml")
+            .ok_or_else(|| OdfError::MissingFile("content.xml".to_string()))
+    }
+
+    /// Get the common styles (styles.xml)
+    pub fn styles_xml(&self) -> Option<&[u8]> {
+        self.get("styles.xml")
+    }
+
+    /// Get the document metadata (meta.xml)
+    pub fn meta_xml(&self) -> Option<&[u8]> {
+        self.get("meta.xml")
+    }
+
+    /// Check if a file exists in the package
+    pub fn contains(&self, path: &str) -> bool {
+        self.files.contains_key(path)
+    }
+
+    /// List all files in the package
+    pub fn file_list(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(|s| s.as_str())
+    }
+
+    /// Set or update a file's contents
+    pub fn set(&mut self, path: impl Into<String>, contents: Vec<u8>) {
+        self.files.insert(path.into(), contents);
+    }
+
+    /// Set a file's contents from a string
+    pub fn set_string(&mut self, path: impl Into<String>, contents: impl Into<String>) {
+        self.files.insert(path.into(), contents.into().into_bytes());
+    }
+
+    /// Remove a file from the package
+    pub fn remove(&mut self, path: &str) -> Option<Vec<u8>> {
+        self.files.remove(path)
+    }
+
+    // =========================================================================
+    // utf8dok container methods
+    // =========================================================================
+
+    /// Read a file from the utf8dok/ folder as a string
+    pub fn read_utf8dok_string(&self, path: &str) -> Option<String> {
+        self.get_string(&format!("utf8dok/{}", path))
+    }
+
+    /// Check if a utf8dok file exists
+    pub fn has_utf8dok_file(&self, path: &str) -> bool {
+        self.contains(&format!("utf8dok/{}", path))
+    }
+
+    /// Write the package to a file
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let file = File::create(path)?;
+        self.write_to(file)
+    }
+
+    /// Write the package to any writer
+    ///
+    /// The `mimetype` file is written first and uncompressed, and
+    /// `META-INF/manifest.xml` is regenerated to list the files present.
+    pub fn write_to<W: Write + Seek>(&self, writer: W) -> Result<()> {
+        let mut zip = ZipWriter::new(writer);
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let mimetype = self.mimetype().unwrap_or(MIMETYPE_TEXT);
+        zip.start_file("mimetype", stored)?;
+        zip.write_all(mimetype.as_bytes())?;
+
+        // Sort keys for deterministic output
+        let mut paths: Vec<_> = self
+            .files
+            .keys()
+            .filter(|path| *path != "mimetype" && *path != PACKAGE_MANIFEST_PATH)
+            .collect();
+        paths.sort();
+
+        zip.start_file(PACKAGE_MANIFEST_PATH, deflated)?;
+        zip.write_all(package_manifest(mimetype, &paths).as_bytes())?;
+
+        for path in paths {
+            zip.start_file(path.as_str(), deflated)?;
+            zip.write_all(&self.files[path])?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+}
+
+/// META-INF/manifest.xml listing `paths`
+fn package_manifest(mimetype: &str, paths: &[&String]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <manifest:manifest xmlns:manifest=\"urn:oasis:names:tc:opendocument:xmlns:manifest:1.0\" manifest:version=\"1.3\">\n",
+    );
+    xml.push_str(&format!(
+        " <manifest:file-entry manifest:full-path=\"/\" manifest:version=\"1.3\" manifest:media-type=\"{}\"/>\n",
+        mimetype
+    ));
+    for path in paths {
+        xml.push_str(&format!(
+            " <manifest:file-entry manifest:full-path=\"{}\" manifest:media-type=\"{}\"/>\n",
+            crate::xml::escape_attr(path),
+            media_type(path)
+        ));
+    }
+    xml.push_str("</manifest:manifest>\n");
+    xml
+}
+
+/// Media type of a package file, by extension
+fn media_type(path: &str) -> &'static str {
+    let extension = path
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "xml" => "text/xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "json" => "application/json",
+        "adoc" | "toml" | "txt" => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn package(files: &[(&str, &str)]) -> OdfPackage {
+        OdfPackage {
+            files: files
+                .iter()
+                .map(|(path, contents)| (path.to_string(), contents.as_bytes().to_vec()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_write_puts_mimetype_first_and_stored() {
+        let package = package(&[
+            ("content.xml", "<office:document-content/>"),
+            ("mimetype", MIMETYPE_TEXT),
+            ("Pictures/logo.png", "png"),
+        ]);
+        let mut buffer = Cursor::new(Vec::new());
+        package.write_to(&mut buffer).unwrap();
+
+        let mut zip = ZipArchive::new(Cursor::new(buffer.into_inner())).unwrap();
+        let first = zip.by_index(0).unwrap();
+        assert_eq!(first.name(), "mimetype");
+        assert_eq!(first.compression(), CompressionMethod::Stored);
+        drop(first);
+
+        let mut manifest = String::new();
+        zip.by_name(PACKAGE_MANIFEST_PATH)
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        assert!(manifest.contains(
+            "manifest:full-path=\"/\" manifest:version=\"1.3\" manifest:media-type=\"application/vnd.oasis.opendocument.text\""
+        ));
+        assert!(manifest
+            .contains("manifest:full-path=\"content.xml\" manifest:media-type=\"text/xml\""));
+        assert!(manifest.contains(
+            "manifest:full-path=\"Pictures/logo.png\" manifest:media-type=\"image/png\""
+        ));
+        assert!(!manifest.contains("full-path=\"mimetype\""));
+    }
+
+    #[test]
+    fn test_ensure_text() {
+        assert!(package(&[("mimetype", MIMETYPE_TEMPLATE)])
+            .ensure_text()
+            .is_ok());
+        assert!(package(&[]).ensure_text().is_ok());
+        assert!(matches!(
+            package(&[("mimetype", "application/vnd.oasis.opendocument.spreadsheet")])
+                .ensure_text(),
+            Err(OdfError::NotText(_))
+        ));
+    }
+
+    #[test]
+    fn test_utf8dok_files() {
+        let package = package(&[("utf8dok/source.adoc", "= Title")]);
+        assert!(package.has_utf8dok_file("source.adoc"));
+        assert_eq!(
+            package.read_utf8dok_string("source.adoc").as_deref(),
+            Some("= Title")
+        );
+        assert!(package.read_utf8dok_string("utf8dok.toml").is_none());
+    }
+}
@@ -0,0 +1,1039 @@
+//! ODT Reader
+//!
+//! This module reads the body of an ODT package into a
+//! `utf8dok_ast::Document`. It understands the structures [`OdtWriter`]
+//! writes and the usual output of office suites: paragraph roles come from
+//! the common styles a paragraph's style derives from, so a `P3` automatic
+//! style based on `Preformatted_20_Text` still reads as a literal block.
+//!
+//! [`OdtWriter`]: crate::OdtWriter
+
+use std::collections::{HashMap, HashSet};
+
+use utf8dok_ast::{
+    Admonition, AdmonitionType, Block, BreakType, Callout, Document, Footnote, FormatType, Heading,
+    Image, Inline, Link, List, ListItem, ListType, LiteralBlock, NumberingStyle, Paragraph,
+    QuoteBlock, Sidebar, Table, TableCell, TableRow,
+};
+
+use crate::error::Result;
+use crate::package::OdfPackage;
+use crate::styles::{ListNumbering, OdtStyles};
+use crate::xml::{Element, Node};
+
+/// Role of a paragraph, from the common style its style derives from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Body,
+    Title,
+    Preformatted,
+    Caption,
+    Quotation,
+    Rule,
+    ListHeading,
+    ListContents,
+}
+
+/// Roles by common style name
+const ROLES: &[(&str, Role)] = &[
+    ("Title", Role::Title),
+    ("Preformatted_20_Text", Role::Preformatted),
+    ("Caption", Role::Caption),
+    ("Quotations", Role::Quotation),
+    ("Horizontal_20_Line", Role::Rule),
+    ("List_20_Heading", Role::ListHeading),
+    ("List_20_Contents", Role::ListContents),
+];
+
+/// Read the document of an ODT package
+///
+/// # Example
+///
+/// ```ignore
+/// use utf8dok_odf::{package::OdfPackage, reader};
+///
+/// let package = OdfPackage::open("report.odt")?;
+/// let doc = reader::read_document(&package)?;
+/// println!("{} blocks", doc.blocks.len());
+/// ```
+pub fn read_document(package: &OdfPackage) -> Result<Document> {
+    let content = Element::parse(package.content_xml()?)?;
+
+    let mut styles = match package.styles_xml() {
+        Some(xml) => OdtStyles::parse(xml)?,
+        None => OdtStyles::default(),
+    };
+    styles.extend(OdtStyles::parse(package.content_xml()?)?);
+
+    let mut reader = OdtReader {
+        styles,
+        referenced_notes: HashSet::new(),
+        document: Document::new(),
+    };
+    collect_note_refs(&content, &mut reader.referenced_notes);
+
+    let text = content
+        .child("office:body")
+        .and_then(|body| body.child("office:text"));
+    if let Some(text) = text {
+        let blocks = reader.read_blocks(text);
+        reader.document.blocks = blocks;
+    }
+
+    if let Some(xml) = package.meta_xml() {
+        reader.read_meta(&Element::parse(xml)?);
+    }
+    Ok(reader.document)
+}
+
+/// Names of notes referred to by `text:note-ref`
+fn collect_note_refs(element: &Element, names: &mut HashSet<String>) {
+    for child in element.elements() {
+        if child.name == "text:note-ref" {
+            if let Some(name) = child.attribute("text:ref-name") {
+                names.insert(name.to_string());
+            }
+        }
+        collect_note_refs(child, names);
+    }
+}
+
+struct OdtReader {
+    /// Common and automatic styles
+    styles: OdtStyles,
+    /// Notes referred to elsewhere, which keep their id
+    referenced_notes: HashSet<String>,
+    /// Document being read (metadata)
+    document: Document,
+}
+
+impl OdtReader {
+    /// Read document metadata from meta.xml
+    fn read_meta(&mut self, root: &Element) {
+        let Some(meta) = root.child("office:meta") else {
+            return;
+        };
+        if self.document.metadata.title.is_none() {
+            self.document.metadata.title = meta
+                .child("dc:title")
+                .map(|e| e.text().trim().to_string())
+                .filter(|t| !t.is_empty());
+        }
+        let author = meta
+            .child("meta:initial-creator")
+            .or_else(|| meta.child("dc:creator"))
+            .map(|e| e.text().trim().to_string())
+            .filter(|a| !a.is_empty());
+        if let Some(author) = author {
+            self.document.metadata.authors.push(author);
+        }
+    }
+
+    /// Role of a paragraph style
+    fn role(&self, style: Option<&str>) -> Role {
+        let Some(style) = style else {
+            return Role::Body;
+        };
+        let lineage = self.styles.lineage("paragraph", style);
+        lineage
+            .iter()
+            .find_map(|name| {
+                ROLES
+                    .iter()
+                    .find(|(common, _)| common == name)
+                    .map(|(_, role)| *role)
+            })
+            .unwrap_or(Role::Body)
+    }
+
+    // =========================================================================
+    // Blocks
+    // =========================================================================
+
+    /// Read the block content of a container element
+    fn read_blocks(&mut self, container: &Element) -> Vec<Block> {
+        let mut blocks = Vec::new();
+        let mut caption: Option<String> = None;
+        let mut children = container.elements().peekable();
+
+        while let Some(element) = children.next() {
+            match element.name.as_str() {
+                "text:h" => {
+                    blocks.push(Block::Heading(self.read_heading(element)));
+                }
+                "text:p" => {
+                    let style = element.attribute("text:style-name");
+                    match self.role(style) {
+                        Role::Title if self.document.metadata.title.is_none() => {
+                            let title = element.text().trim().to_string();
+                            if !title.is_empty() {
+                                self.document.metadata.title = Some(title);
+                            }
+                        }
+                        Role::Caption => {
+                            let text = element.text().trim().to_string();
+                            if !text.is_empty() {
+                                caption = Some(text);
+                            }
+                            continue;
+                        }
+                        Role::Preformatted => {
+                            let mut lines = vec![preformatted_text(element)];
+                            while let Some(next) = children.peek() {
+                                if next.name != "text:p"
+                                    || self.role(next.attribute("text:style-name"))
+                                        != Role::Preformatted
+                                {
+                                    break;
+                                }
+                                lines.push(preformatted_text(next));
+                                children.next();
+                            }
+                            blocks.push(Block::Literal(literal_block(
+                                &lines.join("\n"),
+                                caption.take(),
+                            )));
+                        }
+                        Role::Quotation => {
+                            let mut paragraphs = vec![element];
+                            while let Some(next) = children.peek() {
+                                if next.name != "text:p"
+                                    || self.role(next.attribute("text:style-name"))
+                                        != Role::Quotation
+                                {
+                                    break;
+                                }
+                                paragraphs.push(next);
+                                children.next();
+                            }
+                            blocks.push(Block::Quote(self.read_quote(&paragraphs, caption.take())));
+                        }
+                        Role::ListHeading | Role::ListContents => {
+                            let mut paragraphs = vec![element];
+                            while let Some(next) = children.peek() {
+                                let role = self.role(next.attribute("text:style-name"));
+                                if next.name != "text:p"
+                                    || !matches!(role, Role::ListHeading | Role::ListContents)
+                                {
+                                    break;
+                                }
+                                paragraphs.push(next);
+                                children.next();
+                            }
+                            blocks.push(Block::List(self.read_description_list(&paragraphs)));
+                        }
+                        role => {
+                            if let Some(block) = self.read_paragraph(element, role, caption.take())
+                            {
+                                blocks.push(block);
+                            }
+                        }
+                    }
+                }
+                "text:list" => {
+                    let list = self.read_list(element);
+                    if !list.items.is_empty() {
+                        blocks.push(Block::List(list));
+                    }
+                }
+                "table:table" => {
+                    blocks.push(Block::Table(self.read_table(element, caption.take())));
+                }
+                "text:section" => {
+                    let name = element.attribute("text:name").unwrap_or_default();
+                    if name.starts_with("Sidebar") {
+                        blocks.push(Block::Sidebar(self.read_sidebar(element)));
+                    } else {
+                        blocks.extend(self.read_blocks(element));
+                    }
+                }
+                // Table of contents, forms, sequence declarations...
+                _ => continue,
+            }
+            caption = None;
+        }
+        blocks
+    }
+
+    /// Read a sidebar section; a leading caption is its title
+    fn read_sidebar(&mut self, element: &Element) -> Sidebar {
+        let first = element.elements().next();
+        let title = first
+            .filter(|first| {
+                first.name == "text:p"
+                    && self.role(first.attribute("text:style-name")) == Role::Caption
+            })
+            .map(|first| first.text().trim().to_string());
+        let content = if title.is_some() {
+            let mut skipped = false;
+            Element {
+                name: element.name.clone(),
+                attributes: Vec::new(),
+                children: element
+                    .children
+                    .iter()
+                    .filter(|node| {
+                        let caption = !skipped && matches!(node, Node::Element(_));
+                        skipped |= caption;
+                        !caption
+                    })
+                    .cloned()
+                    .collect(),
+            }
+        } else {
+            element.clone()
+        };
+        Sidebar {
+            title,
+            blocks: self.read_blocks(&content),
+        }
+    }
+
+    fn read_heading(&self, element: &Element) -> Heading {
+        let level = element
+            .attribute("text:outline-level")
+            .and_then(|level| level.parse::<u8>().ok())
+            .unwrap_or(1)
+            .max(1);
+        let mut text = self.read_inlines(element);
+
+        // A bookmark at the start names the heading
+        let anchor = match text.first() {
+            Some(Inline::Anchor(name)) => Some(name.clone()),
+            _ => None,
+        };
+        if anchor.is_some() {
+            text.remove(0);
+        }
+        Heading {
+            level,
+            text,
+            style_id: None,
+            anchor,
+            role: None,
+        }
+    }
+
+    /// Read a body paragraph, or nothing for an empty one
+    fn read_paragraph(
+        &self,
+        element: &Element,
+        role: Role,
+        title: Option<String>,
+    ) -> Option<Block> {
+        let mut inlines = self.read_inlines(element);
+        if inlines.is_empty() {
+            let page_break = element
+                .attribute("text:style-name")
+                .is_some_and(|style| self.styles.formatting("paragraph", style).page_break_before);
+            return match role {
+                Role::Rule => Some(Block::ThematicBreak),
+                _ if page_break => Some(Block::Break(BreakType::Page)),
+                _ => None,
+            };
+        }
+
+        if let Some(kind) = admonition_type(&inlines) {
+            inlines.remove(0);
+            trim_start(&mut inlines);
+            return Some(Block::Admonition(Admonition {
+                admonition_type: kind,
+                content: vec![paragraph(inlines, None)],
+                title: title.map(|t| vec![Inline::Text(t)]),
+            }));
+        }
+        Some(paragraph(inlines, title))
+    }
+
+    fn read_quote(&self, paragraphs: &[&Element], title: Option<String>) -> QuoteBlock {
+        let mut quote = QuoteBlock {
+            blocks: Vec::new(),
+            attribution: None,
+            cite: None,
+            title,
+            verse: false,
+        };
+        for (index, element) in paragraphs.iter().enumerate() {
+            let text = element.text();
+            let text = text.trim();
+            if index + 1 == paragraphs.len() && index > 0 {
+                if let Some(attribution) = text.strip_prefix('\u{2014}') {
+                    let attribution = attribution.trim();
+                    match attribution.split_once(", ") {
+                        Some((who, cite)) => {
+                            quote.attribution = Some(who.to_string());
+                            quote.cite = Some(cite.to_string());
+                        }
+                        None => quote.attribution = Some(attribution.to_string()),
+                    }
+                    continue;
+                }
+            }
+            let inlines = self.read_inlines(element);
+            if !inlines.is_empty() {
+                quote.blocks.push(paragraph(inlines, None));
+            }
+        }
+        quote
+    }
+
+    fn read_description_list(&self, paragraphs: &[&Element]) -> List {
+        let mut items: Vec<ListItem> = Vec::new();
+        for element in paragraphs {
+            let inlines = self.read_inlines(element);
+            if self.role(element.attribute("text:style-name")) == Role::ListHeading {
+                items.push(ListItem {
+                    content: Vec::new(),
+                    level: 0,
+                    term: Some(inlines),
+                    checked: None,
+                });
+            } else if !inlines.is_empty() {
+                match items.last_mut() {
+                    Some(item) => item.content.push(paragraph(inlines, None)),
+                    None => items.push(ListItem {
+                        content: vec![paragraph(inlines, None)],
+                        level: 0,
+                        term: Some(Vec::new()),
+                        checked: None,
+                    }),
+                }
+            }
+        }
+        List {
+            list_type: ListType::Description,
+            items,
+            style_id: None,
+            numbering: None,
+            start: None,
+        }
+    }
+
+    fn read_list(&mut self, element: &Element) -> List {
+        let numbering = element
+            .attribute("text:style-name")
+            .and_then(|style| self.styles.list_numbering(style))
+            .cloned()
+            .unwrap_or(ListNumbering::Bullet);
+        let (list_type, numbering) = match numbering {
+            ListNumbering::Bullet => (ListType::Unordered, None),
+            ListNumbering::Number(format) => (
+                ListType::Ordered,
+                match format.as_str() {
+                    "a" => Some(NumberingStyle::LowerAlpha),
+                    "A" => Some(NumberingStyle::UpperAlpha),
+                    "i" => Some(NumberingStyle::LowerRoman),
+                    "I" => Some(NumberingStyle::UpperRoman),
+                    _ => None,
+                },
+            ),
+        };
+
+        let mut items = Vec::new();
+        self.read_list_items(element, 0, &mut items);
+        List {
+            list_type,
+            items,
+            style_id: None,
+            numbering,
+            start: None,
+        }
+    }
+
+    /// Read the items of a list and its nested lists, flattened with levels
+    fn read_list_items(&mut self, list: &Element, level: u8, items: &mut Vec<ListItem>) {
+        for item in list.elements() {
+            if !matches!(item.name.as_str(), "text:list-item" | "text:list-header") {
+                continue;
+            }
+            let mut content = Vec::new();
+            let mut nested = Vec::new();
+            for child in item.elements() {
+                if child.name == "text:list" {
+                    nested.push(child);
+                }
+            }
+
+            // Content other than nested lists
+            let own = Element {
+                name: item.name.clone(),
+                attributes: Vec::new(),
+                children: item
+                    .children
+                    .iter()
+                    .filter(|node| !matches!(node, Node::Element(e) if e.name == "text:list"))
+                    .cloned()
+                    .collect(),
+            };
+            content.extend(self.read_blocks(&own));
+
+            // A checkbox prefix marks a checklist item
+            let mut checked = None;
+            if let Some(Block::Paragraph(first)) = content.first_mut() {
+                if let Some(Inline::Text(text)) = first.inlines.first_mut() {
+                    for (mark, state) in [('\u{2612}', true), ('\u{2610}', false)] {
+                        if let Some(rest) = text.strip_prefix(mark) {
+                            checked = Some(state);
+                            *text = rest.trim_start().to_string();
+                        }
+                    }
+                    if text.is_empty() {
+                        first.inlines.remove(0);
+                    }
+                }
+                if first.inlines.is_empty() && checked.is_some() {
+                    content.remove(0);
+                }
+            }
+
+            if !content.is_empty() || checked.is_some() || nested.is_empty() {
+                items.push(ListItem {
+                    content,
+                    level,
+                    term: None,
+                    checked,
+                });
+            }
+            for list in nested {
+                self.read_list_items(list, level + 1, items);
+            }
+        }
+    }
+
+    fn read_table(&mut self, element: &Element, title: Option<String>) -> Table {
+        let mut rows = Vec::new();
+        self.read_table_rows(element, false, &mut rows);
+        Table {
+            rows,
+            style_id: None,
+            caption: title.map(|t| vec![Inline::Text(t)]),
+            columns: Vec::new(),
+        }
+    }
+
+    fn read_table_rows(&mut self, container: &Element, header: bool, rows: &mut Vec<TableRow>) {
+        for child in container.elements() {
+            match child.name.as_str() {
+                "table:table-header-rows" => self.read_table_rows(child, true, rows),
+                "table:table-rows" | "table:table-row-group" => {
+                    self.read_table_rows(child, header, rows)
+                }
+                "table:table-row" => {
+                    let mut cells = Vec::new();
+                    for cell in child.elements() {
+                        if cell.name != "table:table-cell" {
+                            continue;
+                        }
+                        let span = |name: &str| {
+                            cell.attribute(name)
+                                .and_then(|n| n.parse::<u32>().ok())
+                                .unwrap_or(1)
+                                .max(1)
+                        };
+                        let colspan = span("table:number-columns-spanned");
+                        let rowspan = span("table:number-rows-spanned");
+                        let repeated = span("table:number-columns-repeated");
+                        let content = self.read_blocks(cell);
+                        for _ in 0..repeated.min(64) {
+                            cells.push(TableCell {
+                                content: content.clone(),
+                                colspan,
+                                rowspan,
+                                align: None,
+                                style: None,
+                            });
+                        }
+                    }
+                    rows.push(TableRow {
+                        cells,
+                        is_header: header,
+                        is_footer: false,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // =========================================================================
+    // Inlines
+    // =========================================================================
+
+    /// Read the inline content of a paragraph or heading
+    fn read_inlines(&self, element: &Element) -> Vec<Inline> {
+        let mut builder = InlineBuilder::default();
+        self.read_inline_children(element, &mut builder);
+        builder.finish()
+    }
+
+    fn read_inline_children(&self, element: &Element, out: &mut InlineBuilder) {
+        for node in &element.children {
+            match node {
+                Node::Text(text) => out.text(text),
+                Node::Element(child) => self.read_inline(child, out),
+            }
+        }
+    }
+
+    fn read_inline(&self, element: &Element, out: &mut InlineBuilder) {
+        match element.name.as_str() {
+            "text:s" => {
+                let count = element
+                    .attribute("text:c")
+                    .and_then(|c| c.parse::<usize>().ok())
+                    .unwrap_or(1);
+                out.spaces(count);
+            }
+            "text:tab" => out.spaces(1),
+            "text:line-break" => out.push(Inline::Break),
+            "text:span" => {
+                let mut inner = InlineBuilder {
+                    last_space: out.last_space,
+                    ..Default::default()
+                };
+                self.read_inline_children(element, &mut inner);
+                out.last_space = inner.last_space;
+                let inlines = inner.inlines();
+                let formatting = element
+                    .attribute("text:style-name")
+                    .map(|style| self.styles.formatting("text", style))
+                    .unwrap_or_default();
+                let formats = [
+                    (formatting.subscript, FormatType::Subscript),
+                    (formatting.superscript, FormatType::Superscript),
+                    (formatting.highlight, FormatType::Highlight),
+                    (formatting.monospace, FormatType::Monospace),
+                    (formatting.italic, FormatType::Italic),
+                    (formatting.bold, FormatType::Bold),
+                ];
+                out.extend(format_inlines(inlines, &formats));
+            }
+            "text:a" => {
+                let url = element
+                    .attribute("xlink:href")
+                    .unwrap_or_default()
+                    .to_string();
+                let mut inner = InlineBuilder {
+                    last_space: out.last_space,
+                    ..Default::default()
+                };
+                self.read_inline_children(element, &mut inner);
+                out.last_space = inner.last_space;
+                let mut text = inner.inlines();
+                if matches!(text.as_slice(), [Inline::Text(t)] if *t == url) {
+                    text.clear();
+                }
+                out.push(Inline::Link(Link { url, text }));
+            }
+            "text:bookmark" | "text:bookmark-start" => {
+                if let Some(name) = element.attribute("text:name") {
+                    // Bookmarks office suites create for their own references
+                    if !name.starts_with('_') && !name.contains("__") {
+                        out.push(Inline::Anchor(name.to_string()));
+                    }
+                }
+            }
+            "text:note" => {
+                let id = element
+                    .attribute("text:id")
+                    .filter(|id| self.referenced_notes.contains(*id))
+                    .map(String::from);
+                let mut content = Vec::new();
+                if let Some(body) = element.child("text:note-body") {
+                    for p in body.elements() {
+                        let inlines = self.read_inlines(p);
+                        if !inlines.is_empty() {
+                            if !content.is_empty() {
+                                content.push(Inline::Text(" ".to_string()));
+                            }
+                            content.extend(inlines);
+                        }
+                    }
+                }
+                out.push(Inline::Footnote(Footnote { id, content }));
+            }
+            "text:note-ref" => {
+                if let Some(name) = element.attribute("text:ref-name") {
+                    out.push(Inline::Footnote(Footnote {
+                        id: Some(name.to_string()),
+                        content: Vec::new(),
+                    }));
+                }
+            }
+            "draw:frame" => {
+                if let Some(image) = element.child("draw:image") {
+                    let href = image.attribute("xlink:href").unwrap_or_default();
+                    let alt = element
+                        .child("svg:title")
+                        .or_else(|| element.child("svg:desc"))
+                        .map(|e| e.text().trim().to_string())
+                        .filter(|alt| !alt.is_empty());
+                    out.push(Inline::Image(Image {
+                        src: image_source(href),
+                        alt,
+                    }));
+                }
+            }
+            // Ignored: bookmark ends, annotations, change marks, soft page breaks
+            "text:bookmark-end"
+            | "office:annotation"
+            | "office:annotation-end"
+            | "text:soft-page-break"
+            | "text:change"
+            | "text:change-start"
+            | "text:change-end"
+            | "text:note-citation" => {}
+            // Fields and other wrappers: their text
+            _ => self.read_inline_children(element, out),
+        }
+    }
+}
+
+/// Collects inlines, collapsing white space as ODF does
+#[derive(Default)]
+struct InlineBuilder {
+    inlines: Vec<Inline>,
+    /// Whether the content so far ends in a space (or is empty)
+    last_space: bool,
+    started: bool,
+}
+
+impl InlineBuilder {
+    fn text(&mut self, text: &str) {
+        let mut collapsed = String::new();
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !self.last_space && self.started {
+                    collapsed.push(' ');
+                    self.last_space = true;
+                }
+            } else {
+                collapsed.push(c);
+                self.last_space = false;
+                self.started = true;
+            }
+        }
+        self.push_text(&collapsed);
+    }
+
+    fn spaces(&mut self, count: usize) {
+        self.push_text(&" ".repeat(count));
+        self.last_space = true;
+        self.started = true;
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if let Some(Inline::Text(last)) = self.inlines.last_mut() {
+            last.push_str(text);
+        } else {
+            self.inlines.push(Inline::Text(text.to_string()));
+        }
+    }
+
+    fn push(&mut self, inline: Inline) {
+        self.started = true;
+        self.last_space = matches!(inline, Inline::Break);
+        self.inlines.push(inline);
+    }
+
+    fn extend(&mut self, inlines: Vec<Inline>) {
+        for inline in inlines {
+            match inline {
+                Inline::Text(text) => {
+                    self.started = true;
+                    self.push_text(&text);
+                }
+                other => {
+                    let last_space = self.last_space;
+                    self.push(other);
+                    self.last_space = last_space;
+                }
+            }
+        }
+    }
+
+    /// Inlines of nested content, untrimmed
+    fn inlines(self) -> Vec<Inline> {
+        self.inlines
+    }
+
+    /// Inlines of a whole paragraph, with trailing white space removed
+    fn finish(mut self) -> Vec<Inline> {
+        while let Some(Inline::Text(last)) = self.inlines.last_mut() {
+            let trimmed = last.trim_end().len();
+            last.truncate(trimmed);
+            if last.is_empty() {
+                self.inlines.pop();
+            } else {
+                break;
+            }
+        }
+        self.inlines
+    }
+}
+
+/// Wrap inlines in the formats that apply, keeping outer white space
+/// outside, where AsciiDoc needs it
+fn format_inlines(mut inlines: Vec<Inline>, formats: &[(bool, FormatType)]) -> Vec<Inline> {
+    if !formats.iter().any(|(on, _)| *on) || inlines.is_empty() {
+        return inlines;
+    }
+    let mut leading = String::new();
+    if let Some(Inline::Text(first)) = inlines.first_mut() {
+        let trimmed = first.trim_start();
+        leading = first[..first.len() - trimmed.len()].to_string();
+        *first = trimmed.to_string();
+    }
+    let mut trailing = String::new();
+    if let Some(Inline::Text(last)) = inlines.last_mut() {
+        let trimmed = last.trim_end().len();
+        trailing = last[trimmed..].to_string();
+        last.truncate(trimmed);
+    }
+    inlines.retain(|inline| !matches!(inline, Inline::Text(t) if t.is_empty()));
+
+    let mut result = Vec::new();
+    if !leading.is_empty() {
+        result.push(Inline::Text(leading));
+    }
+    if !inlines.is_empty() {
+        let mut inline = if inlines.len() == 1 {
+            inlines.remove(0)
+        } else {
+            Inline::Span(inlines)
+        };
+        for (on, format) in formats {
+            if *on {
+                inline = Inline::Format(format.clone(), Box::new(inline));
+            }
+        }
+        result.push(inline);
+    }
+    if !trailing.is_empty() {
+        result.push(Inline::Text(trailing));
+    }
+    result
+}
+
+/// Text of a preformatted paragraph, white space kept as written
+fn preformatted_text(element: &Element) -> String {
+    let mut text = String::new();
+    for node in &element.children {
+        match node {
+            Node::Text(t) => text.push_str(&t.replace(['\n', '\r'], "")),
+            Node::Element(child) => match child.name.as_str() {
+                "text:s" => {
+                    let count = child
+                        .attribute("text:c")
+                        .and_then(|c| c.parse::<usize>().ok())
+                        .unwrap_or(1);
+                    text.push_str(&" ".repeat(count));
+                }
+                "text:tab" => text.push('\t'),
+                "text:line-break" => text.push('\n'),
+                "text:bookmark-end" | "office:annotation" | "text:soft-page-break" => {}
+                _ => text.push_str(&preformatted_text(child)),
+            },
+        }
+    }
+    text
+}
+
+/// A literal block, with trailing `<n>` markers read as callouts
+fn literal_block(text: &str, title: Option<String>) -> LiteralBlock {
+    let mut callouts = Vec::new();
+    let mut lines = Vec::new();
+    for (index, line) in text.split('\n').enumerate() {
+        let mut line = line;
+        let mut numbers = Vec::new();
+        while let Some(rest) = line.trim_end().strip_suffix('>') {
+            let Some((before, number)) = rest.rsplit_once(" <") else {
+                break;
+            };
+            let Ok(number) = number.parse::<u32>() else {
+                break;
+            };
+            numbers.push(number);
+            line = before;
+        }
+        for number in numbers.into_iter().rev() {
+            callouts.push(Callout {
+                number,
+                line: index as u32 + 1,
+            });
+        }
+        lines.push(line);
+    }
+    LiteralBlock {
+        content: lines.join("\n"),
+        language: None,
+        title,
+        style_id: None,
+        callouts,
+        highlight_lines: Vec::new(),
+    }
+}
+
+/// Admonition type of a paragraph starting with a bold `Note:` label
+fn admonition_type(inlines: &[Inline]) -> Option<AdmonitionType> {
+    let Some(Inline::Format(FormatType::Bold, label)) = inlines.first() else {
+        return None;
+    };
+    let Inline::Text(label) = label.as_ref() else {
+        return None;
+    };
+    let kind = match label.trim().strip_suffix(':')? {
+        "Note" => AdmonitionType::Note,
+        "Tip" => AdmonitionType::Tip,
+        "Important" => AdmonitionType::Important,
+        "Warning" => AdmonitionType::Warning,
+        "Caution" => AdmonitionType::Caution,
+        _ => return None,
+    };
+    // The label must be followed by content
+    (inlines.len() > 1).then_some(kind)
+}
+
+/// Remove leading white space of the first inline
+fn trim_start(inlines: &mut Vec<Inline>) {
+    if let Some(Inline::Text(first)) = inlines.first_mut() {
+        *first = first.trim_start().to_string();
+        if first.is_empty() {
+            inlines.remove(0);
+        }
+    }
+}
+
+/// Image source for AsciiDoc, from a package or relative link
+fn image_source(href: &str) -> String {
+    if let Some(name) = href.strip_prefix("Pictures/") {
+        // Extraction copies pictures to media/
+        format!("media/{}", name)
+    } else if let Some(relative) = href.strip_prefix("../") {
+        relative.to_string()
+    } else {
+        href.to_string()
+    }
+}
+
+fn paragraph(inlines: Vec<Inline>, title: Option<String>) -> Block {
+    let mut attributes = HashMap::new();
+    if let Some(title) = title {
+        attributes.insert("title".to_string(), title);
+    }
+    Block::Paragraph(Paragraph {
+        inlines,
+        style_id: None,
+        attributes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_minimal_template;
+    use crate::OdtWriter;
+
+    fn read(xml: &str) -> Document {
+        let mut package =
+            OdfPackage::from_reader(std::io::Cursor::new(create_minimal_template())).unwrap();
+        package.set_string(
+            "content.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" xmlns:xlink="http://www.w3.org/1999/xlink">
+<office:automatic-styles>
+  <style:style style:name="T1" style:family="text"><style:text-properties fo:font-weight="bold"/></style:style>
+  <style:style style:name="P1" style:family="paragraph" style:parent-style-name="Text_20_body"/>
+  <text:list-style style:name="L1"><text:list-level-style-number text:level="1" style:num-format="A"/></text:list-style>
+</office:automatic-styles>
+<office:body><office:text>{}</office:text></office:body>
+</office:document-content>"#,
+                xml
+            ),
+        );
+        read_document(&package).unwrap()
+    }
+
+    #[test]
+    fn test_whitespace_and_spans() {
+        let doc = read(
+            r#"<text:p text:style-name="P1">  Hello
+   <text:span text:style-name="T1">bold </text:span>world<text:s text:c="2"/>!</text:p>"#,
+        );
+        let Block::Paragraph(p) = &doc.blocks[0] else {
+            panic!("expected paragraph");
+        };
+        assert_eq!(
+            p.inlines,
+            vec![
+                Inline::Text("Hello ".to_string()),
+                Inline::Format(FormatType::Bold, Box::new(Inline::Text("bold".to_string()))),
+                Inline::Text(" world  !".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_office_suite_list() {
+        let doc = read(
+            r#"<text:list text:style-name="L1">
+  <text:list-item><text:p>first</text:p>
+    <text:list><text:list-item><text:p>inner</text:p></text:list-item></text:list>
+  </text:list-item>
+  <text:list-item><text:p>second</text:p></text:list-item>
+</text:list>"#,
+        );
+        let Block::List(list) = &doc.blocks[0] else {
+            panic!("expected list");
+        };
+        assert_eq!(list.list_type, ListType::Ordered);
+        assert_eq!(list.numbering, Some(NumberingStyle::UpperAlpha));
+        let levels: Vec<u8> = list.items.iter().map(|i| i.level).collect();
+        assert_eq!(levels, vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn test_literal_callouts() {
+        let literal = literal_block("let x = 1; <1>\nx <2> <3>", None);
+        assert_eq!(literal.content, "let x = 1;\nx");
+        assert_eq!(
+            literal
+                .callouts
+                .iter()
+                .map(|c| (c.number, c.line))
+                .collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_reads_writer_output() {
+        let mut doc = Document::with_title("Guide");
+        doc.push(Block::Heading(Heading {
+            level: 2,
+            text: vec![Inline::Text("Setup".to_string())],
+            style_id: None,
+            anchor: Some("setup".to_string()),
+            role: None,
+        }));
+        doc.push(Block::Admonition(Admonition {
+            admonition_type: AdmonitionType::Warning,
+            content: vec![paragraph(vec![Inline::Text("Hot.".to_string())], None)],
+            title: None,
+        }));
+        doc.push(Block::ThematicBreak);
+
+        let odt = OdtWriter::generate(&doc, &create_minimal_template()).unwrap();
+        let package = OdfPackage::from_reader(std::io::Cursor::new(odt)).unwrap();
+        let read = read_document(&package).unwrap();
+
+        assert_eq!(read.metadata.title.as_deref(), Some("Guide"));
+        assert_eq!(read.blocks[0], doc.blocks[0]);
+        assert_eq!(read.blocks[1], doc.blocks[1]);
+        assert_eq!(read.blocks[2], Block::ThematicBreak);
+    }
+}
@@ -0,0 +1,386 @@
+//! Styles of ODF parts
+//!
+//! Styles live in two places: the common styles of `styles.xml`, which a
+//! template provides, and the automatic styles of `content.xml`, which an
+//! office suite creates for direct formatting (a bold run becomes a span
+//! with an automatic style `T1` whose parent may be a common style).
+//! [`OdtStyles`] holds both kinds, so formatting resolves through parents.
+
+use std::collections::HashMap;
+
+use crate::error::Result;
+use crate::xml::Element;
+
+/// A paragraph, text, table or section style
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OdtStyle {
+    /// Style name (`Heading_20_1`)
+    pub name: String,
+    /// Name shown in the office suite (`Heading 1`)
+    pub display_name: Option<String>,
+    /// Style family (`paragraph`, `text`, `table`...)
+    pub family: String,
+    /// Parent style name
+    pub parent: Option<String>,
+    /// Outline level of paragraphs with this style (headings)
+    pub outline_level: Option<u8>,
+    /// Formatting set by this style itself
+    properties: Properties,
+}
+
+/// Formatting set by one style, `None` where inherited
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Properties {
+    bold: Option<bool>,
+    italic: Option<bool>,
+    monospace: Option<bool>,
+    superscript: Option<bool>,
+    subscript: Option<bool>,
+    highlight: Option<bool>,
+    page_break_before: Option<bool>,
+}
+
+/// Formatting of a style, with inherited values resolved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Formatting {
+    pub bold: bool,
+    pub italic: bool,
+    pub monospace: bool,
+    pub superscript: bool,
+    pub subscript: bool,
+    pub highlight: bool,
+    pub page_break_before: bool,
+}
+
+/// Numbering of the first level of a list style
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListNumbering {
+    /// Bullets (or images)
+    Bullet,
+    /// Numbers in an ODF number format (`1`, `a`, `A`, `i`, `I`)
+    Number(String),
+}
+
+/// Styles of a template or document
+#[derive(Debug, Clone, Default)]
+pub struct OdtStyles {
+    /// Paragraph, text, table and section styles in document order
+    styles: Vec<OdtStyle>,
+    /// Index into `styles` by (family, name)
+    index: HashMap<(String, String), usize>,
+    /// List styles by name
+    lists: HashMap<String, ListNumbering>,
+}
+
+impl OdtStyles {
+    /// Parse the styles declared in an ODF part (styles.xml or content.xml)
+    pub fn parse(xml: &[u8]) -> Result<Self> {
+        let root = Element::parse(xml)?;
+        let mut styles = Self::default();
+        for container in root.elements() {
+            if matches!(
+                container.name.as_str(),
+                "office:styles" | "office:automatic-styles"
+            ) {
+                styles.add_container(container);
+            }
+        }
+        Ok(styles)
+    }
+
+    /// Add the styles of another part, replacing styles of the same name
+    pub fn extend(&mut self, other: OdtStyles) {
+        for style in other.styles {
+            self.insert(style);
+        }
+        self.lists.extend(other.lists);
+    }
+
+    fn add_container(&mut self, container: &Element) {
+        for element in container.elements() {
+            match element.name.as_str() {
+                "style:style" => {
+                    if let Some(style) = parse_style(element) {
+                        self.insert(style);
+                    }
+                }
+                "text:list-style" => {
+                    if let Some(name) = element.attribute("style:name") {
+                        self.lists
+                            .insert(name.to_string(), parse_list_numbering(element));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn insert(&mut self, style: OdtStyle) {
+        let key = (style.family.clone(), style.name.clone());
+        match self.index.get(&key) {
+            Some(&i) => self.styles[i] = style,
+            None => {
+                self.index.insert(key, self.styles.len());
+                self.styles.push(style);
+            }
+        }
+    }
+
+    /// Get a style by family and name
+    pub fn get(&self, family: &str, name: &str) -> Option<&OdtStyle> {
+        self.index
+            .get(&(family.to_string(), name.to_string()))
+            .map(|&i| &self.styles[i])
+    }
+
+    /// Check if a style exists
+    pub fn contains(&self, family: &str, name: &str) -> bool {
+        self.get(family, name).is_some()
+    }
+
+    /// Iterate over all styles
+    pub fn all(&self) -> impl Iterator<Item = &OdtStyle> {
+        self.styles.iter()
+    }
+
+    /// Iterate over paragraph styles
+    pub fn paragraph_styles(&self) -> impl Iterator<Item = &OdtStyle> {
+        self.styles.iter().filter(|s| s.family == "paragraph")
+    }
+
+    /// Name of the paragraph style for headings at `level`
+    ///
+    /// Prefers the standard `Heading_20_N` name, then any paragraph style
+    /// with that default outline level.
+    pub fn heading_style(&self, level: u8) -> Option<&str> {
+        let standard = format!("Heading_20_{}", level);
+        if self.contains("paragraph", &standard) {
+            return self.get("paragraph", &standard).map(|s| s.name.as_str());
+        }
+        self.paragraph_styles()
+            .find(|s| s.outline_level == Some(level))
+            .map(|s| s.name.as_str())
+    }
+
+    /// Numbering of the list style `name`
+    pub fn list_numbering(&self, name: &str) -> Option<&ListNumbering> {
+        self.lists.get(name)
+    }
+
+    /// Names of `name` and its ancestors, nearest first
+    pub fn lineage(&self, family: &str, name: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut current = Some(name.to_string());
+        while let Some(name) = current {
+            if names.contains(&name) {
+                break;
+            }
+            current = self.get(family, &name).and_then(|s| s.parent.clone());
+            names.push(name);
+        }
+        names
+    }
+
+    /// Whether `name` is `ancestor` or inherits from it
+    pub fn inherits(&self, family: &str, name: &str, ancestor: &str) -> bool {
+        self.lineage(family, name).iter().any(|n| n == ancestor)
+    }
+
+    /// Formatting of the style `name`, with inherited values resolved
+    pub fn formatting(&self, family: &str, name: &str) -> Formatting {
+        let mut resolved = Properties::default();
+        for name in self.lineage(family, name) {
+            let Some(style) = self.get(family, &name) else {
+                continue;
+            };
+            let p = &style.properties;
+            resolved.bold = resolved.bold.or(p.bold);
+            resolved.italic = resolved.italic.or(p.italic);
+            resolved.monospace = resolved.monospace.or(p.monospace);
+            resolved.superscript = resolved.superscript.or(p.superscript);
+            resolved.subscript = resolved.subscript.or(p.subscript);
+            resolved.highlight = resolved.highlight.or(p.highlight);
+            resolved.page_break_before = resolved.page_break_before.or(p.page_break_before);
+        }
+        Formatting {
+            bold: resolved.bold.unwrap_or(false),
+            italic: resolved.italic.unwrap_or(false),
+            monospace: resolved.monospace.unwrap_or(false),
+            superscript: resolved.superscript.unwrap_or(false),
+            subscript: resolved.subscript.unwrap_or(false),
+            highlight: resolved.highlight.unwrap_or(false),
+            page_break_before: resolved.page_break_before.unwrap_or(false),
+        }
+    }
+}
+
+/// Parse a `style:style` element
+fn parse_style(element: &Element) -> Option<OdtStyle> {
+    let name = element.attribute("style:name")?.to_string();
+    let mut properties = Properties::default();
+
+    // Common styles with formatting by name, for styles declared without it
+    match name.as_str() {
+        "Strong_20_Emphasis" => properties.bold = Some(true),
+        "Emphasis" => properties.italic = Some(true),
+        "Source_20_Text" | "Preformatted_20_Text" => properties.monospace = Some(true),
+        _ => {}
+    }
+
+    for props in element.elements() {
+        match props.name.as_str() {
+            "style:text-properties" => read_text_properties(props, &mut properties),
+            "style:paragraph-properties" => {
+                if let Some(page) = props
+                    .attribute("fo:break-before")
+                    .map(|value| value == "page")
+                {
+                    properties.page_break_before = Some(page);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(OdtStyle {
+        name,
+        display_name: element.attribute("style:display-name").map(String::from),
+        family: element
+            .attribute("style:family")
+            .unwrap_or_default()
+            .to_string(),
+        parent: element
+            .attribute("style:parent-style-name")
+            .map(String::from),
+        outline_level: element
+            .attribute("style:default-outline-level")
+            .and_then(|level| level.parse().ok()),
+        properties,
+    })
+}
+
+/// Read the formatting of a `style:text-properties` element
+fn read_text_properties(props: &Element, properties: &mut Properties) {
+    if let Some(weight) = props.attribute("fo:font-weight") {
+        properties.bold = Some(weight == "bold" || weight.parse::<u32>().is_ok_and(|w| w >= 600));
+    }
+    if let Some(style) = props.attribute("fo:font-style") {
+        properties.italic = Some(style == "italic" || style == "oblique");
+    }
+    let font = props
+        .attribute("style:font-name")
+        .or_else(|| props.attribute("fo:font-family"));
+    if let Some(font) = font {
+        let font = font.to_ascii_lowercase();
+        properties.monospace = Some(
+            ["mono", "courier", "consolas", "code"]
+                .iter()
+                .any(|family| font.contains(family)),
+        );
+    }
+    if props.attribute("style:font-pitch") == Some("fixed") {
+        properties.monospace = Some(true);
+    }
+    if let Some(position) = props.attribute("style:text-position") {
+        let position = position.split_whitespace().next().unwrap_or_default();
+        let raised = position == "super"
+            || position
+                .trim_end_matches('%')
+                .parse::<f32>()
+                .is_ok_and(|p| p > 0.0);
+        let lowered = position == "sub"
+            || position
+                .trim_end_matches('%')
+                .parse::<f32>()
+                .is_ok_and(|p| p < 0.0);
+        properties.superscript = Some(raised);
+        properties.subscript = Some(lowered);
+    }
+    if let Some(color) = props.attribute("fo:background-color") {
+        properties.highlight = Some(color != "transparent");
+    }
+}
+
+/// Numbering of the first level of a `text:list-style`
+fn parse_list_numbering(element: &Element) -> ListNumbering {
+    let first = element
+        .elements()
+        .find(|level| level.attribute("text:level").unwrap_or("1") == "1");
+    match first {
+        Some(level) if level.name == "text:list-level-style-number" => ListNumbering::Number(
+            level
+                .attribute("style:num-format")
+                .unwrap_or("1")
+                .to_string(),
+        ),
+        _ => ListNumbering::Bullet,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-styles xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0">
+  <office:styles>
+    <style:style style:name="Standard" style:family="paragraph"/>
+    <style:style style:name="Titre_20_1" style:display-name="Titre 1" style:family="paragraph" style:parent-style-name="Standard" style:default-outline-level="1">
+      <style:text-properties fo:font-weight="bold"/>
+    </style:style>
+    <style:style style:name="Code" style:family="text">
+      <style:text-properties style:font-name="DejaVu Sans Mono"/>
+    </style:style>
+  </office:styles>
+  <office:automatic-styles>
+    <style:style style:name="T1" style:family="text" style:parent-style-name="Code">
+      <style:text-properties fo:font-style="italic" style:text-position="super 58%"/>
+    </style:style>
+    <style:style style:name="P1" style:family="paragraph" style:parent-style-name="Standard">
+      <style:paragraph-properties fo:break-before="page"/>
+    </style:style>
+    <text:list-style style:name="L1">
+      <text:list-level-style-number text:level="1" style:num-format="a"/>
+      <text:list-level-style-bullet text:level="2" text:bullet-char="-"/>
+    </text:list-style>
+    <text:list-style style:name="L2">
+      <text:list-level-style-bullet text:level="1" text:bullet-char="-"/>
+    </text:list-style>
+  </office:automatic-styles>
+</office:document-styles>"#;
+
+    #[test]
+    fn test_heading_style_by_outline_level() {
+        let styles = OdtStyles::parse(STYLES.as_bytes()).unwrap();
+        assert_eq!(styles.heading_style(1), Some("Titre_20_1"));
+        assert_eq!(styles.heading_style(2), None);
+        assert_eq!(
+            styles
+                .get("paragraph", "Titre_20_1")
+                .unwrap()
+                .display_name
+                .as_deref(),
+            Some("Titre 1")
+        );
+    }
+
+    #[test]
+    fn test_formatting_resolves_parents() {
+        let styles = OdtStyles::parse(STYLES.as_bytes()).unwrap();
+        let t1 = styles.formatting("text", "T1");
+        assert!(t1.italic && t1.superscript && t1.monospace);
+        assert!(!t1.bold && !t1.subscript);
+        assert!(styles.formatting("paragraph", "P1").page_break_before);
+        assert!(styles.inherits("paragraph", "P1", "Standard"));
+    }
+
+    #[test]
+    fn test_list_numbering() {
+        let styles = OdtStyles::parse(STYLES.as_bytes()).unwrap();
+        assert_eq!(
+            styles.list_numbering("L1"),
+            Some(&ListNumbering::Number("a".to_string()))
+        );
+        assert_eq!(styles.list_numbering("L2"), Some(&ListNumbering::Bullet));
+    }
+}
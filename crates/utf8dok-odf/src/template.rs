@@ -0,0 +1,135 @@
+//! Template loader for OTT/ODT templates
+//!
+//! A template supplies the common styles (`styles.xml`), page layout and
+//! settings of the generated document; the writer replaces its content.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use utf8dok_odf::OdtTemplate;
+//!
+//! let mut template = OdtTemplate::load("corporate.ott")?;
+//! let styles = template.get_styles()?;
+//! println!("Heading 1 style: {:?}", styles.heading_style(1));
+//! ```
+
+use std::path::Path;
+
+use crate::error::Result;
+use crate::package::OdfPackage;
+use crate::styles::OdtStyles;
+
+/// A Writer template (.ott) wrapper providing template-specific operations
+#[derive(Debug)]
+pub struct OdtTemplate {
+    /// The underlying ODF package
+    package: OdfPackage,
+    /// Parsed common styles (cached)
+    styles: Option<OdtStyles>,
+}
+
+impl OdtTemplate {
+    /// Load a template from a file path
+    ///
+    /// # Arguments
+    /// * `path` - Path to the .ott or .odt file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_package(OdfPackage::open(path)?)
+    }
+
+    /// Load a template from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_package(OdfPackage::from_reader(std::io::Cursor::new(bytes))?)
+    }
+
+    fn from_package(package: OdfPackage) -> Result<Self> {
+        package.ensure_text()?;
+        Ok(Self {
+            package,
+            styles: None,
+        })
+    }
+
+    /// Get the common styles of the template
+    ///
+    /// Styles are cached after first parse. A template without
+    /// `styles.xml` has no styles.
+    pub fn get_styles(&mut self) -> Result<&OdtStyles> {
+        if self.styles.is_none() {
+            let styles = match self.package.styles_xml() {
+                Some(xml) => OdtStyles::parse(xml)?,
+                None => OdtStyles::default(),
+            };
+            self.styles = Some(styles);
+        }
+        Ok(self.styles.as_ref().unwrap())
+    }
+
+    /// Get a reference to the underlying package
+    pub fn package(&self) -> &OdfPackage {
+        &self.package
+    }
+
+    /// Get a mutable reference to the underlying package
+    pub fn package_mut(&mut self) -> &mut OdfPackage {
+        &mut self.package
+    }
+
+    /// Consume the template and return the underlying package
+    pub fn into_package(self) -> OdfPackage {
+        self.package
+    }
+
+    /// Check if the template has a utf8dok manifest (round-trip document)
+    pub fn has_manifest(&self) -> bool {
+        self.package.has_utf8dok_file("manifest.json")
+    }
+
+    /// Check if a paragraph or text style exists in the template
+    pub fn has_style(&mut self, name: &str) -> Result<bool> {
+        let styles = self.get_styles()?;
+        Ok(styles.contains("paragraph", name) || styles.contains("text", name))
+    }
+
+    /// Get the paragraph style names available in the template
+    pub fn paragraph_style_names(&mut self) -> Result<Vec<String>> {
+        let styles = self.get_styles()?;
+        Ok(styles.paragraph_styles().map(|s| s.name.clone()).collect())
+    }
+
+    /// Get the heading style names, by level from 1
+    pub fn heading_style_names(&mut self) -> Result<Vec<String>> {
+        let styles = self.get_styles()?;
+        Ok((1..=10)
+            .map_while(|level| styles.heading_style(level).map(String::from))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_minimal_template;
+
+    #[test]
+    fn test_load_from_bytes() {
+        let mut template = OdtTemplate::from_bytes(&create_minimal_template()).unwrap();
+        assert!(template.has_style("Text_20_body").unwrap());
+        assert!(!template.has_style("NonExistent").unwrap());
+        assert!(!template.has_manifest());
+    }
+
+    #[test]
+    fn test_heading_style_names() {
+        let mut template = OdtTemplate::from_bytes(&create_minimal_template()).unwrap();
+        assert_eq!(
+            template.heading_style_names().unwrap(),
+            vec!["Heading_20_1".to_string(), "Heading_20_2".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_load_from_invalid_bytes() {
+        assert!(OdtTemplate::from_bytes(b"This is not a ZIP file").is_err());
+    }
+}
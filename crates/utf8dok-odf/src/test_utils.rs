@@ -0,0 +1,97 @@
+//! Shared test utilities for utf8dok-odf
+//!
+//! This module provides common fixtures and helpers used across tests.
+
+use std::io::{Cursor, Read, Write};
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+use zip::ZipWriter;
+
+/// Create a minimal valid OTT template for testing
+///
+/// The template has a body text style, two heading levels in a corporate
+/// font and a page layout, but none of the other styles the writer uses.
+///
+/// # Example
+/// ```ignore
+/// use utf8dok_odf::test_utils::create_minimal_template;
+/// let template = create_minimal_template();
+/// ```
+pub fn create_minimal_template() -> Vec<u8> {
+    let mut buffer = Cursor::new(Vec::new());
+    let mut zip = ZipWriter::new(&mut buffer);
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    zip.start_file("mimetype", stored).unwrap();
+    zip.write_all(b"application/vnd.oasis.opendocument.text-template")
+        .unwrap();
+
+    zip.start_file("META-INF/manifest.xml", stored).unwrap();
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.3">
+ <manifest:file-entry manifest:full-path="/" manifest:version="1.3" manifest:media-type="application/vnd.oasis.opendocument.text-template"/>
+ <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+ <manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>"#,
+    )
+    .unwrap();
+
+    zip.start_file("content.xml", stored).unwrap();
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" office:version="1.3">
+  <office:body>
+    <office:text>
+      <text:p text:style-name="Text_20_body">Template placeholder</text:p>
+    </office:text>
+  </office:body>
+</office:document-content>"#,
+    )
+    .unwrap();
+
+    zip.start_file("styles.xml", stored).unwrap();
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-styles xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" office:version="1.3">
+  <office:styles>
+    <style:style style:name="Standard" style:family="paragraph" style:class="text"/>
+    <style:style style:name="Text_20_body" style:display-name="Text body" style:family="paragraph" style:parent-style-name="Standard" style:class="text">
+      <style:paragraph-properties fo:margin-bottom="0.25cm"/>
+    </style:style>
+    <style:style style:name="Heading_20_1" style:display-name="Heading 1" style:family="paragraph" style:parent-style-name="Standard" style:default-outline-level="1" style:class="text">
+      <style:text-properties fo:font-family="Corporate Sans" fo:font-size="18pt" fo:font-weight="bold"/>
+    </style:style>
+    <style:style style:name="Heading_20_2" style:display-name="Heading 2" style:family="paragraph" style:parent-style-name="Standard" style:default-outline-level="2" style:class="text">
+      <style:text-properties fo:font-family="Corporate Sans" fo:font-size="14pt" fo:font-weight="bold"/>
+    </style:style>
+  </office:styles>
+  <office:automatic-styles>
+    <style:page-layout style:name="pm1">
+      <style:page-layout-properties fo:page-width="21cm" fo:page-height="29.7cm"/>
+    </style:page-layout>
+  </office:automatic-styles>
+  <office:master-styles>
+    <style:master-page style:name="Standard" style:page-layout-name="pm1"/>
+  </office:master-styles>
+</office:document-styles>"#,
+    )
+    .unwrap();
+
+    zip.finish().unwrap();
+    buffer.into_inner()
+}
+
+/// Extract a file from an ODT package as a string
+pub fn extract_file(odt: &[u8], path: &str) -> Option<String> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(odt)).ok()?;
+    let mut file = zip.by_name(path).ok()?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).ok()?;
+    Some(contents)
+}
+
+/// Extract content.xml from an ODT package
+pub fn extract_content_xml(odt: &[u8]) -> String {
+    extract_file(odt, "content.xml").expect("content.xml should exist")
+}
@@ -0,0 +1,1496 @@
+//! ODT Writer
+//!
+//! This module writes `utf8dok_ast::Document` to OpenDocument Text using a
+//! template. The template keeps its page layout, settings and common styles;
+//! the writer replaces `content.xml` and `meta.xml`, and adds the common
+//! styles the content refers to when the template lacks them.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use utf8dok_odf::{OdtTemplate, OdtWriter};
+//! use utf8dok_ast::Document;
+//!
+//! let doc = Document::new();
+//! let template = OdtTemplate::load("template.ott")?;
+//! let output = OdtWriter::new().generate_with_template(&doc, template)?;
+//! std::fs::write("output.odt", output)?;
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+use utf8dok_ast::{
+    Admonition, AdmonitionType, Block, CellStyle, Document, FormatType, Heading, Inline, List,
+    ListType, LiteralBlock, NumberingStyle, Paragraph, QuoteBlock, Sidebar, Table, TableCell,
+};
+use utf8dok_ooxml::{ElementMeta, Manifest, StyleContract, MANIFEST_PATH};
+
+use crate::error::Result;
+use crate::package::{OdfPackage, MIMETYPE_TEXT};
+use crate::styles::OdtStyles;
+use crate::template::OdtTemplate;
+use crate::xml::{escape_attr, escape_text, namespace_declarations, nesting, Nesting};
+
+/// Default image frame size (2" by 1.5", as in the DOCX output)
+const IMAGE_WIDTH: &str = "2in";
+const IMAGE_HEIGHT: &str = "1.5in";
+
+/// Automatic styles every document declares in content.xml
+const AUTOMATIC_STYLES: &str = r##"<style:style style:name="U_Highlight" style:family="text"><style:text-properties fo:background-color="#ffff00"/></style:style>
+<style:style style:name="U_Superscript" style:family="text"><style:text-properties style:text-position="super 58%"/></style:style>
+<style:style style:name="U_Subscript" style:family="text"><style:text-properties style:text-position="sub 58%"/></style:style>
+<style:style style:name="U_PageBreak" style:family="paragraph" style:parent-style-name="Standard"><style:paragraph-properties fo:break-before="page"/></style:style>
+<style:style style:name="U_Sidebar" style:family="section"><style:section-properties fo:background-color="#f3f3f3"/></style:style>
+<style:style style:name="U_Table" style:family="table"><style:table-properties style:width="17cm" table:align="margins"/></style:style>
+"##;
+
+/// Automatic list styles: name, first-level markup and level markup
+const LIST_STYLES: &[(&str, Option<&str>)] = &[
+    ("U_Bullet", None),
+    ("U_Arabic", Some("1")),
+    ("U_LowerAlpha", Some("a")),
+    ("U_UpperAlpha", Some("A")),
+    ("U_LowerRoman", Some("i")),
+    ("U_UpperRoman", Some("I")),
+];
+
+/// Common styles the writer refers to: name, parent and definition
+///
+/// Definitions are added to the template's styles.xml for styles it does
+/// not define, so the output looks right with a bare template. Names are
+/// the ones LibreOffice uses, so its own templates need none of them.
+const COMMON_STYLES: &[(&str, Option<&str>, &str)] = &[
+    (
+        "Standard",
+        None,
+        r#"<style:style style:name="Standard" style:family="paragraph" style:class="text"/>"#,
+    ),
+    (
+        "Text_20_body",
+        Some("Standard"),
+        r#"<style:style style:name="Text_20_body" style:display-name="Text body" style:family="paragraph" style:parent-style-name="Standard" style:class="text"><style:paragraph-properties fo:margin-top="0cm" fo:margin-bottom="0.247cm" fo:line-height="115%"/></style:style>"#,
+    ),
+    (
+        "Heading",
+        Some("Standard"),
+        r#"<style:style style:name="Heading" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="Text_20_body" style:class="text"><style:paragraph-properties fo:margin-top="0.423cm" fo:margin-bottom="0.212cm" fo:keep-with-next="always"/><style:text-properties fo:font-size="14pt"/></style:style>"#,
+    ),
+    (
+        "Title",
+        Some("Heading"),
+        r#"<style:style style:name="Title" style:family="paragraph" style:parent-style-name="Heading" style:next-style-name="Text_20_body" style:class="chapter"><style:paragraph-properties fo:text-align="center"/><style:text-properties fo:font-size="28pt" fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "Heading_20_1",
+        Some("Heading"),
+        r#"<style:style style:name="Heading_20_1" style:display-name="Heading 1" style:family="paragraph" style:parent-style-name="Heading" style:next-style-name="Text_20_body" style:default-outline-level="1" style:class="text"><style:text-properties fo:font-size="130%" fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "Heading_20_2",
+        Some("Heading"),
+        r#"<style:style style:name="Heading_20_2" style:display-name="Heading 2" style:family="paragraph" style:parent-style-name="Heading" style:next-style-name="Text_20_body" style:default-outline-level="2" style:class="text"><style:text-properties fo:font-size="115%" fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "Heading_20_3",
+        Some("Heading"),
+        r#"<style:style style:name="Heading_20_3" style:display-name="Heading 3" style:family="paragraph" style:parent-style-name="Heading" style:next-style-name="Text_20_body" style:default-outline-level="3" style:class="text"><style:text-properties fo:font-size="101%" fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "Heading_20_4",
+        Some("Heading"),
+        r#"<style:style style:name="Heading_20_4" style:display-name="Heading 4" style:family="paragraph" style:parent-style-name="Heading" style:next-style-name="Text_20_body" style:default-outline-level="4" style:class="text"><style:text-properties fo:font-size="95%" fo:font-style="italic" fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "Heading_20_5",
+        Some("Heading"),
+        r#"<style:style style:name="Heading_20_5" style:display-name="Heading 5" style:family="paragraph" style:parent-style-name="Heading" style:next-style-name="Text_20_body" style:default-outline-level="5" style:class="text"><style:text-properties fo:font-size="85%" fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "Heading_20_6",
+        Some("Heading"),
+        r#"<style:style style:name="Heading_20_6" style:display-name="Heading 6" style:family="paragraph" style:parent-style-name="Heading" style:next-style-name="Text_20_body" style:default-outline-level="6" style:class="text"><style:text-properties fo:font-size="85%" fo:font-style="italic" fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "Preformatted_20_Text",
+        Some("Standard"),
+        r##"<style:style style:name="Preformatted_20_Text" style:display-name="Preformatted Text" style:family="paragraph" style:parent-style-name="Standard" style:class="html"><style:paragraph-properties fo:margin-top="0cm" fo:margin-bottom="0.247cm" fo:background-color="#f5f5f5" fo:padding="0.1cm"/><style:text-properties fo:font-family="'Liberation Mono'" style:font-family-generic="modern" style:font-pitch="fixed" fo:font-size="10pt"/></style:style>"##,
+    ),
+    (
+        "Quotations",
+        Some("Standard"),
+        r#"<style:style style:name="Quotations" style:family="paragraph" style:parent-style-name="Standard" style:class="html"><style:paragraph-properties fo:margin-left="1cm" fo:margin-right="1cm" fo:margin-top="0cm" fo:margin-bottom="0.247cm"/><style:text-properties fo:font-style="italic"/></style:style>"#,
+    ),
+    (
+        "Caption",
+        Some("Standard"),
+        r#"<style:style style:name="Caption" style:family="paragraph" style:parent-style-name="Standard" style:class="extra"><style:paragraph-properties fo:margin-top="0.212cm" fo:margin-bottom="0.212cm" fo:keep-with-next="always"/><style:text-properties fo:font-size="10pt" fo:font-style="italic"/></style:style>"#,
+    ),
+    (
+        "Table_20_Contents",
+        Some("Standard"),
+        r#"<style:style style:name="Table_20_Contents" style:display-name="Table Contents" style:family="paragraph" style:parent-style-name="Standard" style:class="extra"/>"#,
+    ),
+    (
+        "Table_20_Heading",
+        Some("Table_20_Contents"),
+        r#"<style:style style:name="Table_20_Heading" style:display-name="Table Heading" style:family="paragraph" style:parent-style-name="Table_20_Contents" style:class="extra"><style:paragraph-properties fo:text-align="center"/><style:text-properties fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "List_20_Heading",
+        Some("Standard"),
+        r#"<style:style style:name="List_20_Heading" style:display-name="List Heading" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="List_20_Contents" style:class="html"><style:text-properties fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "List_20_Contents",
+        Some("Standard"),
+        r#"<style:style style:name="List_20_Contents" style:display-name="List Contents" style:family="paragraph" style:parent-style-name="Standard" style:class="html"><style:paragraph-properties fo:margin-left="1cm" fo:margin-bottom="0.247cm"/></style:style>"#,
+    ),
+    (
+        "Footnote",
+        Some("Standard"),
+        r#"<style:style style:name="Footnote" style:family="paragraph" style:parent-style-name="Standard" style:class="extra"><style:paragraph-properties fo:margin-left="0.6cm" fo:text-indent="-0.6cm"/><style:text-properties fo:font-size="10pt"/></style:style>"#,
+    ),
+    (
+        "Horizontal_20_Line",
+        Some("Standard"),
+        r#"<style:style style:name="Horizontal_20_Line" style:display-name="Horizontal Line" style:family="paragraph" style:parent-style-name="Standard" style:next-style-name="Text_20_body" style:class="html"><style:paragraph-properties fo:margin-top="0cm" fo:margin-bottom="0.5cm" fo:border-bottom="0.06pt solid #808080" fo:padding="0cm"/><style:text-properties fo:font-size="6pt"/></style:style>"#,
+    ),
+    (
+        "Strong_20_Emphasis",
+        None,
+        r#"<style:style style:name="Strong_20_Emphasis" style:display-name="Strong Emphasis" style:family="text"><style:text-properties fo:font-weight="bold"/></style:style>"#,
+    ),
+    (
+        "Emphasis",
+        None,
+        r#"<style:style style:name="Emphasis" style:family="text"><style:text-properties fo:font-style="italic"/></style:style>"#,
+    ),
+    (
+        "Source_20_Text",
+        None,
+        r#"<style:style style:name="Source_20_Text" style:display-name="Source Text" style:family="text"><style:text-properties fo:font-family="'Liberation Mono'" style:font-family-generic="modern" style:font-pitch="fixed"/></style:style>"#,
+    ),
+    (
+        "Internet_20_link",
+        None,
+        r##"<style:style style:name="Internet_20_link" style:display-name="Internet link" style:family="text"><style:text-properties fo:color="#000080" style:text-underline-style="solid" style:text-underline-width="auto" style:text-underline-color="font-color"/></style:style>"##,
+    ),
+    (
+        "Visited_20_Internet_20_Link",
+        None,
+        r##"<style:style style:name="Visited_20_Internet_20_Link" style:display-name="Visited Internet Link" style:family="text"><style:text-properties fo:color="#800000" style:text-underline-style="solid" style:text-underline-width="auto" style:text-underline-color="font-color"/></style:style>"##,
+    ),
+];
+
+/// ODT Writer for generating ODT files from AST
+pub struct OdtWriter {
+    /// Body XML output buffer
+    output: String,
+    /// Automatic styles of the body beyond [`AUTOMATIC_STYLES`] (table columns)
+    automatic_styles: String,
+    /// Pictures to embed (package path, bytes)
+    pictures: Vec<(String, Vec<u8>)>,
+    /// Package paths of embedded pictures by image source
+    picture_paths: HashMap<String, String>,
+    /// Directory image sources are resolved against, for embedding
+    image_dir: Option<PathBuf>,
+    /// Document manifest
+    manifest: Manifest,
+    /// Style contract for round-trip fidelity (ADR-007)
+    style_contract: Option<StyleContract>,
+    /// Original AsciiDoc source (for self-contained ODT)
+    source_text: Option<String>,
+    /// Configuration TOML (for self-contained ODT)
+    config_text: Option<String>,
+    /// Common styles of the template
+    styles: OdtStyles,
+    /// Common styles referred to by the content
+    used_styles: BTreeSet<&'static str>,
+    /// Paragraph style of plain paragraphs in the current context
+    body_style: &'static str,
+    /// Whether the content being written is inside a list item, which
+    /// admits only paragraphs, headings and lists
+    in_list_item: bool,
+    /// Footnote numbers by AsciiDoc footnote id (for `footnote:id[]` reuse)
+    footnote_ids: HashMap<String, usize>,
+    /// Number of footnotes so far
+    footnotes: usize,
+    /// Number of tables so far (names tables)
+    tables: usize,
+    /// Number of images so far (names frames)
+    images: usize,
+    /// Number of sidebars so far (names sections)
+    sidebars: usize,
+}
+
+impl Default for OdtWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OdtWriter {
+    /// Create a new OdtWriter
+    pub fn new() -> Self {
+        Self {
+            output: String::new(),
+            automatic_styles: String::new(),
+            pictures: Vec::new(),
+            picture_paths: HashMap::new(),
+            image_dir: None,
+            manifest: Manifest::new(),
+            style_contract: None,
+            source_text: None,
+            config_text: None,
+            styles: OdtStyles::default(),
+            used_styles: BTreeSet::new(),
+            body_style: "Text_20_body",
+            in_list_item: false,
+            footnote_ids: HashMap::new(),
+            footnotes: 0,
+            tables: 0,
+            images: 0,
+            sidebars: 0,
+        }
+    }
+
+    /// Set the original AsciiDoc source text to embed in the ODT
+    ///
+    /// This enables round-trip editing - the source can be extracted later.
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source_text = Some(source.into());
+    }
+
+    /// Set the configuration TOML to embed in the ODT
+    pub fn set_config(&mut self, config: impl Into<String>) {
+        self.config_text = Some(config.into());
+    }
+
+    /// Set both source and config at once
+    pub fn set_embedded_content(&mut self, source: impl Into<String>, config: impl Into<String>) {
+        self.source_text = Some(source.into());
+        self.config_text = Some(config.into());
+    }
+
+    /// Set the style contract for round-trip fidelity (ADR-007)
+    ///
+    /// When set, bookmarks and cross references use the original bookmark
+    /// names recorded at extraction.
+    pub fn set_style_contract(&mut self, contract: StyleContract) {
+        self.style_contract = Some(contract);
+    }
+
+    /// Set the directory relative image paths are resolved against
+    ///
+    /// Images found there are embedded in the package. Other images are
+    /// linked relative to the location of the ODT file.
+    pub fn set_image_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.image_dir = Some(dir.into());
+    }
+
+    /// Generate an ODT file from an AST Document using a template
+    ///
+    /// # Arguments
+    ///
+    /// * `doc` - The AST document to convert
+    /// * `template` - The template OTT or ODT file as bytes
+    pub fn generate(doc: &Document, template: &[u8]) -> Result<Vec<u8>> {
+        Self::new().generate_with_template(doc, OdtTemplate::from_bytes(template)?)
+    }
+
+    /// Generate an ODT file using instance settings (source, config, contract)
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use utf8dok_odf::{OdtTemplate, OdtWriter};
+    ///
+    /// let mut writer = OdtWriter::new();
+    /// writer.set_source(&adoc_content);
+    /// writer.set_config(&config_toml);
+    ///
+    /// let template = OdtTemplate::load("template.ott")?;
+    /// let output = writer.generate_with_template(&doc, template)?;
+    /// ```
+    pub fn generate_with_template(
+        mut self,
+        doc: &Document,
+        mut template: OdtTemplate,
+    ) -> Result<Vec<u8>> {
+        self.styles = template.get_styles()?.clone();
+        let mut package = template.into_package();
+
+        // Parse existing manifest if present
+        if let Some(json) = package.read_utf8dok_string("manifest.json") {
+            self.manifest = Manifest::from_json(&json)?;
+        }
+
+        // The template's thumbnail and embedded source describe the template
+        package.remove("Thumbnails/thumbnail.png");
+        package.remove("utf8dok/source.adoc");
+        package.remove("utf8dok/utf8dok.toml");
+
+        let content_xml = self.generate_content_xml(doc);
+        package.set_string("content.xml", content_xml);
+        package.set_string("mimetype", MIMETYPE_TEXT);
+
+        let styles_xml = self.complete_styles_xml(package.get_string("styles.xml"));
+        package.set_string("styles.xml", styles_xml);
+        package.set_string("meta.xml", meta_xml(doc));
+
+        for (path, data) in &self.pictures {
+            package.set(path.clone(), data.clone());
+        }
+
+        // Write embedded content (source, config) for self-contained ODT
+        self.write_embedded_content(&mut package);
+        if !self.manifest.is_empty() {
+            package.set_string(MANIFEST_PATH, self.manifest.to_json()?);
+        }
+
+        let mut output = Cursor::new(Vec::new());
+        package.write_to(&mut output)?;
+        Ok(output.into_inner())
+    }
+
+    /// Write embedded content (source, config) to the package
+    fn write_embedded_content(&mut self, package: &mut OdfPackage) {
+        let entries = [
+            (
+                "source",
+                "utf8dok/source.adoc",
+                self.source_text.as_ref(),
+                "Original AsciiDoc source",
+            ),
+            (
+                "config",
+                "utf8dok/utf8dok.toml",
+                self.config_text.as_ref(),
+                "utf8dok configuration",
+            ),
+        ];
+        for (id, path, text, description) in entries {
+            let Some(text) = text else {
+                continue;
+            };
+            package.set_string(path, text.clone());
+
+            let mut hasher = Sha256::new();
+            hasher.update(text.as_bytes());
+            self.manifest.add_element(
+                id.to_string(),
+                ElementMeta::new(id)
+                    .with_source(path.to_string())
+                    .with_hash(format!("{:x}", hasher.finalize()))
+                    .with_description(description.to_string()),
+            );
+        }
+    }
+
+    /// Generate content.xml for a document
+    fn generate_content_xml(&mut self, doc: &Document) -> String {
+        if let Some(title) = &doc.metadata.title {
+            let style = self.common("Title");
+            self.output.push_str(&format!(
+                "<text:p text:style-name=\"{}\">{}</text:p>\n",
+                style,
+                escape_text(title)
+            ));
+        }
+        self.write_blocks(&doc.blocks);
+
+        // Automatic paragraph styles derive from Standard
+        self.common("Standard");
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <office:document-content{} office:version=\"1.3\">\n\
+             <office:automatic-styles>\n",
+            namespace_declarations()
+        );
+        xml.push_str(AUTOMATIC_STYLES);
+        for (name, format) in LIST_STYLES {
+            xml.push_str(&list_style(name, *format));
+        }
+        xml.push_str(&self.automatic_styles);
+        xml.push_str("</office:automatic-styles>\n<office:body>\n<office:text>\n");
+        xml.push_str(&self.output);
+        xml.push_str("</office:text>\n</office:body>\n</office:document-content>\n");
+        xml
+    }
+
+    /// Add the definitions of used common styles the template lacks
+    fn complete_styles_xml(&self, styles_xml: Option<String>) -> String {
+        let mut missing = String::new();
+        for (name, _, definition) in COMMON_STYLES {
+            if !self.used_styles.contains(name) {
+                continue;
+            }
+            let family = if definition.contains("style:family=\"text\"") {
+                "text"
+            } else {
+                "paragraph"
+            };
+            if !self.styles.contains(family, name) {
+                missing.push_str(definition);
+                missing.push('\n');
+            }
+        }
+
+        let Some(mut xml) = styles_xml else {
+            return format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                 <office:document-styles{} office:version=\"1.3\">\n\
+                 <office:styles>\n{}</office:styles>\n\
+                 </office:document-styles>\n",
+                namespace_declarations(),
+                missing
+            );
+        };
+        if missing.is_empty() {
+            return xml;
+        }
+
+        // The definitions use the standard prefixes
+        if let Some(root_end) = root_start_tag_end(&xml) {
+            let declarations: String = ["office", "style", "text", "fo"]
+                .iter()
+                .filter(|prefix| !xml[..root_end].contains(&format!("xmlns:{}=", prefix)))
+                .map(|prefix| {
+                    let all = namespace_declarations();
+                    let start = all.find(&format!(" xmlns:{}=", prefix)).unwrap_or(0);
+                    let end = all[start + 1..]
+                        .find(" xmlns:")
+                        .map_or(all.len(), |i| start + 1 + i);
+                    all[start..end].to_string()
+                })
+                .collect();
+            let insert_at = if xml[..root_end].ends_with("/>") {
+                root_end - 2
+            } else {
+                root_end - 1
+            };
+            xml.insert_str(insert_at, &declarations);
+        }
+
+        if let Some(end) = xml.find("</office:styles>") {
+            xml.insert_str(end, &missing);
+        } else if let Some(empty) = xml.find("<office:styles/>") {
+            xml.replace_range(
+                empty..empty + "<office:styles/>".len(),
+                &format!("<office:styles>\n{}</office:styles>", missing),
+            );
+        } else if let Some(end) = xml.rfind("</office:document-styles>") {
+            xml.insert_str(
+                end,
+                &format!("<office:styles>\n{}</office:styles>\n", missing),
+            );
+        }
+        xml
+    }
+
+    /// Mark a common style (and its parents) used, returning its name
+    fn common(&mut self, name: &'static str) -> &'static str {
+        let mut current = Some(name);
+        while let Some(style) = current {
+            self.used_styles.insert(style);
+            current = COMMON_STYLES
+                .iter()
+                .find(|(n, _, _)| *n == style)
+                .and_then(|(_, parent, _)| *parent);
+        }
+        name
+    }
+
+    /// Resolve an anchor name to the original bookmark name
+    fn resolve_anchor_name(&self, semantic_anchor: &str) -> String {
+        self.style_contract
+            .as_ref()
+            .and_then(|contract| contract.get_word_bookmark(semantic_anchor))
+            .unwrap_or(semantic_anchor)
+            .to_string()
+    }
+
+    // =========================================================================
+    // Blocks
+    // =========================================================================
+
+    fn write_blocks(&mut self, blocks: &[Block]) {
+        for block in blocks {
+            self.write_block(block);
+        }
+    }
+
+    fn write_block(&mut self, block: &Block) {
+        match block {
+            Block::Paragraph(para) => self.write_paragraph(para, None),
+            Block::Heading(heading) => self.write_heading(heading),
+            Block::List(list) => self.write_list(list),
+            Block::Table(table) => self.write_table(table),
+            Block::Admonition(admonition) => self.write_admonition(admonition),
+            Block::Literal(literal) => self.write_literal(literal),
+            Block::Break(_) => {
+                self.common("Standard");
+                self.output
+                    .push_str("<text:p text:style-name=\"U_PageBreak\"/>\n");
+            }
+            Block::ThematicBreak => {
+                let style = self.common("Horizontal_20_Line");
+                self.output
+                    .push_str(&format!("<text:p text:style-name=\"{}\"/>\n", style));
+            }
+            Block::Open(open) => {
+                self.write_caption(open.title.as_deref());
+                self.write_blocks(&open.blocks);
+            }
+            Block::Sidebar(sidebar) => self.write_sidebar(sidebar),
+            Block::Quote(quote) => self.write_quote(quote),
+            // Comments are not rendered
+            Block::Comment(_) => {}
+        }
+    }
+
+    /// Write a block title as a caption paragraph
+    fn write_caption(&mut self, title: Option<&str>) {
+        if let Some(title) = title {
+            let style = self.common("Caption");
+            self.output.push_str(&format!(
+                "<text:p text:style-name=\"{}\">{}</text:p>\n",
+                style,
+                escape_text(title)
+            ));
+        }
+    }
+
+    /// Write a paragraph, with `prefix` inlines before its content
+    fn write_paragraph(&mut self, para: &Paragraph, prefix: Option<&str>) {
+        self.write_caption(para.attributes.get("title").map(String::as_str));
+
+        // An explicit style is kept when the template defines it
+        let style = match para.style_id.as_deref() {
+            Some(id) if self.styles.contains("paragraph", id) => id.to_string(),
+            _ => self.common(self.body_style).to_string(),
+        };
+        self.output.push_str(&format!(
+            "<text:p text:style-name=\"{}\">",
+            escape_attr(&style)
+        ));
+        if let Some(prefix) = prefix {
+            self.output.push_str(prefix);
+        }
+        self.write_inlines(&para.inlines);
+        self.output.push_str("</text:p>\n");
+    }
+
+    fn write_heading(&mut self, heading: &Heading) {
+        let level = heading.level.clamp(1, 10);
+        let style = match heading.style_id.as_deref() {
+            Some(id) if self.styles.contains("paragraph", id) => id.to_string(),
+            _ => match self.styles.heading_style(level) {
+                Some(name) => name.to_string(),
+                None => self.common(standard_heading(level)).to_string(),
+            },
+        };
+        self.output.push_str(&format!(
+            "<text:h text:style-name=\"{}\" text:outline-level=\"{}\">",
+            escape_attr(&style),
+            level
+        ));
+        if let Some(anchor) = &heading.anchor {
+            let name = self.resolve_anchor_name(anchor);
+            self.output.push_str(&format!(
+                "<text:bookmark text:name=\"{}\"/>",
+                escape_attr(&name)
+            ));
+        }
+        self.write_inlines(&heading.text);
+        self.output.push_str("</text:h>\n");
+    }
+
+    fn write_list(&mut self, list: &List) {
+        if list.list_type == ListType::Description {
+            self.write_description_list(list);
+            return;
+        }
+
+        let style = match (&list.list_type, list.numbering) {
+            (ListType::Unordered, _) => "U_Bullet",
+            (_, Some(NumberingStyle::LowerAlpha)) => "U_LowerAlpha",
+            (_, Some(NumberingStyle::UpperAlpha)) => "U_UpperAlpha",
+            (_, Some(NumberingStyle::LowerRoman)) => "U_LowerRoman",
+            (_, Some(NumberingStyle::UpperRoman)) => "U_UpperRoman",
+            _ => "U_Arabic",
+        };
+        let outer_in_list_item = self.in_list_item;
+        let mut opened = 0;
+        for step in nesting(list.items.iter().map(|item| item.level as usize)) {
+            match step {
+                Nesting::Open => {
+                    if opened == 0 {
+                        self.output
+                            .push_str(&format!("<text:list text:style-name=\"{}\">\n", style));
+                    } else {
+                        self.output.push_str("<text:list>\n");
+                    }
+                    opened += 1;
+                }
+                Nesting::Item(index) => {
+                    let item = &list.items[index];
+                    self.output.push_str("<text:list-item>\n");
+                    self.in_list_item = true;
+                    let checkbox =
+                        item.checked
+                            .map(|checked| if checked { "\u{2612} " } else { "\u{2610} " });
+                    match item.content.split_first() {
+                        Some((Block::Paragraph(first), rest)) => {
+                            self.write_paragraph(first, checkbox);
+                            self.write_blocks(rest);
+                        }
+                        _ => {
+                            // Items start with a paragraph, if only the checkbox
+                            if checkbox.is_some() || item.content.is_empty() {
+                                let style = self.common(self.body_style);
+                                self.output.push_str(&format!(
+                                    "<text:p text:style-name=\"{}\">{}</text:p>\n",
+                                    style,
+                                    checkbox.map(str::trim_end).unwrap_or_default()
+                                ));
+                            }
+                            self.write_blocks(&item.content);
+                        }
+                    }
+                    self.in_list_item = outer_in_list_item;
+                }
+                Nesting::EndItem => self.output.push_str("</text:list-item>\n"),
+                Nesting::Close => self.output.push_str("</text:list>\n"),
+            }
+        }
+    }
+
+    fn write_description_list(&mut self, list: &List) {
+        for item in &list.items {
+            if let Some(term) = &item.term {
+                let style = self.common("List_20_Heading");
+                self.output
+                    .push_str(&format!("<text:p text:style-name=\"{}\">", style));
+                self.write_inlines(term);
+                self.output.push_str("</text:p>\n");
+            }
+            let outer = self.body_style;
+            self.body_style = "List_20_Contents";
+            self.write_blocks(&item.content);
+            self.body_style = outer;
+        }
+    }
+
+    fn write_table(&mut self, table: &Table) {
+        self.tables += 1;
+        let caption = table.caption.as_ref();
+        if let Some(caption) = caption {
+            let style = self.common("Caption");
+            self.output
+                .push_str(&format!("<text:p text:style-name=\"{}\">", style));
+            self.write_inlines(caption);
+            self.output.push_str("</text:p>\n");
+        }
+
+        // List items cannot hold tables: write rows as tab-separated lines
+        if self.in_list_item {
+            let style = self.common(self.body_style);
+            for row in &table.rows {
+                self.output
+                    .push_str(&format!("<text:p text:style-name=\"{}\">", style));
+                for (index, cell) in row.cells.iter().enumerate() {
+                    if index > 0 {
+                        self.output.push_str("<text:tab/>");
+                    }
+                    self.output.push_str(&escape_text(&cell_text(cell)));
+                }
+                self.output.push_str("</text:p>\n");
+            }
+            return;
+        }
+
+        let grid = layout(table);
+        let name = format!("Table{}", self.tables);
+        self.output.push_str(&format!(
+            "<table:table table:name=\"{}\" table:style-name=\"U_Table\">\n",
+            name
+        ));
+
+        let widths: Vec<u32> = table.columns.iter().filter_map(|c| c.width).collect();
+        if widths.len() == grid.columns && grid.columns > 0 {
+            for (index, width) in widths.iter().enumerate() {
+                let column_style = format!("{}.{}", name, column_letter(index));
+                self.automatic_styles.push_str(&format!(
+                    "<style:style style:name=\"{}\" style:family=\"table-column\"><style:table-column-properties style:rel-column-width=\"{}*\"/></style:style>\n",
+                    column_style,
+                    width.max(&1)
+                ));
+                self.output.push_str(&format!(
+                    "<table:table-column table:style-name=\"{}\"/>\n",
+                    column_style
+                ));
+            }
+        } else if grid.columns > 1 {
+            self.output.push_str(&format!(
+                "<table:table-column table:number-columns-repeated=\"{}\"/>\n",
+                grid.columns
+            ));
+        } else {
+            self.output.push_str("<table:table-column/>\n");
+        }
+
+        let header_rows = table.rows.iter().take_while(|row| row.is_header).count();
+        for (index, slots) in grid.rows.iter().enumerate() {
+            if index == 0 && header_rows > 0 {
+                self.output.push_str("<table:table-header-rows>\n");
+            }
+            self.output.push_str("<table:table-row>\n");
+            let header = table.rows[index].is_header;
+            for slot in slots {
+                match slot {
+                    Slot::Cell(cell) => self.write_cell(cell, header),
+                    Slot::Covered => self.output.push_str("<table:covered-table-cell/>\n"),
+                    Slot::Empty => self.output.push_str("<table:table-cell/>\n"),
+                }
+            }
+            self.output.push_str("</table:table-row>\n");
+            if header_rows > 0 && index + 1 == header_rows {
+                self.output.push_str("</table:table-header-rows>\n");
+            }
+        }
+        self.output.push_str("</table:table>\n");
+    }
+
+    fn write_cell(&mut self, cell: &TableCell, header: bool) {
+        let mut attributes = String::from(" office:value-type=\"string\"");
+        if cell.colspan > 1 {
+            attributes.push_str(&format!(
+                " table:number-columns-spanned=\"{}\"",
+                cell.colspan
+            ));
+        }
+        if cell.rowspan > 1 {
+            attributes.push_str(&format!(" table:number-rows-spanned=\"{}\"", cell.rowspan));
+        }
+        self.output
+            .push_str(&format!("<table:table-cell{}>\n", attributes));
+
+        let outer = self.body_style;
+        self.body_style = if header || cell.style == Some(CellStyle::Header) {
+            "Table_20_Heading"
+        } else {
+            "Table_20_Contents"
+        };
+        if cell.content.is_empty() {
+            let style = self.common(self.body_style);
+            self.output
+                .push_str(&format!("<text:p text:style-name=\"{}\"/>\n", style));
+        } else {
+            self.write_blocks(&cell.content);
+        }
+        self.body_style = outer;
+        self.output.push_str("</table:table-cell>\n");
+    }
+
+    /// Write an admonition as its content with a bold label in front
+    fn write_admonition(&mut self, admonition: &Admonition) {
+        if let Some(title) = &admonition.title {
+            let style = self.common("Caption");
+            self.output
+                .push_str(&format!("<text:p text:style-name=\"{}\">", style));
+            self.write_inlines(title);
+            self.output.push_str("</text:p>\n");
+        }
+        let strong = self.common("Strong_20_Emphasis");
+        let label = format!(
+            "<text:span text:style-name=\"{}\">{}:</text:span>",
+            strong,
+            admonition_label(&admonition.admonition_type)
+        );
+        match admonition.content.split_first() {
+            Some((Block::Paragraph(first), rest)) => {
+                self.write_paragraph(first, Some(&format!("{} ", label)));
+                self.write_blocks(rest);
+            }
+            _ => {
+                let style = self.common(self.body_style);
+                self.output.push_str(&format!(
+                    "<text:p text:style-name=\"{}\">{}</text:p>\n",
+                    style, label
+                ));
+                self.write_blocks(&admonition.content);
+            }
+        }
+    }
+
+    /// Write a literal block as one preformatted paragraph
+    ///
+    /// Diagrams are written as their source. Callouts follow their line as
+    /// bold `<n>` markers, which read back as AsciiDoc callouts.
+    fn write_literal(&mut self, literal: &LiteralBlock) {
+        self.write_caption(literal.title.as_deref());
+        let style = self.common("Preformatted_20_Text");
+        self.output
+            .push_str(&format!("<text:p text:style-name=\"{}\">", style));
+        for (index, line) in literal.content.split('\n').enumerate() {
+            let number = index as u32 + 1;
+            if index > 0 {
+                self.output.push_str("<text:line-break/>");
+            }
+            let highlighted = literal.highlight_lines.contains(&number);
+            if highlighted {
+                self.output
+                    .push_str("<text:span text:style-name=\"U_Highlight\">");
+            }
+            self.output.push_str(&preformatted(line));
+            if highlighted {
+                self.output.push_str("</text:span>");
+            }
+            for callout in literal.callouts.iter().filter(|c| c.line == number) {
+                let strong = self.common("Strong_20_Emphasis");
+                self.output.push_str(&format!(
+                    " <text:span text:style-name=\"{}\">&lt;{}&gt;</text:span>",
+                    strong, callout.number
+                ));
+            }
+        }
+        self.output.push_str("</text:p>\n");
+    }
+
+    fn write_sidebar(&mut self, sidebar: &Sidebar) {
+        // List items cannot hold sections
+        if self.in_list_item {
+            self.write_caption(sidebar.title.as_deref());
+            self.write_blocks(&sidebar.blocks);
+            return;
+        }
+        self.sidebars += 1;
+        self.output.push_str(&format!(
+            "<text:section text:style-name=\"U_Sidebar\" text:name=\"Sidebar{}\">\n",
+            self.sidebars
+        ));
+        self.write_caption(sidebar.title.as_deref());
+        if sidebar.blocks.is_empty() {
+            let style = self.common(self.body_style);
+            self.output
+                .push_str(&format!("<text:p text:style-name=\"{}\"/>\n", style));
+        }
+        self.write_blocks(&sidebar.blocks);
+        self.output.push_str("</text:section>\n");
+    }
+
+    fn write_quote(&mut self, quote: &QuoteBlock) {
+        self.write_caption(quote.title.as_deref());
+        let outer = self.body_style;
+        self.body_style = "Quotations";
+        self.write_blocks(&quote.blocks);
+        self.body_style = outer;
+
+        let attribution = match (&quote.attribution, &quote.cite) {
+            (Some(attribution), Some(cite)) => Some(format!("{}, {}", attribution, cite)),
+            (Some(attribution), None) => Some(attribution.clone()),
+            (None, Some(cite)) => Some(cite.clone()),
+            (None, None) => None,
+        };
+        if let Some(attribution) = attribution {
+            let style = self.common("Quotations");
+            self.output.push_str(&format!(
+                "<text:p text:style-name=\"{}\">\u{2014} {}</text:p>\n",
+                style,
+                escape_text(&attribution)
+            ));
+        }
+    }
+
+    // =========================================================================
+    // Inlines
+    // =========================================================================
+
+    fn write_inlines(&mut self, inlines: &[Inline]) {
+        for inline in inlines {
+            self.write_inline(inline);
+        }
+    }
+
+    fn write_inline(&mut self, inline: &Inline) {
+        match inline {
+            Inline::Text(text) => self.output.push_str(&escape_text(text)),
+            Inline::Format(format, inner) => {
+                let style = match format {
+                    FormatType::Bold => self.common("Strong_20_Emphasis"),
+                    FormatType::Italic => self.common("Emphasis"),
+                    FormatType::Monospace => self.common("Source_20_Text"),
+                    FormatType::Highlight => "U_Highlight",
+                    FormatType::Superscript => "U_Superscript",
+                    FormatType::Subscript => "U_Subscript",
+                };
+                self.output
+                    .push_str(&format!("<text:span text:style-name=\"{}\">", style));
+                self.write_inline(inner);
+                self.output.push_str("</text:span>");
+            }
+            Inline::Span(inlines) => self.write_inlines(inlines),
+            Inline::Link(link) => {
+                let href = match link.url.strip_prefix('#') {
+                    Some(anchor) => format!("#{}", self.resolve_anchor_name(anchor)),
+                    None => link.url.clone(),
+                };
+                let style = self.common("Internet_20_link");
+                let visited = self.common("Visited_20_Internet_20_Link");
+                self.output.push_str(&format!(
+                    "<text:a xlink:type=\"simple\" xlink:href=\"{}\" text:style-name=\"{}\" text:visited-style-name=\"{}\">",
+                    escape_attr(&href),
+                    style,
+                    visited
+                ));
+                if link.text.is_empty() {
+                    self.output.push_str(&escape_text(&link.url));
+                } else {
+                    self.write_inlines(&link.text);
+                }
+                self.output.push_str("</text:a>");
+            }
+            Inline::Image(image) => self.write_image(image),
+            Inline::Break => self.output.push_str("<text:line-break/>"),
+            Inline::Anchor(name) => {
+                let name = self.resolve_anchor_name(name);
+                self.output.push_str(&format!(
+                    "<text:bookmark text:name=\"{}\"/>",
+                    escape_attr(&name)
+                ));
+            }
+            Inline::Footnote(footnote) => {
+                // A footnote without text refers back to an earlier one by id
+                if footnote.content.is_empty() {
+                    if let Some(&number) = footnote
+                        .id
+                        .as_ref()
+                        .and_then(|id| self.footnote_ids.get(id))
+                    {
+                        self.output.push_str(&format!(
+                            "<text:note-ref text:note-class=\"footnote\" text:reference-format=\"text\" text:ref-name=\"ftn{0}\">{0}</text:note-ref>",
+                            number
+                        ));
+                    }
+                    return;
+                }
+                self.footnotes += 1;
+                let number = self.footnotes;
+                if let Some(id) = &footnote.id {
+                    self.footnote_ids.insert(id.clone(), number);
+                }
+                let style = self.common("Footnote");
+                self.output.push_str(&format!(
+                    "<text:note text:id=\"ftn{0}\" text:note-class=\"footnote\"><text:note-citation>{0}</text:note-citation><text:note-body><text:p text:style-name=\"{1}\">",
+                    number, style
+                ));
+                self.write_inlines(&footnote.content);
+                self.output
+                    .push_str("</text:p></text:note-body></text:note>");
+            }
+        }
+    }
+
+    fn write_image(&mut self, image: &utf8dok_ast::Image) {
+        self.images += 1;
+        let href = self.image_href(&image.src);
+        self.output.push_str(&format!(
+            "<draw:frame draw:name=\"Image{}\" text:anchor-type=\"as-char\" svg:width=\"{}\" svg:height=\"{}\" draw:z-index=\"0\">",
+            self.images, IMAGE_WIDTH, IMAGE_HEIGHT
+        ));
+        self.output.push_str(&format!(
+            "<draw:image xlink:href=\"{}\" xlink:type=\"simple\" xlink:show=\"embed\" xlink:actuate=\"onLoad\"/>",
+            escape_attr(&href)
+        ));
+        if let Some(alt) = &image.alt {
+            self.output
+                .push_str(&format!("<svg:title>{}</svg:title>", escape_text(alt)));
+        }
+        self.output.push_str("</draw:frame>");
+    }
+
+    /// Reference to an image: a package path when embedded, else a link
+    fn image_href(&mut self, src: &str) -> String {
+        if src.contains("://") || src.starts_with("Pictures/") {
+            return src.to_string();
+        }
+        if let Some(path) = self.picture_paths.get(src) {
+            return path.clone();
+        }
+        let data = self
+            .image_dir
+            .as_ref()
+            .and_then(|dir| std::fs::read(dir.join(src)).ok());
+        let Some(data) = data else {
+            // Relative to the directory of the ODT file
+            return format!("../{}", src);
+        };
+
+        let file_name = src.rsplit('/').next().unwrap_or(src);
+        let mut path = format!("Pictures/{}", file_name);
+        if self.pictures.iter().any(|(existing, _)| *existing == path) {
+            path = format!("Pictures/{}_{}", self.pictures.len() + 1, file_name);
+        }
+        self.pictures.push((path.clone(), data));
+        self.picture_paths.insert(src.to_string(), path.clone());
+        path
+    }
+}
+
+/// Name of the standard heading style for `level`
+fn standard_heading(level: u8) -> &'static str {
+    match level {
+        1 => "Heading_20_1",
+        2 => "Heading_20_2",
+        3 => "Heading_20_3",
+        4 => "Heading_20_4",
+        5 => "Heading_20_5",
+        _ => "Heading_20_6",
+    }
+}
+
+/// Label of an admonition type, as written before its content
+pub(crate) fn admonition_label(kind: &AdmonitionType) -> &'static str {
+    match kind {
+        AdmonitionType::Note => "Note",
+        AdmonitionType::Tip => "Tip",
+        AdmonitionType::Important => "Important",
+        AdmonitionType::Warning => "Warning",
+        AdmonitionType::Caution => "Caution",
+    }
+}
+
+/// Text of a preformatted line, keeping spaces and tabs
+///
+/// ODF collapses white space in text content, so runs of spaces and tabs
+/// become `text:s` and `text:tab` elements.
+fn preformatted(line: &str) -> String {
+    let mut xml = String::new();
+    let mut spaces = 0;
+    let flush = |xml: &mut String, spaces: &mut usize, at_start: bool| {
+        if *spaces == 0 {
+            return;
+        }
+        // A single space between words survives collapsing
+        if *spaces == 1 && !at_start {
+            xml.push(' ');
+        } else if *spaces == 1 {
+            xml.push_str("<text:s/>");
+        } else {
+            xml.push_str(&format!("<text:s text:c=\"{}\"/>", spaces));
+        }
+        *spaces = 0;
+    };
+    for c in line.chars() {
+        match c {
+            ' ' => spaces += 1,
+            '\t' => {
+                let at_start = xml.is_empty();
+                flush(&mut xml, &mut spaces, at_start);
+                xml.push_str("<text:tab/>");
+            }
+            c => {
+                let at_start = xml.is_empty();
+                flush(&mut xml, &mut spaces, at_start);
+                xml.push_str(&escape_text(&c.to_string()));
+            }
+        }
+    }
+    if spaces > 0 {
+        // Trailing spaces would be collapsed too
+        xml.push_str(&format!("<text:s text:c=\"{}\"/>", spaces));
+    }
+    xml
+}
+
+/// Plain text of a table cell
+fn cell_text(cell: &TableCell) -> String {
+    fn inline_text(inline: &Inline, text: &mut String) {
+        match inline {
+            Inline::Text(t) => text.push_str(t),
+            Inline::Format(_, inner) => inline_text(inner, text),
+            Inline::Span(inlines) => inlines.iter().for_each(|i| inline_text(i, text)),
+            Inline::Link(link) => link.text.iter().for_each(|i| inline_text(i, text)),
+            Inline::Image(image) => text.push_str(image.alt.as_deref().unwrap_or_default()),
+            Inline::Break => text.push(' '),
+            Inline::Anchor(_) | Inline::Footnote(_) => {}
+        }
+    }
+    let mut text = String::new();
+    for block in &cell.content {
+        if let Block::Paragraph(para) = block {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            para.inlines.iter().for_each(|i| inline_text(i, &mut text));
+        }
+    }
+    text
+}
+
+/// Column letters as used in table style names: A..Z, AA..
+fn column_letter(index: usize) -> String {
+    let mut name = String::new();
+    let mut n = index + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        name.insert(0, (b'A' + rem as u8) as char);
+        n = (n - 1) / 26;
+    }
+    name
+}
+
+/// A grid position in a table row
+#[derive(Debug)]
+enum Slot<'a> {
+    /// A cell starting here
+    Cell(&'a TableCell),
+    /// A position covered by a spanning cell
+    Covered,
+    /// Padding at the end of a short row
+    Empty,
+}
+
+/// Table rows laid out on the grid
+struct Grid<'a> {
+    columns: usize,
+    rows: Vec<Vec<Slot<'a>>>,
+}
+
+/// Lay out table rows on the grid
+///
+/// ODF tables are regular: every row has an element for every column,
+/// with covered cells where a cell from the left or above spans.
+fn layout(table: &Table) -> Grid<'_> {
+    // Columns each row position is covered by a cell from a row above
+    let mut covered: Vec<Vec<bool>> = vec![Vec::new(); table.rows.len()];
+    let mut rows = Vec::new();
+    let mut columns = table.columns.len();
+
+    for (r, row) in table.rows.iter().enumerate() {
+        let mut slots = Vec::new();
+        let mut c = 0;
+        let is_covered =
+            |covered: &Vec<Vec<bool>>, c: usize| covered[r].get(c).copied().unwrap_or(false);
+        for cell in &row.cells {
+            while is_covered(&covered, c) {
+                slots.push(Slot::Covered);
+                c += 1;
+            }
+            slots.push(Slot::Cell(cell));
+            let colspan = cell.colspan.max(1) as usize;
+            let rowspan = cell.rowspan.max(1) as usize;
+            for _ in 1..colspan {
+                slots.push(Slot::Covered);
+            }
+            for below in covered.iter_mut().skip(r + 1).take(rowspan - 1) {
+                if below.len() < c + colspan {
+                    below.resize(c + colspan, false);
+                }
+                below[c..c + colspan].iter_mut().for_each(|p| *p = true);
+            }
+            c += colspan;
+        }
+        while c < covered[r].len() {
+            slots.push(if is_covered(&covered, c) {
+                Slot::Covered
+            } else {
+                Slot::Empty
+            });
+            c += 1;
+        }
+        columns = columns.max(slots.len());
+        rows.push(slots);
+    }
+
+    for slots in &mut rows {
+        while slots.len() < columns {
+            slots.push(Slot::Empty);
+        }
+    }
+    Grid { columns, rows }
+}
+
+/// An automatic list style with ten levels
+fn list_style(name: &str, format: Option<&str>) -> String {
+    const BULLETS: [char; 3] = ['\u{2022}', '\u{25e6}', '\u{25aa}'];
+    let mut xml = format!("<text:list-style style:name=\"{}\">\n", name);
+    for level in 1..=10 {
+        let indent = 0.635 * level as f32;
+        let properties = format!(
+            "<style:list-level-properties text:list-level-position-and-space-mode=\"label-alignment\"><style:list-level-label-alignment text:label-followed-by=\"listtab\" text:list-tab-stop-position=\"{0:.3}cm\" fo:text-indent=\"-0.635cm\" fo:margin-left=\"{0:.3}cm\"/></style:list-level-properties>",
+            indent
+        );
+        match format {
+            Some(format) => xml.push_str(&format!(
+                "<text:list-level-style-number text:level=\"{}\" style:num-suffix=\".\" style:num-format=\"{}\">{}</text:list-level-style-number>\n",
+                level, format, properties
+            )),
+            None => xml.push_str(&format!(
+                "<text:list-level-style-bullet text:level=\"{}\" text:bullet-char=\"{}\">{}</text:list-level-style-bullet>\n",
+                level,
+                BULLETS[(level - 1) % BULLETS.len()],
+                properties
+            )),
+        }
+    }
+    xml.push_str("</text:list-style>\n");
+    xml
+}
+
+/// meta.xml with the document title and author
+fn meta_xml(doc: &Document) -> String {
+    let mut meta = format!(
+        "<meta:generator>utf8dok/{}</meta:generator>",
+        crate::VERSION
+    );
+    if let Some(title) = &doc.metadata.title {
+        meta.push_str(&format!("<dc:title>{}</dc:title>", escape_text(title)));
+    }
+    if let Some(author) = doc.metadata.authors.first() {
+        meta.push_str(&format!(
+            "<meta:initial-creator>{0}</meta:initial-creator><dc:creator>{0}</dc:creator>",
+            escape_text(author)
+        ));
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <office:document-meta{} office:version=\"1.3\"><office:meta>{}</office:meta></office:document-meta>\n",
+        namespace_declarations(),
+        meta
+    )
+}
+
+/// Byte offset just past the root element's start tag
+fn root_start_tag_end(xml: &str) -> Option<usize> {
+    let mut offset = 0;
+    loop {
+        let start = offset + xml[offset..].find('<')?;
+        let rest = &xml[start + 1..];
+        if rest.starts_with('?') || rest.starts_with('!') {
+            offset = start + 1 + rest.find('>')? + 1;
+            continue;
+        }
+        return Some(start + 1 + rest.find('>')? + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_minimal_template, extract_content_xml, extract_file};
+    use utf8dok_ast::{Footnote, ListItem, TableRow};
+
+    fn text(s: &str) -> Inline {
+        Inline::Text(s.to_string())
+    }
+
+    fn para(inlines: Vec<Inline>) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines,
+            style_id: None,
+            attributes: HashMap::new(),
+        })
+    }
+
+    fn cell(s: &str, colspan: u32, rowspan: u32) -> TableCell {
+        TableCell {
+            content: vec![para(vec![text(s)])],
+            colspan,
+            rowspan,
+            align: None,
+            style: None,
+        }
+    }
+
+    fn generate(doc: &Document) -> Vec<u8> {
+        OdtWriter::generate(doc, &create_minimal_template()).unwrap()
+    }
+
+    #[test]
+    fn test_heading_uses_template_style() {
+        let mut doc = Document::with_title("Guide");
+        doc.push(Block::Heading(Heading {
+            level: 1,
+            text: vec![text("Setup")],
+            style_id: None,
+            anchor: Some("setup".to_string()),
+            role: None,
+        }));
+        doc.push(Block::Heading(Heading {
+            level: 3,
+            text: vec![text("Details")],
+            style_id: None,
+            anchor: None,
+            role: None,
+        }));
+
+        let odt = generate(&doc);
+        let content = extract_content_xml(&odt);
+        assert!(content.contains(
+            "<text:h text:style-name=\"Heading_20_1\" text:outline-level=\"1\"><text:bookmark text:name=\"setup\"/>Setup</text:h>"
+        ));
+        assert!(content.contains(
+            "<text:h text:style-name=\"Heading_20_3\" text:outline-level=\"3\">Details</text:h>"
+        ));
+        assert!(content.contains("<text:p text:style-name=\"Title\">Guide</text:p>"));
+
+        // Template styles are kept; missing ones are added once
+        let styles = extract_file(&odt, "styles.xml").unwrap();
+        assert!(styles.contains("Corporate Sans"));
+        assert_eq!(styles.matches("style:name=\"Heading_20_1\"").count(), 1);
+        assert!(styles.contains("style:name=\"Heading_20_3\""));
+        assert!(styles.contains("style:name=\"Heading\""));
+        assert!(styles.contains("style:name=\"Title\""));
+        assert!(!styles.contains("style:name=\"Quotations\""));
+    }
+
+    #[test]
+    fn test_package_parts() {
+        let mut doc = Document::with_title("Guide");
+        doc.metadata.authors.push("Jo Writer".to_string());
+        doc.push(para(vec![text("Body")]));
+
+        let mut writer = OdtWriter::new();
+        writer.set_embedded_content("= Guide\n\nBody\n", "[template]\n");
+        let odt = writer
+            .generate_with_template(
+                &doc,
+                OdtTemplate::from_bytes(&create_minimal_template()).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            extract_file(&odt, "mimetype").as_deref(),
+            Some(MIMETYPE_TEXT)
+        );
+        let meta = extract_file(&odt, "meta.xml").unwrap();
+        assert!(meta.contains("<dc:title>Guide</dc:title>"));
+        assert!(meta.contains("<dc:creator>Jo Writer</dc:creator>"));
+        assert_eq!(
+            extract_file(&odt, "utf8dok/source.adoc").as_deref(),
+            Some("= Guide\n\nBody\n")
+        );
+        let manifest = extract_file(&odt, "META-INF/manifest.xml").unwrap();
+        assert!(manifest.contains("manifest:full-path=\"utf8dok/source.adoc\""));
+        assert!(manifest.contains("manifest:full-path=\"utf8dok/manifest.json\""));
+        let content = extract_content_xml(&odt);
+        assert!(!content.contains("Template placeholder"));
+    }
+
+    #[test]
+    fn test_table_spans_use_covered_cells() {
+        let mut doc = Document::new();
+        doc.push(Block::Table(Table {
+            rows: vec![
+                TableRow {
+                    cells: vec![cell("Wide", 2, 1), cell("Tall", 1, 2)],
+                    is_header: true,
+                    is_footer: false,
+                },
+                TableRow {
+                    cells: vec![cell("a", 1, 1), cell("b", 1, 1)],
+                    is_header: false,
+                    is_footer: false,
+                },
+            ],
+            style_id: None,
+            caption: Some(vec![text("Spans")]),
+            columns: vec![],
+        }));
+
+        let content = extract_content_xml(&generate(&doc));
+        assert!(content.contains("<text:p text:style-name=\"Caption\">Spans</text:p>"));
+        assert!(content.contains("<table:table-column table:number-columns-repeated=\"3\"/>"));
+        assert!(content.contains(
+            "<table:table-header-rows>\n<table:table-row>\n<table:table-cell office:value-type=\"string\" table:number-columns-spanned=\"2\">\n<text:p text:style-name=\"Table_20_Heading\">Wide</text:p>\n</table:table-cell>\n<table:covered-table-cell/>\n"
+        ));
+        assert!(content.contains(
+            "<text:p text:style-name=\"Table_20_Contents\">b</text:p>\n</table:table-cell>\n<table:covered-table-cell/>\n</table:table-row>"
+        ));
+    }
+
+    #[test]
+    fn test_lists_nest_and_number() {
+        let item = |s: &str, level: u8, checked: Option<bool>| ListItem {
+            content: vec![para(vec![text(s)])],
+            level,
+            term: None,
+            checked,
+        };
+        let mut doc = Document::new();
+        doc.push(Block::List(List {
+            list_type: ListType::Ordered,
+            items: vec![item("one", 0, None), item("nested", 1, None)],
+            style_id: None,
+            numbering: Some(NumberingStyle::LowerRoman),
+            start: None,
+        }));
+        doc.push(Block::List(List {
+            list_type: ListType::Unordered,
+            items: vec![item("done", 0, Some(true))],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+
+        let content = extract_content_xml(&generate(&doc));
+        assert!(content.contains(
+            "<text:list text:style-name=\"U_LowerRoman\">\n<text:list-item>\n<text:p text:style-name=\"Text_20_body\">one</text:p>\n<text:list>\n<text:list-item>"
+        ));
+        assert!(content.contains("style:num-format=\"i\""));
+        assert!(content.contains("<text:p text:style-name=\"Text_20_body\">\u{2612} done</text:p>"));
+    }
+
+    #[test]
+    fn test_inlines_and_footnotes() {
+        let mut doc = Document::new();
+        doc.push(para(vec![
+            Inline::Format(FormatType::Bold, Box::new(text("bold"))),
+            text(" "),
+            Inline::Link(utf8dok_ast::Link {
+                url: "#setup".to_string(),
+                text: vec![text("Setup")],
+            }),
+            Inline::Footnote(Footnote {
+                id: Some("disclaimer".to_string()),
+                content: vec![text("Terms apply.")],
+            }),
+            Inline::Footnote(Footnote {
+                id: Some("disclaimer".to_string()),
+                content: vec![],
+            }),
+        ]));
+
+        let content = extract_content_xml(&generate(&doc));
+        assert!(
+            content.contains("<text:span text:style-name=\"Strong_20_Emphasis\">bold</text:span>")
+        );
+        assert!(content.contains("xlink:href=\"#setup\""));
+        assert!(content.contains(
+            "<text:note text:id=\"ftn1\" text:note-class=\"footnote\"><text:note-citation>1</text:note-citation><text:note-body><text:p text:style-name=\"Footnote\">Terms apply.</text:p></text:note-body></text:note>"
+        ));
+        assert!(content.contains("text:ref-name=\"ftn1\">1</text:note-ref>"));
+    }
+
+    #[test]
+    fn test_preformatted_keeps_spaces() {
+        assert_eq!(preformatted("a b"), "a b");
+        assert_eq!(
+            preformatted("  if x  {"),
+            "<text:s text:c=\"2\"/>if x<text:s text:c=\"2\"/>{"
+        );
+        assert_eq!(preformatted("\tx <y>"), "<text:tab/>x &lt;y&gt;");
+    }
+
+    #[test]
+    fn test_column_letter() {
+        assert_eq!(column_letter(0), "A");
+        assert_eq!(column_letter(25), "Z");
+        assert_eq!(column_letter(26), "AA");
+    }
+}
@@ -0,0 +1,310 @@
+//! XML helpers: escaping and a small element tree for reading ODF parts
+
+use quick_xml::events::Event;
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+
+use crate::error::Result;
+
+/// Namespaces of ODF parts, with the prefixes used to name them here
+///
+/// Names in an [`Element`] tree always use these prefixes, whatever the
+/// prefixes declared in the file, so readers can match on `text:p`.
+const NAMESPACES: &[(&str, &str)] = &[
+    ("office", "urn:oasis:names:tc:opendocument:xmlns:office:1.0"),
+    ("style", "urn:oasis:names:tc:opendocument:xmlns:style:1.0"),
+    ("text", "urn:oasis:names:tc:opendocument:xmlns:text:1.0"),
+    ("table", "urn:oasis:names:tc:opendocument:xmlns:table:1.0"),
+    ("draw", "urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"),
+    (
+        "fo",
+        "urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0",
+    ),
+    (
+        "svg",
+        "urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0",
+    ),
+    ("meta", "urn:oasis:names:tc:opendocument:xmlns:meta:1.0"),
+    ("xlink", "http://www.w3.org/1999/xlink"),
+    ("dc", "http://purl.org/dc/elements/1.1/"),
+];
+
+/// Namespace declarations for the root element of a written part
+pub(crate) fn namespace_declarations() -> String {
+    NAMESPACES
+        .iter()
+        .map(|(prefix, uri)| format!(" xmlns:{}=\"{}\"", prefix, uri))
+        .collect()
+}
+
+/// Escape text content
+///
+/// Control characters other than tab and line breaks are dropped, since
+/// XML 1.0 cannot represent them at all.
+pub(crate) fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape an attribute value
+pub(crate) fn escape_attr(text: &str) -> String {
+    escape_text(text)
+        .replace('"', "&quot;")
+        .replace(['\n', '\r', '\t'], " ")
+}
+
+/// A node of an element tree
+#[derive(Debug, Clone)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// An element with its attributes and children
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Element {
+    /// Qualified name, with the prefix of [`NAMESPACES`] for ODF names
+    pub name: String,
+    /// Attributes by qualified name, in document order
+    pub attributes: Vec<(String, String)>,
+    /// Child nodes in document order
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// Parse a document into the tree of its root element
+    pub fn parse(xml: &[u8]) -> Result<Element> {
+        let mut reader = NsReader::from_reader(xml);
+        let mut stack = vec![Element::default()];
+        let mut buf = Vec::new();
+
+        loop {
+            let (resolved, event) = reader.read_resolved_event_into(&mut buf)?;
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e) => {
+                    let name = qualified_name(resolved, e.name().as_ref(), e.local_name().as_ref());
+                    let mut attributes = Vec::new();
+                    for attr in e.attributes().filter_map(|a| a.ok()) {
+                        if attr.key.as_ref().starts_with(b"xmlns") {
+                            continue;
+                        }
+                        let (resolved, local) = reader.resolve_attribute(attr.key);
+                        let name = qualified_name(resolved, attr.key.as_ref(), local.as_ref());
+                        let value = attr
+                            .unescape_value()
+                            .map(|v| v.into_owned())
+                            .unwrap_or_else(|_| String::from_utf8_lossy(&attr.value).into_owned());
+                        attributes.push((name, value));
+                    }
+                    let element = Element {
+                        name,
+                        attributes,
+                        children: Vec::new(),
+                    };
+                    if matches!(event, Event::Start(_)) {
+                        stack.push(element);
+                    } else if let Some(parent) = stack.last_mut() {
+                        parent.children.push(Node::Element(element));
+                    }
+                }
+                Event::End(_) if stack.len() > 1 => {
+                    let element = stack.pop().unwrap_or_default();
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(Node::Element(element));
+                    }
+                }
+                Event::Text(ref e) => {
+                    let text = e
+                        .unescape()
+                        .map(|t| t.into_owned())
+                        .unwrap_or_else(|_| String::from_utf8_lossy(e).into_owned());
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(Node::Text(text));
+                    }
+                }
+                Event::CData(ref e) => {
+                    if let Some(parent) = stack.last_mut() {
+                        parent
+                            .children
+                            .push(Node::Text(String::from_utf8_lossy(e).into_owned()));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        // Unclosed elements still belong to the tree
+        while stack.len() > 1 {
+            let element = stack.pop().unwrap_or_default();
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(Node::Element(element));
+            }
+        }
+        let document = stack.pop().unwrap_or_default();
+        Ok(document
+            .children
+            .into_iter()
+            .find_map(|node| match node {
+                Node::Element(element) => Some(element),
+                Node::Text(_) => None,
+            })
+            .unwrap_or_default())
+    }
+
+    /// Value of the attribute `name`
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Child elements
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// First child element named `name`
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.name == name)
+    }
+
+    /// Concatenated text of all descendants
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for node in &self.children {
+            match node {
+                Node::Text(t) => text.push_str(t),
+                Node::Element(element) => text.push_str(&element.text()),
+            }
+        }
+        text
+    }
+}
+
+/// Name with the canonical prefix of its namespace, or as written
+fn qualified_name(resolved: ResolveResult, raw: &[u8], local: &[u8]) -> String {
+    let local = String::from_utf8_lossy(local);
+    if let ResolveResult::Bound(Namespace(uri)) = resolved {
+        if let Some((prefix, _)) = NAMESPACES.iter().find(|(_, ns)| ns.as_bytes() == uri) {
+            return format!("{}:{}", prefix, local);
+        }
+    }
+    String::from_utf8_lossy(raw).into_owned()
+}
+
+/// A step in writing items carrying a nesting level as nested lists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Nesting {
+    /// Open a list
+    Open,
+    /// Open the item at this index
+    Item(usize),
+    /// Close the current item
+    EndItem,
+    /// Close the current list
+    Close,
+}
+
+/// The steps for items at `levels`
+///
+/// A nested list opens inside the item before it. An item more than one
+/// level deeper than the previous one is nested only one level, as is the
+/// first item.
+pub(crate) fn nesting(levels: impl IntoIterator<Item = usize>) -> Vec<Nesting> {
+    let mut steps = vec![Nesting::Open];
+    let mut depth = 0;
+    let mut any = false;
+    for (index, level) in levels.into_iter().enumerate() {
+        let target = if index == 0 { 0 } else { level.min(depth + 1) };
+        if target > depth {
+            steps.push(Nesting::Open);
+            depth = target;
+        } else if index > 0 {
+            steps.push(Nesting::EndItem);
+            while depth > target {
+                steps.push(Nesting::Close);
+                steps.push(Nesting::EndItem);
+                depth -= 1;
+            }
+        }
+        steps.push(Nesting::Item(index));
+        any = true;
+    }
+    if any {
+        steps.push(Nesting::EndItem);
+    }
+    for _ in 0..depth {
+        steps.push(Nesting::Close);
+        steps.push(Nesting::EndItem);
+    }
+    steps.push(Nesting::Close);
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(
+            escape_text("a < b && c\u{1} > d"),
+            "a &lt; b &amp;&amp; c &gt; d"
+        );
+        assert_eq!(escape_attr("say \"hi\"\nnow"), "say &quot;hi&quot; now");
+    }
+
+    #[test]
+    fn test_parse_uses_canonical_prefixes() {
+        let root = Element::parse(
+            br#"<?xml version="1.0"?>
+<doc xmlns:t="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:x="http://www.w3.org/1999/xlink">
+  <t:p t:style-name="Text_20_body">Hello <t:a x:href="https://example.com">world</t:a> &amp; more</t:p>
+</doc>"#,
+        )
+        .unwrap();
+
+        assert_eq!(root.name, "doc");
+        let p = root.child("text:p").unwrap();
+        assert_eq!(p.attribute("text:style-name"), Some("Text_20_body"));
+        let a = p.child("text:a").unwrap();
+        assert_eq!(a.attribute("xlink:href"), Some("https://example.com"));
+        assert_eq!(p.text(), "Hello world & more");
+    }
+
+    #[test]
+    fn test_nesting() {
+        use Nesting::*;
+        assert_eq!(
+            nesting([0, 1, 0]),
+            vec![
+                Open,
+                Item(0),
+                Open,
+                Item(1),
+                EndItem,
+                Close,
+                EndItem,
+                Item(2),
+                EndItem,
+                Close
+            ]
+        );
+    }
+}
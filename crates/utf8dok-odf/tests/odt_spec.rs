@@ -0,0 +1,248 @@
+//! ODT Backend Specification Tests
+//!
+//! These tests render parsed AsciiDoc as ODT on a template, check the
+//! package parts, and extract the result back to AsciiDoc, both from the
+//! embedded source and by converting the document body.
+
+use std::io::Cursor;
+
+use utf8dok_ast::{Block, Document, ListType};
+use utf8dok_core::parse as parse_adoc;
+use utf8dok_odf::test_utils::{create_minimal_template, extract_content_xml, extract_file};
+use utf8dok_odf::{
+    reader, OdfPackage, OdtExtractor, OdtTemplate, OdtWriter, SourceOrigin, MIMETYPE_TEXT,
+};
+
+const DOCUMENT: &str = r#"= User Guide
+
+Welcome to the guide.footnote:disclaimer[Preview release.]
+
+[[install]]
+== Installation
+
+Run the installer. See <<usage,the usage section>>.
+
+=== Requirements
+
+* Linux
+** x86_64
+* macOS
+
+[loweralpha]
+. First
+. Second
+
+* [x] Downloaded
+* [ ] Installed
+
+CPU:: Any
+Memory:: 4 GB
+
+.Architecture
+image::images/arch.png[System overview]
+
+[[usage]]
+== Usage
+
+Use the `run` command with *care*.footnote:disclaimer[]
+
+.Options
+|===
+| Name | Short | Default
+
+.2+| verbose 2+| -v
+| -q | off
+|===
+
+WARNING: Mind the *details*.
+
+.Example
+----
+fn main() {
+    run();  <1>
+}
+----
+
+[quote, Ada Lovelace, Notes]
+____
+The engine weaves patterns.
+____
+
+****
+Side note.
+****
+"#;
+
+fn render(source: &str, embed: bool) -> Vec<u8> {
+    let doc = parse_adoc(source).unwrap();
+    let mut writer = OdtWriter::new();
+    if embed {
+        writer.set_embedded_content(source, "[template]\npath = \"template.ott\"\n");
+    }
+    let template = OdtTemplate::from_bytes(&create_minimal_template()).unwrap();
+    writer.generate_with_template(&doc, template).unwrap()
+}
+
+fn read_back(odt: &[u8]) -> Document {
+    let package = OdfPackage::from_reader(Cursor::new(odt)).unwrap();
+    reader::read_document(&package).unwrap()
+}
+
+#[test]
+fn test_package_is_valid_odt() {
+    let odt = render(DOCUMENT, true);
+
+    // The mimetype comes first, uncompressed
+    let mut zip = zip::ZipArchive::new(Cursor::new(&odt)).unwrap();
+    let first = zip.by_index(0).unwrap();
+    assert_eq!(first.name(), "mimetype");
+    assert_eq!(first.compression(), zip::CompressionMethod::Stored);
+    drop(first);
+    assert_eq!(
+        extract_file(&odt, "mimetype").as_deref(),
+        Some(MIMETYPE_TEXT)
+    );
+
+    let manifest = extract_file(&odt, "META-INF/manifest.xml").unwrap();
+    for part in [
+        "content.xml",
+        "styles.xml",
+        "meta.xml",
+        "utf8dok/source.adoc",
+    ] {
+        assert!(
+            manifest.contains(&format!("manifest:full-path=\"{}\"", part)),
+            "manifest lists {}",
+            part
+        );
+    }
+    let meta = extract_file(&odt, "meta.xml").unwrap();
+    assert!(meta.contains("<dc:title>User Guide</dc:title>"));
+}
+
+#[test]
+fn test_content_structure() {
+    let content = extract_content_xml(&render(DOCUMENT, false));
+
+    assert!(content.contains("<text:bookmark text:name=\"install\"/>"));
+    assert!(content.contains(
+        "<text:h text:style-name=\"Heading_20_1\" text:outline-level=\"1\">Installation</text:h>"
+    ));
+    assert!(content.contains("xlink:href=\"#usage\""));
+    assert!(content.contains("<text:list text:style-name=\"U_LowerAlpha\">"));
+    assert!(content.contains("table:number-rows-spanned=\"2\""));
+    assert!(content.contains("<table:covered-table-cell/>"));
+    assert!(content.contains("<text:note-citation>1</text:note-citation>"));
+    assert!(content.contains("text:ref-name=\"ftn1\""));
+    assert!(content.contains("<text:s text:c=\"4\"/>run();"));
+    assert!(content.contains("<text:section text:style-name=\"U_Sidebar\" text:name=\"Sidebar1\">"));
+    assert!(content.contains("xlink:href=\"../images/arch.png\""));
+}
+
+#[test]
+fn test_styles_are_completed() {
+    let styles = extract_file(&render(DOCUMENT, false), "styles.xml").unwrap();
+
+    // Template styles are kept as they are
+    assert!(styles.contains("Corporate Sans"));
+    assert_eq!(styles.matches("style:name=\"Heading_20_1\"").count(), 1);
+    // Styles the content uses are added
+    for name in [
+        "Preformatted_20_Text",
+        "Quotations",
+        "Table_20_Heading",
+        "Footnote",
+        "Strong_20_Emphasis",
+        "Internet_20_link",
+    ] {
+        assert!(
+            styles.contains(&format!("style:name=\"{}\"", name)),
+            "styles.xml defines {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_embedded_source_round_trip() {
+    let odt = render(DOCUMENT, true);
+    let package = OdfPackage::from_reader(Cursor::new(odt)).unwrap();
+
+    let extracted = OdtExtractor::new().extract_package(&package).unwrap();
+    assert_eq!(extracted.source_origin, SourceOrigin::Embedded);
+    assert_eq!(extracted.asciidoc, DOCUMENT);
+}
+
+#[test]
+fn test_body_round_trip() {
+    let original = parse_adoc(DOCUMENT).unwrap();
+    let read = read_back(&render(DOCUMENT, false));
+
+    assert_eq!(read.metadata.title.as_deref(), Some("User Guide"));
+
+    // Block kinds survive, in order
+    let kinds = |doc: &Document| -> Vec<String> {
+        doc.blocks
+            .iter()
+            .map(|block| match block {
+                Block::List(list) => format!("List({:?})", list.list_type),
+                other => format!("{:?}", other)
+                    .split('(')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+            .collect()
+    };
+    assert_eq!(kinds(&read), kinds(&original));
+
+    // Headings, lists and code are the same blocks
+    for (read, original) in read.blocks.iter().zip(&original.blocks) {
+        match (read, original) {
+            (Block::Heading(_), Block::Heading(_)) | (Block::Literal(_), Block::Literal(_)) => {
+                assert_eq!(read, original)
+            }
+            (Block::List(read), Block::List(original))
+                if original.list_type != ListType::Description =>
+            {
+                assert_eq!(read.numbering, original.numbering);
+                let shape = |list: &utf8dok_ast::List| -> Vec<(u8, Option<bool>)> {
+                    list.items.iter().map(|i| (i.level, i.checked)).collect()
+                };
+                assert_eq!(shape(read), shape(original));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_extract_converts_body() {
+    let odt = render(DOCUMENT, true);
+    let package = OdfPackage::from_reader(Cursor::new(odt)).unwrap();
+    let extracted = OdtExtractor::new()
+        .with_force_parse(true)
+        .extract_package(&package)
+        .unwrap();
+    assert_eq!(extracted.source_origin, SourceOrigin::Parsed);
+
+    let adoc = &extracted.asciidoc;
+    assert!(adoc.starts_with("= User Guide\n"));
+    assert!(adoc.contains("[[install]]"));
+    assert!(adoc.contains("<<usage,the usage section>>"));
+    assert!(adoc.contains("[WARNING]\n====\nMind the *details*.\n===="));
+    assert!(adoc.contains("footnote:ftn1[Preview release.]"));
+    assert!(adoc.contains("* [x] Downloaded"));
+    assert!(adoc.contains("    run(); <1>"));
+    assert!(adoc.contains("image::images/arch.png[System overview]"));
+
+    // The extracted AsciiDoc parses to the same headings
+    let reparsed = parse_adoc(adoc).unwrap();
+    let headings = |doc: &Document| {
+        doc.blocks
+            .iter()
+            .filter(|b| matches!(b, Block::Heading(_)))
+            .count()
+    };
+    assert_eq!(headings(&reparsed), 3);
+}
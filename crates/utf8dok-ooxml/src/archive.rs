@@ -107,6 +107,11 @@ impl OoxmlArchive {
         self.get("word/comments.xml")
     }
 
+    /// Get the footnotes (word/footnotes.xml)
+    pub fn footnotes_xml(&self) -> Option<&[u8]> {
+        self.get("word/footnotes.xml")
+    }
+
     /// Check if a file exists in the archive
     pub fn contains(&self, path: &str) -> bool {
         self.files.contains_key(path)
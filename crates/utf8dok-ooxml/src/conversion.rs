@@ -133,8 +133,13 @@ impl ToAst for Hyperlink {
             "#".to_string()
         };
 
-        // Convert child runs to inlines
-        let children: Vec<Inline> = self.runs.iter().map(|r| r.to_ast(ctx)).collect();
+        // Convert child runs to inlines (tracked deletions are accepted)
+        let children: Vec<Inline> = self
+            .runs
+            .iter()
+            .filter(|r| !r.is_deleted())
+            .map(|r| r.to_ast(ctx))
+            .collect();
 
         // Create link inline using the Link struct
         Inline::Link(AstLink {
@@ -153,6 +158,8 @@ impl ToAst for ParagraphChild {
 
     fn to_ast(&self, ctx: &ConversionContext) -> Self::Output {
         match self {
+            // Tracked changes are accepted: deleted runs are left out
+            ParagraphChild::Run(run) if run.is_deleted() => vec![],
             ParagraphChild::Run(run) => vec![run.to_ast(ctx)],
             ParagraphChild::Hyperlink(hyperlink) => vec![hyperlink.to_ast(ctx)],
             ParagraphChild::Image(img) => {
@@ -164,6 +171,18 @@ impl ToAst for ParagraphChild {
                 // Convert bookmark to inline anchor
                 vec![Inline::Anchor(bookmark.name.clone())]
             }
+            ParagraphChild::Footnote(footnote) => {
+                let content = footnote
+                    .paragraphs
+                    .iter()
+                    .flat_map(|para| &para.children)
+                    .flat_map(|child| child.to_ast(ctx))
+                    .collect();
+                vec![Inline::Footnote(utf8dok_ast::Footnote {
+                    id: None,
+                    content,
+                })]
+            }
         }
     }
 }
@@ -195,6 +214,7 @@ impl ToAst for Paragraph {
                     text: inlines,
                     style_id: Some(style_id.clone()),
                     anchor,
+                    role: None,
                 });
             }
         }
@@ -219,6 +239,7 @@ impl ToAst for TableRow {
         AstTableRow {
             cells: self.cells.iter().map(|c| c.to_ast(ctx)).collect(),
             is_header: self.is_header,
+            is_footer: false,
         }
     }
 }
@@ -232,6 +253,7 @@ impl ToAst for TableCell {
             colspan: 1,
             rowspan: 1,
             align: None,
+            style: None,
         }
     }
 }
@@ -277,6 +299,7 @@ impl ToAst for Document {
             metadata: utf8dok_ast::DocumentMeta::default(),
             blocks: self.blocks.iter().map(|b| b.to_ast(ctx)).collect(),
             intent: None,
+            ..Default::default()
         }
     }
 }
@@ -385,6 +408,7 @@ mod tests {
             bold: false,
             italic: false,
             monospace: false,
+            revision: None,
         };
         let ctx = ConversionContext::new();
         let inline = run.to_ast(&ctx);
@@ -399,6 +423,7 @@ mod tests {
             bold: true,
             italic: false,
             monospace: false,
+            revision: None,
         };
         let ctx = ConversionContext::new();
         let inline = run.to_ast(&ctx);
@@ -416,6 +441,7 @@ mod tests {
             bold: true,
             italic: true,
             monospace: false,
+            revision: None,
         };
         let ctx = ConversionContext::new();
         let inline = run.to_ast(&ctx);
@@ -440,6 +466,7 @@ mod tests {
             bold: true,
             italic: true,
             monospace: true,
+            revision: None,
         };
         let ctx = ConversionContext::new();
         let inline = run.to_ast(&ctx);
@@ -469,6 +496,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             })],
             numbering: None,
         };
@@ -494,18 +522,21 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Run(Run {
                     text: "bold".to_string(),
                     bold: true,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Run(Run {
                     text: " text".to_string(),
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
             ],
             numbering: None,
@@ -536,6 +567,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             })],
             numbering: None,
         };
@@ -561,6 +593,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             })],
             numbering: None,
         };
@@ -585,6 +618,7 @@ mod tests {
                         bold: false,
                         italic: false,
                         monospace: false,
+                        revision: None,
                     })],
                     numbering: None,
                 }),
@@ -595,6 +629,7 @@ mod tests {
                         bold: false,
                         italic: false,
                         monospace: false,
+                        revision: None,
                     })],
                     numbering: None,
                 }),
@@ -680,6 +715,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             })],
             numbering: None,
         };
@@ -708,6 +744,7 @@ mod tests {
                                 bold: true,
                                 italic: false,
                                 monospace: false,
+                                revision: None,
                             })],
                             numbering: None,
                         }],
@@ -723,6 +760,7 @@ mod tests {
                                 bold: false,
                                 italic: false,
                                 monospace: false,
+                                revision: None,
                             })],
                             numbering: None,
                         }],
@@ -866,6 +904,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             }],
         };
 
@@ -904,6 +943,7 @@ mod tests {
             bold: true,
             italic: true,
             monospace: true,
+            revision: None,
         };
 
         let inline = run.to_ast(&ctx);
@@ -936,6 +976,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             }],
         };
 
@@ -960,6 +1001,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             }],
         };
 
@@ -999,6 +1041,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             }],
         };
 
@@ -1029,6 +1072,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Hyperlink(Hyperlink {
                     id: None,
@@ -1038,6 +1082,7 @@ mod tests {
                         bold: false,
                         italic: false,
                         monospace: false,
+                        revision: None,
                     }],
                 }),
                 ParagraphChild::Run(Run {
@@ -1045,6 +1090,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
             ],
             numbering: None,
@@ -1072,6 +1118,7 @@ mod tests {
             bold: true,
             italic: false,
             monospace: false,
+            revision: None,
         };
 
         let inline = run.to_ast(&ctx);
@@ -1143,6 +1190,7 @@ mod tests {
                                 bold: false,
                                 italic: false,
                                 monospace: false,
+                                revision: None,
                             })],
                             numbering: None,
                         }],
@@ -1185,6 +1233,7 @@ mod tests {
                         bold: false,
                         italic: false,
                         monospace: false,
+                        revision: None,
                     })],
                     numbering: None,
                 }),
@@ -1196,6 +1245,7 @@ mod tests {
                         bold: false,
                         italic: false,
                         monospace: false,
+                        revision: None,
                     })],
                     numbering: None,
                 }),
@@ -1226,6 +1276,7 @@ mod tests {
                                 bold: false,
                                 italic: false,
                                 monospace: false,
+                                revision: None,
                             })],
                             numbering: None,
                         }],
@@ -1241,6 +1292,7 @@ mod tests {
                                 bold: false,
                                 italic: false,
                                 monospace: false,
+                                revision: None,
                             })],
                             numbering: None,
                         }],
@@ -1278,6 +1330,7 @@ mod tests {
                                 bold: false,
                                 italic: false,
                                 monospace: false,
+                                revision: None,
                             })],
                             numbering: None,
                         },
@@ -1288,6 +1341,7 @@ mod tests {
                                 bold: false,
                                 italic: false,
                                 monospace: false,
+                                revision: None,
                             })],
                             numbering: None,
                         },
@@ -1315,6 +1369,7 @@ mod tests {
             bold: false,
             italic: false,
             monospace: true,
+            revision: None,
         };
 
         let inline = run.to_ast(&ctx);
@@ -1340,6 +1395,7 @@ mod tests {
             bold: true,
             italic: true,
             monospace: true,
+            revision: None,
         };
 
         let inline = run.to_ast(&ctx);
@@ -1367,6 +1423,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             }],
         };
 
@@ -1419,6 +1476,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
             ],
             numbering: None,
@@ -1461,6 +1519,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 })],
                 numbering: None,
             })],
@@ -49,6 +49,24 @@ pub enum ParagraphChild {
     Image(Image),
     /// A bookmark anchor
     Bookmark(Bookmark),
+    /// A footnote reference
+    Footnote(FootnoteReference),
+}
+
+/// A footnote reference with the footnote's content
+#[derive(Debug, Clone)]
+pub struct FootnoteReference {
+    /// Footnote ID (references word/footnotes.xml)
+    pub id: i32,
+    /// Footnote paragraphs (filled in by [`Document::attach_footnotes`])
+    pub paragraphs: Vec<Paragraph>,
+}
+
+/// Parsed footnotes from word/footnotes.xml
+#[derive(Debug, Clone, Default)]
+pub struct Footnotes {
+    /// Map of footnote ID to its paragraphs
+    notes: std::collections::HashMap<i32, Vec<Paragraph>>,
 }
 
 /// A bookmark (anchor point for internal links)
@@ -80,6 +98,37 @@ pub struct Run {
     pub italic: bool,
     /// Whether the text is monospace/code
     pub monospace: bool,
+    /// Tracked change the run belongs to (`w:ins` / `w:del`)
+    pub revision: Option<Revision>,
+}
+
+/// A tracked change (revision mark) on a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    /// Whether the text was inserted or deleted
+    pub kind: RevisionKind,
+    /// Author of the change (w:author)
+    pub author: Option<String>,
+    /// Date of the change (w:date)
+    pub date: Option<String>,
+}
+
+/// Kind of a tracked change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionKind {
+    /// Inserted text (`w:ins`, or `w:moveTo` for moved text)
+    Insertion,
+    /// Deleted text (`w:del`, or `w:moveFrom` for moved text)
+    Deletion,
+}
+
+impl Run {
+    /// Whether the run was deleted by a tracked change
+    pub fn is_deleted(&self) -> bool {
+        self.revision
+            .as_ref()
+            .is_some_and(|revision| revision.kind == RevisionKind::Deletion)
+    }
 }
 
 /// Reference to numbering definition
@@ -134,6 +183,8 @@ impl Document {
         let mut current_run: Option<RunBuilder> = None;
         let mut current_table: Option<TableBuilder> = None;
         let mut current_hyperlink: Option<HyperlinkBuilder> = None;
+        // Tracked change the runs being parsed belong to
+        let mut current_revision: Option<Revision> = None;
         // Track if we're inside a <w:t> or <a:t> element (actual text vs instrText)
         let mut in_text_element = false;
         // Image parsing state
@@ -197,9 +248,24 @@ impl Document {
                                 }
                             }
                         }
+                        b"ins" | b"del" | b"moveTo" | b"moveFrom" if current_para.is_some() => {
+                            // Tracked change around runs (the self-closing form
+                            // in run properties marks a paragraph mark instead)
+                            let kind = match name.as_ref() {
+                                b"ins" | b"moveTo" => RevisionKind::Insertion,
+                                _ => RevisionKind::Deletion,
+                            };
+                            current_revision = Some(Revision {
+                                kind,
+                                author: get_attr(e, b"w:author"),
+                                date: get_attr(e, b"w:date"),
+                            });
+                        }
                         b"r" if current_para.is_some() => {
                             // WordprocessingML run (w:r) or DrawingML run (a:r)
-                            current_run = Some(RunBuilder::new());
+                            let mut run = RunBuilder::new();
+                            run.revision = current_revision.clone();
+                            current_run = Some(run);
                         }
                         b"b" if current_run.is_some() => {
                             // Check for w:val="0" which means NOT bold
@@ -226,8 +292,8 @@ impl Document {
                                 }
                             }
                         }
-                        b"t" if current_run.is_some() => {
-                            // Start of actual text element (w:t or a:t) - capture text from here
+                        b"t" | b"delText" if current_run.is_some() => {
+                            // Start of actual text element (w:t, w:delText or a:t) - capture text from here
                             in_text_element = true;
                         }
                         b"drawing" if current_para.is_some() => {
@@ -361,10 +427,13 @@ impl Document {
                                 blocks.push(Block::Paragraph(para));
                             }
                         }
-                        b"t" => {
+                        b"t" | b"delText" => {
                             // End of text element
                             in_text_element = false;
                         }
+                        b"ins" | b"del" | b"moveTo" | b"moveFrom" => {
+                            current_revision = None;
+                        }
                         b"r" if current_run.is_some() => {
                             let run = current_run.take().unwrap().build();
                             if !run.text.is_empty() {
@@ -493,6 +562,17 @@ impl Document {
                                 }
                             }
                         }
+                        b"footnoteReference" if current_para.is_some() => {
+                            // Footnote reference (content lives in footnotes.xml)
+                            if let Some(id) = get_attr(e, b"w:id").and_then(|v| v.parse().ok()) {
+                                let para = current_para.as_mut().unwrap();
+                                para.children
+                                    .push(ParagraphChild::Footnote(FootnoteReference {
+                                        id,
+                                        paragraphs: Vec::new(),
+                                    }));
+                            }
+                        }
                         b"extent" if current_image.is_some() => {
                             // Image dimensions in EMUs (self-closing)
                             if let Some(cx) = get_attr(e, b"cx") {
@@ -562,6 +642,31 @@ impl Document {
         Ok(Document { blocks })
     }
 
+    /// Fill in footnote references with the content of their footnotes
+    pub fn attach_footnotes(&mut self, footnotes: &Footnotes) {
+        let attach = |para: &mut Paragraph| {
+            for child in &mut para.children {
+                if let ParagraphChild::Footnote(reference) = child {
+                    if let Some(paragraphs) = footnotes.get(reference.id) {
+                        reference.paragraphs = paragraphs.to_vec();
+                    }
+                }
+            }
+        };
+
+        for block in &mut self.blocks {
+            match block {
+                Block::Paragraph(para) => attach(para),
+                Block::Table(table) => {
+                    for cell in table.rows.iter_mut().flat_map(|row| &mut row.cells) {
+                        cell.paragraphs.iter_mut().for_each(attach);
+                    }
+                }
+                Block::SectionBreak => {}
+            }
+        }
+    }
+
     /// Get all paragraphs (flattening tables)
     pub fn paragraphs(&self) -> impl Iterator<Item = &Paragraph> {
         self.blocks.iter().flat_map(|block| match block {
@@ -586,18 +691,85 @@ impl Document {
     }
 }
 
+impl Footnotes {
+    /// Parse footnotes from XML
+    ///
+    /// Separator footnotes (those with a `w:type`) are skipped.
+    pub fn parse(xml: &[u8]) -> Result<Self> {
+        let mut reader = Reader::from_reader(xml);
+        reader.config_mut().trim_text(false);
+
+        let mut notes = std::collections::HashMap::new();
+        let mut buf = Vec::new();
+        let mut current: Option<(i32, usize)> = None;
+
+        loop {
+            let position = reader.buffer_position() as usize;
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"footnote" => {
+                    let is_separator = get_attr(e, b"w:type").is_some_and(|t| t != "normal");
+                    current = get_attr(e, b"w:id")
+                        .and_then(|id| id.parse().ok())
+                        .filter(|_| !is_separator)
+                        .map(|id| (id, reader.buffer_position() as usize));
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"footnote" => {
+                    if let Some((id, start)) = current.take() {
+                        // Parse the footnote body like document content
+                        let mut body = b"<w:body>".to_vec();
+                        body.extend_from_slice(&xml[start..position]);
+                        body.extend_from_slice(b"</w:body>");
+                        let paragraphs = Document::parse(&body)?
+                            .blocks
+                            .into_iter()
+                            .filter_map(|block| match block {
+                                Block::Paragraph(para) => Some(para),
+                                _ => None,
+                            })
+                            .collect();
+                        notes.insert(id, paragraphs);
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e)),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(Footnotes { notes })
+    }
+
+    /// Get the paragraphs of a footnote by ID
+    pub fn get(&self, id: i32) -> Option<&[Paragraph]> {
+        self.notes.get(&id).map(Vec::as_slice)
+    }
+
+    /// Number of footnotes
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    /// Check if there are no footnotes
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+}
+
 impl Paragraph {
-    /// Get plain text of this paragraph
+    /// Get plain text of this paragraph, with tracked changes accepted
     pub fn plain_text(&self) -> String {
         self.children
             .iter()
             .map(|child| match child {
+                ParagraphChild::Run(run) if run.is_deleted() => String::new(),
                 ParagraphChild::Run(run) => run.text.clone(),
                 ParagraphChild::Hyperlink(hyperlink) => {
                     // Collect text from all runs in the hyperlink
                     hyperlink
                         .runs
                         .iter()
+                        .filter(|r| !r.is_deleted())
                         .map(|r| r.text.as_str())
                         .collect::<String>()
                 }
@@ -606,21 +778,24 @@ impl Paragraph {
                     img.alt.clone().unwrap_or_default()
                 }
                 ParagraphChild::Bookmark(_) => String::new(), // Bookmarks have no text
+                ParagraphChild::Footnote(_) => String::new(), // Footnote text is out of line
             })
             .collect::<Vec<_>>()
             .join("")
     }
 
-    /// Check if this paragraph is empty
+    /// Check if this paragraph is empty, with tracked changes accepted
     pub fn is_empty(&self) -> bool {
         self.children.is_empty()
             || self.children.iter().all(|child| match child {
-                ParagraphChild::Run(run) => run.text.trim().is_empty(),
-                ParagraphChild::Hyperlink(hyperlink) => {
-                    hyperlink.runs.iter().all(|r| r.text.trim().is_empty())
-                }
+                ParagraphChild::Run(run) => run.is_deleted() || run.text.trim().is_empty(),
+                ParagraphChild::Hyperlink(hyperlink) => hyperlink
+                    .runs
+                    .iter()
+                    .all(|r| r.is_deleted() || r.text.trim().is_empty()),
                 ParagraphChild::Image(_) => false, // Images are never "empty"
                 ParagraphChild::Bookmark(_) => true, // Bookmarks are "empty" (no visible content)
+                ParagraphChild::Footnote(_) => false,
             })
     }
 
@@ -633,6 +808,7 @@ impl Paragraph {
             }
             ParagraphChild::Image(_) => vec![].into_iter(),
             ParagraphChild::Bookmark(_) => vec![].into_iter(), // Bookmarks have no runs
+            ParagraphChild::Footnote(_) => vec![].into_iter(),
         })
     }
 
@@ -695,6 +871,7 @@ struct RunBuilder {
     bold: bool,
     italic: bool,
     monospace: bool,
+    revision: Option<Revision>,
 }
 
 impl RunBuilder {
@@ -708,6 +885,7 @@ impl RunBuilder {
             bold: self.bold,
             italic: self.italic,
             monospace: self.monospace,
+            revision: self.revision,
         }
     }
 }
@@ -1307,6 +1485,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             })],
             numbering: None,
         };
@@ -1360,6 +1539,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }],
             })],
             numbering: None,
@@ -1406,12 +1586,14 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Run(Run {
                     text: "content".to_string(), // Has content
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
             ],
             numbering: None,
@@ -1430,6 +1612,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Hyperlink(Hyperlink {
                     id: None,
@@ -1440,12 +1623,14 @@ mod tests {
                             bold: true,
                             italic: false,
                             monospace: false,
+                            revision: None,
                         },
                         Run {
                             text: " text".to_string(),
                             bold: false,
                             italic: false,
                             monospace: false,
+                            revision: None,
                         },
                     ],
                 }),
@@ -1454,6 +1639,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
             ],
             numbering: None,
@@ -1479,6 +1665,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Image(Image {
                     id: 1,
@@ -1691,6 +1878,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Image(Image {
                     id: 1,
@@ -1765,6 +1953,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }],
             })],
             numbering: None,
@@ -1953,6 +2142,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
             ],
             numbering: None,
@@ -2815,4 +3005,88 @@ mod tests {
         assert_eq!(doc.blocks.len(), 1);
         assert_eq!(doc.plain_text(), "Inside body");
     }
+
+    #[test]
+    fn test_footnote_reference_attached() {
+        let doc_xml = br#"<?xml version="1.0" encoding="UTF-8"?>
+        <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:body>
+                <w:p>
+                    <w:r><w:t>Claim</w:t></w:r>
+                    <w:r><w:rPr><w:vertAlign w:val="superscript"/></w:rPr><w:footnoteReference w:id="1"/></w:r>
+                </w:p>
+            </w:body>
+        </w:document>"#;
+        let footnotes_xml = br#"<?xml version="1.0" encoding="UTF-8"?>
+        <w:footnotes xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:footnote w:type="separator" w:id="-1"><w:p><w:r><w:separator/></w:r></w:p></w:footnote>
+            <w:footnote w:id="1">
+                <w:p><w:r><w:footnoteRef/></w:r><w:r><w:t xml:space="preserve"> See the spec.</w:t></w:r></w:p>
+            </w:footnote>
+        </w:footnotes>"#;
+
+        let footnotes = Footnotes::parse(footnotes_xml).unwrap();
+        assert_eq!(footnotes.len(), 1);
+        assert!(footnotes.get(-1).is_none());
+
+        let mut doc = Document::parse(doc_xml).unwrap();
+        doc.attach_footnotes(&footnotes);
+
+        let Block::Paragraph(para) = &doc.blocks[0] else {
+            panic!("Expected paragraph");
+        };
+        assert_eq!(para.plain_text(), "Claim");
+        let Some(ParagraphChild::Footnote(footnote)) = para.children.last() else {
+            panic!("Expected footnote reference");
+        };
+        assert_eq!(footnote.id, 1);
+        assert_eq!(footnote.paragraphs[0].plain_text().trim(), "See the spec.");
+    }
+
+    #[test]
+    fn test_tracked_changes() {
+        let doc_xml = br#"<?xml version="1.0" encoding="UTF-8"?>
+        <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:body>
+                <w:p>
+                    <w:pPr><w:rPr><w:ins w:id="9" w:author="Ann" w:date="2024-05-01T10:00:00Z"/></w:rPr></w:pPr>
+                    <w:r><w:t xml:space="preserve">Runs </w:t></w:r>
+                    <w:del w:id="1" w:author="Bob" w:date="2024-05-02T09:30:00Z">
+                        <w:r><w:delText>slowly</w:delText></w:r>
+                    </w:del>
+                    <w:ins w:id="2" w:author="Ann">
+                        <w:r><w:rPr><w:b/></w:rPr><w:t>fast</w:t></w:r>
+                    </w:ins>
+                    <w:r><w:t>.</w:t></w:r>
+                </w:p>
+            </w:body>
+        </w:document>"#;
+
+        let doc = Document::parse(doc_xml).unwrap();
+        let Block::Paragraph(para) = &doc.blocks[0] else {
+            panic!("Expected paragraph");
+        };
+        let runs: Vec<&Run> = para.runs().collect();
+        assert_eq!(runs.len(), 4);
+        assert_eq!(runs[0].revision, None);
+        assert_eq!(runs[1].text, "slowly");
+        assert_eq!(
+            runs[1].revision,
+            Some(Revision {
+                kind: RevisionKind::Deletion,
+                author: Some("Bob".to_string()),
+                date: Some("2024-05-02T09:30:00Z".to_string()),
+            })
+        );
+        assert!(runs[1].is_deleted());
+        let inserted = runs[2].revision.as_ref().unwrap();
+        assert_eq!(inserted.kind, RevisionKind::Insertion);
+        assert_eq!(inserted.author.as_deref(), Some("Ann"));
+        assert_eq!(inserted.date, None);
+        assert!(runs[2].bold);
+        assert_eq!(runs[3].revision, None);
+
+        // Plain text reads with the changes accepted
+        assert_eq!(para.plain_text(), "Runs fast.");
+    }
 }
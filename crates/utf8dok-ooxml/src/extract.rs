@@ -6,14 +6,21 @@
 //! When extracting from a self-contained utf8dok DOCX, the extractor
 //! prioritizes the embedded `utf8dok/source.adoc` over parsing the
 //! document content (unless `force_parse` is set).
+//!
+//! Tracked changes are accepted in the AsciiDoc and listed separately in a
+//! change report ([`ExtractedDocument::change_report`]).
 
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::path::Path;
 
 use crate::archive::OoxmlArchive;
-use crate::document::{Block, Document, Hyperlink, Paragraph, ParagraphChild, Run, Table};
+use crate::document::{
+    Block, Document, Footnotes, Hyperlink, Paragraph, ParagraphChild, Revision, RevisionKind, Run,
+    Table,
+};
 use crate::error::Result;
+use crate::numbering::{ordered_style, Numbering};
 use crate::relationships::Relationships;
 use crate::style_map::{
     classify_bookmark, normalize_heading_to_anchor, AnchorMapping, AnchorType, HyperlinkMapping,
@@ -69,9 +76,13 @@ impl Comments {
                 }
                 Ok(Event::End(ref e)) => {
                     let name = e.local_name();
+                    // Paragraphs of a comment become its lines
+                    if name.as_ref() == b"p" && in_comment {
+                        current_text.push('\n');
+                    }
                     if name.as_ref() == b"comment" {
                         if let Some(id) = current_id.take() {
-                            comments.insert(id, current_text.clone());
+                            comments.insert(id, current_text.trim_end().to_string());
                         }
                         in_comment = false;
                         current_text.clear();
@@ -92,10 +103,17 @@ impl Comments {
         self.comments.get(&id).map(|s| s.as_str())
     }
 
-    /// Extract language from a comment if it matches "Language: XXX"
+    /// Whether a comment holds a code block language (`Language: XXX`)
+    pub fn is_language(&self, id: u32) -> bool {
+        self.get(id)
+            .is_some_and(|text| text.trim().starts_with("Language:"))
+    }
+
+    /// Extract language from a comment if its first line matches
+    /// "Language: XXX"
     pub fn get_language(&self, id: u32) -> Option<String> {
         self.get(id).and_then(|text| {
-            let text = text.trim();
+            let text = text.trim().lines().next().unwrap_or_default();
             if text.starts_with("Language:") {
                 Some(text.trim_start_matches("Language:").trim().to_string())
             } else {
@@ -225,6 +243,75 @@ pub struct ExtractedDocument {
     pub metadata: DocumentMetadata,
     /// Indicates where the AsciiDoc content came from
     pub source_origin: SourceOrigin,
+    /// Tracked changes of the document body, in document order
+    pub changes: Vec<TrackedChange>,
+}
+
+/// A tracked change of the document body
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedChange {
+    /// Whether the text was inserted or deleted, by whom and when
+    pub revision: Revision,
+    /// The inserted or deleted text
+    pub text: String,
+}
+
+impl ExtractedDocument {
+    /// The tracked changes as an AsciiDoc table, if there are any
+    ///
+    /// The AsciiDoc of the document has the changes accepted, so this is
+    /// where deleted text and the authors and dates of changes are kept.
+    pub fn change_report(&self) -> Option<String> {
+        if self.changes.is_empty() {
+            return None;
+        }
+        let cell = |text: &str| text.trim().replace('|', "\\|");
+        let mut report = String::from("= Tracked Changes\n\n");
+        report.push_str("[cols=\"1,4,2,2\"]\n|===\n|Change |Text |Author |Date\n\n");
+        for change in &self.changes {
+            let kind = match change.revision.kind {
+                RevisionKind::Insertion => "Inserted",
+                RevisionKind::Deletion => "Deleted",
+            };
+            writeln!(
+                report,
+                "|{} |{} |{} |{}",
+                kind,
+                cell(&change.text),
+                cell(change.revision.author.as_deref().unwrap_or_default()),
+                cell(change.revision.date.as_deref().unwrap_or_default())
+            )
+            .unwrap();
+        }
+        report.push_str("|===\n");
+        Some(report)
+    }
+}
+
+/// Tracked changes of the paragraphs of a document, merging consecutive
+/// runs of the same change
+fn tracked_changes(document: &Document) -> Vec<TrackedChange> {
+    let mut changes: Vec<TrackedChange> = Vec::new();
+    for paragraph in document.paragraphs() {
+        let mut previous: Option<&Revision> = None;
+        for run in paragraph.runs() {
+            match &run.revision {
+                Some(revision) if previous == Some(revision) => {
+                    if let Some(last) = changes.last_mut() {
+                        last.text.push_str(&run.text);
+                    }
+                }
+                Some(revision) => changes.push(TrackedChange {
+                    revision: revision.clone(),
+                    text: run.text.clone(),
+                }),
+                None => {}
+            }
+            previous = run.revision.as_ref();
+        }
+    }
+    changes.retain(|change| !change.text.trim().is_empty());
+    changes
 }
 
 /// Style mappings detected from the document
@@ -394,6 +481,8 @@ impl AsciiDocExtractor {
                 let style_contract =
                     self.build_style_contract_from_stylesheet(&styles, source_file);
                 let metadata = DocumentMetadata::default();
+                // Changes made in Word since, or those of a redline
+                let changes = tracked_changes(&Document::parse(archive.document_xml()?)?);
 
                 return Ok(ExtractedDocument {
                     asciidoc: embedded_source,
@@ -401,15 +490,21 @@ impl AsciiDocExtractor {
                     style_contract,
                     metadata,
                     source_origin: SourceOrigin::Embedded,
+                    changes,
                 });
             }
         }
 
         // Parse document.xml and generate AsciiDoc
         let doc_xml = archive.document_xml()?;
-        let document = Document::parse(doc_xml)?;
+        let mut document = Document::parse(doc_xml)?;
         let styles = StyleSheet::parse(archive.styles_xml()?)?;
 
+        // Attach footnote content so references become footnote:[] macros
+        if let Some(footnotes_xml) = archive.footnotes_xml() {
+            document.attach_footnotes(&Footnotes::parse(footnotes_xml)?);
+        }
+
         // Load relationships for hyperlink resolution
         let relationships = archive
             .document_rels_xml()
@@ -432,6 +527,12 @@ impl AsciiDocExtractor {
         // Parse comment ranges from document.xml
         let comment_ranges = CommentRanges::parse(doc_xml);
 
+        // Parse numbering definitions to tell numbered from bulleted lists
+        let numbering = archive
+            .numbering_xml()
+            .map(Numbering::parse)
+            .unwrap_or_default();
+
         // Build the style contract (ADR-007)
         let style_contract =
             self.build_style_contract(&document, &styles, relationships.as_ref(), source_file);
@@ -443,6 +544,7 @@ impl AsciiDocExtractor {
             &metadata,
             &comments,
             &comment_ranges,
+            &numbering,
         );
 
         Ok(ExtractedDocument {
@@ -451,6 +553,7 @@ impl AsciiDocExtractor {
             style_contract,
             metadata,
             source_origin: SourceOrigin::Parsed,
+            changes: tracked_changes(&document),
         })
     }
 
@@ -711,6 +814,7 @@ impl AsciiDocExtractor {
     }
 
     /// Convert document to AsciiDoc string
+    #[allow(clippy::too_many_arguments)]
     fn convert_to_asciidoc(
         &self,
         document: &Document,
@@ -719,11 +823,13 @@ impl AsciiDocExtractor {
         metadata: &DocumentMetadata,
         comments: &Comments,
         comment_ranges: &CommentRanges,
+        numbering: &Numbering,
     ) -> String {
         let mut output = String::new();
         let mut title_written = false;
         let mut last_was_list = false;
-        let mut last_num_id: Option<u32> = None;
+        // Numbering instances of the list being written
+        let mut list_num_ids: Vec<u32> = Vec::new();
         let mut block_index: usize = 0;
 
         // If we have a title from docProps, use it as the document title
@@ -757,7 +863,6 @@ impl AsciiDocExtractor {
                             if last_was_list {
                                 writeln!(output).unwrap();
                                 last_was_list = false;
-                                last_num_id = None;
                             }
                             // If no title was written and this is level 1, use as title
                             if !title_written && level == 1 && self.include_header {
@@ -770,6 +875,12 @@ impl AsciiDocExtractor {
                                 writeln!(output).unwrap();
                                 title_written = true;
                             } else {
+                                self.write_comments(
+                                    &mut output,
+                                    block_index,
+                                    comments,
+                                    comment_ranges,
+                                );
                                 let prefix = "=".repeat(level as usize + 1);
                                 writeln!(output, "{} {}", prefix, text.trim()).unwrap();
                                 writeln!(output).unwrap();
@@ -784,7 +895,6 @@ impl AsciiDocExtractor {
                             if last_was_list {
                                 writeln!(output).unwrap();
                                 last_was_list = false;
-                                last_num_id = None;
                             }
                             // Check for language from comment
                             let lang = self.get_language_from_comment(
@@ -812,7 +922,6 @@ impl AsciiDocExtractor {
                         if last_was_list {
                             writeln!(output).unwrap();
                             last_was_list = false;
-                            last_num_id = None;
                         }
                         // Get raw text without formatting marks for code blocks
                         let raw_text = self.get_raw_paragraph_text(para);
@@ -833,29 +942,33 @@ impl AsciiDocExtractor {
                     }
 
                     // Check if this is a list item
-                    if let Some(ref numbering) = para.numbering {
-                        let is_new_list = last_num_id != Some(numbering.num_id);
+                    if let Some(ref numbering_ref) = para.numbering {
+                        let num_id = numbering_ref.num_id;
+                        let ilvl = numbering_ref.ilvl;
+                        let ordered = self.is_numbered_list(num_id, ilvl, numbering);
 
-                        // Add blank line before new list
-                        if is_new_list && !last_was_list {
-                            // Already have blank line from previous paragraph
+                        if !last_was_list {
+                            list_num_ids.clear();
+                        }
+                        if !list_num_ids.contains(&num_id) {
+                            if ilvl == 0 {
+                                // Separate a following list so it restarts
+                                if last_was_list {
+                                    writeln!(output).unwrap();
+                                    writeln!(output, "//").unwrap();
+                                }
+                                if ordered {
+                                    self.write_list_attributes(&mut output, num_id, numbering);
+                                }
+                            }
+                            list_num_ids.push(num_id);
                         }
 
-                        // Determine list marker based on style
-                        // NumId 1-9 are typically bullet lists, 10+ are numbered
-                        // Also check if the numbering ilvl > 0 for nested items
-                        let indent = "*".repeat((numbering.ilvl + 1) as usize);
-
-                        // Check if this looks like a numbered list (could improve with numbering.xml)
-                        let marker = if self.is_numbered_list(numbering.num_id, styles) {
-                            ".".repeat((numbering.ilvl + 1) as usize)
-                        } else {
-                            indent
-                        };
+                        let marker = if ordered { "." } else { "*" };
+                        let marker = marker.repeat((ilvl + 1) as usize);
 
                         writeln!(output, "{} {}", marker, text.trim()).unwrap();
                         last_was_list = true;
-                        last_num_id = Some(numbering.num_id);
                         block_index += 1;
                         continue;
                     }
@@ -864,11 +977,11 @@ impl AsciiDocExtractor {
                     if last_was_list {
                         writeln!(output).unwrap();
                         last_was_list = false;
-                        last_num_id = None;
                     }
 
                     // Regular paragraph
                     if !text.trim().is_empty() {
+                        self.write_comments(&mut output, block_index, comments, comment_ranges);
                         writeln!(output, "{}", text.trim()).unwrap();
                         writeln!(output).unwrap();
                     }
@@ -878,7 +991,6 @@ impl AsciiDocExtractor {
                     if last_was_list {
                         writeln!(output).unwrap();
                         last_was_list = false;
-                        last_num_id = None;
                     }
                     let table_text = self.convert_table(table);
                     // table_text already ends with newline from |===
@@ -895,7 +1007,6 @@ impl AsciiDocExtractor {
                     if last_was_list {
                         writeln!(output).unwrap();
                         last_was_list = false;
-                        last_num_id = None;
                     }
                     writeln!(output, "'''").unwrap();
                     writeln!(output).unwrap();
@@ -907,6 +1018,34 @@ impl AsciiDocExtractor {
         output
     }
 
+    /// Write the Word comments on the block at `block_index` as `//`
+    /// comment lines
+    ///
+    /// Code block language comments are left to the code block.
+    fn write_comments(
+        &self,
+        output: &mut String,
+        block_index: usize,
+        comments: &Comments,
+        comment_ranges: &CommentRanges,
+    ) {
+        let Some(comment_ids) = comment_ranges.get_comment_ids(block_index) else {
+            return;
+        };
+        for &comment_id in comment_ids {
+            if comments.is_language(comment_id) {
+                continue;
+            }
+            for line in comments.get(comment_id).unwrap_or_default().lines() {
+                if line.is_empty() {
+                    writeln!(output, "//").unwrap();
+                } else {
+                    writeln!(output, "// {}", line).unwrap();
+                }
+            }
+        }
+    }
+
     /// Get language from comment for a code block at a given index
     fn get_language_from_comment(
         &self,
@@ -925,17 +1064,34 @@ impl AsciiDocExtractor {
         None
     }
 
-    /// Check if a numbering ID represents a numbered list
-    ///
-    /// This uses a simple heuristic based on our writer's convention:
-    /// - numId 1 = unordered (bullet) list
-    /// - numId 2 = ordered (numbered) list
+    /// Check if a level of a numbering instance is numbered
     ///
-    /// A more complete implementation would parse numbering.xml to check
-    /// the numFmt value (bullet vs decimal/lowerLetter/etc.)
-    fn is_numbered_list(&self, num_id: u32, _styles: &StyleSheet) -> bool {
-        // Our writer uses numId 2 for ordered lists
-        num_id == 2
+    /// Uses the numbering definitions when the document has them, and
+    /// otherwise falls back to the convention of older writer versions:
+    /// numId 1 for bullet lists and numId 2 for numbered lists.
+    fn is_numbered_list(&self, num_id: u32, ilvl: u32, numbering: &Numbering) -> bool {
+        match numbering.level(num_id, ilvl) {
+            Some(level) => !level.is_bullet(),
+            None => num_id == 2,
+        }
+    }
+
+    /// Write the `[loweralpha,start=3]` attributes of a numbered list
+    /// whose numbering differs from the AsciiDoc default
+    fn write_list_attributes(&self, output: &mut String, num_id: u32, numbering: &Numbering) {
+        let Some(level) = numbering.level(num_id, 0) else {
+            return;
+        };
+        let mut attributes = Vec::new();
+        if let Some(style) = level.style().filter(|&style| style != ordered_style(0)) {
+            attributes.push(style.as_style().to_string());
+        }
+        if level.start != 1 {
+            attributes.push(format!("start={}", level.start));
+        }
+        if !attributes.is_empty() {
+            writeln!(output, "[{}]", attributes.join(",")).unwrap();
+        }
     }
 
     /// Get raw text from a paragraph without any formatting marks
@@ -945,10 +1101,12 @@ impl AsciiDocExtractor {
         for child in &para.children {
             match child {
                 ParagraphChild::Run(run) => {
-                    result.push_str(&run.text);
+                    if !run.is_deleted() {
+                        result.push_str(&run.text);
+                    }
                 }
                 ParagraphChild::Hyperlink(hyperlink) => {
-                    for run in &hyperlink.runs {
+                    for run in hyperlink.runs.iter().filter(|run| !run.is_deleted()) {
                         result.push_str(&run.text);
                     }
                 }
@@ -958,8 +1116,8 @@ impl AsciiDocExtractor {
                         result.push_str(alt);
                     }
                 }
-                ParagraphChild::Bookmark(_) => {
-                    // Bookmarks have no text content
+                ParagraphChild::Bookmark(_) | ParagraphChild::Footnote(_) => {
+                    // Bookmarks and footnote references have no inline text
                 }
             }
         }
@@ -1019,10 +1177,7 @@ impl AsciiDocExtractor {
                 ParagraphChild::Run(run) => {
                     // Try to merge with previous run if formatting matches
                     if let Some(last) = merged_runs.last_mut() {
-                        if last.bold == run.bold
-                            && last.italic == run.italic
-                            && last.monospace == run.monospace
-                        {
+                        if same_formatting(last, run) {
                             // Same formatting - merge text
                             last.text.push_str(&run.text);
                         } else {
@@ -1058,6 +1213,20 @@ impl AsciiDocExtractor {
                     // Output AsciiDoc anchor
                     result.push_str(&format!("[[{}]]", bookmark.name));
                 }
+                ParagraphChild::Footnote(footnote) => {
+                    // Flush any pending merged runs before footnote
+                    for merged in merged_runs.drain(..) {
+                        result.push_str(&self.convert_run(&merged));
+                    }
+                    let text = footnote
+                        .paragraphs
+                        .iter()
+                        .map(|p| self.convert_paragraph_with_rels(p, rels).trim().to_string())
+                        .filter(|t| !t.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    result.push_str(&format!("footnote:[{}]", text.replace(']', "\\]")));
+                }
             }
         }
 
@@ -1109,8 +1278,16 @@ impl AsciiDocExtractor {
         format!("image::{}{}\n", target, attrs_str)
     }
 
-    /// Convert a run to AsciiDoc text
+    /// Convert a run to AsciiDoc text, with tracked changes accepted
     fn convert_run(&self, run: &Run) -> String {
+        if run.is_deleted() {
+            return String::new();
+        }
+        self.convert_run_formatting(run)
+    }
+
+    /// Convert a run to AsciiDoc text with its formatting marks
+    fn convert_run_formatting(&self, run: &Run) -> String {
         let text = &run.text;
 
         if !self.preserve_formatting {
@@ -1138,10 +1315,7 @@ impl AsciiDocExtractor {
 
         for run in runs {
             if let Some(last) = merged_runs.last_mut() {
-                if last.bold == run.bold
-                    && last.italic == run.italic
-                    && last.monospace == run.monospace
-                {
+                if same_formatting(last, run) {
                     // Same formatting - merge text
                     last.text.push_str(&run.text);
                 } else {
@@ -1241,6 +1415,16 @@ impl AsciiDocExtractor {
     }
 }
 
+/// Whether two runs can be merged: same formatting and the same kind of
+/// tracked change
+fn same_formatting(a: &Run, b: &Run) -> bool {
+    a.bold == b.bold
+        && a.italic == b.italic
+        && a.monospace == b.monospace
+        && a.revision.as_ref().map(|revision| revision.kind)
+            == b.revision.as_ref().map(|revision| revision.kind)
+}
+
 impl StyleMappings {
     /// Generate TOML configuration content
     pub fn to_toml(&self) -> String {
@@ -1284,6 +1468,7 @@ mod tests {
         let metadata = DocumentMetadata::default();
         let comments = Comments::default();
         let comment_ranges = CommentRanges::default();
+        let numbering = Numbering::default();
 
         let extractor = AsciiDocExtractor::new();
         let asciidoc = extractor.convert_to_asciidoc(
@@ -1293,6 +1478,7 @@ mod tests {
             &metadata,
             &comments,
             &comment_ranges,
+            &numbering,
         );
 
         assert!(asciidoc.contains("Hello, world!"));
@@ -1330,6 +1516,7 @@ mod tests {
         let metadata = DocumentMetadata::default();
         let comments = Comments::default();
         let comment_ranges = CommentRanges::default();
+        let numbering = Numbering::default();
 
         let extractor = AsciiDocExtractor::new();
         let asciidoc = extractor.convert_to_asciidoc(
@@ -1339,6 +1526,7 @@ mod tests {
             &metadata,
             &comments,
             &comment_ranges,
+            &numbering,
         );
 
         println!("Generated AsciiDoc:\n{}", asciidoc);
@@ -1350,6 +1538,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_tracked_changes_as_change_report() {
+        let xml = br#"<?xml version="1.0" encoding="UTF-8"?>
+        <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:body>
+                <w:p>
+                    <w:r><w:t xml:space="preserve">Runs </w:t></w:r>
+                    <w:del w:id="1" w:author="Bob"><w:r><w:delText>slowly</w:delText></w:r></w:del>
+                    <w:ins w:id="2" w:author="Ann" w:date="2024-05-01T09:30:00Z"><w:r><w:t>fast</w:t></w:r><w:r><w:t>er</w:t></w:r></w:ins>
+                    <w:r><w:t>.</w:t></w:r>
+                </w:p>
+                <w:p>
+                    <w:del w:id="3" w:author="Bob"><w:r><w:delText>Dropped | gone.</w:delText></w:r></w:del>
+                </w:p>
+            </w:body>
+        </w:document>"#;
+
+        let doc = Document::parse(xml).unwrap();
+        let asciidoc = AsciiDocExtractor::new().convert_to_asciidoc(
+            &doc,
+            &StyleSheet::default(),
+            None,
+            &DocumentMetadata::default(),
+            &Comments::default(),
+            &CommentRanges::default(),
+            &Numbering::default(),
+        );
+
+        // The AsciiDoc has the changes accepted
+        assert_eq!(asciidoc, "Runs faster.\n\n");
+
+        let extracted = ExtractedDocument {
+            asciidoc,
+            style_mappings: StyleMappings::default(),
+            style_contract: StyleContract::new(),
+            metadata: DocumentMetadata::default(),
+            source_origin: SourceOrigin::Parsed,
+            changes: tracked_changes(&doc),
+        };
+        let change = |kind, author: &str, date: Option<&str>, text: &str| TrackedChange {
+            revision: Revision {
+                kind,
+                author: Some(author.to_string()),
+                date: date.map(str::to_string),
+            },
+            text: text.to_string(),
+        };
+        assert_eq!(
+            extracted.changes,
+            vec![
+                change(RevisionKind::Deletion, "Bob", None, "slowly"),
+                change(
+                    RevisionKind::Insertion,
+                    "Ann",
+                    Some("2024-05-01T09:30:00Z"),
+                    "faster"
+                ),
+                change(RevisionKind::Deletion, "Bob", None, "Dropped | gone."),
+            ]
+        );
+        assert_eq!(
+            extracted.change_report().unwrap(),
+            "= Tracked Changes\n\n[cols=\"1,4,2,2\"]\n|===\n|Change |Text |Author |Date\n\n\
+             |Deleted |slowly |Bob |\n\
+             |Inserted |faster |Ann |2024-05-01T09:30:00Z\n\
+             |Deleted |Dropped \\| gone. |Bob |\n\
+             |===\n"
+        );
+    }
+
     #[test]
     fn test_comments_parse() {
         let xml = br#"<?xml version="1.0" encoding="UTF-8"?>
@@ -1574,15 +1832,15 @@ mod tests {
     #[test]
     fn test_is_numbered_list_heuristic() {
         let extractor = AsciiDocExtractor::new();
-        let styles = StyleSheet::default();
+        let numbering = Numbering::default();
 
         // numId 2 is ordered list by convention
-        assert!(extractor.is_numbered_list(2, &styles));
+        assert!(extractor.is_numbered_list(2, 0, &numbering));
         // numId 1 is unordered list
-        assert!(!extractor.is_numbered_list(1, &styles));
+        assert!(!extractor.is_numbered_list(1, 0, &numbering));
         // Other numIds default to unordered
-        assert!(!extractor.is_numbered_list(0, &styles));
-        assert!(!extractor.is_numbered_list(3, &styles));
+        assert!(!extractor.is_numbered_list(0, 0, &numbering));
+        assert!(!extractor.is_numbered_list(3, 0, &numbering));
     }
 
     #[test]
@@ -1599,12 +1857,14 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Run(Run {
                     text: "code\nmore".to_string(),
                     bold: false,
                     italic: false,
                     monospace: true,
+                    revision: None,
                 }),
             ],
         };
@@ -1625,6 +1885,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: true,
+                revision: None,
             })],
         };
 
@@ -1644,6 +1905,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: true,
+                revision: None,
             })],
         };
 
@@ -1663,6 +1925,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Hyperlink(Hyperlink {
                     id: None,
@@ -1672,6 +1935,7 @@ mod tests {
                         bold: false,
                         italic: false,
                         monospace: false,
+                        revision: None,
                     }],
                 }),
                 ParagraphChild::Run(Run {
@@ -1679,6 +1943,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
             ],
         };
@@ -1700,6 +1965,7 @@ mod tests {
                     bold: false,
                     italic: false,
                     monospace: false,
+                    revision: None,
                 }),
                 ParagraphChild::Image(crate::image::Image {
                     id: 1,
@@ -1731,6 +1997,7 @@ mod tests {
                 bold: false,
                 italic: false,
                 monospace: false,
+                revision: None,
             }],
         };
 
@@ -1756,6 +2023,7 @@ mod tests {
             bold: true,
             italic: false,
             monospace: false,
+            revision: None,
         };
 
         let result = extractor.convert_run(&run);
@@ -1773,6 +2041,7 @@ mod tests {
             bold: true,
             italic: true,
             monospace: false,
+            revision: None,
         };
 
         let result = extractor.convert_run(&run);
@@ -1882,9 +2151,10 @@ mod tests {
         </w:comments>"#;
 
         let comments = Comments::parse(xml);
-        // Current parser concatenates all text in a comment
+        // Paragraphs of a comment are kept as lines
         let text = comments.get(0).unwrap();
-        assert!(text.contains("Language: typescript"));
+        assert_eq!(text, "Language: typescript\nAdditional info ignored");
+        assert_eq!(comments.get_language(0), Some("typescript".to_string()));
     }
 
     #[test]
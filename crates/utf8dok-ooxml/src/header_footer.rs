@@ -0,0 +1,371 @@
+//! Page headers and footers
+//!
+//! [`parts`] renders the header or footer parts configured by a
+//! [`HeaderFooterConfig`] of the style contract: one part per page variant
+//! (every page, first page, even pages), with `PAGE` and `NUMPAGES` fields
+//! in place of the `{page}` and `{pages}` placeholders. The writer
+//! references the parts from the properties of every section.
+
+use std::collections::HashMap;
+
+use crate::relationships::Relationships;
+use crate::style_map::{CoverMetadata, HeaderFooterConfig};
+use crate::styles::{ElementType, StyleMap};
+use crate::toc::field;
+use crate::writer::escape_xml;
+
+/// Header or footer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Header,
+    Footer,
+}
+
+impl Kind {
+    /// Part name prefix, e.g. `header` for `word/header1.xml`
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Header => "header",
+            Kind::Footer => "footer",
+        }
+    }
+
+    /// Root element of the part
+    fn element(self) -> &'static str {
+        match self {
+            Kind::Header => "w:hdr",
+            Kind::Footer => "w:ftr",
+        }
+    }
+
+    /// Relationship type of the part
+    pub fn relationship_type(self) -> &'static str {
+        match self {
+            Kind::Header => Relationships::TYPE_HEADER,
+            Kind::Footer => Relationships::TYPE_FOOTER,
+        }
+    }
+
+    /// Content type of the part
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Kind::Header => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml"
+            }
+            Kind::Footer => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.footer+xml"
+            }
+        }
+    }
+
+    fn style(self) -> ElementType {
+        match self {
+            Kind::Header => ElementType::Header,
+            Kind::Footer => ElementType::Footer,
+        }
+    }
+}
+
+/// Pages a part is shown on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Every page, or odd pages when even pages have their own part
+    Default,
+    /// First page of each section
+    First,
+    /// Even pages
+    Even,
+}
+
+impl Variant {
+    /// `w:type` of the section's reference to the part
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Default => "default",
+            Variant::First => "first",
+            Variant::Even => "even",
+        }
+    }
+}
+
+/// A rendered header or footer part
+#[derive(Debug, Clone)]
+pub struct Part {
+    pub kind: Kind,
+    pub variant: Variant,
+    pub xml: String,
+}
+
+/// Render the parts of a header or footer configuration
+///
+/// With `title_page` or `even_pages` set (because the other of header and
+/// footer has that variant), a missing first-page or even-page template
+/// falls back to `content`, so the pages do not lose it.
+pub fn parts(
+    kind: Kind,
+    config: &HeaderFooterConfig,
+    title_page: bool,
+    even_pages: bool,
+    metadata: &CoverMetadata,
+    attributes: &HashMap<String, String>,
+    styles: &StyleMap,
+) -> Vec<Part> {
+    let content = config.content.as_ref();
+    let first = config.first.as_ref().or(content.filter(|_| title_page));
+    let even = config.even.as_ref().or(content.filter(|_| even_pages));
+    let style = config
+        .style
+        .as_deref()
+        .unwrap_or_else(|| styles.get(kind.style()));
+
+    [
+        (Variant::Default, content),
+        (Variant::First, first),
+        (Variant::Even, even),
+    ]
+    .into_iter()
+    .filter_map(|(variant, template)| {
+        let text = HeaderFooterConfig::expand_template(template?, metadata, attributes);
+        Some(Part {
+            kind,
+            variant,
+            xml: part_xml(kind, style, &text),
+        })
+    })
+    .collect()
+}
+
+/// Generate a header or footer part with one paragraph per line of `text`
+fn part_xml(kind: Kind, style: &str, text: &str) -> String {
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<{} xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\n",
+        kind.element()
+    );
+    // A part needs at least one paragraph, even when empty
+    let lines: Vec<&str> = text.lines().collect();
+    for line in if lines.is_empty() { vec![""] } else { lines } {
+        xml.push_str(&format!(
+            "<w:p>\n<w:pPr>\n<w:pStyle w:val=\"{}\"/>\n</w:pPr>\n",
+            escape_xml(style)
+        ));
+        for (index, segment) in line.split('\t').enumerate() {
+            if index > 0 {
+                xml.push_str("<w:r>\n<w:tab/>\n</w:r>\n");
+            }
+            push_segment(&mut xml, segment);
+        }
+        xml.push_str("</w:p>\n");
+    }
+    xml.push_str(&format!("</{}>", kind.element()));
+    xml
+}
+
+/// Push the runs of text with page number placeholders
+fn push_segment(xml: &mut String, mut segment: &str) {
+    loop {
+        let next = [
+            (HeaderFooterConfig::PAGE, "PAGE"),
+            (HeaderFooterConfig::PAGES, "NUMPAGES"),
+        ]
+        .into_iter()
+        .filter_map(|(placeholder, instruction)| {
+            segment
+                .find(placeholder)
+                .map(|at| (at, placeholder, instruction))
+        })
+        .min_by_key(|(at, _, _)| *at);
+
+        let text = next.map_or(segment, |(at, _, _)| &segment[..at]);
+        if !text.is_empty() {
+            xml.push_str(&format!(
+                "<w:r>\n<w:t xml:space=\"preserve\">{}</w:t>\n</w:r>\n",
+                escape_xml(text)
+            ));
+        }
+        let Some((at, placeholder, instruction)) = next else {
+            return;
+        };
+        // The number is left to Word, which knows the layout
+        xml.push_str(&field(instruction, ""));
+        segment = &segment[at + placeholder.len()..];
+    }
+}
+
+/// Settings elements that follow `w:evenAndOddHeaders` in `word/settings.xml`
+const SETTINGS_AFTER_EVEN_AND_ODD: &[&str] = &[
+    "bookFoldRevPrinting",
+    "bookFoldPrinting",
+    "bookFoldPrintingSheets",
+    "drawingGridHorizontalSpacing",
+    "drawingGridVerticalSpacing",
+    "displayHorizontalDrawingGridEvery",
+    "displayVerticalDrawingGridEvery",
+    "doNotUseMarginsForDrawingGridOrigin",
+    "drawingGridHorizontalOrigin",
+    "drawingGridVerticalOrigin",
+    "doNotShadeFormData",
+    "noPunctuationKerning",
+    "characterSpacingControl",
+    "printTwoOnOne",
+    "strictFirstAndLastChars",
+    "noLineBreaksAfter",
+    "noLineBreaksBefore",
+    "savePreviewPicture",
+    "updateFields",
+    "hdrShapeDefaults",
+    "footnotePr",
+    "endnotePr",
+    "compat",
+    "docVars",
+    "rsids",
+    "mathPr",
+    "attachedSchema",
+    "themeFontLang",
+    "clrSchemeMapping",
+    "doNotIncludeSubdocsInStats",
+    "doNotAutoCompressPictures",
+    "forceUpgrade",
+    "captions",
+    "readModeInkLockDown",
+    "smartTagType",
+    "schemaLibrary",
+    "shapeDefaults",
+    "doNotEmbedSmartTags",
+    "decimalSymbol",
+    "listSeparator",
+];
+
+/// Turn on separate even and odd page headers in a settings part
+///
+/// Creates the part when the template has none.
+pub fn enable_even_and_odd_headers(settings: Option<&str>) -> String {
+    const ELEMENT: &str = "<w:evenAndOddHeaders/>";
+    let Some(settings) = settings else {
+        return format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<w:settings xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\n{}\n</w:settings>",
+            ELEMENT
+        );
+    };
+    if settings.contains("<w:evenAndOddHeaders") {
+        return settings.to_string();
+    }
+
+    // Keep the schema order of the settings
+    let position = SETTINGS_AFTER_EVEN_AND_ODD
+        .iter()
+        .filter_map(|name| {
+            let tag = format!("<w:{}", name);
+            settings.match_indices(&tag).find_map(|(at, _)| {
+                // Skip longer names with the same prefix
+                let next = settings[at + tag.len()..].chars().next();
+                matches!(next, Some(' ' | '/' | '>' | '\n' | '\r' | '\t')).then_some(at)
+            })
+        })
+        .min()
+        .or_else(|| settings.rfind("</w:settings>"));
+    match position {
+        Some(at) => format!("{}{}{}", &settings[..at], ELEMENT, &settings[at..]),
+        None => settings.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(content: &str) -> HeaderFooterConfig {
+        HeaderFooterConfig {
+            content: Some(content.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_page_fields_tabs_and_lines() {
+        let metadata = CoverMetadata {
+            title: "Design & Notes".to_string(),
+            ..Default::default()
+        };
+        let mut attributes = HashMap::new();
+        attributes.insert("classification".to_string(), "Internal".to_string());
+        let parts = parts(
+            Kind::Footer,
+            &config("{classification}\t{title}\tPage {page} of {pages}\nsecond"),
+            false,
+            false,
+            &metadata,
+            &attributes,
+            &StyleMap::default(),
+        );
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].variant, Variant::Default);
+        let xml = &parts[0].xml;
+        assert!(xml.contains("<w:ftr "));
+        assert!(xml.ends_with("</w:ftr>"));
+        assert_eq!(xml.matches("<w:pStyle w:val=\"Footer\"/>").count(), 2);
+        assert!(xml.contains(">Internal</w:t>"));
+        assert!(xml.contains(">Design &amp; Notes</w:t>"));
+        assert_eq!(xml.matches("<w:tab/>").count(), 2);
+        assert!(xml.contains(">Page </w:t>"));
+        assert!(xml.contains("<w:instrText xml:space=\"preserve\"> PAGE </w:instrText>"));
+        assert!(xml.contains("> of </w:t>"));
+        assert!(xml.contains("<w:instrText xml:space=\"preserve\"> NUMPAGES </w:instrText>"));
+        assert!(xml.contains(">second</w:t>"));
+    }
+
+    #[test]
+    fn test_variants_fall_back_to_content() {
+        let header = HeaderFooterConfig {
+            style: Some("CorporateHeader".to_string()),
+            content: Some("{title}".to_string()),
+            first: Some(String::new()),
+            even: None,
+        };
+        let render = |even_pages| {
+            parts(
+                Kind::Header,
+                &header,
+                true,
+                even_pages,
+                &CoverMetadata::default(),
+                &HashMap::new(),
+                &StyleMap::default(),
+            )
+        };
+
+        let variants: Vec<Variant> = render(false).iter().map(|p| p.variant).collect();
+        assert_eq!(variants, vec![Variant::Default, Variant::First]);
+        let parts = render(true);
+        let variants: Vec<Variant> = parts.iter().map(|p| p.variant).collect();
+        assert_eq!(
+            variants,
+            vec![Variant::Default, Variant::First, Variant::Even]
+        );
+
+        // The empty first page header still has a paragraph
+        assert!(parts[1]
+            .xml
+            .contains("<w:pStyle w:val=\"CorporateHeader\"/>"));
+        assert!(!parts[1].xml.contains("<w:r>"));
+    }
+
+    #[test]
+    fn test_enable_even_and_odd_headers() {
+        let created = enable_even_and_odd_headers(None);
+        assert!(created.contains("<w:settings "));
+        assert!(created.contains("<w:evenAndOddHeaders/>"));
+
+        let settings = "<w:settings><w:zoom w:percent=\"100\"/><w:defaultTabStop w:val=\"720\"/><w:characterSpacingControl w:val=\"doNotCompress\"/><w:compat/></w:settings>";
+        assert_eq!(
+            enable_even_and_odd_headers(Some(settings)),
+            "<w:settings><w:zoom w:percent=\"100\"/><w:defaultTabStop w:val=\"720\"/><w:evenAndOddHeaders/><w:characterSpacingControl w:val=\"doNotCompress\"/><w:compat/></w:settings>"
+        );
+        let enabled = enable_even_and_odd_headers(Some(settings));
+        assert_eq!(enable_even_and_odd_headers(Some(&enabled)), enabled);
+        assert_eq!(
+            enable_even_and_odd_headers(Some("<w:settings></w:settings>")),
+            "<w:settings><w:evenAndOddHeaders/></w:settings>"
+        );
+    }
+}
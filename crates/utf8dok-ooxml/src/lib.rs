@@ -27,13 +27,17 @@ pub mod conversion;
 pub mod document;
 pub mod error;
 pub mod extract;
+pub mod header_footer;
 pub mod image;
 pub mod manifest;
+pub mod numbering;
+pub mod redline;
 pub mod relationships;
 pub mod style_contract_validator;
 pub mod style_map;
 pub mod styles;
 pub mod template;
+pub mod toc;
 pub mod writer;
 
 /// Test utilities for creating DOCX fixtures. Available unconditionally for integration tests.
@@ -43,10 +47,11 @@ pub mod test_utils;
 pub use archive::OoxmlArchive;
 pub use conversion::{convert_document, convert_document_with_styles, ConversionContext, ToAst};
 pub use document::{
-    Block, Document, Hyperlink, Paragraph, ParagraphChild, Run, Table, TableCell, TableRow,
+    Block, Document, FootnoteReference, Footnotes, Hyperlink, Paragraph, ParagraphChild, Revision,
+    RevisionKind, Run, Table, TableCell, TableRow,
 };
 pub use error::{OoxmlError, Result};
-pub use extract::{AsciiDocExtractor, ExtractedDocument, SourceOrigin};
+pub use extract::{AsciiDocExtractor, ExtractedDocument, SourceOrigin, TrackedChange};
 pub use manifest::{ElementMeta, Manifest, MANIFEST_PATH};
 pub use relationships::Relationships;
 pub use style_contract_validator::{
@@ -0,0 +1,438 @@
+//! Numbering definitions (word/numbering.xml)
+//!
+//! [`Numbering`] builds the `w:abstractNum`/`w:num` definitions the writer
+//! gives each list, and reads the level formats of an existing numbering
+//! part so the extractor can tell numbered lists from bulleted ones.
+//!
+//! Every list gets a definition of its own, so each list restarts at its
+//! start number. Generated definitions are numbered after those of the
+//! template, and merged into its numbering part when it has one.
+
+use std::collections::HashMap;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use utf8dok_ast::{ListType, NumberingStyle};
+
+/// Number of levels in a numbering definition
+pub const LEVELS: u8 = 9;
+
+/// Bullet characters by level
+const BULLETS: [&str; 3] = ["\u{2022}", "\u{25E6}", "\u{25AA}"];
+
+/// Numbering styles by level, the way AsciiDoc numbers nested lists
+const ORDERED_STYLES: [NumberingStyle; 5] = [
+    NumberingStyle::Arabic,
+    NumberingStyle::LowerAlpha,
+    NumberingStyle::LowerRoman,
+    NumberingStyle::UpperAlpha,
+    NumberingStyle::UpperRoman,
+];
+
+/// One level of a numbering definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    /// Number format (`w:numFmt`: `bullet`, `decimal`, `lowerLetter`, ...)
+    pub format: String,
+    /// Level text (`%1.` or the bullet character)
+    pub text: String,
+    /// First number of the level
+    pub start: u32,
+}
+
+impl Level {
+    /// Whether the level shows a bullet instead of a number
+    pub fn is_bullet(&self) -> bool {
+        self.format == "bullet"
+    }
+
+    /// The AsciiDoc numbering style of a numbered level
+    pub fn style(&self) -> Option<NumberingStyle> {
+        match self.format.as_str() {
+            "decimal" => Some(NumberingStyle::Arabic),
+            "lowerLetter" => Some(NumberingStyle::LowerAlpha),
+            "upperLetter" => Some(NumberingStyle::UpperAlpha),
+            "lowerRoman" => Some(NumberingStyle::LowerRoman),
+            "upperRoman" => Some(NumberingStyle::UpperRoman),
+            _ => None,
+        }
+    }
+}
+
+/// A definition generated for a list
+#[derive(Debug, Clone)]
+struct Definition {
+    abstract_id: u32,
+    num_id: u32,
+}
+
+/// Numbering definitions of a document
+#[derive(Debug, Clone)]
+pub struct Numbering {
+    /// Levels of each abstract numbering definition
+    abstract_levels: HashMap<u32, Vec<Level>>,
+    /// Abstract definition and start overrides (level, start) of each numId
+    nums: HashMap<u32, (u32, Vec<(u32, u32)>)>,
+    /// Definitions generated for lists, in order
+    generated: Vec<Definition>,
+    next_abstract_id: u32,
+    next_num_id: u32,
+}
+
+impl Default for Numbering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Numbering {
+    /// Create empty numbering definitions
+    pub fn new() -> Self {
+        Self {
+            abstract_levels: HashMap::new(),
+            nums: HashMap::new(),
+            generated: Vec::new(),
+            next_abstract_id: 0,
+            // numId 0 means "not numbered"
+            next_num_id: 1,
+        }
+    }
+
+    /// Parse the definitions of a numbering part
+    ///
+    /// Definitions added afterwards are numbered after the parsed ones.
+    pub fn parse(xml: &[u8]) -> Self {
+        let mut numbering = Self::new();
+        let mut reader = Reader::from_reader(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut buf = Vec::new();
+        let mut abstract_id: Option<u32> = None;
+        let mut num_id: Option<u32> = None;
+        let mut level: Option<(u32, Level)> = None;
+        let mut override_level: Option<u32> = None;
+
+        loop {
+            let (event, empty) = match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => (e, false),
+                Ok(Event::Empty(e)) => (e, true),
+                Ok(Event::End(e)) => {
+                    match e.local_name().as_ref() {
+                        b"abstractNum" => abstract_id = None,
+                        b"num" => num_id = None,
+                        b"lvlOverride" => override_level = None,
+                        b"lvl" => {
+                            if let (Some(id), Some((index, lvl))) = (abstract_id, level.take()) {
+                                let levels = numbering.abstract_levels.entry(id).or_default();
+                                let index = index as usize;
+                                if levels.len() <= index {
+                                    levels.resize(index + 1, lvl.clone());
+                                }
+                                levels[index] = lvl;
+                            }
+                        }
+                        _ => {}
+                    }
+                    buf.clear();
+                    continue;
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {
+                    buf.clear();
+                    continue;
+                }
+            };
+
+            let value = val(&event);
+            match event.local_name().as_ref() {
+                b"abstractNum" if !empty => {
+                    abstract_id = attribute(&event, b"abstractNumId").and_then(|v| v.parse().ok());
+                    if let Some(id) = abstract_id {
+                        numbering.next_abstract_id = numbering.next_abstract_id.max(id + 1);
+                    }
+                }
+                b"num" if !empty => {
+                    num_id = attribute(&event, b"numId").and_then(|v| v.parse().ok());
+                    if let Some(id) = num_id {
+                        numbering.next_num_id = numbering.next_num_id.max(id + 1);
+                    }
+                }
+                b"abstractNumId" => {
+                    if let (Some(id), Some(target)) = (num_id, value.and_then(|v| v.parse().ok())) {
+                        numbering.nums.entry(id).or_insert((target, Vec::new())).0 = target;
+                    }
+                }
+                b"lvlOverride" if num_id.is_some() => {
+                    override_level = attribute(&event, b"ilvl").and_then(|v| v.parse().ok());
+                }
+                b"startOverride" => {
+                    if let (Some(id), Some(ilvl), Some(start)) =
+                        (num_id, override_level, value.and_then(|v| v.parse().ok()))
+                    {
+                        if let Some((_, overrides)) = numbering.nums.get_mut(&id) {
+                            overrides.push((ilvl, start));
+                        }
+                    }
+                }
+                b"lvl" if abstract_id.is_some() => {
+                    let index = attribute(&event, b"ilvl")
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(0);
+                    let lvl = Level {
+                        format: "decimal".to_string(),
+                        text: String::new(),
+                        start: 1,
+                    };
+                    level = Some((index, lvl));
+                }
+                b"start" => {
+                    if let (Some((_, lvl)), Some(start)) =
+                        (level.as_mut(), value.and_then(|v| v.parse().ok()))
+                    {
+                        lvl.start = start;
+                    }
+                }
+                b"numFmt" => {
+                    if let (Some((_, lvl)), Some(format)) = (level.as_mut(), value) {
+                        lvl.format = format;
+                    }
+                }
+                b"lvlText" => {
+                    if let (Some((_, lvl)), Some(text)) = (level.as_mut(), value) {
+                        lvl.text = text;
+                    }
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        numbering
+    }
+
+    /// Add a definition for a list whose items start at level `base`
+    ///
+    /// `style` and `start` apply to the base level; deeper levels number
+    /// the way AsciiDoc numbers nested lists. Returns the numId for the
+    /// list's paragraphs.
+    pub fn add_list(
+        &mut self,
+        list_type: &ListType,
+        base: u8,
+        style: Option<NumberingStyle>,
+        start: Option<u32>,
+    ) -> u32 {
+        let levels = (0..LEVELS)
+            .map(|index| match list_type {
+                ListType::Ordered => {
+                    let (style, start) = if index == base {
+                        (style, start)
+                    } else {
+                        (None, None)
+                    };
+                    let style = style.unwrap_or_else(|| ordered_style(index));
+                    Level {
+                        format: num_format(style).to_string(),
+                        text: format!("%{}.", index + 1),
+                        start: start.unwrap_or(1),
+                    }
+                }
+                _ => Level {
+                    format: "bullet".to_string(),
+                    text: BULLETS[index as usize % BULLETS.len()].to_string(),
+                    start: 1,
+                },
+            })
+            .collect();
+
+        let definition = Definition {
+            abstract_id: self.next_abstract_id,
+            num_id: self.next_num_id,
+        };
+        self.next_abstract_id += 1;
+        self.next_num_id += 1;
+        self.abstract_levels.insert(definition.abstract_id, levels);
+        self.nums
+            .insert(definition.num_id, (definition.abstract_id, Vec::new()));
+        let num_id = definition.num_id;
+        self.generated.push(definition);
+        num_id
+    }
+
+    /// The format of level `ilvl` of numbering `num_id`, with its start
+    /// override applied
+    pub fn level(&self, num_id: u32, ilvl: u32) -> Option<Level> {
+        let (abstract_id, overrides) = self.nums.get(&num_id)?;
+        let mut level = self
+            .abstract_levels
+            .get(abstract_id)?
+            .get(ilvl as usize)?
+            .clone();
+        if let Some(&(_, start)) = overrides.iter().find(|(index, _)| *index == ilvl) {
+            level.start = start;
+        }
+        Some(level)
+    }
+
+    /// Whether definitions were added for lists
+    pub fn is_empty(&self) -> bool {
+        self.generated.is_empty()
+    }
+
+    /// Generate the numbering part
+    ///
+    /// The added definitions are merged into `existing` (the template's
+    /// numbering part) when given.
+    pub fn to_xml(&self, existing: Option<&str>) -> String {
+        let mut abstracts = String::new();
+        let mut nums = String::new();
+        for definition in &self.generated {
+            abstracts.push_str(&format!(
+                "<w:abstractNum w:abstractNumId=\"{}\">\n<w:multiLevelType w:val=\"hybridMultilevel\"/>\n",
+                definition.abstract_id
+            ));
+            let levels = &self.abstract_levels[&definition.abstract_id];
+            for (index, level) in levels.iter().enumerate() {
+                abstracts.push_str(&format!(
+                    "<w:lvl w:ilvl=\"{}\">\n<w:start w:val=\"{}\"/>\n<w:numFmt w:val=\"{}\"/>\n<w:lvlText w:val=\"{}\"/>\n<w:lvlJc w:val=\"left\"/>\n<w:pPr>\n<w:ind w:left=\"{}\" w:hanging=\"360\"/>\n</w:pPr>\n</w:lvl>\n",
+                    index,
+                    level.start,
+                    level.format,
+                    level.text,
+                    720 * (index + 1)
+                ));
+            }
+            abstracts.push_str("</w:abstractNum>\n");
+            nums.push_str(&format!(
+                "<w:num w:numId=\"{}\">\n<w:abstractNumId w:val=\"{}\"/>\n</w:num>\n",
+                definition.num_id, definition.abstract_id
+            ));
+        }
+
+        match existing.filter(|xml| xml.contains("</w:numbering>")) {
+            Some(xml) => {
+                // Abstract definitions go before the first instance, and
+                // instances before the closing cleanup element
+                let end = xml
+                    .find("<w:numIdMacAtCleanup")
+                    .unwrap_or_else(|| xml.rfind("</w:numbering>").unwrap_or(xml.len()));
+                let first_num = [xml.find("<w:num>"), xml.find("<w:num ")]
+                    .into_iter()
+                    .flatten()
+                    .min()
+                    .unwrap_or(end);
+                format!(
+                    "{}{}{}{}{}",
+                    &xml[..first_num],
+                    abstracts,
+                    &xml[first_num..end],
+                    nums,
+                    &xml[end..]
+                )
+            }
+            None => format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<w:numbering xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\n{}{}</w:numbering>",
+                abstracts, nums
+            ),
+        }
+    }
+}
+
+/// Numbering style of an ordered list level without an explicit style
+pub fn ordered_style(level: u8) -> NumberingStyle {
+    ORDERED_STYLES[level as usize % ORDERED_STYLES.len()]
+}
+
+/// `w:numFmt` value of a numbering style
+fn num_format(style: NumberingStyle) -> &'static str {
+    match style {
+        NumberingStyle::Arabic => "decimal",
+        NumberingStyle::LowerAlpha => "lowerLetter",
+        NumberingStyle::UpperAlpha => "upperLetter",
+        NumberingStyle::LowerRoman => "lowerRoman",
+        NumberingStyle::UpperRoman => "upperRoman",
+    }
+}
+
+/// Value of an attribute, by local name
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .filter_map(|a| a.ok())
+        .find(|a| a.key.local_name().as_ref() == name)
+        .and_then(|a| a.unescape_value().ok().map(|v| v.to_string()))
+}
+
+/// The `w:val` attribute of an element
+fn val(element: &BytesStart) -> Option<String> {
+    attribute(element, b"val")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_get_their_own_definitions() {
+        let mut numbering = Numbering::new();
+        let first = numbering.add_list(&ListType::Ordered, 0, None, None);
+        let second = numbering.add_list(&ListType::Unordered, 0, None, None);
+        assert_eq!((first, second), (1, 2));
+
+        assert_eq!(numbering.level(1, 0).unwrap().format, "decimal");
+        assert_eq!(numbering.level(1, 1).unwrap().format, "lowerLetter");
+        assert_eq!(numbering.level(1, 1).unwrap().text, "%2.");
+        assert!(numbering.level(2, 0).unwrap().is_bullet());
+        assert_eq!(numbering.level(3, 0), None);
+    }
+
+    #[test]
+    fn test_style_and_start_apply_to_the_base_level() {
+        let mut numbering = Numbering::new();
+        let id = numbering.add_list(
+            &ListType::Ordered,
+            1,
+            Some(NumberingStyle::UpperRoman),
+            Some(4),
+        );
+        let base = numbering.level(id, 1).unwrap();
+        assert_eq!(base.style(), Some(NumberingStyle::UpperRoman));
+        assert_eq!(base.start, 4);
+        assert_eq!(numbering.level(id, 0).unwrap().start, 1);
+        assert_eq!(
+            numbering.level(id, 2).unwrap().style(),
+            Some(NumberingStyle::LowerRoman)
+        );
+    }
+
+    #[test]
+    fn test_parse_and_number_after_existing_definitions() {
+        let xml = br#"<?xml version="1.0" encoding="UTF-8"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:abstractNum w:abstractNumId="3">
+<w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="-"/></w:lvl>
+<w:lvl w:ilvl="1"><w:start w:val="1"/><w:numFmt w:val="upperLetter"/><w:lvlText w:val="%2)"/></w:lvl>
+</w:abstractNum>
+<w:num w:numId="7"><w:abstractNumId w:val="3"/>
+<w:lvlOverride w:ilvl="1"><w:startOverride w:val="5"/></w:lvlOverride>
+</w:num>
+</w:numbering>"#;
+        let mut numbering = Numbering::parse(xml);
+        assert!(numbering.level(7, 0).unwrap().is_bullet());
+        let level = numbering.level(7, 1).unwrap();
+        assert_eq!(level.style(), Some(NumberingStyle::UpperAlpha));
+        assert_eq!(level.start, 5);
+        assert!(numbering.is_empty());
+
+        assert_eq!(numbering.add_list(&ListType::Ordered, 0, None, None), 8);
+        let merged = numbering.to_xml(Some(std::str::from_utf8(xml).unwrap()));
+        let abstract_at = merged.find("w:abstractNumId=\"4\"").unwrap();
+        assert!(merged.find("w:abstractNumId=\"3\"").unwrap() < abstract_at);
+        assert!(abstract_at < merged.find("<w:num w:numId=\"7\">").unwrap());
+        assert!(
+            merged.find("<w:num w:numId=\"7\">").unwrap()
+                < merged.find("<w:num w:numId=\"8\">").unwrap()
+        );
+        assert!(merged.ends_with("</w:numbering>"));
+    }
+}
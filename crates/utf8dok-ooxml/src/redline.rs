@@ -0,0 +1,1131 @@
+//! Tracked changes computed from an AST diff (redlines)
+//!
+//! [`diff_blocks`] compares the blocks of an original and a revised
+//! document. Edited paragraphs and headings are compared word by word,
+//! edited lists item by item and edited tables row by row; other blocks are
+//! inserted or deleted as a whole. [`mark_runs`] and
+//! [`mark_block`] turn the WordprocessingML generated for the changed
+//! content into `w:ins` / `w:del` revisions, so Word shows the changes as
+//! tracked changes to accept or reject, by the [`Reviser`] of the revised
+//! document.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use utf8dok_ast::{Block, Document, Inline, ListItem, TableRow};
+
+use crate::document::RevisionKind;
+use crate::writer::escape_xml;
+
+/// A block of the revised document, or of the original one when deleted
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockChange<'a> {
+    /// Block in both documents
+    Unchanged(&'a Block),
+    /// Block only in the revised document
+    Inserted(&'a Block),
+    /// Block only in the original document
+    Deleted(&'a Block),
+    /// Edited paragraph or heading, with the words of both versions
+    ///
+    /// The inlines of the block are the merged words; each has the kind of
+    /// change it belongs to, if any.
+    Edited(Block, Vec<Option<RevisionKind>>),
+    /// Edited list, with the items of both versions
+    ///
+    /// The items of the list are the merged items; each has the change it
+    /// belongs to, if any.
+    EditedList(Block, Vec<Option<ItemRevision>>),
+    /// Edited table, with the rows of both versions
+    ///
+    /// The rows of the table are the merged rows; each has the change it
+    /// belongs to, if any.
+    EditedTable(Block, Vec<Option<RowRevision>>),
+}
+
+/// Change to an item of an edited list
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemRevision {
+    /// Item inserted or deleted as a whole
+    Whole(RevisionKind),
+    /// Item with an edited first paragraph, with the kind of change of each
+    /// of its inlines
+    Edited(Vec<Option<RevisionKind>>),
+}
+
+/// Change to a row of an edited table
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowRevision {
+    /// Row inserted or deleted as a whole
+    Whole(RevisionKind),
+    /// Row with edited cells, with the original content of each cell
+    Edited(Vec<Vec<Block>>),
+}
+
+/// An item of the revised sequence, or of the original one when deleted,
+/// or a deleted and an inserted item paired as an edit
+enum Change<'a, T, E> {
+    Unchanged(&'a T),
+    Inserted(&'a T),
+    Deleted(&'a T),
+    Edited(E),
+}
+
+/// One step of an edit script between two sequences
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Edit {
+    /// Items `old[i]` and `new[j]` are equal
+    Keep(usize, usize),
+    /// Item `old[i]` was deleted
+    Delete(usize),
+    /// Item `new[j]` was inserted
+    Insert(usize),
+}
+
+/// Shortest edit script from `old` to `new` (longest common subsequence)
+///
+/// Deletions come before insertions where both replace the same items.
+fn edit_script<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    // lengths[i][j]: LCS length of a[i..] and b[j..]
+    let width = b.len() + 1;
+    let mut lengths = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * width + j] = if a[i] == b[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut script: Vec<Edit> = (0..prefix).map(|i| Edit::Keep(i, i)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            script.push(Edit::Keep(prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if i < a.len()
+            && (j == b.len() || lengths[(i + 1) * width + j] >= lengths[i * width + j + 1])
+        {
+            script.push(Edit::Delete(prefix + i));
+            i += 1;
+        } else {
+            script.push(Edit::Insert(prefix + j));
+            j += 1;
+        }
+    }
+    let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+    script.extend((0..suffix).map(|k| Edit::Keep(old_end + k, new_end + k)));
+    script
+}
+
+/// Largest run of changed items whose deleted and inserted items are all
+/// compared with each other; longer runs are paired by position
+const MAX_PAIRINGS: usize = 4096;
+
+/// Compare two sequences
+///
+/// Within a run of changed items, deleted and inserted items are paired as
+/// edits where `edit` merges them. `edit` also scores how much of the items
+/// is kept; the pairing keeps the order of the items and has the highest
+/// total score.
+fn diff_sequence<'a, T: PartialEq, E>(
+    old: &'a [T],
+    new: &'a [T],
+    edit: impl Fn(&T, &T) -> Option<(usize, E)>,
+) -> Vec<Change<'a, T, E>> {
+    let mut changes = Vec::new();
+    let mut deleted = Vec::new();
+    let mut inserted = Vec::new();
+    for step in edit_script(old, new) {
+        match step {
+            Edit::Keep(_, j) => {
+                pair_run(&mut changes, &mut deleted, &mut inserted, &edit);
+                changes.push(Change::Unchanged(&new[j]));
+            }
+            Edit::Delete(i) => deleted.push(&old[i]),
+            Edit::Insert(j) => inserted.push(&new[j]),
+        }
+    }
+    pair_run(&mut changes, &mut deleted, &mut inserted, &edit);
+    changes
+}
+
+/// Pair the deleted and inserted items of a run of changes
+///
+/// Unpaired deleted items come before the inserted ones between two edits.
+fn pair_run<'a, T, E>(
+    changes: &mut Vec<Change<'a, T, E>>,
+    deleted: &mut Vec<&'a T>,
+    inserted: &mut Vec<&'a T>,
+    edit: impl Fn(&T, &T) -> Option<(usize, E)>,
+) {
+    let (rows, columns) = (deleted.len(), inserted.len());
+    let mut edits: Vec<Option<(usize, E)>> = if rows * columns <= MAX_PAIRINGS {
+        deleted
+            .iter()
+            .flat_map(|old| inserted.iter().map(|new| edit(old, new)))
+            .collect()
+    } else {
+        (0..rows * columns)
+            .map(|k| match k / columns == k % columns {
+                true => edit(deleted[k / columns], inserted[k % columns]),
+                false => None,
+            })
+            .collect()
+    };
+
+    // best[i][j]: highest score pairing deleted[i..] with inserted[j..];
+    // every edit counts, however little of the items it keeps
+    let width = columns + 1;
+    let mut best = vec![0usize; (rows + 1) * width];
+    for i in (0..rows).rev() {
+        for j in (0..columns).rev() {
+            let paired = edits[i * columns + j]
+                .as_ref()
+                .map_or(0, |(score, _)| score + 1 + best[(i + 1) * width + j + 1]);
+            best[i * width + j] = paired
+                .max(best[(i + 1) * width + j])
+                .max(best[i * width + j + 1]);
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let (mut pending_deleted, mut pending_inserted) = (Vec::new(), Vec::new());
+    while i < rows && j < columns {
+        let score = best[i * width + j];
+        let paired = edits[i * columns + j]
+            .as_ref()
+            .is_some_and(|(kept, _)| kept + 1 + best[(i + 1) * width + j + 1] == score);
+        if paired {
+            changes.extend(pending_deleted.drain(..).map(Change::Deleted));
+            changes.extend(pending_inserted.drain(..).map(Change::Inserted));
+            if let Some((_, edited)) = edits[i * columns + j].take() {
+                changes.push(Change::Edited(edited));
+            }
+            i += 1;
+            j += 1;
+        } else if best[(i + 1) * width + j] == score {
+            pending_deleted.push(deleted[i]);
+            i += 1;
+        } else {
+            pending_inserted.push(inserted[j]);
+            j += 1;
+        }
+    }
+    changes.extend(pending_deleted.into_iter().map(Change::Deleted));
+    changes.extend(deleted[i..].iter().map(|&old| Change::Deleted(old)));
+    changes.extend(pending_inserted.into_iter().map(Change::Inserted));
+    changes.extend(inserted[j..].iter().map(|&new| Change::Inserted(new)));
+    deleted.clear();
+    inserted.clear();
+}
+
+/// Compare the blocks of the original and the revised document
+///
+/// Within a run of changed blocks, deleted and inserted blocks are paired
+/// as edits by similarity: paragraphs that share most of their words,
+/// headings at the same level, lists of the same kind that share an item
+/// and tables with the same columns that share a row.
+pub fn diff_blocks<'a>(old: &'a [Block], new: &'a [Block]) -> Vec<BlockChange<'a>> {
+    diff_sequence(old, new, edit_block)
+        .into_iter()
+        .map(|change| match change {
+            Change::Unchanged(block) => BlockChange::Unchanged(block),
+            Change::Inserted(block) => BlockChange::Inserted(block),
+            Change::Deleted(block) => BlockChange::Deleted(block),
+            Change::Edited(edited) => edited,
+        })
+        .collect()
+}
+
+/// Pair a deleted and an inserted block as an edit, if they are the same
+/// kind of block with similar content, scored by how much of it is kept
+fn edit_block(old: &Block, new: &Block) -> Option<(usize, BlockChange<'static>)> {
+    match (old, new) {
+        (Block::Paragraph(a), Block::Paragraph(b))
+            if a.style_id == b.style_id && a.attributes == b.attributes =>
+        {
+            let merged = merge_words(&a.inlines, &b.inlines).similar()?;
+            let mut edited = b.clone();
+            edited.inlines = merged.inlines;
+            Some((
+                merged.kept,
+                BlockChange::Edited(Block::Paragraph(edited), merged.revisions),
+            ))
+        }
+        // A heading at the same level is renamed, however little is kept
+        (Block::Heading(a), Block::Heading(b)) if a.level == b.level => {
+            let merged = merge_words(&a.text, &b.text);
+            let mut edited = b.clone();
+            edited.text = merged.inlines;
+            Some((
+                merged.kept,
+                BlockChange::Edited(Block::Heading(edited), merged.revisions),
+            ))
+        }
+        (Block::List(a), Block::List(b))
+            if a.list_type == b.list_type
+                && a.style_id == b.style_id
+                && a.numbering == b.numbering
+                && a.start == b.start =>
+        {
+            let (kept, items, revisions) = merge_changes(
+                diff_sequence(&a.items, &b.items, edit_item),
+                ItemRevision::Whole,
+                ItemRevision::Edited,
+            )?;
+            let mut edited = b.clone();
+            edited.items = items;
+            Some((
+                kept,
+                BlockChange::EditedList(Block::List(edited), revisions),
+            ))
+        }
+        // Rows spanning others are not merged, as they would overlap
+        (Block::Table(a), Block::Table(b))
+            if a.style_id == b.style_id
+                && a.caption == b.caption
+                && a.columns == b.columns
+                && !spans_rows(&a.rows)
+                && !spans_rows(&b.rows) =>
+        {
+            let (kept, rows, revisions) = merge_changes(
+                diff_sequence(&a.rows, &b.rows, edit_row),
+                RowRevision::Whole,
+                RowRevision::Edited,
+            )?;
+            let mut edited = b.clone();
+            edited.rows = rows;
+            Some((
+                kept,
+                BlockChange::EditedTable(Block::Table(edited), revisions),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Score of an edit pairing two items, with the merged item and its changes
+type Paired<T, E> = (usize, (T, E));
+
+/// Items kept or edited, the merged items of both versions and the change
+/// each belongs to, if any
+type Merged<T, R> = (usize, Vec<T>, Vec<Option<R>>);
+
+/// Merge the items of a compared sequence
+///
+/// Returns `None` when no item is kept or edited.
+fn merge_changes<T: Clone, E, R>(
+    changes: Vec<Change<'_, T, (T, E)>>,
+    whole: fn(RevisionKind) -> R,
+    edited: fn(E) -> R,
+) -> Option<Merged<T, R>> {
+    let kept = changes
+        .iter()
+        .filter(|change| matches!(change, Change::Unchanged(_) | Change::Edited(_)))
+        .count();
+    if kept == 0 {
+        return None;
+    }
+    let (items, revisions) = changes
+        .into_iter()
+        .map(|change| match change {
+            Change::Unchanged(item) => (item.clone(), None),
+            Change::Inserted(item) => (item.clone(), Some(whole(RevisionKind::Insertion))),
+            Change::Deleted(item) => (item.clone(), Some(whole(RevisionKind::Deletion))),
+            Change::Edited((item, change)) => (item, Some(edited(change))),
+        })
+        .unzip();
+    Some((kept, items, revisions))
+}
+
+/// Pair a deleted and an inserted list item as an edit, if only the text
+/// of their first paragraph differs, and not by much
+fn edit_item(
+    old: &ListItem,
+    new: &ListItem,
+) -> Option<Paired<ListItem, Vec<Option<RevisionKind>>>> {
+    if old.level != new.level
+        || old.term != new.term
+        || old.checked != new.checked
+        || old.content.get(1..) != new.content.get(1..)
+    {
+        return None;
+    }
+    let (Some(Block::Paragraph(a)), Some(Block::Paragraph(b))) =
+        (old.content.first(), new.content.first())
+    else {
+        return None;
+    };
+    if a.style_id != b.style_id || a.attributes != b.attributes {
+        return None;
+    }
+    let merged = merge_words(&a.inlines, &b.inlines).similar()?;
+    let mut edited = new.clone();
+    if let Some(Block::Paragraph(paragraph)) = edited.content.first_mut() {
+        paragraph.inlines = merged.inlines;
+    }
+    Some((merged.kept, (edited, merged.revisions)))
+}
+
+/// Pair a deleted and an inserted table row as an edit, if their cells
+/// line up and some of them keep content, scored by the number of those
+fn edit_row(old: &TableRow, new: &TableRow) -> Option<Paired<TableRow, Vec<Vec<Block>>>> {
+    if old.is_header != new.is_header
+        || old.is_footer != new.is_footer
+        || old.cells.len() != new.cells.len()
+    {
+        return None;
+    }
+    let mut kept = 0;
+    for (a, b) in old.cells.iter().zip(&new.cells) {
+        if (a.colspan, a.rowspan, &a.align, &a.style) != (b.colspan, b.rowspan, &b.align, &b.style)
+        {
+            return None;
+        }
+        let keeps = a.content == b.content
+            || diff_blocks(&a.content, &b.content).iter().any(|change| {
+                !matches!(change, BlockChange::Inserted(_) | BlockChange::Deleted(_))
+            });
+        kept += usize::from(keeps);
+    }
+    let originals = old.cells.iter().map(|cell| cell.content.clone()).collect();
+    (kept > 0).then(|| (kept, (new.clone(), originals)))
+}
+
+/// Whether a cell of `rows` spans several rows
+fn spans_rows(rows: &[TableRow]) -> bool {
+    rows.iter()
+        .flat_map(|row| &row.cells)
+        .any(|cell| cell.rowspan > 1)
+}
+
+/// Words of two versions of some inlines, merged
+struct MergedWords {
+    /// The words of both versions, joined where they have the same change
+    inlines: Vec<Inline>,
+    /// The kind of change each of `inlines` belongs to, if any
+    revisions: Vec<Option<RevisionKind>>,
+    /// Number of words kept
+    kept: usize,
+    /// Number of words of the longer version
+    total: usize,
+}
+
+impl MergedWords {
+    /// The merged words, if at least half of the words are kept
+    fn similar(self) -> Option<Self> {
+        (2 * self.kept >= self.total).then_some(self)
+    }
+}
+
+/// Compare inlines word by word
+///
+/// Returns the merged words of both versions, each with the kind of change
+/// it belongs to, or `None` when less than half of the words are kept.
+pub fn diff_inlines(
+    old: &[Inline],
+    new: &[Inline],
+) -> Option<(Vec<Inline>, Vec<Option<RevisionKind>>)> {
+    let merged = merge_words(old, new).similar()?;
+    Some((merged.inlines, merged.revisions))
+}
+
+/// Merge the words of two versions of some inlines
+fn merge_words(old: &[Inline], new: &[Inline]) -> MergedWords {
+    let (old, new) = (words(old), words(new));
+    let script = edit_script(&old, &new);
+    let kept = script
+        .iter()
+        .filter(|edit| matches!(edit, Edit::Keep(_, j) if !is_space(&new[*j])))
+        .count();
+    let count = |words: &[Inline]| words.iter().filter(|word| !is_space(word)).count();
+    let total = count(&old).max(count(&new));
+
+    let mut merged: Vec<(Inline, Option<RevisionKind>)> = Vec::new();
+    for edit in script {
+        let (word, kind) = match edit {
+            Edit::Keep(_, j) => (&new[j], None),
+            Edit::Delete(i) => (&old[i], Some(RevisionKind::Deletion)),
+            Edit::Insert(j) => (&new[j], Some(RevisionKind::Insertion)),
+        };
+        let joined = match merged.last_mut() {
+            Some((last, last_kind)) => *last_kind == kind && join(last, word),
+            None => false,
+        };
+        if !joined {
+            merged.push((word.clone(), kind));
+        }
+    }
+    let (inlines, revisions) = merged.into_iter().unzip();
+    MergedWords {
+        inlines,
+        revisions,
+        kept,
+        total,
+    }
+}
+
+/// Split inlines into words and the whitespace between them, keeping the
+/// formatting of formatted text; other inlines are single words
+fn words(inlines: &[Inline]) -> Vec<Inline> {
+    let mut words = Vec::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => {
+                words.extend(split_words(text).map(|word| Inline::Text(word.to_string())))
+            }
+            Inline::Format(format, inner) => match inner.as_ref() {
+                Inline::Text(text) => words.extend(split_words(text).map(|word| {
+                    Inline::Format(format.clone(), Box::new(Inline::Text(word.to_string())))
+                })),
+                _ => words.push(inline.clone()),
+            },
+            _ => words.push(inline.clone()),
+        }
+    }
+    words
+}
+
+/// Split text into runs of whitespace and runs of other characters
+fn split_words(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let end = rest
+            .find(|c: char| c.is_whitespace() != first.is_whitespace())
+            .unwrap_or(rest.len());
+        let (word, tail) = rest.split_at(end);
+        rest = tail;
+        Some(word)
+    })
+}
+
+/// Whether a word is whitespace between words
+fn is_space(word: &Inline) -> bool {
+    matches!(word, Inline::Text(text) if text.trim().is_empty())
+}
+
+/// Append `word` to `last` when both are text with the same formatting
+fn join(last: &mut Inline, word: &Inline) -> bool {
+    match (last, word) {
+        (Inline::Text(a), Inline::Text(b)) => {
+            a.push_str(b);
+            true
+        }
+        (Inline::Format(format_a, a), Inline::Format(format_b, b)) if format_a == format_b => {
+            match (a.as_mut(), b.as_ref()) {
+                (Inline::Text(a), Inline::Text(b)) => {
+                    a.push_str(b);
+                    true
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Author and date of the revision marks of a redline
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Reviser {
+    /// Author of the changes (`w:author`)
+    pub author: String,
+    /// Date and time of the changes in ISO 8601 (`w:date`)
+    pub date: String,
+}
+
+impl Reviser {
+    /// The reviser of a revised document: its first author, or "utf8dok",
+    /// at its `revdate` when that is an ISO 8601 date, or else now
+    ///
+    /// The current time honours `SOURCE_DATE_EPOCH` for reproducible output.
+    pub fn of(doc: &Document) -> Self {
+        let author = doc
+            .metadata
+            .authors
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "utf8dok".to_string());
+        let date = doc
+            .metadata
+            .attributes
+            .get("revdate")
+            .and_then(|revdate| iso_date(revdate))
+            .unwrap_or_else(|| {
+                let seconds = std::env::var("SOURCE_DATE_EPOCH")
+                    .ok()
+                    .and_then(|value| value.parse::<i64>().ok())
+                    .unwrap_or_else(|| {
+                        SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map(|d| d.as_secs() as i64)
+                            .unwrap_or(0)
+                    });
+                date_time(seconds)
+            });
+        Self { author, date }
+    }
+}
+
+/// `revdate` as an ISO 8601 date and time, if it starts with a `YYYY-MM-DD` date
+fn iso_date(revdate: &str) -> Option<String> {
+    let revdate = revdate.trim();
+    let date = revdate.get(..10)?.as_bytes();
+    let is_date = date.iter().enumerate().all(|(i, &b)| match i {
+        4 | 7 => b == b'-',
+        _ => b.is_ascii_digit(),
+    });
+    match (is_date, revdate.len()) {
+        (false, _) => None,
+        (true, 10) => Some(format!("{}T00:00:00Z", revdate)),
+        (true, _) if revdate.as_bytes()[10] == b'T' => Some(revdate.to_string()),
+        (true, _) => None,
+    }
+}
+
+/// UTC date and time in ISO 8601 of `seconds` since the epoch
+fn date_time(seconds: i64) -> String {
+    // Civil date from days since 1970-01-01
+    let z = seconds.div_euclid(86_400) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let secs = seconds.rem_euclid(86_400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// Attributes of a revision mark
+fn revision_attributes(id: usize, reviser: &Reviser) -> String {
+    format!(
+        "w:id=\"{}\" w:author=\"{}\" w:date=\"{}\"",
+        id,
+        escape_xml(&reviser.author),
+        escape_xml(&reviser.date)
+    )
+}
+
+/// Name of the revision element of `kind`
+fn element(kind: RevisionKind) -> &'static str {
+    match kind {
+        RevisionKind::Insertion => "w:ins",
+        RevisionKind::Deletion => "w:del",
+    }
+}
+
+/// Wrap every run (`w:r`) of generated XML in a `w:ins` or `w:del`
+/// revision, numbered from `next_id`
+///
+/// The text of deleted runs becomes `w:delText` (and field instructions
+/// `w:delInstrText`), as Word expects.
+pub fn mark_runs(xml: &str, kind: RevisionKind, reviser: &Reviser, next_id: &mut usize) -> String {
+    let mut output = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find("<w:r>") {
+        let Some(length) = rest[start..].find("</w:r>") else {
+            break;
+        };
+        let end = start + length + "</w:r>".len();
+        output.push_str(&rest[..start]);
+
+        let run = &rest[start..end];
+        output.push_str(&format!(
+            "<{} {}>",
+            element(kind),
+            revision_attributes(*next_id, reviser)
+        ));
+        *next_id += 1;
+        if kind == RevisionKind::Deletion {
+            output.push_str(
+                &run.replace("<w:t>", "<w:delText>")
+                    .replace("<w:t xml:space=", "<w:delText xml:space=")
+                    .replace("</w:t>", "</w:delText>")
+                    .replace("<w:instrText", "<w:delInstrText")
+                    .replace("</w:instrText>", "</w:delInstrText>"),
+            );
+        } else {
+            output.push_str(run);
+        }
+        output.push_str(&format!("</{}>", element(kind)));
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Mark all of the generated XML of an inserted or deleted block
+///
+/// Besides the runs, the paragraph marks and table rows get revision marks,
+/// so accepting a deletion removes the paragraphs and rows entirely.
+pub fn mark_block(xml: &str, kind: RevisionKind, reviser: &Reviser, next_id: &mut usize) -> String {
+    let mut xml = mark_runs(xml, kind, reviser, next_id);
+
+    // Paragraph marks: w:rPr in the paragraph properties, ahead of any
+    // section properties
+    let mut offset = 0;
+    while let Some(found) = xml[offset..].find("<w:p>") {
+        let start = offset + found + "<w:p>".len();
+        let mark = format!(
+            "<{} {}/>",
+            element(kind),
+            revision_attributes(*next_id, reviser)
+        );
+        *next_id += 1;
+        let after = xml[start..].trim_start();
+        let insertion = if after.starts_with("<w:pPr>") {
+            let properties = start + xml[start..].find("<w:pPr>").unwrap();
+            let end = properties + xml[properties..].find("</w:pPr>").unwrap_or(0);
+            let section = xml[properties..end]
+                .find("<w:sectPr")
+                .map(|s| properties + s);
+            (section.unwrap_or(end), format!("<w:rPr>{}</w:rPr>", mark))
+        } else {
+            (start, format!("<w:pPr><w:rPr>{}</w:rPr></w:pPr>", mark))
+        };
+        xml.insert_str(insertion.0, &insertion.1);
+        offset = insertion.0 + insertion.1.len();
+    }
+
+    // Table rows: the mark ends the row properties
+    let mut offset = 0;
+    while let Some(found) = xml[offset..].find("<w:tr>") {
+        let start = offset + found + "<w:tr>".len();
+        let mark = format!(
+            "<{} {}/>",
+            element(kind),
+            revision_attributes(*next_id, reviser)
+        );
+        *next_id += 1;
+        let insertion = if xml[start..].trim_start().starts_with("<w:trPr>") {
+            let end = start + xml[start..].find("</w:trPr>").unwrap_or(0);
+            (end, mark)
+        } else {
+            (start, format!("<w:trPr>{}</w:trPr>", mark))
+        };
+        xml.insert_str(insertion.0, &insertion.1);
+        offset = insertion.0 + insertion.1.len();
+    }
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use utf8dok_ast::{FormatType, Heading, List, ListType, Paragraph, Table, TableCell};
+
+    fn paragraph(text: &str) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines: vec![Inline::Text(text.to_string())],
+            style_id: None,
+            attributes: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_block_diff() {
+        let old = vec![
+            paragraph("Intro."),
+            paragraph("The service runs slowly on every node."),
+            paragraph("Obsolete."),
+            paragraph("End."),
+        ];
+        let new = vec![
+            paragraph("Intro."),
+            paragraph("The service runs fast on every node."),
+            paragraph("Completely different words here."),
+            paragraph("End."),
+            paragraph("Appendix."),
+        ];
+        let changes = diff_blocks(&old, &new);
+
+        assert_eq!(changes.len(), 6);
+        assert_eq!(changes[0], BlockChange::Unchanged(&new[0]));
+        let BlockChange::Edited(Block::Paragraph(edited), revisions) = &changes[1] else {
+            panic!("Expected edited paragraph, got {:?}", changes[1]);
+        };
+        assert_eq!(
+            edited.inlines,
+            vec![
+                Inline::Text("The service runs ".to_string()),
+                Inline::Text("slowly".to_string()),
+                Inline::Text("fast".to_string()),
+                Inline::Text(" on every node.".to_string()),
+            ]
+        );
+        assert_eq!(
+            revisions,
+            &vec![
+                None,
+                Some(RevisionKind::Deletion),
+                Some(RevisionKind::Insertion),
+                None
+            ]
+        );
+        // Dissimilar paragraphs are replaced instead of edited
+        assert_eq!(changes[2], BlockChange::Deleted(&old[2]));
+        assert_eq!(changes[3], BlockChange::Inserted(&new[2]));
+        assert_eq!(changes[4], BlockChange::Unchanged(&new[3]));
+        assert_eq!(changes[5], BlockChange::Inserted(&new[4]));
+    }
+
+    #[test]
+    fn test_inline_diff_keeps_formatting() {
+        let bold =
+            |text: &str| Inline::Format(FormatType::Bold, Box::new(Inline::Text(text.to_string())));
+        let old = vec![Inline::Text("Press ".to_string()), bold("Save now")];
+        let new = vec![Inline::Text("Press ".to_string()), bold("Save all now")];
+        let (inlines, revisions) = diff_inlines(&old, &new).unwrap();
+
+        assert_eq!(
+            inlines,
+            vec![
+                Inline::Text("Press ".to_string()),
+                bold("Save "),
+                bold("all "),
+                bold("now"),
+            ]
+        );
+        assert_eq!(
+            revisions,
+            vec![None, None, Some(RevisionKind::Insertion), None]
+        );
+    }
+
+    #[test]
+    fn test_headings_are_edited_at_the_same_level() {
+        let heading = |level: u8, text: &str| {
+            Block::Heading(Heading {
+                level,
+                text: vec![Inline::Text(text.to_string())],
+                style_id: None,
+                anchor: None,
+                role: None,
+            })
+        };
+        let old = [heading(2, "Getting started")];
+        let edited = [heading(2, "Getting started quickly")];
+        assert!(matches!(
+            diff_blocks(&old, &edited)[0],
+            BlockChange::Edited(Block::Heading(_), _)
+        ));
+
+        // A renamed heading is edited, not replaced
+        let (intro, renamed) = ([heading(2, "Intro")], [heading(2, "Introduction")]);
+        let BlockChange::Edited(Block::Heading(edited), revisions) =
+            &diff_blocks(&intro, &renamed)[0]
+        else {
+            panic!("Expected edited heading");
+        };
+        assert_eq!(
+            edited.text,
+            vec![
+                Inline::Text("Intro".to_string()),
+                Inline::Text("Introduction".to_string())
+            ]
+        );
+        assert_eq!(
+            revisions,
+            &vec![Some(RevisionKind::Deletion), Some(RevisionKind::Insertion)]
+        );
+
+        let promoted = [heading(1, "Getting started")];
+        let changes = diff_blocks(&old, &promoted);
+        assert_eq!(
+            changes,
+            vec![
+                BlockChange::Deleted(&old[0]),
+                BlockChange::Inserted(&promoted[0])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lists_are_diffed_item_by_item() {
+        let list = |items: &[&str]| {
+            Block::List(List {
+                list_type: ListType::Unordered,
+                items: items
+                    .iter()
+                    .map(|text| ListItem {
+                        content: vec![paragraph(text)],
+                        level: 0,
+                        term: None,
+                        checked: None,
+                    })
+                    .collect(),
+                style_id: None,
+                numbering: None,
+                start: None,
+            })
+        };
+        let old = [list(&[
+            "Install it",
+            "Configure the service",
+            "Obsolete step",
+        ])];
+        let new = [list(&[
+            "Install it",
+            "Configure the new service",
+            "Restart",
+        ])];
+        let changes = diff_blocks(&old, &new);
+
+        assert_eq!(changes.len(), 1);
+        let BlockChange::EditedList(Block::List(edited), revisions) = &changes[0] else {
+            panic!("Expected edited list, got {:?}", changes[0]);
+        };
+        let (Block::List(old_list), Block::List(new_list)) = (&old[0], &new[0]) else {
+            unreachable!();
+        };
+        assert_eq!(edited.items.len(), 4);
+        assert_eq!(edited.items[0], new_list.items[0]);
+        assert_eq!(revisions[0], None);
+        assert_eq!(
+            revisions[1],
+            Some(ItemRevision::Edited(vec![
+                None,
+                Some(RevisionKind::Insertion),
+                None
+            ]))
+        );
+        assert_eq!(edited.items[2], old_list.items[2]);
+        assert_eq!(
+            revisions[2],
+            Some(ItemRevision::Whole(RevisionKind::Deletion))
+        );
+        assert_eq!(edited.items[3], new_list.items[2]);
+        assert_eq!(
+            revisions[3],
+            Some(ItemRevision::Whole(RevisionKind::Insertion))
+        );
+
+        // Lists without an item in common are replaced whole
+        let other = [list(&["Something else entirely"])];
+        assert_eq!(
+            diff_blocks(&old, &other),
+            vec![
+                BlockChange::Deleted(&old[0]),
+                BlockChange::Inserted(&other[0])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_items_are_paired_by_similarity() {
+        let list = |items: &[&str]| {
+            Block::List(List {
+                list_type: ListType::Unordered,
+                items: items
+                    .iter()
+                    .map(|text| ListItem {
+                        content: vec![paragraph(text)],
+                        level: 0,
+                        term: None,
+                        checked: None,
+                    })
+                    .collect(),
+                style_id: None,
+                numbering: None,
+                start: None,
+            })
+        };
+        // One item edited, one inserted before it and one appended
+        let old = [list(&["Install it", "Configure the service"])];
+        let new = [list(&[
+            "Install it",
+            "Download the tool",
+            "Configure the new service",
+            "Check the logs",
+        ])];
+        let changes = diff_blocks(&old, &new);
+
+        let BlockChange::EditedList(Block::List(edited), revisions) = &changes[0] else {
+            panic!("Expected edited list, got {:?}", changes[0]);
+        };
+        assert_eq!(edited.items.len(), 4);
+        assert_eq!(revisions[0], None);
+        assert_eq!(
+            revisions[1],
+            Some(ItemRevision::Whole(RevisionKind::Insertion))
+        );
+        assert_eq!(
+            revisions[2],
+            Some(ItemRevision::Edited(vec![
+                None,
+                Some(RevisionKind::Insertion),
+                None
+            ]))
+        );
+        assert_eq!(
+            revisions[3],
+            Some(ItemRevision::Whole(RevisionKind::Insertion))
+        );
+    }
+
+    #[test]
+    fn test_tables_are_diffed_row_by_row() {
+        let table = |rows: &[[&str; 2]]| {
+            Block::Table(Table {
+                rows: rows
+                    .iter()
+                    .map(|cells| TableRow {
+                        cells: cells
+                            .iter()
+                            .map(|text| TableCell {
+                                content: vec![paragraph(text)],
+                                colspan: 1,
+                                rowspan: 1,
+                                align: None,
+                                style: None,
+                            })
+                            .collect(),
+                        is_header: false,
+                        is_footer: false,
+                    })
+                    .collect(),
+                style_id: None,
+                caption: None,
+                columns: Vec::new(),
+            })
+        };
+        // One cell edited
+        let old = [table(&[["Name", "Value"], ["port", "8080"]])];
+        let new = [table(&[["Name", "Value"], ["port", "9090"]])];
+        let changes = diff_blocks(&old, &new);
+
+        assert_eq!(changes.len(), 1);
+        let BlockChange::EditedTable(Block::Table(edited), revisions) = &changes[0] else {
+            panic!("Expected edited table, got {:?}", changes[0]);
+        };
+        let Block::Table(revised) = &new[0] else {
+            unreachable!();
+        };
+        assert_eq!(edited.rows, revised.rows);
+        assert_eq!(revisions[0], None);
+        assert_eq!(
+            revisions[1],
+            Some(RowRevision::Edited(vec![
+                vec![paragraph("port")],
+                vec![paragraph("8080")]
+            ]))
+        );
+
+        // A row sharing no cell is replaced
+        let replaced = [table(&[["Name", "Value"], ["host", "example"]])];
+        let BlockChange::EditedTable(_, revisions) = &diff_blocks(&old, &replaced)[0] else {
+            panic!("Expected edited table");
+        };
+        assert_eq!(
+            revisions[1..],
+            [
+                Some(RowRevision::Whole(RevisionKind::Deletion)),
+                Some(RowRevision::Whole(RevisionKind::Insertion))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mark_runs() {
+        let xml = "<w:p>\n<w:r>\n<w:t xml:space=\"preserve\">Old </w:t>\n</w:r>\n<w:r>\n<w:tab/>\n<w:t>text</w:t>\n</w:r>\n</w:p>\n";
+        let mut next_id = 1;
+        let reviser = Reviser {
+            author: "A & B".to_string(),
+            date: "2024-05-01T00:00:00Z".to_string(),
+        };
+
+        let deleted = mark_runs(xml, RevisionKind::Deletion, &reviser, &mut next_id);
+        assert_eq!(
+            deleted,
+            "<w:p>\n<w:del w:id=\"1\" w:author=\"A &amp; B\" w:date=\"2024-05-01T00:00:00Z\"><w:r>\n<w:delText xml:space=\"preserve\">Old </w:delText>\n</w:r></w:del>\n<w:del w:id=\"2\" w:author=\"A &amp; B\" w:date=\"2024-05-01T00:00:00Z\"><w:r>\n<w:tab/>\n<w:delText>text</w:delText>\n</w:r></w:del>\n</w:p>\n"
+        );
+
+        let inserted = mark_runs(xml, RevisionKind::Insertion, &reviser, &mut next_id);
+        assert!(inserted.contains("<w:ins w:id=\"3\" w:author=\"A &amp; B\" w:date=\"2024-05-01T00:00:00Z\"><w:r>\n<w:t xml:space"));
+        assert_eq!(next_id, 5);
+    }
+
+    #[test]
+    fn test_mark_block() {
+        let xml = "<w:p>\n<w:pPr>\n<w:pStyle w:val=\"Normal\"/>\n</w:pPr>\n<w:r>\n<w:t>A</w:t>\n</w:r>\n</w:p>\n<w:tbl>\n<w:tr>\n<w:tc>\n<w:p>\n<w:r>\n<w:t>B</w:t>\n</w:r>\n</w:p>\n</w:tc>\n</w:tr>\n<w:tr>\n<w:trPr>\n<w:tblHeader/>\n</w:trPr>\n</w:tr>\n</w:tbl>\n";
+        let mut next_id = 1;
+        let reviser = Reviser {
+            author: "utf8dok".to_string(),
+            date: "2024-05-01T00:00:00Z".to_string(),
+        };
+        let marked = mark_block(xml, RevisionKind::Deletion, &reviser, &mut next_id);
+
+        assert!(marked.contains(
+            "<w:pStyle w:val=\"Normal\"/>\n<w:rPr><w:del w:id=\"3\" w:author=\"utf8dok\" w:date=\"2024-05-01T00:00:00Z\"/></w:rPr></w:pPr>"
+        ));
+        assert!(marked.contains(
+            "<w:tc>\n<w:p><w:pPr><w:rPr><w:del w:id=\"4\" w:author=\"utf8dok\" w:date=\"2024-05-01T00:00:00Z\"/></w:rPr></w:pPr>\n"
+        ));
+        assert!(marked.contains("<w:tr><w:trPr><w:del w:id=\"5\" w:author=\"utf8dok\" w:date=\"2024-05-01T00:00:00Z\"/></w:trPr>"));
+        assert!(
+            marked.contains("<w:tblHeader/>\n<w:del w:id=\"6\" w:author=\"utf8dok\" w:date=\"2024-05-01T00:00:00Z\"/></w:trPr>")
+        );
+    }
+
+    #[test]
+    fn test_reviser_of_revised_document() {
+        let mut doc = Document::default();
+        doc.metadata.authors.push("Jane Doe".to_string());
+        doc.metadata
+            .attributes
+            .insert("revdate".to_string(), "2024-05-01".to_string());
+        assert_eq!(
+            Reviser::of(&doc),
+            Reviser {
+                author: "Jane Doe".to_string(),
+                date: "2024-05-01T00:00:00Z".to_string(),
+            }
+        );
+
+        // Without an author or an ISO 8601 revdate
+        doc.metadata.authors.clear();
+        doc.metadata
+            .attributes
+            .insert("revdate".to_string(), "May 2024".to_string());
+        let reviser = Reviser::of(&doc);
+        assert_eq!(reviser.author, "utf8dok");
+        assert_eq!(reviser.date.len(), "2024-05-01T00:00:00Z".len());
+        assert!(reviser.date.ends_with('Z'));
+
+        assert_eq!(date_time(0), "1970-01-01T00:00:00Z");
+        assert_eq!(date_time(1_714_567_890), "2024-05-01T12:51:30Z");
+        assert_eq!(
+            iso_date("2024-05-01T09:30:00Z").as_deref(),
+            Some("2024-05-01T09:30:00Z")
+        );
+        assert_eq!(iso_date("2024-05-01 v2"), None);
+    }
+}
@@ -51,6 +51,15 @@ impl Relationships {
     /// Settings relationship type
     pub const TYPE_SETTINGS: &'static str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings";
+    /// Footnotes relationship type
+    pub const TYPE_FOOTNOTES: &'static str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/footnotes";
+    /// Header relationship type
+    pub const TYPE_HEADER: &'static str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/header";
+    /// Footer relationship type
+    pub const TYPE_FOOTER: &'static str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/footer";
 }
 
 /// Parsed relationships from a .rels file
@@ -92,6 +92,14 @@ pub struct StyleContract {
     /// Cover page configuration (ADR-009)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cover: Option<CoverConfig>,
+
+    /// Page header configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<HeaderFooterConfig>,
+
+    /// Page footer configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<HeaderFooterConfig>,
 }
 
 /// Metadata about the style contract source
@@ -612,17 +620,13 @@ impl CoverConfig {
 
     /// Expand a content template with metadata values
     ///
-    /// Supports: {title}, {subtitle}, {author}, {email}, {revnumber}, {revdate}, {delimiter}
+    /// Supports: {title}, {subtitle}, {author}, {email}, {revnumber}, {revdate},
+    /// {revremark}, {delimiter}
     pub fn expand_template(template: &str, metadata: &CoverMetadata, delimiter: &str) -> String {
-        template
-            .replace("{title}", &metadata.title)
-            .replace("{subtitle}", &metadata.subtitle)
-            .replace("{author}", &metadata.author)
-            .replace("{email}", &metadata.email)
-            .replace("{revnumber}", &metadata.revnumber)
-            .replace("{revdate}", &metadata.revdate)
-            .replace("{revremark}", &metadata.revremark)
-            .replace("{delimiter}", delimiter)
+        expand_placeholders(template, |name| match name {
+            "delimiter" => Some(delimiter),
+            name => metadata.get(name),
+        })
     }
 }
 
@@ -638,6 +642,103 @@ pub struct CoverMetadata {
     pub revremark: String,
 }
 
+impl CoverMetadata {
+    /// Value of a metadata placeholder name, e.g. `title` for `{title}`
+    fn get(&self, name: &str) -> Option<&str> {
+        let value = match name {
+            "title" => &self.title,
+            "subtitle" => &self.subtitle,
+            "author" => &self.author,
+            "email" => &self.email,
+            "revnumber" => &self.revnumber,
+            "revdate" => &self.revdate,
+            "revremark" => &self.revremark,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Replace each `{name}` in `template` with `value(name)`, keeping
+/// placeholders without a value as-is
+fn expand_placeholders<'a>(template: &str, value: impl Fn(&str) -> Option<&'a str>) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let placeholder = &rest[..=end];
+        expanded.push_str(value(&placeholder[1..end]).unwrap_or(placeholder));
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+// =============================================================================
+// HEADER AND FOOTER CONFIGURATION
+// =============================================================================
+
+/// Page header or footer configuration
+///
+/// Each content template is one paragraph per line; a tab moves to the next
+/// tab stop of the paragraph style (center, then right in Word's built-in
+/// Header and Footer styles). Templates support the placeholders of
+/// [`CoverConfig::expand_template`], any document attribute as `{name}`, and
+/// the `{page}` and `{pages}` page number fields.
+///
+/// ```toml
+/// [footer]
+/// content = "{classification}\t{title}\tPage {page} of {pages}"
+/// first = ""
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct HeaderFooterConfig {
+    /// Word style ID to use (defaults to the template's Header or Footer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+
+    /// Content template of every page (of odd pages when `even` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+
+    /// Content template of the first page of each section ("" leaves it empty)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first: Option<String>,
+
+    /// Content template of even pages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub even: Option<String>,
+}
+
+impl HeaderFooterConfig {
+    /// Placeholder of the current page number field
+    pub const PAGE: &'static str = "{page}";
+
+    /// Placeholder of the total page count field
+    pub const PAGES: &'static str = "{pages}";
+
+    /// Expand a content template with metadata and document attribute values
+    ///
+    /// Metadata placeholders take precedence over attributes of the same
+    /// name. `{page}`, `{pages}` and unknown placeholders are kept as-is.
+    pub fn expand_template(
+        template: &str,
+        metadata: &CoverMetadata,
+        attributes: &HashMap<String, String>,
+    ) -> String {
+        expand_placeholders(template, |name| match name {
+            "page" | "pages" => None,
+            name => metadata
+                .get(name)
+                .or_else(|| attributes.get(name).map(String::as_str)),
+        })
+    }
+}
+
 impl StyleContract {
     /// Create a new empty StyleContract
     pub fn new() -> Self {
@@ -1222,6 +1323,57 @@ content = "v{revnumber} ({revdate})"
         assert_eq!(cover.title.color, "FFFFFF");
     }
 
+    #[test]
+    fn test_style_contract_with_header_and_footer() {
+        let toml_str = r#"
+[header]
+content = "{title}\t\t{classification}"
+first = ""
+
+[footer]
+style = "CorporateFooter"
+content = "Page {page} of {pages}"
+"#;
+        let contract = StyleContract::from_toml(toml_str).unwrap();
+        let header = contract.header.as_ref().unwrap();
+        assert_eq!(
+            header.content.as_deref(),
+            Some("{title}\t\t{classification}")
+        );
+        assert_eq!(header.first.as_deref(), Some(""));
+        assert_eq!(header.even, None);
+        let footer = contract.footer.as_ref().unwrap();
+        assert_eq!(footer.style.as_deref(), Some("CorporateFooter"));
+
+        // Round-trip
+        let parsed = StyleContract::from_toml(&contract.to_toml().unwrap()).unwrap();
+        assert_eq!(parsed.header, contract.header);
+        assert_eq!(parsed.footer, contract.footer);
+        assert!(!StyleContract::new().to_toml().unwrap().contains("[header]"));
+    }
+
+    #[test]
+    fn test_header_footer_template_expansion() {
+        let metadata = CoverMetadata {
+            title: "Design".to_string(),
+            revnumber: "2.0".to_string(),
+            ..Default::default()
+        };
+        let mut attributes = HashMap::new();
+        attributes.insert("classification".to_string(), "Internal".to_string());
+        attributes.insert("title".to_string(), "Attribute title".to_string());
+        attributes.insert("page".to_string(), "not a field".to_string());
+
+        assert_eq!(
+            HeaderFooterConfig::expand_template(
+                "{title} v{revnumber} | {classification} | {page}/{pages} {unknown} {open",
+                &metadata,
+                &attributes
+            ),
+            "Design v2.0 | Internal | {page}/{pages} {unknown} {open"
+        );
+    }
+
     #[test]
     fn test_parse_essential_style_contract_with_cover() {
         // Test parsing the actual Essential template style-contract.toml
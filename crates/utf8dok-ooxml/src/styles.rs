@@ -305,6 +305,18 @@ pub enum ElementType {
     AdmonitionWarning,
     /// Admonition: Caution
     AdmonitionCaution,
+    /// Figure and table caption
+    Caption,
+    /// Title of a table of contents
+    TocHeading,
+    /// Table of contents entry for a heading level 1-9
+    Toc(u8),
+    /// List of figures or tables entry
+    TableOfFigures,
+    /// Page header paragraph
+    Header,
+    /// Page footer paragraph
+    Footer,
 }
 
 /// Maps semantic document elements to Word style IDs
@@ -366,6 +378,18 @@ impl Default for StyleMap {
         mappings.insert(ElementType::AdmonitionWarning, "Warning".to_string());
         mappings.insert(ElementType::AdmonitionCaution, "Caution".to_string());
 
+        // Captions and tables of contents (Word built-in styles)
+        mappings.insert(ElementType::Caption, "Caption".to_string());
+        mappings.insert(ElementType::TocHeading, "TOCHeading".to_string());
+        for level in 1..=9 {
+            mappings.insert(ElementType::Toc(level), format!("TOC{}", level));
+        }
+        mappings.insert(ElementType::TableOfFigures, "TableofFigures".to_string());
+
+        // Page headers and footers (Word built-in styles)
+        mappings.insert(ElementType::Header, "Header".to_string());
+        mappings.insert(ElementType::Footer, "Footer".to_string());
+
         Self { mappings }
     }
 }
@@ -393,6 +417,11 @@ impl StyleMap {
             .unwrap_or_else(|| Self::fallback_style(element))
     }
 
+    /// Get the style ID mapped for an element type, without a fallback
+    pub fn mapped(&self, element: ElementType) -> Option<&str> {
+        self.mappings.get(&element).map(|s| s.as_str())
+    }
+
     /// Get style ID for a heading level (1-9)
     pub fn heading(&self, level: u8) -> &str {
         let level = level.clamp(1, 9);
@@ -439,6 +468,17 @@ impl StyleMap {
             | ElementType::AdmonitionImportant
             | ElementType::AdmonitionWarning
             | ElementType::AdmonitionCaution => "Normal",
+            ElementType::Caption => "Caption",
+            ElementType::TocHeading => "TOCHeading",
+            ElementType::Toc(level) => {
+                const TOC: [&str; 9] = [
+                    "TOC1", "TOC2", "TOC3", "TOC4", "TOC5", "TOC6", "TOC7", "TOC8", "TOC9",
+                ];
+                TOC[level.clamp(1, 9) as usize - 1]
+            }
+            ElementType::TableOfFigures => "TableofFigures",
+            ElementType::Header => "Header",
+            ElementType::Footer => "Footer",
         }
     }
 
@@ -526,7 +566,31 @@ impl StyleMap {
             }
         }
 
-        // 6. Fallback: also check by styleId for English templates
+        // 6. Map caption, table of contents, header and footer styles
+        // (built-in names are English in every locale)
+        let mut by_name = |element: ElementType, name: &str| {
+            if let Some(id) = name_to_id.get(name) {
+                map.set(element, id);
+            }
+        };
+        by_name(ElementType::Caption, "caption");
+        by_name(ElementType::TocHeading, "toc heading");
+        by_name(ElementType::TableOfFigures, "table of figures");
+        for level in 1..=9 {
+            by_name(ElementType::Toc(level), &format!("toc {}", level));
+        }
+        by_name(ElementType::Header, "header");
+        by_name(ElementType::Footer, "footer");
+
+        // 7. Map admonition and description list styles the template defines
+        by_name(ElementType::AdmonitionNote, "note");
+        by_name(ElementType::AdmonitionTip, "tip");
+        by_name(ElementType::AdmonitionImportant, "important");
+        by_name(ElementType::AdmonitionWarning, "warning");
+        by_name(ElementType::AdmonitionCaution, "caution");
+        by_name(ElementType::ListDescription, "list paragraph");
+
+        // 8. Fallback: also check by styleId for English templates
         if !map.mappings.contains_key(&ElementType::Heading(1)) {
             for level in 1..=9 {
                 let default_id = format!("Heading{}", level);
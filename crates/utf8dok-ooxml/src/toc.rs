@@ -0,0 +1,477 @@
+//! Tables of contents (TOC fields)
+//!
+//! [`Contents`] lists the headings, figures and tables of a document in the
+//! order the writer renders them, and writes the table of contents, list of
+//! figures and list of tables as Word `TOC` fields. The fields carry
+//! pre-rendered entries linked to bookmarks on the headings and captions,
+//! so the lists work before Word updates them.
+//!
+//! The lists are requested with document attributes:
+//! - `:toc:` for the table of contents, down to `:toclevels:` (default 2)
+//! - `:toc-figures:` for the list of figures
+//! - `:toc-tables:` for the list of tables
+//!
+//! Their titles are set with `:toc-title:`, `:toc-figures-title:` and
+//! `:toc-tables-title:`.
+//!
+//! [`Contents`] also numbers the figures and tables, so cross-references to
+//! a `[[id]]` anchor in front of a captioned figure or table can show
+//! "Figure N".
+
+use std::collections::HashMap;
+
+use utf8dok_ast::{Block, Document, Inline, Node, Paragraph};
+use utf8dok_core::ids::{section_ids, IdSource, SectionId};
+
+use crate::styles::{ElementType, StyleMap};
+use crate::writer::escape_xml;
+
+/// Caption label of figures
+pub const FIGURE_LABEL: &str = "Figure";
+
+/// Caption label of tables
+pub const TABLE_LABEL: &str = "Table";
+
+/// A requested list with its entries
+#[derive(Debug, Clone, Default)]
+struct EntryList {
+    title: String,
+    /// (level, text) of each entry, in document order
+    entries: Vec<(u8, String)>,
+}
+
+/// Headings, figures and tables of a document, for its tables of contents
+#[derive(Debug, Clone, Default)]
+pub struct Contents {
+    /// Deepest heading level of the table of contents
+    levels: u8,
+    toc: Option<EntryList>,
+    figures: Option<EntryList>,
+    tables: Option<EntryList>,
+    /// Level of every heading, in document order
+    heading_levels: Vec<u8>,
+    /// Section id of every heading, in document order
+    section_ids: Vec<SectionId>,
+    /// Number of figure and table captions
+    figure_count: usize,
+    table_count: usize,
+    /// (label, number) of the caption of each anchored figure and table
+    references: HashMap<String, (&'static str, usize)>,
+}
+
+impl Contents {
+    /// Collect the lists requested by the attributes of `doc`
+    pub fn collect(doc: &Document) -> Self {
+        let attributes = &doc.metadata.attributes;
+        let list = |name: &str, default_title: &str| {
+            attributes.contains_key(name).then(|| EntryList {
+                title: attributes
+                    .get(&format!("{}-title", name))
+                    .cloned()
+                    .unwrap_or_else(|| default_title.to_string()),
+                entries: Vec::new(),
+            })
+        };
+        let mut contents = Self {
+            levels: attributes
+                .get("toclevels")
+                .and_then(|levels| levels.trim().parse().ok())
+                .unwrap_or(2)
+                .clamp(1, 9),
+            toc: list("toc", "Table of Contents"),
+            figures: list("toc-figures", "List of Figures"),
+            tables: list("toc-tables", "List of Tables"),
+            heading_levels: Vec::new(),
+            section_ids: section_ids(doc),
+            figure_count: 0,
+            table_count: 0,
+            references: HashMap::new(),
+        };
+        contents.visit_all(doc.blocks.iter().map(Node::Block));
+        contents
+    }
+
+    /// Visit sibling nodes, passing each the anchor paragraph in front of it
+    fn visit_all<'a>(&mut self, nodes: impl IntoIterator<Item = Node<'a>>) {
+        let mut anchor = None;
+        for node in nodes {
+            if let Node::Inline(_) = node {
+                continue;
+            }
+            self.visit(node, anchor.take());
+            if let Node::Block(Block::Paragraph(para)) = node {
+                if let [Inline::Anchor(id)] = para.inlines.as_slice() {
+                    anchor = Some(id.as_str());
+                }
+            }
+        }
+    }
+
+    fn visit(&mut self, node: Node, anchor: Option<&str>) {
+        match node {
+            Node::Block(Block::Heading(heading)) => {
+                self.heading_levels.push(heading.level);
+                if let Some(toc) = self.toc.as_mut().filter(|_| heading.level <= self.levels) {
+                    toc.entries.push((heading.level, plain_text(&heading.text)));
+                }
+            }
+            Node::Block(Block::Paragraph(para)) => {
+                if let Some(title) = figure_title(para) {
+                    self.figure_count += 1;
+                    let number = self.figure_count;
+                    if let Some(figures) = self.figures.as_mut() {
+                        figures
+                            .entries
+                            .push((1, format!("{} {}. {}", FIGURE_LABEL, number, title)));
+                    }
+                    if let Some(id) = anchor {
+                        self.references
+                            .insert(id.to_string(), (FIGURE_LABEL, number));
+                    }
+                }
+            }
+            Node::Block(Block::Table(table)) => {
+                if let Some(caption) = &table.caption {
+                    self.table_count += 1;
+                    let number = self.table_count;
+                    if let Some(tables) = self.tables.as_mut() {
+                        tables.entries.push((
+                            1,
+                            format!("{} {}. {}", TABLE_LABEL, number, plain_text(caption)),
+                        ));
+                    }
+                    if let Some(id) = anchor {
+                        self.references
+                            .insert(id.to_string(), (TABLE_LABEL, number));
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.visit_all(node.children());
+    }
+
+    /// Whether any list was requested
+    pub fn is_empty(&self) -> bool {
+        self.toc.is_none() && self.figures.is_none() && self.tables.is_none()
+    }
+
+    /// Bookmark of the `index`th heading (from 1), when it is listed
+    pub fn heading_bookmark(&self, index: usize) -> Option<String> {
+        let level = *self.heading_levels.get(index.checked_sub(1)?)?;
+        (self.toc.is_some() && level <= self.levels).then(|| format!("_TocHeading{}", index))
+    }
+
+    /// Section id of the `index`th heading (from 1), unless an anchor
+    /// paragraph above the heading already bookmarks it
+    pub fn section_id(&self, index: usize) -> Option<String> {
+        let section = self.section_ids.get(index.checked_sub(1)?)?;
+        (section.source != IdSource::AnchorAbove).then(|| section.id.clone())
+    }
+
+    /// Bookmark of the caption of figure `number`, when figures are listed
+    pub fn figure_bookmark(&self, number: usize) -> Option<String> {
+        self.figures
+            .as_ref()
+            .map(|_| format!("_Toc{}{}", FIGURE_LABEL, number))
+    }
+
+    /// Bookmark of the caption of table `number`, when tables are listed
+    pub fn table_bookmark(&self, number: usize) -> Option<String> {
+        self.tables
+            .as_ref()
+            .map(|_| format!("_Toc{}{}", TABLE_LABEL, number))
+    }
+
+    /// The caption label and number of the figure or table anchored by `id`
+    pub fn reference(&self, id: &str) -> Option<(&'static str, usize)> {
+        self.references.get(id).copied()
+    }
+
+    /// The anchor of the figure or table with the given caption
+    pub fn caption_anchor(&self, label: &str, number: usize) -> Option<&str> {
+        self.references
+            .iter()
+            .find(|(_, &reference)| reference == (label, number))
+            .map(|(id, _)| id.as_str())
+    }
+
+    /// Generate the requested lists as TOC fields
+    pub fn to_xml(&self, styles: &StyleMap) -> String {
+        let mut xml = String::new();
+        if let Some(toc) = &self.toc {
+            let instruction = format!("TOC \\o \"1-{}\" \\h \\z \\u", self.levels);
+            // Headings are bookmarked by their index among all headings
+            let mut bookmarks = self
+                .heading_levels
+                .iter()
+                .enumerate()
+                .filter(|(_, &level)| level <= self.levels)
+                .map(|(index, _)| format!("_TocHeading{}", index + 1));
+            write_list(
+                &mut xml,
+                styles,
+                toc,
+                &instruction,
+                ElementType::Toc,
+                |_| bookmarks.next().unwrap_or_default(),
+            );
+        }
+        for (list, label) in [(&self.figures, FIGURE_LABEL), (&self.tables, TABLE_LABEL)] {
+            if let Some(list) = list {
+                let instruction = format!("TOC \\h \\z \\c \"{}\"", label);
+                write_list(
+                    &mut xml,
+                    styles,
+                    list,
+                    &instruction,
+                    |_| ElementType::TableOfFigures,
+                    |number| format!("_Toc{}{}", label, number),
+                );
+            }
+        }
+        xml
+    }
+}
+
+/// Write a titled list as a TOC field with one hyperlinked paragraph per
+/// entry, styled by the entry level; `bookmark` names the target of the
+/// entry with the given number
+fn write_list(
+    xml: &mut String,
+    styles: &StyleMap,
+    list: &EntryList,
+    instruction: &str,
+    entry_style: impl Fn(u8) -> ElementType,
+    mut bookmark: impl FnMut(usize) -> String,
+) {
+    xml.push_str(&format!(
+        "<w:p>\n<w:pPr>\n<w:pStyle w:val=\"{}\"/>\n</w:pPr>\n<w:r>\n<w:t>{}</w:t>\n</w:r>\n</w:p>\n",
+        escape_xml(styles.get(ElementType::TocHeading)),
+        escape_xml(&list.title)
+    ));
+
+    // Marked dirty, so Word offers to rebuild the entries with page numbers
+    let begin = format!(
+        "<w:r>\n<w:fldChar w:fldCharType=\"begin\" w:dirty=\"true\"/>\n</w:r>\n<w:r>\n<w:instrText xml:space=\"preserve\"> {} </w:instrText>\n</w:r>\n<w:r>\n<w:fldChar w:fldCharType=\"separate\"/>\n</w:r>\n",
+        escape_xml(instruction)
+    );
+    let end = "<w:r>\n<w:fldChar w:fldCharType=\"end\"/>\n</w:r>\n";
+    if list.entries.is_empty() {
+        xml.push_str(&format!("<w:p>\n{}{}</w:p>\n", begin, end));
+        return;
+    }
+
+    for (index, (level, text)) in list.entries.iter().enumerate() {
+        let style = styles.get(entry_style(*level));
+        let target = bookmark(index + 1);
+        xml.push_str(&format!(
+            "<w:p>\n<w:pPr>\n<w:pStyle w:val=\"{}\"/>\n</w:pPr>\n",
+            escape_xml(style)
+        ));
+        if index == 0 {
+            xml.push_str(&begin);
+        }
+        // The page number is left to Word, which knows the layout
+        xml.push_str(&format!(
+            "<w:hyperlink w:anchor=\"{0}\" w:history=\"1\">\n<w:r>\n<w:t xml:space=\"preserve\">{1}</w:t>\n</w:r>\n<w:r>\n<w:tab/>\n</w:r>\n{2}</w:hyperlink>\n</w:p>\n",
+            escape_xml(&target),
+            escape_xml(text),
+            field(&format!("PAGEREF {} \\h", target), "")
+        ));
+    }
+    xml.push_str(&format!("<w:p>\n{}</w:p>\n", end));
+}
+
+/// Runs of a complex field with its cached result
+pub fn field(instruction: &str, result: &str) -> String {
+    let result = if result.is_empty() {
+        String::new()
+    } else {
+        format!("<w:r>\n<w:t>{}</w:t>\n</w:r>\n", escape_xml(result))
+    };
+    format!(
+        "<w:r>\n<w:fldChar w:fldCharType=\"begin\"/>\n</w:r>\n<w:r>\n<w:instrText xml:space=\"preserve\"> {} </w:instrText>\n</w:r>\n<w:r>\n<w:fldChar w:fldCharType=\"separate\"/>\n</w:r>\n{}<w:r>\n<w:fldChar w:fldCharType=\"end\"/>\n</w:r>\n",
+        escape_xml(instruction),
+        result
+    )
+}
+
+/// The title of a figure: a titled paragraph holding only a block image
+pub fn figure_title(para: &Paragraph) -> Option<&str> {
+    match para.inlines.as_slice() {
+        [Inline::Image(_)] => para.attributes.get("title").map(String::as_str),
+        _ => None,
+    }
+}
+
+/// Plain text of inline content
+fn plain_text(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    for inline in inlines {
+        collect_text(Node::Inline(inline), &mut text);
+    }
+    text
+}
+
+fn collect_text(node: Node, text: &mut String) {
+    match node {
+        Node::Inline(Inline::Text(t)) => text.push_str(t),
+        Node::Inline(Inline::Footnote(_)) => {}
+        _ => {
+            for child in node.children() {
+                collect_text(child, text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use utf8dok_ast::{DocumentMeta, Heading, Image, Table};
+
+    fn heading(level: u8, text: &str) -> Block {
+        Block::Heading(Heading {
+            level,
+            text: vec![Inline::Text(text.to_string())],
+            style_id: None,
+            anchor: None,
+            role: None,
+        })
+    }
+
+    fn document(attributes: &[(&str, &str)], blocks: Vec<Block>) -> Document {
+        let mut metadata = DocumentMeta::default();
+        for (name, value) in attributes {
+            metadata
+                .attributes
+                .insert(name.to_string(), value.to_string());
+        }
+        Document {
+            metadata,
+            intent: None,
+            blocks,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_nothing_requested() {
+        let contents = Contents::collect(&document(&[], vec![heading(1, "Intro")]));
+        assert!(contents.is_empty());
+        assert_eq!(contents.heading_bookmark(1), None);
+        assert_eq!(contents.to_xml(&StyleMap::default()), "");
+    }
+
+    #[test]
+    fn test_toc_lists_headings_down_to_toclevels() {
+        let doc = document(
+            &[("toc", ""), ("toclevels", "1"), ("toc-title", "Contents")],
+            vec![heading(1, "Intro"), heading(2, "Detail"), heading(1, "End")],
+        );
+        let contents = Contents::collect(&doc);
+        assert_eq!(
+            contents.heading_bookmark(1).as_deref(),
+            Some("_TocHeading1")
+        );
+        assert_eq!(contents.heading_bookmark(2), None);
+        assert_eq!(
+            contents.heading_bookmark(3).as_deref(),
+            Some("_TocHeading3")
+        );
+
+        let xml = contents.to_xml(&StyleMap::default());
+        assert!(xml.contains("<w:t>Contents</w:t>"));
+        assert!(xml.contains(" TOC \\o &quot;1-1&quot; \\h \\z \\u "));
+        assert!(xml.contains("<w:hyperlink w:anchor=\"_TocHeading3\""));
+        assert!(xml.contains(" PAGEREF _TocHeading3 \\h "));
+        assert!(!xml.contains("Detail"));
+        assert_eq!(xml.matches("<w:pStyle w:val=\"TOC1\"/>").count(), 2);
+    }
+
+    #[test]
+    fn test_lists_of_figures_and_tables() {
+        let mut attributes = HashMap::new();
+        attributes.insert("title".to_string(), "Architecture".to_string());
+        let figure = Block::Paragraph(Paragraph {
+            inlines: vec![Inline::Image(Image {
+                src: "arch.png".to_string(),
+                alt: None,
+            })],
+            style_id: None,
+            attributes,
+        });
+        let table = Block::Table(Table {
+            rows: Vec::new(),
+            style_id: None,
+            caption: Some(vec![Inline::Text("Ports".to_string())]),
+            columns: Vec::new(),
+        });
+        let doc = document(
+            &[("toc-figures", ""), ("toc-tables", "")],
+            vec![figure, table],
+        );
+        let contents = Contents::collect(&doc);
+        assert_eq!(contents.figure_bookmark(1).as_deref(), Some("_TocFigure1"));
+        assert_eq!(contents.table_bookmark(1).as_deref(), Some("_TocTable1"));
+
+        let xml = contents.to_xml(&StyleMap::default());
+        assert!(xml.contains(" TOC \\h \\z \\c &quot;Figure&quot; "));
+        assert!(xml.contains("Figure 1. Architecture"));
+        assert!(xml.contains(" TOC \\h \\z \\c &quot;Table&quot; "));
+        assert!(xml.contains("Table 1. Ports"));
+        assert!(xml.contains("<w:pStyle w:val=\"TableofFigures\"/>"));
+    }
+
+    #[test]
+    fn test_anchored_captions_are_referenced() {
+        let anchor = |id: &str| {
+            Block::Paragraph(Paragraph {
+                inlines: vec![Inline::Anchor(id.to_string())],
+                style_id: None,
+                attributes: HashMap::new(),
+            })
+        };
+        let figure = || {
+            let mut attributes = HashMap::new();
+            attributes.insert("title".to_string(), "Architecture".to_string());
+            Block::Paragraph(Paragraph {
+                inlines: vec![Inline::Image(Image {
+                    src: "arch.png".to_string(),
+                    alt: None,
+                })],
+                style_id: None,
+                attributes,
+            })
+        };
+        let table = Block::Table(Table {
+            rows: Vec::new(),
+            style_id: None,
+            caption: Some(vec![Inline::Text("Ports".to_string())]),
+            columns: Vec::new(),
+        });
+        let doc = document(
+            &[],
+            vec![
+                figure(),
+                anchor("fig-two"),
+                figure(),
+                anchor("intro"),
+                heading(1, "Intro"),
+                anchor("tab-ports"),
+                table,
+            ],
+        );
+
+        // Captions are numbered even without lists of figures and tables
+        let contents = Contents::collect(&doc);
+        assert!(contents.is_empty());
+        assert_eq!(contents.reference("fig-two"), Some((FIGURE_LABEL, 2)));
+        assert_eq!(contents.reference("tab-ports"), Some((TABLE_LABEL, 1)));
+        assert_eq!(contents.reference("intro"), None);
+        assert_eq!(contents.caption_anchor(FIGURE_LABEL, 2), Some("fig-two"));
+        assert_eq!(contents.caption_anchor(FIGURE_LABEL, 1), None);
+    }
+}
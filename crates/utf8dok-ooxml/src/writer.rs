@@ -16,21 +16,28 @@
 //! std::fs::write("output.docx", output)?;
 //! ```
 
+use std::collections::HashMap;
 use std::io::Cursor;
 
 use sha2::{Digest, Sha256};
 use utf8dok_ast::{
-    Block, Document, FormatType, Heading, Inline, List, ListItem, ListType, Paragraph, Table,
+    Block, Document, Footnote, FormatType, Heading, Inline, List, ListItem, ListType, Paragraph,
+    Table, TableCell,
 };
 use utf8dok_diagrams::{DiagramEngine, DiagramType};
 
 use crate::archive::OoxmlArchive;
+use crate::document::RevisionKind;
 use crate::error::Result;
+use crate::header_footer::{self, Kind, Part, Variant};
 use crate::manifest::{ElementMeta, Manifest};
+use crate::numbering::{self, Numbering};
+use crate::redline::{self, BlockChange, ItemRevision, Reviser, RowRevision};
 use crate::relationships::Relationships;
 use crate::style_map::{CoverConfig, CoverMetadata, StyleContract, TextAlign};
 use crate::styles::StyleMap;
 use crate::template::Template;
+use crate::toc::{self, Contents};
 
 /// Known diagram style IDs that should be rendered as images
 const DIAGRAM_STYLES: &[&str] = &[
@@ -66,6 +73,59 @@ struct Comment {
     author: String,
 }
 
+/// A footnote to be added to footnotes.xml
+#[derive(Debug, Clone)]
+struct FootnoteEntry {
+    /// Footnote ID (referenced by w:footnoteReference)
+    id: usize,
+    /// Run XML for the footnote text
+    runs: String,
+}
+
+/// Visual frame for the paragraphs of a compound block
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockFrame {
+    /// Example block: boxed
+    Example,
+    /// Sidebar block: boxed and shaded
+    Sidebar,
+    /// Quote block: indented with a bar on the left
+    Quote,
+}
+
+impl BlockFrame {
+    /// Paragraph properties following `w:pStyle`
+    fn paragraph_properties(self) -> &'static str {
+        match self {
+            BlockFrame::Example => concat!(
+                "<w:pBdr>",
+                "<w:top w:val=\"single\" w:sz=\"4\" w:space=\"4\" w:color=\"BFBFBF\"/>",
+                "<w:left w:val=\"single\" w:sz=\"4\" w:space=\"4\" w:color=\"BFBFBF\"/>",
+                "<w:bottom w:val=\"single\" w:sz=\"4\" w:space=\"4\" w:color=\"BFBFBF\"/>",
+                "<w:right w:val=\"single\" w:sz=\"4\" w:space=\"4\" w:color=\"BFBFBF\"/>",
+                "</w:pBdr>\n",
+                "<w:ind w:left=\"144\" w:right=\"144\"/>\n",
+            ),
+            BlockFrame::Sidebar => concat!(
+                "<w:pBdr>",
+                "<w:top w:val=\"single\" w:sz=\"4\" w:space=\"4\" w:color=\"D9D9D9\"/>",
+                "<w:left w:val=\"single\" w:sz=\"4\" w:space=\"4\" w:color=\"D9D9D9\"/>",
+                "<w:bottom w:val=\"single\" w:sz=\"4\" w:space=\"4\" w:color=\"D9D9D9\"/>",
+                "<w:right w:val=\"single\" w:sz=\"4\" w:space=\"4\" w:color=\"D9D9D9\"/>",
+                "</w:pBdr>\n",
+                "<w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"F2F2F2\"/>\n",
+                "<w:ind w:left=\"144\" w:right=\"144\"/>\n",
+            ),
+            BlockFrame::Quote => concat!(
+                "<w:pBdr>",
+                "<w:left w:val=\"single\" w:sz=\"18\" w:space=\"8\" w:color=\"A6A6A6\"/>",
+                "</w:pBdr>\n",
+                "<w:ind w:left=\"720\" w:right=\"720\"/>\n",
+            ),
+        }
+    }
+}
+
 /// DOCX Writer for generating DOCX files from AST
 pub struct DocxWriter {
     /// XML output buffer
@@ -96,10 +156,68 @@ pub struct DocxWriter {
     comments: Vec<Comment>,
     /// Next comment ID
     next_comment_id: usize,
+    /// Whether comment blocks become Word comments
+    word_comments: bool,
+    /// Word comments waiting for a paragraph: (output offset, comment ID)
+    pending_comments: Vec<(usize, usize)>,
     /// Next bookmark ID for unique bookmark IDs
     next_bookmark_id: usize,
     /// Cover image path and bytes (for title page)
     cover_image: Option<(String, Vec<u8>)>,
+    /// Footnotes to be added to footnotes.xml
+    footnotes: Vec<FootnoteEntry>,
+    /// Footnote IDs by AsciiDoc footnote id (for `footnote:id[]` reuse)
+    footnote_ids: HashMap<String, usize>,
+    /// Relationships of word/footnotes.xml (hyperlinks inside footnotes)
+    footnote_relationships: Relationships,
+    /// Frames of the compound blocks being rendered, innermost last
+    frames: Vec<BlockFrame>,
+    /// Number of code blocks with callouts so far (names callout bookmarks)
+    callout_blocks: usize,
+    /// Numbering definitions (template definitions plus one per list)
+    numbering: Numbering,
+    /// Numbered lists still open, by level, that a following list continues
+    list_chain: Vec<ChainedList>,
+    /// Level of the outermost items of the list being rendered
+    list_base_level: u8,
+    /// Headings, figures and tables listed by the tables of contents, and
+    /// the numbers of anchored figures and tables
+    contents: Contents,
+    /// Number of headings so far (names their TOC bookmarks)
+    headings: usize,
+    /// Number of figure captions so far
+    figures: usize,
+    /// Number of table captions so far
+    tables: usize,
+    /// Header and footer parts referenced by every section
+    page_parts: Vec<PagePart>,
+    /// Original document the output is redlined against
+    redline: Option<Document>,
+    /// Author and date of the tracked changes of a redline
+    reviser: Reviser,
+    /// Next revision ID for tracked changes
+    next_revision_id: usize,
+    /// Changes of the inlines of the edited block being rendered
+    inline_revisions: Option<Vec<Option<RevisionKind>>>,
+    /// Changes of the items of the edited list being rendered
+    list_revisions: Option<Vec<Option<ItemRevision>>>,
+    /// Changes of the rows of the edited table being rendered
+    table_revisions: Option<Vec<Option<RowRevision>>>,
+}
+
+/// A header or footer part with its target (relative to word/) and
+/// relationship ID
+struct PagePart {
+    part: Part,
+    target: String,
+    rel_id: String,
+}
+
+/// A list whose numbering a following list at the same level continues
+struct ChainedList {
+    level: u8,
+    list_type: ListType,
+    num_id: u32,
 }
 
 impl Default for DocxWriter {
@@ -126,8 +244,29 @@ impl DocxWriter {
             config_text: None,
             comments: Vec::new(),
             next_comment_id: 1,
+            word_comments: false,
+            pending_comments: Vec::new(),
             next_bookmark_id: 0,
             cover_image: None,
+            footnotes: Vec::new(),
+            footnote_ids: HashMap::new(),
+            footnote_relationships: Relationships::new(),
+            frames: Vec::new(),
+            callout_blocks: 0,
+            numbering: Numbering::new(),
+            list_chain: Vec::new(),
+            list_base_level: 0,
+            contents: Contents::default(),
+            headings: 0,
+            figures: 0,
+            tables: 0,
+            page_parts: Vec::new(),
+            redline: None,
+            reviser: Reviser::default(),
+            next_revision_id: 1,
+            inline_revisions: None,
+            list_revisions: None,
+            table_revisions: None,
         }
     }
 
@@ -148,8 +287,29 @@ impl DocxWriter {
             config_text: None,
             comments: Vec::new(),
             next_comment_id: 1,
+            word_comments: false,
+            pending_comments: Vec::new(),
             next_bookmark_id: 0,
             cover_image: None,
+            footnotes: Vec::new(),
+            footnote_ids: HashMap::new(),
+            footnote_relationships: Relationships::new(),
+            frames: Vec::new(),
+            callout_blocks: 0,
+            numbering: Numbering::new(),
+            list_chain: Vec::new(),
+            list_base_level: 0,
+            contents: Contents::default(),
+            headings: 0,
+            figures: 0,
+            tables: 0,
+            page_parts: Vec::new(),
+            redline: None,
+            reviser: Reviser::default(),
+            next_revision_id: 1,
+            inline_revisions: None,
+            list_revisions: None,
+            table_revisions: None,
         }
     }
 
@@ -179,6 +339,24 @@ impl DocxWriter {
         self.config_text = Some(config.into());
     }
 
+    /// Turn comment blocks into Word comments
+    ///
+    /// Each comment is anchored to the paragraph that follows it. Comment
+    /// blocks are left out of the document otherwise.
+    pub fn set_word_comments(&mut self, enabled: bool) {
+        self.word_comments = enabled;
+    }
+
+    /// Render the changes from `original` as tracked changes (a redline)
+    ///
+    /// Blocks are compared with the original: edited paragraphs and
+    /// headings get word-level insertions and deletions, other changed
+    /// blocks are inserted or deleted whole. The changes are by the first
+    /// author of the revised document, dated by its `revdate`.
+    pub fn set_redline(&mut self, original: Document) {
+        self.redline = Some(original);
+    }
+
     /// Set the style contract for round-trip fidelity (ADR-007)
     ///
     /// When set, the writer will use the contract to restore original
@@ -230,6 +408,15 @@ impl DocxWriter {
         self.style_map.paragraph()
     }
 
+    /// Resolve an element to the template's Word style ID
+    ///
+    /// Elements the template has no style for use the body text style.
+    fn resolve_element_style(&self, element: crate::styles::ElementType) -> &str {
+        self.style_map
+            .mapped(element)
+            .unwrap_or_else(|| self.resolve_paragraph_style("body"))
+    }
+
     /// Get the next unique bookmark ID
     fn next_bookmark_id(&mut self) -> usize {
         let id = self.next_bookmark_id;
@@ -244,6 +431,13 @@ impl DocxWriter {
             self.relationships = Relationships::parse(rels_xml)?;
         }
 
+        // Parse existing numbering definitions, so list definitions follow them
+        if let Some(target) = self.numbering_target() {
+            if let Some(numbering_xml) = archive.get(&format!("word/{}", target)) {
+                self.numbering = Numbering::parse(numbering_xml);
+            }
+        }
+
         // Parse existing manifest if present
         if let Some(manifest_bytes) = archive.read_utf8dok_file("manifest.json") {
             self.manifest = Manifest::from_json_bytes(manifest_bytes)?;
@@ -393,6 +587,15 @@ impl DocxWriter {
         // Write comments.xml if we have any language annotations
         self.write_comments(&mut archive)?;
 
+        // Write footnotes.xml if the document has footnotes
+        self.write_footnotes(&mut archive)?;
+
+        // Write numbering.xml if the document has lists
+        self.write_numbering(&mut archive)?;
+
+        // Write the header and footer parts of the style contract
+        self.write_headers_footers(&mut archive)?;
+
         // Update docProps/core.xml with document metadata (title, author)
         self.update_core_properties(&mut archive, doc)?;
 
@@ -467,6 +670,15 @@ impl DocxWriter {
         // Write comments.xml if we have any language annotations
         writer.write_comments(&mut archive)?;
 
+        // Write footnotes.xml if the document has footnotes
+        writer.write_footnotes(&mut archive)?;
+
+        // Write numbering.xml if the document has lists
+        writer.write_numbering(&mut archive)?;
+
+        // Write the header and footer parts of the style contract
+        writer.write_headers_footers(&mut archive)?;
+
         // Update docProps/core.xml with document metadata (title, author)
         writer.update_core_properties(&mut archive, doc)?;
 
@@ -563,6 +775,15 @@ impl DocxWriter {
         // Write comments.xml if we have any language annotations
         writer.write_comments(&mut archive)?;
 
+        // Write footnotes.xml if the document has footnotes
+        writer.write_footnotes(&mut archive)?;
+
+        // Write numbering.xml if the document has lists
+        writer.write_numbering(&mut archive)?;
+
+        // Write the header and footer parts of the style contract
+        writer.write_headers_footers(&mut archive)?;
+
         // Update docProps/core.xml with document metadata (title, author)
         writer.update_core_properties(&mut archive, doc)?;
 
@@ -748,14 +969,26 @@ impl DocxWriter {
         );
 
         for comment in &self.comments {
+            // One paragraph per line of the comment
+            let paragraphs: String = comment
+                .text
+                .split('\n')
+                .map(|line| {
+                    format!(
+                        "<w:p><w:r><w:t xml:space=\"preserve\">{}</w:t></w:r></w:p>",
+                        escape_xml(line)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
             xml.push_str(&format!(
                 r#"
 <w:comment w:id="{}" w:author="{}" w:date="2024-01-01T00:00:00Z">
-<w:p><w:r><w:t>{}</w:t></w:r></w:p>
+{}
 </w:comment>"#,
                 comment.id,
                 escape_xml(&comment.author),
-                escape_xml(&comment.text)
+                paragraphs
             ));
         }
 
@@ -801,6 +1034,299 @@ impl DocxWriter {
         Ok(())
     }
 
+    /// Target of the footnotes part, relative to word/
+    ///
+    /// Reuses the template's footnotes relationship when present.
+    fn footnotes_target(&self) -> Option<String> {
+        self.relationships
+            .iter()
+            .find(|(_, rel)| rel.rel_type == Relationships::TYPE_FOOTNOTES)
+            .map(|(_, rel)| rel.target.clone())
+    }
+
+    /// Generate footnotes.xml if there are any footnotes
+    ///
+    /// Word requires the separator and continuation separator footnotes
+    /// (IDs -1 and 0) before the document's own footnotes.
+    fn generate_footnotes_xml(&self) -> Option<String> {
+        if self.footnotes.is_empty() {
+            return None;
+        }
+
+        let mut xml = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:footnotes xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<w:footnote w:type="separator" w:id="-1"><w:p><w:r><w:separator/></w:r></w:p></w:footnote>
+<w:footnote w:type="continuationSeparator" w:id="0"><w:p><w:r><w:continuationSeparator/></w:r></w:p></w:footnote>"#,
+        );
+
+        for footnote in &self.footnotes {
+            xml.push_str(&format!(
+                r#"
+<w:footnote w:id="{}">
+<w:p>
+<w:pPr>
+<w:pStyle w:val="FootnoteText"/>
+</w:pPr>
+<w:r>
+<w:rPr>
+<w:vertAlign w:val="superscript"/>
+</w:rPr>
+<w:footnoteRef/>
+</w:r>
+<w:r>
+<w:t xml:space="preserve"> </w:t>
+</w:r>
+{}</w:p>
+</w:footnote>"#,
+                footnote.id, footnote.runs
+            ));
+        }
+
+        xml.push_str("\n</w:footnotes>");
+        Some(xml)
+    }
+
+    /// Write footnotes.xml with its relationships and content type
+    ///
+    /// The document relationship is registered by `generate_document_xml`.
+    fn write_footnotes(&self, archive: &mut OoxmlArchive) -> Result<()> {
+        let (Some(footnotes_xml), Some(target)) =
+            (self.generate_footnotes_xml(), self.footnotes_target())
+        else {
+            return Ok(());
+        };
+
+        archive.set_string(format!("word/{}", target), footnotes_xml);
+
+        if !self.footnote_relationships.is_empty() {
+            archive.set_string(
+                format!("word/_rels/{}.rels", target),
+                self.footnote_relationships.to_xml(),
+            );
+        }
+
+        // Update [Content_Types].xml to include footnotes
+        if let Some(content_types) = archive.get_string("[Content_Types].xml")? {
+            let part_name = format!("/word/{}", target);
+            if !content_types.contains(&part_name) {
+                let new_content_types = content_types.replace(
+                    "</Types>",
+                    &format!(
+                        r#"<Override PartName="{}" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.footnotes+xml"/>
+</Types>"#,
+                        part_name
+                    ),
+                );
+                archive.set_string("[Content_Types].xml", new_content_types);
+            }
+        }
+        Ok(())
+    }
+
+    /// Target of the numbering part, relative to word/
+    ///
+    /// Reuses the template's numbering relationship when present.
+    fn numbering_target(&self) -> Option<String> {
+        self.relationships
+            .iter()
+            .find(|(_, rel)| rel.rel_type == Relationships::TYPE_NUMBERING)
+            .map(|(_, rel)| rel.target.clone())
+    }
+
+    /// Write numbering.xml with the lists' definitions and its content type
+    ///
+    /// The definitions are merged into the template's numbering part, if
+    /// any. The document relationship is registered by
+    /// `generate_document_xml`.
+    fn write_numbering(&self, archive: &mut OoxmlArchive) -> Result<()> {
+        let Some(target) = self
+            .numbering_target()
+            .filter(|_| !self.numbering.is_empty())
+        else {
+            return Ok(());
+        };
+
+        let path = format!("word/{}", target);
+        let existing = archive.get_string(&path)?;
+        archive.set_string(path, self.numbering.to_xml(existing.as_deref()));
+
+        // Update [Content_Types].xml to include numbering
+        if let Some(content_types) = archive.get_string("[Content_Types].xml")? {
+            let part_name = format!("/word/{}", target);
+            if !content_types.contains(&part_name) {
+                let new_content_types = content_types.replace(
+                    "</Types>",
+                    &format!(
+                        r#"<Override PartName="{}" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>
+</Types>"#,
+                        part_name
+                    ),
+                );
+                archive.set_string("[Content_Types].xml", new_content_types);
+            }
+        }
+        Ok(())
+    }
+
+    /// Render and register the header and footer parts of the style contract
+    fn prepare_headers_footers(&mut self, doc: &Document) {
+        self.page_parts.clear();
+        let Some(contract) = self.style_contract.as_ref() else {
+            return;
+        };
+        let configs = [
+            (Kind::Header, contract.header.as_ref()),
+            (Kind::Footer, contract.footer.as_ref()),
+        ];
+        let title_page = configs
+            .iter()
+            .any(|(_, config)| config.is_some_and(|c| c.first.is_some()));
+        let even_pages = configs
+            .iter()
+            .any(|(_, config)| config.is_some_and(|c| c.even.is_some()));
+
+        let metadata = self.extract_cover_metadata(doc);
+        let mut parts = Vec::new();
+        for (kind, config) in configs {
+            if let Some(config) = config {
+                parts.extend(header_footer::parts(
+                    kind,
+                    config,
+                    title_page,
+                    even_pages,
+                    &metadata,
+                    &doc.metadata.attributes,
+                    &self.style_map,
+                ));
+            }
+        }
+
+        for part in parts {
+            // Template parts keep their names
+            let target = (1..)
+                .map(|index| format!("{}{}.xml", part.kind.name(), index))
+                .find(|target| {
+                    !self
+                        .relationships
+                        .iter()
+                        .any(|(_, rel)| &rel.target == target)
+                })
+                .unwrap_or_default();
+            let rel_id = self
+                .relationships
+                .add(target.clone(), part.kind.relationship_type().to_string());
+            self.page_parts.push(PagePart {
+                part,
+                target,
+                rel_id,
+            });
+        }
+
+        // Register the settings part (written by write_headers_footers)
+        if self.even_and_odd_pages() && self.settings_target().is_none() {
+            self.relationships.add(
+                "settings.xml".to_string(),
+                Relationships::TYPE_SETTINGS.to_string(),
+            );
+        }
+    }
+
+    /// Whether even pages have their own headers and footers
+    fn even_and_odd_pages(&self) -> bool {
+        self.page_parts
+            .iter()
+            .any(|page_part| page_part.part.variant == Variant::Even)
+    }
+
+    /// Section properties referencing the header and footer parts
+    ///
+    /// Every section references the parts, so each one starts with the
+    /// first-page variant. `extra` goes between the references and
+    /// `w:titlePg`, as the schema orders them.
+    fn section_properties(&self, extra: &str) -> String {
+        let mut xml = String::from("<w:sectPr>\n");
+        for page_part in &self.page_parts {
+            xml.push_str(&format!(
+                "<w:{}Reference w:type=\"{}\" r:id=\"{}\"/>\n",
+                page_part.part.kind.name(),
+                page_part.part.variant.name(),
+                page_part.rel_id
+            ));
+        }
+        xml.push_str(extra);
+        if self
+            .page_parts
+            .iter()
+            .any(|page_part| page_part.part.variant == Variant::First)
+        {
+            xml.push_str("<w:titlePg/>\n");
+        }
+        xml.push_str("</w:sectPr>\n");
+        xml
+    }
+
+    /// Target of the settings part, relative to word/
+    fn settings_target(&self) -> Option<String> {
+        self.relationships
+            .iter()
+            .find(|(_, rel)| rel.rel_type == Relationships::TYPE_SETTINGS)
+            .map(|(_, rel)| rel.target.clone())
+    }
+
+    /// Write the header and footer parts with their content types
+    ///
+    /// Separate even page headers are turned on in the settings part. The
+    /// document relationships are registered by `prepare_headers_footers`.
+    fn write_headers_footers(&self, archive: &mut OoxmlArchive) -> Result<()> {
+        if self.page_parts.is_empty() {
+            return Ok(());
+        }
+
+        let mut overrides = Vec::new();
+        for page_part in &self.page_parts {
+            archive.set_string(
+                format!("word/{}", page_part.target),
+                page_part.part.xml.clone(),
+            );
+            overrides.push((
+                format!("/word/{}", page_part.target),
+                page_part.part.kind.content_type(),
+            ));
+        }
+
+        if let Some(target) = self.settings_target().filter(|_| self.even_and_odd_pages()) {
+            let path = format!("word/{}", target);
+            let settings = archive.get_string(&path)?;
+            archive.set_string(
+                path,
+                header_footer::enable_even_and_odd_headers(settings.as_deref()),
+            );
+            overrides.push((
+                format!("/word/{}", target),
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml",
+            ));
+        }
+
+        // Update [Content_Types].xml to include the parts
+        if let Some(mut content_types) = archive.get_string("[Content_Types].xml")? {
+            for (part_name, content_type) in overrides {
+                if !content_types.contains(&format!("\"{}\"", part_name)) {
+                    content_types = content_types.replace(
+                        "</Types>",
+                        &format!(
+                            r#"<Override PartName="{}" ContentType="{}"/>
+</Types>"#,
+                            part_name, content_type
+                        ),
+                    );
+                }
+            }
+            archive.set_string("[Content_Types].xml", content_types);
+        }
+        Ok(())
+    }
+
     /// Update [Content_Types].xml to include PNG extension
     fn update_content_types(&self, archive: &mut OoxmlArchive) -> Result<()> {
         if let Some(content_types) = archive.get_string("[Content_Types].xml")? {
@@ -841,18 +1367,57 @@ impl DocxWriter {
         self.output.push('\n');
         self.output.push_str("<w:body>\n");
 
+        // Headers and footers configured by the style contract
+        self.prepare_headers_footers(doc);
+
         // Generate cover page if set (with document metadata)
         self.generate_cover_page(doc);
 
-        // Generate blocks
-        for block in &doc.blocks {
-            self.generate_block(block);
+        // Tables of contents requested by the document attributes
+        self.contents = Contents::collect(doc);
+        let contents = self.contents.to_xml(&self.style_map);
+        self.output.push_str(&contents);
+
+        // Generate blocks, as tracked changes when redlined
+        match self.redline.take() {
+            Some(original) => {
+                self.reviser = Reviser::of(doc);
+                self.generate_redline(&original.blocks, &doc.blocks)
+            }
+            None => {
+                for block in &doc.blocks {
+                    self.generate_block(block);
+                }
+            }
+        }
+        self.anchor_pending_comments();
+
+        // Properties of the last section
+        if !self.page_parts.is_empty() {
+            let section = self.section_properties("");
+            self.output.push_str(&section);
         }
 
         // Close body and document
         self.output.push_str("</w:body>\n");
         self.output.push_str("</w:document>");
 
+        // Register the numbering part (written by write_numbering)
+        if !self.numbering.is_empty() && self.numbering_target().is_none() {
+            self.relationships.add(
+                "numbering.xml".to_string(),
+                Relationships::TYPE_NUMBERING.to_string(),
+            );
+        }
+
+        // Register the footnotes part (written by write_footnotes)
+        if !self.footnotes.is_empty() && self.footnotes_target().is_none() {
+            self.relationships.add(
+                "footnotes.xml".to_string(),
+                Relationships::TYPE_FOOTNOTES.to_string(),
+            );
+        }
+
         self.output.clone()
     }
 
@@ -1176,144 +1741,573 @@ impl DocxWriter {
             Block::Literal(literal) => self.generate_literal(literal),
             Block::Admonition(admon) => self.generate_admonition(admon),
             Block::Open(open) => {
-                // Render open block contents (e.g., [slides], [example])
-                for inner in &open.blocks {
-                    self.generate_block(inner);
+                if let Some(title) = &open.title {
+                    self.generate_block_title(title);
+                }
+                if open.role.as_deref() == Some("example") {
+                    self.generate_framed(BlockFrame::Example, &open.blocks);
+                } else {
+                    // Other open blocks (e.g., [slides], [abstract]) are unframed
+                    for inner in &open.blocks {
+                        self.generate_block(inner);
+                    }
                 }
             }
             Block::Sidebar(sidebar) => {
-                // Render sidebar as a styled paragraph block
-                for inner in &sidebar.blocks {
-                    self.generate_block(inner);
+                // Sidebars are shaded boxes with the title inside
+                self.frames.push(BlockFrame::Sidebar);
+                if let Some(title) = &sidebar.title {
+                    self.generate_block_title(title.trim_start_matches('.'));
                 }
-            }
-            Block::Quote(quote) => {
-                // Render quote block contents
-                for inner in &quote.blocks {
+                for inner in &sidebar.blocks {
                     self.generate_block(inner);
                 }
+                self.frames.pop();
             }
+            Block::Quote(quote) => self.generate_quote(quote),
             Block::ThematicBreak => {
                 // Render as a horizontal rule / page break
                 self.output
                     .push_str("<w:p><w:pPr><w:pBdr><w:bottom w:val=\"single\" w:sz=\"6\" w:space=\"1\" w:color=\"auto\"/></w:pBdr></w:pPr></w:p>\n");
             }
+            Block::Comment(comment) => self.generate_comment(comment),
+        }
+        // Lists continue only across the lists nested in between
+        if !matches!(block, Block::List(_)) {
+            self.list_chain.clear();
         }
     }
 
-    /// Generate XML for a paragraph
-    fn generate_paragraph(&mut self, para: &Paragraph) {
-        self.output.push_str("<w:p>\n");
+    /// Generate the blocks of a redline: the revised blocks, with the
+    /// changes from the original ones as tracked changes
+    fn generate_redline(&mut self, original: &[Block], revised: &[Block]) {
+        for change in redline::diff_blocks(original, revised) {
+            match change {
+                BlockChange::Unchanged(block) => self.generate_block(block),
+                BlockChange::Inserted(block) => {
+                    self.generate_revision(block, RevisionKind::Insertion)
+                }
+                BlockChange::Deleted(block) => {
+                    // Deleted blocks are not in the tables of contents
+                    let contents = std::mem::take(&mut self.contents);
+                    let counters = (self.headings, self.figures, self.tables);
+                    self.generate_revision(block, RevisionKind::Deletion);
+                    (self.headings, self.figures, self.tables) = counters;
+                    self.contents = contents;
+                }
+                BlockChange::Edited(block, revisions) => {
+                    self.inline_revisions = Some(revisions);
+                    self.generate_block(&block);
+                    self.inline_revisions = None;
+                }
+                BlockChange::EditedList(block, revisions) => {
+                    self.list_revisions = Some(revisions);
+                    self.generate_block(&block);
+                    self.list_revisions = None;
+                }
+                BlockChange::EditedTable(block, revisions) => {
+                    self.table_revisions = Some(revisions);
+                    self.generate_block(&block);
+                    self.table_revisions = None;
+                }
+            }
+        }
+    }
 
-        // Paragraph style resolution priority:
-        // 1. Explicit style_id from AST
-        // 2. StyleContract body role mapping (for round-trip fidelity)
-        // 3. StyleMap default (English style IDs)
-        let style: String = para
-            .style_id
-            .clone()
-            .unwrap_or_else(|| self.resolve_paragraph_style("body").to_string());
-        self.output.push_str("<w:pPr>\n");
+    /// Generate a block inserted or deleted as a whole
+    fn generate_revision(&mut self, block: &Block, kind: RevisionKind) {
+        let start = self.output.len();
+        self.generate_block(block);
+        self.mark_revision(start, kind);
+    }
+
+    /// Mark the output generated since `start` as inserted or deleted whole
+    fn mark_revision(&mut self, start: usize, kind: RevisionKind) {
+        let xml = self.output.split_off(start);
+        let marked = redline::mark_block(&xml, kind, &self.reviser, &mut self.next_revision_id);
+        self.output.push_str(&marked);
+    }
+
+    /// Queue a comment block as a Word comment on the next paragraph
+    fn generate_comment(&mut self, comment: &utf8dok_ast::CommentBlock) {
+        if !self.word_comments {
+            return;
+        }
+        let id = self.next_comment_id;
+        self.next_comment_id += 1;
+        self.comments.push(Comment {
+            id,
+            text: comment.text.clone(),
+            author: "utf8dok".to_string(),
+        });
+        self.pending_comments.push((self.output.len(), id));
+    }
+
+    /// Anchor queued Word comments to the first paragraph written after
+    /// each of them
+    ///
+    /// Comments with no paragraph after them get an empty paragraph at the
+    /// end of the output.
+    fn anchor_pending_comments(&mut self) {
+        let pending = std::mem::take(&mut self.pending_comments);
+        let mut trailing = Vec::new();
+        // Last first, so insertions don't move the offsets still to anchor
+        for &(offset, id) in pending.iter().rev() {
+            let Some((content, end)) = paragraph_after(&self.output, offset) else {
+                trailing.push(id);
+                continue;
+            };
+            self.output.insert_str(
+                end,
+                &format!(
+                    "<w:commentRangeEnd w:id=\"{id}\"/>\n<w:r>\n<w:commentReference w:id=\"{id}\"/>\n</w:r>\n"
+                ),
+            );
+            self.output
+                .insert_str(content, &format!("<w:commentRangeStart w:id=\"{id}\"/>\n"));
+        }
+        for id in trailing.into_iter().rev() {
+            self.output.push_str(&format!(
+                "<w:p>\n<w:commentRangeStart w:id=\"{id}\"/>\n<w:commentRangeEnd w:id=\"{id}\"/>\n<w:r>\n<w:commentReference w:id=\"{id}\"/>\n</w:r>\n</w:p>\n"
+            ));
+        }
+    }
+
+    /// Render blocks inside a compound block frame
+    fn generate_framed(&mut self, frame: BlockFrame, blocks: &[Block]) {
+        self.frames.push(frame);
+        for block in blocks {
+            self.generate_block(block);
+        }
+        self.frames.pop();
+    }
+
+    /// Paragraph properties of the innermost compound block frame
+    fn frame_properties(&self) -> &'static str {
+        self.frames
+            .last()
+            .map(|frame| frame.paragraph_properties())
+            .unwrap_or("")
+    }
+
+    /// Generate a bold block title paragraph, kept with the block it names
+    fn generate_block_title(&mut self, title: &str) {
+        let style = self.resolve_paragraph_style("body").to_string();
+        self.output.push_str("<w:p>\n<w:pPr>\n");
         self.output
             .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(&style)));
+        self.output.push_str("<w:keepNext/>\n");
+        self.output.push_str(self.frame_properties());
         self.output.push_str("</w:pPr>\n");
+        self.output.push_str("<w:r>\n<w:rPr>\n<w:b/>\n</w:rPr>\n");
+        self.output
+            .push_str(&format!("<w:t>{}</w:t>\n", escape_xml(title)));
+        self.output.push_str("</w:r>\n</w:p>\n");
+    }
 
-        // Generate runs for inline content
-        for inline in &para.inlines {
-            self.generate_inline(inline);
+    /// Generate XML for a quote block with its attribution
+    fn generate_quote(&mut self, quote: &utf8dok_ast::QuoteBlock) {
+        if let Some(title) = &quote.title {
+            self.generate_block_title(title);
+        }
+        self.frames.push(BlockFrame::Quote);
+        for inner in &quote.blocks {
+            self.generate_block(inner);
         }
 
-        self.output.push_str("</w:p>\n");
+        // Attribution line: "— Author, Source", right-aligned
+        let attribution = [quote.attribution.as_deref(), quote.cite.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !attribution.is_empty() {
+            let style = self.resolve_paragraph_style("body").to_string();
+            self.output.push_str("<w:p>\n<w:pPr>\n");
+            self.output
+                .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(&style)));
+            self.output.push_str(self.frame_properties());
+            self.output.push_str("<w:jc w:val=\"right\"/>\n");
+            self.output.push_str("</w:pPr>\n");
+            self.output.push_str("<w:r>\n<w:rPr>\n<w:i/>\n</w:rPr>\n");
+            self.output.push_str(&format!(
+                "<w:t>\u{2014} {}</w:t>\n",
+                escape_xml(&attribution)
+            ));
+            self.output.push_str("</w:r>\n</w:p>\n");
+        }
+        self.frames.pop();
     }
 
-    /// Generate XML for a heading
-    fn generate_heading(&mut self, heading: &Heading) {
+    /// Generate XML for a paragraph
+    fn generate_paragraph(&mut self, para: &Paragraph) {
+        // The anchor of a figure or table is bookmarked on its caption number
+        if let [Inline::Anchor(id)] = para.inlines.as_slice() {
+            if self.contents.reference(id).is_some() {
+                return;
+            }
+        }
+
         self.output.push_str("<w:p>\n");
 
-        // Heading style resolution priority:
+        // Paragraph style resolution priority:
         // 1. Explicit style_id from AST
-        // 2. StyleContract heading level mapping (for round-trip fidelity)
+        // 2. StyleContract body role mapping (for round-trip fidelity)
         // 3. StyleMap default (English style IDs)
-        let style: String = heading
+        let style: String = para
             .style_id
             .clone()
-            .unwrap_or_else(|| self.resolve_heading_style(heading.level).to_string());
-
+            .unwrap_or_else(|| self.resolve_paragraph_style("body").to_string());
+        let figure_title = toc::figure_title(para);
         self.output.push_str("<w:pPr>\n");
         self.output
             .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(&style)));
+        if figure_title.is_some() {
+            self.output.push_str("<w:keepNext/>\n");
+        }
+        self.output.push_str(self.frame_properties());
         self.output.push_str("</w:pPr>\n");
 
-        // Generate runs for heading text
-        for inline in &heading.text {
-            self.generate_inline(inline);
-        }
+        // Generate runs for inline content
+        self.generate_inlines(&para.inlines);
 
         self.output.push_str("</w:p>\n");
-    }
 
-    /// Generate XML for a list
-    fn generate_list(&mut self, list: &List) {
-        for item in &list.items {
-            self.generate_list_item(item, &list.list_type, list.style_id.as_deref());
+        // Block images with a title are figures, captioned below
+        if let Some(title) = figure_title {
+            self.figures += 1;
+            let bookmark = self.contents.figure_bookmark(self.figures);
+            let title = [Inline::Text(title.to_string())];
+            self.generate_caption(toc::FIGURE_LABEL, self.figures, &title, bookmark, false);
         }
     }
 
-    /// Generate XML for a list item
-    fn generate_list_item(
+    /// Generate a caption paragraph numbered by a SEQ field, such as
+    /// `Figure 2. Title`, bookmarked for the lists of figures and tables
+    ///
+    /// The label and number of an anchored caption get a bookmark of their
+    /// own, the target of the REF fields of cross-references.
+    fn generate_caption(
         &mut self,
-        item: &ListItem,
-        list_type: &ListType,
+        label: &str,
+        number: usize,
+        title: &[Inline],
+        bookmark: Option<String>,
+        keep_next: bool,
+    ) {
+        let style = self
+            .style_map
+            .get(crate::styles::ElementType::Caption)
+            .to_string();
+        self.output.push_str("<w:p>\n<w:pPr>\n");
+        self.output
+            .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(&style)));
+        if keep_next {
+            self.output.push_str("<w:keepNext/>\n");
+        }
+        self.output.push_str(self.frame_properties());
+        self.output.push_str("</w:pPr>\n");
+
+        let bookmark = bookmark.map(|name| (self.next_bookmark_id(), name));
+        if let Some((id, name)) = &bookmark {
+            self.output.push_str(&format!(
+                "<w:bookmarkStart w:id=\"{}\" w:name=\"{}\"/>\n",
+                id,
+                escape_xml(name)
+            ));
+        }
+        let anchor = self
+            .contents
+            .caption_anchor(label, number)
+            .map(|id| self.resolve_anchor_name(id))
+            .map(|name| (self.next_bookmark_id(), name));
+        if let Some((id, name)) = &anchor {
+            self.output.push_str(&format!(
+                "<w:bookmarkStart w:id=\"{}\" w:name=\"{}\"/>\n",
+                id,
+                escape_xml(name)
+            ));
+        }
+        self.output.push_str(&format!(
+            "<w:r>\n<w:t xml:space=\"preserve\">{} </w:t>\n</w:r>\n",
+            escape_xml(label)
+        ));
+        self.output.push_str(&toc::field(
+            &format!("SEQ {} \\* ARABIC", label),
+            &number.to_string(),
+        ));
+        if let Some((id, _)) = anchor {
+            self.output
+                .push_str(&format!("<w:bookmarkEnd w:id=\"{}\"/>\n", id));
+        }
+        self.output
+            .push_str("<w:r>\n<w:t xml:space=\"preserve\">. </w:t>\n</w:r>\n");
+        for inline in title {
+            self.generate_inline(inline);
+        }
+        if let Some((id, _)) = bookmark {
+            self.output
+                .push_str(&format!("<w:bookmarkEnd w:id=\"{}\"/>\n", id));
+        }
+        self.output.push_str("</w:p>\n");
+    }
+
+    /// Generate XML for a heading
+    fn generate_heading(&mut self, heading: &Heading) {
+        self.output.push_str("<w:p>\n");
+
+        // Heading style resolution priority:
+        // 1. Explicit style_id from AST
+        // 2. StyleContract heading level mapping (for round-trip fidelity)
+        // 3. StyleMap default (English style IDs)
+        let style: String = heading
+            .style_id
+            .clone()
+            .unwrap_or_else(|| self.resolve_heading_style(heading.level).to_string());
+
+        self.output.push_str("<w:pPr>\n");
+        self.output
+            .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(&style)));
+        self.output.push_str("</w:pPr>\n");
+
+        // Headings are bookmarked with their section id, as in the other
+        // formats, and listed ones as targets of the TOC entries
+        self.headings += 1;
+        let section = self
+            .contents
+            .section_id(self.headings)
+            .map(|id| self.resolve_anchor_name(&id));
+        let toc = self.contents.heading_bookmark(self.headings);
+        let bookmarks: Vec<(usize, String)> = [toc, section]
+            .into_iter()
+            .flatten()
+            .map(|name| (self.next_bookmark_id(), name))
+            .collect();
+        for (id, name) in &bookmarks {
+            self.output.push_str(&format!(
+                "<w:bookmarkStart w:id=\"{}\" w:name=\"{}\"/>\n",
+                id,
+                escape_xml(name)
+            ));
+        }
+
+        // Generate runs for heading text
+        self.generate_inlines(&heading.text);
+
+        for (id, _) in bookmarks.iter().rev() {
+            self.output
+                .push_str(&format!("<w:bookmarkEnd w:id=\"{}\"/>\n", id));
+        }
+
+        self.output.push_str("</w:p>\n");
+    }
+
+    /// Generate XML for a list
+    ///
+    /// The items of an edited list in a redline are marked as inserted,
+    /// deleted or edited.
+    fn generate_list(&mut self, list: &List) {
+        let revisions = self.list_revisions.take().unwrap_or_default();
+        let base = self.list_base_level;
+        let top = base + list.items.iter().map(|item| item.level).min().unwrap_or(0);
+
+        // Bullets and numbers come from numbering definitions; checkboxes
+        // and callout badges are written as labels
+        let numbered = match list.list_type {
+            ListType::Ordered => true,
+            ListType::Unordered => list.items.iter().any(|item| item.checked.is_none()),
+            ListType::Callout | ListType::Description => false,
+        };
+        let num_id = numbered.then(|| self.list_num_id(list, top));
+
+        // Item counters per nesting level, for callout numbers
+        let mut counters: Vec<usize> = Vec::new();
+        for (index, item) in list.items.iter().enumerate() {
+            let level = item.level as usize;
+            counters.resize(level + 1, 0);
+            counters[level] += 1;
+
+            let label = match (&list.list_type, item.checked) {
+                (ListType::Unordered, Some(checked)) => {
+                    Some(if checked { "\u{2612}" } else { "\u{2610}" }.to_string())
+                }
+                (ListType::Callout, _) => Some(counters[level].to_string()),
+                _ => None,
+            };
+            let start = self.output.len();
+            let whole = match revisions.get(index).cloned().flatten() {
+                Some(ItemRevision::Whole(kind)) => Some(kind),
+                Some(ItemRevision::Edited(inlines)) => {
+                    self.inline_revisions = Some(inlines);
+                    None
+                }
+                None => None,
+            };
+            self.generate_list_item(
+                item,
+                &list.list_type,
+                list.style_id.as_deref(),
+                label,
+                num_id,
+            );
+            self.inline_revisions = None;
+            if let Some(kind) = whole {
+                self.mark_revision(start, kind);
+            }
+        }
+    }
+
+    /// The numbering instance of a list whose outermost items are at `top`
+    ///
+    /// A list continues the numbering of an open list of the same type at
+    /// the same level, as when a nested list of another type interrupts
+    /// it. Other lists get a definition of their own, so they restart.
+    fn list_num_id(&mut self, list: &List, top: u8) -> u32 {
+        self.list_chain.retain(|chained| chained.level <= top);
+        if let Some(chained) = self.list_chain.last() {
+            if chained.level == top && chained.list_type == list.list_type && list.start.is_none() {
+                return chained.num_id;
+            }
+        }
+        self.list_chain.retain(|chained| chained.level < top);
+
+        let top = top.min(numbering::LEVELS - 1);
+        let num_id = self
+            .numbering
+            .add_list(&list.list_type, top, list.numbering, list.start);
+        self.list_chain.push(ChainedList {
+            level: top,
+            list_type: list.list_type.clone(),
+            num_id,
+        });
+        num_id
+    }
+
+    /// Generate XML for a list item
+    fn generate_list_item(
+        &mut self,
+        item: &ListItem,
+        list_type: &ListType,
         style_id: Option<&str>,
+        label: Option<String>,
+        num_id: Option<u32>,
     ) {
-        // Each list item becomes a paragraph with list properties
+        // Use style_id if provided, otherwise use style_map
+        let style = style_id
+            .unwrap_or_else(|| match list_type {
+                ListType::Unordered => self.style_map.list(false),
+                ListType::Ordered | ListType::Callout => self.style_map.list(true),
+                ListType::Description => {
+                    self.resolve_element_style(crate::styles::ElementType::ListDescription)
+                }
+            })
+            .to_string();
+        let level = (self.list_base_level + item.level).min(numbering::LEVELS - 1);
+        let indent = 720 * (level as u32 + 1);
+
+        // Description terms are bold paragraphs kept with their definition
+        if let Some(term) = &item.term {
+            self.output.push_str("<w:p>\n<w:pPr>\n");
+            self.output
+                .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(&style)));
+            self.output.push_str("<w:keepNext/>\n");
+            self.output
+                .push_str(&format!("<w:ind w:left=\"{}\"/>\n", indent - 720));
+            self.output.push_str("</w:pPr>\n");
+            for inline in term {
+                self.generate_formatted_inline(&FormatType::Bold, inline);
+            }
+            self.output.push_str("</w:p>\n");
+        }
+
+        let mut marker_pending = item.term.is_none();
         for block in &item.content {
-            if let Block::Paragraph(para) = block {
-                self.output.push_str("<w:p>\n");
-                self.output.push_str("<w:pPr>\n");
+            let Block::Paragraph(para) = block else {
+                // For non-paragraph content, generate as normal block; nested
+                // lists continue below the item's level
+                let chain = std::mem::take(&mut self.list_chain);
+                let base = std::mem::replace(&mut self.list_base_level, level + 1);
+                self.generate_block(block);
+                self.list_base_level = base;
+                self.list_chain = chain;
+                continue;
+            };
 
-                // Use style_id if provided, otherwise use style_map
-                let style = style_id.unwrap_or_else(|| match list_type {
-                    ListType::Unordered => self.style_map.list(false),
-                    ListType::Ordered => self.style_map.list(true),
-                    ListType::Description => self
-                        .style_map
-                        .get(crate::styles::ElementType::ListDescription),
-                });
-                self.output
-                    .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(style)));
+            self.output.push_str("<w:p>\n");
+            self.output.push_str("<w:pPr>\n");
+            self.output
+                .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(&style)));
 
+            if !marker_pending {
+                // Definitions and continuation paragraphs align with the text
+                self.output
+                    .push_str(&format!("<w:ind w:left=\"{}\"/>\n", indent));
+            } else if label.is_some() {
+                // Labelled items hang the label in front of the text
+                self.output.push_str(&format!(
+                    "<w:ind w:left=\"{}\" w:hanging=\"360\"/>\n",
+                    indent
+                ));
+            } else if let Some(num_id) = num_id {
                 // List numbering properties
                 self.output.push_str("<w:numPr>\n");
                 self.output
-                    .push_str(&format!("<w:ilvl w:val=\"{}\"/>\n", item.level));
-                // numId would need to reference numbering.xml; use 1 as default
-                let num_id = match list_type {
-                    ListType::Unordered => 1,
-                    ListType::Ordered => 2,
-                    ListType::Description => 3,
-                };
+                    .push_str(&format!("<w:ilvl w:val=\"{}\"/>\n", level));
                 self.output
                     .push_str(&format!("<w:numId w:val=\"{}\"/>\n", num_id));
                 self.output.push_str("</w:numPr>\n");
+            } else {
+                self.output
+                    .push_str(&format!("<w:ind w:left=\"{}\"/>\n", indent));
+            }
 
-                self.output.push_str("</w:pPr>\n");
+            self.output.push_str("</w:pPr>\n");
 
-                // Generate runs for content
-                for inline in &para.inlines {
-                    self.generate_inline(inline);
+            if marker_pending {
+                match (&label, list_type) {
+                    (Some(number), ListType::Callout) => {
+                        // Badge bookmarked as the target of the code callout
+                        let id = self.next_bookmark_id();
+                        self.output.push_str(&format!(
+                            "<w:bookmarkStart w:id=\"{}\" w:name=\"_Callout{}_{}\"/>\n",
+                            id, self.callout_blocks, number
+                        ));
+                        self.output.push_str(&callout_badge(number));
+                        self.output
+                            .push_str(&format!("<w:bookmarkEnd w:id=\"{}\"/>\n", id));
+                        self.output.push_str("<w:r>\n<w:tab/>\n</w:r>\n");
+                    }
+                    (Some(label), _) => {
+                        self.output.push_str(&format!(
+                            "<w:r>\n<w:t>{}</w:t>\n<w:tab/>\n</w:r>\n",
+                            escape_xml(label)
+                        ));
+                    }
+                    (None, _) => {}
                 }
-
-                self.output.push_str("</w:p>\n");
-            } else {
-                // For non-paragraph content, generate as normal block
-                self.generate_block(block);
             }
+            marker_pending = false;
+
+            // Generate runs for content
+            self.generate_inlines(&para.inlines);
+
+            self.output.push_str("</w:p>\n");
         }
     }
 
     /// Generate XML for a table
+    ///
+    /// The rows of an edited table in a redline are marked as inserted or
+    /// deleted, or have the changes to their cells marked.
     fn generate_table(&mut self, table: &Table) {
+        let revisions = self.table_revisions.take().unwrap_or_default();
+        // Captioned tables are numbered, with the caption kept above
+        if let Some(caption) = &table.caption {
+            self.tables += 1;
+            let bookmark = self.contents.table_bookmark(self.tables);
+            self.generate_caption(toc::TABLE_LABEL, self.tables, caption, bookmark, true);
+        }
+
         self.output.push_str("<w:tbl>\n");
 
         // Table properties - use style_id or mapped table style
@@ -1328,17 +2322,39 @@ impl DocxWriter {
             .push_str("<w:tblW w:w=\"5000\" w:type=\"pct\"/>\n");
         self.output.push_str("</w:tblPr>\n");
 
-        // Table grid (column definitions)
-        if !table.columns.is_empty() {
+        // Table grid (column definitions), sized by relative column widths
+        let layout = table_layout(table);
+        let grid_columns = layout
+            .iter()
+            .map(|slots| slots.iter().map(|slot| slot.colspan()).sum::<u32>())
+            .max()
+            .unwrap_or(0) as usize;
+        if grid_columns > 0 {
+            let widths: Vec<u32> = (0..grid_columns)
+                .map(|i| {
+                    table
+                        .columns
+                        .get(i)
+                        .and_then(|column| column.width)
+                        .unwrap_or(1)
+                        .max(1)
+                })
+                .collect();
+            let total: u32 = widths.iter().sum();
             self.output.push_str("<w:tblGrid>\n");
-            for _col in &table.columns {
-                self.output.push_str("<w:gridCol w:w=\"2000\"/>\n");
+            for width in widths {
+                self.output.push_str(&format!(
+                    "<w:gridCol w:w=\"{}\"/>\n",
+                    TABLE_WIDTH_TWIPS * width / total
+                ));
             }
             self.output.push_str("</w:tblGrid>\n");
         }
 
         // Table rows
-        for row in &table.rows {
+        for (index, (row, slots)) in table.rows.iter().zip(&layout).enumerate() {
+            let revision = revisions.get(index).cloned().flatten();
+            let start = self.output.len();
             self.output.push_str("<w:tr>\n");
 
             // Row properties for header rows
@@ -1349,7 +2365,22 @@ impl DocxWriter {
             }
 
             // Cells
-            for cell in &row.cells {
+            let mut cells = 0;
+            for slot in slots {
+                let cell = match slot {
+                    GridSlot::Cell(cell) => cell,
+                    GridSlot::Merged { colspan } => {
+                        // Continuation of a cell spanning rows from above
+                        self.output.push_str("<w:tc>\n<w:tcPr>\n");
+                        if *colspan > 1 {
+                            self.output
+                                .push_str(&format!("<w:gridSpan w:val=\"{}\"/>\n", colspan));
+                        }
+                        self.output
+                            .push_str("<w:vMerge/>\n</w:tcPr>\n<w:p/>\n</w:tc>\n");
+                        continue;
+                    }
+                };
                 self.output.push_str("<w:tc>\n");
 
                 // Cell properties
@@ -1363,13 +2394,23 @@ impl DocxWriter {
                 }
                 self.output.push_str("</w:tcPr>\n");
 
-                // Cell content (blocks)
-                for block in &cell.content {
-                    self.generate_block(block);
+                // Cell content (blocks), redlined against the original
+                // content of an edited row
+                match &revision {
+                    Some(RowRevision::Edited(originals)) => {
+                        let original = originals.get(cells).map_or(&[][..], Vec::as_slice);
+                        self.generate_redline(original, &cell.content);
+                    }
+                    _ => {
+                        for block in &cell.content {
+                            self.generate_block(block);
+                        }
+                    }
                 }
+                cells += 1;
 
-                // Ensure at least one paragraph in cell
-                if cell.content.is_empty() {
+                // A cell must end with a paragraph (also after a nested table)
+                if cell.content.is_empty() || matches!(cell.content.last(), Some(Block::Table(_))) {
                     self.output.push_str("<w:p/>\n");
                 }
 
@@ -1377,6 +2418,9 @@ impl DocxWriter {
             }
 
             self.output.push_str("</w:tr>\n");
+            if let Some(RowRevision::Whole(kind)) = revision {
+                self.mark_revision(start, kind);
+            }
         }
 
         self.output.push_str("</w:tbl>\n");
@@ -1394,9 +2438,8 @@ impl DocxWriter {
                 // Section break requires sectPr
                 self.output.push_str("</w:r>\n");
                 self.output.push_str("<w:pPr>\n");
-                self.output.push_str("<w:sectPr>\n");
-                self.output.push_str("<w:type w:val=\"nextPage\"/>\n");
-                self.output.push_str("</w:sectPr>\n");
+                let section = self.section_properties("<w:type w:val=\"nextPage\"/>\n");
+                self.output.push_str(&section);
                 self.output.push_str("</w:pPr>\n");
                 self.output.push_str("</w:p>\n");
                 return;
@@ -1408,6 +2451,20 @@ impl DocxWriter {
 
     /// Generate XML for a literal/code block
     fn generate_literal(&mut self, literal: &utf8dok_ast::LiteralBlock) {
+        // Passthrough blocks: raw WordprocessingML is inserted as-is,
+        // anything else (e.g. HTML for other backends) is shown as code
+        if literal.style_id.as_deref() == Some("pass") {
+            if literal.content.trim_start().starts_with("<w:") {
+                self.output.push_str(&literal.content);
+                self.output.push('\n');
+                return;
+            }
+            return self.generate_literal(&utf8dok_ast::LiteralBlock {
+                style_id: None,
+                ..literal.clone()
+            });
+        }
+
         // Check if this is a diagram block
         if let Some(style) = &literal.style_id {
             let style_lower = style.to_lowercase();
@@ -1420,12 +2477,14 @@ impl DocxWriter {
             }
         }
 
-        // Regular code block rendering - use style_map
+        // Regular code block rendering - use style_map (`....` literal
+        // blocks are code blocks too)
         self.output.push_str("<w:p>\n");
         self.output.push_str("<w:pPr>\n");
         let style = literal
             .style_id
             .as_deref()
+            .filter(|&style| style != "literal")
             .unwrap_or_else(|| self.style_map.code_block());
         self.output
             .push_str(&format!("<w:pStyle w:val=\"{}\"/>\n", escape_xml(style)));
@@ -1448,17 +2507,21 @@ impl DocxWriter {
             None
         };
 
-        // Generate the content as a run with preserved whitespace
-        self.output.push_str("<w:r>\n");
-        self.output.push_str("<w:rPr>\n");
-        self.output
-            .push_str("<w:rFonts w:ascii=\"Courier New\" w:hAnsi=\"Courier New\"/>\n");
-        self.output.push_str("</w:rPr>\n");
-        self.output.push_str(&format!(
-            "<w:t xml:space=\"preserve\">{}</w:t>\n",
-            escape_xml(&literal.content)
-        ));
-        self.output.push_str("</w:r>\n");
+        if literal.callouts.is_empty() && literal.highlight_lines.is_empty() {
+            // Generate the content as a run with preserved whitespace
+            self.output.push_str("<w:r>\n");
+            self.output.push_str("<w:rPr>\n");
+            self.output
+                .push_str("<w:rFonts w:ascii=\"Courier New\" w:hAnsi=\"Courier New\"/>\n");
+            self.output.push_str("</w:rPr>\n");
+            self.output.push_str(&format!(
+                "<w:t xml:space=\"preserve\">{}</w:t>\n",
+                escape_xml(&literal.content)
+            ));
+            self.output.push_str("</w:r>\n");
+        } else {
+            self.generate_code_lines(literal);
+        }
 
         // Close comment range if we added one
         if let Some(id) = comment_id {
@@ -1473,6 +2536,42 @@ impl DocxWriter {
         self.output.push_str("</w:p>\n");
     }
 
+    /// Generate code line by line, highlighting lines and ending them with
+    /// callout badges that link to the callout list
+    fn generate_code_lines(&mut self, literal: &utf8dok_ast::LiteralBlock) {
+        if !literal.callouts.is_empty() {
+            self.callout_blocks += 1;
+        }
+        for (index, line) in literal.content.split('\n').enumerate() {
+            let number = index as u32 + 1;
+            if index > 0 {
+                self.output.push_str("<w:r>\n<w:br/>\n</w:r>\n");
+            }
+            self.output.push_str("<w:r>\n");
+            self.output.push_str("<w:rPr>\n");
+            self.output
+                .push_str("<w:rFonts w:ascii=\"Courier New\" w:hAnsi=\"Courier New\"/>\n");
+            if literal.highlight_lines.contains(&number) {
+                self.output.push_str("<w:highlight w:val=\"yellow\"/>\n");
+            }
+            self.output.push_str("</w:rPr>\n");
+            self.output.push_str(&format!(
+                "<w:t xml:space=\"preserve\">{}</w:t>\n",
+                escape_xml(line)
+            ));
+            self.output.push_str("</w:r>\n");
+
+            for callout in literal.callouts.iter().filter(|c| c.line == number) {
+                self.output.push_str(&format!(
+                    "<w:r>\n<w:t xml:space=\"preserve\"> </w:t>\n</w:r>\n<w:hyperlink w:anchor=\"_Callout{}_{}\">\n{}</w:hyperlink>\n",
+                    self.callout_blocks,
+                    callout.number,
+                    callout_badge(&callout.number.to_string())
+                ));
+            }
+        }
+    }
+
     /// Generate a diagram as an embedded image
     ///
     /// Returns true if successful, false if rendering failed
@@ -1625,9 +2724,27 @@ impl DocxWriter {
             self.output.push_str("</w:p>\n");
         }
 
-        // Content blocks
+        // Content blocks; paragraphs take the admonition's paragraph style
+        let element = match admonition.admonition_type {
+            utf8dok_ast::AdmonitionType::Note => crate::styles::ElementType::AdmonitionNote,
+            utf8dok_ast::AdmonitionType::Tip => crate::styles::ElementType::AdmonitionTip,
+            utf8dok_ast::AdmonitionType::Important => {
+                crate::styles::ElementType::AdmonitionImportant
+            }
+            utf8dok_ast::AdmonitionType::Warning => crate::styles::ElementType::AdmonitionWarning,
+            utf8dok_ast::AdmonitionType::Caution => crate::styles::ElementType::AdmonitionCaution,
+        };
+        let style = self.resolve_element_style(element).to_string();
         for block in &admonition.content {
-            self.generate_block(block);
+            match block {
+                Block::Paragraph(para) if para.style_id.is_none() => {
+                    self.generate_paragraph(&Paragraph {
+                        style_id: Some(style.clone()),
+                        ..para.clone()
+                    })
+                }
+                _ => self.generate_block(block),
+            }
         }
     }
 
@@ -1649,7 +2766,21 @@ impl DocxWriter {
                 }
             }
             Inline::Link(link) => {
-                if link.url.starts_with('#') {
+                let target = link.url.strip_prefix('#');
+                let reference = target.and_then(|id| self.contents.reference(id));
+                if let (Some(id), Some((label, number))) = (target, reference) {
+                    // Cross-reference to a figure or table: a REF field to the
+                    // caption number, showing "Figure N" unless given a text
+                    let default_text = [Inline::Text(id.to_string())];
+                    let text = if link.text.is_empty() || link.text == default_text {
+                        format!("{} {}", label, number)
+                    } else {
+                        link.text.iter().map(extract_text).collect()
+                    };
+                    let bookmark = self.resolve_anchor_name(id);
+                    self.output
+                        .push_str(&toc::field(&format!("REF {} \\h", bookmark), &text));
+                } else if link.url.starts_with('#') {
                     // Internal link (cross-reference): use w:hyperlink with w:anchor
                     // Use StyleContract to restore original anchor name if available
                     let semantic_anchor = &link.url[1..]; // Strip the leading #
@@ -1712,7 +2843,77 @@ impl DocxWriter {
                 self.output
                     .push_str(&format!("<w:bookmarkEnd w:id=\"{}\"/>\n", bookmark_id));
             }
+            Inline::Footnote(footnote) => {
+                self.generate_footnote(footnote);
+            }
+        }
+    }
+
+    /// Generate runs for the inlines of a paragraph or heading
+    ///
+    /// The inlines of an edited block in a redline are marked as inserted
+    /// or deleted.
+    fn generate_inlines(&mut self, inlines: &[Inline]) {
+        let revisions = self.inline_revisions.take().unwrap_or_default();
+        for (index, inline) in inlines.iter().enumerate() {
+            let Some(kind) = revisions.get(index).copied().flatten() else {
+                self.generate_inline(inline);
+                continue;
+            };
+            let start = self.output.len();
+            self.generate_inline(inline);
+            let runs = self.output.split_off(start);
+            let marked = redline::mark_runs(&runs, kind, &self.reviser, &mut self.next_revision_id);
+            self.output.push_str(&marked);
+        }
+    }
+
+    /// Generate XML for a footnote reference, collecting the footnote text
+    ///
+    /// A footnote referring back to an earlier one by id repeats its number
+    /// as superscript text, since Word allows one reference per footnote.
+    fn generate_footnote(&mut self, footnote: &Footnote) {
+        if let Some(&id) = footnote
+            .id
+            .as_ref()
+            .and_then(|id| self.footnote_ids.get(id))
+        {
+            if footnote.content.is_empty() {
+                self.output.push_str("<w:r>\n");
+                self.output.push_str("<w:rPr>\n");
+                self.output
+                    .push_str("<w:vertAlign w:val=\"superscript\"/>\n");
+                self.output.push_str("</w:rPr>\n");
+                self.output.push_str(&format!("<w:t>{}</w:t>\n", id));
+                self.output.push_str("</w:r>\n");
+                return;
+            }
         }
+
+        let id = self.footnotes.len() + 1;
+        if let Some(ref name) = footnote.id {
+            self.footnote_ids.insert(name.clone(), id);
+        }
+
+        // Render the footnote text into its own buffer, with hyperlink
+        // relationships going to footnotes.xml.rels
+        let document_output = std::mem::take(&mut self.output);
+        std::mem::swap(&mut self.relationships, &mut self.footnote_relationships);
+        for inline in &footnote.content {
+            self.generate_inline(inline);
+        }
+        std::mem::swap(&mut self.relationships, &mut self.footnote_relationships);
+        let runs = std::mem::replace(&mut self.output, document_output);
+        self.footnotes.push(FootnoteEntry { id, runs });
+
+        self.output.push_str("<w:r>\n");
+        self.output.push_str("<w:rPr>\n");
+        self.output
+            .push_str("<w:vertAlign w:val=\"superscript\"/>\n");
+        self.output.push_str("</w:rPr>\n");
+        self.output
+            .push_str(&format!("<w:footnoteReference w:id=\"{}\"/>\n", id));
+        self.output.push_str("</w:r>\n");
     }
 
     /// Generate XML for formatted inline content
@@ -1900,11 +3101,12 @@ fn extract_text(inline: &Inline) -> String {
         Inline::Image(image) => image.alt.clone().unwrap_or_default(),
         Inline::Break => String::new(),
         Inline::Anchor(_) => String::new(), // Anchors have no text content
+        Inline::Footnote(_) => String::new(), // Footnote text lives in footnotes.xml
     }
 }
 
 /// Escape special XML characters
-fn escape_xml(text: &str) -> String {
+pub(crate) fn escape_xml(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -1912,12 +3114,118 @@ fn escape_xml(text: &str) -> String {
         .replace('\'', "&apos;")
 }
 
+/// Offsets of the content and closing tag of the first paragraph in
+/// `xml` at or after `offset`
+///
+/// The content starts after the paragraph properties.
+fn paragraph_after(xml: &str, offset: usize) -> Option<(usize, usize)> {
+    let rest = &xml[offset..];
+    let start = offset
+        + [rest.find("<w:p>"), rest.find("<w:p ")]
+            .into_iter()
+            .flatten()
+            .min()?;
+    let mut content = start + xml[start..].find('>')? + 1;
+    let end = content + xml[content..].find("</w:p>")?;
+    let after_open = xml[content..end].trim_start();
+    if after_open.starts_with("<w:pPr>") {
+        if let Some(properties) = xml[content..end].find("</w:pPr>") {
+            content += properties + "</w:pPr>".len();
+        }
+    }
+    Some((content, end))
+}
+
+/// Run with a callout number as a white-on-dark badge
+fn callout_badge(number: &str) -> String {
+    format!(
+        "<w:r>\n<w:rPr>\n<w:b/>\n<w:color w:val=\"FFFFFF\"/>\n<w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"404040\"/>\n</w:rPr>\n<w:t xml:space=\"preserve\"> {} </w:t>\n</w:r>\n",
+        escape_xml(number)
+    )
+}
+
+/// Width of the table grid in twips (6.25" of text width)
+const TABLE_WIDTH_TWIPS: u32 = 9000;
+
+/// A grid position in a table row
+enum GridSlot<'a> {
+    /// A cell starting in this row
+    Cell(&'a TableCell),
+    /// Covered by a cell spanning rows from above
+    Merged { colspan: u32 },
+}
+
+impl GridSlot<'_> {
+    fn colspan(&self) -> u32 {
+        match self {
+            Self::Cell(cell) => cell.colspan.max(1),
+            Self::Merged { colspan } => *colspan,
+        }
+    }
+}
+
+/// Lay out table rows on the grid, adding the continuation slots that
+/// Word needs below cells spanning several rows
+fn table_layout(table: &Table) -> Vec<Vec<GridSlot<'_>>> {
+    // Per grid column: rows still covered from above, and the colspan of
+    // the covering cell at its first column (0 for its other columns)
+    let mut covered: Vec<u32> = Vec::new();
+    let mut spans: Vec<u32> = Vec::new();
+    let mut layout = Vec::new();
+
+    for row in &table.rows {
+        let mut slots = Vec::new();
+        let mut cells = row.cells.iter();
+        let mut column = 0;
+        loop {
+            if covered.get(column).is_some_and(|&rows| rows > 0) {
+                let colspan = spans[column].max(1);
+                slots.push(GridSlot::Merged { colspan });
+                column += colspan as usize;
+                continue;
+            }
+            let Some(cell) = cells.next() else {
+                // Only covered columns may follow the last cell
+                match covered[column.min(covered.len())..]
+                    .iter()
+                    .position(|&rows| rows > 0)
+                {
+                    Some(offset) => {
+                        column += offset;
+                        continue;
+                    }
+                    None => break,
+                }
+            };
+            let colspan = cell.colspan.max(1) as usize;
+            if cell.rowspan > 1 {
+                if covered.len() < column + colspan {
+                    covered.resize(column + colspan, 0);
+                    spans.resize(column + colspan, 0);
+                }
+                covered[column..column + colspan].fill(cell.rowspan);
+                spans[column..column + colspan].fill(0);
+                spans[column] = colspan as u32;
+            }
+            slots.push(GridSlot::Cell(cell));
+            column += colspan;
+        }
+
+        for rows in covered.iter_mut() {
+            *rows = rows.saturating_sub(1);
+        }
+        layout.push(slots);
+    }
+    layout
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_utils::create_minimal_template;
     use std::collections::HashMap;
     use std::io::{Cursor, Write};
+    use utf8dok_ast::NumberingStyle;
 
     #[test]
     fn test_write_basic_doc() {
@@ -1933,6 +3241,7 @@ mod tests {
                     text: vec![Inline::Text("Hello World".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![
@@ -1947,6 +3256,7 @@ mod tests {
                     attributes: HashMap::new(),
                 }),
             ],
+            ..Default::default()
         };
 
         // Generate DOCX without diagram rendering
@@ -2002,6 +3312,7 @@ mod tests {
                         })],
                         level: 0,
                         term: None,
+                        checked: None,
                     },
                     ListItem {
                         content: vec![Block::Paragraph(Paragraph {
@@ -2011,10 +3322,14 @@ mod tests {
                         })],
                         level: 0,
                         term: None,
+                        checked: None,
                     },
                 ],
                 style_id: None,
+                numbering: None,
+                start: None,
             })],
+            ..Default::default()
         };
 
         let result = DocxWriter::generate_with_options(&doc, &template, false);
@@ -2025,172 +3340,908 @@ mod tests {
         let archive = OoxmlArchive::from_reader(cursor).unwrap();
         let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
 
-        assert!(doc_xml.contains("First item"));
-        assert!(doc_xml.contains("Second item"));
-        assert!(doc_xml.contains("w:numPr"));
+        assert!(doc_xml.contains("First item"));
+        assert!(doc_xml.contains("Second item"));
+        assert!(doc_xml.contains("w:numPr"));
+    }
+
+    #[test]
+    fn test_write_table() {
+        let template = create_minimal_template();
+
+        let doc = Document {
+            metadata: utf8dok_ast::DocumentMeta::default(),
+            intent: None,
+            blocks: vec![Block::Table(Table {
+                rows: vec![
+                    utf8dok_ast::TableRow {
+                        cells: vec![
+                            utf8dok_ast::TableCell {
+                                content: vec![Block::Paragraph(Paragraph {
+                                    inlines: vec![Inline::Text("Header 1".to_string())],
+                                    style_id: None,
+                                    attributes: HashMap::new(),
+                                })],
+                                colspan: 1,
+                                rowspan: 1,
+                                align: None,
+                                style: None,
+                            },
+                            utf8dok_ast::TableCell {
+                                content: vec![Block::Paragraph(Paragraph {
+                                    inlines: vec![Inline::Text("Header 2".to_string())],
+                                    style_id: None,
+                                    attributes: HashMap::new(),
+                                })],
+                                colspan: 1,
+                                rowspan: 1,
+                                align: None,
+                                style: None,
+                            },
+                        ],
+                        is_header: true,
+                        is_footer: false,
+                    },
+                    utf8dok_ast::TableRow {
+                        cells: vec![
+                            utf8dok_ast::TableCell {
+                                content: vec![Block::Paragraph(Paragraph {
+                                    inlines: vec![Inline::Text("Cell 1".to_string())],
+                                    style_id: None,
+                                    attributes: HashMap::new(),
+                                })],
+                                colspan: 1,
+                                rowspan: 1,
+                                align: None,
+                                style: None,
+                            },
+                            utf8dok_ast::TableCell {
+                                content: vec![Block::Paragraph(Paragraph {
+                                    inlines: vec![Inline::Text("Cell 2".to_string())],
+                                    style_id: None,
+                                    attributes: HashMap::new(),
+                                })],
+                                colspan: 1,
+                                rowspan: 1,
+                                align: None,
+                                style: None,
+                            },
+                        ],
+                        is_header: false,
+                        is_footer: false,
+                    },
+                ],
+                style_id: Some("TableGrid".to_string()),
+                caption: None,
+                columns: vec![],
+            })],
+            ..Default::default()
+        };
+
+        let result = DocxWriter::generate_with_options(&doc, &template, false);
+        assert!(result.is_ok());
+
+        let output = result.unwrap();
+        let cursor = Cursor::new(&output);
+        let archive = OoxmlArchive::from_reader(cursor).unwrap();
+        let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
+
+        assert!(doc_xml.contains("<w:tbl>"));
+        assert!(doc_xml.contains("Header 1"));
+        assert!(doc_xml.contains("Cell 2"));
+        assert!(doc_xml.contains("<w:tblHeader/>"));
+    }
+
+    #[test]
+    fn test_write_formatted_text() {
+        let template = create_minimal_template();
+
+        let doc = Document {
+            metadata: utf8dok_ast::DocumentMeta::default(),
+            intent: None,
+            blocks: vec![Block::Paragraph(Paragraph {
+                inlines: vec![
+                    Inline::Format(FormatType::Bold, Box::new(Inline::Text("bold".to_string()))),
+                    Inline::Text(" ".to_string()),
+                    Inline::Format(
+                        FormatType::Italic,
+                        Box::new(Inline::Text("italic".to_string())),
+                    ),
+                    Inline::Text(" ".to_string()),
+                    Inline::Format(
+                        FormatType::Monospace,
+                        Box::new(Inline::Text("mono".to_string())),
+                    ),
+                ],
+                style_id: None,
+                attributes: HashMap::new(),
+            })],
+            ..Default::default()
+        };
+
+        let result = DocxWriter::generate_with_options(&doc, &template, false);
+        assert!(result.is_ok());
+
+        let output = result.unwrap();
+        let cursor = Cursor::new(&output);
+        let archive = OoxmlArchive::from_reader(cursor).unwrap();
+        let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
+
+        assert!(doc_xml.contains("<w:b/>"));
+        assert!(doc_xml.contains("<w:i/>"));
+        assert!(doc_xml.contains("Courier New"));
+    }
+
+    #[test]
+    fn test_external_hyperlink_with_relationship() {
+        let template = create_minimal_template();
+
+        let doc = Document {
+            metadata: utf8dok_ast::DocumentMeta::default(),
+            intent: None,
+            blocks: vec![Block::Paragraph(Paragraph {
+                inlines: vec![Inline::Link(utf8dok_ast::Link {
+                    url: "https://example.com".to_string(),
+                    text: vec![Inline::Text("Example".to_string())],
+                })],
+                style_id: None,
+                attributes: HashMap::new(),
+            })],
+            ..Default::default()
+        };
+
+        let result = DocxWriter::generate_with_options(&doc, &template, false);
+        assert!(result.is_ok());
+
+        let output = result.unwrap();
+        let cursor = Cursor::new(&output);
+        let archive = OoxmlArchive::from_reader(cursor).unwrap();
+
+        // Check document.xml has hyperlink with r:id
+        let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
+        assert!(doc_xml.contains("r:id=\"rId"), "Hyperlink should have r:id");
+        assert!(doc_xml.contains("Example"), "Link text should be present");
+
+        // Check relationships file has the hyperlink
+        let rels_xml = archive
+            .get_string("word/_rels/document.xml.rels")
+            .unwrap()
+            .unwrap();
+        assert!(
+            rels_xml.contains("https://example.com"),
+            "Relationship should have URL"
+        );
+        assert!(
+            rels_xml.contains("hyperlink"),
+            "Relationship type should be hyperlink"
+        );
+    }
+
+    #[test]
+    fn test_footnotes_part_with_relationships() {
+        let template = create_minimal_template();
+
+        let doc = Document {
+            metadata: utf8dok_ast::DocumentMeta::default(),
+            intent: None,
+            blocks: vec![Block::Paragraph(Paragraph {
+                inlines: vec![
+                    Inline::Text("Claim".to_string()),
+                    Inline::Footnote(Footnote {
+                        id: Some("src".to_string()),
+                        content: vec![
+                            Inline::Text("See ".to_string()),
+                            Inline::Link(utf8dok_ast::Link {
+                                url: "https://example.com/spec".to_string(),
+                                text: vec![Inline::Text("the spec".to_string())],
+                            }),
+                        ],
+                    }),
+                    Inline::Text(" and again".to_string()),
+                    Inline::Footnote(Footnote {
+                        id: Some("src".to_string()),
+                        content: vec![],
+                    }),
+                ],
+                style_id: None,
+                attributes: HashMap::new(),
+            })],
+            ..Default::default()
+        };
+
+        let output = DocxWriter::generate_with_options(&doc, &template, false).unwrap();
+        let archive = OoxmlArchive::from_reader(Cursor::new(&output)).unwrap();
+
+        // One reference in the document; the reuse repeats the number
+        let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
+        assert_eq!(
+            doc_xml.matches("<w:footnoteReference w:id=\"1\"/>").count(),
+            1
+        );
+        assert!(doc_xml.contains("<w:t>1</w:t>"));
+        assert!(!doc_xml.contains("the spec"));
+
+        let footnotes_xml = archive.get_string("word/footnotes.xml").unwrap().unwrap();
+        assert!(footnotes_xml.contains("w:type=\"separator\" w:id=\"-1\""));
+        assert!(footnotes_xml.contains("<w:footnote w:id=\"1\">"));
+        assert!(footnotes_xml.contains("<w:footnoteRef/>"));
+        assert!(footnotes_xml.contains("the spec"));
+
+        // The hyperlink inside the footnote belongs to the footnotes part
+        let footnote_rels = archive
+            .get_string("word/_rels/footnotes.xml.rels")
+            .unwrap()
+            .unwrap();
+        assert!(footnote_rels.contains("https://example.com/spec"));
+
+        let rels_xml = archive
+            .get_string("word/_rels/document.xml.rels")
+            .unwrap()
+            .unwrap();
+        assert!(rels_xml.contains(Relationships::TYPE_FOOTNOTES));
+        assert!(!rels_xml.contains("https://example.com/spec"));
+
+        let content_types = archive.get_string("[Content_Types].xml").unwrap().unwrap();
+        assert!(content_types.contains("/word/footnotes.xml"));
+    }
+
+    #[test]
+    fn test_compound_blocks_are_framed() {
+        let template = create_minimal_template();
+        let para = |text: &str| {
+            Block::Paragraph(Paragraph {
+                inlines: vec![Inline::Text(text.to_string())],
+                style_id: None,
+                attributes: HashMap::new(),
+            })
+        };
+
+        let doc = Document {
+            metadata: utf8dok_ast::DocumentMeta::default(),
+            intent: None,
+            blocks: vec![
+                Block::Open(utf8dok_ast::OpenBlock {
+                    title: Some("Sample".to_string()),
+                    blocks: vec![para("In an example")],
+                    ..utf8dok_ast::OpenBlock::with_role("example")
+                }),
+                Block::Sidebar(utf8dok_ast::Sidebar {
+                    title: None,
+                    blocks: vec![para("In a sidebar")],
+                }),
+                Block::Quote(utf8dok_ast::QuoteBlock {
+                    blocks: vec![para("In a quote")],
+                    attribution: Some("Ada Lovelace".to_string()),
+                    cite: Some("Notes".to_string()),
+                    title: None,
+                    verse: false,
+                }),
+                para("Outside"),
+            ],
+            ..Default::default()
+        };
+
+        let output = DocxWriter::generate_with_options(&doc, &template, false).unwrap();
+        let archive = OoxmlArchive::from_reader(Cursor::new(&output)).unwrap();
+        let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
+
+        let paragraph_with = |text: &str| {
+            let end = doc_xml.find(text).unwrap();
+            let start = doc_xml[..end].rfind("<w:p>").unwrap();
+            doc_xml[start..end].to_string()
+        };
+
+        assert!(paragraph_with("Sample").contains("<w:keepNext/>"));
+        assert!(paragraph_with("In an example").contains("<w:top w:val=\"single\""));
+        assert!(paragraph_with("In a sidebar").contains("w:fill=\"F2F2F2\""));
+        assert!(paragraph_with("In a quote").contains("<w:ind w:left=\"720\""));
+        assert!(paragraph_with("\u{2014} Ada Lovelace, Notes").contains("<w:jc w:val=\"right\"/>"));
+        assert!(!paragraph_with("Outside").contains("<w:pBdr>"));
+    }
+
+    #[test]
+    fn test_toc_fields_and_captions() {
+        let template = create_minimal_template();
+        let heading = |level: u8, text: &str| {
+            Block::Heading(Heading {
+                level,
+                text: vec![Inline::Text(text.to_string())],
+                style_id: None,
+                anchor: None,
+                role: None,
+            })
+        };
+        let mut figure_attributes = HashMap::new();
+        figure_attributes.insert("title".to_string(), "Overview".to_string());
+        let mut metadata = utf8dok_ast::DocumentMeta::default();
+        for name in ["toc", "toc-figures", "toc-tables"] {
+            metadata.attributes.insert(name.to_string(), String::new());
+        }
+        let doc = Document {
+            metadata,
+            intent: None,
+            blocks: vec![
+                heading(1, "Intro"),
+                heading(3, "Too deep"),
+                Block::Paragraph(Paragraph {
+                    inlines: vec![Inline::Image(utf8dok_ast::Image {
+                        src: "missing.png".to_string(),
+                        alt: None,
+                    })],
+                    style_id: None,
+                    attributes: figure_attributes,
+                }),
+                Block::Table(Table {
+                    rows: vec![utf8dok_ast::TableRow {
+                        cells: vec![TableCell {
+                            content: vec![Block::Paragraph(Paragraph {
+                                inlines: vec![Inline::Text("cell".to_string())],
+                                style_id: None,
+                                attributes: HashMap::new(),
+                            })],
+                            colspan: 1,
+                            rowspan: 1,
+                            align: None,
+                            style: None,
+                        }],
+                        is_header: false,
+                        is_footer: false,
+                    }],
+                    style_id: None,
+                    caption: Some(vec![Inline::Text("Ports".to_string())]),
+                    columns: Vec::new(),
+                }),
+            ],
+            ..Default::default()
+        };
+
+        let output = DocxWriter::generate_with_options(&doc, &template, false).unwrap();
+        let doc_xml = crate::test_utils::extract_document_xml(&output);
+
+        // The lists come first, with entries linked to bookmarked targets
+        let intro_entry = doc_xml
+            .find("<w:hyperlink w:anchor=\"_TocHeading1\"")
+            .unwrap();
+        let intro_heading = doc_xml.find("w:name=\"_TocHeading1\"").unwrap();
+        assert!(intro_entry < intro_heading);
+        assert!(doc_xml.contains(" TOC \\o &quot;1-2&quot; \\h \\z \\u "));
+        assert!(!doc_xml.contains("_TocHeading2"));
+        assert!(doc_xml.contains("<w:t xml:space=\"preserve\">Figure 1. Overview</w:t>"));
+        assert!(doc_xml.contains("w:name=\"_TocFigure1\""));
+        assert!(doc_xml.contains("<w:t xml:space=\"preserve\">Table 1. Ports</w:t>"));
+        assert!(doc_xml.contains("w:name=\"_TocTable1\""));
+
+        // Captions are numbered by SEQ fields; the table caption comes first
+        assert!(doc_xml.contains(" SEQ Figure \\* ARABIC "));
+        let table_caption = doc_xml.rfind(" SEQ Table \\* ARABIC ").unwrap();
+        assert!(table_caption < doc_xml.find("<w:tbl>").unwrap());
+        assert!(doc_xml.find(" SEQ Figure").unwrap() > doc_xml.find("<w:drawing>").unwrap_or(0));
+    }
+
+    #[test]
+    fn test_cross_references_to_figures_and_tables() {
+        let template = create_minimal_template();
+        let paragraph = |inlines: Vec<Inline>| {
+            Block::Paragraph(Paragraph {
+                inlines,
+                style_id: None,
+                attributes: HashMap::new(),
+            })
+        };
+        let figure = |title: &str| {
+            let mut attributes = HashMap::new();
+            attributes.insert("title".to_string(), title.to_string());
+            Block::Paragraph(Paragraph {
+                inlines: vec![Inline::Image(utf8dok_ast::Image {
+                    src: "missing.png".to_string(),
+                    alt: None,
+                })],
+                style_id: None,
+                attributes,
+            })
+        };
+        let xref = |id: &str, text: &str| {
+            Inline::Link(utf8dok_ast::Link {
+                url: format!("#{}", id),
+                text: vec![Inline::Text(text.to_string())],
+            })
+        };
+        let doc = Document {
+            metadata: Default::default(),
+            intent: None,
+            blocks: vec![
+                figure("Overview"),
+                paragraph(vec![Inline::Anchor("fig-arch".to_string())]),
+                figure("Architecture"),
+                paragraph(vec![Inline::Anchor("tab-ports".to_string())]),
+                Block::Table(Table {
+                    rows: Vec::new(),
+                    style_id: None,
+                    caption: Some(vec![Inline::Text("Ports".to_string())]),
+                    columns: Vec::new(),
+                }),
+                paragraph(vec![
+                    xref("fig-arch", "fig-arch"),
+                    xref("tab-ports", "the ports"),
+                    xref("other", "other"),
+                ]),
+            ],
+            ..Default::default()
+        };
+
+        let output = DocxWriter::generate_with_options(&doc, &template, false).unwrap();
+        let doc_xml = crate::test_utils::extract_document_xml(&output);
+
+        // The anchors bookmark the caption numbers instead of empty paragraphs
+        let bookmark = doc_xml.find("w:name=\"fig-arch\"").unwrap();
+        let number = doc_xml.rfind(" SEQ Figure \\* ARABIC ").unwrap();
+        assert!(bookmark < number);
+        assert!(
+            doc_xml[number..].find("<w:t>2</w:t>").unwrap()
+                < doc_xml[number..].find("<w:bookmarkEnd").unwrap()
+        );
+        assert!(
+            doc_xml.find("w:name=\"tab-ports\"").unwrap() < doc_xml.find(" SEQ Table").unwrap()
+        );
+        assert_eq!(doc_xml.matches("<w:p>").count(), 6);
+
+        // Cross-references become REF fields showing the caption label and number
+        assert!(doc_xml.contains(" REF fig-arch \\h "));
+        assert!(doc_xml.contains("<w:t>Figure 2</w:t>"));
+        assert!(doc_xml.contains(" REF tab-ports \\h "));
+        assert!(doc_xml.contains("<w:t>the ports</w:t>"));
+        assert!(doc_xml.contains("<w:hyperlink w:anchor=\"other\">"));
+    }
+
+    #[test]
+    fn test_headers_and_footers_from_style_contract() {
+        use crate::style_map::HeaderFooterConfig;
+        use crate::test_utils::{create_template_with_styles, extract_file};
+
+        let mut metadata = utf8dok_ast::DocumentMeta {
+            title: Some("Design Notes".to_string()),
+            ..Default::default()
+        };
+        metadata
+            .attributes
+            .insert("classification".to_string(), "Internal".to_string());
+        let doc = Document {
+            metadata,
+            intent: None,
+            blocks: vec![
+                Block::Paragraph(Paragraph {
+                    inlines: vec![Inline::Text("One".to_string())],
+                    style_id: None,
+                    attributes: HashMap::new(),
+                }),
+                Block::Break(utf8dok_ast::BreakType::Section),
+                Block::Paragraph(Paragraph {
+                    inlines: vec![Inline::Text("Two".to_string())],
+                    style_id: None,
+                    attributes: HashMap::new(),
+                }),
+            ],
+            ..Default::default()
+        };
+
+        let mut contract = StyleContract::new();
+        contract.header = Some(HeaderFooterConfig {
+            content: Some("{title}\t\t{classification}".to_string()),
+            first: Some(String::new()),
+            ..Default::default()
+        });
+        contract.footer = Some(HeaderFooterConfig {
+            content: Some("Page {page} of {pages}".to_string()),
+            even: Some("{pages}".to_string()),
+            ..Default::default()
+        });
+        let mut writer = DocxWriter::new();
+        writer.set_style_contract(contract);
+        let template = Template::from_bytes(&create_template_with_styles()).unwrap();
+        let output = writer.generate_with_template(&doc, template).unwrap();
+
+        // Both sections reference every variant; the footer gets a first
+        // page variant and the header an even page variant from `content`
+        let doc_xml = crate::test_utils::extract_document_xml(&output);
+        assert_eq!(doc_xml.matches("<w:sectPr>").count(), 2);
+        assert_eq!(doc_xml.matches("<w:headerReference ").count(), 6);
+        assert_eq!(doc_xml.matches("<w:footerReference ").count(), 6);
+        assert_eq!(doc_xml.matches("<w:titlePg/>").count(), 2);
+        assert!(doc_xml.contains("<w:type w:val=\"nextPage\"/>\n<w:titlePg/>"));
+        assert!(doc_xml
+            .trim_end()
+            .ends_with("</w:sectPr>\n</w:body>\n</w:document>"));
+
+        let rels = extract_file(&output, "word/_rels/document.xml.rels").unwrap();
+        let content_types = extract_file(&output, "[Content_Types].xml").unwrap();
+        for kind in ["header", "footer"] {
+            for index in 1..=3 {
+                let target = format!("{}{}.xml", kind, index);
+                assert!(rels.contains(&format!("Target=\"{}\"", target)));
+                assert!(content_types.contains(&format!("PartName=\"/word/{}\"", target)));
+            }
+        }
+
+        let header = extract_file(&output, "word/header1.xml").unwrap();
+        assert!(header.contains(">Design Notes</w:t>"));
+        assert!(header.contains(">Internal</w:t>"));
+        let empty_first = extract_file(&output, "word/header2.xml").unwrap();
+        assert!(!empty_first.contains("<w:t"));
+        let footer = extract_file(&output, "word/footer1.xml").unwrap();
+        assert!(footer.contains(" PAGE "));
+        assert!(footer.contains(" NUMPAGES "));
+
+        // Even pages are turned on in a new settings part
+        let settings = extract_file(&output, "word/settings.xml").unwrap();
+        assert!(settings.contains("<w:evenAndOddHeaders/>"));
+        assert!(rels.contains("Target=\"settings.xml\""));
+    }
+
+    #[test]
+    fn test_redline_against_original() {
+        let paragraph = |text: &str| {
+            Block::Paragraph(Paragraph {
+                inlines: vec![Inline::Text(text.to_string())],
+                style_id: None,
+                attributes: HashMap::new(),
+            })
+        };
+        let heading = |text: &str| {
+            Block::Heading(Heading {
+                level: 1,
+                text: vec![Inline::Text(text.to_string())],
+                style_id: None,
+                anchor: None,
+                role: None,
+            })
+        };
+        let document = |blocks: Vec<Block>| Document {
+            metadata: Default::default(),
+            intent: None,
+            blocks,
+            ..Default::default()
+        };
+        let original = document(vec![
+            heading("Setup"),
+            paragraph("The service runs slowly."),
+            paragraph("Removed entirely."),
+        ]);
+        let mut revised = document(vec![
+            heading("Setup"),
+            paragraph("The service runs fast."),
+            heading("Appendix"),
+        ]);
+        revised.metadata.authors.push("Jane Doe".to_string());
+        revised
+            .metadata
+            .attributes
+            .insert("revdate".to_string(), "2024-05-01".to_string());
+
+        let mut writer = DocxWriter::new();
+        writer.set_redline(original);
+        let template =
+            Template::from_bytes(&crate::test_utils::create_template_with_styles()).unwrap();
+        let output = writer.generate_with_template(&revised, template).unwrap();
+        let doc_xml = crate::test_utils::extract_document_xml(&output);
+
+        // Edited words are tracked in place
+        assert!(doc_xml.contains("<w:t>The service runs </w:t>"));
+        assert!(doc_xml.contains(
+            "<w:del w:id=\"1\" w:author=\"Jane Doe\" w:date=\"2024-05-01T00:00:00Z\"><w:r>\n<w:delText>slowly.</w:delText>\n</w:r></w:del>"
+        ));
+        assert!(doc_xml.contains(
+            "<w:ins w:id=\"2\" w:author=\"Jane Doe\" w:date=\"2024-05-01T00:00:00Z\"><w:r>\n<w:t>fast.</w:t>\n</w:r></w:ins>"
+        ));
+
+        // Replaced blocks are deleted and inserted whole, paragraph marks too
+        let deleted = doc_xml
+            .find("<w:delText>Removed entirely.</w:delText>")
+            .unwrap();
+        let inserted = doc_xml.find("<w:t>Appendix</w:t>").unwrap();
+        assert!(deleted < inserted);
+        assert_eq!(doc_xml.matches("<w:rPr><w:del ").count(), 1);
+        assert_eq!(doc_xml.matches("<w:rPr><w:ins ").count(), 1);
+        assert_eq!(doc_xml.matches("<w:t>Setup</w:t>").count(), 1);
+    }
+
+    #[test]
+    fn test_redline_lists_item_by_item() {
+        let list = |items: &[&str]| Document {
+            blocks: vec![Block::List(List {
+                list_type: ListType::Ordered,
+                items: items
+                    .iter()
+                    .map(|text| ListItem {
+                        content: vec![Block::Paragraph(Paragraph {
+                            inlines: vec![Inline::Text(text.to_string())],
+                            style_id: None,
+                            attributes: HashMap::new(),
+                        })],
+                        level: 0,
+                        term: None,
+                        checked: None,
+                    })
+                    .collect(),
+                style_id: None,
+                numbering: None,
+                start: None,
+            })],
+            ..Default::default()
+        };
+        let original = list(&["Install it", "Configure the service", "Obsolete step"]);
+        let revised = list(&["Install it", "Configure the new service", "Restart"]);
+
+        let mut writer = DocxWriter::new();
+        writer.set_redline(original);
+        let template =
+            Template::from_bytes(&crate::test_utils::create_template_with_styles()).unwrap();
+        let output = writer.generate_with_template(&revised, template).unwrap();
+        let doc_xml = crate::test_utils::extract_document_xml(&output);
+
+        // One numbered list: kept and edited items are unmarked, the edit
+        // is tracked in place, and replaced items are deleted and inserted
+        assert_eq!(doc_xml.matches("<w:numPr>").count(), 4);
+        assert_eq!(doc_xml.matches("<w:numId w:val=\"1\"/>").count(), 4);
+        assert!(doc_xml.contains("<w:t>Install it</w:t>"));
+        assert!(doc_xml.contains("<w:t>Configure the </w:t>"));
+        assert!(doc_xml.contains("<w:t>new </w:t>\n</w:r></w:ins>"));
+        assert!(doc_xml.contains("<w:delText>Obsolete step</w:delText>"));
+        assert!(doc_xml.contains("<w:t>Restart</w:t>\n</w:r></w:ins>"));
+        assert_eq!(doc_xml.matches("<w:rPr><w:del ").count(), 1);
+        assert_eq!(doc_xml.matches("<w:rPr><w:ins ").count(), 1);
     }
 
     #[test]
-    fn test_write_table() {
-        let template = create_minimal_template();
-
-        let doc = Document {
-            metadata: utf8dok_ast::DocumentMeta::default(),
-            intent: None,
+    fn test_redline_tables_row_by_row() {
+        let table = |value: &str| Document {
             blocks: vec![Block::Table(Table {
-                rows: vec![
-                    utf8dok_ast::TableRow {
-                        cells: vec![
-                            utf8dok_ast::TableCell {
-                                content: vec![Block::Paragraph(Paragraph {
-                                    inlines: vec![Inline::Text("Header 1".to_string())],
-                                    style_id: None,
-                                    attributes: HashMap::new(),
-                                })],
-                                colspan: 1,
-                                rowspan: 1,
-                                align: None,
-                            },
-                            utf8dok_ast::TableCell {
-                                content: vec![Block::Paragraph(Paragraph {
-                                    inlines: vec![Inline::Text("Header 2".to_string())],
-                                    style_id: None,
-                                    attributes: HashMap::new(),
-                                })],
-                                colspan: 1,
-                                rowspan: 1,
-                                align: None,
-                            },
-                        ],
-                        is_header: true,
-                    },
-                    utf8dok_ast::TableRow {
-                        cells: vec![
-                            utf8dok_ast::TableCell {
-                                content: vec![Block::Paragraph(Paragraph {
-                                    inlines: vec![Inline::Text("Cell 1".to_string())],
-                                    style_id: None,
-                                    attributes: HashMap::new(),
-                                })],
-                                colspan: 1,
-                                rowspan: 1,
-                                align: None,
-                            },
-                            utf8dok_ast::TableCell {
+                rows: [["port", value], ["host", "localhost"]]
+                    .iter()
+                    .map(|cells| utf8dok_ast::TableRow {
+                        cells: cells
+                            .iter()
+                            .map(|text| TableCell {
                                 content: vec![Block::Paragraph(Paragraph {
-                                    inlines: vec![Inline::Text("Cell 2".to_string())],
+                                    inlines: vec![Inline::Text(text.to_string())],
                                     style_id: None,
                                     attributes: HashMap::new(),
                                 })],
                                 colspan: 1,
                                 rowspan: 1,
                                 align: None,
-                            },
-                        ],
+                                style: None,
+                            })
+                            .collect(),
                         is_header: false,
-                    },
-                ],
-                style_id: Some("TableGrid".to_string()),
+                        is_footer: false,
+                    })
+                    .collect(),
+                style_id: None,
                 caption: None,
-                columns: vec![],
+                columns: Vec::new(),
             })],
+            ..Default::default()
         };
 
-        let result = DocxWriter::generate_with_options(&doc, &template, false);
-        assert!(result.is_ok());
-
-        let output = result.unwrap();
-        let cursor = Cursor::new(&output);
-        let archive = OoxmlArchive::from_reader(cursor).unwrap();
-        let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
-
-        assert!(doc_xml.contains("<w:tbl>"));
-        assert!(doc_xml.contains("Header 1"));
-        assert!(doc_xml.contains("Cell 2"));
-        assert!(doc_xml.contains("<w:tblHeader/>"));
+        let mut writer = DocxWriter::new();
+        writer.set_redline(table("8080"));
+        let template =
+            Template::from_bytes(&crate::test_utils::create_template_with_styles()).unwrap();
+        let output = writer
+            .generate_with_template(&table("9090"), template)
+            .unwrap();
+        let doc_xml = crate::test_utils::extract_document_xml(&output);
+
+        // One table whose rows stay; only the edited cell is tracked
+        assert_eq!(doc_xml.matches("<w:tbl>").count(), 1);
+        assert_eq!(doc_xml.matches("<w:tr>").count(), 2);
+        assert!(!doc_xml.contains("<w:trPr><w:"));
+        assert!(doc_xml.contains("<w:t>port</w:t>"));
+        assert!(doc_xml.contains("<w:delText>8080</w:delText>"));
+        assert!(doc_xml.contains("<w:t>9090</w:t>\n</w:r></w:ins>"));
+        assert!(doc_xml.contains("<w:t>localhost</w:t>"));
     }
 
     #[test]
-    fn test_write_formatted_text() {
-        let template = create_minimal_template();
-
+    fn test_no_section_properties_without_headers_and_footers() {
         let doc = Document {
-            metadata: utf8dok_ast::DocumentMeta::default(),
+            metadata: Default::default(),
             intent: None,
             blocks: vec![Block::Paragraph(Paragraph {
-                inlines: vec![
-                    Inline::Format(FormatType::Bold, Box::new(Inline::Text("bold".to_string()))),
-                    Inline::Text(" ".to_string()),
-                    Inline::Format(
-                        FormatType::Italic,
-                        Box::new(Inline::Text("italic".to_string())),
-                    ),
-                    Inline::Text(" ".to_string()),
-                    Inline::Format(
-                        FormatType::Monospace,
-                        Box::new(Inline::Text("mono".to_string())),
-                    ),
-                ],
+                inlines: vec![Inline::Text("Plain".to_string())],
                 style_id: None,
                 attributes: HashMap::new(),
             })],
+            ..Default::default()
         };
+        let output =
+            DocxWriter::generate_with_options(&doc, &create_minimal_template(), false).unwrap();
+        let doc_xml = crate::test_utils::extract_document_xml(&output);
+        assert!(!doc_xml.contains("<w:sectPr>"));
+        assert!(crate::test_utils::extract_file(&output, "word/header1.xml").is_none());
+    }
 
-        let result = DocxWriter::generate_with_options(&doc, &template, false);
-        assert!(result.is_ok());
+    #[test]
+    fn test_styled_lists_and_checklists() {
+        let template = create_minimal_template();
+        let item = |text: &str, checked: Option<bool>, content: Vec<Block>| ListItem {
+            content: [
+                vec![Block::Paragraph(Paragraph {
+                    inlines: vec![Inline::Text(text.to_string())],
+                    style_id: None,
+                    attributes: HashMap::new(),
+                })],
+                content,
+            ]
+            .concat(),
+            level: 0,
+            term: None,
+            checked,
+        };
+        let code = Block::Literal(utf8dok_ast::LiteralBlock {
+            content: "make".to_string(),
+            language: None,
+            title: None,
+            style_id: None,
+            callouts: Vec::new(),
+            highlight_lines: Vec::new(),
+        });
 
-        let output = result.unwrap();
-        let cursor = Cursor::new(&output);
-        let archive = OoxmlArchive::from_reader(cursor).unwrap();
+        let doc = Document {
+            metadata: utf8dok_ast::DocumentMeta::default(),
+            intent: None,
+            blocks: vec![
+                Block::List(List {
+                    list_type: ListType::Ordered,
+                    items: vec![
+                        item("First", None, vec![code]),
+                        item("Second", None, vec![]),
+                    ],
+                    style_id: None,
+                    numbering: Some(NumberingStyle::LowerRoman),
+                    start: None,
+                }),
+                Block::List(List {
+                    list_type: ListType::Unordered,
+                    items: vec![
+                        item("Done", Some(true), vec![]),
+                        item("Open", Some(false), vec![]),
+                    ],
+                    style_id: None,
+                    numbering: None,
+                    start: None,
+                }),
+            ],
+            ..Default::default()
+        };
+
+        let output = DocxWriter::generate_with_options(&doc, &template, false).unwrap();
+        let archive = OoxmlArchive::from_reader(Cursor::new(&output)).unwrap();
         let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
 
-        assert!(doc_xml.contains("<w:b/>"));
-        assert!(doc_xml.contains("<w:i/>"));
-        assert!(doc_xml.contains("Courier New"));
+        let numbering_xml = archive.get_string("word/numbering.xml").unwrap().unwrap();
+
+        // Styled numbering is native; checkboxes are labels
+        assert_eq!(doc_xml.matches("<w:numId w:val=\"1\"/>").count(), 2);
+        assert!(numbering_xml.contains("<w:numFmt w:val=\"lowerRoman\"/>"));
+        assert!(!numbering_xml.contains("<w:num w:numId=\"2\">"));
+        assert!(doc_xml.contains("<w:t>\u{2612}</w:t>"));
+        assert!(doc_xml.contains("<w:t>\u{2610}</w:t>"));
+        // The continuation block follows its item, before the next one
+        let code_at = doc_xml.find("make").unwrap();
+        assert!(doc_xml.find("First").unwrap() < code_at);
+        assert!(code_at < doc_xml.find("Second").unwrap());
     }
 
     #[test]
-    fn test_external_hyperlink_with_relationship() {
+    fn test_code_callouts_link_to_callout_list() {
         let template = create_minimal_template();
-
         let doc = Document {
             metadata: utf8dok_ast::DocumentMeta::default(),
             intent: None,
-            blocks: vec![Block::Paragraph(Paragraph {
-                inlines: vec![Inline::Link(utf8dok_ast::Link {
-                    url: "https://example.com".to_string(),
-                    text: vec![Inline::Text("Example".to_string())],
-                })],
+            blocks: vec![
+                Block::Literal(utf8dok_ast::LiteralBlock {
+                    content: "use std::io;\nfn main() {}".to_string(),
+                    language: None,
+                    title: None,
+                    style_id: None,
+                    callouts: vec![utf8dok_ast::Callout { number: 1, line: 2 }],
+                    highlight_lines: vec![1],
+                }),
+                Block::List(List {
+                    list_type: ListType::Callout,
+                    items: vec![ListItem {
+                        content: vec![Block::Paragraph(Paragraph {
+                            inlines: vec![Inline::Text("Entry point".to_string())],
+                            style_id: None,
+                            attributes: HashMap::new(),
+                        })],
+                        level: 0,
+                        term: None,
+                        checked: None,
+                    }],
+                    style_id: None,
+                    numbering: None,
+                    start: None,
+                }),
+            ],
+            ..Default::default()
+        };
+
+        let output = DocxWriter::generate_with_options(&doc, &template, false).unwrap();
+        let archive = OoxmlArchive::from_reader(Cursor::new(&output)).unwrap();
+        let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
+
+        // One run per line, the first one highlighted
+        let first = doc_xml.find("use std::io;").unwrap();
+        let second = doc_xml.find("fn main() {}").unwrap();
+        assert!(doc_xml[..first].contains("<w:highlight w:val=\"yellow\"/>"));
+        assert!(!doc_xml[first..second].contains("<w:highlight"));
+        assert!(doc_xml[first..second].contains("<w:br/>"));
+
+        // The badge after line 2 links to the bookmarked list badge
+        assert!(doc_xml[second..].contains("<w:hyperlink w:anchor=\"_Callout1_1\">"));
+        assert!(doc_xml.contains("w:name=\"_Callout1_1\""));
+        assert_eq!(
+            doc_xml
+                .matches("<w:t xml:space=\"preserve\"> 1 </w:t>")
+                .count(),
+            2
+        );
+        assert!(doc_xml.contains("w:fill=\"404040\""));
+    }
+
+    #[test]
+    fn test_merged_table_cells() {
+        let template = create_minimal_template();
+        let cell = |text: &str, colspan: u32, rowspan: u32| TableCell {
+            content: vec![Block::Paragraph(Paragraph {
+                inlines: vec![Inline::Text(text.to_string())],
                 style_id: None,
                 attributes: HashMap::new(),
             })],
+            colspan,
+            rowspan,
+            align: None,
+            style: None,
+        };
+        let row = |cells: Vec<TableCell>| utf8dok_ast::TableRow {
+            cells,
+            is_header: false,
+            is_footer: false,
         };
 
-        let result = DocxWriter::generate_with_options(&doc, &template, false);
-        assert!(result.is_ok());
-
-        let output = result.unwrap();
-        let cursor = Cursor::new(&output);
-        let archive = OoxmlArchive::from_reader(cursor).unwrap();
+        // | Tall (2x2) | C |
+        // |            | D |
+        // | E | F | G      (G is absent: ragged rows are allowed)
+        let doc = Document {
+            metadata: utf8dok_ast::DocumentMeta::default(),
+            intent: None,
+            blocks: vec![Block::Table(Table {
+                rows: vec![
+                    row(vec![cell("Tall", 2, 2), cell("C", 1, 1)]),
+                    row(vec![cell("D", 1, 1)]),
+                    row(vec![cell("E", 1, 1), cell("F", 1, 1), cell("G", 1, 1)]),
+                ],
+                style_id: None,
+                caption: None,
+                columns: vec![],
+            })],
+            ..Default::default()
+        };
 
-        // Check document.xml has hyperlink with r:id
+        let output = DocxWriter::generate_with_options(&doc, &template, false).unwrap();
+        let archive = OoxmlArchive::from_reader(Cursor::new(&output)).unwrap();
         let doc_xml = archive.get_string("word/document.xml").unwrap().unwrap();
-        assert!(doc_xml.contains("r:id=\"rId"), "Hyperlink should have r:id");
-        assert!(doc_xml.contains("Example"), "Link text should be present");
 
-        // Check relationships file has the hyperlink
-        let rels_xml = archive
-            .get_string("word/_rels/document.xml.rels")
-            .unwrap()
-            .unwrap();
-        assert!(
-            rels_xml.contains("https://example.com"),
-            "Relationship should have URL"
-        );
-        assert!(
-            rels_xml.contains("hyperlink"),
-            "Relationship type should be hyperlink"
-        );
+        assert_eq!(doc_xml.matches("<w:gridCol ").count(), 3);
+        let rows: Vec<&str> = doc_xml.split("<w:tr>").skip(1).collect();
+        assert!(rows[0].contains("<w:gridSpan w:val=\"2\"/>\n<w:vMerge w:val=\"restart\"/>"));
+        // The second row starts with the continuation of the tall cell
+        let continuation = rows[1].find("<w:vMerge/>").unwrap();
+        assert!(rows[1][..continuation].contains("<w:gridSpan w:val=\"2\"/>"));
+        assert!(continuation < rows[1].find(">D<").unwrap());
+        assert!(!rows[2].contains("vMerge"));
     }
 
     #[test]
@@ -2215,7 +4266,10 @@ mod tests {
                 language: None,
                 title: None,
                 style_id: Some("mermaid".to_string()),
+                callouts: Vec::new(),
+                highlight_lines: Vec::new(),
             })],
+            ..Default::default()
         };
 
         // Generate without diagram rendering - should fall back to code block
@@ -2346,6 +4400,7 @@ mod tests {
                     text: vec![Inline::Text("Introduction".to_string())],
                     style_id: None,
                     anchor: Some("sec-intro".to_string()),
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![Inline::Text("This is the first paragraph.".to_string())],
@@ -2357,6 +4412,7 @@ mod tests {
                     text: vec![Inline::Text("Details".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![
@@ -2382,6 +4438,7 @@ mod tests {
                                 colspan: 1,
                                 rowspan: 1,
                                 align: None,
+                                style: None,
                             },
                             utf8dok_ast::TableCell {
                                 content: vec![Block::Paragraph(Paragraph {
@@ -2392,15 +4449,18 @@ mod tests {
                                 colspan: 1,
                                 rowspan: 1,
                                 align: None,
+                                style: None,
                             },
                         ],
                         is_header: true,
+                        is_footer: false,
                     }],
                     style_id: None, // Should use mapped TableGrid
                     caption: None,
                     columns: vec![],
                 }),
             ],
+            ..Default::default()
         };
 
         // Generate using template-based method (without diagrams for test speed)
@@ -2477,7 +4537,9 @@ mod tests {
                 text: vec![Inline::Text("Title".to_string())],
                 style_id: None,
                 anchor: None,
+                role: None,
             })],
+            ..Default::default()
         };
 
         let result = DocxWriter::generate_from_template_with_options(
@@ -2534,6 +4596,7 @@ paragraph = "Normal"
                     text: vec![Inline::Text("My Document".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![Inline::Text("Some content.".to_string())],
@@ -2541,6 +4604,7 @@ paragraph = "Normal"
                     attributes: HashMap::new(),
                 }),
             ],
+            ..Default::default()
         };
 
         // Create writer and set embedded content
@@ -2636,6 +4700,7 @@ paragraph = "Normal"
                 style_id: None,
                 attributes: HashMap::new(),
             })],
+            ..Default::default()
         };
 
         let mut writer = DocxWriter::new();
@@ -2758,6 +4823,7 @@ paragraph = "Normal"
                 style_id: None,
                 attributes: HashMap::new(),
             })],
+            ..Default::default()
         };
 
         let mut writer = DocxWriter::new();
@@ -2784,6 +4850,7 @@ paragraph = "Normal"
                     text: vec![Inline::Text("Target Section".to_string())],
                     style_id: None,
                     anchor: Some("target-section".to_string()),
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![Inline::Link(utf8dok_ast::Link {
@@ -2794,6 +4861,7 @@ paragraph = "Normal"
                     attributes: HashMap::new(),
                 }),
             ],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -2921,6 +4989,7 @@ paragraph = "Normal"
             metadata: meta,
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -2941,6 +5010,7 @@ paragraph = "Normal"
             metadata: meta,
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -2959,6 +5029,7 @@ paragraph = "Normal"
             metadata: meta,
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -2979,6 +5050,7 @@ paragraph = "Normal"
             metadata: meta,
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -2994,6 +5066,7 @@ paragraph = "Normal"
             metadata: utf8dok_ast::DocumentMeta::default(),
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -3018,6 +5091,7 @@ paragraph = "Normal"
             metadata: utf8dok_ast::DocumentMeta::default(),
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -3045,6 +5119,7 @@ paragraph = "Normal"
             metadata: meta,
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -3086,6 +5161,7 @@ paragraph = "Normal"
             metadata: meta,
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -3116,6 +5192,7 @@ paragraph = "Normal"
             metadata: meta,
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -3151,6 +5228,7 @@ paragraph = "Normal"
             metadata: meta,
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let writer = DocxWriter::new();
@@ -3184,6 +5262,7 @@ paragraph = "Normal"
                     ..Default::default()
                 }),
             ],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3214,6 +5293,7 @@ paragraph = "Normal"
                     ..Default::default()
                 }),
             ],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3238,7 +5318,10 @@ paragraph = "Normal"
                 language: Some("rust".to_string()),
                 title: None,
                 style_id: None,
+                callouts: Vec::new(),
+                highlight_lines: Vec::new(),
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3263,7 +5346,10 @@ paragraph = "Normal"
                 language: None,
                 title: Some("Example".to_string()),
                 style_id: None,
+                callouts: Vec::new(),
+                highlight_lines: Vec::new(),
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3289,6 +5375,7 @@ paragraph = "Normal"
                     ..Default::default()
                 })],
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3314,6 +5401,7 @@ paragraph = "Normal"
                     ..Default::default()
                 })],
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3348,6 +5436,7 @@ paragraph = "Normal"
                         ..Default::default()
                     })],
                 })],
+                ..Default::default()
             };
 
             let template = create_minimal_template();
@@ -3360,6 +5449,64 @@ paragraph = "Normal"
         }
     }
 
+    #[test]
+    fn test_admonitions_and_description_lists_use_localised_body_style() {
+        use crate::test_utils::{create_minimal_template, extract_document_xml};
+        use crate::Template;
+        use utf8dok_ast::{Admonition, AdmonitionType, List, ListItem};
+
+        let mut archive =
+            OoxmlArchive::from_reader(Cursor::new(create_minimal_template())).unwrap();
+        archive.set_string(
+            "word/styles.xml",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:style w:type="paragraph" w:styleId="Normale" w:default="1"><w:name w:val="Normale"/></w:style>
+</w:styles>"#
+                .to_string(),
+        );
+        let mut template = Cursor::new(Vec::new());
+        archive.write_to(&mut template).unwrap();
+
+        let paragraph = |text: &str| {
+            Block::Paragraph(Paragraph {
+                inlines: vec![Inline::Text(text.to_string())],
+                ..Default::default()
+            })
+        };
+        let doc = Document {
+            blocks: vec![
+                Block::Admonition(Admonition {
+                    admonition_type: AdmonitionType::Tip,
+                    title: None,
+                    content: vec![paragraph("Tip text")],
+                }),
+                Block::List(List {
+                    list_type: ListType::Description,
+                    items: vec![ListItem {
+                        content: vec![paragraph("Definition")],
+                        level: 0,
+                        term: Some(vec![Inline::Text("Term".to_string())]),
+                        checked: None,
+                    }],
+                    style_id: None,
+                    numbering: None,
+                    start: None,
+                }),
+            ],
+            ..Default::default()
+        };
+        let template = Template::from_bytes(&template.into_inner()).unwrap();
+        let docx = DocxWriter::new()
+            .generate_with_template(&doc, template)
+            .unwrap();
+
+        let doc_xml = extract_document_xml(&docx);
+        assert!(!doc_xml.contains("w:val=\"Normal\""), "{}", doc_xml);
+        // Admonition paragraph, description term and definition
+        assert_eq!(doc_xml.matches("<w:pStyle w:val=\"Normale\"/>").count(), 3);
+    }
+
     #[test]
     fn test_generate_image_inline() {
         use crate::test_utils::create_minimal_template;
@@ -3374,6 +5521,7 @@ paragraph = "Normal"
                 })],
                 ..Default::default()
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3399,6 +5547,7 @@ paragraph = "Normal"
                         })],
                         level: 0,
                         term: None,
+                        checked: None,
                     },
                     ListItem {
                         content: vec![Block::Paragraph(Paragraph {
@@ -3407,6 +5556,7 @@ paragraph = "Normal"
                         })],
                         level: 1,
                         term: None,
+                        checked: None,
                     },
                     ListItem {
                         content: vec![Block::Paragraph(Paragraph {
@@ -3415,10 +5565,14 @@ paragraph = "Normal"
                         })],
                         level: 0,
                         term: None,
+                        checked: None,
                     },
                 ],
                 style_id: None,
+                numbering: None,
+                start: None,
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3447,6 +5601,7 @@ paragraph = "Normal"
                         })],
                         level: 0,
                         term: None,
+                        checked: None,
                     },
                     ListItem {
                         content: vec![Block::Paragraph(Paragraph {
@@ -3455,10 +5610,14 @@ paragraph = "Normal"
                         })],
                         level: 0,
                         term: None,
+                        checked: None,
                     },
                 ],
                 style_id: None,
+                numbering: None,
+                start: None,
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3490,6 +5649,7 @@ paragraph = "Normal"
                                 colspan: 1,
                                 rowspan: 1,
                                 align: None,
+                                style: None,
                             },
                             TableCell {
                                 content: vec![Block::Paragraph(Paragraph {
@@ -3499,9 +5659,11 @@ paragraph = "Normal"
                                 colspan: 1,
                                 rowspan: 1,
                                 align: None,
+                                style: None,
                             },
                         ],
                         is_header: true,
+                        is_footer: false,
                     },
                     TableRow {
                         cells: vec![
@@ -3513,6 +5675,7 @@ paragraph = "Normal"
                                 colspan: 1,
                                 rowspan: 1,
                                 align: None,
+                                style: None,
                             },
                             TableCell {
                                 content: vec![Block::Paragraph(Paragraph {
@@ -3522,14 +5685,17 @@ paragraph = "Normal"
                                 colspan: 1,
                                 rowspan: 1,
                                 align: None,
+                                style: None,
                             },
                         ],
                         is_header: false,
+                        is_footer: false,
                     },
                 ],
                 style_id: None,
                 columns: vec![],
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3554,7 +5720,9 @@ paragraph = "Normal"
                 text: vec![Inline::Text("Section Title".to_string())],
                 anchor: Some("section-title".to_string()),
                 style_id: None,
+                role: None,
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3564,6 +5732,43 @@ paragraph = "Normal"
         assert!(doc_xml.contains("Section Title"));
         // Heading level 2 should have Heading2 style
         assert!(doc_xml.contains("Heading2") || doc_xml.contains("w:pStyle"));
+        assert!(doc_xml.contains("w:name=\"section-title\""));
+    }
+
+    #[test]
+    fn test_headings_bookmarked_with_section_ids() {
+        use crate::test_utils::create_minimal_template;
+
+        let heading = |text: &str| {
+            Block::Heading(Heading {
+                level: 1,
+                text: vec![Inline::Text(text.to_string())],
+                anchor: None,
+                style_id: None,
+                role: None,
+            })
+        };
+        let doc = Document {
+            blocks: vec![
+                heading("Intro Part"),
+                Block::Paragraph(Paragraph {
+                    inlines: vec![Inline::Anchor("custom".to_string())],
+                    ..Default::default()
+                }),
+                heading("Custom"),
+                heading("Intro part"),
+            ],
+            ..Default::default()
+        };
+
+        let template = create_minimal_template();
+        let result = DocxWriter::generate(&doc, &template).unwrap();
+
+        // The ids of the HTML, DocBook and JATS output
+        let doc_xml = crate::test_utils::extract_document_xml(&result);
+        assert!(doc_xml.contains("w:name=\"_intro_part\""));
+        assert!(doc_xml.contains("w:name=\"_intro_part_2\""));
+        assert_eq!(doc_xml.matches("w:name=\"custom\"").count(), 1);
     }
 
     #[test]
@@ -3581,6 +5786,7 @@ paragraph = "Normal"
                 })],
                 ..Default::default()
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3613,6 +5819,7 @@ paragraph = "Normal"
                 ],
                 ..Default::default()
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3640,6 +5847,7 @@ paragraph = "Normal"
                 )],
                 ..Default::default()
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3657,6 +5865,7 @@ paragraph = "Normal"
             metadata: Default::default(),
             intent: None,
             blocks: vec![],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3696,6 +5905,7 @@ paragraph = "Normal"
                 inlines: vec![Inline::Text("Test".to_string())],
                 ..Default::default()
             })],
+            ..Default::default()
         };
 
         let mut writer = DocxWriter::new();
@@ -3935,7 +6145,10 @@ paragraph = "Normal"
                 language: Some("rust".to_string()),
                 title: None,
                 style_id: None,
+                callouts: Vec::new(),
+                highlight_lines: Vec::new(),
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3963,7 +6176,10 @@ paragraph = "Normal"
                 language: None,
                 title: None,
                 style_id: None,
+                callouts: Vec::new(),
+                highlight_lines: Vec::new(),
             })],
+            ..Default::default()
         };
 
         let template = create_minimal_template();
@@ -3994,6 +6210,7 @@ paragraph = "Normal"
                 inlines: vec![Inline::Text("Content".to_string())],
                 ..Default::default()
             })],
+            ..Default::default()
         };
 
         let mut writer = DocxWriter::new();
@@ -4037,6 +6254,7 @@ paragraph = "Normal"
                 inlines: vec![Inline::Text("Body content".to_string())],
                 ..Default::default()
             })],
+            ..Default::default()
         };
 
         let mut writer = DocxWriter::new();
@@ -4051,6 +6269,66 @@ paragraph = "Normal"
         assert!(doc_xml.contains("<w:br w:type=\"page\"/>"));
     }
 
+    #[test]
+    fn test_comment_blocks_are_skipped_by_default() {
+        let doc = Document {
+            blocks: vec![
+                Block::Comment(utf8dok_ast::CommentBlock {
+                    text: "Check the figures".to_string(),
+                    delimited: false,
+                }),
+                Block::Paragraph(Paragraph {
+                    inlines: vec![Inline::Text("Revenue grew.".to_string())],
+                    ..Default::default()
+                }),
+            ],
+            ..Default::default()
+        };
+        let mut writer = DocxWriter::new();
+        let xml = writer.generate_document_xml(&doc);
+        assert!(!xml.contains("Check the figures"));
+        assert!(!xml.contains("commentRangeStart"));
+        assert!(writer.comments.is_empty());
+    }
+
+    #[test]
+    fn test_comment_blocks_become_word_comments() {
+        let comment = |text: &str| {
+            Block::Comment(utf8dok_ast::CommentBlock {
+                text: text.to_string(),
+                delimited: false,
+            })
+        };
+        let doc = Document {
+            blocks: vec![
+                comment("Check the figures"),
+                Block::Heading(Heading {
+                    level: 1,
+                    text: vec![Inline::Text("Results".to_string())],
+                    ..Default::default()
+                }),
+                comment("Trailing"),
+            ],
+            ..Default::default()
+        };
+        let mut writer = DocxWriter::new();
+        writer.set_word_comments(true);
+        let xml = writer.generate_document_xml(&doc);
+
+        // The first comment spans the heading text, after its properties
+        let start = xml.find("<w:commentRangeStart w:id=\"1\"/>").unwrap();
+        let end = xml.find("<w:commentRangeEnd w:id=\"1\"/>").unwrap();
+        assert!(xml.find("</w:pPr>").unwrap() < start);
+        assert!(xml[start..end].contains("Results"));
+        assert!(xml[end..].contains("<w:commentReference w:id=\"1\"/>"));
+
+        // The last one gets a paragraph of its own
+        assert!(xml
+            .contains("<w:p>\n<w:commentRangeStart w:id=\"2\"/>\n<w:commentRangeEnd w:id=\"2\"/>"));
+        let texts: Vec<&str> = writer.comments.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, ["Check the figures", "Trailing"]);
+    }
+
     #[test]
     fn test_next_comment_id_increments() {
         let mut writer = DocxWriter::new();
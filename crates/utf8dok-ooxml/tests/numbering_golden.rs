@@ -0,0 +1,267 @@
+//! Numbering Golden-File Tests
+//!
+//! Verify the `word/numbering.xml` the writer generates for lists against
+//! the golden files in `tests/golden/numbering/`, along with the numbering
+//! properties of the list paragraphs and the package entries of the part.
+//!
+//! Run with `UTF8DOK_UPDATE_GOLDEN=1` to rewrite the golden files after an
+//! intended change.
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use utf8dok_ast::{
+    Block, Document, DocumentMeta, Inline, List, ListItem, ListType, NumberingStyle, Paragraph,
+};
+use utf8dok_ooxml::test_utils::{
+    create_minimal_template, create_template_with_styles, extract_document_xml, extract_file,
+};
+use utf8dok_ooxml::writer::DocxWriter;
+use utf8dok_ooxml::{AsciiDocExtractor, OoxmlArchive};
+
+fn paragraph(text: &str) -> Block {
+    Block::Paragraph(Paragraph {
+        inlines: vec![Inline::Text(text.to_string())],
+        ..Default::default()
+    })
+}
+
+fn item(text: &str, level: u8) -> ListItem {
+    ListItem {
+        content: vec![paragraph(text)],
+        level,
+        term: None,
+        checked: None,
+    }
+}
+
+fn list(list_type: ListType, items: Vec<ListItem>) -> List {
+    List {
+        list_type,
+        items,
+        style_id: None,
+        numbering: None,
+        start: None,
+    }
+}
+
+fn document(blocks: Vec<Block>) -> Document {
+    Document {
+        metadata: DocumentMeta::default(),
+        intent: None,
+        blocks,
+        ..Default::default()
+    }
+}
+
+/// The (ilvl, numId) of each numbered paragraph, in document order
+fn numbering_properties(document_xml: &str) -> Vec<(u32, u32)> {
+    document_xml
+        .split("<w:numPr>")
+        .skip(1)
+        .map(|num_pr| {
+            let value = |element: &str| {
+                let start = num_pr.find(element).unwrap() + element.len();
+                let end = start + num_pr[start..].find('"').unwrap();
+                num_pr[start..end].parse().unwrap()
+            };
+            (value("<w:ilvl w:val=\""), value("<w:numId w:val=\""))
+        })
+        .collect()
+}
+
+/// Compare the generated numbering part with a golden file
+fn assert_golden(name: &str, docx: &[u8]) {
+    let actual = extract_file(docx, "word/numbering.xml").expect("numbering.xml is written");
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/numbering")
+        .join(format!("{}.xml", name));
+
+    if std::env::var_os("UTF8DOK_UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, &actual).unwrap();
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Missing golden file {}: {}", path.display(), e));
+    assert_eq!(actual, expected, "numbering.xml differs from {}", name);
+}
+
+/// A template whose numbering part already defines a bullet list
+fn template_with_numbering() -> Vec<u8> {
+    let mut archive = OoxmlArchive::from_reader(Cursor::new(create_minimal_template())).unwrap();
+    archive.set_string(
+        "word/numbering.xml",
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:abstractNum w:abstractNumId="4"><w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="bullet"/><w:lvlText w:val="-"/></w:lvl></w:abstractNum>
+<w:num w:numId="9"><w:abstractNumId w:val="4"/></w:num>
+</w:numbering>"#
+            .to_string(),
+    );
+    archive.set_string(
+        "word/_rels/document.xml.rels",
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering" Target="numbering.xml"/>
+</Relationships>"#
+            .to_string(),
+    );
+    archive.set_string(
+        "[Content_Types].xml",
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+<Override PartName="/word/numbering.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml"/>
+</Types>"#
+            .to_string(),
+    );
+    let mut output = Cursor::new(Vec::new());
+    archive.write_to(&mut output).unwrap();
+    output.into_inner()
+}
+
+#[test]
+fn test_each_list_restarts() {
+    let doc = document(vec![
+        Block::List(list(
+            ListType::Ordered,
+            vec![item("One", 0), item("Two", 0)],
+        )),
+        paragraph("Between the lists."),
+        Block::List(list(ListType::Ordered, vec![item("One again", 0)])),
+    ]);
+    let docx = DocxWriter::generate(&doc, &create_minimal_template()).unwrap();
+
+    assert_eq!(
+        numbering_properties(&extract_document_xml(&docx)),
+        vec![(0, 1), (0, 1), (0, 2)]
+    );
+    assert_golden("restart", &docx);
+}
+
+#[test]
+fn test_start_offset_and_style() {
+    let mut styled = list(ListType::Ordered, vec![item("Third", 0), item("Fourth", 0)]);
+    styled.numbering = Some(NumberingStyle::LowerAlpha);
+    styled.start = Some(3);
+    let docx = DocxWriter::generate(
+        &document(vec![Block::List(styled)]),
+        &create_minimal_template(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        numbering_properties(&extract_document_xml(&docx)),
+        vec![(0, 1), (0, 1)]
+    );
+    assert_golden("start_offset", &docx);
+}
+
+#[test]
+fn test_mixed_nesting() {
+    // * Bullet
+    // .. Numbered
+    // .. Numbered
+    // * Bullet again
+    // +
+    // . Numbered in the item
+    let mut last = item("Bullet again", 0);
+    last.content.push(Block::List(list(
+        ListType::Ordered,
+        vec![item("Numbered in the item", 0)],
+    )));
+    let doc = document(vec![
+        Block::List(list(ListType::Unordered, vec![item("Bullet", 0)])),
+        Block::List(list(
+            ListType::Ordered,
+            vec![item("Numbered", 1), item("Numbered", 1)],
+        )),
+        Block::List(list(ListType::Unordered, vec![last])),
+    ]);
+    let docx = DocxWriter::generate(&doc, &create_minimal_template()).unwrap();
+
+    // The outer bullet list continues across the nested numbered list
+    assert_eq!(
+        numbering_properties(&extract_document_xml(&docx)),
+        vec![(0, 1), (1, 2), (1, 2), (0, 1), (1, 3)]
+    );
+    assert_golden("mixed_nesting", &docx);
+}
+
+#[test]
+fn test_template_numbering_is_kept() {
+    let doc = document(vec![Block::List(list(
+        ListType::Ordered,
+        vec![item("First", 0)],
+    ))]);
+    let docx = DocxWriter::generate(&doc, &template_with_numbering()).unwrap();
+
+    // New definitions are numbered after the template's
+    assert_eq!(
+        numbering_properties(&extract_document_xml(&docx)),
+        vec![(0, 10)]
+    );
+    assert_golden("template_merge", &docx);
+
+    let rels = extract_file(&docx, "word/_rels/document.xml.rels").unwrap();
+    assert_eq!(rels.matches("numbering.xml").count(), 1);
+    let content_types = extract_file(&docx, "[Content_Types].xml").unwrap();
+    assert_eq!(content_types.matches("/word/numbering.xml").count(), 1);
+}
+
+#[test]
+fn test_numbering_part_is_registered() {
+    let doc = document(vec![Block::List(list(
+        ListType::Unordered,
+        vec![item("Bullet", 0)],
+    ))]);
+    let docx = DocxWriter::generate(&doc, &create_minimal_template()).unwrap();
+
+    let rels = extract_file(&docx, "word/_rels/document.xml.rels").unwrap();
+    assert!(rels.contains(
+        "Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering\""
+    ));
+    assert!(rels.contains("Target=\"numbering.xml\""));
+    let content_types = extract_file(&docx, "[Content_Types].xml").unwrap();
+    assert!(content_types.contains(
+        "<Override PartName=\"/word/numbering.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml\"/>"
+    ));
+}
+
+#[test]
+fn test_documents_without_lists_have_no_numbering_part() {
+    let doc = document(vec![paragraph("No lists here.")]);
+    let docx = DocxWriter::generate(&doc, &create_minimal_template()).unwrap();
+
+    assert_eq!(extract_file(&docx, "word/numbering.xml"), None);
+    let rels = extract_file(&docx, "word/_rels/document.xml.rels").unwrap();
+    assert!(!rels.contains("numbering"));
+}
+
+#[test]
+fn test_extracted_lists_keep_their_numbering() {
+    let mut styled = list(ListType::Ordered, vec![item("Third", 0)]);
+    styled.numbering = Some(NumberingStyle::LowerAlpha);
+    styled.start = Some(3);
+    let doc = document(vec![
+        Block::List(list(ListType::Ordered, vec![item("One", 0)])),
+        Block::List(list(ListType::Unordered, vec![item("Bullet", 1)])),
+        Block::List(list(ListType::Ordered, vec![item("Two", 0)])),
+        Block::List(styled),
+    ]);
+    let docx = DocxWriter::generate(&doc, &create_template_with_styles()).unwrap();
+
+    let archive = OoxmlArchive::from_reader(Cursor::new(docx)).unwrap();
+    let extracted = AsciiDocExtractor::new()
+        .with_force_parse(true)
+        .extract_archive(&archive)
+        .unwrap();
+    assert!(
+        extracted
+            .asciidoc
+            .contains(". One\n** Bullet\n. Two\n\n//\n[loweralpha,start=3]\n. Third\n"),
+        "{}",
+        extracted.asciidoc
+    );
+}
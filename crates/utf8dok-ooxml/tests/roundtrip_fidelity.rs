@@ -683,6 +683,95 @@ mod bookmark_anchor_tests {
     }
 }
 
+mod footnote_tests {
+    //! Footnotes survive AsciiDoc -> DOCX -> AsciiDoc
+
+    use std::collections::HashMap;
+    use utf8dok_ast::{Block, Document, DocumentMeta, Footnote, Inline, Paragraph};
+    use utf8dok_ooxml::test_utils::create_template_with_styles;
+    use utf8dok_ooxml::{AsciiDocExtractor, DocxWriter, OoxmlArchive};
+
+    #[test]
+    fn test_footnote_roundtrip() {
+        let doc = Document {
+            metadata: DocumentMeta::default(),
+            blocks: vec![Block::Paragraph(Paragraph {
+                inlines: vec![
+                    Inline::Text("A claim".into()),
+                    Inline::Footnote(Footnote {
+                        id: None,
+                        content: vec![Inline::Text("Source [1].".into())],
+                    }),
+                ],
+                style_id: None,
+                attributes: HashMap::new(),
+            })],
+            intent: None,
+            ..Default::default()
+        };
+
+        let docx = DocxWriter::generate(&doc, &create_template_with_styles()).unwrap();
+        let archive = OoxmlArchive::from_reader(std::io::Cursor::new(docx)).unwrap();
+        let extracted = AsciiDocExtractor::new()
+            .with_force_parse(true)
+            .extract_archive(&archive)
+            .unwrap();
+
+        assert!(
+            extracted
+                .asciidoc
+                .contains("A claimfootnote:[Source [1\\].]"),
+            "Footnote should be extracted as a macro. Got:\n{}",
+            extracted.asciidoc
+        );
+    }
+}
+
+mod comment_tests {
+    //! Comment blocks survive AsciiDoc -> DOCX (Word comments) -> AsciiDoc
+
+    use std::collections::HashMap;
+    use utf8dok_ast::{Block, CommentBlock, Document, Inline, Paragraph};
+    use utf8dok_ooxml::test_utils::create_template_with_styles;
+    use utf8dok_ooxml::{AsciiDocExtractor, DocxWriter, OoxmlArchive, Template};
+
+    #[test]
+    fn test_word_comment_roundtrip() {
+        let doc = Document {
+            blocks: vec![
+                Block::Comment(CommentBlock {
+                    text: "Check the figures\nwith finance".into(),
+                    delimited: true,
+                }),
+                Block::Paragraph(Paragraph {
+                    inlines: vec![Inline::Text("Revenue grew.".into())],
+                    style_id: None,
+                    attributes: HashMap::new(),
+                }),
+            ],
+            ..Default::default()
+        };
+
+        let mut writer = DocxWriter::new();
+        writer.set_word_comments(true);
+        let template = Template::from_bytes(&create_template_with_styles()).unwrap();
+        let docx = writer.generate_with_template(&doc, template).unwrap();
+        let archive = OoxmlArchive::from_reader(std::io::Cursor::new(docx)).unwrap();
+        let extracted = AsciiDocExtractor::new()
+            .with_force_parse(true)
+            .extract_archive(&archive)
+            .unwrap();
+
+        assert!(
+            extracted
+                .asciidoc
+                .contains("// Check the figures\n// with finance\nRevenue grew.\n"),
+            "Word comment should be extracted as comment lines. Got:\n{}",
+            extracted.asciidoc
+        );
+    }
+}
+
 // =============================================================================
 // PART 7: TEXT BOX TESTS (ALREADY IMPLEMENTED)
 // =============================================================================
@@ -1211,6 +1300,7 @@ mod essential_template_tests {
                     text: vec![Inline::Text("Introduction".into())],
                     anchor: Some("introduction".into()),
                     style_id: None,
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![Inline::Text("This is the introduction paragraph.".into())],
@@ -1221,6 +1311,7 @@ mod essential_template_tests {
                     text: vec![Inline::Text("Background".into())],
                     anchor: Some("background".into()),
                     style_id: None,
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![Inline::Text("Some background information.".into())],
@@ -1228,6 +1319,7 @@ mod essential_template_tests {
                 }),
             ],
             intent: None,
+            ..Default::default()
         };
 
         // Render to DOCX
@@ -1297,6 +1389,7 @@ mod essential_template_tests {
                     text: vec![Inline::Text("Test Heading".into())],
                     anchor: None,
                     style_id: None, // Let StyleContract resolve this
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![Inline::Text("Test paragraph.".into())],
@@ -1305,6 +1398,7 @@ mod essential_template_tests {
                 }),
             ],
             intent: None,
+            ..Default::default()
         };
 
         // Render with StyleContract
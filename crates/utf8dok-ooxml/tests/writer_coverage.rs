@@ -37,8 +37,10 @@ fn test_write_complex_table_with_colspan_rowspan() {
                         colspan: 2, // Spans 2 columns
                         rowspan: 1,
                         align: None,
+                        style: None,
                     }],
                     is_header: true,
+                    is_footer: false,
                 },
                 // Data row with rowspan
                 TableRow {
@@ -52,6 +54,7 @@ fn test_write_complex_table_with_colspan_rowspan() {
                             colspan: 1,
                             rowspan: 2, // Spans 2 rows
                             align: None,
+                            style: None,
                         },
                         TableCell {
                             content: vec![Block::Paragraph(Paragraph {
@@ -62,9 +65,11 @@ fn test_write_complex_table_with_colspan_rowspan() {
                             colspan: 1,
                             rowspan: 1,
                             align: None,
+                            style: None,
                         },
                     ],
                     is_header: false,
+                    is_footer: false,
                 },
             ],
             style_id: Some("TableGrid".to_string()),
@@ -73,13 +78,16 @@ fn test_write_complex_table_with_colspan_rowspan() {
                 ColumnSpec {
                     width: Some(2000),
                     align: None,
+                    style: None,
                 },
                 ColumnSpec {
                     width: Some(3000),
                     align: None,
+                    style: None,
                 },
             ],
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -132,6 +140,7 @@ fn test_write_table_with_empty_cell() {
                         colspan: 1,
                         rowspan: 1,
                         align: None,
+                        style: None,
                     },
                     TableCell {
                         content: vec![Block::Paragraph(Paragraph {
@@ -142,14 +151,17 @@ fn test_write_table_with_empty_cell() {
                         colspan: 1,
                         rowspan: 1,
                         align: None,
+                        style: None,
                     },
                 ],
                 is_header: false,
+                is_footer: false,
             }],
             style_id: None,
             caption: None,
             columns: vec![],
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -192,6 +204,7 @@ fn test_write_all_admonition_types() {
                     attributes: HashMap::new(),
                 })],
             })],
+            ..Default::default()
         };
 
         let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -230,6 +243,7 @@ fn test_write_admonition_without_title() {
                 attributes: HashMap::new(),
             })],
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -267,6 +281,7 @@ fn test_write_nested_unordered_list() {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
                 ListItem {
                     content: vec![Block::Paragraph(Paragraph {
@@ -276,6 +291,7 @@ fn test_write_nested_unordered_list() {
                     })],
                     level: 1,
                     term: None,
+                    checked: None,
                 },
                 ListItem {
                     content: vec![Block::Paragraph(Paragraph {
@@ -285,10 +301,14 @@ fn test_write_nested_unordered_list() {
                     })],
                     level: 2,
                     term: None,
+                    checked: None,
                 },
             ],
             style_id: None,
+            numbering: None,
+            start: None,
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -336,6 +356,7 @@ fn test_write_ordered_list() {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
                 ListItem {
                     content: vec![Block::Paragraph(Paragraph {
@@ -345,10 +366,14 @@ fn test_write_ordered_list() {
                     })],
                     level: 0,
                     term: None,
+                    checked: None,
                 },
             ],
             style_id: None,
+            numbering: None,
+            start: None,
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -360,9 +385,10 @@ fn test_write_ordered_list() {
         "Should have ListNumber style: {}",
         xml
     );
-    assert!(
-        xml.contains("<w:numId w:val=\"2\"/>"),
-        "Should have ordered numId: {}",
+    assert_eq!(
+        xml.matches("<w:numId w:val=\"1\"/>").count(),
+        2,
+        "Should number both steps with the list's numId: {}",
         xml
     );
 }
@@ -384,23 +410,32 @@ fn test_write_description_list() {
                 })],
                 level: 0,
                 term: Some(vec![Inline::Text("Term".to_string())]),
+                checked: None,
             }],
             style_id: None,
+            numbering: None,
+            start: None,
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
     let xml = extract_document_xml(&result);
 
-    // Verify description list style and numId
+    // Verify description list style, bold term and unnumbered definition
     assert!(
         xml.contains("<w:pStyle w:val=\"ListParagraph\"/>"),
         "Should have ListParagraph style: {}",
         xml
     );
     assert!(
-        xml.contains("<w:numId w:val=\"3\"/>"),
-        "Should have description numId: {}",
+        xml.contains("<w:b/>") && xml.contains("<w:t>Term</w:t>"),
+        "Should have bold term: {}",
+        xml
+    );
+    assert!(
+        !xml.contains("<w:numPr>"),
+        "Description lists should not be numbered: {}",
         xml
     );
 }
@@ -422,9 +457,13 @@ fn test_write_list_with_custom_style() {
                 })],
                 level: 0,
                 term: None,
+                checked: None,
             }],
             style_id: Some("CustomListStyle".to_string()),
+            numbering: None,
+            start: None,
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -454,12 +493,17 @@ fn test_write_list_with_non_paragraph_content() {
                     text: vec![Inline::Text("Heading in list".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 })],
                 level: 0,
                 term: None,
+                checked: None,
             }],
             style_id: None,
+            numbering: None,
+            start: None,
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -497,6 +541,7 @@ fn test_write_internal_hyperlink() {
             style_id: None,
             attributes: HashMap::new(),
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -535,6 +580,7 @@ fn test_write_external_hyperlink() {
             style_id: None,
             attributes: HashMap::new(),
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate_with_options(&doc, &template, false).unwrap();
@@ -589,6 +635,7 @@ fn test_write_all_format_types() {
             style_id: None,
             attributes: HashMap::new(),
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -635,6 +682,7 @@ fn test_write_break_types() {
                 attributes: HashMap::new(),
             }),
         ],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -667,6 +715,7 @@ fn test_write_section_break() {
                 attributes: HashMap::new(),
             }),
         ],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -692,8 +741,11 @@ fn test_write_literal_block() {
             language: Some("rust".to_string()),
             title: None,
             style_id: Some("CodeBlock".to_string()),
+            callouts: Vec::new(),
+            highlight_lines: Vec::new(),
         })],
         intent: None,
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -734,6 +786,7 @@ fn test_write_inline_span() {
             style_id: None,
             attributes: HashMap::new(),
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -758,6 +811,7 @@ fn test_write_inline_image() {
             style_id: None,
             attributes: HashMap::new(),
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -807,6 +861,7 @@ fn test_write_inline_break() {
             style_id: None,
             attributes: HashMap::new(),
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -828,26 +883,31 @@ fn test_write_heading_levels() {
                 text: vec![Inline::Text("H1".to_string())],
                 style_id: None,
                 anchor: None,
+                role: None,
             }),
             Block::Heading(Heading {
                 level: 4,
                 text: vec![Inline::Text("H4".to_string())],
                 style_id: None,
                 anchor: None,
+                role: None,
             }),
             Block::Heading(Heading {
                 level: 6,
                 text: vec![Inline::Text("H6".to_string())],
                 style_id: None,
                 anchor: None,
+                role: None,
             }),
             Block::Heading(Heading {
                 level: 7, // Beyond 6, should default to Heading1
                 text: vec![Inline::Text("H7".to_string())],
                 style_id: None,
                 anchor: None,
+                role: None,
             }),
         ],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -882,7 +942,9 @@ fn test_write_heading_with_custom_style() {
             text: vec![Inline::Text("Custom".to_string())],
             style_id: Some("MyHeadingStyle".to_string()),
             anchor: None,
+            role: None,
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
@@ -907,6 +969,7 @@ fn test_xml_escaping() {
             style_id: None,
             attributes: HashMap::new(),
         })],
+        ..Default::default()
     };
 
     let result = DocxWriter::generate(&doc, &template).unwrap();
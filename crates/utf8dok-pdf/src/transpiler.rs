@@ -3,7 +3,8 @@
 //! Converts utf8dok AST nodes to Typst markup strings.
 
 use utf8dok_ast::{
-    AdmonitionType, Block, Document, FormatType, Inline, List, ListItem, ListType, Table, TableRow,
+    AdmonitionType, Block, Document, FormatType, Inline, List, ListItem, ListType, LiteralBlock,
+    Table, TableRow,
 };
 
 /// Transpiler for converting AST to Typst markup
@@ -78,6 +79,12 @@ impl Transpiler {
 
             Block::List(list) => Self::transpile_list(list),
 
+            Block::Literal(code)
+                if !code.callouts.is_empty() || !code.highlight_lines.is_empty() =>
+            {
+                Self::transpile_annotated_code(code)
+            }
+
             Block::Literal(code) => {
                 let lang = code.language.as_deref().unwrap_or("");
                 if lang.is_empty() {
@@ -104,6 +111,9 @@ impl Transpiler {
 
             Block::ThematicBreak => "#line(length: 100%)\n".to_string(),
 
+            // Comments are not rendered
+            Block::Comment(_) => String::new(),
+
             Block::Admonition(admon) => {
                 let kind = match admon.admonition_type {
                     AdmonitionType::Note => "Note",
@@ -113,6 +123,9 @@ impl Transpiler {
                     AdmonitionType::Caution => "Caution",
                 };
                 let mut content = String::new();
+                if let Some(title) = &admon.title {
+                    content.push_str(&format!("*{}*\n\n", Self::transpile_inlines(title)));
+                }
                 for block in &admon.content {
                     content.push_str(&Self::transpile_block(block));
                 }
@@ -146,8 +159,56 @@ impl Transpiler {
         }
     }
 
+    /// Transpile a code block with highlighted lines and callout badges
+    ///
+    /// A `raw.line` show rule decorates the lines of a single raw block.
+    fn transpile_annotated_code(code: &LiteralBlock) -> String {
+        let mut rule = String::new();
+        if !code.highlight_lines.is_empty() {
+            let lines: Vec<String> = code.highlight_lines.iter().map(u32::to_string).collect();
+            rule.push_str(&format!(
+                "    if it.number in ({},) {{ highlight(fill: rgb(\"#fff3a8\"), it) }} else {{ it }}\n",
+                lines.join(", ")
+            ));
+        } else {
+            rule.push_str("    it\n");
+        }
+        for callout in &code.callouts {
+            rule.push_str(&format!(
+                "    if it.number == {} {{ h(0.5em) + {} }}\n",
+                callout.line,
+                callout_badge(&format!("\"{}\"", callout.number))
+            ));
+        }
+
+        let lang = code
+            .language
+            .as_ref()
+            .map(|lang| format!("lang: {}, ", raw_string(lang)))
+            .unwrap_or_default();
+        format!(
+            "#{{\n  show raw.line: it => {{\n{}  }}\n  raw(block: true, {}{})\n}}\n",
+            rule,
+            lang,
+            raw_string(&code.content)
+        )
+    }
+
     /// Transpile a list
     fn transpile_list(list: &List) -> String {
+        if list.list_type == ListType::Callout {
+            // Numbered with the same badges as the code callouts
+            let items: String = list
+                .items
+                .iter()
+                .map(|item| format!("  [{}],\n", Self::transpile_blocks(&item.content).trim()))
+                .collect();
+            return format!(
+                "#enum(\n  numbering: n => {},\n{})\n",
+                callout_badge("str(n)"),
+                items
+            );
+        }
         let ordered = matches!(list.list_type, ListType::Ordered);
         Self::transpile_list_items(&list.items, ordered, 0)
     }
@@ -270,10 +331,39 @@ impl Transpiler {
             Inline::Break => " \\\n".to_string(),
 
             Inline::Anchor(id) => format!("<{}>", id),
+
+            Inline::Footnote(footnote) => match footnote.id {
+                // Reuse an earlier footnote through its label
+                Some(ref id) if footnote.is_reference() => format!("#footnote(<fn-{}>)", id),
+                Some(ref id) => format!(
+                    "#footnote[{}] <fn-{}>",
+                    Self::transpile_inlines(&footnote.content),
+                    id
+                ),
+                None => format!("#footnote[{}]", Self::transpile_inlines(&footnote.content)),
+            },
         }
     }
 }
 
+/// Typst code for a callout badge showing `number` (a string expression)
+fn callout_badge(number: &str) -> String {
+    format!(
+        "box(fill: luma(64), inset: (x: 3pt, y: 1pt), radius: 40%, text(fill: white, size: 0.75em, weight: \"bold\", {}))",
+        number
+    )
+}
+
+/// Quote text as a Typst string literal
+fn raw_string(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
 /// Escape special characters in strings for Typst
 fn escape_string(s: &str) -> String {
     s.replace('\\', "\\\\")
@@ -285,7 +375,7 @@ fn escape_string(s: &str) -> String {
 mod tests {
     use super::*;
     use std::collections::HashMap;
-    use utf8dok_ast::{Heading, Paragraph};
+    use utf8dok_ast::{Callout, Footnote, Heading, Paragraph};
 
     #[test]
     fn test_transpile_heading() {
@@ -295,6 +385,7 @@ mod tests {
             text: vec![Inline::Text("Hello World".to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         }));
 
         let typst = Transpiler::transpile(&doc);
@@ -370,4 +461,69 @@ mod tests {
         assert_eq!(escape_string("say \"hi\""), "say \\\"hi\\\"");
         assert_eq!(escape_string("#heading"), "\\#heading");
     }
+
+    #[test]
+    fn test_transpile_footnotes() {
+        let mut doc = Document::new();
+        doc.push(Block::Paragraph(Paragraph {
+            inlines: vec![
+                Inline::Text("Claim".to_string()),
+                Inline::Footnote(Footnote {
+                    id: None,
+                    content: vec![Inline::Text("Source.".to_string())],
+                }),
+                Inline::Footnote(Footnote {
+                    id: Some("legal".to_string()),
+                    content: vec![Inline::Text("Disclaimer.".to_string())],
+                }),
+                Inline::Footnote(Footnote {
+                    id: Some("legal".to_string()),
+                    content: vec![],
+                }),
+            ],
+            style_id: None,
+            attributes: HashMap::new(),
+        }));
+
+        let typst = Transpiler::transpile(&doc);
+        assert!(typst.contains("Claim#footnote[Source.]"));
+        assert!(typst.contains("#footnote[Disclaimer.] <fn-legal>"));
+        assert!(typst.contains("#footnote(<fn-legal>)"));
+    }
+
+    #[test]
+    fn test_transpile_callouts_and_highlights() {
+        let mut doc = Document::new();
+        doc.push(Block::Literal(LiteralBlock {
+            content: "use std::io;\nfn main() {}".to_string(),
+            language: Some("rust".to_string()),
+            title: None,
+            style_id: None,
+            callouts: vec![Callout { number: 1, line: 2 }],
+            highlight_lines: vec![1],
+        }));
+        doc.push(Block::List(List {
+            list_type: ListType::Callout,
+            items: vec![ListItem {
+                content: vec![Block::Paragraph(Paragraph {
+                    inlines: vec![Inline::Text("Entry point".to_string())],
+                    style_id: None,
+                    attributes: HashMap::new(),
+                })],
+                level: 0,
+                term: None,
+                checked: None,
+            }],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+
+        let typst = Transpiler::transpile(&doc);
+        assert!(typst.contains("raw(block: true, lang: \"rust\", \"use std::io;\\nfn main() {}\")"));
+        assert!(typst.contains("if it.number in (1,) { highlight("));
+        assert!(typst.contains("if it.number == 2 { h(0.5em) + box("));
+        assert!(typst.contains("#enum(\n  numbering: n => box("));
+        assert!(typst.contains("  [Entry point],"));
+    }
 }
@@ -23,10 +23,30 @@
 //! let ast = engine.compile(script).unwrap();
 //! let diagnostics = engine.run_validation(&doc, &ast).unwrap();
 //! ```
+//!
+//! # Transforms
+//!
+//! Scripts can also rewrite documents as part of a transform pipeline (see
+//! `utf8dok_core::transform`). A [`ScriptTransform`] gives the script the
+//! document as the variable `doc`, in its JSON form, and reads it back when
+//! the script is done:
+//!
+//! ```
+//! use utf8dok_core::transform::Transform;
+//! use utf8dok_plugins::ScriptTransform;
+//!
+//! let mut doc = utf8dok_core::parse("= Draft\n\nText.\n").unwrap();
+//! let transform = ScriptTransform::new("retitle", r#"
+//!     doc.metadata.title = "Final";
+//! "#).unwrap();
+//! transform.apply(&mut doc).unwrap();
+//! assert_eq!(doc.metadata.title.as_deref(), Some("Final"));
+//! ```
 
 use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
 use thiserror::Error;
 use utf8dok_core::diagnostics::{Diagnostic, Severity};
+use utf8dok_core::transform::{Transform, TransformError};
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -49,6 +69,10 @@ pub enum PluginError {
     /// Script did not return expected type
     #[error("Script must return an array of diagnostics")]
     InvalidReturnType,
+
+    /// A transform script left `doc` in a form that is not a document
+    #[error("Script left an invalid document: {0}")]
+    InvalidDocument(String),
 }
 
 /// Result type for plugin operations
@@ -193,6 +217,30 @@ impl PluginEngine {
         self.convert_result_to_diagnostics(result)
     }
 
+    /// Run a transform script on a document
+    ///
+    /// The script receives the document as the `doc` variable and changes
+    /// it in place; its return value is ignored.
+    pub fn run_transform(&self, doc: &mut utf8dok_ast::Document, ast: &AST) -> Result<()> {
+        let doc_dynamic = rhai::serde::to_dynamic(&*doc).map_err(|e| {
+            PluginError::ExecutionError(format!("Failed to serialize document: {}", e))
+        })?;
+
+        let mut scope = Scope::new();
+        scope.push("doc", doc_dynamic);
+
+        self.engine
+            .run_ast_with_scope(&mut scope, ast)
+            .map_err(|e| PluginError::ExecutionError(e.to_string()))?;
+
+        let result: Dynamic = scope
+            .get_value("doc")
+            .ok_or_else(|| PluginError::InvalidDocument("`doc` was removed".to_string()))?;
+        *doc = rhai::serde::from_dynamic(&result)
+            .map_err(|e| PluginError::InvalidDocument(e.to_string()))?;
+        Ok(())
+    }
+
     /// Convert script result to Vec<Diagnostic>
     fn convert_result_to_diagnostics(&self, result: Dynamic) -> Result<Vec<Diagnostic>> {
         // Result should be an array
@@ -279,6 +327,54 @@ impl PluginEngine {
     }
 }
 
+/// A Rhai script run as a document transform
+///
+/// See [`PluginEngine::run_transform`] for what the script sees.
+pub struct ScriptTransform {
+    engine: PluginEngine,
+    ast: AST,
+    name: String,
+}
+
+impl ScriptTransform {
+    /// Compile a transform script, named `name` in progress output and errors
+    pub fn new(name: impl Into<String>, script: &str) -> Result<Self> {
+        let engine = PluginEngine::new();
+        let ast = engine.compile(script)?;
+        Ok(Self {
+            engine,
+            ast,
+            name: name.into(),
+        })
+    }
+
+    /// Compile a transform script from a file, named after the file
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let engine = PluginEngine::new();
+        let ast = engine.compile_file(path)?;
+        Ok(Self {
+            engine,
+            ast,
+            name: path.display().to_string(),
+        })
+    }
+}
+
+impl Transform for ScriptTransform {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn apply(&self, doc: &mut utf8dok_ast::Document) -> std::result::Result<(), TransformError> {
+        self.engine
+            .run_transform(doc, &self.ast)
+            .map_err(|e| TransformError::Failed {
+                transform: self.name.clone(),
+                message: e.to_string(),
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,6 +393,7 @@ mod tests {
                     text: vec![Inline::Text("Introduction".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 Block::Paragraph(Paragraph {
                     inlines: vec![Inline::Text("This is a test paragraph.".to_string())],
@@ -312,6 +409,7 @@ mod tests {
                 }),
             ],
             intent: None,
+            ..Default::default()
         }
     }
 
@@ -561,6 +659,41 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_transform_rewrites_blocks() {
+        let script = r#"
+            // Promote every heading and drop paragraphs mentioning "written"
+            for i in 0..doc.blocks.len() {
+                if "Heading" in doc.blocks[i] {
+                    doc.blocks[i].Heading.level += 1;
+                }
+            }
+            doc.blocks.retain(|block| !("Paragraph" in block
+                && block.Paragraph.inlines[0].Text.contains("written")));
+        "#;
+        let transform = ScriptTransform::new("tidy", script).unwrap();
+        assert_eq!(transform.name(), "tidy");
+
+        let mut doc = sample_document();
+        transform.apply(&mut doc).unwrap();
+        assert_eq!(doc.blocks.len(), 2);
+        match &doc.blocks[0] {
+            Block::Heading(heading) => assert_eq!(heading.level, 2),
+            other => panic!("Expected heading, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_transform_invalid_document() {
+        let transform = ScriptTransform::new("broken", "doc.blocks = 42;").unwrap();
+        let mut doc = sample_document();
+        let error = transform.apply(&mut doc).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Script left an invalid document"));
+        assert_eq!(doc, sample_document());
+    }
+
     #[test]
     fn test_version() {
         assert!(!VERSION.is_empty());
@@ -8,8 +8,8 @@
 //! - **Speaker Notes**: `[.notes]` sidebars become speaker notes
 
 use crate::slide::{
-    CodeContent, ListContent, ListItem as SlideListItem, Slide, SlideContent, SlideLayoutHint,
-    SpeakerNotes, TextContent, TextRun,
+    CodeContent, ListContent, ListItem as SlideListItem, NumberingStyle, Slide, SlideContent,
+    SlideLayoutHint, SpeakerNotes, TextContent, TextRun,
 };
 use utf8dok_ast::{Block, Document, Heading, Inline, List, ListItem, ListType, Sidebar};
 
@@ -328,9 +328,23 @@ impl SlideExtractor {
         let items: Vec<SlideListItem> = list.items.iter().flat_map(convert_list_item).collect();
 
         if let Some(ref mut slide) = self.current_slide {
-            let list_content = ListContent { items };
+            let numbering = match list.numbering {
+                None | Some(utf8dok_ast::NumberingStyle::Arabic) => NumberingStyle::Arabic,
+                Some(utf8dok_ast::NumberingStyle::LowerAlpha) => NumberingStyle::LowerAlpha,
+                Some(utf8dok_ast::NumberingStyle::UpperAlpha) => NumberingStyle::UpperAlpha,
+                Some(utf8dok_ast::NumberingStyle::LowerRoman) => NumberingStyle::LowerRoman,
+                Some(utf8dok_ast::NumberingStyle::UpperRoman) => NumberingStyle::UpperRoman,
+            };
+            let list_content = ListContent { items, numbering };
 
             match list.list_type {
+                // Callout explanations are numbered with badges
+                ListType::Callout => {
+                    slide.content.push(SlideContent::NumberedList(ListContent {
+                        numbering: NumberingStyle::Circled,
+                        ..list_content
+                    }));
+                }
                 ListType::Ordered => {
                     slide.content.push(SlideContent::NumberedList(list_content));
                 }
@@ -345,11 +359,15 @@ impl SlideExtractor {
     fn handle_code_block(&mut self, literal: &utf8dok_ast::LiteralBlock) {
         if let Some(ref mut slide) = self.current_slide {
             let code = CodeContent::new(&literal.content);
-            let code = if let Some(lang) = &literal.language {
+            let mut code = if let Some(lang) = &literal.language {
                 code.with_language(lang)
             } else {
                 code
             };
+            code.highlight_lines = literal.highlight_lines.clone();
+            for callout in &literal.callouts {
+                code = code.with_callout(callout.number, callout.line);
+            }
             slide.content.push(SlideContent::Code(code));
         }
     }
@@ -498,7 +516,19 @@ fn convert_list_item(item: &ListItem) -> Vec<SlideListItem> {
         .collect::<Vec<_>>()
         .join(" ");
 
-    result.push(SlideListItem::at_level(text, item.level as u32));
+    let mut slide_item = SlideListItem::at_level(text, item.level as u32);
+    slide_item.checked = item.checked;
+
+    // Description items lead with their term in bold
+    if let Some(term) = &item.term {
+        let mut runs = vec![TextRun::bold(inlines_to_text(term))];
+        let definition = slide_item.content.as_plain_text();
+        if !definition.is_empty() {
+            runs.push(TextRun::plain(format!(": {}", definition)));
+        }
+        slide_item.content = TextContent::from_runs(runs);
+    }
+    result.push(slide_item);
 
     // Handle nested lists
     for block in &item.content {
@@ -527,6 +557,7 @@ mod tests {
             },
             blocks: Vec::new(),
             intent: None,
+            ..Default::default()
         }
     }
 
@@ -537,6 +568,7 @@ mod tests {
             text: vec![Inline::Text(text.to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         })
     }
 
@@ -559,9 +591,12 @@ mod tests {
                     content: vec![para(text)],
                     level: 0,
                     term: None,
+                    checked: None,
                 })
                 .collect(),
             style_id: None,
+            numbering: None,
+            start: None,
         })
     }
 
@@ -763,6 +798,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_checklist_and_description_mapping() {
+        let item = |text: &str, term: Option<&str>, checked: Option<bool>| ListItem {
+            content: vec![para(text)],
+            level: 0,
+            term: term.map(|term| vec![Inline::Text(term.to_string())]),
+            checked,
+        };
+        let mut doc = doc_with_title("Presentation");
+        doc.blocks.push(heading(2, "Checks"));
+        doc.blocks.push(Block::List(List {
+            list_type: ListType::Unordered,
+            items: vec![item("Backup", None, Some(true))],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+        doc.blocks.push(Block::List(List {
+            list_type: ListType::Description,
+            items: vec![item("Central processing unit", Some("CPU"), None)],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+        doc.blocks.push(Block::List(List {
+            list_type: ListType::Ordered,
+            items: vec![item("First", None, None)],
+            style_id: None,
+            numbering: Some(utf8dok_ast::NumberingStyle::UpperRoman),
+            start: None,
+        }));
+
+        let deck = SlideExtractor::extract(&doc);
+        let slide = &deck.slides[1];
+
+        let SlideContent::BulletList(checklist) = &slide.content[0] else {
+            panic!("Expected BulletList");
+        };
+        assert_eq!(checklist.items[0].checked, Some(true));
+
+        let SlideContent::BulletList(terms) = &slide.content[1] else {
+            panic!("Expected BulletList");
+        };
+        let runs = &terms.items[0].content.runs;
+        assert!(runs[0].bold);
+        assert_eq!(
+            terms.items[0].content.as_plain_text(),
+            "CPU: Central processing unit"
+        );
+
+        let SlideContent::NumberedList(numbered) = &slide.content[2] else {
+            panic!("Expected NumberedList");
+        };
+        assert_eq!(numbered.numbering, NumberingStyle::UpperRoman);
+    }
+
     #[test]
     fn test_code_block_mapping() {
         let mut doc = doc_with_title("Presentation");
@@ -772,6 +863,8 @@ mod tests {
             language: Some("rust".to_string()),
             title: None,
             style_id: None,
+            callouts: Vec::new(),
+            highlight_lines: Vec::new(),
         }));
 
         let deck = SlideExtractor::extract(&doc);
@@ -787,6 +880,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_code_callouts_mapping() {
+        let mut doc = doc_with_title("Presentation");
+        doc.blocks.push(heading(2, "Callouts"));
+        doc.blocks.push(Block::Literal(utf8dok_ast::LiteralBlock {
+            content: "use std::io;\nfn main() {}".to_string(),
+            language: Some("rust".to_string()),
+            title: None,
+            style_id: None,
+            callouts: vec![utf8dok_ast::Callout { number: 1, line: 2 }],
+            highlight_lines: vec![1],
+        }));
+        doc.blocks.push(Block::List(List {
+            list_type: ListType::Callout,
+            items: vec![ListItem {
+                content: vec![para("Entry point")],
+                level: 0,
+                term: None,
+                checked: None,
+            }],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+
+        let deck = SlideExtractor::extract(&doc);
+        let slide = &deck.slides[1];
+
+        let SlideContent::Code(code) = &slide.content[0] else {
+            panic!("Expected Code");
+        };
+        assert_eq!(code.highlight_lines, vec![1]);
+        assert_eq!(code.callouts[0].line, 2);
+
+        let SlideContent::NumberedList(callouts) = &slide.content[1] else {
+            panic!("Expected NumberedList");
+        };
+        assert_eq!(callouts.numbering, NumberingStyle::Circled);
+    }
+
     // =========================================================================
     // Test 5: Edge Cases
     // =========================================================================
@@ -201,6 +201,36 @@ impl TextContent {
 pub struct ListContent {
     /// List items
     pub items: Vec<ListItem>,
+
+    /// Numbering scheme (numbered lists only)
+    pub numbering: NumberingStyle,
+}
+
+/// Numbering scheme of a numbered list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NumberingStyle {
+    #[default]
+    Arabic,
+    LowerAlpha,
+    UpperAlpha,
+    LowerRoman,
+    UpperRoman,
+    /// Filled circled numbers, used as callout badges
+    Circled,
+}
+
+impl NumberingStyle {
+    /// DrawingML auto-numbering scheme (`a:buAutoNum/@type`)
+    pub fn auto_num_type(&self) -> &'static str {
+        match self {
+            Self::Arabic => "arabicPeriod",
+            Self::LowerAlpha => "alphaLcPeriod",
+            Self::UpperAlpha => "alphaUcPeriod",
+            Self::LowerRoman => "romanLcPeriod",
+            Self::UpperRoman => "romanUcPeriod",
+            Self::Circled => "circleNumWdBlackPlain",
+        }
+    }
 }
 
 /// A single list item (can be nested)
@@ -214,6 +244,9 @@ pub struct ListItem {
 
     /// Nested items (for hierarchical lists)
     pub children: Vec<ListItem>,
+
+    /// Checklist state (`None` for plain items)
+    pub checked: Option<bool>,
 }
 
 impl ListItem {
@@ -223,6 +256,7 @@ impl ListItem {
             content: TextContent::plain(text),
             level: 0,
             children: Vec::new(),
+            checked: None,
         }
     }
 
@@ -232,8 +266,15 @@ impl ListItem {
             content: TextContent::plain(text),
             level,
             children: Vec::new(),
+            checked: None,
         }
     }
+
+    /// Mark the item as a checklist item
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
 }
 
 /// Image content
@@ -291,6 +332,31 @@ pub struct CodeContent {
 
     /// Highlighted lines
     pub highlight_lines: Vec<u32>,
+
+    /// Callout badges at the ends of lines
+    pub callouts: Vec<CodeCallout>,
+}
+
+/// A numbered callout badge at the end of a code line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeCallout {
+    /// Callout number, matching the item of the callout list
+    pub number: u32,
+
+    /// Line carrying the badge (1-based)
+    pub line: u32,
+}
+
+impl CodeCallout {
+    /// Badge text: a filled circled number (❶ to ❿), `(n)` beyond ten
+    pub fn badge(&self) -> String {
+        match self.number {
+            1..=10 => char::from_u32(0x2775 + self.number)
+                .map(String::from)
+                .unwrap_or_default(),
+            n => format!("({})", n),
+        }
+    }
 }
 
 impl CodeContent {
@@ -303,6 +369,7 @@ impl CodeContent {
             line_numbers: false,
             start_line: 1,
             highlight_lines: Vec::new(),
+            callouts: Vec::new(),
         }
     }
 
@@ -311,6 +378,12 @@ impl CodeContent {
         self.language = Some(lang.into());
         self
     }
+
+    /// Add a callout badge to the end of a line (1-based)
+    pub fn with_callout(mut self, number: u32, line: u32) -> Self {
+        self.callouts.push(CodeCallout { number, line });
+        self
+    }
 }
 
 /// Block quote content
@@ -551,6 +624,10 @@ mod tests {
 
         assert_eq!(code.source, "fn main() {}");
         assert_eq!(code.language, Some("rust".to_string()));
+
+        let code = code.with_callout(1, 1).with_callout(12, 1);
+        assert_eq!(code.callouts[0].badge(), "\u{2776}");
+        assert_eq!(code.callouts[1].badge(), "(12)");
     }
 
     #[test]
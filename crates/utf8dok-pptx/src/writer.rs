@@ -5,7 +5,9 @@
 use crate::constants::*;
 use crate::error::Result;
 use crate::layout::LayoutMapping;
-use crate::slide::{ListContent, ListItem, Slide, SlideContent, TextContent, TextRun};
+use crate::slide::{
+    CodeContent, ListContent, ListItem, NumberingStyle, Slide, SlideContent, TextContent, TextRun,
+};
 use crate::slide_contract::SlideContract;
 use crate::template::PotxTemplate;
 use std::io::{Cursor, Write};
@@ -890,6 +892,10 @@ impl PptxWriter {
                     shapes.push_str(&self.generate_numbered_list_shape(shape_id, list));
                     shape_id += 1;
                 }
+                SlideContent::Code(code) => {
+                    shapes.push_str(&self.generate_code_shape(shape_id, code));
+                    shape_id += 1;
+                }
                 // Other content types will be implemented in later phases
                 _ => {}
             }
@@ -927,7 +933,7 @@ impl PptxWriter {
         let mut paragraphs = String::new();
 
         for item in &list.items {
-            paragraphs.push_str(&self.generate_list_item_paragraph(item, false));
+            paragraphs.push_str(&self.generate_list_item_paragraph(item, None));
         }
 
         format!(
@@ -953,7 +959,7 @@ impl PptxWriter {
         let mut paragraphs = String::new();
 
         for item in &list.items {
-            paragraphs.push_str(&self.generate_list_item_paragraph(item, true));
+            paragraphs.push_str(&self.generate_list_item_paragraph(item, Some(list.numbering)));
         }
 
         format!(
@@ -974,14 +980,85 @@ impl PptxWriter {
         )
     }
 
+    /// Generate a code block shape
+    ///
+    /// Each source line is a monospace paragraph; highlighted lines get a
+    /// highlight colour and callouts a badge at the end of their line.
+    fn generate_code_shape(&self, id: u32, code: &CodeContent) -> String {
+        let mut paragraphs = String::new();
+
+        for (index, line) in code.source.lines().enumerate() {
+            let number = index as u32 + 1;
+            let highlight = if code.highlight_lines.contains(&number) {
+                r#"<a:highlight><a:srgbClr val="FFF3A8"/></a:highlight>"#
+            } else {
+                ""
+            };
+            paragraphs.push_str(&format!(
+                "          <a:p>\n            <a:pPr><a:buNone/></a:pPr>\n            <a:r>\n              <a:rPr lang=\"{}\">{}<a:latin typeface=\"Consolas\"/></a:rPr>\n              <a:t>{}</a:t>\n            </a:r>\n",
+                self.contract.meta.locale,
+                highlight,
+                escape_xml(line)
+            ));
+            for callout in code.callouts.iter().filter(|c| c.line == number) {
+                paragraphs.push_str(&format!(
+                    "            <a:r>\n              <a:rPr lang=\"{}\" b=\"1\"><a:solidFill><a:srgbClr val=\"C00000\"/></a:solidFill><a:latin typeface=\"Segoe UI Symbol\"/></a:rPr>\n              <a:t> {}</a:t>\n            </a:r>\n",
+                    self.contract.meta.locale,
+                    escape_xml(&callout.badge())
+                ));
+            }
+            paragraphs.push_str("          </a:p>\n");
+        }
+
+        format!(
+            r#"      <p:sp>
+        <p:nvSpPr>
+          <p:cNvPr id="{}" name="Code {}"/>
+          <p:cNvSpPr><a:spLocks noGrp="1"/></p:cNvSpPr>
+          <p:nvPr><p:ph idx="1"/></p:nvPr>
+        </p:nvSpPr>
+        <p:spPr/>
+        <p:txBody>
+          <a:bodyPr/>
+          <a:lstStyle/>
+{}        </p:txBody>
+      </p:sp>
+"#,
+            id, id, paragraphs
+        )
+    }
+
     /// Generate a list item as a paragraph
-    fn generate_list_item_paragraph(&self, item: &ListItem, _numbered: bool) -> String {
+    ///
+    /// Plain bullets inherit the placeholder's bullet style; numbered items
+    /// get auto-numbering and checklist items a ballot box bullet.
+    fn generate_list_item_paragraph(
+        &self,
+        item: &ListItem,
+        numbering: Option<NumberingStyle>,
+    ) -> String {
+        let bullet = match (item.checked, numbering) {
+            (Some(checked), _) => Some(format!(
+                r#"<a:buFont typeface="Segoe UI Symbol"/><a:buChar char="{}"/>"#,
+                if checked { '\u{2612}' } else { '\u{2610}' }
+            )),
+            (None, Some(numbering)) => Some(format!(
+                r#"<a:buAutoNum type="{}"/>"#,
+                numbering.auto_num_type()
+            )),
+            (None, None) => None,
+        };
+        let properties = match bullet {
+            Some(bullet) => format!(r#"<a:pPr lvl="{}">{}</a:pPr>"#, item.level, bullet),
+            None => format!(r#"<a:pPr lvl="{}"/>"#, item.level),
+        };
+
         format!(
             r#"          <a:p>
-            <a:pPr lvl="{}"/>
+            {}
 {}          </a:p>
 "#,
-            item.level,
+            properties,
             self.generate_text_runs(&item.content.runs)
         )
     }
@@ -1165,6 +1242,7 @@ mod tests {
                     ListItem::simple("First point"),
                     ListItem::simple("Second point"),
                 ],
+                ..Default::default()
             }),
         ));
 
@@ -1229,4 +1307,53 @@ mod tests {
             .get_layout_for_hint(SlideLayoutHint::Content);
         assert_eq!(idx, 2);
     }
+
+    #[test]
+    fn test_list_bullets() {
+        let writer = PptxWriter::default();
+        let slide = Slide::content_slide(1, "Runbook")
+            .with_content(SlideContent::NumberedList(ListContent {
+                items: vec![ListItem::simple("Prepare"), ListItem::at_level("Detail", 1)],
+                numbering: NumberingStyle::LowerAlpha,
+            }))
+            .with_content(SlideContent::BulletList(ListContent {
+                items: vec![
+                    ListItem::simple("Done").with_checked(true),
+                    ListItem::simple("Open").with_checked(false),
+                    ListItem::simple("Plain"),
+                ],
+                ..Default::default()
+            }));
+
+        let xml = writer.generate_slide_xml(&slide, 2).unwrap();
+
+        assert!(xml.contains(r#"<a:pPr lvl="0"><a:buAutoNum type="alphaLcPeriod"/></a:pPr>"#));
+        assert!(xml.contains(r#"<a:pPr lvl="1"><a:buAutoNum type="alphaLcPeriod"/></a:pPr>"#));
+        assert!(xml.contains("<a:buChar char=\"\u{2612}\"/>"));
+        assert!(xml.contains("<a:buChar char=\"\u{2610}\"/>"));
+        assert!(xml.contains(r#"<a:pPr lvl="0"/>"#));
+    }
+
+    #[test]
+    fn test_code_callouts_and_highlights() {
+        let writer = PptxWriter::default();
+        let mut code = CodeContent::new("use std::io;\nfn main() {}").with_callout(1, 2);
+        code.highlight_lines = vec![1];
+        let slide = Slide::content_slide(1, "Code").with_content(SlideContent::Code(code));
+
+        let xml = writer.generate_slide_xml(&slide, 2).unwrap();
+
+        assert!(xml.contains("<a:t>fn main() {}</a:t>"));
+        assert!(xml.contains("<a:t> \u{2776}</a:t>"));
+        assert_eq!(xml.matches("<a:highlight>").count(), 1);
+
+        let slide = Slide::content_slide(2, "Callouts").with_content(SlideContent::NumberedList(
+            ListContent {
+                items: vec![ListItem::simple("Entry point")],
+                numbering: NumberingStyle::Circled,
+            },
+        ));
+        let xml = writer.generate_slide_xml(&slide, 3).unwrap();
+        assert!(xml.contains(r#"<a:buAutoNum type="circleNumWdBlackPlain"/>"#));
+    }
 }
@@ -30,10 +30,11 @@ use crate::Validator;
 /// let doc = Document {
 ///     metadata: utf8dok_ast::DocumentMeta::default(),
 ///     blocks: vec![
-///         Block::Heading(Heading { level: 1, text: vec![], style_id: None, anchor: None }),
-///         Block::Heading(Heading { level: 2, text: vec![], style_id: None, anchor: None }),
+///         Block::Heading(Heading { level: 1, text: vec![], style_id: None, anchor: None, role: None }),
+///         Block::Heading(Heading { level: 2, text: vec![], style_id: None, anchor: None, role: None }),
 ///     ],
 ///     intent: None,
+///     ..Default::default()
 /// };
 ///
 /// assert!(validator.validate(&doc).is_empty());
@@ -75,7 +76,7 @@ impl Validator for SectionHierarchyValidator {
                         )
                     };
 
-                    let diagnostic = Diagnostic::warning(message)
+                    let mut diagnostic = Diagnostic::warning(message)
                         .with_code("DOC101")
                         .with_help(
                             "Heading hierarchy should not skip levels. \
@@ -84,6 +85,20 @@ impl Validator for SectionHierarchyValidator {
                         )
                         .with_note(format!("Found at block index {}", block_index));
 
+                    if let Some(span) = doc.source_map.block_span(block_index) {
+                        diagnostic = diagnostic.with_span((*span).into());
+                    }
+
+                    // Point at the included file the heading came from
+                    if let Some(location) = doc.source_map.block(block_index) {
+                        if let Some(file) = &location.file {
+                            diagnostic = diagnostic.with_file(file.clone()).with_note(format!(
+                                "Heading at line {} of {}",
+                                location.line, file
+                            ));
+                        }
+                    }
+
                     diagnostics.push(diagnostic);
                 }
 
@@ -108,6 +123,7 @@ mod tests {
             text: vec![Inline::Text(text.to_string())],
             style_id: None,
             anchor: None,
+            role: None,
         })
     }
 
@@ -145,6 +161,7 @@ mod tests {
             metadata: utf8dok_ast::DocumentMeta::default(),
             blocks: vec![heading(1, "Title")],
             intent: None,
+            ..Default::default()
         };
         assert!(validator.validate(&doc).is_empty());
     }
@@ -162,6 +179,7 @@ mod tests {
                 heading(1, "Chapter 2"),
             ],
             intent: None,
+            ..Default::default()
         };
         assert!(validator.validate(&doc).is_empty());
     }
@@ -176,6 +194,7 @@ mod tests {
                 heading(3, "Subsection"), // Jump!
             ],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = validator.validate(&doc);
@@ -197,6 +216,7 @@ mod tests {
                 heading(4, "Deep"), // Jump!
             ],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = validator.validate(&doc);
@@ -212,6 +232,7 @@ mod tests {
             metadata: utf8dok_ast::DocumentMeta::default(),
             blocks: vec![heading(2, "Starting at Level 2")],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = validator.validate(&doc);
@@ -226,6 +247,7 @@ mod tests {
             metadata: utf8dok_ast::DocumentMeta::default(),
             blocks: vec![heading(3, "Starting at Level 3")],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = validator.validate(&doc);
@@ -246,6 +268,7 @@ mod tests {
                 heading(3, "Specifics"),
             ],
             intent: None,
+            ..Default::default()
         };
         assert!(validator.validate(&doc).is_empty());
     }
@@ -261,6 +284,7 @@ mod tests {
                 heading(4, "Deep"), // Jump from 1 to 4!
             ],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = validator.validate(&doc);
@@ -279,6 +303,7 @@ mod tests {
                 heading(1, "Chapter 2"), // Going back up is fine
             ],
             intent: None,
+            ..Default::default()
         };
         assert!(validator.validate(&doc).is_empty());
     }
@@ -294,6 +319,7 @@ mod tests {
                 heading(5, "Jump 2"), // Second jump
             ],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = validator.validate(&doc);
@@ -307,6 +333,7 @@ mod tests {
             metadata: utf8dok_ast::DocumentMeta::default(),
             blocks: vec![heading(1, "Chapter"), heading(3, "Jump")],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = validator.validate(&doc);
@@ -321,10 +348,45 @@ mod tests {
             metadata: utf8dok_ast::DocumentMeta::default(),
             blocks: vec![heading(3, "Deep Start")],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = validator.validate(&doc);
         assert!(diagnostics[0].is_warning());
         assert!(!diagnostics[0].is_error());
     }
+
+    #[test]
+    fn test_diagnostic_points_at_included_file() {
+        let mut doc = Document {
+            metadata: utf8dok_ast::DocumentMeta::default(),
+            blocks: vec![heading(1, "Intro"), heading(3, "Deep")],
+            intent: None,
+            ..Default::default()
+        };
+        doc.source_map
+            .insert_block(1, utf8dok_ast::SourceLocation::in_file("chapter.adoc", 4));
+
+        let diagnostics = SectionHierarchyValidator.validate(&doc);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file.as_deref(), Some("chapter.adoc"));
+    }
+
+    #[test]
+    fn test_diagnostic_uses_source_span() {
+        let doc = utf8dok_core::parse(
+            "== Intro
+
+Text.
+
+==== Deep",
+        )
+        .unwrap();
+
+        let diagnostics = SectionHierarchyValidator.validate(&doc);
+        assert_eq!(diagnostics.len(), 1);
+        let span = diagnostics[0].span.as_ref().unwrap();
+        assert_eq!((span.start, span.end), (17, 26));
+        assert_eq!((span.line, span.column), (Some(5), Some(1)));
+    }
 }
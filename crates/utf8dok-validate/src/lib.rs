@@ -171,21 +171,25 @@ mod tests {
                     text: vec![Inline::Text("Level 1".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 Block::Heading(Heading {
                     level: 2,
                     text: vec![Inline::Text("Level 2".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 Block::Heading(Heading {
                     level: 3,
                     text: vec![Inline::Text("Level 3".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
             ],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = engine.validate(&doc);
@@ -208,12 +212,14 @@ mod tests {
                     text: vec![Inline::Text("Level 1".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 Block::Heading(Heading {
                     level: 2,
                     text: vec![Inline::Text("Level 2".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 // Jump from level 2 to level 4 - missing level 3!
                 Block::Heading(Heading {
@@ -221,9 +227,11 @@ mod tests {
                     text: vec![Inline::Text("Level 4".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
             ],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = engine.validate(&doc);
@@ -250,8 +258,10 @@ mod tests {
                 text: vec![Inline::Text("Starting at Level 3".to_string())],
                 style_id: None,
                 anchor: None,
+                role: None,
             })],
             intent: None,
+            ..Default::default()
         };
 
         let diagnostics = engine.validate(&doc);
@@ -284,15 +294,18 @@ mod tests {
                     text: vec![Inline::Text("Level 1".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
                 Block::Heading(Heading {
                     level: 4,
                     text: vec![Inline::Text("Level 4".to_string())],
                     style_id: None,
                     anchor: None,
+                    role: None,
                 }),
             ],
             intent: None,
+            ..Default::default()
         };
 
         assert!(engine.has_issues(&doc_with_jump));
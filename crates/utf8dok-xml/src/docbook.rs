@@ -0,0 +1,823 @@
+//! DocBook 5 writer
+//!
+//! Renders a document as a DocBook 5.0 `<article>`. Headings open nested
+//! `<section>` elements, tables use the CALS model with `namest`/`nameend`
+//! and `morerows` for spans, and images become figures. Presentational
+//! breaks have no DocBook element and are kept as `<?asciidoc-pagebreak?>`,
+//! `<?asciidoc-hr?>` and `<?asciidoc-br?>` processing instructions, as
+//! Asciidoctor writes them.
+
+use std::collections::{HashMap, HashSet};
+
+use utf8dok_ast::{
+    Admonition, AdmonitionType, Alignment, Block, Document, FormatType, Image, Inline, List,
+    ListType, LiteralBlock, NumberingStyle, QuoteBlock, Table, TableRow,
+};
+use utf8dok_ooxml::StyleContract;
+
+use crate::outline::{label, xml_id, Outline};
+use crate::xml::{escape_attr, escape_text, nesting, row_group, Nesting, ROW_GROUPS};
+
+/// DocBook 5 namespace
+pub const DOCBOOK_NAMESPACE: &str = "http://docbook.org/ns/docbook";
+
+/// DocBook 5 document writer
+///
+/// Ids use the same names as the bookmarks written by the DOCX writer: with
+/// a style contract, anchors extracted from Word get their original
+/// bookmark names back.
+#[derive(Default)]
+pub struct DocBookWriter {
+    style_contract: Option<StyleContract>,
+}
+
+impl DocBookWriter {
+    /// Create a writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style contract used for anchor names
+    pub fn set_style_contract(&mut self, contract: StyleContract) {
+        self.style_contract = Some(contract);
+    }
+
+    /// Generate a DocBook 5 article for the whole document
+    pub fn generate(&self, doc: &Document) -> String {
+        let contract = self.style_contract.as_ref();
+        let mut renderer = Renderer {
+            contract,
+            outline: Outline::new(doc, contract),
+            out: String::new(),
+            blocks: 0,
+            written: HashSet::new(),
+            label: None,
+            note_ids: HashMap::new(),
+            note_count: 0,
+            listing_count: 0,
+            callouts: HashMap::new(),
+        };
+        renderer.article(doc);
+        renderer.out
+    }
+}
+
+/// Rendering state for one document
+struct Renderer<'a> {
+    contract: Option<&'a StyleContract>,
+    outline: Outline,
+    out: String,
+    /// Block elements written so far, to keep sections from being empty
+    blocks: usize,
+    /// Ids written so far: a repeated anchor is written once
+    written: HashSet<String>,
+    /// Id for the next figure or table, from the anchor paragraph above it
+    label: Option<String>,
+    /// Id of each footnote with a name, for later references to it
+    note_ids: HashMap<String, String>,
+    note_count: usize,
+    /// Listings with callouts written so far
+    listing_count: usize,
+    /// `<co>` ids of the last listing with callouts, by callout number
+    callouts: HashMap<u32, Vec<String>>,
+}
+
+impl Renderer<'_> {
+    fn article(&mut self, doc: &Document) {
+        let meta = &doc.metadata;
+        self.out
+            .push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.out.push_str(&format!(
+            "<article xmlns=\"{}\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" version=\"5.0\"",
+            DOCBOOK_NAMESPACE
+        ));
+        if let Some(lang) = meta.attributes.get("lang") {
+            self.out
+                .push_str(&format!(" xml:lang=\"{}\"", escape_attr(lang)));
+        }
+        self.out.push_str(">\n<info>\n");
+        // An article must have a title
+        self.out.push_str(&format!(
+            "<title>{}</title>\n",
+            escape_text(meta.title.as_deref().unwrap_or("Untitled"))
+        ));
+        for author in &meta.authors {
+            self.out.push_str(&format!(
+                "<author>\n<personname>{}</personname>\n</author>\n",
+                escape_text(author)
+            ));
+        }
+        if let Some(revision) = &meta.revision {
+            self.out.push_str(&format!(
+                "<releaseinfo>{}</releaseinfo>\n",
+                escape_text(revision)
+            ));
+        }
+        self.out.push_str("</info>\n");
+        self.write_body(doc);
+        self.out.push_str("</article>\n");
+    }
+
+    /// Write the top-level blocks, wrapping sections
+    fn write_body(&mut self, doc: &Document) {
+        // Level and block count of each open section
+        let mut open_sections: Vec<(u8, usize)> = Vec::new();
+        for (index, block) in doc.blocks.iter().enumerate() {
+            if self.outline.is_absorbed(index) {
+                continue;
+            }
+            if let Some((anchor, _)) = label(block, doc.blocks.get(index + 1)) {
+                self.label = Some(xml_id(self.contract, anchor));
+                continue;
+            }
+            let Block::Heading(heading) = block else {
+                self.write_block(block);
+                continue;
+            };
+            while open_sections
+                .last()
+                .is_some_and(|&(level, _)| level >= heading.level)
+            {
+                let (_, blocks) = open_sections.pop().unwrap_or_default();
+                self.close_section(blocks);
+            }
+            let id = self
+                .outline
+                .heading_id(index)
+                .unwrap_or_default()
+                .to_string();
+            self.written.insert(id.clone());
+            self.out.push_str(&format!(
+                "<section xml:id=\"{}\">\n<title>",
+                escape_attr(&id)
+            ));
+            self.write_inlines(&heading.text);
+            self.out.push_str("</title>\n");
+            self.blocks += 1;
+            open_sections.push((heading.level, self.blocks));
+        }
+        while let Some((_, blocks)) = open_sections.pop() {
+            self.close_section(blocks);
+        }
+        if self.blocks == 0 {
+            self.out.push_str("<para/>\n");
+        }
+    }
+
+    /// Close a section, which must not be empty
+    fn close_section(&mut self, blocks_at_open: usize) {
+        if self.blocks == blocks_at_open {
+            self.out.push_str("<para/>\n");
+        }
+        self.out.push_str("</section>\n");
+    }
+
+    fn write_blocks(&mut self, blocks: &[Block]) {
+        for (index, block) in blocks.iter().enumerate() {
+            if let Some((anchor, _)) = label(block, blocks.get(index + 1)) {
+                self.label = Some(xml_id(self.contract, anchor));
+                continue;
+            }
+            self.write_block(block);
+        }
+    }
+
+    /// The `xml:id` attribute of a figure or table, from its label
+    fn label_attr(&mut self) -> String {
+        match self.label.take() {
+            Some(id) if self.written.insert(id.clone()) => {
+                format!(" xml:id=\"{}\"", escape_attr(&id))
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Write blocks into an element that needs at least one
+    fn write_required_blocks(&mut self, blocks: &[Block]) {
+        let before = self.blocks;
+        self.write_blocks(blocks);
+        if self.blocks == before {
+            self.out.push_str("<para/>\n");
+            self.blocks += 1;
+        }
+    }
+
+    fn write_block(&mut self, block: &Block) {
+        match block {
+            Block::Paragraph(p) => {
+                let title = p.attributes.get("title");
+                match (p.inlines.as_slice(), title) {
+                    ([Inline::Image(image)], _) => self.write_figure(image, title),
+                    (inlines, Some(title)) => {
+                        self.out.push_str(&format!(
+                            "<formalpara>\n<title>{}</title>\n<para>",
+                            escape_text(title)
+                        ));
+                        self.write_inlines(inlines);
+                        self.out.push_str("</para>\n</formalpara>\n");
+                    }
+                    (inlines, None) => {
+                        self.out.push_str("<para>");
+                        self.write_inlines(inlines);
+                        self.out.push_str("</para>\n");
+                    }
+                }
+            }
+            Block::Heading(heading) => {
+                // A heading inside another block cannot open a section
+                self.out.push_str(&format!(
+                    "<bridgehead renderas=\"sect{}\"",
+                    heading.level.clamp(1, 5)
+                ));
+                if let Some(anchor) = &heading.anchor {
+                    let id = xml_id(self.contract, anchor);
+                    if self.written.insert(id.clone()) {
+                        self.out
+                            .push_str(&format!(" xml:id=\"{}\"", escape_attr(&id)));
+                    }
+                }
+                self.out.push('>');
+                self.write_inlines(&heading.text);
+                self.out.push_str("</bridgehead>\n");
+            }
+            Block::List(list) => self.write_list(list),
+            Block::Table(table) => self.write_table(table),
+            Block::Admonition(admonition) => self.write_admonition(admonition),
+            Block::Literal(literal) => self.write_literal(literal),
+            Block::Break(_) => {
+                self.out.push_str("<?asciidoc-pagebreak?>\n");
+                return;
+            }
+            Block::Open(open) => {
+                if let Some(title) = &open.title {
+                    self.out.push_str(&format!(
+                        "<para role=\"title\">{}</para>\n",
+                        escape_text(title)
+                    ));
+                    self.blocks += 1;
+                }
+                self.write_blocks(&open.blocks);
+                return;
+            }
+            Block::Sidebar(sidebar) => {
+                self.out.push_str("<sidebar>\n");
+                self.write_title(sidebar.title.as_deref());
+                self.write_required_blocks(&sidebar.blocks);
+                self.out.push_str("</sidebar>\n");
+            }
+            Block::Quote(quote) => self.write_quote(quote),
+            Block::ThematicBreak => {
+                self.out.push_str("<?asciidoc-hr?>\n");
+                return;
+            }
+            // Comments are not rendered
+            Block::Comment(_) => return,
+        }
+        self.blocks += 1;
+    }
+
+    fn write_title(&mut self, title: Option<&str>) {
+        if let Some(title) = title {
+            self.out
+                .push_str(&format!("<title>{}</title>\n", escape_text(title)));
+        }
+    }
+
+    /// Write an image paragraph as a figure, formal if it has a title
+    fn write_figure(&mut self, image: &Image, title: Option<&String>) {
+        let id = self.label_attr();
+        match title {
+            Some(title) => self.out.push_str(&format!(
+                "<figure{}>\n<title>{}</title>\n",
+                id,
+                escape_text(title)
+            )),
+            None => self.out.push_str(&format!("<informalfigure{}>\n", id)),
+        }
+        self.out.push_str(&format!(
+            "<mediaobject>\n<imageobject>\n<imagedata fileref=\"{}\"/>\n</imageobject>\n",
+            escape_attr(&image.src)
+        ));
+        if let Some(alt) = &image.alt {
+            self.out.push_str(&format!(
+                "<textobject><phrase>{}</phrase></textobject>\n",
+                escape_text(alt)
+            ));
+        }
+        self.out.push_str("</mediaobject>\n");
+        match title {
+            Some(_) => self.out.push_str("</figure>\n"),
+            None => self.out.push_str("</informalfigure>\n"),
+        }
+    }
+
+    fn write_list(&mut self, list: &List) {
+        let (open, close) = match (&list.list_type, list.numbering) {
+            (ListType::Description, _) => {
+                self.out.push_str("<variablelist>\n");
+                for item in &list.items {
+                    self.out.push_str("<varlistentry>\n<term>");
+                    if let Some(term) = &item.term {
+                        self.write_inlines(term);
+                    }
+                    self.out.push_str("</term>\n<listitem>\n");
+                    self.write_required_blocks(&item.content);
+                    self.out.push_str("</listitem>\n</varlistentry>\n");
+                }
+                self.out.push_str("</variablelist>\n");
+                return;
+            }
+            (ListType::Callout, _) if self.write_calloutlist(list) => return,
+            (ListType::Unordered, _) if list.items.iter().any(|i| i.checked.is_some()) => {
+                ("<itemizedlist role=\"checklist\">", "</itemizedlist>")
+            }
+            (ListType::Unordered, _) => ("<itemizedlist>", "</itemizedlist>"),
+            (ListType::Ordered, Some(NumberingStyle::LowerAlpha)) => {
+                ("<orderedlist numeration=\"loweralpha\">", "</orderedlist>")
+            }
+            (ListType::Ordered, Some(NumberingStyle::UpperAlpha)) => {
+                ("<orderedlist numeration=\"upperalpha\">", "</orderedlist>")
+            }
+            (ListType::Ordered, Some(NumberingStyle::LowerRoman)) => {
+                ("<orderedlist numeration=\"lowerroman\">", "</orderedlist>")
+            }
+            (ListType::Ordered, Some(NumberingStyle::UpperRoman)) => {
+                ("<orderedlist numeration=\"upperroman\">", "</orderedlist>")
+            }
+            (ListType::Ordered | ListType::Callout, _) => ("<orderedlist>", "</orderedlist>"),
+        };
+        let levels = list.items.iter().map(|item| item.level as usize);
+        for step in nesting(levels) {
+            match step {
+                Nesting::Open => {
+                    self.out.push_str(open);
+                    self.out.push('\n');
+                }
+                Nesting::Item(index) => {
+                    let item = &list.items[index];
+                    match item.checked {
+                        Some(true) => self.out.push_str("<listitem role=\"checked\">\n"),
+                        Some(false) => self.out.push_str("<listitem role=\"unchecked\">\n"),
+                        None => self.out.push_str("<listitem>\n"),
+                    }
+                    self.write_required_blocks(&item.content);
+                }
+                Nesting::EndItem => self.out.push_str("</listitem>\n"),
+                Nesting::Close => {
+                    self.out.push_str(close);
+                    self.out.push('\n');
+                }
+            }
+        }
+    }
+
+    /// Write a callout list pointing at the callouts of the last listing,
+    /// returning false if the listing has no callout for some item
+    fn write_calloutlist(&mut self, list: &List) -> bool {
+        let numbers = 1..=list.items.len() as u32;
+        if list.items.is_empty() || !numbers.clone().all(|n| self.callouts.contains_key(&n)) {
+            return false;
+        }
+        self.out.push_str("<calloutlist>\n");
+        for (number, item) in numbers.zip(&list.items) {
+            let arearefs = self.callouts[&number].join(" ");
+            self.out.push_str(&format!(
+                "<callout arearefs=\"{}\">\n",
+                escape_attr(&arearefs)
+            ));
+            self.write_required_blocks(&item.content);
+            self.out.push_str("</callout>\n");
+        }
+        self.out.push_str("</calloutlist>\n");
+        true
+    }
+
+    fn write_table(&mut self, table: &Table) {
+        let id = self.label_attr();
+        let (columns, starts) = layout(table);
+        if columns == 0 {
+            return;
+        }
+        match &table.caption {
+            Some(caption) => {
+                self.out.push_str(&format!(
+                    "<table{} frame=\"all\" rowsep=\"1\" colsep=\"1\">\n<title>",
+                    id
+                ));
+                self.write_inlines(caption);
+                self.out.push_str("</title>\n");
+            }
+            None => self.out.push_str(&format!(
+                "<informaltable{} frame=\"all\" rowsep=\"1\" colsep=\"1\">\n",
+                id
+            )),
+        }
+        self.out
+            .push_str(&format!("<tgroup cols=\"{}\">\n", columns));
+        for column in 0..columns {
+            let spec = table.columns.get(column);
+            self.out
+                .push_str(&format!("<colspec colname=\"c{}\"", column + 1));
+            if let Some(width) = spec.and_then(|spec| spec.width) {
+                self.out.push_str(&format!(" colwidth=\"{}*\"", width));
+            }
+            self.out.push_str("/>\n");
+        }
+
+        let has_body = table.rows.iter().any(|r| !r.is_header && !r.is_footer);
+        for group in ROW_GROUPS {
+            let rows: Vec<_> = table
+                .rows
+                .iter()
+                .zip(&starts)
+                .filter(|(row, _)| row_group(row, has_body) == group)
+                .collect();
+            if rows.is_empty() {
+                continue;
+            }
+            self.out.push_str(&format!("<{}>\n", group));
+            for (row, starts) in rows {
+                self.write_row(table, row, starts);
+            }
+            self.out.push_str(&format!("</{}>\n", group));
+        }
+
+        self.out.push_str("</tgroup>\n");
+        match &table.caption {
+            Some(_) => self.out.push_str("</table>\n"),
+            None => self.out.push_str("</informaltable>\n"),
+        }
+    }
+
+    /// Write a row whose cells start at the columns in `starts`
+    fn write_row(&mut self, table: &Table, row: &TableRow, starts: &[usize]) {
+        self.out.push_str("<row>\n");
+        for (cell, &start) in row.cells.iter().zip(starts) {
+            self.out.push_str("<entry");
+            if cell.colspan > 1 {
+                self.out.push_str(&format!(
+                    " namest=\"c{}\" nameend=\"c{}\"",
+                    start + 1,
+                    start + cell.colspan as usize
+                ));
+            }
+            if cell.rowspan > 1 {
+                self.out
+                    .push_str(&format!(" morerows=\"{}\"", cell.rowspan - 1));
+            }
+            let align = cell
+                .align
+                .or_else(|| table.columns.get(start).and_then(|c| c.align));
+            match align {
+                Some(Alignment::Left) => self.out.push_str(" align=\"left\""),
+                Some(Alignment::Center) => self.out.push_str(" align=\"center\""),
+                Some(Alignment::Right) => self.out.push_str(" align=\"right\""),
+                None => {}
+            }
+            self.out.push_str(">\n");
+            self.write_blocks(&cell.content);
+            self.out.push_str("</entry>\n");
+        }
+        self.out.push_str("</row>\n");
+    }
+
+    fn write_admonition(&mut self, admonition: &Admonition) {
+        let tag = match admonition.admonition_type {
+            AdmonitionType::Note => "note",
+            AdmonitionType::Tip => "tip",
+            AdmonitionType::Important => "important",
+            AdmonitionType::Warning => "warning",
+            AdmonitionType::Caution => "caution",
+        };
+        self.out.push_str(&format!("<{}>\n", tag));
+        if let Some(title) = &admonition.title {
+            self.out.push_str("<title>");
+            self.write_inlines(title);
+            self.out.push_str("</title>\n");
+        }
+        self.write_required_blocks(&admonition.content);
+        self.out.push_str(&format!("</{}>\n", tag));
+    }
+
+    fn write_quote(&mut self, quote: &QuoteBlock) {
+        self.out.push_str("<blockquote>\n");
+        self.write_title(quote.title.as_deref());
+        if quote.attribution.is_some() || quote.cite.is_some() {
+            self.out.push_str("<attribution>");
+            if let Some(attribution) = &quote.attribution {
+                self.out.push_str(&escape_text(attribution));
+            }
+            if let Some(cite) = &quote.cite {
+                self.out
+                    .push_str(&format!("<citetitle>{}</citetitle>", escape_text(cite)));
+            }
+            self.out.push_str("</attribution>\n");
+        }
+        self.write_required_blocks(&quote.blocks);
+        self.out.push_str("</blockquote>\n");
+    }
+
+    fn write_literal(&mut self, literal: &LiteralBlock) {
+        let style = literal.style_id.as_deref();
+        // Passthrough content is written as is, unless it was written for Word
+        if style == Some("pass") && !literal.content.trim_start().starts_with("<w:") {
+            self.out.push_str(&literal.content);
+            self.out.push('\n');
+            return;
+        }
+
+        if let Some(title) = &literal.title {
+            self.out.push_str(&format!(
+                "<formalpara>\n<title>{}</title>\n<para>\n",
+                escape_text(title)
+            ));
+        }
+        // Diagrams are kept as source, in the diagram language; literal
+        // blocks have none
+        match literal
+            .language
+            .as_deref()
+            .or(style.filter(|&style| !matches!(style, "pass" | "literal")))
+        {
+            Some(language) => self.out.push_str(&format!(
+                "<programlisting language=\"{}\">",
+                escape_attr(language)
+            )),
+            None => self.out.push_str("<programlisting>"),
+        }
+
+        let mut callouts: HashMap<u32, Vec<String>> = HashMap::new();
+        if !literal.callouts.is_empty() {
+            self.listing_count += 1;
+        }
+        for (index, line) in literal.content.split('\n').enumerate() {
+            let number = index as u32 + 1;
+            if index > 0 {
+                self.out.push('\n');
+            }
+            let highlighted = literal.highlight_lines.contains(&number);
+            if highlighted {
+                self.out.push_str("<emphasis role=\"marked\">");
+            }
+            self.out.push_str(&escape_text(line));
+            for (position, callout) in literal.callouts.iter().enumerate() {
+                if callout.line != number {
+                    continue;
+                }
+                let id = format!("_callout_{}_{}", self.listing_count, position + 1);
+                self.out.push_str(&format!(" <co xml:id=\"{}\"/>", id));
+                callouts.entry(callout.number).or_default().push(id);
+            }
+            if highlighted {
+                self.out.push_str("</emphasis>");
+            }
+        }
+        self.out.push_str("</programlisting>\n");
+        if literal.title.is_some() {
+            self.out.push_str("</para>\n</formalpara>\n");
+        }
+        if !callouts.is_empty() {
+            self.callouts = callouts;
+        }
+    }
+
+    fn write_inlines(&mut self, inlines: &[Inline]) {
+        for inline in inlines {
+            self.write_inline(inline);
+        }
+    }
+
+    fn write_inline(&mut self, inline: &Inline) {
+        match inline {
+            Inline::Text(text) => self.out.push_str(&escape_text(text)),
+            Inline::Format(format_type, inner) => {
+                let (open, close) = match format_type {
+                    FormatType::Bold => ("<emphasis role=\"strong\">", "</emphasis>"),
+                    FormatType::Italic => ("<emphasis>", "</emphasis>"),
+                    FormatType::Monospace => ("<literal>", "</literal>"),
+                    FormatType::Highlight => ("<emphasis role=\"marked\">", "</emphasis>"),
+                    FormatType::Superscript => ("<superscript>", "</superscript>"),
+                    FormatType::Subscript => ("<subscript>", "</subscript>"),
+                };
+                self.out.push_str(open);
+                self.write_inline(inner);
+                self.out.push_str(close);
+            }
+            Inline::Span(inlines) => self.write_inlines(inlines),
+            Inline::Link(link) => {
+                let target = link
+                    .url
+                    .strip_prefix('#')
+                    .map(|anchor| xml_id(self.contract, anchor))
+                    .filter(|id| self.outline.has_target(id));
+                match target {
+                    Some(id) if link.text.is_empty() => self
+                        .out
+                        .push_str(&format!("<xref linkend=\"{}\"/>", escape_attr(&id))),
+                    Some(id) => {
+                        self.out
+                            .push_str(&format!("<link linkend=\"{}\">", escape_attr(&id)));
+                        self.write_inlines(&link.text);
+                        self.out.push_str("</link>");
+                    }
+                    // Links to anchors missing from the document stay plain
+                    // URIs, since a dangling linkend is invalid
+                    None => {
+                        self.out
+                            .push_str(&format!("<link xlink:href=\"{}\">", escape_attr(&link.url)));
+                        self.write_inlines(&link.text);
+                        self.out.push_str("</link>");
+                    }
+                }
+            }
+            Inline::Image(image) => {
+                self.out.push_str(&format!(
+                    "<inlinemediaobject><imageobject><imagedata fileref=\"{}\"/></imageobject>",
+                    escape_attr(&image.src)
+                ));
+                if let Some(alt) = &image.alt {
+                    self.out.push_str(&format!(
+                        "<textobject><phrase>{}</phrase></textobject>",
+                        escape_text(alt)
+                    ));
+                }
+                self.out.push_str("</inlinemediaobject>");
+            }
+            Inline::Break => self.out.push_str("<?asciidoc-br?>\n"),
+            Inline::Anchor(name) => {
+                let id = xml_id(self.contract, name);
+                if self.written.insert(id.clone()) {
+                    self.out
+                        .push_str(&format!("<anchor xml:id=\"{}\"/>", escape_attr(&id)));
+                }
+            }
+            Inline::Footnote(footnote) => {
+                // A later use of a footnote id refers back to the same note
+                let earlier = footnote
+                    .id
+                    .as_ref()
+                    .and_then(|id| self.note_ids.get(id))
+                    .filter(|_| footnote.content.is_empty());
+                if let Some(id) = earlier {
+                    self.out
+                        .push_str(&format!("<footnoteref linkend=\"{}\"/>", id));
+                    return;
+                }
+                self.note_count += 1;
+                let id = format!("_footnote_{}", self.note_count);
+                if let Some(name) = &footnote.id {
+                    self.note_ids.insert(name.clone(), id.clone());
+                }
+                self.out
+                    .push_str(&format!("<footnote xml:id=\"{}\"><para>", id));
+                self.write_inlines(&footnote.content);
+                self.out.push_str("</para></footnote>");
+            }
+        }
+    }
+}
+
+/// Number of columns of `table`, and the column each cell starts at
+///
+/// A cell spanning rows keeps its columns occupied in the rows below it.
+pub(crate) fn layout(table: &Table) -> (usize, Vec<Vec<usize>>) {
+    let mut columns = table.columns.len();
+    // Rows each column is still occupied for
+    let mut occupied: Vec<u32> = Vec::new();
+    let mut starts = Vec::with_capacity(table.rows.len());
+    for row in &table.rows {
+        let mut column = 0;
+        let mut row_starts = Vec::with_capacity(row.cells.len());
+        for cell in &row.cells {
+            while occupied.get(column).is_some_and(|&rows| rows > 0) {
+                column += 1;
+            }
+            row_starts.push(column);
+            let span = cell.colspan.max(1) as usize;
+            if occupied.len() < column + span {
+                occupied.resize(column + span, 0);
+            }
+            for rows in &mut occupied[column..column + span] {
+                *rows = cell.rowspan.max(1);
+            }
+            column += span;
+        }
+        columns = columns.max(occupied.len());
+        for rows in &mut occupied {
+            *rows = rows.saturating_sub(1);
+        }
+        starts.push(row_starts);
+    }
+    (columns, starts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utf8dok_ast::{Callout, Paragraph, TableCell};
+
+    fn text(t: &str) -> Inline {
+        Inline::Text(t.to_string())
+    }
+
+    fn paragraph(inlines: Vec<Inline>) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines,
+            style_id: None,
+            attributes: HashMap::new(),
+        })
+    }
+
+    fn cell(t: &str, colspan: u32, rowspan: u32) -> TableCell {
+        TableCell {
+            content: vec![paragraph(vec![text(t)])],
+            colspan,
+            rowspan,
+            align: None,
+            style: None,
+        }
+    }
+
+    fn row(cells: Vec<TableCell>) -> TableRow {
+        TableRow {
+            cells,
+            is_header: false,
+            is_footer: false,
+        }
+    }
+
+    /// The content of the article after its `<info>`
+    fn body(doc: &Document) -> String {
+        let xml = DocBookWriter::new().generate(doc);
+        let start = xml.find("</info>\n").unwrap() + 8;
+        let end = xml.find("</article>").unwrap();
+        xml[start..end].to_string()
+    }
+
+    #[test]
+    fn test_layout() {
+        // | a (2 rows) | b (2 cols) |
+        // |            | c   | d    |
+        let table = Table {
+            rows: vec![
+                row(vec![cell("a", 1, 2), cell("b", 2, 1)]),
+                row(vec![cell("c", 1, 1), cell("d", 1, 1)]),
+            ],
+            style_id: None,
+            caption: None,
+            columns: Vec::new(),
+        };
+        let (columns, starts) = layout(&table);
+        assert_eq!(columns, 3);
+        assert_eq!(starts, vec![vec![0, 1], vec![1, 2]]);
+    }
+
+    #[test]
+    fn test_empty_document_and_sections() {
+        assert_eq!(body(&Document::new()), "<para/>\n");
+
+        let mut doc = Document::new();
+        doc.push(Block::Heading(utf8dok_ast::Heading {
+            level: 1,
+            text: vec![text("Empty")],
+            style_id: None,
+            anchor: None,
+            role: None,
+        }));
+        doc.push(Block::Break(utf8dok_ast::BreakType::Page));
+        assert_eq!(
+            body(&doc),
+            "<section xml:id=\"_empty\">\n<title>Empty</title>\n<?asciidoc-pagebreak?>\n<para/>\n</section>\n"
+        );
+    }
+
+    #[test]
+    fn test_listing_with_callouts() {
+        let mut doc = Document::new();
+        doc.push(Block::Literal(LiteralBlock {
+            content: "let x = 1;\nlet y = x < 2;".to_string(),
+            language: Some("rust".to_string()),
+            title: None,
+            style_id: None,
+            callouts: vec![Callout { number: 1, line: 2 }],
+            highlight_lines: vec![1],
+        }));
+        doc.push(Block::List(List {
+            list_type: ListType::Callout,
+            items: vec![utf8dok_ast::ListItem {
+                content: vec![paragraph(vec![text("Compare")])],
+                level: 0,
+                term: None,
+                checked: None,
+            }],
+            style_id: None,
+            numbering: None,
+            start: None,
+        }));
+        assert_eq!(
+            body(&doc),
+            "<programlisting language=\"rust\"><emphasis role=\"marked\">let x = 1;</emphasis>\nlet y = x &lt; 2; <co xml:id=\"_callout_1_1\"/></programlisting>\n\
+             <calloutlist>\n<callout arearefs=\"_callout_1_1\">\n<para>Compare</para>\n</callout>\n</calloutlist>\n"
+        );
+    }
+}
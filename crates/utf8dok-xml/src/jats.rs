@@ -0,0 +1,731 @@
+//! JATS writer
+//!
+//! Renders a document as a JATS 1.3 (Archiving and Interchange) article:
+//! the title and authors go in `<front>`, sections become nested `<sec>`
+//! elements in `<body>`, and footnotes are collected into a `<fn-group>`
+//! in `<back>`, referenced by `<xref ref-type="fn">`. Tables use the XHTML
+//! table model inside `<table-wrap>`, which expresses spans directly.
+
+use std::collections::{HashMap, HashSet};
+
+use utf8dok_ast::{
+    Admonition, AdmonitionType, Alignment, Block, CellStyle, Document, FormatType, Image, Inline,
+    List, ListType, LiteralBlock, NumberingStyle, QuoteBlock, Table, TableCell,
+};
+use utf8dok_ooxml::StyleContract;
+
+use crate::outline::{label, xml_id, Outline, Target};
+use crate::xml::{escape_attr, escape_text, nesting, row_group, Nesting, ROW_GROUPS};
+
+/// JATS DTD version written in `dtd-version`
+pub const JATS_VERSION: &str = "1.3";
+
+/// JATS document writer
+///
+/// Ids are resolved as in the DocBook writer, including the original Word
+/// bookmark names restored through a style contract.
+#[derive(Default)]
+pub struct JatsWriter {
+    style_contract: Option<StyleContract>,
+}
+
+impl JatsWriter {
+    /// Create a writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style contract used for anchor names
+    pub fn set_style_contract(&mut self, contract: StyleContract) {
+        self.style_contract = Some(contract);
+    }
+
+    /// Generate a JATS article for the whole document
+    pub fn generate(&self, doc: &Document) -> String {
+        let contract = self.style_contract.as_ref();
+        let mut renderer = Renderer {
+            contract,
+            outline: Outline::new(doc, contract),
+            out: String::new(),
+            written: HashSet::new(),
+            label: None,
+            notes: Vec::new(),
+            note_ids: HashMap::new(),
+            in_cell: false,
+        };
+        renderer.article(doc);
+        renderer.out
+    }
+}
+
+/// Rendering state for one document
+struct Renderer<'a> {
+    contract: Option<&'a StyleContract>,
+    outline: Outline,
+    out: String,
+    /// Ids written so far: a repeated anchor is written once
+    written: HashSet<String>,
+    /// Id for the next figure or table, from the anchor paragraph above it
+    label: Option<String>,
+    /// Rendered content of each footnote, in order
+    notes: Vec<String>,
+    /// Number of each footnote with a name, for later references to it
+    note_ids: HashMap<String, usize>,
+    /// Writing a table cell, which holds inline content only
+    in_cell: bool,
+}
+
+impl Renderer<'_> {
+    fn article(&mut self, doc: &Document) {
+        let meta = &doc.metadata;
+        self.out
+            .push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.out.push_str("<!DOCTYPE article PUBLIC \"-//NLM//DTD JATS (Z39.96) Journal Archiving and Interchange DTD v1.3 20210610//EN\" \"JATS-archivearticle1-3.dtd\">\n");
+        self.out.push_str(&format!(
+            "<article xmlns:xlink=\"http://www.w3.org/1999/xlink\" dtd-version=\"{}\" article-type=\"other\" xml:lang=\"{}\">\n",
+            JATS_VERSION,
+            escape_attr(meta.attributes.get("lang").map_or("en", String::as_str))
+        ));
+
+        self.out.push_str("<front>\n<article-meta>\n");
+        if let Some(revision) = &meta.revision {
+            self.out.push_str(&format!(
+                "<article-version>{}</article-version>\n",
+                escape_text(revision)
+            ));
+        }
+        self.out.push_str(&format!(
+            "<title-group>\n<article-title>{}</article-title>\n</title-group>\n",
+            escape_text(meta.title.as_deref().unwrap_or("Untitled"))
+        ));
+        if !meta.authors.is_empty() {
+            self.out.push_str("<contrib-group>\n");
+            for author in &meta.authors {
+                self.out.push_str(&format!(
+                    "<contrib contrib-type=\"author\">\n<string-name>{}</string-name>\n</contrib>\n",
+                    escape_text(author)
+                ));
+            }
+            self.out.push_str("</contrib-group>\n");
+        }
+        self.out.push_str("</article-meta>\n</front>\n");
+
+        self.out.push_str("<body>\n");
+        self.write_body(doc);
+        self.out.push_str("</body>\n");
+        self.write_footnotes();
+        self.out.push_str("</article>\n");
+    }
+
+    /// Write the top-level blocks, wrapping sections
+    fn write_body(&mut self, doc: &Document) {
+        let mut open_sections: Vec<u8> = Vec::new();
+        for (index, block) in doc.blocks.iter().enumerate() {
+            if self.outline.is_absorbed(index) {
+                continue;
+            }
+            if let Some((anchor, _)) = label(block, doc.blocks.get(index + 1)) {
+                self.label = Some(xml_id(self.contract, anchor));
+                continue;
+            }
+            let Block::Heading(heading) = block else {
+                self.write_block(block);
+                continue;
+            };
+            while open_sections
+                .last()
+                .is_some_and(|&level| level >= heading.level)
+            {
+                open_sections.pop();
+                self.out.push_str("</sec>\n");
+            }
+            open_sections.push(heading.level);
+            let id = self
+                .outline
+                .heading_id(index)
+                .unwrap_or_default()
+                .to_string();
+            self.written.insert(id.clone());
+            self.out
+                .push_str(&format!("<sec id=\"{}\">\n<title>", escape_attr(&id)));
+            self.write_inlines(&heading.text);
+            self.out.push_str("</title>\n");
+        }
+        for _ in open_sections {
+            self.out.push_str("</sec>\n");
+        }
+    }
+
+    fn write_blocks(&mut self, blocks: &[Block]) {
+        for (index, block) in blocks.iter().enumerate() {
+            if let Some((anchor, _)) = label(block, blocks.get(index + 1)) {
+                self.label = Some(xml_id(self.contract, anchor));
+                continue;
+            }
+            self.write_block(block);
+        }
+    }
+
+    /// The `id` attribute of a figure or table, from its label
+    fn label_attr(&mut self) -> String {
+        match self.label.take() {
+            Some(id) if self.written.insert(id.clone()) => {
+                format!(" id=\"{}\"", escape_attr(&id))
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Write blocks into an element that needs at least one
+    fn write_required_blocks(&mut self, blocks: &[Block]) {
+        let before = self.out.len();
+        self.write_blocks(blocks);
+        if self.out.len() == before {
+            self.out.push_str("<p/>\n");
+        }
+    }
+
+    fn write_block(&mut self, block: &Block) {
+        match block {
+            Block::Paragraph(p) => match p.inlines.as_slice() {
+                [Inline::Image(image)] => self.write_figure(image, p.attributes.get("title")),
+                inlines => {
+                    self.out.push_str("<p>");
+                    self.write_inlines(inlines);
+                    self.out.push_str("</p>\n");
+                }
+            },
+            Block::Heading(heading) => {
+                // A heading inside another block cannot open a section
+                self.out.push_str("<p content-type=\"heading\"><bold>");
+                if let Some(anchor) = &heading.anchor {
+                    self.write_inline(&Inline::Anchor(anchor.clone()));
+                }
+                self.write_inlines(&heading.text);
+                self.out.push_str("</bold></p>\n");
+            }
+            Block::List(list) => self.write_list(list),
+            Block::Table(table) => self.write_table(table),
+            Block::Admonition(admonition) => self.write_admonition(admonition),
+            Block::Literal(literal) => self.write_literal(literal),
+            Block::Break(_) => self.out.push_str("<?asciidoc-pagebreak?>\n"),
+            Block::Open(open) => {
+                if let Some(title) = &open.title {
+                    self.out.push_str(&format!(
+                        "<p content-type=\"title\">{}</p>\n",
+                        escape_text(title)
+                    ));
+                }
+                self.write_blocks(&open.blocks);
+            }
+            Block::Sidebar(sidebar) => {
+                self.out.push_str("<boxed-text content-type=\"sidebar\">\n");
+                self.write_caption(sidebar.title.as_deref());
+                self.write_blocks(&sidebar.blocks);
+                self.out.push_str("</boxed-text>\n");
+            }
+            Block::Quote(quote) => self.write_quote(quote),
+            Block::ThematicBreak => self.out.push_str("<?asciidoc-hr?>\n"),
+            // Comments are not rendered
+            Block::Comment(_) => {}
+        }
+    }
+
+    /// Write a `<caption>` holding a plain text title
+    fn write_caption(&mut self, title: Option<&str>) {
+        if let Some(title) = title {
+            self.out.push_str(&format!(
+                "<caption>\n<title>{}</title>\n</caption>\n",
+                escape_text(title)
+            ));
+        }
+    }
+
+    /// Write an image paragraph as a figure
+    fn write_figure(&mut self, image: &Image, title: Option<&String>) {
+        let id = self.label_attr();
+        self.out.push_str(&format!("<fig{}>\n", id));
+        self.write_caption(title.map(String::as_str));
+        self.out.push_str(&format!(
+            "<graphic xlink:href=\"{}\"",
+            escape_attr(&image.src)
+        ));
+        match &image.alt {
+            Some(alt) => self.out.push_str(&format!(
+                ">\n<alt-text>{}</alt-text>\n</graphic>\n",
+                escape_text(alt)
+            )),
+            None => self.out.push_str("/>\n"),
+        }
+        self.out.push_str("</fig>\n");
+    }
+
+    fn write_list(&mut self, list: &List) {
+        let list_type = match (&list.list_type, list.numbering) {
+            (ListType::Description, _) => {
+                self.out.push_str("<def-list>\n");
+                for item in &list.items {
+                    self.out.push_str("<def-item>\n<term>");
+                    if let Some(term) = &item.term {
+                        self.write_inlines(term);
+                    }
+                    self.out.push_str("</term>\n<def>\n");
+                    self.write_required_blocks(&item.content);
+                    self.out.push_str("</def>\n</def-item>\n");
+                }
+                self.out.push_str("</def-list>\n");
+                return;
+            }
+            (ListType::Unordered, _) => "bullet",
+            (ListType::Ordered, Some(NumberingStyle::LowerAlpha)) => "alpha-lower",
+            (ListType::Ordered, Some(NumberingStyle::UpperAlpha)) => "alpha-upper",
+            (ListType::Ordered, Some(NumberingStyle::LowerRoman)) => "roman-lower",
+            (ListType::Ordered, Some(NumberingStyle::UpperRoman)) => "roman-upper",
+            (ListType::Ordered | ListType::Callout, _) => "order",
+        };
+        let levels = list.items.iter().map(|item| item.level as usize);
+        for step in nesting(levels) {
+            match step {
+                Nesting::Open => self
+                    .out
+                    .push_str(&format!("<list list-type=\"{}\">\n", list_type)),
+                Nesting::Item(index) => {
+                    let item = &list.items[index];
+                    self.out.push_str("<list-item>\n");
+                    // JATS has no checklists; the box is kept as the label
+                    match item.checked {
+                        Some(true) => self.out.push_str("<label>\u{2611}</label>\n"),
+                        Some(false) => self.out.push_str("<label>\u{2610}</label>\n"),
+                        None => {}
+                    }
+                    self.write_required_blocks(&item.content);
+                }
+                Nesting::EndItem => self.out.push_str("</list-item>\n"),
+                Nesting::Close => self.out.push_str("</list>\n"),
+            }
+        }
+    }
+
+    fn write_table(&mut self, table: &Table) {
+        let id = self.label_attr();
+        if table.rows.is_empty() {
+            return;
+        }
+        self.out.push_str(&format!("<table-wrap{}>\n", id));
+        if let Some(caption) = &table.caption {
+            self.out.push_str("<caption>\n<title>");
+            self.write_inlines(caption);
+            self.out.push_str("</title>\n</caption>\n");
+        }
+        self.out.push_str("<table>\n");
+
+        let total: u32 = table.columns.iter().filter_map(|c| c.width).sum();
+        if total > 0 {
+            self.out.push_str("<colgroup>\n");
+            for column in &table.columns {
+                match column.width {
+                    Some(width) => self.out.push_str(&format!(
+                        "<col width=\"{:.2}%\"/>\n",
+                        width as f64 * 100.0 / total as f64
+                    )),
+                    None => self.out.push_str("<col/>\n"),
+                }
+            }
+            self.out.push_str("</colgroup>\n");
+        }
+
+        let has_body = table.rows.iter().any(|r| !r.is_header && !r.is_footer);
+        for group in ROW_GROUPS {
+            let rows: Vec<_> = table
+                .rows
+                .iter()
+                .filter(|row| row_group(row, has_body) == group)
+                .collect();
+            if rows.is_empty() {
+                continue;
+            }
+            self.out.push_str(&format!("<{}>\n", group));
+            for row in rows {
+                self.out.push_str("<tr>\n");
+                let mut column = 0;
+                for cell in &row.cells {
+                    let align = cell
+                        .align
+                        .or_else(|| table.columns.get(column).and_then(|c| c.align));
+                    self.write_cell(cell, row.is_header && has_body, align);
+                    column += cell.colspan.max(1) as usize;
+                }
+                self.out.push_str("</tr>\n");
+            }
+            self.out.push_str(&format!("</{}>\n", group));
+        }
+
+        self.out.push_str("</table>\n</table-wrap>\n");
+    }
+
+    fn write_cell(&mut self, cell: &TableCell, header_row: bool, align: Option<Alignment>) {
+        let header = header_row || cell.style == Some(CellStyle::Header);
+        let tag = if header { "th" } else { "td" };
+        let mut attributes = String::new();
+        if cell.colspan > 1 {
+            attributes.push_str(&format!(" colspan=\"{}\"", cell.colspan));
+        }
+        if cell.rowspan > 1 {
+            attributes.push_str(&format!(" rowspan=\"{}\"", cell.rowspan));
+        }
+        match align {
+            Some(Alignment::Left) => attributes.push_str(" align=\"left\""),
+            Some(Alignment::Center) => attributes.push_str(" align=\"center\""),
+            Some(Alignment::Right) => attributes.push_str(" align=\"right\""),
+            None => {}
+        }
+        self.out.push_str(&format!("<{}{}>", tag, attributes));
+        self.in_cell = true;
+        let mut first = true;
+        self.write_cell_blocks(&cell.content, &mut first);
+        self.in_cell = false;
+        self.out.push_str(&format!("</{}>\n", tag));
+    }
+
+    /// Write the content of a cell as lines of inline content, since a
+    /// cell holds no paragraphs
+    fn write_cell_blocks(&mut self, blocks: &[Block], first: &mut bool) {
+        for block in blocks {
+            let inlines = match block {
+                Block::Paragraph(p) => Some(p.inlines.as_slice()),
+                Block::Heading(heading) => Some(heading.text.as_slice()),
+                _ => None,
+            };
+            if let Some(inlines) = inlines {
+                self.cell_line(first);
+                self.write_inlines(inlines);
+                continue;
+            }
+            match block {
+                Block::Literal(literal) => {
+                    for line in literal.content.split('\n') {
+                        self.cell_line(first);
+                        self.out
+                            .push_str(&format!("<monospace>{}</monospace>", escape_text(line)));
+                    }
+                }
+                Block::List(list) => {
+                    for item in &list.items {
+                        if let Some(term) = &item.term {
+                            self.cell_line(first);
+                            self.write_inlines(term);
+                        }
+                        self.write_cell_blocks(&item.content, first);
+                    }
+                }
+                Block::Table(table) => {
+                    for row in &table.rows {
+                        for cell in &row.cells {
+                            self.write_cell_blocks(&cell.content, first);
+                        }
+                    }
+                }
+                Block::Admonition(admonition) => self.write_cell_blocks(&admonition.content, first),
+                Block::Open(open) => self.write_cell_blocks(&open.blocks, first),
+                Block::Sidebar(sidebar) => self.write_cell_blocks(&sidebar.blocks, first),
+                Block::Quote(quote) => self.write_cell_blocks(&quote.blocks, first),
+                _ => {}
+            }
+        }
+    }
+
+    /// Start a line of cell content
+    fn cell_line(&mut self, first: &mut bool) {
+        if !*first {
+            self.out.push_str("<break/>");
+        }
+        *first = false;
+    }
+
+    fn write_admonition(&mut self, admonition: &Admonition) {
+        let (content_type, label) = match admonition.admonition_type {
+            AdmonitionType::Note => ("note", "Note"),
+            AdmonitionType::Tip => ("tip", "Tip"),
+            AdmonitionType::Important => ("important", "Important"),
+            AdmonitionType::Warning => ("warning", "Warning"),
+            AdmonitionType::Caution => ("caution", "Caution"),
+        };
+        self.out.push_str(&format!(
+            "<boxed-text content-type=\"{}\">\n<caption>\n<title>",
+            content_type
+        ));
+        match &admonition.title {
+            Some(title) => self.write_inlines(title),
+            None => self.out.push_str(label),
+        }
+        self.out.push_str("</title>\n</caption>\n");
+        self.write_blocks(&admonition.content);
+        self.out.push_str("</boxed-text>\n");
+    }
+
+    fn write_quote(&mut self, quote: &QuoteBlock) {
+        self.out.push_str("<disp-quote>\n");
+        if let Some(title) = &quote.title {
+            self.out
+                .push_str(&format!("<title>{}</title>\n", escape_text(title)));
+        }
+        self.write_required_blocks(&quote.blocks);
+        if quote.attribution.is_some() || quote.cite.is_some() {
+            self.out.push_str("<attrib>");
+            if let Some(attribution) = &quote.attribution {
+                self.out.push_str(&escape_text(attribution));
+            }
+            if let Some(cite) = &quote.cite {
+                if quote.attribution.is_some() {
+                    self.out.push_str(", ");
+                }
+                self.out
+                    .push_str(&format!("<italic>{}</italic>", escape_text(cite)));
+            }
+            self.out.push_str("</attrib>\n");
+        }
+        self.out.push_str("</disp-quote>\n");
+    }
+
+    fn write_literal(&mut self, literal: &LiteralBlock) {
+        let style = literal.style_id.as_deref();
+        // Passthrough content is written as is, unless it was written for Word
+        if style == Some("pass") && !literal.content.trim_start().starts_with("<w:") {
+            self.out.push_str(&literal.content);
+            self.out.push('\n');
+            return;
+        }
+
+        if let Some(title) = &literal.title {
+            self.out.push_str("<boxed-text content-type=\"listing\">\n");
+            self.write_caption(Some(title));
+        }
+        // Diagrams are kept as source, in the diagram language; literal
+        // blocks have none
+        match literal
+            .language
+            .as_deref()
+            .or(style.filter(|&style| !matches!(style, "pass" | "literal")))
+        {
+            Some(language) => self
+                .out
+                .push_str(&format!("<code language=\"{}\">", escape_attr(language))),
+            None => self.out.push_str("<code>"),
+        }
+        for (index, line) in literal.content.split('\n').enumerate() {
+            let number = index as u32 + 1;
+            if index > 0 {
+                self.out.push('\n');
+            }
+            self.out.push_str(&escape_text(line));
+            // Callouts have no JATS element; their numbers stay in the code
+            for callout in literal.callouts.iter().filter(|c| c.line == number) {
+                self.out.push_str(&format!(" ({})", callout.number));
+            }
+        }
+        self.out.push_str("</code>\n");
+        if literal.title.is_some() {
+            self.out.push_str("</boxed-text>\n");
+        }
+    }
+
+    fn write_inlines(&mut self, inlines: &[Inline]) {
+        for inline in inlines {
+            self.write_inline(inline);
+        }
+    }
+
+    fn write_inline(&mut self, inline: &Inline) {
+        match inline {
+            Inline::Text(text) => self.out.push_str(&escape_text(text)),
+            Inline::Format(format_type, inner) => {
+                let (open, close) = match format_type {
+                    FormatType::Bold => ("<bold>", "</bold>"),
+                    FormatType::Italic => ("<italic>", "</italic>"),
+                    FormatType::Monospace => ("<monospace>", "</monospace>"),
+                    FormatType::Highlight => (
+                        "<styled-content style-type=\"highlight\">",
+                        "</styled-content>",
+                    ),
+                    FormatType::Superscript => ("<sup>", "</sup>"),
+                    FormatType::Subscript => ("<sub>", "</sub>"),
+                };
+                self.out.push_str(open);
+                self.write_inline(inner);
+                self.out.push_str(close);
+            }
+            Inline::Span(inlines) => self.write_inlines(inlines),
+            Inline::Link(link) => {
+                let target = link
+                    .url
+                    .strip_prefix('#')
+                    .map(|anchor| xml_id(self.contract, anchor))
+                    .filter(|id| self.outline.has_target(id));
+                match target {
+                    Some(id) => {
+                        let ref_type = match self.outline.kind(&id) {
+                            Some(Target::Section) => "sec",
+                            Some(Target::Figure) => "fig",
+                            Some(Target::Table) => "table",
+                            None => "other",
+                        };
+                        self.out.push_str(&format!(
+                            "<xref ref-type=\"{}\" rid=\"{}\">",
+                            ref_type,
+                            escape_attr(&id)
+                        ));
+                        self.write_inlines(&link.text);
+                        self.out.push_str("</xref>");
+                    }
+                    // Links to anchors missing from the document stay plain
+                    // URIs, since a dangling rid is invalid
+                    None => {
+                        self.out.push_str(&format!(
+                            "<ext-link ext-link-type=\"uri\" xlink:href=\"{}\">",
+                            escape_attr(&link.url)
+                        ));
+                        if link.text.is_empty() {
+                            self.out.push_str(&escape_text(&link.url));
+                        }
+                        self.write_inlines(&link.text);
+                        self.out.push_str("</ext-link>");
+                    }
+                }
+            }
+            Inline::Image(image) => {
+                self.out.push_str(&format!(
+                    "<inline-graphic xlink:href=\"{}\"",
+                    escape_attr(&image.src)
+                ));
+                match &image.alt {
+                    Some(alt) => self.out.push_str(&format!(
+                        "><alt-text>{}</alt-text></inline-graphic>",
+                        escape_text(alt)
+                    )),
+                    None => self.out.push_str("/>"),
+                }
+            }
+            Inline::Break if self.in_cell => self.out.push_str("<break/>"),
+            Inline::Break => self.out.push_str("<?asciidoc-br?>\n"),
+            Inline::Anchor(name) => {
+                let id = xml_id(self.contract, name);
+                if self.written.insert(id.clone()) {
+                    self.out
+                        .push_str(&format!("<target id=\"{}\"/>", escape_attr(&id)));
+                }
+            }
+            Inline::Footnote(footnote) => {
+                // A later use of a footnote id refers back to the same note
+                let earlier = footnote
+                    .id
+                    .as_ref()
+                    .and_then(|id| self.note_ids.get(id))
+                    .copied()
+                    .filter(|_| footnote.content.is_empty());
+                let number = match earlier {
+                    Some(number) => number,
+                    None => {
+                        // Reserve the number before rendering, in case the
+                        // content holds a footnote of its own
+                        self.notes.push(String::new());
+                        let number = self.notes.len();
+                        if let Some(name) = &footnote.id {
+                            self.note_ids.insert(name.clone(), number);
+                        }
+                        let saved = std::mem::take(&mut self.out);
+                        self.write_inlines(&footnote.content);
+                        self.notes[number - 1] = std::mem::replace(&mut self.out, saved);
+                        number
+                    }
+                };
+                self.out.push_str(&format!(
+                    "<xref ref-type=\"fn\" rid=\"fn{0}\"><sup>{0}</sup></xref>",
+                    number
+                ));
+            }
+        }
+    }
+
+    /// Write the footnotes into `<back>`
+    fn write_footnotes(&mut self) {
+        if self.notes.is_empty() {
+            return;
+        }
+        self.out.push_str("<back>\n<fn-group>\n");
+        for (index, content) in std::mem::take(&mut self.notes).into_iter().enumerate() {
+            self.out.push_str(&format!(
+                "<fn id=\"fn{0}\">\n<label>{0}</label>\n<p>{1}</p>\n</fn>\n",
+                index + 1,
+                content
+            ));
+        }
+        self.out.push_str("</fn-group>\n</back>\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utf8dok_ast::{Footnote, Paragraph, TableRow};
+
+    fn text(t: &str) -> Inline {
+        Inline::Text(t.to_string())
+    }
+
+    fn paragraph(inlines: Vec<Inline>) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines,
+            style_id: None,
+            attributes: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_footnotes_go_to_back() {
+        let mut doc = Document::new();
+        doc.push(paragraph(vec![
+            text("A"),
+            Inline::Footnote(Footnote {
+                id: Some("src".to_string()),
+                content: vec![text("Source.")],
+            }),
+            text(" B"),
+            Inline::Footnote(Footnote {
+                id: Some("src".to_string()),
+                content: Vec::new(),
+            }),
+        ]));
+        let xml = JatsWriter::new().generate(&doc);
+        assert!(xml.contains(
+            "<p>A<xref ref-type=\"fn\" rid=\"fn1\"><sup>1</sup></xref> B<xref ref-type=\"fn\" rid=\"fn1\"><sup>1</sup></xref></p>"
+        ));
+        assert!(xml.contains(
+            "</body>\n<back>\n<fn-group>\n<fn id=\"fn1\">\n<label>1</label>\n<p>Source.</p>\n</fn>\n</fn-group>\n</back>\n</article>"
+        ));
+    }
+
+    #[test]
+    fn test_cell_content_is_inline() {
+        let mut doc = Document::new();
+        doc.push(Block::Table(Table {
+            rows: vec![TableRow {
+                cells: vec![TableCell {
+                    content: vec![paragraph(vec![text("one")]), paragraph(vec![text("two")])],
+                    colspan: 2,
+                    rowspan: 1,
+                    align: Some(Alignment::Right),
+                    style: None,
+                }],
+                is_header: false,
+                is_footer: false,
+            }],
+            style_id: None,
+            caption: None,
+            columns: Vec::new(),
+        }));
+        let xml = JatsWriter::new().generate(&doc);
+        assert!(xml.contains(
+            "<table-wrap>\n<table>\n<tbody>\n<tr>\n<td colspan=\"2\" align=\"right\">one<break/>two</td>\n</tr>\n</tbody>\n</table>\n</table-wrap>\n"
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+//! utf8dok-xml - DocBook 5 and JATS generation
+//!
+//! This crate renders utf8dok documents as XML for publishing toolchains:
+//! DocBook 5 articles, and JATS articles for journal workflows.
+//!
+//! # Features
+//!
+//! - Nested sections with ids matching the bookmarks of the DOCX and HTML
+//!   output, including the original Word bookmark names restored through a
+//!   `StyleContract`
+//! - Tables with column and row spans (CALS for DocBook, XHTML for JATS)
+//! - Figures with captions, footnotes and cross references
+//! - Cross references only to ids present in the document, so the output
+//!   stays valid when a link target is missing
+//!
+//! # Example
+//!
+//! ```
+//! use utf8dok_ast::{Block, Document, Heading, Inline};
+//! use utf8dok_xml::{render_docbook, render_jats};
+//!
+//! let mut doc = Document::with_title("Guide");
+//! doc.push(Block::Heading(Heading {
+//!     level: 1,
+//!     text: vec![Inline::Text("Setup".to_string())],
+//!     style_id: None,
+//!     anchor: None,
+//!     role: None,
+//! }));
+//!
+//! let docbook = render_docbook(&doc);
+//! assert!(docbook.contains("<section xml:id=\"_setup\">\n<title>Setup</title>"));
+//!
+//! let jats = render_jats(&doc);
+//! assert!(jats.contains("<sec id=\"_setup\">\n<title>Setup</title>"));
+//! ```
+
+mod docbook;
+mod jats;
+mod outline;
+mod xml;
+
+pub use docbook::{DocBookWriter, DOCBOOK_NAMESPACE};
+pub use jats::{JatsWriter, JATS_VERSION};
+
+/// Crate version
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Convenience function to render a document as a DocBook 5 article
+pub fn render_docbook(doc: &utf8dok_ast::Document) -> String {
+    DocBookWriter::new().generate(doc)
+}
+
+/// Convenience function to render a document as a JATS article
+pub fn render_jats(doc: &utf8dok_ast::Document) -> String {
+    JatsWriter::new().generate(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_structure() {
+        // Verify exports are accessible
+        let _ = DocBookWriter::generate;
+        let _ = JatsWriter::generate;
+        let _ = render_docbook;
+        let _ = render_jats;
+    }
+}
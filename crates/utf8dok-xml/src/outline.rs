@@ -0,0 +1,230 @@
+//! Document ids shared by the DocBook and JATS writers
+//!
+//! Both vocabularies nest sections, so every top-level heading needs an id
+//! before rendering starts, and cross references may only point at ids that
+//! exist: a validator rejects a dangling `linkend` or `rid`.
+//!
+//! As in the DOCX output, an anchor-only paragraph in front of a figure or
+//! table becomes the id of that figure or table.
+
+use std::collections::{HashMap, HashSet};
+
+use utf8dok_ast::{Block, Document, Inline, Node};
+use utf8dok_core::ids::{anchor_only, section_ids, IdSource};
+use utf8dok_ooxml::StyleContract;
+
+/// The kind of element an id names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Target {
+    Section,
+    Figure,
+    Table,
+}
+
+/// Ids of a document, computed before rendering
+pub(crate) struct Outline {
+    /// Section id by block index of the top-level heading
+    heading_ids: HashMap<usize, String>,
+    /// Anchor-only paragraphs whose anchor became the following heading's id
+    absorbed: HashSet<usize>,
+    /// What the ids of sections, figures and tables name
+    kinds: HashMap<String, Target>,
+    /// Every id in the document: sections and anchors
+    targets: HashSet<String>,
+}
+
+impl Outline {
+    /// Assign ids to the sections of `doc`
+    ///
+    /// A heading takes its own anchor, or the anchor on the line above it,
+    /// and otherwise an Asciidoctor-style id generated from its text. Anchor
+    /// names go through the style contract, as in the DOCX and HTML output.
+    pub fn new(doc: &Document, contract: Option<&StyleContract>) -> Self {
+        let mut targets = HashSet::new();
+        for block in &doc.blocks {
+            collect_anchors(Node::Block(block), contract, &mut |id| {
+                targets.insert(id);
+            });
+        }
+
+        let mut heading_ids = HashMap::new();
+        let mut absorbed = HashSet::new();
+        let mut kinds = HashMap::new();
+        visit_labels(
+            doc.blocks.iter().map(Node::Block).collect(),
+            &mut |anchor, target| {
+                kinds.insert(xml_id(contract, anchor), target);
+            },
+        );
+        for section in section_ids(doc) {
+            let Some(index) = section.block() else {
+                continue;
+            };
+            let id = match section.source {
+                IdSource::Generated => section.id,
+                IdSource::Anchor => xml_id(contract, &section.id),
+                IdSource::AnchorAbove => {
+                    absorbed.insert(index - 1);
+                    xml_id(contract, &section.id)
+                }
+            };
+            targets.insert(id.clone());
+            kinds.insert(id.clone(), Target::Section);
+            heading_ids.insert(index, id);
+        }
+
+        Self {
+            heading_ids,
+            absorbed,
+            kinds,
+            targets,
+        }
+    }
+
+    /// The id of the top-level heading at block `index`
+    pub fn heading_id(&self, index: usize) -> Option<&str> {
+        self.heading_ids.get(&index).map(String::as_str)
+    }
+
+    /// Whether the block at `index` is an anchor paragraph merged into a heading
+    pub fn is_absorbed(&self, index: usize) -> bool {
+        self.absorbed.contains(&index)
+    }
+
+    /// Whether `id` names an element of the document
+    pub fn has_target(&self, id: &str) -> bool {
+        self.targets.contains(id)
+    }
+
+    /// The kind of section, figure or table `id` names
+    pub fn kind(&self, id: &str) -> Option<Target> {
+        self.kinds.get(id).copied()
+    }
+}
+
+/// The anchor of `block` if it is an anchor-only paragraph naming `next`,
+/// a figure or table
+pub(crate) fn label<'a>(block: &'a Block, next: Option<&Block>) -> Option<(&'a str, Target)> {
+    let anchor = anchor_only(block)?;
+    match next? {
+        Block::Paragraph(p) if matches!(p.inlines.as_slice(), [Inline::Image(_)]) => {
+            Some((anchor, Target::Figure))
+        }
+        Block::Table(table) if !table.rows.is_empty() => Some((anchor, Target::Table)),
+        _ => None,
+    }
+}
+
+/// Call `found` with the label of every figure and table among sibling
+/// `nodes` and their descendants
+fn visit_labels<'a>(nodes: Vec<Node<'a>>, found: &mut dyn FnMut(&'a str, Target)) {
+    for (index, node) in nodes.iter().enumerate() {
+        if let Node::Block(block) = node {
+            let next = match nodes.get(index + 1) {
+                Some(Node::Block(next)) => Some(*next),
+                _ => None,
+            };
+            if let Some((anchor, target)) = label(block, next) {
+                found(anchor, target);
+            }
+        }
+        visit_labels(node.children(), found);
+    }
+}
+
+/// The XML id for an anchor: the Word bookmark name when the style contract
+/// maps it, made a valid NCName
+pub(crate) fn xml_id(contract: Option<&StyleContract>, anchor: &str) -> String {
+    let name = contract
+        .and_then(|contract| contract.get_word_bookmark(anchor))
+        .unwrap_or(anchor);
+    ncname(name)
+}
+
+/// `name` with the characters an XML id cannot hold replaced by `_`
+fn ncname(name: &str) -> String {
+    let mut id: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !id.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        id.insert(0, '_');
+    }
+    id
+}
+
+/// Call `found` with the id of every anchor in `node` and its descendants
+fn collect_anchors(
+    node: Node<'_>,
+    contract: Option<&StyleContract>,
+    found: &mut dyn FnMut(String),
+) {
+    match node {
+        Node::Inline(Inline::Anchor(name)) => found(xml_id(contract, name)),
+        Node::Block(Block::Heading(heading)) => {
+            if let Some(anchor) = &heading.anchor {
+                found(xml_id(contract, anchor));
+            }
+        }
+        _ => {}
+    }
+    for child in node.children() {
+        collect_anchors(child, contract, found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utf8dok_ast::{Heading, Paragraph};
+
+    fn heading(level: u8, text: &str) -> Block {
+        Block::Heading(Heading {
+            level,
+            text: vec![Inline::Text(text.to_string())],
+            style_id: None,
+            anchor: None,
+            role: None,
+        })
+    }
+
+    fn anchor(name: &str) -> Block {
+        Block::Paragraph(Paragraph {
+            inlines: vec![Inline::Anchor(name.to_string())],
+            style_id: None,
+            attributes: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_ncname() {
+        assert_eq!(ncname("_Toc123"), "_Toc123");
+        assert_eq!(ncname("2nd section"), "_2nd_section");
+        assert_eq!(ncname("a:b"), "a_b");
+        assert_eq!(ncname(""), "_");
+    }
+
+    #[test]
+    fn test_section_ids() {
+        let mut doc = Document::new();
+        doc.push(heading(1, "Getting Started!"));
+        doc.push(anchor("custom id"));
+        doc.push(heading(2, "Custom"));
+        doc.push(heading(1, "Getting started"));
+
+        let outline = Outline::new(&doc, None);
+        assert_eq!(outline.heading_id(0), Some("_getting_started"));
+        assert_eq!(outline.heading_id(2), Some("custom_id"));
+        assert_eq!(outline.heading_id(3), Some("_getting_started_2"));
+        assert!(outline.is_absorbed(1));
+        assert_eq!(outline.kind("custom_id"), Some(Target::Section));
+        assert!(outline.has_target("custom_id"));
+        assert!(!outline.has_target("missing"));
+    }
+}
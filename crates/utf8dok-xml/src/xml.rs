@@ -0,0 +1,140 @@
+//! Helpers shared by the XML writers
+
+/// Escape text content
+///
+/// Control characters other than tab and line breaks are dropped, since
+/// XML 1.0 cannot represent them at all.
+pub(crate) fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape an attribute value
+pub(crate) fn escape_attr(text: &str) -> String {
+    escape_text(text)
+        .replace('"', "&quot;")
+        .replace(['\n', '\r', '\t'], " ")
+}
+
+/// Table row groups, in the order they are written
+///
+/// Both DocBook (CALS) and JATS (XHTML) tables put the footer before the
+/// body.
+pub(crate) const ROW_GROUPS: [&str; 3] = ["thead", "tfoot", "tbody"];
+
+/// The row group a row is written in
+///
+/// A table without body rows puts every row in the body, which must not be
+/// empty.
+pub(crate) fn row_group(row: &utf8dok_ast::TableRow, has_body: bool) -> &'static str {
+    if !has_body {
+        "tbody"
+    } else if row.is_header {
+        "thead"
+    } else if row.is_footer {
+        "tfoot"
+    } else {
+        "tbody"
+    }
+}
+
+/// A step in writing items carrying a nesting level as nested lists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Nesting {
+    /// Open a list
+    Open,
+    /// Open the item at this index
+    Item(usize),
+    /// Close the current item
+    EndItem,
+    /// Close the current list
+    Close,
+}
+
+/// The steps for items at `levels`
+///
+/// A nested list opens inside the item before it. An item more than one
+/// level deeper than the previous one is nested only one level, as is the
+/// first item.
+pub(crate) fn nesting(levels: impl IntoIterator<Item = usize>) -> Vec<Nesting> {
+    let mut steps = vec![Nesting::Open];
+    let mut depth = 0;
+    let mut any = false;
+    for (index, level) in levels.into_iter().enumerate() {
+        let target = if index == 0 { 0 } else { level.min(depth + 1) };
+        if target > depth {
+            steps.push(Nesting::Open);
+            depth = target;
+        } else if index > 0 {
+            steps.push(Nesting::EndItem);
+            while depth > target {
+                steps.push(Nesting::Close);
+                steps.push(Nesting::EndItem);
+                depth -= 1;
+            }
+        }
+        steps.push(Nesting::Item(index));
+        any = true;
+    }
+    if any {
+        steps.push(Nesting::EndItem);
+    }
+    for _ in 0..depth {
+        steps.push(Nesting::Close);
+        steps.push(Nesting::EndItem);
+    }
+    steps.push(Nesting::Close);
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Nesting::*;
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(
+            escape_text("a < b && c\u{1} > d"),
+            "a &lt; b &amp;&amp; c &gt; d"
+        );
+        assert_eq!(escape_attr("say \"hi\"\nnow"), "say &quot;hi&quot; now");
+    }
+
+    #[test]
+    fn test_nesting() {
+        assert_eq!(
+            nesting([0, 1, 3, 0]),
+            vec![
+                Open,
+                Item(0),
+                Open,
+                Item(1),
+                Open,
+                Item(2),
+                EndItem,
+                Close,
+                EndItem,
+                Close,
+                EndItem,
+                Item(3),
+                EndItem,
+                Close
+            ]
+        );
+        assert_eq!(
+            nesting([0, 0]),
+            vec![Open, Item(0), EndItem, Item(1), EndItem, Close]
+        );
+    }
+}
@@ -0,0 +1,572 @@
+//! Content model checks for the generated XML
+//!
+//! The DocBook 5 and JATS content models of the elements the writers
+//! produce: the allowed and required children of each element in order,
+//! whether it holds text, its attributes and their values, and the
+//! integrity of ids and references. Anything the writers emit outside these
+//! elements is reported as an unknown element. The checks run without
+//! external tools and report errors by element; the schemas themselves are
+//! validated against in `schema`.
+
+use std::collections::{HashMap, HashSet};
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use regex::Regex;
+
+/// An element of a parsed document
+#[derive(Debug)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    /// Non-whitespace text directly inside the element
+    pub text: String,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// This element and its descendants named `name`, in document order
+    pub fn descendants<'a>(&'a self, name: &str) -> Vec<&'a Element> {
+        let mut found = Vec::new();
+        self.collect(name, &mut found);
+        found
+    }
+
+    fn collect<'a>(&'a self, name: &str, found: &mut Vec<&'a Element>) {
+        if self.name == name {
+            found.push(self);
+        }
+        for child in &self.children {
+            child.collect(name, found);
+        }
+    }
+}
+
+/// Parse a well-formed document into its root element
+pub fn parse(xml: &str) -> Element {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Element> = Vec::new();
+    loop {
+        match reader.read_event().expect("well-formed XML") {
+            Event::Start(start) => stack.push(element(&start)),
+            Event::Empty(start) => {
+                let empty = element(&start);
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(empty),
+                    None => return empty,
+                }
+            }
+            Event::End(_) => {
+                let done = stack.pop().expect("balanced tags");
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(done),
+                    None => return done,
+                }
+            }
+            Event::Text(text) => {
+                let text = text.unescape().expect("valid text");
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(text.trim());
+                }
+            }
+            Event::Eof => panic!("document has no root element"),
+            _ => {}
+        }
+    }
+}
+
+fn element(start: &quick_xml::events::BytesStart<'_>) -> Element {
+    let name = String::from_utf8(start.name().as_ref().to_vec()).unwrap();
+    let attributes = start
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute.expect("valid attribute");
+            let key = String::from_utf8(attribute.key.as_ref().to_vec()).unwrap();
+            let value = attribute
+                .unescape_value()
+                .expect("valid value")
+                .into_owned();
+            (key, value)
+        })
+        .collect();
+    Element {
+        name,
+        attributes,
+        children: Vec::new(),
+        text: String::new(),
+    }
+}
+
+/// The content model of an element
+struct Rule {
+    /// Pattern over the names of the children, each followed by a space;
+    /// `%NAME` expands to a class of elements
+    content: &'static str,
+    /// Whether the element may hold text
+    text: bool,
+    /// Attributes the element must have
+    required: &'static [&'static str],
+    /// Other attributes it may have
+    optional: &'static [&'static str],
+}
+
+const fn rule(
+    content: &'static str,
+    text: bool,
+    required: &'static [&'static str],
+    optional: &'static [&'static str],
+) -> Rule {
+    Rule {
+        content,
+        text,
+        required,
+        optional,
+    }
+}
+
+/// A hand-written subset of a content model
+struct ContentModel {
+    root: &'static str,
+    /// Attributes every element may have
+    common: &'static [&'static str],
+    classes: &'static [(&'static str, &'static str)],
+    rules: &'static [(&'static str, Rule)],
+    /// Enumerated attribute values, by element and attribute
+    values: &'static [(&'static str, &'static str, &'static [&'static str])],
+    /// The attribute holding ids, and those referencing them
+    id: &'static str,
+    references: &'static [&'static str],
+}
+
+const DOCBOOK_BLOCKS: &str = "para|formalpara|itemizedlist|orderedlist|variablelist|calloutlist|note|tip|important|warning|caution|programlisting|figure|informalfigure|table|informaltable|sidebar|blockquote|bridgehead";
+const DOCBOOK_INLINES: &str = "emphasis|literal|superscript|subscript|link|xref|anchor|footnote|footnoteref|inlinemediaobject";
+
+/// Subset of the DocBook 5.0 content model
+const DOCBOOK: ContentModel = ContentModel {
+    root: "article",
+    common: &["xml:id", "xml:lang", "role"],
+    classes: &[("BLOCK", DOCBOOK_BLOCKS), ("INLINE", DOCBOOK_INLINES)],
+    rules: &[
+        (
+            "article",
+            rule(
+                "info ((%BLOCK)+(section )*|(section )+)",
+                false,
+                &["version"],
+                &["xmlns", "xmlns:xlink"],
+            ),
+        ),
+        (
+            "info",
+            rule("title (author )*(releaseinfo )?", false, &[], &[]),
+        ),
+        ("author", rule("personname ", false, &[], &[])),
+        ("personname", rule("", true, &[], &[])),
+        ("releaseinfo", rule("", true, &[], &[])),
+        ("title", rule("(%INLINE)*", true, &[], &[])),
+        (
+            "section",
+            rule("title ((%BLOCK)+(section )*|(section )+)", false, &[], &[]),
+        ),
+        ("para", rule("(%INLINE|programlisting )*", true, &[], &[])),
+        ("formalpara", rule("title para ", false, &[], &[])),
+        ("bridgehead", rule("(%INLINE)*", true, &[], &["renderas"])),
+        ("itemizedlist", rule("(listitem )+", false, &[], &[])),
+        (
+            "orderedlist",
+            rule("(listitem )+", false, &[], &["numeration"]),
+        ),
+        ("listitem", rule("(%BLOCK)+", false, &[], &[])),
+        ("variablelist", rule("(varlistentry )+", false, &[], &[])),
+        ("varlistentry", rule("(term )+listitem ", false, &[], &[])),
+        ("term", rule("(%INLINE)*", true, &[], &[])),
+        ("calloutlist", rule("(callout )+", false, &[], &[])),
+        ("callout", rule("(%BLOCK)+", false, &["arearefs"], &[])),
+        ("note", rule("(title )?(%BLOCK)+", false, &[], &[])),
+        ("tip", rule("(title )?(%BLOCK)+", false, &[], &[])),
+        ("important", rule("(title )?(%BLOCK)+", false, &[], &[])),
+        ("warning", rule("(title )?(%BLOCK)+", false, &[], &[])),
+        ("caution", rule("(title )?(%BLOCK)+", false, &[], &[])),
+        ("sidebar", rule("(title )?(%BLOCK)+", false, &[], &[])),
+        (
+            "blockquote",
+            rule("(title )?(attribution )?(%BLOCK)+", false, &[], &[]),
+        ),
+        ("attribution", rule("(citetitle )?", true, &[], &[])),
+        ("citetitle", rule("", true, &[], &[])),
+        (
+            "programlisting",
+            rule("(co |emphasis )*", true, &[], &["language"]),
+        ),
+        ("co", rule("", false, &["xml:id"], &[])),
+        ("figure", rule("title mediaobject ", false, &[], &[])),
+        ("informalfigure", rule("mediaobject ", false, &[], &[])),
+        (
+            "mediaobject",
+            rule("imageobject (textobject )?", false, &[], &[]),
+        ),
+        (
+            "inlinemediaobject",
+            rule("imageobject (textobject )?", false, &[], &[]),
+        ),
+        ("imageobject", rule("imagedata ", false, &[], &[])),
+        ("imagedata", rule("", false, &["fileref"], &[])),
+        ("textobject", rule("phrase ", false, &[], &[])),
+        ("phrase", rule("(%INLINE)*", true, &[], &[])),
+        (
+            "table",
+            rule("title tgroup ", false, &[], &["frame", "rowsep", "colsep"]),
+        ),
+        (
+            "informaltable",
+            rule("tgroup ", false, &[], &["frame", "rowsep", "colsep"]),
+        ),
+        (
+            "tgroup",
+            rule("(colspec )*(thead )?(tfoot )?tbody ", false, &["cols"], &[]),
+        ),
+        ("colspec", rule("", false, &["colname"], &["colwidth"])),
+        ("thead", rule("(row )+", false, &[], &[])),
+        ("tfoot", rule("(row )+", false, &[], &[])),
+        ("tbody", rule("(row )+", false, &[], &[])),
+        ("row", rule("(entry )+", false, &[], &[])),
+        (
+            "entry",
+            rule(
+                "(%INLINE)*|(%BLOCK)+",
+                true,
+                &[],
+                &["namest", "nameend", "morerows", "align"],
+            ),
+        ),
+        ("emphasis", rule("(%INLINE)*", true, &[], &[])),
+        ("literal", rule("(%INLINE)*", true, &[], &[])),
+        ("superscript", rule("(%INLINE)*", true, &[], &[])),
+        ("subscript", rule("(%INLINE)*", true, &[], &[])),
+        (
+            "link",
+            rule("(%INLINE)*", true, &[], &["linkend", "xlink:href"]),
+        ),
+        ("xref", rule("", false, &["linkend"], &[])),
+        ("anchor", rule("", false, &["xml:id"], &[])),
+        ("footnote", rule("(%BLOCK)+", false, &[], &[])),
+        ("footnoteref", rule("", false, &["linkend"], &[])),
+    ],
+    values: &[
+        ("article", "version", &["5.0"]),
+        ("article", "xmlns", &["http://docbook.org/ns/docbook"]),
+        (
+            "orderedlist",
+            "numeration",
+            &[
+                "arabic",
+                "loweralpha",
+                "upperalpha",
+                "lowerroman",
+                "upperroman",
+            ],
+        ),
+        (
+            "bridgehead",
+            "renderas",
+            &["sect1", "sect2", "sect3", "sect4", "sect5"],
+        ),
+        (
+            "entry",
+            "align",
+            &["left", "center", "right", "justify", "char"],
+        ),
+        (
+            "table",
+            "frame",
+            &["all", "bottom", "none", "sides", "top", "topbot"],
+        ),
+    ],
+    id: "xml:id",
+    references: &["linkend", "arearefs"],
+};
+
+const JATS_PARAS: &str = "p|list|def-list|table-wrap|fig|boxed-text|disp-quote|code|graphic";
+const JATS_INLINES: &str =
+    "bold|italic|monospace|sup|sub|styled-content|xref|ext-link|target|inline-graphic";
+
+/// Subset of the JATS 1.3 Archiving and Interchange content model
+const JATS: ContentModel = ContentModel {
+    root: "article",
+    common: &[],
+    classes: &[("PARA", JATS_PARAS), ("INLINE", JATS_INLINES)],
+    rules: &[
+        (
+            "article",
+            rule(
+                "front (body )?(back )?",
+                false,
+                &["dtd-version"],
+                &["xmlns:xlink", "article-type", "xml:lang"],
+            ),
+        ),
+        ("front", rule("article-meta ", false, &[], &[])),
+        (
+            "article-meta",
+            rule(
+                "(article-version )?(title-group )?(contrib-group )*",
+                false,
+                &[],
+                &[],
+            ),
+        ),
+        ("article-version", rule("", true, &[], &[])),
+        ("title-group", rule("article-title ", false, &[], &[])),
+        ("article-title", rule("(%INLINE)*", true, &[], &[])),
+        ("contrib-group", rule("(contrib )+", false, &[], &[])),
+        (
+            "contrib",
+            rule("(string-name )*", false, &[], &["contrib-type"]),
+        ),
+        ("string-name", rule("", true, &[], &[])),
+        ("body", rule("(%PARA)*(sec )*", false, &[], &[])),
+        ("sec", rule("(title )?(%PARA)*(sec )*", false, &[], &["id"])),
+        ("title", rule("(%INLINE|break )*", true, &[], &[])),
+        ("p", rule("(%INLINE)*", true, &[], &["content-type", "id"])),
+        ("list", rule("(list-item )+", false, &[], &["list-type"])),
+        ("list-item", rule("(label )?(%PARA)+", false, &[], &[])),
+        ("label", rule("", true, &[], &[])),
+        ("def-list", rule("(def-item )+", false, &[], &[])),
+        ("def-item", rule("term (def )*", false, &[], &[])),
+        ("term", rule("(%INLINE)*", true, &[], &[])),
+        ("def", rule("(%PARA)+", false, &[], &[])),
+        ("table-wrap", rule("(caption )?table ", false, &[], &["id"])),
+        ("caption", rule("(title )?(p )*", false, &[], &[])),
+        (
+            "table",
+            rule("(colgroup )?(thead )?(tfoot )?(tbody )+", false, &[], &[]),
+        ),
+        ("colgroup", rule("(col )+", false, &[], &[])),
+        ("col", rule("", false, &[], &["width"])),
+        ("thead", rule("(tr )+", false, &[], &[])),
+        ("tfoot", rule("(tr )+", false, &[], &[])),
+        ("tbody", rule("(tr )+", false, &[], &[])),
+        ("tr", rule("(th |td )+", false, &[], &[])),
+        (
+            "th",
+            rule(
+                "(%INLINE|break )*",
+                true,
+                &[],
+                &["colspan", "rowspan", "align"],
+            ),
+        ),
+        (
+            "td",
+            rule(
+                "(%INLINE|break )*",
+                true,
+                &[],
+                &["colspan", "rowspan", "align"],
+            ),
+        ),
+        ("break", rule("", false, &[], &[])),
+        ("fig", rule("(caption )?graphic ", false, &[], &["id"])),
+        ("graphic", rule("(alt-text )?", false, &["xlink:href"], &[])),
+        (
+            "inline-graphic",
+            rule("(alt-text )?", false, &["xlink:href"], &[]),
+        ),
+        ("alt-text", rule("", true, &[], &[])),
+        (
+            "boxed-text",
+            rule("(caption )?(%PARA)*", false, &[], &["content-type"]),
+        ),
+        (
+            "disp-quote",
+            rule("(title )?(%PARA)+(attrib )*", false, &[], &[]),
+        ),
+        ("attrib", rule("(%INLINE)*", true, &[], &[])),
+        ("code", rule("", true, &[], &["language"])),
+        ("bold", rule("(%INLINE)*", true, &[], &[])),
+        ("italic", rule("(%INLINE)*", true, &[], &[])),
+        ("monospace", rule("(%INLINE)*", true, &[], &[])),
+        ("sup", rule("(%INLINE)*", true, &[], &[])),
+        ("sub", rule("(%INLINE)*", true, &[], &[])),
+        (
+            "styled-content",
+            rule("(%INLINE)*", true, &[], &["style-type"]),
+        ),
+        ("xref", rule("(%INLINE)*", true, &["ref-type", "rid"], &[])),
+        (
+            "ext-link",
+            rule("(%INLINE)*", true, &["xlink:href"], &["ext-link-type"]),
+        ),
+        ("target", rule("", false, &["id"], &[])),
+        ("back", rule("(fn-group )?", false, &[], &[])),
+        ("fn-group", rule("(fn )+", false, &[], &[])),
+        ("fn", rule("(label )?(p )+", false, &["id"], &[])),
+    ],
+    values: &[
+        ("article", "dtd-version", &["1.3"]),
+        (
+            "list",
+            "list-type",
+            &[
+                "order",
+                "bullet",
+                "alpha-lower",
+                "alpha-upper",
+                "roman-lower",
+                "roman-upper",
+                "simple",
+            ],
+        ),
+        (
+            "th",
+            "align",
+            &["left", "center", "right", "justify", "char"],
+        ),
+        (
+            "td",
+            "align",
+            &["left", "center", "right", "justify", "char"],
+        ),
+    ],
+    id: "id",
+    references: &["rid"],
+};
+
+/// Check `xml` against the DocBook 5 or JATS content model subset, chosen
+/// by the root
+/// element, returning the violations found
+pub fn check_content_model(xml: &str) -> Vec<String> {
+    let root = parse(xml);
+    let model = if root.attribute("xmlns").is_some() {
+        &DOCBOOK
+    } else {
+        &JATS
+    };
+    let mut checker = Checker {
+        model,
+        patterns: HashMap::new(),
+        ids: HashSet::new(),
+        references: Vec::new(),
+        errors: Vec::new(),
+    };
+    if root.name != model.root {
+        checker
+            .errors
+            .push(format!("root element is <{}>", root.name));
+    }
+    checker.check(&root);
+    for (element, reference) in std::mem::take(&mut checker.references) {
+        if !checker.ids.contains(&reference) {
+            checker.errors.push(format!(
+                "<{}> references missing id '{}'",
+                element, reference
+            ));
+        }
+    }
+    checker.errors
+}
+
+struct Checker {
+    model: &'static ContentModel,
+    patterns: HashMap<String, Regex>,
+    ids: HashSet<String>,
+    references: Vec<(String, String)>,
+    errors: Vec<String>,
+}
+
+impl Checker {
+    fn check(&mut self, element: &Element) {
+        let Some((_, rule)) = self
+            .model
+            .rules
+            .iter()
+            .find(|(name, _)| *name == element.name)
+        else {
+            self.errors
+                .push(format!("unknown element <{}>", element.name));
+            return;
+        };
+
+        let children: String = element
+            .children
+            .iter()
+            .map(|child| format!("{} ", child.name))
+            .collect();
+        if !self.pattern(&element.name, rule).is_match(&children) {
+            self.errors.push(format!(
+                "<{}> cannot contain [{}]",
+                element.name,
+                children.trim_end()
+            ));
+        }
+        if !rule.text && !element.text.is_empty() {
+            self.errors
+                .push(format!("<{}> cannot contain text", element.name));
+        }
+
+        for required in rule.required {
+            if element.attribute(required).is_none() {
+                self.errors.push(format!(
+                    "<{}> is missing attribute {}",
+                    element.name, required
+                ));
+            }
+        }
+        for (key, value) in &element.attributes {
+            let known = rule.required.contains(&key.as_str())
+                || rule.optional.contains(&key.as_str())
+                || self.model.common.contains(&key.as_str())
+                || (key == self.model.id);
+            if !known {
+                self.errors
+                    .push(format!("<{}> cannot have attribute {}", element.name, key));
+            }
+            let allowed = self
+                .model
+                .values
+                .iter()
+                .find(|(name, attribute, _)| *name == element.name && attribute == key);
+            if let Some((_, _, values)) = allowed {
+                if !values.contains(&value.as_str()) {
+                    self.errors.push(format!(
+                        "<{}> has invalid {}=\"{}\"",
+                        element.name, key, value
+                    ));
+                }
+            }
+            if key == self.model.id && !self.ids.insert(value.clone()) {
+                self.errors.push(format!("duplicate id '{}'", value));
+            }
+            if self.model.references.contains(&key.as_str()) {
+                for reference in value.split_whitespace() {
+                    self.references
+                        .push((element.name.clone(), reference.to_string()));
+                }
+            }
+        }
+
+        for child in &element.children {
+            self.check(child);
+        }
+    }
+
+    fn pattern(&mut self, name: &str, rule: &Rule) -> &Regex {
+        let classes = self.model.classes;
+        self.patterns.entry(name.to_string()).or_insert_with(|| {
+            let mut pattern = rule.content.to_string();
+            for (class, members) in classes {
+                let alternatives: Vec<String> =
+                    members.split('|').map(|m| format!("{} ", m)).collect();
+                pattern = pattern.replace(&format!("%{}", class), &alternatives.join("|"));
+            }
+            Regex::new(&format!("^(?:{})$", pattern)).unwrap()
+        })
+    }
+}
@@ -0,0 +1,320 @@
+//! DocBook 5 Backend Specification Tests
+//!
+//! These tests render parsed AsciiDoc as DocBook and validate the output
+//! against the DocBook 5 RELAX NG schema (see `schema`) and the content
+//! model checks in `content_model`, along with the section structure, CALS
+//! table spans, figures, footnotes and xrefs.
+
+mod content_model;
+mod schema;
+
+use std::collections::HashMap;
+
+use content_model::{check_content_model, parse, Element};
+use schema::assert_valid;
+use utf8dok_ast::{Block, Document, Inline, Link, Paragraph};
+use utf8dok_core::parse as parse_adoc;
+use utf8dok_ooxml::{AnchorMapping, AnchorType, StyleContract};
+use utf8dok_xml::{render_docbook, DocBookWriter};
+
+const DOCUMENT: &str = r#"= User Guide
+:lang: en
+
+Welcome to the guide.footnote:disclaimer[Preview release.]
+
+[[install]]
+== Installation
+
+Run the installer. See <<usage,the usage section>> and <<missing>>.
+
+=== Requirements
+
+* Linux
+** x86_64
+* macOS
+
+. First
+. Second
+
+CPU:: Any
+Memory:: 4 GB
+
+.Architecture
+image::images/arch.png[System overview]
+
+image::images/logo.png[]
+
+== Usage
+
+[[usage]]Use the `run` command, as in <<install>>.footnote:disclaimer[]
+
+.Options
+[cols="2,1,1"]
+|===
+| Name | Short | Default
+
+.2+| verbose 2+| -v
+| -q | off
+
+| Total 2+| 3
+|===
+
+NOTE: Mind the *details*.
+
+.Example
+[source,rust]
+----
+fn main() {} // <1>
+----
+<1> The entry point
+
+[quote, Jane Doe, Memoirs]
+____
+Quoted text.
+____
+
+.Tip box
+****
+Sidebar text.
+****
+"#;
+
+fn docbook() -> String {
+    render_docbook(&parse_adoc(DOCUMENT).unwrap())
+}
+
+fn assert_follows_content_model(xml: &str) {
+    let errors = check_content_model(xml);
+    assert!(errors.is_empty(), "{:#?}\n{}", errors, xml);
+}
+
+#[test]
+fn test_document_follows_content_model() {
+    assert_follows_content_model(&docbook());
+}
+
+#[test]
+fn test_document_validates_against_schema() {
+    assert_valid(&docbook());
+}
+
+#[test]
+fn test_article_root_and_info() {
+    let xml = docbook();
+    assert!(xml.starts_with(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<article xmlns=\"http://docbook.org/ns/docbook\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" version=\"5.0\" xml:lang=\"en\">\n<info>\n<title>User Guide</title>\n</info>\n"
+    ));
+
+    let mut doc = Document::new();
+    doc.metadata.authors = vec!["Jane Doe".to_string()];
+    doc.metadata.revision = Some("1.2".to_string());
+    let xml = render_docbook(&doc);
+    assert!(xml.contains("<title>Untitled</title>\n<author>\n<personname>Jane Doe</personname>\n</author>\n<releaseinfo>1.2</releaseinfo>\n</info>\n<para/>\n"));
+    assert_follows_content_model(&xml);
+}
+
+#[test]
+fn test_nested_sections() {
+    let root = parse(&docbook());
+    let sections: Vec<(&str, &str)> = root
+        .descendants("section")
+        .into_iter()
+        .map(|s| (s.attribute("xml:id").unwrap(), s.children[0].text.as_str()))
+        .collect();
+    assert_eq!(
+        sections,
+        vec![
+            ("install", "Installation"),
+            ("_requirements", "Requirements"),
+            ("_usage", "Usage")
+        ]
+    );
+    // Requirements is inside Installation
+    let install = root.descendants("section")[0];
+    assert_eq!(install.descendants("section").len(), 2);
+}
+
+#[test]
+fn test_table_spans() {
+    let root = parse(&docbook());
+    let table = root.descendants("table")[0];
+    assert_eq!(table.children[0].name, "title");
+    assert_eq!(table.children[0].text, "Options");
+    let tgroup = &table.children[1];
+    assert_eq!(tgroup.attribute("cols"), Some("3"));
+    let widths: Vec<_> = tgroup
+        .descendants("colspec")
+        .iter()
+        .map(|c| c.attribute("colwidth").unwrap())
+        .collect();
+    assert_eq!(widths, vec!["2*", "1*", "1*"]);
+    assert_eq!(tgroup.children[3].name, "thead");
+    assert_cals_consistent(tgroup);
+
+    let entries = tgroup.descendants("entry");
+    let verbose = entries
+        .iter()
+        .find(|e| e.descendants("para")[0].text == "verbose")
+        .unwrap();
+    assert_eq!(verbose.attribute("morerows"), Some("1"));
+    let short = entries
+        .iter()
+        .find(|e| e.descendants("para")[0].text == "-v")
+        .unwrap();
+    assert_eq!(short.attribute("namest"), Some("c2"));
+    assert_eq!(short.attribute("nameend"), Some("c3"));
+}
+
+/// Check that the entries of a CALS `tgroup` tile its grid: every entry
+/// lies inside the declared columns and no two entries overlap
+fn assert_cals_consistent(tgroup: &Element) {
+    let cols: usize = tgroup.attribute("cols").unwrap().parse().unwrap();
+    let colnums: HashMap<&str, usize> = tgroup
+        .descendants("colspec")
+        .iter()
+        .enumerate()
+        .map(|(n, spec)| (spec.attribute("colname").unwrap(), n))
+        .collect();
+    for group in tgroup.children.iter().filter(|c| c.name != "colspec") {
+        let mut occupied: Vec<usize> = vec![0; cols];
+        for row in &group.children {
+            let mut column = 0;
+            for entry in &row.children {
+                while column < cols && occupied[column] > 0 {
+                    column += 1;
+                }
+                let first = entry.attribute("namest").map_or(column, |n| colnums[n]);
+                let last = entry.attribute("nameend").map_or(first, |n| colnums[n]);
+                assert!(first >= column && last < cols, "entry outside the grid");
+                let rows = 1 + entry
+                    .attribute("morerows")
+                    .map_or(0, |n| n.parse::<usize>().unwrap());
+                for slot in &mut occupied[first..=last] {
+                    assert_eq!(*slot, 0, "overlapping entries");
+                    *slot = rows;
+                }
+                column = last + 1;
+            }
+            for slot in &mut occupied {
+                *slot = slot.saturating_sub(1);
+            }
+        }
+        assert!(
+            occupied.iter().all(|&slot| slot == 0),
+            "span leaves {}",
+            group.name
+        );
+    }
+}
+
+#[test]
+fn test_figures() {
+    let xml = docbook();
+    assert!(xml.contains(
+        "<figure>\n<title>Architecture</title>\n<mediaobject>\n<imageobject>\n<imagedata fileref=\"images/arch.png\"/>\n</imageobject>\n<textobject><phrase>System overview</phrase></textobject>\n</mediaobject>\n</figure>\n"
+    ));
+    assert!(xml.contains(
+        "<informalfigure>\n<mediaobject>\n<imageobject>\n<imagedata fileref=\"images/logo.png\"/>"
+    ));
+}
+
+#[test]
+fn test_anchored_figures_and_tables() {
+    // The anchor paragraph in front of a figure or table becomes its id
+    let source = "= Doc\n\nSee <<arch>> and <<options>>.\n\n[[arch]]\n.Architecture\n\
+                  image::arch.png[]\n\n[[options]]\n|===\n| a | b\n|===\n";
+    let xml = render_docbook(&parse_adoc(source).unwrap());
+    assert!(xml.contains("<figure xml:id=\"arch\">\n<title>Architecture</title>"));
+    assert!(xml.contains("<informaltable xml:id=\"options\" frame=\"all\""));
+    assert!(!xml.contains("<anchor"));
+    assert!(xml.contains("See <link linkend=\"arch\">arch</link>"));
+    assert_follows_content_model(&xml);
+    assert_valid(&xml);
+}
+
+#[test]
+fn test_footnotes() {
+    let xml = docbook();
+    assert!(xml.contains(
+        "Welcome to the guide.<footnote xml:id=\"_footnote_1\"><para>Preview release.</para></footnote>"
+    ));
+    assert!(xml.contains("<footnoteref linkend=\"_footnote_1\"/>"));
+}
+
+#[test]
+fn test_cross_references() {
+    let xml = docbook();
+    assert!(xml.contains("See <link linkend=\"usage\">the usage section</link>"));
+    assert!(xml.contains("<anchor xml:id=\"usage\"/>Use the <literal>run</literal> command"));
+    assert!(xml.contains("as in <link linkend=\"install\">install</link>"));
+    // A dangling linkend would be invalid
+    assert!(xml.contains("<link xlink:href=\"#missing\">missing</link>"));
+
+    let mut doc = Document::new();
+    doc.push(Block::Paragraph(Paragraph {
+        inlines: vec![Inline::Link(Link {
+            url: "https://example.com".to_string(),
+            text: vec![Inline::Text("the site".to_string())],
+        })],
+        style_id: None,
+        attributes: HashMap::new(),
+    }));
+    let xml = render_docbook(&doc);
+    assert!(xml.contains("<para><link xlink:href=\"https://example.com\">the site</link></para>"));
+    assert_follows_content_model(&xml);
+}
+
+#[test]
+fn test_blocks() {
+    let xml = docbook();
+    assert!(xml.contains("<itemizedlist>\n<listitem>\n<para>Linux</para>\n<itemizedlist>\n<listitem>\n<para>x86_64</para>\n</listitem>\n</itemizedlist>\n</listitem>\n"));
+    assert!(xml.contains("<orderedlist>\n<listitem>\n<para>First</para>"));
+    assert!(xml.contains(
+        "<variablelist>\n<varlistentry>\n<term>CPU</term>\n<listitem>\n<para>Any</para>"
+    ));
+    assert!(xml.contains(
+        "<note>\n<para>Mind the <emphasis role=\"strong\">details</emphasis>.</para>\n</note>"
+    ));
+    assert!(xml.contains(
+        "<formalpara>\n<title>Example</title>\n<para>\n<programlisting language=\"rust\">fn main() {} <co xml:id=\"_callout_1_1\"/></programlisting>\n</para>\n</formalpara>\n<calloutlist>\n<callout arearefs=\"_callout_1_1\">"
+    ));
+    assert!(xml.contains("<blockquote>\n<attribution>Jane Doe<citetitle>Memoirs</citetitle></attribution>\n<para>Quoted text.</para>\n</blockquote>"));
+    assert!(
+        xml.contains("<sidebar>\n<title>Tip box</title>\n<para>Sidebar text.</para>\n</sidebar>")
+    );
+}
+
+#[test]
+fn test_ids_use_contract_bookmarks() {
+    let mut contract = StyleContract::default();
+    contract.add_anchor(
+        "_Toc192197374",
+        AnchorMapping {
+            semantic_id: "install".to_string(),
+            anchor_type: AnchorType::Heading,
+            target_heading: None,
+            original_bookmark: Some("_Toc192197374".to_string()),
+        },
+    );
+    let mut writer = DocBookWriter::new();
+    writer.set_style_contract(contract);
+    let xml = writer.generate(&parse_adoc(DOCUMENT).unwrap());
+    assert!(xml.contains("<section xml:id=\"_Toc192197374\">\n<title>Installation</title>"));
+    assert!(xml.contains("<link linkend=\"_Toc192197374\">install</link>"));
+    assert_follows_content_model(&xml);
+}
+
+#[test]
+fn test_content_model_checks_catch_errors() {
+    // The content model checks are not vacuous
+    let errors = check_content_model(
+        "<article xmlns=\"http://docbook.org/ns/docbook\" version=\"5.0\"><info><title>T</title></info><section><para/></section><para><xref linkend=\"nowhere\"/></para></article>",
+    );
+    assert!(errors
+        .iter()
+        .any(|e| e.starts_with("<article> cannot contain")));
+    assert!(errors
+        .iter()
+        .any(|e| e.starts_with("<section> cannot contain")));
+    assert!(errors.iter().any(|e| e.contains("missing id 'nowhere'")));
+}
@@ -0,0 +1,211 @@
+//! JATS Backend Specification Tests
+//!
+//! These tests render parsed AsciiDoc as JATS and validate the output
+//! against the JATS Archiving DTD (see `schema`) and the content model
+//! checks in `content_model`, along with the front matter, sections,
+//! tables, figures, footnotes and cross references.
+
+mod content_model;
+mod schema;
+
+use content_model::{check_content_model, parse};
+use schema::assert_valid;
+use utf8dok_ast::Document;
+use utf8dok_core::parse as parse_adoc;
+use utf8dok_xml::render_jats;
+
+const DOCUMENT: &str = r#"= User Guide
+:lang: en
+
+Welcome to the guide.footnote:disclaimer[Preview release.]
+
+[[install]]
+== Installation
+
+Run the installer. See <<usage,the usage section>> and <<missing>>.
+
+=== Requirements
+
+* Linux
+** x86_64
+* macOS
+
+. First
+. Second
+
+CPU:: Any
+Memory:: 4 GB
+
+.Architecture
+image::images/arch.png[System overview]
+
+image::images/logo.png[]
+
+== Usage
+
+[[usage]]Use the `run` command, as in <<install>>.footnote:disclaimer[]
+
+.Options
+[cols="2,1,1"]
+|===
+| Name | Short | Default
+
+.2+| verbose 2+| -v
+| -q | off
+
+| Total 2+| 3
+|===
+
+NOTE: Mind the *details*.
+
+.Example
+[source,rust]
+----
+fn main() {} // <1>
+----
+<1> The entry point
+
+[quote, Jane Doe, Memoirs]
+____
+Quoted text.
+____
+
+.Tip box
+****
+Sidebar text.
+****
+"#;
+
+fn jats() -> String {
+    render_jats(&parse_adoc(DOCUMENT).unwrap())
+}
+
+fn assert_follows_content_model(xml: &str) {
+    let errors = check_content_model(xml);
+    assert!(errors.is_empty(), "{:#?}\n{}", errors, xml);
+}
+
+#[test]
+fn test_document_follows_content_model() {
+    assert_follows_content_model(&jats());
+}
+
+#[test]
+fn test_document_validates_against_schema() {
+    assert_valid(&jats());
+}
+
+#[test]
+fn test_front_matter() {
+    let xml = jats();
+    assert!(xml.contains("<!DOCTYPE article PUBLIC \"-//NLM//DTD JATS (Z39.96) Journal Archiving and Interchange DTD v1.3 20210610//EN\""));
+    assert!(xml.contains(
+        "<article xmlns:xlink=\"http://www.w3.org/1999/xlink\" dtd-version=\"1.3\" article-type=\"other\" xml:lang=\"en\">\n<front>\n<article-meta>\n<title-group>\n<article-title>User Guide</article-title>\n</title-group>\n</article-meta>\n</front>\n<body>\n"
+    ));
+
+    let mut doc = Document::new();
+    doc.metadata.authors = vec!["Jane Doe".to_string()];
+    doc.metadata.revision = Some("1.2".to_string());
+    let xml = render_jats(&doc);
+    assert!(xml.contains("<article-meta>\n<article-version>1.2</article-version>\n<title-group>\n<article-title>Untitled</article-title>\n</title-group>\n<contrib-group>\n<contrib contrib-type=\"author\">\n<string-name>Jane Doe</string-name>\n</contrib>\n</contrib-group>\n"));
+    assert_follows_content_model(&xml);
+}
+
+#[test]
+fn test_nested_sections() {
+    let root = parse(&jats());
+    let sections: Vec<(&str, &str)> = root
+        .descendants("sec")
+        .into_iter()
+        .map(|s| (s.attribute("id").unwrap(), s.children[0].text.as_str()))
+        .collect();
+    assert_eq!(
+        sections,
+        vec![
+            ("install", "Installation"),
+            ("_requirements", "Requirements"),
+            ("_usage", "Usage")
+        ]
+    );
+    assert_eq!(root.descendants("sec")[0].descendants("sec").len(), 2);
+}
+
+#[test]
+fn test_table_spans() {
+    let xml = jats();
+    assert!(xml.contains("<table-wrap>\n<caption>\n<title>Options</title>\n</caption>\n<table>\n<colgroup>\n<col width=\"50.00%\"/>\n<col width=\"25.00%\"/>\n<col width=\"25.00%\"/>\n</colgroup>\n<thead>\n<tr>\n<th>Name</th>"));
+    assert!(xml.contains("<tr>\n<td rowspan=\"2\">verbose</td>\n<td colspan=\"2\">-v</td>\n</tr>\n<tr>\n<td>-q</td>\n<td>off</td>\n</tr>"));
+}
+
+#[test]
+fn test_figures() {
+    let xml = jats();
+    assert!(xml.contains(
+        "<fig>\n<caption>\n<title>Architecture</title>\n</caption>\n<graphic xlink:href=\"images/arch.png\">\n<alt-text>System overview</alt-text>\n</graphic>\n</fig>\n"
+    ));
+    assert!(xml.contains("<fig>\n<graphic xlink:href=\"images/logo.png\"/>\n</fig>\n"));
+}
+
+#[test]
+fn test_anchored_figures_and_tables() {
+    // The anchor paragraph in front of a figure or table becomes its id
+    let source = "= Doc\n\nSee <<arch>> and <<options>>.\n\n[[arch]]\n.Architecture\n\
+                  image::arch.png[]\n\n[[options]]\n|===\n| a | b\n|===\n";
+    let xml = render_jats(&parse_adoc(source).unwrap());
+    assert!(xml.contains("<fig id=\"arch\">\n<caption>"));
+    assert!(xml.contains("<table-wrap id=\"options\">\n<table>"));
+    assert!(!xml.contains("<target"));
+    assert!(xml.contains("See <xref ref-type=\"fig\" rid=\"arch\">arch</xref> and <xref ref-type=\"table\" rid=\"options\">options</xref>."));
+    assert_follows_content_model(&xml);
+    assert_valid(&xml);
+}
+
+#[test]
+fn test_footnotes() {
+    let xml = jats();
+    assert!(xml.contains(
+        "Welcome to the guide.<xref ref-type=\"fn\" rid=\"fn1\"><sup>1</sup></xref></p>"
+    ));
+    assert!(
+        xml.contains(".<xref ref-type=\"fn\" rid=\"fn1\"><sup>1</sup></xref></p>\n<table-wrap>")
+    );
+    assert!(xml.ends_with(
+        "</body>\n<back>\n<fn-group>\n<fn id=\"fn1\">\n<label>1</label>\n<p>Preview release.</p>\n</fn>\n</fn-group>\n</back>\n</article>\n"
+    ));
+}
+
+#[test]
+fn test_cross_references() {
+    let xml = jats();
+    assert!(xml.contains("See <xref ref-type=\"other\" rid=\"usage\">the usage section</xref>"));
+    assert!(xml.contains("<target id=\"usage\"/>Use the <monospace>run</monospace> command"));
+    assert!(xml.contains("as in <xref ref-type=\"sec\" rid=\"install\">install</xref>"));
+    // A dangling rid would be invalid
+    assert!(
+        xml.contains("<ext-link ext-link-type=\"uri\" xlink:href=\"#missing\">missing</ext-link>")
+    );
+}
+
+#[test]
+fn test_blocks() {
+    let xml = jats();
+    assert!(xml.contains("<list list-type=\"bullet\">\n<list-item>\n<p>Linux</p>\n<list list-type=\"bullet\">\n<list-item>\n<p>x86_64</p>\n</list-item>\n</list>\n</list-item>\n"));
+    assert!(xml.contains("<list list-type=\"order\">\n<list-item>\n<p>First</p>"));
+    assert!(xml.contains("<def-list>\n<def-item>\n<term>CPU</term>\n<def>\n<p>Any</p>\n</def>"));
+    assert!(xml.contains("<boxed-text content-type=\"note\">\n<caption>\n<title>Note</title>\n</caption>\n<p>Mind the <bold>details</bold>.</p>\n</boxed-text>"));
+    assert!(xml.contains("<boxed-text content-type=\"listing\">\n<caption>\n<title>Example</title>\n</caption>\n<code language=\"rust\">fn main() {} (1)</code>\n</boxed-text>"));
+    assert!(xml.contains("<disp-quote>\n<p>Quoted text.</p>\n<attrib>Jane Doe, <italic>Memoirs</italic></attrib>\n</disp-quote>"));
+    assert!(xml.contains("<boxed-text content-type=\"sidebar\">\n<caption>\n<title>Tip box</title>\n</caption>\n<p>Sidebar text.</p>\n</boxed-text>"));
+}
+
+#[test]
+fn test_content_model_checks_catch_errors() {
+    // The content model checks are not vacuous
+    let errors = check_content_model(
+        "<article dtd-version=\"1.3\"><front><article-meta/></front><body><sec id=\"a\"/><p><xref ref-type=\"sec\" rid=\"b\"/></p></body></article>",
+    );
+    assert!(errors
+        .iter()
+        .any(|e| e.starts_with("<body> cannot contain")));
+    assert!(errors.iter().any(|e| e.contains("missing id 'b'")));
+}
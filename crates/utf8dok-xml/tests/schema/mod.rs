@@ -0,0 +1,93 @@
+//! Validation against the published schemas
+//!
+//! The DocBook 5.0 RELAX NG schema and the JATS 1.3 Journal Archiving DTD
+//! live under `tests/schemas` (`tests/schemas/fetch.sh` downloads them),
+//! and `xmllint` validates the generated XML against them. Validation is
+//! skipped, with a note on stderr, when `xmllint` or a schema is missing,
+//! so the tests still run offline.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// A published schema
+#[derive(Debug, Clone, Copy)]
+enum Schema {
+    /// DocBook 5.0, `schemas/docbook/docbook.rng`
+    DocBook,
+    /// JATS 1.3 Journal Archiving, `schemas/jats/JATS-archivearticle1-3.dtd`
+    Jats,
+}
+
+impl Schema {
+    /// The schema of a DocBook or JATS document
+    fn of(xml: &str) -> Self {
+        match xml.contains("xmlns=\"http://docbook.org/ns/docbook\"") {
+            true => Schema::DocBook,
+            false => Schema::Jats,
+        }
+    }
+
+    fn path(self) -> PathBuf {
+        let schemas = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/schemas");
+        match self {
+            Schema::DocBook => schemas.join("docbook/docbook.rng"),
+            Schema::Jats => schemas.join("jats/JATS-archivearticle1-3.dtd"),
+        }
+    }
+
+    fn option(self) -> &'static str {
+        match self {
+            Schema::DocBook => "--relaxng",
+            Schema::Jats => "--dtdvalid",
+        }
+    }
+}
+
+/// Validate a DocBook or JATS document against its schema
+///
+/// Returns `None` when validation was skipped, and otherwise the messages
+/// of `xmllint` on failure.
+fn validate(xml: &str) -> Option<Result<(), String>> {
+    let schema = Schema::of(xml);
+    let path = schema.path();
+    if !path.exists() {
+        eprintln!(
+            "skipping {:?} validation: {} is missing (run tests/schemas/fetch.sh)",
+            schema,
+            path.display()
+        );
+        return None;
+    }
+    let child = Command::new("xmllint")
+        .args(["--noout", "--nonet", schema.option()])
+        .arg(&path)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let Ok(mut child) = child else {
+        eprintln!("skipping {:?} validation: xmllint is not installed", schema);
+        return None;
+    };
+    child
+        .stdin
+        .take()
+        .expect("piped stdin")
+        .write_all(xml.as_bytes())
+        .expect("xmllint reads the document");
+    let output = child.wait_with_output().expect("xmllint runs");
+    Some(match output.status.success() {
+        true => Ok(()),
+        false => Err(String::from_utf8_lossy(&output.stderr).into_owned()),
+    })
+}
+
+/// Assert that a DocBook or JATS document is valid, unless validation is
+/// skipped
+pub fn assert_valid(xml: &str) {
+    if let Some(Err(errors)) = validate(xml) {
+        panic!("not valid {:?}:\n{}\n{}", Schema::of(xml), errors, xml);
+    }
+}
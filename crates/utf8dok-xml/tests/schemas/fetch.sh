@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+# Download the schemas the DocBook and JATS output is validated against:
+#   docbook/  DocBook 5.0 RELAX NG schema
+#   jats/     JATS 1.3 Journal Archiving and Interchange DTD
+# Commit the downloaded files so the validation tests run offline.
+set -euo pipefail
+
+cd "$(dirname "$0")"
+
+DOCBOOK_URL="https://docbook.org/xml/5.0/rng/docbook.rng"
+JATS_URL="https://ftp.ncbi.nih.gov/pub/jats/archiving/1.3/JATS-Archiving-1-3-MathML3-DTD.zip"
+
+mkdir -p docbook
+curl -fsSL -o docbook/docbook.rng "$DOCBOOK_URL"
+
+tmp="$(mktemp -d)"
+trap 'rm -rf "$tmp"' EXIT
+curl -fsSL -o "$tmp/jats.zip" "$JATS_URL"
+unzip -q "$tmp/jats.zip" -d "$tmp/jats"
+rm -rf jats
+dtd="$(find "$tmp/jats" -name 'JATS-archivearticle1-3.dtd' -print -quit)"
+cp -R "$(dirname "$dtd")" jats
+
+echo "Schemas in $(pwd): docbook/docbook.rng, jats/JATS-archivearticle1-3.dtd"